@@ -3,10 +3,12 @@
 //! [WebDriver]: https://w3.org/TR/webdriver
 
 use std::{
+    str::FromStr,
     sync::{mpsc, Arc},
     time::Duration,
 };
 
+use derive_more::{Display, Error};
 use fantoccini::{
     wd::{Capabilities, WindowHandle},
     Client, ClientBuilder, Locator,
@@ -16,20 +18,55 @@ use serde::Deserialize;
 use serde_json::{json, Value as Json};
 use tokio::task;
 
-use super::{js::Statement, Error, Result};
+use super::{helpers, js::Statement, Error, Result};
 
 /// Arguments for Chrome browser.
 const CHROME_ARGS: &[&str] = &[
     "--use-fake-device-for-media-stream",
     "--use-fake-ui-for-media-stream",
+    "--autoplay-policy=no-user-gesture-required",
     "--disable-web-security",
     "--disable-dev-shm-usage",
     "--no-sandbox",
+    "--js-flags=--expose-gc",
 ];
 
 /// Arguments for Firefox browser.
 const FIREFOX_ARGS: &[&str] = &[];
 
+/// Default maximum duration of a JS code execution in a browser.
+const DEFAULT_EXECUTE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Browser to run [`WebDriverClient`] against.
+#[derive(Clone, Copy, Debug, Display, Eq, PartialEq)]
+pub enum Browser {
+    /// Chrome (or Chromium) browser.
+    #[display(fmt = "chrome")]
+    Chrome,
+
+    /// Firefox browser.
+    #[display(fmt = "firefox")]
+    Firefox,
+}
+
+impl FromStr for Browser {
+    type Err = UnknownBrowserError;
+
+    #[allow(clippy::absolute_paths)]
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "chrome" | "chromium" => Ok(Self::Chrome),
+            "firefox" => Ok(Self::Firefox),
+            _ => Err(UnknownBrowserError(s.to_owned())),
+        }
+    }
+}
+
+/// Error of parsing an unknown [`Browser`] name.
+#[derive(Clone, Debug, Display, Error, Eq, PartialEq)]
+#[display(fmt = "Unknown browser: {}", _0)]
+pub struct UnknownBrowserError(#[error(not(source))] String);
+
 /// Result returned from all the JS code executed in a browser.
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -39,6 +76,9 @@ enum JsResult {
 
     /// [`Json`] value of an error result.
     Err(Json),
+
+    /// Helpers required by the executed [`Statement`] are missing.
+    HelpersMissing,
 }
 
 impl From<JsResult> for Result<Json> {
@@ -46,6 +86,7 @@ impl From<JsResult> for Result<Json> {
         match from {
             JsResult::Ok(ok) => Self::Ok(ok),
             JsResult::Err(err) => Self::Err(Error::Js(err)),
+            JsResult::HelpersMissing => Self::Err(Error::HelpersMissing),
         }
     }
 }
@@ -61,9 +102,33 @@ pub struct WebDriverClient {
 
     /// Host of the file server to load `index.html` page from.
     file_server_host: String,
+
+    /// Default maximum duration of a JS code execution in a browser.
+    execute_timeout: Duration,
+
+    /// [`Browser`] this [`WebDriverClient`] was explicitly requested to run
+    /// against, if any.
+    browser: Option<Browser>,
 }
 
 impl WebDriverClient {
+    /// Returns default maximum duration of a JS code execution in a browser.
+    #[must_use]
+    pub const fn execute_timeout(&self) -> Duration {
+        self.execute_timeout
+    }
+
+    /// Returns [`Browser`] this [`WebDriverClient`] was explicitly requested
+    /// to run against.
+    ///
+    /// [`None`] means that a browser is chosen by a [WebDriver] server.
+    ///
+    /// [WebDriver]: https://w3.org/TR/webdriver
+    #[must_use]
+    pub const fn browser(&self) -> Option<Browser> {
+        self.browser
+    }
+
     /// Creates a new window in a browser and returns its ID.
     ///
     /// # Errors
@@ -145,6 +210,9 @@ pub struct WebDriverClientBuilder<'a, Caps = AutoCapabilities> {
 
     /// [`WebDriverClient`] [`Capabilities`].
     capabilities: Caps,
+
+    /// Default maximum duration of a JS code execution in a browser.
+    execute_timeout: Duration,
 }
 
 impl<'a> WebDriverClientBuilder<'a> {
@@ -154,12 +222,27 @@ impl<'a> WebDriverClientBuilder<'a> {
         Self {
             webdriver_address,
             capabilities: AutoCapabilities {
+                browser: None,
                 headless_firefox: false,
                 headless_chrome: false,
             },
+            execute_timeout: DEFAULT_EXECUTE_TIMEOUT,
         }
     }
 
+    /// Requests the provided [`Browser`] to be launched by a [WebDriver]
+    /// server.
+    ///
+    /// If not set, then [`Capabilities`] for all the supported browsers are
+    /// provided, and a browser is chosen by a [WebDriver] server.
+    ///
+    /// [WebDriver]: https://w3.org/TR/webdriver
+    #[must_use]
+    pub const fn browser(mut self, browser: Browser) -> Self {
+        self.capabilities.browser = Some(browser);
+        self
+    }
+
     /// Sets manually provided browser [`Capabilities`].
     // false positive: destructors cannot be evaluated at compile-time
     #[allow(clippy::missing_const_for_fn)]
@@ -171,6 +254,7 @@ impl<'a> WebDriverClientBuilder<'a> {
         WebDriverClientBuilder {
             webdriver_address: self.webdriver_address,
             capabilities,
+            execute_timeout: self.execute_timeout,
         }
     }
 
@@ -189,6 +273,19 @@ impl<'a> WebDriverClientBuilder<'a> {
     }
 }
 
+impl<'a, Caps> WebDriverClientBuilder<'a, Caps> {
+    /// Sets default maximum duration of a JS code execution in a browser.
+    ///
+    /// Default: 60 seconds.
+    // false positive: destructors cannot be evaluated at compile-time
+    #[allow(clippy::missing_const_for_fn)]
+    #[must_use]
+    pub fn execute_timeout(mut self, timeout: Duration) -> Self {
+        self.execute_timeout = timeout;
+        self
+    }
+}
+
 impl<'a, Caps: Into<Capabilities>> WebDriverClientBuilder<'a, Caps> {
     /// Creates a new [`WebDriverClient`] connected to a [WebDriver].
     ///
@@ -201,12 +298,15 @@ impl<'a, Caps: Into<Capabilities>> WebDriverClientBuilder<'a, Caps> {
         self,
         file_server_host: &str,
     ) -> Result<WebDriverClient> {
+        let caps = self.capabilities.into();
+        let browser = browser_of(&caps);
         Ok(WebDriverClient {
             inner: Arc::new(Mutex::new(
-                Inner::new(self.webdriver_address, self.capabilities.into())
-                    .await?,
+                Inner::new(self.webdriver_address, caps).await?,
             )),
             file_server_host: file_server_host.to_owned(),
+            execute_timeout: self.execute_timeout,
+            browser,
         })
     }
 }
@@ -246,7 +346,9 @@ impl Inner {
                         {inner_js}
                         callback({{ ok: lastResult }});
                     }} catch (e) {{
-                        if (e.__wbg_ptr > 0) {{
+                        if (e === '{missing}') {{
+                            callback('helpersmissing');
+                        }} else if (e.__wbg_ptr > 0) {{
                             callback({{
                                 err: {{
                                     kind: e.kind ? e.kind() : undefined,
@@ -262,6 +364,7 @@ impl Inner {
                 }}
             )();
             "#,
+            missing = helpers::MISSING,
         );
         let res = self.0.execute_async(&js, args).await?;
 
@@ -326,9 +429,21 @@ impl Inner {
     }
 }
 
+/// Returns [`Browser`] explicitly requested by the provided [`Capabilities`].
+fn browser_of(caps: &Capabilities) -> Option<Browser> {
+    caps.get("browserName")
+        .and_then(Json::as_str)
+        .and_then(|name| name.parse().ok())
+}
+
 /// Settings to build [`Capabilities`] automatically.
 #[derive(Clone, Copy, Debug)]
 pub struct AutoCapabilities {
+    /// [`Browser`] to request from a [WebDriver] server.
+    ///
+    /// [WebDriver]: https://w3.org/TR/webdriver
+    browser: Option<Browser>,
+
     /// Indicator whether [`WebDriverClient`] will run against headless Firefox
     /// browser.
     headless_firefox: bool,
@@ -371,8 +486,20 @@ impl AutoCapabilities {
 impl From<AutoCapabilities> for Capabilities {
     fn from(auto: AutoCapabilities) -> Self {
         let mut caps = Self::new();
-        drop(caps.insert("moz:firefoxOptions".to_owned(), auto.firefox()));
-        drop(caps.insert("goog:chromeOptions".to_owned(), auto.chrome()));
+        if let Some(browser) = auto.browser {
+            drop(
+                caps.insert(
+                    "browserName".to_owned(),
+                    browser.to_string().into(),
+                ),
+            );
+        }
+        if auto.browser != Some(Browser::Chrome) {
+            drop(caps.insert("moz:firefoxOptions".to_owned(), auto.firefox()));
+        }
+        if auto.browser != Some(Browser::Firefox) {
+            drop(caps.insert("goog:chromeOptions".to_owned(), auto.chrome()));
+        }
         caps
     }
 }
@@ -0,0 +1,173 @@
+//! Capturing of a browser console output and page errors.
+
+use derive_more::Display;
+use serde::Deserialize;
+
+use super::{Result, Statement, Window};
+
+/// Maximum number of [`ConsoleLog`]s buffered in a browser window.
+///
+/// The oldest [`ConsoleLog`]s are discarded once this limit is exceeded.
+const MAX_BUFFERED_LOGS: u64 = 500;
+
+/// Maximum length of a [`ConsoleLog::message`] in characters.
+///
+/// Longer messages are truncated on the JS side.
+const MAX_MESSAGE_LEN: u64 = 4096;
+
+/// Level of a captured [`ConsoleLog`].
+#[derive(Clone, Copy, Debug, Deserialize, Display, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum ConsoleLogLevel {
+    /// `console.error()` call.
+    #[display(fmt = "ERROR")]
+    Error,
+
+    /// `console.warn()` call.
+    #[display(fmt = "WARN")]
+    Warn,
+
+    /// Uncaught JS exception reported via `window.onerror`.
+    #[display(fmt = "UNCAUGHT")]
+    Uncaught,
+
+    /// Unhandled `Promise` rejection reported via `unhandledrejection` event.
+    #[display(fmt = "UNHANDLED REJECTION")]
+    UnhandledRejection,
+}
+
+/// Single entry captured from a browser console.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+pub struct ConsoleLog {
+    /// [`ConsoleLogLevel`] of this [`ConsoleLog`].
+    pub level: ConsoleLogLevel,
+
+    /// Serialized arguments of the console call or message of the error.
+    pub message: String,
+
+    /// JS stack trace at the moment this [`ConsoleLog`] was captured, if any.
+    pub stack: Option<String>,
+}
+
+#[allow(clippy::multiple_inherent_impl)] // better keep these functions here
+impl Window {
+    /// Starts capturing `console.error()`, `console.warn()` calls, uncaught
+    /// errors and unhandled `Promise` rejections happening in this
+    /// [`Window`].
+    ///
+    /// Captured entries are buffered in this [`Window`] until
+    /// [`Window::take_console_logs()`] is called. Calling this method more
+    /// than once has no effect.
+    ///
+    /// # Errors
+    ///
+    /// If failed to execute JS statement.
+    pub async fn start_console_capture(&self) -> Result<()> {
+        self.execute(Statement::new(
+            // language=JavaScript
+            r#"
+            async () => {
+                const [maxLogs, maxMessageLen] = args;
+                if (window.consoleCapture !== undefined) {
+                    return;
+                }
+                const capture = { logs: [] };
+                window.consoleCapture = capture;
+
+                const serialize = (arg) => {
+                    if (typeof arg === "string") {
+                        return arg;
+                    }
+                    if (arg instanceof Error) {
+                        return `${arg.name}: ${arg.message}`;
+                    }
+                    try {
+                        const seen = new WeakSet();
+                        const json = JSON.stringify(arg, (_, val) => {
+                            if (typeof val === "bigint") {
+                                return `${val}n`;
+                            }
+                            if (typeof val === "function") {
+                                return `[Function ${val.name}]`;
+                            }
+                            if (typeof val === "symbol") {
+                                return val.toString();
+                            }
+                            if (typeof val === "object" && val !== null) {
+                                if (seen.has(val)) {
+                                    return "[Circular]";
+                                }
+                                seen.add(val);
+                            }
+                            return val;
+                        });
+                        return json === undefined ? String(arg) : json;
+                    } catch (e) {
+                        return String(arg);
+                    }
+                };
+                const push = (level, args, stack) => {
+                    let message = args.map(serialize).join(" ");
+                    if (message.length > maxMessageLen) {
+                        message = message.substring(0, maxMessageLen) + "...";
+                    }
+                    capture.logs.push({
+                        level: level,
+                        message: message,
+                        stack: typeof stack === "string" ? stack : null
+                    });
+                    if (capture.logs.length > maxLogs) {
+                        capture.logs.shift();
+                    }
+                };
+
+                for (const level of ["error", "warn"]) {
+                    const original = console[level];
+                    console[level] = (...args) => {
+                        push(level, args, new Error().stack);
+                        original.apply(console, args);
+                    };
+                }
+                window.addEventListener("error", (e) => {
+                    push("uncaught", [e.message], e.error?.stack);
+                });
+                window.addEventListener("unhandledrejection", (e) => {
+                    push("unhandledRejection", [e.reason], e.reason?.stack);
+                });
+            }
+            "#,
+            [MAX_BUFFERED_LOGS.into(), MAX_MESSAGE_LEN.into()],
+        ))
+        .await
+        .map(drop)
+    }
+
+    /// Returns all the [`ConsoleLog`]s captured in this [`Window`] since the
+    /// last call of this method, clearing the buffer.
+    ///
+    /// Returns nothing if [`Window::start_console_capture()`] wasn't called.
+    ///
+    /// # Errors
+    ///
+    /// - If failed to execute JS statement.
+    /// - If failed to deserialize the captured [`ConsoleLog`]s.
+    pub async fn take_console_logs(&self) -> Result<Vec<ConsoleLog>> {
+        let logs = self
+            .execute(Statement::new(
+                // language=JavaScript
+                "
+                async () => {
+                    if (window.consoleCapture === undefined) {
+                        return [];
+                    }
+                    const logs = window.consoleCapture.logs;
+                    window.consoleCapture.logs = [];
+                    return logs;
+                }
+                ",
+                [],
+            ))
+            .await?;
+        Ok(serde_json::from_value(logs)?)
+    }
+}
@@ -0,0 +1,91 @@
+//! Reusable JS helpers installed once into every [`Window`].
+//!
+//! [`Window`]: super::Window
+
+/// JS namespace the [`JS`] helpers are defined in.
+pub const NAMESPACE: &str = "window.__medea_e2e";
+
+/// Value thrown by a [`Statement`] using the [`NAMESPACE`] which is missing in
+/// a browser window (e.g. after a page navigation).
+///
+/// [`Statement`]: super::Statement
+pub(super) const MISSING: &str = "__medea_e2e_missing";
+
+/// JS code defining the helpers in the [`NAMESPACE`].
+///
+/// - `matchesTrack(track, kind, sourceKind)` indicates whether the provided
+///   tracks store entry has the provided `MediaKind` and `MediaSourceKind` (any
+///   one if `sourceKind` is `undefined`).
+/// - `findTrack(store, kind, sourceKind)` returns the first track of the
+///   provided tracks store matching the provided kinds, if any.
+/// - `waitForTrack(store, kind, sourceKind)` returns the first track of the
+///   provided tracks store matching the provided kinds, awaiting for it to
+///   appear if there is none.
+/// - `waitUntil(subs, check)` resolves once the provided `check` returns
+///   `true`, re-checking it whenever any of the provided store subscription
+///   lists notifies.
+/// - `subscribe(subs, key, sub)` adds the provided `sub` into the list stored
+///   under the provided `key` of the provided `Map` of subscriptions.
+// language=JavaScript
+pub const JS: &str = "
+    async () => {
+        const ns = {};
+        ns.matchesTrack = (track, kind, sourceKind) => {
+            return track.track.kind() === kind && (
+                sourceKind === undefined
+                    || track.track.media_source_kind() === sourceKind
+            );
+        };
+        ns.findTrack = (store, kind, sourceKind) => {
+            return store.tracks.find((track) => {
+                return ns.matchesTrack(track, kind, sourceKind);
+            });
+        };
+        ns.waitForTrack = async (store, kind, sourceKind) => {
+            const track = ns.findTrack(store, kind, sourceKind);
+            if (track !== undefined) {
+                return track;
+            }
+            return await new Promise((resolve) => {
+                store.subs.push((track) => {
+                    if (ns.matchesTrack(track, kind, sourceKind)) {
+                        resolve(track);
+                        return false;
+                    }
+                    return true;
+                });
+            });
+        };
+        ns.waitUntil = async (subs, check) => {
+            if (check()) {
+                return;
+            }
+            await new Promise((resolve) => {
+                let isResolved = false;
+                const sub = () => {
+                    if (isResolved) {
+                        return false;
+                    }
+                    if (check()) {
+                        isResolved = true;
+                        resolve();
+                        return false;
+                    }
+                    return true;
+                };
+                for (const list of subs) {
+                    list.push(sub);
+                }
+            });
+        };
+        ns.subscribe = (subs, key, sub) => {
+            let list = subs.get(key);
+            if (list === undefined) {
+                list = [];
+                subs.set(key, list);
+            }
+            list.push(sub);
+        };
+        window.__medea_e2e = ns;
+    }
+";
@@ -6,6 +6,8 @@ use serde_json::Value as Json;
 
 use crate::object::ObjectPtr;
 
+use super::helpers;
+
 /// Representation of a JS code executable in a browser.
 ///
 /// Example of a JS expression:
@@ -18,7 +20,7 @@ use crate::object::ObjectPtr;
 ///     return "foobar";
 /// }
 /// ```
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Statement {
     /// Actual JS code to be executed.
     expression: String,
@@ -92,8 +94,44 @@ impl Statement {
         self
     }
 
+    /// Returns source JS code of this [`Statement`] and all the [`Statement`]s
+    /// chained to it via [`Statement::and_then()`].
+    #[must_use]
+    pub fn source(&self) -> String {
+        let mut source = self.expression.trim().to_owned();
+        let mut next = self.and_then.as_deref();
+        while let Some(stmt) = next {
+            source.push('\n');
+            source.push_str(stmt.expression.trim());
+            next = stmt.and_then.as_deref();
+        }
+        source
+    }
+
+    /// Indicates whether this [`Statement`] (or any [`Statement`] chained to
+    /// it) uses the helpers installed via [`Window::install_helpers()`].
+    ///
+    /// [`Window::install_helpers()`]: super::Window::install_helpers
+    #[must_use]
+    pub fn requires_helpers(&self) -> bool {
+        let mut next = Some(self);
+        while let Some(stmt) = next {
+            if stmt.expression.contains(helpers::NAMESPACE) {
+                return true;
+            }
+            next = stmt.and_then.as_deref();
+        }
+        false
+    }
+
     /// Returns a JS code which should be executed in a browser and [`Json`]
     /// arguments for this code.
+    ///
+    /// If this [`Statement`] [requires helpers][1], then the returned code
+    /// throws [`helpers::MISSING`] without executing anything in case they're
+    /// missing in a browser window.
+    ///
+    /// [1]: Statement::requires_helpers
     pub(super) fn prepare(self) -> (String, Vec<Json>) {
         // language=JavaScript
         let mut final_js = "
@@ -102,6 +140,19 @@ impl Statement {
             let args;
         "
         .to_owned();
+        if self.requires_helpers() {
+            // language=JavaScript
+            let guard_js = format!(
+                "
+                if ({ns} === undefined) {{
+                    throw '{missing}';
+                }}
+                ",
+                ns = helpers::NAMESPACE,
+                missing = helpers::MISSING,
+            );
+            final_js.push_str(&guard_js);
+        }
 
         let mut statement = Some(Box::new(self));
         let (mut i, mut args) = (0, Vec::new());
@@ -145,3 +196,21 @@ impl Statement {
         )
     }
 }
+
+#[cfg(test)]
+mod spec {
+    use super::{helpers, Statement};
+
+    #[test]
+    fn guards_only_statements_requiring_helpers() {
+        let plain = Statement::new("async () => 1", []);
+        assert!(!plain.requires_helpers());
+        assert!(!plain.prepare().0.contains(helpers::MISSING));
+
+        let chained = Statement::new("async () => 1", []).and_then(
+            Statement::new("async (n) => window.__medea_e2e.findTrack", []),
+        );
+        assert!(chained.requires_helpers());
+        assert!(chained.prepare().0.contains(helpers::MISSING));
+    }
+}
@@ -0,0 +1,273 @@
+//! [getDisplayMedia()][1] function mock.
+//!
+//! [1]: https://w3.org/TR/screen-capture#dom-mediadevices-getdisplaymedia
+
+use crate::browser::{Statement, Window};
+
+/// Mock of a [getDisplayMedia()][1] function, capturing a fake screen from
+/// a `<canvas>` instead of asking a user to pick one.
+///
+/// Allows to emulate a user canceling the screen picker and clicking the
+/// "Stop sharing" button of a browser.
+///
+/// [1]: https://w3.org/TR/screen-capture#dom-mediadevices-getdisplaymedia
+#[derive(Debug)]
+pub struct DisplayMedia<'a>(pub(super) &'a Window);
+
+impl<'a> DisplayMedia<'a> {
+    /// Instantiates a [getDisplayMedia()][1] function mock in the provided
+    /// [`Window`].
+    ///
+    /// All the [MediaStreamTrack][2]s returned by the mock (and their clones)
+    /// are stored in the `window.displayMediaMock.captures` array, and all
+    /// the options it's called with are stored in the
+    /// `window.displayMediaMock.requests` array.
+    ///
+    /// [1]: https://w3.org/TR/screen-capture#dom-mediadevices-getdisplaymedia
+    /// [2]: https://w3.org/TR/mediacapture-streams#dom-mediastreamtrack
+    pub(super) async fn instantiate(window: &Window) {
+        window
+            .execute(Statement::new(
+                // language=JavaScript
+                r#"
+                async () => {
+                    const mock = {
+                        width: 1280,
+                        height: 720,
+                        frameRate: 30,
+                        displaySurface: "monitor",
+                        cancelNext: 0,
+                        captures: [],
+                        requests: []
+                    };
+                    window.displayMediaMock = mock;
+
+                    const capture = (track, surface) => {
+                        const clone = track.clone.bind(track);
+                        track.clone = () => {
+                            const cloned = clone();
+                            capture(cloned, surface);
+                            return cloned;
+                        };
+                        const getSettings = track.getSettings.bind(track);
+                        track.getSettings = () => {
+                            const settings = getSettings();
+                            settings.displaySurface = surface;
+                            return settings;
+                        };
+                        mock.captures.push(track);
+                    };
+
+                    const gdm = async (cons) => {
+                        mock.requests.push(cons);
+                        if (mock.cancelNext > 0) {
+                            mock.cancelNext--;
+                            throw new DOMException(
+                                "Mocked screen picker cancel",
+                                "NotAllowedError"
+                            );
+                        }
+                        const canvas = document.createElement("canvas");
+                        canvas.width = mock.width;
+                        canvas.height = mock.height;
+                        const ctx = canvas.getContext("2d");
+                        let frame = 0;
+                        const draw = () => {
+                            frame++;
+                            ctx.fillStyle = `hsl(${frame % 360}, 50%, 50%)`;
+                            ctx.fillRect(0, 0, canvas.width, canvas.height);
+                        };
+                        draw();
+                        const interval = setInterval(
+                            draw, 1000 / mock.frameRate
+                        );
+
+                        const stream = canvas.captureStream(mock.frameRate);
+                        for (const track of stream.getVideoTracks()) {
+                            const stop = track.stop.bind(track);
+                            track.stop = () => {
+                                clearInterval(interval);
+                                stop();
+                            };
+                            capture(track, mock.displaySurface);
+                        }
+                        return stream;
+                    };
+                    window.mockProperty(
+                        navigator.mediaDevices, "getDisplayMedia", gdm
+                    );
+                }
+                "#,
+                [],
+            ))
+            .await
+            .map(drop)
+            .unwrap();
+    }
+
+    /// Sets the resolution of the screen captured by the next
+    /// [getDisplayMedia()][1] requests.
+    ///
+    /// # Panics
+    ///
+    /// If failed to execute JS statement.
+    ///
+    /// [1]: https://w3.org/TR/screen-capture#dom-mediadevices-getdisplaymedia
+    pub async fn set_resolution(&self, width: u32, height: u32) {
+        self.0
+            .execute(Statement::new(
+                // language=JavaScript
+                "
+                async () => {
+                    const [width, height] = args;
+                    window.displayMediaMock.width = width;
+                    window.displayMediaMock.height = height;
+                }
+                ",
+                [width.into(), height.into()],
+            ))
+            .await
+            .map(drop)
+            .unwrap();
+    }
+
+    /// Sets the frame rate of the screen captured by the next
+    /// [getDisplayMedia()][1] requests.
+    ///
+    /// # Panics
+    ///
+    /// If failed to execute JS statement.
+    ///
+    /// [1]: https://w3.org/TR/screen-capture#dom-mediadevices-getdisplaymedia
+    pub async fn set_frame_rate(&self, frame_rate: u32) {
+        self.0
+            .execute(Statement::new(
+                // language=JavaScript
+                "
+                async () => {
+                    const [frameRate] = args;
+                    window.displayMediaMock.frameRate = frameRate;
+                }
+                ",
+                [frame_rate.into()],
+            ))
+            .await
+            .map(drop)
+            .unwrap();
+    }
+
+    /// Sets the [displaySurface][2] of the screen captured by the next
+    /// [getDisplayMedia()][1] requests.
+    ///
+    /// # Panics
+    ///
+    /// If failed to execute JS statement.
+    ///
+    /// [1]: https://w3.org/TR/screen-capture#dom-mediadevices-getdisplaymedia
+    /// [2]: https://w3.org/TR/screen-capture#extensions-to-mediatracksettings
+    pub async fn set_display_surface(&self, surface: &str) {
+        self.0
+            .execute(Statement::new(
+                // language=JavaScript
+                "
+                async () => {
+                    const [surface] = args;
+                    window.displayMediaMock.displaySurface = surface;
+                }
+                ",
+                [surface.into()],
+            ))
+            .await
+            .map(drop)
+            .unwrap();
+    }
+
+    /// Returns the options of the last [getDisplayMedia()][1] request, if
+    /// any.
+    ///
+    /// # Panics
+    ///
+    /// If failed to execute JS statement.
+    ///
+    /// [1]: https://w3.org/TR/screen-capture#dom-mediadevices-getdisplaymedia
+    pub async fn last_request_options(&self) -> serde_json::Value {
+        self.0
+            .execute(Statement::new(
+                // language=JavaScript
+                "
+                async () => {
+                    const requests = window.displayMediaMock.requests;
+                    if (requests.length === 0) {
+                        return null;
+                    }
+                    return JSON.parse(
+                        JSON.stringify(requests[requests.length - 1])
+                    );
+                }
+                ",
+                [],
+            ))
+            .await
+            .unwrap()
+    }
+
+    /// Makes the next [getDisplayMedia()][1] request reject with
+    /// a `NotAllowedError`, the same way as if a user has canceled the screen
+    /// picker.
+    ///
+    /// # Panics
+    ///
+    /// If failed to execute JS statement.
+    ///
+    /// [1]: https://w3.org/TR/screen-capture#dom-mediadevices-getdisplaymedia
+    pub async fn cancel_next_request(&self) {
+        self.0
+            .execute(Statement::new(
+                // language=JavaScript
+                "
+                async () => {
+                    window.displayMediaMock.cancelNext++;
+                }
+                ",
+                [],
+            ))
+            .await
+            .map(drop)
+            .unwrap();
+    }
+
+    /// Ends all the live screen captures, the same way as if a user has
+    /// clicked the "Stop sharing" button of a browser.
+    ///
+    /// Unlike a [MediaStreamTrack.stop()][1] call, this fires the `ended`
+    /// event on the captured [MediaStreamTrack][2]s.
+    ///
+    /// # Panics
+    ///
+    /// If failed to execute JS statement.
+    ///
+    /// [1]: https://w3.org/TR/mediacapture-streams#dom-mediastreamtrack-stop
+    /// [2]: https://w3.org/TR/mediacapture-streams#dom-mediastreamtrack
+    pub async fn end_current_capture(&self) {
+        self.0
+            .execute(Statement::new(
+                // language=JavaScript
+                r#"
+                async () => {
+                    const mock = window.displayMediaMock;
+                    const live = mock.captures
+                        .filter((t) => t.readyState === "live");
+                    mock.captures = [];
+                    for (const track of live) {
+                        track.stop();
+                        track.dispatchEvent(new Event("ended"));
+                    }
+                }
+                "#,
+                [],
+            ))
+            .await
+            .map(drop)
+            .unwrap();
+    }
+}
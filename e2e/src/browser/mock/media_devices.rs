@@ -2,7 +2,59 @@
 //!
 //! [1]: https://www.w3.org/TR/mediacapture-streams/#mediadevices
 
-use crate::browser::{Statement, Window};
+use crate::{
+    browser::{Statement, Window},
+    object::{MediaKind, MediaSourceKind},
+};
+
+/// Frequency (in Hz) of a sine tone generated for mocked audio tracks.
+const TEST_TONE_FREQUENCY: u32 = 440;
+
+/// [DOMException][1] to be thrown by a mocked media request.
+///
+/// [1]: https://webidl.spec.whatwg.org#idl-DOMException
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MediaRequestError {
+    /// `NotAllowedError` thrown when a user or a browser denies the access to
+    /// the requested media.
+    NotAllowed,
+
+    /// `NotReadableError` thrown when a hardware error occurs on an OS or
+    /// a browser level, preventing the access to the requested media.
+    NotReadable,
+
+    /// `OverconstrainedError` thrown when the provided `constraint` cannot be
+    /// satisfied by any of the available media sources.
+    Overconstrained {
+        /// Name of the constraint which cannot be satisfied.
+        constraint: String,
+    },
+}
+
+impl MediaRequestError {
+    /// Returns a name of the [DOMException][1] represented by this
+    /// [`MediaRequestError`].
+    ///
+    /// [1]: https://webidl.spec.whatwg.org#idl-DOMException
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::NotAllowed => "NotAllowedError",
+            Self::NotReadable => "NotReadableError",
+            Self::Overconstrained { .. } => "OverconstrainedError",
+        }
+    }
+
+    /// Returns a `constraint` field of an `OverconstrainedError`, if this
+    /// [`MediaRequestError`] is the one.
+    #[must_use]
+    pub fn constraint(&self) -> Option<&str> {
+        match self {
+            Self::Overconstrained { constraint } => Some(constraint),
+            Self::NotAllowed | Self::NotReadable => None,
+        }
+    }
+}
 
 /// Mock of a [MediaDevices][1] interface.
 ///
@@ -14,25 +66,134 @@ impl<'a> MediaDevices<'a> {
     /// Instantiates a [MediaDevices][1] interface mock in the provided
     /// [`Window`].
     ///
+    /// Audio tracks returned by [getUserMedia()][2] are replaced with a
+    /// [`TEST_TONE_FREQUENCY`] sine tone (or a silence, once
+    /// [`MediaDevices::silence_audio()`] is called), so the received audio
+    /// can be verified deterministically. Requests for unknown device IDs are
+    /// rejected with an `OverconstrainedError`.
+    ///
+    /// [`MediaKind`]s of all the [getUserMedia()][2] requests are stored in
+    /// the `window.gumMock.requests` array.
+    ///
     /// [1]: https://w3.org/TR/mediacapture-streams#mediadevices
+    /// [2]: https://tinyurl.com/w3-streams#dom-mediadevices-getusermedia
     pub(super) async fn instantiate(window: &Window) {
         window
             .execute(Statement::new(
                 // language=JavaScript
-                "
+                r#"
                 async () => {
-                    window.gumMock = {
-                        original: navigator.mediaDevices.getUserMedia
+                    const [frequency] = args;
+                    const devices = navigator.mediaDevices;
+                    const gum = devices.getUserMedia.bind(devices);
+                    const mock = { toneCtx: null, requests: [], silent: false };
+                    window.gumMock = mock;
+
+                    const tone = () => {
+                        if (mock.toneCtx === null) {
+                            mock.toneCtx = new AudioContext();
+                        }
+                        const ctx = mock.toneCtx;
+                        ctx.resume();
+                        const osc = ctx.createOscillator();
+                        osc.type = "sine";
+                        osc.frequency.value = frequency;
+                        const gain = ctx.createGain();
+                        gain.gain.value = mock.silent ? 0 : 1;
+                        const dst = ctx.createMediaStreamDestination();
+                        osc.connect(gain);
+                        gain.connect(dst);
+                        osc.start();
+                        const track = dst.stream.getAudioTracks()[0];
+                        const stop = track.stop.bind(track);
+                        track.stop = () => {
+                            osc.stop();
+                            osc.disconnect();
+                            gain.disconnect();
+                            stop();
+                        };
+                        return track;
                     };
+
+                    const exactDeviceId = (cons) => {
+                        if (typeof cons !== "object" || cons === null) {
+                            return null;
+                        }
+                        const id = cons.deviceId;
+                        if (typeof id === "string") {
+                            return id;
+                        }
+                        return id?.exact ?? null;
+                    };
+
+                    mock.original = async (cons) => {
+                        mock.requests.push(["audio", "video"].filter((k) => {
+                            return cons[k] != null && cons[k] !== false;
+                        }));
+                        const known = await devices.enumerateDevices();
+                        const requested = [
+                            ["audioinput", exactDeviceId(cons.audio)],
+                            ["videoinput", exactDeviceId(cons.video)]
+                        ];
+                        for (const [kind, id] of requested) {
+                            const isKnown = known.some((d) => {
+                                return d.kind === kind && d.deviceId === id;
+                            });
+                            if (id !== null && !isKnown) {
+                                const err = new DOMException(
+                                    `Mocked unknown ${kind} device`,
+                                    "OverconstrainedError"
+                                );
+                                Object.defineProperty(err, "constraint", {
+                                    value: "deviceId"
+                                });
+                                throw err;
+                            }
+                        }
+                        const stream = await gum(cons);
+                        for (const track of stream.getAudioTracks()) {
+                            stream.removeTrack(track);
+                            track.stop();
+                            stream.addTrack(tone());
+                        }
+                        return stream;
+                    };
+                    window.mockProperty(
+                        devices, "getUserMedia", mock.original
+                    );
                 }
-                ",
-                [],
+                "#,
+                [TEST_TONE_FREQUENCY.into()],
             ))
             .await
             .map(drop)
             .unwrap();
     }
 
+    /// Returns [`MediaKind`]s requested by each [getUserMedia()][1] request
+    /// made in this [`Window`], in the order of the requests.
+    ///
+    /// Requests rejected by [`MediaDevices::mock_gum()`] or
+    /// [`MediaDevices::fail_get_user_media()`] are not included.
+    ///
+    /// # Panics
+    ///
+    /// If failed to execute JS statement.
+    ///
+    /// [1]: https://tinyurl.com/w3-streams#dom-mediadevices-getusermedia
+    pub async fn get_user_media_requests(&self) -> Vec<Vec<MediaKind>> {
+        let requests = self
+            .0
+            .execute(Statement::new(
+                // language=JavaScript
+                "async () => window.gumMock.requests",
+                [],
+            ))
+            .await
+            .unwrap();
+        serde_json::from_value(requests).unwrap()
+    }
+
     /// Mocks [getUserMedia()][1] requests to return error for the provided
     /// media types.
     ///
@@ -48,7 +209,7 @@ impl<'a> MediaDevices<'a> {
                 "
                 async () => {
                     const [isVideoBroken, isAudioBroken] = args;
-                    navigator.mediaDevices.getUserMedia = async (cons) => {
+                    const gum = async (cons) => {
                         if (isAudioBroken && cons.audio != null) {
                             throw new NotFoundError();
                         }
@@ -56,7 +217,10 @@ impl<'a> MediaDevices<'a> {
                             throw new NotFoundError();
                         }
                         return await window.gumMock.original(cons);
-                    }
+                    };
+                    window.mockProperty(
+                        navigator.mediaDevices, 'getUserMedia', gum
+                    );
                 }
                 ",
                 [video.into(), audio.into()],
@@ -65,4 +229,467 @@ impl<'a> MediaDevices<'a> {
             .map(drop)
             .unwrap();
     }
+
+    /// Mocks the next `times` media requests matching the provided
+    /// [`MediaKind`] and [`MediaSourceKind`] to reject with the provided
+    /// [`MediaRequestError`].
+    ///
+    /// [`MediaSourceKind::Device`] requests are matched against
+    /// [getUserMedia()][1] calls, while [`MediaSourceKind::Display`] ones are
+    /// matched against [getDisplayMedia()][2] calls. Once the failures are
+    /// exhausted, requests are handled as usual.
+    ///
+    /// # Panics
+    ///
+    /// If failed to execute JS statement.
+    ///
+    /// [1]: https://tinyurl.com/w3-streams#dom-mediadevices-getusermedia
+    /// [2]: https://w3.org/TR/screen-capture#dom-mediadevices-getdisplaymedia
+    pub async fn fail_get_user_media(
+        &self,
+        kind: MediaKind,
+        source_kind: MediaSourceKind,
+        error: MediaRequestError,
+        times: u64,
+    ) {
+        let kind = match kind {
+            MediaKind::Audio => "audio",
+            MediaKind::Video => "video",
+        };
+        let is_display = source_kind == MediaSourceKind::Display;
+        self.0
+            .execute(Statement::new(
+                // language=JavaScript
+                r#"
+                async () => {
+                    const [kind, isDisplay, name, constraint, times] = args;
+                    const mock = window.gumMock;
+                    if (mock.failures === undefined) {
+                        mock.failures = [];
+                        const takeFailure = (isDisplay, cons) => {
+                            const idx = mock.failures.findIndex((f) => {
+                                return f.isDisplay === isDisplay
+                                    && cons[f.kind] != null
+                                    && cons[f.kind] !== false;
+                            });
+                            if (idx === -1) {
+                                return null;
+                            }
+                            const failure = mock.failures[idx];
+                            failure.times--;
+                            if (failure.times <= 0) {
+                                mock.failures.splice(idx, 1);
+                            }
+                            const err = new DOMException(
+                                `Mocked ${failure.name} for ${failure.kind}`,
+                                failure.name
+                            );
+                            if (failure.constraint !== null) {
+                                Object.defineProperty(err, "constraint", {
+                                    value: failure.constraint
+                                });
+                            }
+                            return err;
+                        };
+
+                        const devices = navigator.mediaDevices;
+                        const gum = devices.getUserMedia.bind(devices);
+                        window.mockProperty(devices, "getUserMedia",
+                            async (cons) => {
+                                const err = takeFailure(false, cons);
+                                if (err !== null) {
+                                    throw err;
+                                }
+                                return await gum(cons);
+                            }
+                        );
+                        const gdm = devices.getDisplayMedia.bind(devices);
+                        window.mockProperty(devices, "getDisplayMedia",
+                            async (cons) => {
+                                const err = takeFailure(true, cons);
+                                if (err !== null) {
+                                    throw err;
+                                }
+                                return await gdm(cons);
+                            }
+                        );
+                    }
+                    mock.failures.push({
+                        kind: kind,
+                        isDisplay: isDisplay,
+                        name: name,
+                        constraint: constraint,
+                        times: times
+                    });
+                }
+                "#,
+                [
+                    kind.into(),
+                    is_display.into(),
+                    error.name().into(),
+                    error.constraint().into(),
+                    times.into(),
+                ],
+            ))
+            .await
+            .map(drop)
+            .unwrap();
+    }
+
+    /// Makes all the subsequent [getUserMedia()][1] requests ignore an ideal
+    /// [deviceId][2] constraint of the provided [`MediaKind`], capturing the
+    /// `default` device instead, like some platforms do. If `is_exact_ignored`
+    /// is `true`, then an exact [deviceId][2] constraint is ignored too.
+    ///
+    /// Requested devices are not required to exist, as [getSettings()][3] of
+    /// the captured tracks are patched to report the captured device ID.
+    ///
+    /// # Panics
+    ///
+    /// If failed to execute JS statement.
+    ///
+    /// [1]: https://tinyurl.com/w3-streams#dom-mediadevices-getusermedia
+    /// [2]: https://w3.org/TR/mediacapture-streams#def-constraint-deviceId
+    /// [3]: https://tinyurl.com/w3-streams#dom-mediastreamtrack-getsettings
+    pub async fn ignore_device_id(
+        &self,
+        kind: MediaKind,
+        is_exact_ignored: bool,
+    ) {
+        let kind = match kind {
+            MediaKind::Audio => "audio",
+            MediaKind::Video => "video",
+        };
+        self.0
+            .execute(Statement::new(
+                // language=JavaScript
+                r#"
+                async () => {
+                    const [kind, isExactIgnored] = args;
+                    const devices = navigator.mediaDevices;
+                    const gum = devices.getUserMedia.bind(devices);
+                    window.mockProperty(devices, "getUserMedia",
+                        async (cons) => {
+                            const caps = cons[kind];
+                            if (typeof caps !== "object" || caps === null
+                                || caps.deviceId === undefined) {
+                                return await gum(cons);
+                            }
+                            const id = caps.deviceId;
+                            const exact = typeof id === "string"
+                                ? id : id.exact ?? null;
+                            const captured = exact === null || isExactIgnored
+                                ? "default" : exact;
+                            const { deviceId, ...rest } = caps;
+                            const stream = await gum({ ...cons, [kind]: rest });
+                            const tracks = kind === "audio"
+                                ? stream.getAudioTracks()
+                                : stream.getVideoTracks();
+                            for (const track of tracks) {
+                                const settings = track.getSettings.bind(track);
+                                track.getSettings = () => ({
+                                    ...settings(),
+                                    deviceId: captured
+                                });
+                            }
+                            return stream;
+                        }
+                    );
+                }
+                "#,
+                [kind.into(), is_exact_ignored.into()],
+            ))
+            .await
+            .map(drop)
+            .unwrap();
+    }
+
+    /// Replaces all the video input devices listed by
+    /// [enumerateDevices()][1] with the fake cameras of the provided IDs.
+    ///
+    /// All the subsequent [getUserMedia()][2] requests capture video from the
+    /// requested fake camera (or the first one, if none is requested), as
+    /// [getSettings()][3] of the captured tracks and their clones are patched
+    /// to report its ID.
+    ///
+    /// # Panics
+    ///
+    /// If failed to execute JS statement.
+    ///
+    /// [1]: https://tinyurl.com/w3-streams#dom-mediadevices-enumeratedevices
+    /// [2]: https://tinyurl.com/w3-streams#dom-mediadevices-getusermedia
+    /// [3]: https://tinyurl.com/w3-streams#dom-mediastreamtrack-getsettings
+    pub async fn mock_cameras(&self, ids: &[String]) {
+        self.0
+            .execute(Statement::new(
+                // language=JavaScript
+                r#"
+                async () => {
+                    const [ids] = args;
+                    const devices = navigator.mediaDevices;
+                    const enumerate = devices.enumerateDevices.bind(devices);
+                    window.mockProperty(devices, "enumerateDevices",
+                        async () => {
+                            const list = await enumerate();
+                            return list
+                                .filter((d) => d.kind !== "videoinput")
+                                .concat(ids.map((id) => ({
+                                    deviceId: id,
+                                    kind: "videoinput",
+                                    label: id,
+                                    groupId: id
+                                })));
+                        }
+                    );
+                    const patch = (track, id) => {
+                        const settings = track.getSettings.bind(track);
+                        track.getSettings = () => ({
+                            ...settings(),
+                            deviceId: id
+                        });
+                        const clone = track.clone.bind(track);
+                        track.clone = () => patch(clone(), id);
+                        return track;
+                    };
+                    const gum = devices.getUserMedia.bind(devices);
+                    window.mockProperty(devices, "getUserMedia",
+                        async (cons) => {
+                            if (!cons.video) {
+                                return await gum(cons);
+                            }
+                            const video = typeof cons.video === "object"
+                                ? cons.video : {};
+                            const id = video.deviceId;
+                            const requested = typeof id === "string"
+                                ? id : id?.exact ?? id?.ideal ?? null;
+                            const captured = ids.includes(requested)
+                                ? requested : ids[0];
+                            const { deviceId, ...rest } = video;
+                            const stream = await gum({ ...cons, video: rest });
+                            for (const track of stream.getVideoTracks()) {
+                                patch(track, captured);
+                            }
+                            return stream;
+                        }
+                    );
+                }
+                "#,
+                [ids.into()],
+            ))
+            .await
+            .map(drop)
+            .unwrap();
+    }
+
+    /// Makes all the subsequent [getUserMedia()][1] requests return silent
+    /// audio tracks, emulating a microphone muted on an OS level.
+    ///
+    /// # Panics
+    ///
+    /// If failed to execute JS statement.
+    ///
+    /// [1]: https://tinyurl.com/w3-streams#dom-mediadevices-getusermedia
+    pub async fn silence_audio(&self) {
+        self.0
+            .execute(Statement::new(
+                // language=JavaScript
+                "async () => { window.gumMock.silent = true; }",
+                [],
+            ))
+            .await
+            .map(drop)
+            .unwrap();
+    }
+
+    /// Hides all the input devices of the provided [`MediaKind`] from the
+    /// [enumerateDevices()][1] results, emulating a hardware without them.
+    ///
+    /// # Panics
+    ///
+    /// If failed to execute JS statement.
+    ///
+    /// [1]: https://tinyurl.com/w3-streams#dom-mediadevices-enumeratedevices
+    pub async fn hide_input_devices(&self, kind: MediaKind) {
+        let kind = match kind {
+            MediaKind::Audio => "audioinput",
+            MediaKind::Video => "videoinput",
+        };
+        self.0
+            .execute(Statement::new(
+                // language=JavaScript
+                "
+                async () => {
+                    const [kind] = args;
+                    const devices = navigator.mediaDevices;
+                    const enumerate = devices.enumerateDevices.bind(devices);
+                    window.mockProperty(devices, 'enumerateDevices',
+                        async () => {
+                            const list = await enumerate();
+                            return list.filter((d) => d.kind !== kind);
+                        }
+                    );
+                }
+                ",
+                [kind.into()],
+            ))
+            .await
+            .map(drop)
+            .unwrap();
+    }
+
+    /// Sets a state of the [Permissions API][1] permission to access media
+    /// input devices of the provided [`MediaKind`], notifying the observers
+    /// of its `change` event.
+    ///
+    /// While the permission is denied, [getUserMedia()][2] requests of the
+    /// provided [`MediaKind`] are rejected with a `NotAllowedError`, and its
+    /// input devices are listed by [enumerateDevices()][3] without IDs and
+    /// labels, the same way as browsers do.
+    ///
+    /// # Panics
+    ///
+    /// If failed to execute JS statement.
+    ///
+    /// [1]: https://w3.org/TR/permissions
+    /// [2]: https://tinyurl.com/w3-streams#dom-mediadevices-getusermedia
+    /// [3]: https://tinyurl.com/w3-streams#dom-mediadevices-enumeratedevices
+    pub async fn set_permission(&self, kind: MediaKind, is_granted: bool) {
+        let kind = match kind {
+            MediaKind::Audio => "audio",
+            MediaKind::Video => "video",
+        };
+        self.0
+            .execute(Statement::new(
+                // language=JavaScript
+                r#"
+                async () => {
+                    const [kind, isGranted] = args;
+                    const mock = window.gumMock;
+                    if (mock.permissions === undefined) {
+                        mock.permissions = { audio: {}, video: {} };
+                        const names = { microphone: "audio", camera: "video" };
+                        const isDenied = (kind) => {
+                            return mock.permissions[kind].state === "denied";
+                        };
+
+                        const perms = navigator.permissions;
+                        const query = perms.query.bind(perms);
+                        window.mockProperty(perms, "query", async (desc) => {
+                            const kind = names[desc.name];
+                            if (kind === undefined) {
+                                return await query(desc);
+                            }
+                            const perm = mock.permissions[kind];
+                            if (perm.status === undefined) {
+                                perm.status = new EventTarget();
+                                Object.defineProperty(perm.status, "state", {
+                                    get: () => perm.state ?? "granted"
+                                });
+                            }
+                            return perm.status;
+                        });
+
+                        const devices = navigator.mediaDevices;
+                        const gum = devices.getUserMedia.bind(devices);
+                        window.mockProperty(devices, "getUserMedia",
+                            async (cons) => {
+                                for (const kind of ["audio", "video"]) {
+                                    const isRequested = cons[kind] != null
+                                        && cons[kind] !== false;
+                                    if (isRequested && isDenied(kind)) {
+                                        throw new DOMException(
+                                            `Mocked denied ${kind}`,
+                                            "NotAllowedError"
+                                        );
+                                    }
+                                }
+                                return await gum(cons);
+                            }
+                        );
+                        const enumerate =
+                            devices.enumerateDevices.bind(devices);
+                        window.mockProperty(devices, "enumerateDevices",
+                            async () => {
+                                const list = await enumerate();
+                                return list.map((d) => {
+                                    const kind = d.kind.replace("input", "");
+                                    if (d.kind.endsWith("output")
+                                        || !isDenied(kind)) {
+                                        return d;
+                                    }
+                                    return {
+                                        deviceId: "",
+                                        kind: d.kind,
+                                        label: "",
+                                        groupId: ""
+                                    };
+                                });
+                            }
+                        );
+                    }
+                    const perm = mock.permissions[kind];
+                    perm.state = isGranted ? "granted" : "denied";
+                    if (perm.status !== undefined) {
+                        perm.status.dispatchEvent(new Event("change"));
+                        // Let the observers react on the change.
+                        await new Promise((r) => setTimeout(r, 200));
+                    }
+                }
+                "#,
+                [kind.into(), is_granted.into()],
+            ))
+            .await
+            .map(drop)
+            .unwrap();
+    }
+
+    /// Makes [InputDeviceInfo.getCapabilities()][1] of all the input devices
+    /// of the provided [`MediaKind`] return the provided
+    /// [MediaTrackCapabilities][2].
+    ///
+    /// An empty `capabilities` object emulates a platform not reporting
+    /// capabilities of the devices before they're captured.
+    ///
+    /// # Panics
+    ///
+    /// If failed to execute JS statement.
+    ///
+    /// [1]: https://tinyurl.com/w3-streams#dom-inputdeviceinfo-getcapabilities
+    /// [2]: https://w3.org/TR/mediacapture-streams#media-track-capabilities
+    pub async fn set_capabilities(
+        &self,
+        kind: MediaKind,
+        capabilities: serde_json::Value,
+    ) {
+        let kind = match kind {
+            MediaKind::Audio => "audioinput",
+            MediaKind::Video => "videoinput",
+        };
+        self.0
+            .execute(Statement::new(
+                // language=JavaScript
+                "
+                async () => {
+                    const [kind, capabilities] = args;
+                    const devices = navigator.mediaDevices;
+                    const enumerate = devices.enumerateDevices.bind(devices);
+                    window.mockProperty(devices, 'enumerateDevices',
+                        async () => {
+                            const list = await enumerate();
+                            for (const d of list) {
+                                if (d.kind === kind) {
+                                    d.getCapabilities = () => capabilities;
+                                }
+                            }
+                            return list;
+                        }
+                    );
+                }
+                ",
+                [kind.into(), capabilities],
+            ))
+            .await
+            .map(drop)
+            .unwrap();
+    }
 }
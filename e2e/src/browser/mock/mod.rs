@@ -1,16 +1,57 @@
 //! WebAPI objects mocks.
 
+pub mod display_media;
 pub mod media_devices;
+pub mod network;
+pub mod peer_connection;
 pub mod websocket;
 
-use super::Window;
+use super::{Statement, Window};
 
-pub use self::{media_devices::MediaDevices, websocket::WebSocket};
+pub use self::{
+    display_media::DisplayMedia,
+    media_devices::{MediaDevices, MediaRequestError},
+    network::Network,
+    peer_connection::RtcPeerConnection,
+    websocket::WebSocket,
+};
 
 /// Instantiates all the required mocks in the provided [`Window`].
 pub async fn instantiate_mocks(window: &Window) {
+    instantiate_helpers(window).await;
     WebSocket::instantiate(window).await;
     MediaDevices::instantiate(window).await;
+    DisplayMedia::instantiate(window).await;
+    RtcPeerConnection::instantiate(window).await;
+}
+
+/// Instantiates JS helpers used by mocks in the provided [`Window`].
+///
+/// `window.mockProperty(obj, name, value)` overrides a property of a WebAPI
+/// object in the same way in all the browsers. Plain assignments silently do
+/// nothing in some browsers for properties defined on prototypes as
+/// non-writable accessors.
+async fn instantiate_helpers(window: &Window) {
+    window
+        .execute(Statement::new(
+            // language=JavaScript
+            "
+            async () => {
+                window.mockProperty = (obj, name, value) => {
+                    Object.defineProperty(obj, name, {
+                        value: value,
+                        writable: true,
+                        configurable: true,
+                        enumerable: true
+                    });
+                };
+            }
+            ",
+            [],
+        ))
+        .await
+        .map(drop)
+        .unwrap();
 }
 
 #[allow(clippy::multiple_inherent_impl)] // better keep these functions here
@@ -26,4 +67,23 @@ impl Window {
     pub const fn media_devices_mock(&self) -> MediaDevices<'_> {
         MediaDevices(self)
     }
+
+    /// Returns `getDisplayMedia()` function mock for this [`Window`].
+    #[must_use]
+    pub const fn display_media_mock(&self) -> DisplayMedia<'_> {
+        DisplayMedia(self)
+    }
+
+    /// Returns network availability and page visibility mock for this
+    /// [`Window`].
+    #[must_use]
+    pub const fn network_mock(&self) -> Network<'_> {
+        Network(self)
+    }
+
+    /// Returns `RTCPeerConnection` object mock for this [`Window`].
+    #[must_use]
+    pub const fn peer_connection_mock(&self) -> RtcPeerConnection<'_> {
+        RtcPeerConnection(self)
+    }
 }
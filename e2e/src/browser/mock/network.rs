@@ -0,0 +1,60 @@
+//! Network availability and page visibility mock.
+
+use crate::browser::{Statement, Window};
+
+/// Mock for the `navigator.onLine` and `document.visibilityState` WebAPI
+/// properties, along with their change events.
+#[derive(Debug)]
+pub struct Network<'a>(pub(super) &'a Window);
+
+impl<'a> Network<'a> {
+    /// Sets `navigator.onLine` to the provided value and fires the
+    /// corresponding `online` or `offline` event on the `window`.
+    ///
+    /// # Panics
+    ///
+    /// If failed to execute JS statement.
+    pub async fn set_online(&self, online: bool) {
+        self.0
+            .execute(Statement::new(
+                // language=JavaScript
+                "
+                async () => {
+                    const [online] = args;
+                    window.mockProperty(navigator, 'onLine', online);
+                    window.dispatchEvent(
+                        new Event(online ? 'online' : 'offline')
+                    );
+                }
+                ",
+                [online.into()],
+            ))
+            .await
+            .map(drop)
+            .unwrap();
+    }
+
+    /// Sets `document.visibilityState` to `visible` and fires the
+    /// `visibilitychange` event on the `document`, as if the page has been
+    /// brought back from a background tab.
+    ///
+    /// # Panics
+    ///
+    /// If failed to execute JS statement.
+    pub async fn show_page(&self) {
+        self.0
+            .execute(Statement::new(
+                // language=JavaScript
+                "
+                async () => {
+                    window.mockProperty(document, 'visibilityState', 'visible');
+                    document.dispatchEvent(new Event('visibilitychange'));
+                }
+                ",
+                [],
+            ))
+            .await
+            .map(drop)
+            .unwrap();
+    }
+}
@@ -0,0 +1,608 @@
+//! [RTCPeerConnection][1] object mock.
+//!
+//! [1]: https://w3.org/TR/webrtc#dom-rtcpeerconnection
+
+use crate::{
+    browser::{Statement, Window},
+    object::MediaKind,
+};
+
+/// JS function returning device IDs of all the live `MediaStreamTrack`s of the
+/// provided kind being sent by the not closed `RTCPeerConnection`s.
+// language=JavaScript
+const SENT_DEVICE_IDS_JS: &str = r#"
+    (kind) => {
+        return window.pcMock.connections
+            .filter((pc) => pc.signalingState !== "closed")
+            .flatMap((pc) => pc.getSenders())
+            .map((s) => s.track)
+            .filter((t) => t?.kind === kind && t.readyState === "live")
+            .map((t) => t.getSettings().deviceId ?? "");
+    }
+"#;
+
+/// ICE servers the [RTCPeerConnection][1]s are given while the direct
+/// connectivity is blocked, pointing to a non-routable [TEST-NET-1][2] TURN
+/// address.
+///
+/// [1]: https://w3.org/TR/webrtc#dom-rtcpeerconnection
+/// [2]: https://tools.ietf.org/html/rfc5737#section-3
+// language=JavaScript
+const BLOCKED_ICE_SERVERS_JS: &str = r#"
+    [{
+        urls: ["turn:192.0.2.1:3478", "turn:192.0.2.1:3478?transport=tcp"],
+        username: "blocked",
+        credential: "blocked"
+    }]
+"#;
+
+/// Mock of a [RTCPeerConnection][1] object registering all the created
+/// instances, so they can be inspected by tests.
+///
+/// Also allows to emulate a media traffic loss while the signaling stays
+/// alive, and a direct connectivity loss by rewriting the ICE servers.
+///
+/// [1]: https://w3.org/TR/webrtc#dom-rtcpeerconnection
+#[derive(Debug)]
+pub struct RtcPeerConnection<'a>(pub(super) &'a Window);
+
+impl<'a> RtcPeerConnection<'a> {
+    /// Instantiates a [RTCPeerConnection][1] object mock in the provided
+    /// [`Window`].
+    ///
+    /// All the created [RTCPeerConnection][1]s are stored in the
+    /// `window.pcMock.connections` array, and SDPs of all the created offers
+    /// are stored in the `window.pcMock.offers` array.
+    ///
+    /// [1]: https://w3.org/TR/webrtc#dom-rtcpeerconnection
+    pub(super) async fn instantiate(window: &Window) {
+        window
+            .execute(Statement::new(
+                // language=JavaScript
+                r#"
+                async () => {
+                    const original = window.RTCPeerConnection;
+                    const mock = {
+                        original: original,
+                        connections: [],
+                        offers: [],
+                        isBlocked: false,
+                        blockedIceServers: null,
+                        isDisabled: false,
+                        iceRestarts: 0,
+                        iceRestartSubs: []
+                    };
+                    window.pcMock = mock;
+
+                    const mocked = class extends original {
+                        constructor(...args) {
+                            if (mock.isDisabled) {
+                                throw new DOMException(
+                                    "Mocked disabled WebRTC",
+                                    "NotSupportedError"
+                                );
+                            }
+                            const config = args[0] ?? {};
+                            if (mock.blockedIceServers !== null) {
+                                args[0] = {
+                                    ...config,
+                                    iceServers: mock.blockedIceServers
+                                };
+                            }
+                            super(...args);
+                            this.unblockedIceServers = config.iceServers ?? [];
+                            mock.connections.push(this);
+                        }
+
+                        get iceConnectionState() {
+                            const state = super.iceConnectionState;
+                            return mock.isBlocked && state !== "closed" ?
+                                "failed" : state;
+                        }
+
+                        get connectionState() {
+                            const state = super.connectionState;
+                            return mock.isBlocked && state !== "closed" ?
+                                "failed" : state;
+                        }
+
+                        async createOffer(...args) {
+                            const opts = args.find((a) => a?.iceRestart);
+                            if (opts !== undefined) {
+                                mock.iceRestarts++;
+                                mock.iceRestartSubs = mock.iceRestartSubs
+                                    .filter((sub) => sub(mock.iceRestarts));
+                            }
+                            const offer = await super.createOffer(...args);
+                            mock.offers.push(offer.sdp);
+                            return offer;
+                        }
+                    };
+                    window.mockProperty(window, "RTCPeerConnection", mocked);
+                }
+                "#,
+                [],
+            ))
+            .await
+            .map(drop)
+            .unwrap();
+    }
+
+    /// Returns count of the created [RTCPeerConnection][1]s which are not
+    /// closed yet.
+    ///
+    /// # Panics
+    ///
+    /// If failed to execute JS statement.
+    ///
+    /// [1]: https://w3.org/TR/webrtc#dom-rtcpeerconnection
+    pub async fn count_active(&self) -> u64 {
+        self.0
+            .execute(Statement::new(
+                // language=JavaScript
+                r#"
+                async () => {
+                    return window.pcMock.connections
+                        .filter((pc) => pc.signalingState !== "closed")
+                        .length;
+                }
+                "#,
+                [],
+            ))
+            .await
+            .unwrap()
+            .as_u64()
+            .unwrap()
+    }
+
+    /// Returns `bundlePolicy`s of the [RTCPeerConnection][1]s which are not
+    /// closed yet.
+    ///
+    /// # Panics
+    ///
+    /// If failed to execute JS statement.
+    ///
+    /// [1]: https://w3.org/TR/webrtc#dom-rtcpeerconnection
+    pub async fn bundle_policies(&self) -> Vec<String> {
+        let policies = self
+            .0
+            .execute(Statement::new(
+                // language=JavaScript
+                r#"
+                async () => {
+                    return window.pcMock.connections
+                        .filter((pc) => pc.signalingState !== "closed")
+                        .map((pc) => pc.getConfiguration().bundlePolicy);
+                }
+                "#,
+                [],
+            ))
+            .await
+            .unwrap();
+        serde_json::from_value(policies).unwrap()
+    }
+
+    /// Emulates a loss of the media traffic in all the [RTCPeerConnection][1]s
+    /// while keeping the signaling connection alive.
+    ///
+    /// All the senders' encodings are deactivated, and all the
+    /// [RTCPeerConnection][1]s report `failed` ICE connection and connection
+    /// states until [`RtcPeerConnection::unblock_media_traffic()`] is called.
+    ///
+    /// # Panics
+    ///
+    /// If failed to execute JS statement.
+    ///
+    /// [1]: https://w3.org/TR/webrtc#dom-rtcpeerconnection
+    pub async fn block_media_traffic(&self) {
+        self.set_media_traffic_blocked(true).await;
+    }
+
+    /// Disables [`RtcPeerConnection::block_media_traffic()`] effects.
+    ///
+    /// # Panics
+    ///
+    /// If failed to execute JS statement.
+    pub async fn unblock_media_traffic(&self) {
+        self.set_media_traffic_blocked(false).await;
+    }
+
+    /// Emulates a loss of the direct connectivity by rewriting the ICE servers
+    /// of all the existing and subsequently created [RTCPeerConnection][1]s to
+    /// a non-routable TURN address, so no working ICE candidates can be
+    /// gathered by them if the media is force relayed.
+    ///
+    /// # Panics
+    ///
+    /// If failed to execute JS statement.
+    ///
+    /// [1]: https://w3.org/TR/webrtc#dom-rtcpeerconnection
+    pub async fn block_connectivity(&self) {
+        self.0
+            .execute(Statement::new(
+                // language=JavaScript
+                &format!(
+                    r#"
+                    async () => {{
+                        const mock = window.pcMock;
+                        mock.blockedIceServers = {BLOCKED_ICE_SERVERS_JS};
+                        const pcs = mock.connections
+                            .filter((pc) => pc.signalingState !== "closed");
+                        for (const pc of pcs) {{
+                            pc.setConfiguration({{
+                                ...pc.getConfiguration(),
+                                iceServers: mock.blockedIceServers
+                            }});
+                        }}
+                    }}
+                    "#
+                ),
+                [],
+            ))
+            .await
+            .map(drop)
+            .unwrap();
+    }
+
+    /// Disables [`RtcPeerConnection::block_connectivity()`] effects by
+    /// restoring the original ICE servers of all the [RTCPeerConnection][1]s.
+    ///
+    /// Then fires the state change events on them, so their current (failed)
+    /// states are reported to a media server once again, making it restart
+    /// ICE with the restored ICE servers.
+    ///
+    /// # Panics
+    ///
+    /// If failed to execute JS statement.
+    ///
+    /// [1]: https://w3.org/TR/webrtc#dom-rtcpeerconnection
+    pub async fn unblock_connectivity(&self) {
+        self.0
+            .execute(Statement::new(
+                // language=JavaScript
+                r#"
+                async () => {
+                    const mock = window.pcMock;
+                    mock.blockedIceServers = null;
+                    const pcs = mock.connections
+                        .filter((pc) => pc.signalingState !== "closed");
+                    for (const pc of pcs) {
+                        pc.setConfiguration({
+                            ...pc.getConfiguration(),
+                            iceServers: pc.unblockedIceServers
+                        });
+                        pc.dispatchEvent(
+                            new Event("iceconnectionstatechange")
+                        );
+                        pc.dispatchEvent(new Event("connectionstatechange"));
+                    }
+                }
+                "#,
+                [],
+            ))
+            .await
+            .map(drop)
+            .unwrap();
+    }
+
+    /// Waits for any of the [RTCPeerConnection][1]s which are not closed yet
+    /// to reach the provided [connectionState][2].
+    ///
+    /// # Panics
+    ///
+    /// If failed to execute JS statement.
+    ///
+    /// [1]: https://w3.org/TR/webrtc#dom-rtcpeerconnection
+    /// [2]: https://w3.org/TR/webrtc#dom-peerconnection-connection-state
+    pub async fn wait_for_connection_state(&self, state: &str) {
+        self.0
+            .execute(Statement::new(
+                // language=JavaScript
+                "
+                async () => {
+                    const [state] = args;
+                    const isReached = () => window.pcMock.connections
+                        .filter((pc) => pc.signalingState !== 'closed')
+                        .some((pc) => pc.connectionState === state);
+                    while (!isReached()) {
+                        await new Promise((r) => setTimeout(r, 100));
+                    }
+                }
+                ",
+                [state.into()],
+            ))
+            .await
+            .map(drop)
+            .unwrap();
+    }
+
+    /// Caps the maximum bitrate of all the video senders' encodings in all the
+    /// [RTCPeerConnection][1]s at the provided value (in bits per second),
+    /// making a browser report the sent video as quality-limited.
+    ///
+    /// # Panics
+    ///
+    /// If failed to execute JS statement.
+    ///
+    /// [1]: https://w3.org/TR/webrtc#dom-rtcpeerconnection
+    pub async fn cap_video_bitrate(&self, bitrate: u32) {
+        self.0
+            .execute(Statement::new(
+                // language=JavaScript
+                r#"
+                async () => {
+                    const [bitrate] = args;
+                    const pcs = window.pcMock.connections
+                        .filter((pc) => pc.signalingState !== "closed");
+                    for (const pc of pcs) {
+                        const senders = pc.getSenders().filter((s) => {
+                            return s.track && s.track.kind === "video";
+                        });
+                        for (const sender of senders) {
+                            const params = sender.getParameters();
+                            if (params.encodings === undefined) {
+                                continue;
+                            }
+                            for (const enc of params.encodings) {
+                                enc.maxBitrate = bitrate;
+                            }
+                            await sender.setParameters(params);
+                        }
+                    }
+                }
+                "#,
+                [bitrate.into()],
+            ))
+            .await
+            .map(drop)
+            .unwrap();
+    }
+
+    /// Pauses (or resumes, if `paused` is `false`) encoding of all the video
+    /// senders in all the [RTCPeerConnection][1]s by deactivating their
+    /// encodings, while keeping the sent tracks live and unmuted.
+    ///
+    /// # Panics
+    ///
+    /// If failed to execute JS statement.
+    ///
+    /// [1]: https://w3.org/TR/webrtc#dom-rtcpeerconnection
+    pub async fn set_video_encoding_paused(&self, paused: bool) {
+        self.0
+            .execute(Statement::new(
+                // language=JavaScript
+                r#"
+                async () => {
+                    const [paused] = args;
+                    const pcs = window.pcMock.connections
+                        .filter((pc) => pc.signalingState !== "closed");
+                    for (const pc of pcs) {
+                        const senders = pc.getSenders().filter((s) => {
+                            return s.track && s.track.kind === "video";
+                        });
+                        for (const sender of senders) {
+                            const params = sender.getParameters();
+                            if (params.encodings === undefined) {
+                                continue;
+                            }
+                            for (const enc of params.encodings) {
+                                enc.active = !paused;
+                            }
+                            await sender.setParameters(params);
+                        }
+                    }
+                }
+                "#,
+                [paused.into()],
+            ))
+            .await
+            .map(drop)
+            .unwrap();
+    }
+
+    /// Makes all the subsequent [RTCPeerConnection][1] constructions throw,
+    /// emulating WebRTC being disabled by a browser policy.
+    ///
+    /// # Panics
+    ///
+    /// If failed to execute JS statement.
+    ///
+    /// [1]: https://w3.org/TR/webrtc#dom-rtcpeerconnection
+    pub async fn disable_webrtc(&self) {
+        self.0
+            .execute(Statement::new(
+                // language=JavaScript
+                "async () => { window.pcMock.isDisabled = true; }",
+                [],
+            ))
+            .await
+            .map(drop)
+            .unwrap();
+    }
+
+    /// Waits for the provided `count` of ICE restarts (`createOffer()` calls
+    /// with the `iceRestart` option) to happen in this [`Window`].
+    ///
+    /// # Panics
+    ///
+    /// If failed to execute JS statement.
+    pub async fn wait_for_ice_restarts(&self, count: u64) {
+        self.0
+            .execute(Statement::new(
+                // language=JavaScript
+                "
+                async () => {
+                    const [count] = args;
+                    if (window.pcMock.iceRestarts >= count) {
+                        return;
+                    }
+                    await new Promise((resolve) => {
+                        window.pcMock.iceRestartSubs.push((restarts) => {
+                            if (restarts >= count) {
+                                resolve();
+                                return false;
+                            }
+                            return true;
+                        });
+                    });
+                }
+                ",
+                [count.into()],
+            ))
+            .await
+            .map(drop)
+            .unwrap();
+    }
+
+    /// Waits for at least one SDP offer to be created in this [`Window`] and
+    /// returns SDPs of all the created offers.
+    ///
+    /// # Panics
+    ///
+    /// If failed to execute JS statement.
+    pub async fn wait_for_offers(&self) -> Vec<String> {
+        let offers = self
+            .0
+            .execute(Statement::new(
+                // language=JavaScript
+                "
+                async () => {
+                    while (window.pcMock.offers.length === 0) {
+                        await new Promise((r) => setTimeout(r, 100));
+                    }
+                    return window.pcMock.offers;
+                }
+                ",
+                [],
+            ))
+            .await
+            .unwrap();
+        serde_json::from_value(offers).unwrap()
+    }
+
+    /// Returns device IDs of all the live [MediaStreamTrack][2]s of the
+    /// provided [`MediaKind`] being sent by the [RTCPeerConnection][1]s which
+    /// are not closed yet.
+    ///
+    /// # Panics
+    ///
+    /// If failed to execute JS statement.
+    ///
+    /// [1]: https://w3.org/TR/webrtc#dom-rtcpeerconnection
+    /// [2]: https://w3.org/TR/mediacapture-streams#dom-mediastreamtrack
+    pub async fn sent_device_ids(&self, kind: MediaKind) -> Vec<String> {
+        let ids = self
+            .0
+            .execute(Statement::new(
+                // language=JavaScript
+                &format!(
+                    "
+                    async () => {{
+                        const [kind] = args;
+                        return ({SENT_DEVICE_IDS_JS})(kind);
+                    }}
+                    "
+                ),
+                [track_kind(kind).into()],
+            ))
+            .await
+            .unwrap();
+        serde_json::from_value(ids).unwrap()
+    }
+
+    /// Waits for the [RTCPeerConnection][1]s to send live
+    /// [MediaStreamTrack][2]s of the provided [`MediaKind`] from exactly the
+    /// provided devices.
+    ///
+    /// See [`RtcPeerConnection::sent_device_ids()`] for details.
+    ///
+    /// # Panics
+    ///
+    /// If failed to execute JS statement.
+    ///
+    /// [1]: https://w3.org/TR/webrtc#dom-rtcpeerconnection
+    /// [2]: https://w3.org/TR/mediacapture-streams#dom-mediastreamtrack
+    pub async fn wait_for_sent_device_ids(
+        &self,
+        kind: MediaKind,
+        device_ids: &[String],
+    ) {
+        self.0
+            .execute(Statement::new(
+                // language=JavaScript
+                &format!(
+                    "
+                    async () => {{
+                        const [kind, expected] = args;
+                        const sentIds = {SENT_DEVICE_IDS_JS};
+                        const key = (ids) => JSON.stringify([...ids].sort());
+                        while (key(sentIds(kind)) !== key(expected)) {{
+                            await new Promise((r) => setTimeout(r, 100));
+                        }}
+                    }}
+                    "
+                ),
+                [track_kind(kind).into(), device_ids.into()],
+            ))
+            .await
+            .map(drop)
+            .unwrap();
+    }
+
+    /// Blocks or unblocks the media traffic in all the [RTCPeerConnection][1]s,
+    /// firing the state change events on them.
+    ///
+    /// [1]: https://w3.org/TR/webrtc#dom-rtcpeerconnection
+    async fn set_media_traffic_blocked(&self, blocked: bool) {
+        self.0
+            .execute(Statement::new(
+                // language=JavaScript
+                r#"
+                async () => {
+                    const [blocked] = args;
+                    const mock = window.pcMock;
+                    if (mock.isBlocked === blocked) {
+                        return;
+                    }
+                    mock.isBlocked = blocked;
+                    const pcs = mock.connections
+                        .filter((pc) => pc.signalingState !== "closed");
+                    for (const pc of pcs) {
+                        for (const sender of pc.getSenders()) {
+                            const params = sender.getParameters();
+                            if (params.encodings === undefined) {
+                                continue;
+                            }
+                            for (const enc of params.encodings) {
+                                enc.active = !blocked;
+                            }
+                            try {
+                                await sender.setParameters(params);
+                            } catch (e) {
+                                console.warn(
+                                    "Failed to toggle sender encodings", e
+                                );
+                            }
+                        }
+                        pc.dispatchEvent(
+                            new Event("iceconnectionstatechange")
+                        );
+                        pc.dispatchEvent(new Event("connectionstatechange"));
+                    }
+                }
+                "#,
+                [blocked.into()],
+            ))
+            .await
+            .map(drop)
+            .unwrap();
+    }
+}
+
+/// Returns a `MediaStreamTrack.kind` value of the provided [`MediaKind`].
+const fn track_kind(kind: MediaKind) -> &'static str {
+    match kind {
+        MediaKind::Audio => "audio",
+        MediaKind::Video => "video",
+    }
+}
@@ -14,17 +14,39 @@ impl<'a> WebSocket<'a> {
                 // language=JavaScript
                 r#"
                 async () => {
+                    const nativeSend = WebSocket.prototype.send;
                     let ws = {
-                        originalSend: WebSocket.prototype.send,
+                        originalSend: function (data) {
+                            if (ws.droppedCommand !== null
+                                && typeof data === 'string'
+                                && data.includes(`"${ws.droppedCommand}"`)) {
+                                return;
+                            }
+                            ws.sent.push(data);
+                            nativeSend.call(this, data);
+                        },
                         isClosed: false,
                         closeCode: 0,
-                        allSockets: []
+                        allSockets: [],
+                        sent: [],
+                        received: [],
+                        droppedCommand: null
                     };
                     window.wsMock = ws;
+                    window.mockProperty(
+                        window.originalWs.prototype,
+                        'send',
+                        function (data) {
+                            ws.originalSend.call(this, data);
+                        }
+                    );
 
                     window.wsConstructor = (url) => {
                         let createdWs = new window.originalWs(url);
                         ws.allSockets.push(createdWs);
+                        createdWs.addEventListener('message', (e) => {
+                            ws.received.push(e.data);
+                        });
                         if (ws.isClosed) {
                             createdWs.dispatchEvent(
                                 new CloseEvent("close", { code: ws.code })
@@ -73,6 +95,69 @@ impl<'a> WebSocket<'a> {
             .unwrap();
     }
 
+    /// Fires a `CloseEvent` with the provided `code` and `reason` on all the
+    /// created `WebSocket` instances, as if a server has sent a close frame.
+    ///
+    /// Unlike [`WebSocket::enable_connection_loss()`], doesn't affect the
+    /// `WebSocket`s created afterwards.
+    ///
+    /// # Panics
+    ///
+    /// If failed to execute JS statement.
+    pub async fn close(&self, code: u16, reason: &str) {
+        self.0
+            .execute(Statement::new(
+                // language=JavaScript
+                r#"
+                async () => {
+                    const [code, reason] = args;
+                    for (socket of window.wsMock.allSockets) {
+                        socket.dispatchEvent(new CloseEvent("close", {
+                            code: code,
+                            reason: reason,
+                            wasClean: true
+                        }));
+                    }
+                }
+                "#,
+                [code.into(), reason.into()],
+            ))
+            .await
+            .map(drop)
+            .unwrap();
+    }
+
+    /// Drops all the created `WebSocket` instances abruptly, without any close
+    /// frame, the same way a browser reports a network failure: with an
+    /// `error` event followed by a `CloseEvent` with `1006` code.
+    ///
+    /// Doesn't affect the `WebSocket`s created afterwards.
+    ///
+    /// # Panics
+    ///
+    /// If failed to execute JS statement.
+    pub async fn abort(&self) {
+        self.0
+            .execute(Statement::new(
+                // language=JavaScript
+                r#"
+                async () => {
+                    for (socket of window.wsMock.allSockets) {
+                        socket.dispatchEvent(new Event("error"));
+                        socket.dispatchEvent(new CloseEvent("close", {
+                            code: 1006,
+                            wasClean: false
+                        }));
+                    }
+                }
+                "#,
+                [],
+            ))
+            .await
+            .map(drop)
+            .unwrap();
+    }
+
     /// Disables [`WebSocket::enable_connection_loss()`] effects.
     ///
     /// After this method call, `WebSocket`'s constructor will work the same way
@@ -97,4 +182,236 @@ impl<'a> WebSocket<'a> {
             .map(drop)
             .unwrap();
     }
+
+    /// Returns count of the `WebSocket`s created in the [`Window`].
+    ///
+    /// # Panics
+    ///
+    /// If failed to execute JS statement.
+    pub async fn created_count(&self) -> u64 {
+        self.0
+            .execute(Statement::new(
+                // language=JavaScript
+                "async () => window.wsMock.allSockets.length",
+                [],
+            ))
+            .await
+            .unwrap()
+            .as_u64()
+            .unwrap()
+    }
+
+    /// Disables [`WebSocket::enable_connection_loss()`] effects after the
+    /// provided `ms` milliseconds, so the outage duration is controlled
+    /// precisely on the browser side.
+    ///
+    /// # Panics
+    ///
+    /// If failed to execute JS statement.
+    pub async fn restore_after(&self, ms: u64) {
+        self.0
+            .execute(Statement::new(
+                // language=JavaScript
+                "
+                async () => {
+                    const [ms] = args;
+                    setTimeout(() => {
+                        window.wsMock.isClosed = false;
+                        window.wsMock.closeCode = 0;
+                    }, ms);
+                }
+                ",
+                [ms.into()],
+            ))
+            .await
+            .map(drop)
+            .unwrap();
+    }
+
+    /// Delays all the messages sent by the `WebSocket`s in the [`Window`] by
+    /// the provided `ms` milliseconds.
+    ///
+    /// Zero `ms` disables the delay.
+    ///
+    /// # Panics
+    ///
+    /// If failed to execute JS statement.
+    pub async fn set_send_latency(&self, ms: u64) {
+        self.0
+            .execute(Statement::new(
+                // language=JavaScript
+                "
+                async () => {
+                    const [ms] = args;
+                    const mock = window.wsMock;
+                    mock.sendLatency = ms;
+                    if (mock.isSendDelayed) {
+                        return;
+                    }
+                    mock.isSendDelayed = true;
+                    window.mockProperty(
+                        window.originalWs.prototype,
+                        'send',
+                        function (data) {
+                            if (mock.sendLatency === 0) {
+                                mock.originalSend.call(this, data);
+                                return;
+                            }
+                            setTimeout(() => {
+                                if (this.readyState === WebSocket.OPEN) {
+                                    mock.originalSend.call(this, data);
+                                }
+                            }, mock.sendLatency);
+                        }
+                    );
+                }
+                ",
+                [ms.into()],
+            ))
+            .await
+            .map(drop)
+            .unwrap();
+    }
+
+    /// Forgets all the messages sent by the `WebSocket`s in the [`Window`] so
+    /// far.
+    ///
+    /// # Panics
+    ///
+    /// If failed to execute JS statement.
+    pub async fn clear_sent(&self) {
+        self.0
+            .execute(Statement::new(
+                // language=JavaScript
+                "async () => { window.wsMock.sent = []; }",
+                [],
+            ))
+            .await
+            .map(drop)
+            .unwrap();
+    }
+
+    /// Stops sending the messages containing a `Command` with the provided
+    /// `name` (`UpdateTracks`, `MakeSdpOffer`, etc), as if a server ignores
+    /// them, or resumes sending them if [`None`] is provided.
+    ///
+    /// # Panics
+    ///
+    /// If failed to execute JS statement.
+    pub async fn drop_commands(&self, name: Option<&str>) {
+        self.0
+            .execute(Statement::new(
+                // language=JavaScript
+                "
+                async () => {
+                    const [name] = args;
+                    window.wsMock.droppedCommand = name;
+                }
+                ",
+                [name.into()],
+            ))
+            .await
+            .map(drop)
+            .unwrap();
+    }
+
+    /// Dispatches a `PeerUpdated` event on all the open `WebSocket`s, patching
+    /// the mute state of all the outgoing tracks with the provided `kind`
+    /// (`Audio` or `Video`), as if a server has forced it.
+    ///
+    /// Only the tracks received in `PeerCreated` events are patched.
+    ///
+    /// # Panics
+    ///
+    /// If failed to execute JS statement.
+    pub async fn force_mute_state(&self, kind: &str, muted: bool) {
+        self.0
+            .execute(Statement::new(
+                // language=JavaScript
+                "
+                async () => {
+                    const [kind, muted] = args;
+                    const mock = window.wsMock;
+                    const peers = new Map();
+                    for (const data of mock.received) {
+                        if (typeof data !== 'string') {
+                            continue;
+                        }
+                        const msg = JSON.parse(data);
+                        if (msg.msg !== 'Event'
+                            || msg.data.event.event !== 'PeerCreated') {
+                            continue;
+                        }
+                        const peer = msg.data.event.data;
+                        peers.set(peer.peer_id, {
+                            roomId: msg.data.room_id,
+                            tracks: peer.tracks
+                                .filter((t) => t.direction.Send !== undefined
+                                    && t.media_type[kind] !== undefined)
+                                .map((t) => t.id)
+                        });
+                    }
+                    for (const [peerId, peer] of peers) {
+                        const data = JSON.stringify({
+                            msg: 'Event',
+                            data: {
+                                room_id: peer.roomId,
+                                event: {
+                                    event: 'PeerUpdated',
+                                    data: {
+                                        peer_id: peerId,
+                                        updates: peer.tracks.map((id) => ({
+                                            Updated: { id: id, muted: muted }
+                                        })),
+                                        negotiation_role: null
+                                    }
+                                }
+                            }
+                        });
+                        for (const socket of mock.allSockets) {
+                            if (socket.readyState === WebSocket.OPEN) {
+                                socket.dispatchEvent(
+                                    new MessageEvent('message', { data: data })
+                                );
+                            }
+                        }
+                    }
+                }
+                ",
+                [kind.into(), muted.into()],
+            ))
+            .await
+            .map(drop)
+            .unwrap();
+    }
+
+    /// Returns count of the sent messages containing a `Command` with the
+    /// provided `name` (`UpdateTracks`, `MakeSdpOffer`, etc).
+    ///
+    /// Only the messages sent after the last
+    /// [`WebSocket::clear_sent()`] call are counted.
+    ///
+    /// # Panics
+    ///
+    /// If failed to execute JS statement.
+    pub async fn sent_commands_count(&self, name: &str) -> u64 {
+        self.0
+            .execute(Statement::new(
+                // language=JavaScript
+                r#"
+                async () => {
+                    const [name] = args;
+                    return window.wsMock.sent
+                        .filter((msg) => typeof msg === 'string')
+                        .filter((msg) => msg.includes(`"${name}"`))
+                        .length;
+                }
+                "#,
+                [name.into()],
+            ))
+            .await
+            .unwrap()
+            .as_u64()
+            .unwrap()
+    }
 }
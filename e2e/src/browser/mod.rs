@@ -3,19 +3,30 @@
 //! [WebDriver]: https://w3.org/TR/webdriver
 
 mod client;
+pub mod console;
+pub mod helpers;
 mod js;
 pub mod mock;
 
-use std::sync::{
-    atomic::{AtomicUsize, Ordering},
-    Arc,
+use std::{
+    mem,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex, MutexGuard, PoisonError,
+    },
+    time::Duration,
 };
 
 use derive_more::{Display, Error, From};
-use fantoccini::wd::WindowHandle;
+use fantoccini::wd::{Capabilities, WindowHandle};
 use serde_json::Value as Json;
 
-pub use self::client::{WebDriverClient, WebDriverClientBuilder};
+pub use self::{
+    client::{
+        Browser, UnknownBrowserError, WebDriverClient, WebDriverClientBuilder,
+    },
+    console::{ConsoleLog, ConsoleLogLevel},
+};
 
 #[doc(inline)]
 pub use self::js::Statement;
@@ -41,6 +52,14 @@ pub enum Error {
     ///
     /// Should never happen.
     Deserialize(serde_json::Error),
+
+    /// Helpers installed via [`Window::install_helpers()`] are missing in a
+    /// browser window (e.g. because of a page navigation).
+    ///
+    /// Handled by [`Window::execute()`] by re-installing them.
+    #[display(fmt = "JS helpers are missing in the browser window")]
+    #[from(ignore)]
+    HelpersMissing,
 }
 
 /// Shortcut for a [`Result`] with an [`Error`](enum@Error) inside.
@@ -72,6 +91,11 @@ pub struct Window {
     ///
     /// Used in a [`Drop`] implementation of this [`Window`].
     rc: Arc<AtomicUsize>,
+
+    /// JS code of the helpers installed via [`Window::install_helpers()`].
+    ///
+    /// Kept to re-install them once they're lost by a browser window.
+    helpers: Arc<Mutex<Vec<String>>>,
 }
 
 impl Clone for Window {
@@ -81,6 +105,7 @@ impl Clone for Window {
             client: self.client.clone(),
             window: self.window.clone(),
             rc: Arc::clone(&self.rc),
+            helpers: Arc::clone(&self.helpers),
         }
     }
 }
@@ -102,49 +127,208 @@ impl Window {
             client,
             window,
             rc: Arc::new(AtomicUsize::new(1)),
+            helpers: Arc::default(),
         };
+        this.install_helpers(helpers::JS).await.unwrap();
         mock::instantiate_mocks(&this).await;
         this
     }
 
+    /// Returns default maximum duration of a [`Statement`] execution in this
+    /// [`Window`].
+    #[must_use]
+    pub const fn execute_timeout(&self) -> Duration {
+        self.client.execute_timeout()
+    }
+
+    /// Returns [`Browser`] this [`Window`] was explicitly requested to be
+    /// opened in.
+    #[must_use]
+    pub const fn browser(&self) -> Option<Browser> {
+        self.client.browser()
+    }
+
     /// Executes the provided [`Statement`] in this [`Window`].
     ///
+    /// If the provided [`Statement`] [requires helpers][1] which are missing in
+    /// this [`Window`], then re-installs them lazily and executes the
+    /// [`Statement`] again.
+    ///
     /// # Errors
     ///
     /// - If failed to switch browser to this [`Window`].
     /// - If failed to execute JS statement.
+    ///
+    /// [1]: Statement::requires_helpers
     pub async fn execute(&self, exec: Statement) -> Result<Json> {
+        if !exec.requires_helpers() {
+            return self.execute_once(exec).await;
+        }
+
+        match self.execute_once(exec.clone()).await {
+            Err(Error::HelpersMissing) => {
+                self.reinstall_helpers().await?;
+                self.execute_once(exec).await
+            }
+            res => res,
+        }
+    }
+
+    /// Installs the provided JS helpers into this [`Window`].
+    ///
+    /// The provided `js` should be a [`Statement`] expression defining the
+    /// helpers in the [`helpers::NAMESPACE`]. It's executed once, and then
+    /// executed again only if the helpers are lost by this [`Window`] (e.g.
+    /// because of a page navigation).
+    ///
+    /// # Errors
+    ///
+    /// If failed to execute the provided `js`.
+    pub async fn install_helpers(&self, js: &str) -> Result<()> {
+        self.execute_once(Statement::new(js, [])).await?;
+        self.lock_helpers().push(js.to_owned());
+        Ok(())
+    }
+
+    /// Re-installs all the helpers installed via
+    /// [`Window::install_helpers()`] into this [`Window`].
+    async fn reinstall_helpers(&self) -> Result<()> {
+        let helpers = self.lock_helpers().clone();
+        for js in helpers {
+            self.execute_once(Statement::new(&js, [])).await?;
+        }
+        Ok(())
+    }
+
+    /// Executes the provided [`Statement`] in this [`Window`] without
+    /// re-installing missing helpers.
+    async fn execute_once(&self, exec: Statement) -> Result<Json> {
         self.client
             .switch_to_window_and_execute(self.window.clone(), exec)
             .await
     }
+
+    /// Locks the JS code of the helpers installed into this [`Window`].
+    fn lock_helpers(&self) -> MutexGuard<'_, Vec<String>> {
+        self.helpers.lock().unwrap_or_else(PoisonError::into_inner)
+    }
 }
 
 /// Root [WebDriver] client for some browser.
 ///
 /// This client can create new [`Window`]s.
 ///
-/// [WebDriver] session will be closed on this object's [`Drop`].
+/// [WebDriver] session will be closed on this object's [`Drop`], unless this
+/// [`WindowFactory`] was taken from a [`SessionPool`], in which case the
+/// session is returned back to that [`SessionPool`].
 ///
 /// [WebDriver]: https://w3.org/TR/webdriver
-#[derive(Debug, From)]
-pub struct WindowFactory(WebDriverClient);
+#[derive(Debug)]
+pub struct WindowFactory {
+    /// [WebDriver] client of this [`WindowFactory`].
+    ///
+    /// [WebDriver]: https://w3.org/TR/webdriver
+    client: WebDriverClient,
+
+    /// [`SessionPool`] to return the [`WindowFactory::client`] into on
+    /// [`Drop`].
+    pool: Option<SessionPool>,
+}
+
+impl From<WebDriverClient> for WindowFactory {
+    fn from(client: WebDriverClient) -> Self {
+        Self::new(client)
+    }
+}
 
 impl WindowFactory {
     /// Returns a new [`WindowFactory`] from [`WebDriverClient`].
     #[must_use]
     pub const fn new(client: WebDriverClient) -> Self {
-        Self(client)
+        Self { client, pool: None }
     }
 
     /// Creates and returns a new [`Window`].
     pub async fn new_window(&self) -> Window {
-        Window::new(self.0.clone()).await
+        Window::new(self.client.clone()).await
     }
 }
 
 impl Drop for WindowFactory {
     fn drop(&mut self) {
-        self.0.blocking_close();
+        if let Some(pool) = self.pool.take() {
+            pool.put(self.client.clone());
+        } else {
+            self.client.blocking_close();
+        }
+    }
+}
+
+/// Pool of [WebDriver] sessions reused by [`WindowFactory`]s.
+///
+/// Allows to avoid creating a new [WebDriver] session for every
+/// [`WindowFactory`], which is quite slow, while still running every
+/// [`WindowFactory`] in a dedicated session, so they can be used concurrently.
+///
+/// [WebDriver]: https://w3.org/TR/webdriver
+#[derive(Clone, Debug, Default)]
+pub struct SessionPool(Arc<Mutex<Vec<WebDriverClient>>>);
+
+impl SessionPool {
+    /// Creates a new empty [`SessionPool`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a [`WindowFactory`] running in an idle [WebDriver] session of
+    /// this [`SessionPool`], or in a new one connected via the provided
+    /// [`WebDriverClientBuilder`] if there are no idle sessions.
+    ///
+    /// The session is returned back to this [`SessionPool`] once the returned
+    /// [`WindowFactory`] is [`Drop`]ped.
+    ///
+    /// # Errors
+    ///
+    /// If failed to connect a new [WebDriver] session.
+    ///
+    /// [WebDriver]: https://w3.org/TR/webdriver
+    pub async fn take<Caps: Into<Capabilities>>(
+        &self,
+        builder: WebDriverClientBuilder<'_, Caps>,
+        file_server_host: &str,
+    ) -> Result<WindowFactory> {
+        let idle = self.lock().pop();
+        let client = if let Some(client) = idle {
+            client
+        } else {
+            builder.connect(file_server_host).await?
+        };
+        Ok(WindowFactory {
+            client,
+            pool: Some(self.clone()),
+        })
+    }
+
+    /// Synchronously closes all the idle [WebDriver] sessions of this
+    /// [`SessionPool`].
+    ///
+    /// [WebDriver]: https://w3.org/TR/webdriver
+    pub fn blocking_close_all(&self) {
+        let idle = mem::take(&mut *self.lock());
+        for client in idle {
+            client.blocking_close();
+        }
+    }
+
+    /// Returns the provided idle [`WebDriverClient`] into this
+    /// [`SessionPool`].
+    fn put(&self, client: WebDriverClient) {
+        self.lock().push(client);
+    }
+
+    /// Locks the idle [`WebDriverClient`]s of this [`SessionPool`].
+    fn lock(&self) -> MutexGuard<'_, Vec<WebDriverClient>> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
     }
 }
@@ -1,8 +1,12 @@
 //! `Connection` JS object's representation.
 
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
 use crate::{
-    browser::Statement,
-    object::{tracks_store, MediaKind, Object},
+    browser::{self, Statement},
+    object::{tracks_store, MediaKind, MediaSourceKind, Object},
 };
 
 use super::Error;
@@ -11,6 +15,26 @@ use super::Error;
 #[derive(Clone, Copy, Debug)]
 pub struct Connection;
 
+/// Directions of the media exchange with a remote `Member`, as returned by the
+/// `Connection.media_exchange_state()`.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MediaExchangeDirections {
+    /// Indicator whether media is intended to be sent to the remote `Member`.
+    pub we_send: bool,
+
+    /// Indicator whether the remote `Member` has receiving of the sent media
+    /// enabled.
+    pub they_receive: bool,
+
+    /// Indicator whether the remote `Member` sends media.
+    pub they_send: bool,
+
+    /// Indicator whether media is intended to be received from the remote
+    /// `Member`.
+    pub we_receive: bool,
+}
+
 impl Object<Connection> {
     /// Returns a [`tracks_store::Remote`] of this [`Connection`].
     ///
@@ -28,6 +52,42 @@ impl Object<Connection> {
         .await
     }
 
+    /// Replaces the `ConnectionHandle` of this [`Connection`] with its clone,
+    /// freeing the original one and forcing a garbage collection (if exposed
+    /// via `window.gc`).
+    ///
+    /// Returns `true` if calling a method of the freed `ConnectionHandle`
+    /// throws an error.
+    ///
+    /// # Errors
+    ///
+    /// If failed to execute JS statement.
+    pub async fn free_and_replace_handle(&self) -> Result<bool, Error> {
+        self.execute(Statement::new(
+            // language=JavaScript
+            "
+            async (c) => {
+                const freed = c.conn;
+                c.conn = freed.clone_handle();
+                freed.free();
+                if (window.gc !== undefined) {
+                    window.gc();
+                }
+                try {
+                    freed.get_remote_member_id();
+                    return false;
+                } catch (e) {
+                    return true;
+                }
+            }
+            ",
+            [],
+        ))
+        .await?
+        .as_bool()
+        .ok_or(Error::TypeCast)
+    }
+
     /// Enables remote media receiving for the provided [`MediaKind`].
     ///
     /// # Errors
@@ -84,6 +144,210 @@ impl Object<Connection> {
         .map(drop)
     }
 
+    /// Enables or disables video receiving via the
+    /// `Connection.set_receive_video()`.
+    ///
+    /// # Errors
+    ///
+    /// If failed to execute JS statement.
+    pub async fn set_receive_video(&self, enabled: bool) -> Result<(), Error> {
+        self.execute(Statement::new(
+            // language=JavaScript
+            r#"
+                async (c) => {
+                    const [enabled] = args;
+                    await c.conn.set_receive_video(enabled);
+                }
+            "#,
+            [enabled.into()],
+        ))
+        .await
+        .map(drop)
+    }
+
+    /// Waits for this [`Connection`] to be connected and returns milliseconds
+    /// spent on every its setup phase (or [`None`] for the phases didn't
+    /// happen on this side).
+    ///
+    /// # Errors
+    ///
+    /// - If failed to execute JS statement.
+    /// - If failed to deserialize the returned timings.
+    pub async fn wait_for_setup_timings(
+        &self,
+    ) -> Result<HashMap<String, Option<f64>>, Error> {
+        let timings = self
+            .execute(Statement::new(
+                // language=JavaScript
+                "
+                async (c) => {
+                    for (;;) {
+                        const timings = c.conn.setup_timings();
+                        if (timings !== null) {
+                            return timings;
+                        }
+                        await new Promise((r) => setTimeout(r, 100));
+                    }
+                }
+                ",
+                [],
+            ))
+            .await?;
+        Ok(serde_json::from_value(timings).map_err(browser::Error::from)?)
+    }
+
+    /// Returns numbers of bytes transferred via this [`Connection`] (as
+    /// returned by the `Connection.traffic_totals()`) by their names.
+    ///
+    /// # Errors
+    ///
+    /// - If failed to execute JS statement.
+    /// - If failed to deserialize the returned totals.
+    pub async fn traffic_totals(&self) -> Result<HashMap<String, u64>, Error> {
+        let totals = self
+            .execute(Statement::new(
+                // language=JavaScript
+                "
+                async (c) => {
+                    const totals = c.conn.traffic_totals();
+                    const res = {
+                        bytes_sent: Number(totals.bytes_sent()),
+                        bytes_received: Number(totals.bytes_received()),
+                        audio_bytes: Number(totals.audio_bytes()),
+                        video_bytes: Number(totals.video_bytes()),
+                    };
+                    totals.free();
+                    return res;
+                }
+                ",
+                [],
+            ))
+            .await?;
+        Ok(serde_json::from_value(totals).map_err(browser::Error::from)?)
+    }
+
+    /// Returns number of SDP negotiations performed by this [`Connection`] (as
+    /// returned by the `Connection.negotiation_stats()`).
+    ///
+    /// # Errors
+    ///
+    /// - If failed to execute JS statement.
+    /// - If failed to parse the returned count.
+    pub async fn negotiations_count(&self) -> Result<u64, Error> {
+        let count = self
+            .execute(Statement::new(
+                // language=JavaScript
+                "
+                async (c) => {
+                    const stats = c.conn.negotiation_stats();
+                    const count = stats.count();
+                    stats.free();
+                    return count;
+                }
+                ",
+                [],
+            ))
+            .await?;
+        count.as_u64().ok_or(Error::TypeCast)
+    }
+
+    /// Returns the current [`MediaExchangeDirections`] of the provided
+    /// [`MediaKind`] and [`MediaSourceKind`] in this [`Connection`].
+    ///
+    /// # Errors
+    ///
+    /// - If failed to execute JS statement.
+    /// - If failed to deserialize the returned [`MediaExchangeDirections`].
+    pub async fn media_exchange_state(
+        &self,
+        kind: MediaKind,
+        source_kind: MediaSourceKind,
+    ) -> Result<MediaExchangeDirections, Error> {
+        let state = self
+            .execute(Statement::new(
+                // language=JavaScript
+                &format!(
+                    r#"
+                    async (c) => {{
+                        const s = c.conn.media_exchange_state(
+                            {kind}, {source_kind}
+                        );
+                        const state = {{
+                            weSend: s.we_send(),
+                            theyReceive: s.they_receive(),
+                            theySend: s.they_send(),
+                            weReceive: s.we_receive()
+                        }};
+                        s.free();
+                        return state;
+                    }}
+                    "#,
+                    kind = kind.as_js(),
+                    source_kind = source_kind.as_js(),
+                ),
+                [],
+            ))
+            .await?;
+        Ok(serde_json::from_value(state).map_err(browser::Error::from)?)
+    }
+
+    /// Returns number of the tracks reported by the
+    /// `Connection.on_remote_track_removed()` callback so far.
+    ///
+    /// # Errors
+    ///
+    /// If failed to execute JS statement.
+    pub async fn removed_tracks_count(&self) -> Result<u64, Error> {
+        self.execute(Statement::new(
+            // language=JavaScript
+            "async (conn) => conn.removedTracksStore.tracks.length",
+            [],
+        ))
+        .await?
+        .as_u64()
+        .ok_or(Error::TypeCast)
+    }
+
+    /// Returns a [`Future`] resolving when the
+    /// `Connection.on_remote_track_removed()` callback reports the provided
+    /// number of tracks.
+    ///
+    /// # Errors
+    ///
+    /// If failed to execute JS statement.
+    ///
+    /// [`Future`]: std::future::Future
+    pub async fn wait_for_removed_count(
+        &self,
+        count: u64,
+    ) -> Result<(), Error> {
+        self.execute(Statement::new(
+            // language=JavaScript
+            "
+            async (conn) => {
+                const [count] = args;
+                const store = conn.removedTracksStore;
+                await new Promise((resolve) => {
+                    if (store.tracks.length >= count) {
+                        resolve();
+                    } else {
+                        store.subs.push(() => {
+                            if (store.tracks.length >= count) {
+                                resolve();
+                                return false;
+                            }
+                            return true;
+                        });
+                    }
+                });
+            }
+            ",
+            [count.into()],
+        ))
+        .await
+        .map(drop)
+    }
+
     /// Returns a [`Future`] resolving when `Connection.on_close()` callback is
     /// fired.
     ///
@@ -1,6 +1,8 @@
 //! [`Object`] storing all the [`Connection`]s thrown by
 //! `Room.on_new_connection()` callback.
 
+use std::time::Duration;
+
 use crate::{
     browser::Statement,
     object::{connection::Connection, Error, Object},
@@ -43,29 +45,146 @@ impl Object<ConnectionStore> {
     ///
     /// # Errors
     ///
-    /// If failed to execute JS statement.
+    /// - If failed to execute JS statement.
+    /// - If the [`Connection`] doesn't appear in the provided `timeout`.
     pub async fn wait_for_connection(
         &self,
         remote_id: String,
+        timeout: Duration,
     ) -> Result<Object<Connection>, Error> {
-        self.execute_and_fetch(Statement::new(
+        self.execute_and_fetch_with_timeout(
+            Statement::new(
+                // language=JavaScript
+                "
+                async (store) => {
+                    const [remoteId] = args;
+                    let conn = store.connections.get(remoteId);
+                    if (conn !== undefined) {
+                        return conn;
+                    } else {
+                        let waiter = new Promise((resolve) => {
+                            store.subs.set(remoteId, resolve);
+                        });
+                        return await waiter;
+                    }
+                }
+                ",
+                [remote_id.into()],
+            ),
+            timeout,
+        )
+        .await
+    }
+
+    /// Returns count of the currently open [`Connection`]s in this
+    /// [`ConnectionStore`].
+    ///
+    /// # Errors
+    ///
+    /// - If failed to execute JS statement.
+    /// - If failed to deserialize result as [`u64`].
+    pub async fn count(&self) -> Result<u64, Error> {
+        self.execute_json(Statement::new(
+            // language=JavaScript
+            "async (store) => store.connections.size",
+            [],
+        ))
+        .await
+    }
+
+    /// Indicates whether the open [`Connection`]s in this [`ConnectionStore`]
+    /// are the same ones enumerated by the `RoomHandle.connections()` and
+    /// looked up by the `RoomHandle.connection()`.
+    ///
+    /// Frees all the `ConnectionHandle`s returned by the `RoomHandle`.
+    ///
+    /// # Errors
+    ///
+    /// - If failed to execute JS statement.
+    /// - If failed to deserialize result as [`bool`].
+    pub async fn matches_room_connections(&self) -> Result<bool, Error> {
+        self.execute_json(Statement::new(
+            // language=JavaScript
+            "
+            async (store) => {
+                const handles = store.room.connections();
+                const ids = handles.map((h) => h.get_remote_member_id());
+                handles.forEach((h) => h.free());
+                if (ids.length !== store.connections.size) {
+                    return false;
+                }
+                for (const id of ids) {
+                    const handle = store.room.connection(id);
+                    if (handle === undefined || handle === null) {
+                        return false;
+                    }
+                    handle.free();
+                    if (!store.connections.has(id)) {
+                        return false;
+                    }
+                }
+                return true;
+            }
+            ",
+            [],
+        ))
+        .await
+    }
+
+    /// Waits this [`ConnectionStore`] to contain `count` open [`Connection`]s.
+    ///
+    /// # Errors
+    ///
+    /// If failed to execute JS statement.
+    pub async fn wait_for_connection_count(
+        &self,
+        count: u64,
+    ) -> Result<(), Error> {
+        self.execute(Statement::new(
+            // language=JavaScript
+            "
+            async (store) => {
+                const [neededCount] = args;
+                await window.__medea_e2e.waitUntil(
+                    [store.countSubs],
+                    () => store.connections.size === neededCount
+                );
+            }
+            ",
+            [count.into()],
+        ))
+        .await
+        .map(drop)
+    }
+
+    /// Waits for a [`Connection`] with the provided remote member to be
+    /// closed.
+    ///
+    /// Resolves instantly if such [`Connection`] has been closed already.
+    ///
+    /// # Errors
+    ///
+    /// If failed to execute JS statement.
+    pub async fn wait_for_close(&self, remote_id: String) -> Result<(), Error> {
+        self.execute(Statement::new(
             // language=JavaScript
             "
             async (store) => {
                 const [remoteId] = args;
-                let conn = store.connections.get(remoteId);
-                if (conn !== undefined) {
-                    return conn;
-                } else {
-                    let waiter = new Promise((resolve) => {
-                        store.subs.set(remoteId, resolve);
-                    });
-                    return await waiter;
+                if (store.closedConnections.has(remoteId)
+                    && !store.connections.has(remoteId)) {
+                    return;
                 }
+                await new Promise((resolve) => {
+                    window.__medea_e2e.subscribe(
+                        store.closeSubs, remoteId, resolve
+                    );
+                });
             }
             ",
             [remote_id.into()],
         ))
         .await
+        .map(drop)
     }
 }
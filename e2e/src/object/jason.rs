@@ -1,8 +1,13 @@
 //! `Jason` JS object's representation.
 
+use serde::Deserialize;
+
 use crate::{
-    browser::Statement,
-    object::{room::Room, Builder, Object},
+    browser::{self, Statement},
+    object::{
+        room::{MediaKind, Room},
+        Builder, Object,
+    },
 };
 
 use super::Error;
@@ -11,6 +16,76 @@ use super::Error;
 #[derive(Clone, Copy, Debug)]
 pub struct Jason;
 
+/// Summary of probing ICE servers via a `Jason.diagnostics()`.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IceProbeReport {
+    /// Number of distinct gathered `host` candidates.
+    pub host_candidates: u64,
+
+    /// Number of distinct gathered `srflx` candidates.
+    pub srflx_candidates: u64,
+
+    /// Number of distinct gathered `relay` candidates.
+    pub relay_candidates: u64,
+
+    /// Number of distinct gathered candidates filtered out.
+    pub filtered_candidates: u64,
+
+    /// Results of probing every ICE server URL.
+    pub servers: Vec<IceServerProbe>,
+}
+
+/// Result of probing a single ICE server URL.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IceServerProbe {
+    /// Probed URL.
+    pub url: String,
+
+    /// Indicator whether the probed URL is reachable.
+    pub is_reachable: bool,
+
+    /// Error occurred while probing the URL, if any.
+    pub error: Option<String>,
+}
+
+/// Report of a `Jason.is_supported()` about WebRTC functionality available in
+/// a browser.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WebRtcSupport {
+    /// Indicator whether a `RTCPeerConnection` can be created.
+    pub peer_connection: bool,
+
+    /// Indicator whether a `getUserMedia()` request can be made.
+    pub get_user_media: bool,
+
+    /// Indicator whether a `getDisplayMedia()` request can be made.
+    pub get_display_media: bool,
+
+    /// Indicator whether insertable streams of encoded media are available.
+    pub insertable_streams: bool,
+}
+
+/// Capabilities of a camera reported by a
+/// `MediaManagerHandle.device_capabilities()`.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CameraCapabilities {
+    /// Supported `[min, max]` range of a video width, if reported.
+    pub width_range: Option<[f64; 2]>,
+
+    /// Supported `[min, max]` range of a video height, if reported.
+    pub height_range: Option<[f64; 2]>,
+
+    /// Supported `[min, max]` range of a video frame rate, if reported.
+    pub frame_rate_range: Option<[f64; 2]>,
+
+    /// Names of the supported `FacingMode`s.
+    pub facing_modes: Vec<String>,
+}
+
 impl Builder for Jason {
     fn build(self) -> Statement {
         Statement::new(
@@ -28,29 +103,69 @@ impl Object<Jason> {
     ///
     /// If failed to execute JS statement.
     pub async fn init_room(&self) -> Result<Object<Room>, Error> {
+        self.new_room(None).await
+    }
+
+    /// Returns a new [`Room`] restored in this [`Jason`] [`Object`] from the
+    /// provided snapshot exported via [`Object::<Room>::export_state()`].
+    ///
+    /// # Errors
+    ///
+    /// If failed to execute JS statement.
+    pub async fn restore_room(
+        &self,
+        snapshot: &str,
+    ) -> Result<Object<Room>, Error> {
+        self.new_room(Some(snapshot)).await
+    }
+
+    /// Returns a new [`Room`] initiated in this [`Jason`] [`Object`], restoring
+    /// it from the provided snapshot, if any.
+    async fn new_room(
+        &self,
+        snapshot: Option<&str>,
+    ) -> Result<Object<Room>, Error> {
         self.execute_and_fetch(Statement::new(
             // language=JavaScript
             "
             async (jason) => {
-                let room = await jason.init_room();
+                const [snapshot] = args;
+                let room = snapshot === null ?
+                    await jason.init_room() :
+                    jason.restore_room(snapshot);
                 let onFailedLocalStreamListener = {
                     subs: [],
-                    count: 0
+                    count: 0,
+                    errors: []
                 };
-                room.on_failed_local_media(() => {
+                room.on_failed_local_media((err) => {
                     onFailedLocalStreamListener.count++;
+                    onFailedLocalStreamListener.errors.push(
+                        err.constructor.name
+                    );
                     onFailedLocalStreamListener.subs =
                         onFailedLocalStreamListener.subs
                             .filter((sub) => sub());
                 });
                 let connLossListener = {
+                    count: 0,
                     isLost: false,
                     reconnectHandle: null,
-                    subs: []
+                    firstReconnectHandle: null,
+                    lostAt: null,
+                    lastReconnectMs: null,
+                    subs: [],
+                    reconnectSubs: []
                 };
                 room.on_connection_loss(async (recon) => {
+                    connLossListener.count++;
                     connLossListener.isLost = true;
                     connLossListener.reconnectHandle = recon;
+                    if (connLossListener.firstReconnectHandle === null) {
+                        connLossListener.firstReconnectHandle = recon;
+                    }
+                    connLossListener.lostAt = performance.now();
+                    connLossListener.lastReconnectMs = null;
                     for (sub of connLossListener.subs) {
                         sub();
                     }
@@ -61,9 +176,23 @@ impl Object<Jason> {
                     isClosed: false,
                     subs: []
                 };
+                let adaptationListener = {
+                    adaptations: [],
+                    subs: []
+                };
+                room.on_adaptation((a) => {
+                    adaptationListener.adaptations.push({
+                        maxBitrate: a.max_bitrate() ?? null,
+                        isVideoPaused: a.is_video_paused()
+                    });
+                    adaptationListener.subs = adaptationListener.subs
+                        .filter((sub) => sub());
+                });
                 let localTracksStore = {
                     tracks: [],
-                    subs: []
+                    subs: [],
+                    stoppedSubs: [],
+                    counters: new Map()
                 };
                 room.on_close((reason) => {
                     closeListener.closeReason = reason;
@@ -73,7 +202,20 @@ impl Object<Jason> {
                     }
                 });
                 room.on_local_track((t) => {
-                    let track = { track: t };
+                    let key = t.kind() + '/' + t.media_source_kind();
+                    let counter = localTracksStore.counters.get(key);
+                    if (counter === undefined) {
+                        counter = { created: 0 };
+                        localTracksStore.counters.set(key, counter);
+                    }
+                    counter.created++;
+                    let track = { track: t, stopped: false, counter: counter };
+                    t.get_track().addEventListener('ended', () => {
+                        track.stopped = true;
+                        localTracksStore.stoppedSubs = localTracksStore
+                            .stoppedSubs
+                            .filter((sub) => sub(track));
+                    });
                     localTracksStore.tracks.push(track);
                     let newSubs = localTracksStore.subs
                         .filter((sub) => sub(track));
@@ -93,11 +235,12 @@ impl Object<Jason> {
                     closeListener: closeListener,
                     localTracksStore: localTracksStore,
                     connLossListener: connLossListener,
+                    adaptationListener: adaptationListener,
                     onFailedLocalStreamListener: onFailedLocalStreamListener
                 };
             }
             ",
-            [],
+            [snapshot.into()],
         ))
         .await
     }
@@ -123,6 +266,220 @@ impl Object<Jason> {
         .map(drop)
     }
 
+    /// Probes the ICE server with the provided `url` and credentials via
+    /// `Jason.diagnostics()`, waiting for candidates no longer than the
+    /// provided `timeout_ms`.
+    ///
+    /// If `relay_only` is `true`, then all the non-relay candidates are
+    /// filtered out with an `IceCandidateFilter.relay_only()`.
+    ///
+    /// # Errors
+    ///
+    /// - If failed to execute JS statement.
+    /// - If failed to deserialize the returned [`IceProbeReport`].
+    pub async fn probe_ice_server(
+        &self,
+        url: &str,
+        username: &str,
+        credential: &str,
+        timeout_ms: u64,
+        relay_only: bool,
+    ) -> Result<IceProbeReport, Error> {
+        let report = self
+            .execute(Statement::new(
+                // language=JavaScript
+                "
+                async (jason) => {
+                    const [
+                        url, username, credential, timeoutMs, relayOnly
+                    ] = args;
+                    const diagnostics = jason.diagnostics();
+                    if (relayOnly) {
+                        diagnostics.set_ice_candidate_filter(
+                            window.rust.IceCandidateFilter.relay_only()
+                        );
+                    }
+                    const report = await diagnostics.probe_ice_servers(
+                        [{
+                            urls: url,
+                            username: username,
+                            credential: credential
+                        }],
+                        timeoutMs
+                    );
+                    return {
+                        hostCandidates: report.host_candidates(),
+                        srflxCandidates: report.srflx_candidates(),
+                        relayCandidates: report.relay_candidates(),
+                        filteredCandidates: report.filtered_candidates(),
+                        servers: report.servers().map((s) => ({
+                            url: s.url(),
+                            isReachable: s.is_reachable(),
+                            error: s.error() ?? null
+                        }))
+                    };
+                }
+                ",
+                [
+                    url.into(),
+                    username.into(),
+                    credential.into(),
+                    timeout_ms.into(),
+                    relay_only.into(),
+                ],
+            ))
+            .await?;
+        Ok(serde_json::from_value(report).map_err(browser::Error::from)?)
+    }
+
+    /// Returns [`WebRtcSupport`] reported by a `Jason.is_supported()`.
+    ///
+    /// # Errors
+    ///
+    /// - If failed to execute JS statement.
+    /// - If failed to deserialize the returned [`WebRtcSupport`].
+    pub async fn webrtc_support(&self) -> Result<WebRtcSupport, Error> {
+        let support = self
+            .execute(Statement::new(
+                // language=JavaScript
+                "
+                async (jason) => {
+                    const support = window.rust.Jason.is_supported();
+                    return {
+                        peerConnection: support.peer_connection(),
+                        getUserMedia: support.get_user_media(),
+                        getDisplayMedia: support.get_display_media(),
+                        insertableStreams: support.insertable_streams()
+                    };
+                }
+                ",
+                [],
+            ))
+            .await?;
+        Ok(serde_json::from_value(support).map_err(browser::Error::from)?)
+    }
+
+    /// Indicates whether the `MediaManagerHandle` of this [`Jason`] reports at
+    /// least one input device of the provided [`MediaKind`].
+    ///
+    /// # Errors
+    ///
+    /// If failed to execute JS statement.
+    pub async fn has_input_device(
+        &self,
+        kind: MediaKind,
+    ) -> Result<bool, Error> {
+        let kind = match kind {
+            MediaKind::Audio => "AudioInput",
+            MediaKind::Video => "VideoInput",
+        };
+        let has = self
+            .execute(Statement::new(
+                // language=JavaScript
+                "
+                async (jason) => {
+                    const [kind] = args;
+                    return await jason.media_manager().has_device(
+                        window.rust.MediaDeviceKind[kind]
+                    );
+                }
+                ",
+                [kind.into()],
+            ))
+            .await?;
+        Ok(serde_json::from_value(has).map_err(browser::Error::from)?)
+    }
+
+    /// Returns [`CameraCapabilities`] of the first camera reported by the
+    /// `MediaManagerHandle` of this [`Jason`].
+    ///
+    /// # Errors
+    ///
+    /// - If failed to execute JS statement.
+    /// - If failed to deserialize the returned [`CameraCapabilities`].
+    pub async fn camera_capabilities(
+        &self,
+    ) -> Result<CameraCapabilities, Error> {
+        let caps = self
+            .execute(Statement::new(
+                // language=JavaScript
+                "
+                async (jason) => {
+                    const manager = jason.media_manager();
+                    const devices = await manager.enumerate_devices();
+                    const camera = devices.find((d) => {
+                        return d.kind() === window.rust.MediaDeviceKind
+                            .VideoInput;
+                    });
+                    const caps = await manager.device_capabilities(
+                        camera.device_id()
+                    );
+                    const range = (r) => r ? [r.min(), r.max()] : null;
+                    return {
+                        widthRange: range(caps.width_range()),
+                        heightRange: range(caps.height_range()),
+                        frameRateRange: range(caps.frame_rate_range()),
+                        facingModes: caps.facing_modes().map((m) => {
+                            return window.rust.FacingMode[m];
+                        })
+                    };
+                }
+                ",
+                [],
+            ))
+            .await?;
+        Ok(serde_json::from_value(caps).map_err(browser::Error::from)?)
+    }
+
+    /// Obtains local audio and device video tracks for a preview via
+    /// `MediaManagerHandle.init_local_tracks()`, keeping them alive till the
+    /// page is closed.
+    ///
+    /// # Errors
+    ///
+    /// If failed to execute JS statement.
+    pub async fn init_preview_tracks(&self) -> Result<(), Error> {
+        self.execute(Statement::new(
+            // language=JavaScript
+            "
+            async (jason) => {
+                let constraints = new rust.MediaStreamSettings();
+                constraints.audio(new window.rust.AudioTrackConstraints());
+                constraints.device_video(
+                    new window.rust.DeviceVideoTrackConstraints()
+                );
+                window.previewTracks = await jason.media_manager()
+                    .init_local_tracks(constraints);
+            }
+            ",
+            [],
+        ))
+        .await
+        .map(drop)
+    }
+
+    /// Returns `LocalMediaTrack.is_published()` of every track obtained with
+    /// a [`Jason::init_preview_tracks()`].
+    ///
+    /// # Errors
+    ///
+    /// If failed to execute JS statement.
+    pub async fn preview_tracks_published(&self) -> Result<Vec<bool>, Error> {
+        let published = self
+            .execute(Statement::new(
+                // language=JavaScript
+                "
+                async (jason) => {
+                    return (window.previewTracks || [])
+                        .map((t) => t.is_published());
+                }
+                ",
+                [],
+            ))
+            .await?;
+        Ok(serde_json::from_value(published).map_err(browser::Error::from)?)
+    }
+
     /// Drops [`Jason`] API object, so all the related objects (rooms,
     /// connections, streams, etc.) respectively.
     ///
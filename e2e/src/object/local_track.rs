@@ -1,9 +1,25 @@
 //! `LocalMediaTrack` JS object's representation.
 
-use crate::{browser::Statement, object::Object};
+use crate::{
+    browser::{self, Statement},
+    object::Object,
+};
 
 use super::Error;
 
+/// JS function checking whether the underlying `MediaStreamTrack` of the
+/// provided `LocalMediaTrack` wrapper is sent by any of the
+/// `RTCPeerConnection`s.
+// language=JavaScript
+const IS_SENT_JS: &str = r#"
+    (t) => {
+        const sysTrack = t.track.get_track();
+        return window.pcMock.connections
+            .filter((pc) => pc.signalingState !== "closed")
+            .some((pc) => pc.getSenders().some((s) => s.track === sysTrack));
+    }
+"#;
+
 /// Representation of a `LocalMediaTrack` object.
 #[derive(Clone, Copy, Debug)]
 pub struct LocalTrack;
@@ -50,4 +66,334 @@ impl Object<LocalTrack> {
         .as_bool()
         .ok_or(Error::TypeCast)
     }
+
+    /// Indicates whether this [`LocalTrack`] is enabled, meaning that its
+    /// underlying `MediaStreamTrack` is being sent by any of the
+    /// `RTCPeerConnection`s.
+    ///
+    /// # Errors
+    ///
+    /// If failed to execute JS statement.
+    pub async fn enabled(&self) -> Result<bool, Error> {
+        self.execute(Statement::new(
+            // language=JavaScript
+            &format!("async (t) => {{ return ({IS_SENT_JS})(t); }}"),
+            [],
+        ))
+        .await?
+        .as_bool()
+        .ok_or(Error::TypeCast)
+    }
+
+    /// Waits for this [`LocalTrack`] to become enabled.
+    ///
+    /// See [`Object::<LocalTrack>::enabled()`] for details.
+    ///
+    /// # Errors
+    ///
+    /// If failed to execute JS statement.
+    pub async fn wait_for_enabled(&self) -> Result<(), Error> {
+        self.wait_for_sent(true).await
+    }
+
+    /// Waits for this [`LocalTrack`] to become disabled.
+    ///
+    /// See [`Object::<LocalTrack>::enabled()`] for details.
+    ///
+    /// # Errors
+    ///
+    /// If failed to execute JS statement.
+    pub async fn wait_for_disabled(&self) -> Result<(), Error> {
+        self.wait_for_sent(false).await
+    }
+
+    /// Returns how many times a track with the same `MediaKind` and
+    /// `MediaSourceKind` as this [`LocalTrack`] was recreated by its `Room`.
+    ///
+    /// # Errors
+    ///
+    /// - If failed to execute JS statement.
+    /// - If failed to parse result as [`u64`].
+    pub async fn free_count(&self) -> Result<u64, Error> {
+        self.execute(Statement::new(
+            // language=JavaScript
+            "async (t) => t.counter.created - 1",
+            [],
+        ))
+        .await?
+        .as_u64()
+        .ok_or(Error::TypeCast)
+    }
+
+    /// Returns name of the `DisplaySurface` captured by this [`LocalTrack`], or
+    /// [`None`] if it isn't a display one.
+    ///
+    /// # Errors
+    ///
+    /// - If failed to execute JS statement.
+    /// - If failed to parse result as [`String`].
+    pub async fn display_surface(&self) -> Result<Option<String>, Error> {
+        let surface = self
+            .execute(Statement::new(
+                // language=JavaScript
+                "
+                async (t) => {
+                    const surface = t.track.display_surface();
+                    return surface === undefined ?
+                        null : window.rust.DisplaySurface[surface];
+                }
+                ",
+                [],
+            ))
+            .await?;
+        if surface.is_null() {
+            return Ok(None);
+        }
+        surface
+            .as_str()
+            .map(|s| Some(s.to_owned()))
+            .ok_or(Error::TypeCast)
+    }
+
+    /// Returns ID of the device this [`LocalTrack`] is captured from.
+    ///
+    /// # Errors
+    ///
+    /// - If failed to execute JS statement.
+    /// - If failed to parse result as [`String`].
+    pub async fn device_id(&self) -> Result<String, Error> {
+        self.execute(Statement::new(
+            // language=JavaScript
+            "async (t) => t.track.get_track().getSettings().deviceId",
+            [],
+        ))
+        .await?
+        .as_str()
+        .map(ToOwned::to_owned)
+        .ok_or(Error::TypeCast)
+    }
+
+    /// Returns the requested and the actual device IDs of the
+    /// `DeviceMismatch` reported by this [`LocalTrack`], if any.
+    ///
+    /// # Errors
+    ///
+    /// - If failed to execute JS statement.
+    /// - If failed to deserialize the result.
+    pub async fn device_mismatch(
+        &self,
+    ) -> Result<Option<(String, String)>, Error> {
+        let mismatch = self
+            .execute(Statement::new(
+                // language=JavaScript
+                "
+                async (t) => {
+                    const mismatch = t.track.device_mismatch();
+                    return mismatch === undefined ? null : [
+                        mismatch.requested_device_id(),
+                        mismatch.actual_device_id()
+                    ];
+                }
+                ",
+                [],
+            ))
+            .await?;
+        if mismatch.is_null() {
+            return Ok(None);
+        }
+        Ok(Some(
+            serde_json::from_value(mismatch).map_err(browser::Error::from)?,
+        ))
+    }
+
+    /// Requests this [`LocalTrack`] to be encoded as a new keyframe.
+    ///
+    /// # Errors
+    ///
+    /// If failed to execute JS statement.
+    pub async fn request_keyframe(&self) -> Result<(), Error> {
+        self.execute(Statement::new(
+            // language=JavaScript
+            "async (t) => await t.track.request_keyframe()",
+            [],
+        ))
+        .await
+        .map(drop)
+    }
+
+    /// Rotates this [`LocalTrack`] clockwise by the provided `rotation`
+    /// degrees and mirrors it if `mirror` is `true`, as it's being sent.
+    ///
+    /// # Errors
+    ///
+    /// If failed to execute JS statement.
+    pub async fn set_transform(
+        &self,
+        rotation: u16,
+        mirror: bool,
+    ) -> Result<(), Error> {
+        self.execute(Statement::new(
+            // language=JavaScript
+            r#"
+                async (t) => {
+                    const [rotation, mirror] = args;
+                    await t.track.set_transform(rotation, mirror);
+                }
+            "#,
+            [rotation.into(), mirror.into()],
+        ))
+        .await
+        .map(drop)
+    }
+
+    /// Returns total `keyFramesEncoded` of the `outbound-rtp` stats of all
+    /// the `RTCRtpSender`s sending this [`LocalTrack`].
+    ///
+    /// # Errors
+    ///
+    /// - If failed to execute JS statement.
+    /// - If failed to parse result as [`u64`].
+    pub async fn keyframes_encoded(&self) -> Result<u64, Error> {
+        self.execute(Statement::new(
+            // language=JavaScript
+            r#"
+            async (t) => {
+                const sysTrack = t.track.get_track();
+                const senders = window.pcMock.connections
+                    .filter((pc) => pc.signalingState !== "closed")
+                    .flatMap((pc) => pc.getSenders())
+                    .filter((s) => s.track === sysTrack);
+                let encoded = 0;
+                for (const sender of senders) {
+                    const stats = await sender.getStats();
+                    stats.forEach((s) => {
+                        if (s.type === "outbound-rtp") {
+                            encoded += s.keyFramesEncoded || 0;
+                        }
+                    });
+                }
+                return encoded;
+            }
+            "#,
+            [],
+        ))
+        .await?
+        .as_u64()
+        .ok_or(Error::TypeCast)
+    }
+
+    /// Enables silence detection of this [`LocalTrack`], recording all the
+    /// reported silence changes.
+    ///
+    /// # Errors
+    ///
+    /// If failed to execute JS statement.
+    pub async fn enable_silence_detection(
+        &self,
+        threshold_db: f64,
+        duration_ms: u32,
+    ) -> Result<(), Error> {
+        self.execute(Statement::new(
+            // language=JavaScript
+            "
+            async (t) => {
+                const [thresholdDb, durationMs] = args;
+                t.silenceChanges = [];
+                t.track.enable_silence_detection(
+                    thresholdDb,
+                    durationMs,
+                    (isSilent) => t.silenceChanges.push(isSilent)
+                );
+            }
+            ",
+            [threshold_db.into(), duration_ms.into()],
+        ))
+        .await
+        .map(drop)
+    }
+
+    /// Returns all the silence changes reported since the
+    /// [`Object::enable_silence_detection()`] call.
+    ///
+    /// # Errors
+    ///
+    /// - If failed to execute JS statement.
+    /// - If failed to deserialize the returned changes.
+    pub async fn silence_changes(&self) -> Result<Vec<bool>, Error> {
+        let changes = self
+            .execute(Statement::new(
+                // language=JavaScript
+                "async (t) => t.silenceChanges",
+                [],
+            ))
+            .await?;
+        Ok(serde_json::from_value(changes).map_err(browser::Error::from)?)
+    }
+
+    /// Starts recording all the media exchange changes (`enabled` or
+    /// `disabled`) reported by `on_enabled` and `on_disabled` callbacks of
+    /// this [`LocalTrack`].
+    ///
+    /// # Errors
+    ///
+    /// If failed to execute JS statement.
+    pub async fn record_media_exchange_changes(&self) -> Result<(), Error> {
+        self.execute(Statement::new(
+            // language=JavaScript
+            r#"
+            async (t) => {
+                t.mediaExchangeChanges = [];
+                t.track.on_enabled(
+                    () => t.mediaExchangeChanges.push("enabled")
+                );
+                t.track.on_disabled(
+                    () => t.mediaExchangeChanges.push("disabled")
+                );
+            }
+            "#,
+            [],
+        ))
+        .await
+        .map(drop)
+    }
+
+    /// Returns all the media exchange changes reported since the
+    /// [`Object::record_media_exchange_changes()`] call.
+    ///
+    /// # Errors
+    ///
+    /// - If failed to execute JS statement.
+    /// - If failed to deserialize the returned changes.
+    pub async fn media_exchange_changes(&self) -> Result<Vec<String>, Error> {
+        let changes = self
+            .execute(Statement::new(
+                // language=JavaScript
+                "async (t) => t.mediaExchangeChanges",
+                [],
+            ))
+            .await?;
+        Ok(serde_json::from_value(changes).map_err(browser::Error::from)?)
+    }
+
+    /// Waits for the underlying `MediaStreamTrack` of this [`LocalTrack`] to
+    /// become sent (or not sent) by any of the `RTCPeerConnection`s.
+    async fn wait_for_sent(&self, sent: bool) -> Result<(), Error> {
+        self.execute(Statement::new(
+            // language=JavaScript
+            &format!(
+                "
+                async (t) => {{
+                    const [sent] = args;
+                    const isSent = {IS_SENT_JS};
+                    while (isSent(t) !== sent) {{
+                        await new Promise((r) => setTimeout(r, 100));
+                    }}
+                }}
+                "
+            ),
+            [sent.into()],
+        ))
+        .await
+        .map(drop)
+    }
 }
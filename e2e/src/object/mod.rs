@@ -8,18 +8,25 @@ pub mod remote_track;
 pub mod room;
 pub mod tracks_store;
 
-use std::{marker::PhantomData, sync::mpsc};
+use std::{any, marker::PhantomData, sync::mpsc, time::Duration};
 
 use derive_more::{Display, Error, From};
+use serde::de::DeserializeOwned;
 use serde_json::Value as Json;
-use tokio::task;
+use tokio::{task, time};
 use uuid::Uuid;
 
 use crate::browser::{self, Statement};
 
 pub use self::{
-    jason::Jason,
-    room::{MediaKind, MediaSourceKind, Room},
+    jason::{
+        CameraCapabilities, IceProbeReport, IceServerProbe, Jason,
+        WebRtcSupport,
+    },
+    room::{
+        AudioTrackSettings, DeviceVideoTrackSettings, JoinError, LateCallbacks,
+        LocalMediaSettings, MediaKind, MediaSourceKind, Room,
+    },
 };
 
 /// All errors which can happen while working with [`Object`]s.
@@ -30,6 +37,68 @@ pub enum Error {
 
     /// Failed JS object type casting.
     TypeCast,
+
+    /// JS statement didn't complete in time.
+    Timeout(TimeoutError),
+
+    /// JS statement returned a value of unexpected shape.
+    Deserialize(DeserializeError),
+
+    /// `Room.join()` was rejected.
+    Join(room::JoinError),
+}
+
+/// Error of a JS statement not completing in time.
+#[derive(Clone, Debug, Display, Error)]
+#[display(
+    fmt = "`{object}` statement didn't complete in {timeout:?}:\n{statement}"
+)]
+pub struct TimeoutError {
+    /// Type name of the [`Object`] the statement was executed on.
+    pub object: &'static str,
+
+    /// Source code of the timed out statement.
+    pub statement: String,
+
+    /// Duration the statement was awaited for.
+    pub timeout: Duration,
+}
+
+/// Error of a JS statement returning a value of unexpected shape.
+#[derive(Debug, Display, Error)]
+#[display(fmt = "`{object}` statement returned unexpected `{value}`: \
+           {source}\n{statement}")]
+pub struct DeserializeError {
+    /// Type name of the [`Object`] the statement was executed on.
+    pub object: &'static str,
+
+    /// Source code of the executed statement.
+    pub statement: String,
+
+    /// Raw value returned by the statement.
+    pub value: Json,
+
+    /// Error of deserializing the returned value.
+    pub source: serde_json::Error,
+}
+
+/// Deserializes the provided `value` returned by the provided `statement`
+/// executed on an [`Object`] of the provided type.
+///
+/// # Errors
+///
+/// If the `value` cannot be deserialized into the requested type.
+fn parse_json<R: DeserializeOwned>(
+    object: &'static str,
+    statement: String,
+    value: Json,
+) -> Result<R, DeserializeError> {
+    R::deserialize(&value).map_err(|source| DeserializeError {
+        object,
+        statement,
+        value,
+        source,
+    })
 }
 
 /// Policy applied to [`Object`]'s functions spawning promises.
@@ -119,18 +188,40 @@ impl<T> Object<T> {
     pub async fn execute_and_fetch<O>(
         &self,
         statement: Statement,
+    ) -> Result<Object<O>, Error> {
+        self.execute_and_fetch_with_timeout(
+            statement,
+            self.window.execute_timeout(),
+        )
+        .await
+    }
+
+    /// Executes the provided [`Statement`] and returns the resulting
+    /// [`Object`], failing if it doesn't complete in the provided `timeout`.
+    ///
+    /// # Errors
+    ///
+    /// - If failed to execute JS statement.
+    /// - If JS statement didn't complete in the provided `timeout`.
+    pub async fn execute_and_fetch_with_timeout<O>(
+        &self,
+        statement: Statement,
+        timeout: Duration,
     ) -> Result<Object<O>, Error> {
         let id = Uuid::new_v4().to_string();
-        self.execute(statement.and_then(Statement::new(
-            // language=JavaScript
-            "
-            async (obj) => {
-                const [id] = args;
-                window.registry.set(id, obj);
-            }
-            ",
-            [id.clone().into()],
-        )))
+        self.execute_with_timeout(
+            statement.and_then(Statement::new(
+                // language=JavaScript
+                "
+                async (obj) => {
+                    const [id] = args;
+                    window.registry.set(id, obj);
+                }
+                ",
+                [id.clone().into()],
+            )),
+            timeout,
+        )
         .await
         .map(drop)?;
 
@@ -142,27 +233,73 @@ impl<T> Object<T> {
     /// # Errors
     ///
     /// - If failed to execute JS statement.
-    /// - If failed to parse result as [`bool`].
+    /// - If failed to deserialize result as [`bool`].
     pub async fn is_undefined(&self) -> Result<bool, Error> {
-        self.execute(Statement::new(
+        self.execute_json(Statement::new(
             // language=JavaScript
             "async (o) => o === undefined",
             [],
         ))
-        .await?
-        .as_bool()
-        .ok_or(Error::TypeCast)
+        .await
+    }
+
+    /// Executes the provided [`Statement`] in a browser, failing if it doesn't
+    /// complete in the provided `timeout`.
+    ///
+    /// JS object representing this [`Object`] will be passed to the provided
+    /// [`Statement`] as a lambda argument.
+    ///
+    /// # Errors
+    ///
+    /// - If failed to execute JS statement.
+    /// - If JS statement didn't complete in the provided `timeout`.
+    pub async fn execute_with_timeout(
+        &self,
+        js: Statement,
+        timeout: Duration,
+    ) -> Result<Json, Error> {
+        let statement = js.source();
+        match time::timeout(
+            timeout,
+            self.window.execute(self.get_obj().and_then(js)),
+        )
+        .await
+        {
+            Ok(res) => res.map_err(Error::Browser),
+            Err(_elapsed) => Err(Error::Timeout(TimeoutError {
+                object: any::type_name::<T>(),
+                statement,
+                timeout,
+            })),
+        }
     }
 
-    /// Executes the provided [`Statement`] in a browser.
+    /// Executes the provided [`Statement`] in a browser, failing if it doesn't
+    /// complete in the default [`browser::Window::execute_timeout()`].
     ///
     /// JS object representing this [`Object`] will be passed to the provided
     /// [`Statement`] as a lambda argument.
     async fn execute(&self, js: Statement) -> Result<Json, Error> {
-        self.window
-            .execute(self.get_obj().and_then(js))
+        self.execute_with_timeout(js, self.window.execute_timeout())
             .await
-            .map_err(Error::Browser)
+    }
+
+    /// Executes the provided [`Statement`] in a browser, same way as
+    /// [`Object::execute()`] does, deserializing its result into the requested
+    /// type.
+    ///
+    /// # Errors
+    ///
+    /// - If failed to execute JS statement.
+    /// - If JS statement didn't complete in time.
+    /// - If the returned value cannot be deserialized into the requested type.
+    async fn execute_json<R: DeserializeOwned>(
+        &self,
+        js: Statement,
+    ) -> Result<R, Error> {
+        let statement = js.source();
+        let value = self.execute(js).await?;
+        parse_json(any::type_name::<T>(), statement, value).map_err(Into::into)
     }
 
     /// Returns a [`Statement`] obtaining JS object of this [`Object`].
@@ -212,3 +349,34 @@ pub trait Builder {
     #[must_use]
     fn build(self) -> Statement;
 }
+
+#[cfg(test)]
+mod spec {
+    use serde_json::json;
+
+    use super::{parse_json, DeserializeError};
+
+    #[test]
+    fn parses_typed_value() {
+        let count: u64 =
+            parse_json("Store", "async (s) => s.count".into(), json!(3))
+                .unwrap();
+        assert_eq!(count, 3);
+    }
+
+    #[test]
+    fn reports_raw_value_and_statement() {
+        let err: DeserializeError = parse_json::<u64>(
+            "Store",
+            "async (s) => s.count".into(),
+            json!("three"),
+        )
+        .unwrap_err();
+
+        let text = err.to_string();
+        assert!(text.starts_with("`Store` statement returned unexpected"));
+        assert!(text.contains(r#"`"three"`"#), "{text}");
+        assert!(text.contains("expected u64"), "{text}");
+        assert!(text.ends_with("\nasync (s) => s.count"), "{text}");
+    }
+}
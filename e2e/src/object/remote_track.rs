@@ -4,6 +4,53 @@ use crate::{browser::Statement, object::Object};
 
 use super::Error;
 
+/// Duration (in milliseconds) over which a single audio level of
+/// a [`RemoteTrack`] is measured.
+const AUDIO_LEVEL_SAMPLE_MS: u64 = 500;
+
+/// JS function measuring the audio level of the underlying `MediaStreamTrack`
+/// of the provided `RemoteMediaTrack` wrapper as an average [RMS][1] of its
+/// samples in `[0; 1]` range.
+///
+/// [1]: https://en.wikipedia.org/wiki/Root_mean_square
+// language=JavaScript
+const MEASURE_AUDIO_LEVEL_JS: &str = r#"
+    async (t, sampleMs) => {
+        if (t.audioAnalyser === undefined) {
+            if (window.audioLevelCtx === undefined) {
+                window.audioLevelCtx = new AudioContext();
+            }
+            const ctx = window.audioLevelCtx;
+            await ctx.resume();
+            const stream = new MediaStream([t.track.get_track()]);
+            // Chrome doesn't pass remote audio into WebAudio unless it's
+            // played by some media element.
+            const sink = new Audio();
+            sink.muted = true;
+            sink.srcObject = stream;
+            sink.play().catch(() => {});
+            const analyser = ctx.createAnalyser();
+            analyser.fftSize = 2048;
+            ctx.createMediaStreamSource(stream).connect(analyser);
+            t.audioAnalyser = { analyser: analyser, sink: sink };
+        }
+        const analyser = t.audioAnalyser.analyser;
+        const data = new Float32Array(analyser.fftSize);
+        const deadline = Date.now() + sampleMs;
+        let sum = 0;
+        let count = 0;
+        do {
+            await new Promise((r) => setTimeout(r, 50));
+            analyser.getFloatTimeDomainData(data);
+            sum += Math.sqrt(
+                data.reduce((acc, v) => acc + v * v, 0) / data.length
+            );
+            count++;
+        } while (Date.now() < deadline);
+        return sum / count;
+    }
+"#;
+
 /// Media exchange direction of a `RemoteMediaTrack`.
 #[derive(Clone, Copy, Debug)]
 #[repr(u8)]
@@ -33,6 +80,63 @@ impl From<MediaDirection> for u8 {
 pub struct RemoteTrack;
 
 impl Object<RemoteTrack> {
+    /// Measures the audio level of this [`RemoteTrack`] as an average [RMS][1]
+    /// of its samples in `[0; 1]` range.
+    ///
+    /// # Errors
+    ///
+    /// - If failed to execute JS statement.
+    /// - If failed to parse result as [`f64`].
+    ///
+    /// [1]: https://en.wikipedia.org/wiki/Root_mean_square
+    pub async fn audio_level(&self) -> Result<f64, Error> {
+        self.execute(Statement::new(
+            // language=JavaScript
+            &format!(
+                "
+                async (t) => {{
+                    const [sampleMs] = args;
+                    return await ({MEASURE_AUDIO_LEVEL_JS})(t, sampleMs);
+                }}
+                "
+            ),
+            [AUDIO_LEVEL_SAMPLE_MS.into()],
+        ))
+        .await?
+        .as_f64()
+        .ok_or(Error::TypeCast)
+    }
+
+    /// Waits for the audio level of this [`RemoteTrack`] to rise above the
+    /// provided `threshold`.
+    ///
+    /// See [`Object::<RemoteTrack>::audio_level()`] for details.
+    ///
+    /// # Errors
+    ///
+    /// If failed to execute JS statement.
+    pub async fn wait_for_audio_level_above(
+        &self,
+        threshold: f64,
+    ) -> Result<(), Error> {
+        self.wait_for_audio_level(threshold, true).await
+    }
+
+    /// Waits for the audio level of this [`RemoteTrack`] to fall below the
+    /// provided `threshold`.
+    ///
+    /// See [`Object::<RemoteTrack>::audio_level()`] for details.
+    ///
+    /// # Errors
+    ///
+    /// If failed to execute JS statement.
+    pub async fn wait_for_audio_level_below(
+        &self,
+        threshold: f64,
+    ) -> Result<(), Error> {
+        self.wait_for_audio_level(threshold, false).await
+    }
+
     /// Waits for this [`RemoteTrack`] being enabled.
     ///
     /// # Errors
@@ -84,6 +188,37 @@ impl Object<RemoteTrack> {
         .map(drop)
     }
 
+    /// Waits for this [`RemoteTrack`] to report the `DisplaySurface` with the
+    /// provided name.
+    ///
+    /// # Errors
+    ///
+    /// If failed to execute JS statement.
+    pub async fn wait_for_display_surface(
+        &self,
+        surface: &str,
+    ) -> Result<(), Error> {
+        self.execute(Statement::new(
+            // language=JavaScript
+            "
+            async (t) => {
+                const [expected] = args;
+                const current = () => {
+                    const surface = t.track.display_surface();
+                    return surface === undefined ?
+                        null : window.rust.DisplaySurface[surface];
+                };
+                while (current() !== expected) {
+                    await new Promise((r) => setTimeout(r, 100));
+                }
+            }
+            ",
+            [surface.into()],
+        ))
+        .await
+        .map(drop)
+    }
+
     /// Indicates whether this [`RemoteTrack`]'s underlying `MediaStreamTrack`
     /// is disabled.
     ///
@@ -106,6 +241,90 @@ impl Object<RemoteTrack> {
         .ok_or(Error::TypeCast)
     }
 
+    /// Indicates whether this [`RemoteTrack`] was muted at the moment it was
+    /// provided to the `Connection.on_remote_track_added()` callback.
+    ///
+    /// # Errors
+    ///
+    /// If failed to execute JS statement.
+    pub async fn muted_on_arrival(&self) -> Result<bool, Error> {
+        self.execute(Statement::new(
+            // language=JavaScript
+            "async (t) => t.mutedOnArrival",
+            [],
+        ))
+        .await?
+        .as_bool()
+        .ok_or(Error::TypeCast)
+    }
+
+    /// Indicates whether this [`RemoteTrack`] was disabled at the moment it
+    /// was provided to the `Connection.on_remote_track_added()` callback.
+    ///
+    /// # Errors
+    ///
+    /// If failed to execute JS statement.
+    pub async fn disabled_on_arrival(&self) -> Result<bool, Error> {
+        self.execute(Statement::new(
+            // language=JavaScript
+            "async (t) => t.directionOnArrival != 0",
+            [],
+        ))
+        .await?
+        .as_bool()
+        .ok_or(Error::TypeCast)
+    }
+
+    /// Starts counting mutes of this [`RemoteTrack`], being both the
+    /// `RemoteMediaTrack.on_muted()` callback fires and the [mute][1] events
+    /// of its underlying `MediaStreamTrack`.
+    ///
+    /// # Errors
+    ///
+    /// If failed to execute JS statement.
+    ///
+    /// [1]: https://w3.org/TR/mediacapture-streams#event-mediastreamtrack-mute
+    pub async fn watch_mutes(&self) -> Result<(), Error> {
+        self.execute(Statement::new(
+            // language=JavaScript
+            "
+            async (t) => {
+                t.mutedBeforeWatch = t.on_muted_fire_count;
+                t.nativeMutes = 0;
+                t.track.get_track().addEventListener('mute', () => {
+                    t.nativeMutes++;
+                });
+            }
+            ",
+            [],
+        ))
+        .await
+        .map(drop)
+    }
+
+    /// Returns number of mutes of this [`RemoteTrack`] happened since the
+    /// [`Object::<RemoteTrack>::watch_mutes()`] call.
+    ///
+    /// # Errors
+    ///
+    /// - If failed to execute JS statement.
+    /// - If failed to parse result as [`u64`].
+    pub async fn mutes_count(&self) -> Result<u64, Error> {
+        self.execute(Statement::new(
+            // language=JavaScript
+            "
+            async (t) => {
+                return t.on_muted_fire_count - t.mutedBeforeWatch
+                    + t.nativeMutes;
+            }
+            ",
+            [],
+        ))
+        .await?
+        .as_u64()
+        .ok_or(Error::TypeCast)
+    }
+
     /// Waits for the `RemoteMediaTrack.on_disabled()` callback to fire `count`
     /// times.
     ///
@@ -234,6 +453,99 @@ impl Object<RemoteTrack> {
         .map(drop)
     }
 
+    /// Starts detecting freezes of this [`RemoteTrack`] by setting its
+    /// `RemoteMediaTrack.on_freeze()` and `RemoteMediaTrack.on_unfreeze()`
+    /// callbacks, considering it frozen after the provided `timeout_ms` of not
+    /// decoding new frames.
+    ///
+    /// # Errors
+    ///
+    /// If failed to execute JS statement.
+    pub async fn watch_freezes(&self, timeout_ms: u32) -> Result<(), Error> {
+        self.execute(Statement::new(
+            // language=JavaScript
+            "
+            async (track) => {
+                const [timeoutMs] = args;
+                track.on_freeze_fire_count = 0;
+                track.on_unfreeze_fire_count = 0;
+                track.onFreezeSubs = [];
+                track.track.set_freeze_timeout(timeoutMs);
+                const fired = () => {
+                    for (sub of track.onFreezeSubs) {
+                        sub();
+                    }
+                    track.onFreezeSubs = [];
+                };
+                track.track.on_freeze(() => {
+                    track.on_freeze_fire_count++;
+                    fired();
+                });
+                track.track.on_unfreeze(() => {
+                    track.on_unfreeze_fire_count++;
+                    fired();
+                });
+            }
+            ",
+            [timeout_ms.into()],
+        ))
+        .await
+        .map(drop)
+    }
+
+    /// Waits for the `RemoteMediaTrack.on_freeze()` (or the
+    /// `RemoteMediaTrack.on_unfreeze()`, if `unfreeze` is `true`) callback to
+    /// fire `count` times.
+    ///
+    /// [`Object::<RemoteTrack>::watch_freezes()`] should be called beforehand.
+    ///
+    /// # Errors
+    ///
+    /// If failed to execute JS statement.
+    pub async fn wait_for_on_freeze_fire_count(
+        &self,
+        count: u64,
+        unfreeze: bool,
+    ) -> Result<(), Error> {
+        self.execute(Statement::new(
+            // language=JavaScript
+            "
+            async (track) => {
+                const [count, unfreeze] = args;
+                const fireCount = () => unfreeze
+                    ? track.on_unfreeze_fire_count
+                    : track.on_freeze_fire_count;
+                while (fireCount() !== count) {
+                    await new Promise((resolve) => {
+                        track.onFreezeSubs.push(resolve);
+                    });
+                }
+            }
+            ",
+            [count.into(), unfreeze.into()],
+        ))
+        .await
+        .map(drop)
+    }
+
+    /// Indicates whether this [`RemoteTrack`] is reported as frozen by the
+    /// `RemoteMediaTrack.is_frozen()` method.
+    ///
+    /// # Errors
+    ///
+    /// - If failed to execute JS statement.
+    /// - If failed to parse result as [`bool`].
+    pub async fn is_frozen(&self) -> Result<bool, Error> {
+        self.execute(Statement::new(
+            // language=JavaScript
+            "async (t) => t.track.is_frozen()",
+            [],
+        ))
+        .await?
+        .as_bool()
+        .ok_or(Error::TypeCast)
+    }
+
     /// Waits for the `RemoteMediaTrack.on_media_direction` with the provided
     /// [`MediaDirection`].
     ///
@@ -266,4 +578,126 @@ impl Object<RemoteTrack> {
         .await
         .map(drop)
     }
+
+    /// Attaches the `MediaStreamTrack` returned by the
+    /// `RemoteMediaTrack.get_track()` to a new playing `<video>` element, and
+    /// frees the `RemoteMediaTrack` afterwards.
+    ///
+    /// This [`RemoteTrack`] mustn't be used after this call, besides
+    /// [`Object::attached_video_renders()`] method.
+    ///
+    /// # Errors
+    ///
+    /// If failed to execute JS statement.
+    pub async fn attach_and_free(&self) -> Result<(), Error> {
+        self.execute(Statement::new(
+            // language=JavaScript
+            "
+            async (t) => {
+                const video = document.createElement('video');
+                video.muted = true;
+                video.srcObject = new MediaStream([t.track.get_track()]);
+                document.body.appendChild(video);
+                await video.play();
+                t.attachedVideo = video;
+                t.track.free();
+            }
+            ",
+            [],
+        ))
+        .await
+        .map(drop)
+    }
+
+    /// Indicates whether the `<video>` element attached via the
+    /// [`Object::attach_and_free()`] method keeps rendering new frames of a
+    /// live `MediaStreamTrack`.
+    ///
+    /// # Errors
+    ///
+    /// - If failed to execute JS statement.
+    /// - If failed to parse result as [`bool`].
+    pub async fn attached_video_renders(&self) -> Result<bool, Error> {
+        self.execute(Statement::new(
+            // language=JavaScript
+            "
+            async (t) => {
+                const video = t.attachedVideo;
+                const track = video.srcObject.getVideoTracks()[0];
+                const frames = () =>
+                    video.getVideoPlaybackQuality().totalVideoFrames;
+                const before = frames();
+                await new Promise((r) => setTimeout(r, 1000));
+                return track.readyState === 'live' && frames() > before;
+            }
+            ",
+            [],
+        ))
+        .await?
+        .as_bool()
+        .ok_or(Error::TypeCast)
+    }
+
+    /// Waits for the video of this [`RemoteTrack`] to be rendered in the
+    /// provided size.
+    ///
+    /// # Errors
+    ///
+    /// If failed to execute JS statement.
+    pub async fn wait_for_video_size(
+        &self,
+        width: u32,
+        height: u32,
+    ) -> Result<(), Error> {
+        self.execute(Statement::new(
+            // language=JavaScript
+            "
+            async (t) => {
+                const [width, height] = args;
+                const video = document.createElement('video');
+                video.muted = true;
+                video.srcObject = new MediaStream([t.track.get_track()]);
+                document.body.appendChild(video);
+                await video.play();
+                while (video.videoWidth !== width
+                    || video.videoHeight !== height) {
+                    await new Promise((r) => setTimeout(r, 100));
+                }
+                video.remove();
+            }
+            ",
+            [width.into(), height.into()],
+        ))
+        .await
+        .map(drop)
+    }
+
+    /// Waits for the audio level of this [`RemoteTrack`] to become above (or
+    /// below) the provided `threshold`.
+    async fn wait_for_audio_level(
+        &self,
+        threshold: f64,
+        above: bool,
+    ) -> Result<(), Error> {
+        self.execute(Statement::new(
+            // language=JavaScript
+            &format!(
+                "
+                async (t) => {{
+                    const [threshold, above, sampleMs] = args;
+                    const measure = {MEASURE_AUDIO_LEVEL_JS};
+                    while (true) {{
+                        const level = await measure(t, sampleMs);
+                        if ((level > threshold) === above) {{
+                            return;
+                        }}
+                    }}
+                }}
+                "
+            ),
+            [threshold.into(), above.into(), AUDIO_LEVEL_SAMPLE_MS.into()],
+        ))
+        .await
+        .map(drop)
+    }
 }
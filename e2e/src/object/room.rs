@@ -2,8 +2,11 @@
 
 use std::{borrow::Cow, str::FromStr};
 
+use derive_more::{Display, Error as StdError};
+use serde::Deserialize;
+
 use crate::{
-    browser::Statement,
+    browser::{self, Statement},
     object::{connections_store::ConnectionStore, tracks_store, Object},
 };
 
@@ -13,8 +16,13 @@ use super::{AwaitCompletion, Error};
 #[derive(Clone, Copy, Debug)]
 pub struct Room;
 
+/// Interval between two `getStats()` samples used to calculate bitrates in
+/// [`Object::<Room>::get_stats()`], in milliseconds.
+const STATS_SAMPLE_INTERVAL_MS: u64 = 500;
+
 /// Representation of a `MediaKind` JS enum.
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq)]
+#[serde(rename_all = "lowercase")]
 pub enum MediaKind {
     /// Audio media.
     Audio,
@@ -27,17 +35,37 @@ impl FromStr for MediaKind {
     type Err = ParsingFailedError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s.contains("audio") {
-            Ok(Self::Audio)
-        } else if s.contains("video") {
-            Ok(Self::Video)
-        } else {
-            Err(ParsingFailedError)
-        }
+        Self::from_name(s)
     }
 }
 
 impl MediaKind {
+    /// All the [`MediaKind`]s.
+    pub const ALL: [Self; 2] = [Self::Audio, Self::Video];
+
+    /// Returns the stable lowercase name of this [`MediaKind`], the same as
+    /// the `media_kind_to_string()` JS function does.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Audio => "audio",
+            Self::Video => "video",
+        }
+    }
+
+    /// Parses a [`MediaKind`] from its exact lowercase name, the same as the
+    /// `media_kind_from_string()` JS function does.
+    ///
+    /// # Errors
+    ///
+    /// If the provided `name` is unknown.
+    pub fn from_name(name: &str) -> Result<Self, ParsingFailedError> {
+        Self::ALL
+            .into_iter()
+            .find(|k| k.name() == name)
+            .ok_or(ParsingFailedError)
+    }
+
     /// Converts this [`MediaKind`] to the JS code for this enum variant.
     #[must_use]
     pub const fn as_js(self) -> &'static str {
@@ -49,7 +77,8 @@ impl MediaKind {
 }
 
 /// Representation of a `MediaSourceKind` JS enum.
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq)]
+#[serde(rename_all = "lowercase")]
 pub enum MediaSourceKind {
     /// Device source of media (camera, mic, etc).
     Device,
@@ -62,17 +91,37 @@ impl FromStr for MediaSourceKind {
     type Err = ParsingFailedError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s.contains("device") {
-            Ok(Self::Device)
-        } else if s.contains("display") {
-            Ok(Self::Display)
-        } else {
-            Err(ParsingFailedError)
-        }
+        Self::from_name(s)
     }
 }
 
 impl MediaSourceKind {
+    /// All the [`MediaSourceKind`]s.
+    pub const ALL: [Self; 2] = [Self::Device, Self::Display];
+
+    /// Returns the stable lowercase name of this [`MediaSourceKind`], the same
+    /// as the `media_source_kind_to_string()` JS function does.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Device => "device",
+            Self::Display => "display",
+        }
+    }
+
+    /// Parses a [`MediaSourceKind`] from its exact lowercase name, the same as
+    /// the `media_source_kind_from_string()` JS function does.
+    ///
+    /// # Errors
+    ///
+    /// If the provided `name` is unknown.
+    pub fn from_name(name: &str) -> Result<Self, ParsingFailedError> {
+        Self::ALL
+            .into_iter()
+            .find(|k| k.name() == name)
+            .ok_or(ParsingFailedError)
+    }
+
     /// Converts this [`MediaSourceKind`] to a JS code for this enum variant.
     #[must_use]
     pub const fn as_js(self) -> &'static str {
@@ -83,25 +132,168 @@ impl MediaSourceKind {
     }
 }
 
+/// Parses a [`MediaKind`] and a [`MediaSourceKind`] of a track from its name:
+/// either a [`MediaKind`] name alone (meaning a [`MediaSourceKind::Device`]
+/// track), or a [`MediaSourceKind`] name followed by a [`MediaKind`] name
+/// (like `display video`).
+///
+/// # Errors
+///
+/// If any of the names is unknown.
+pub fn parse_track_kinds(
+    s: &str,
+) -> Result<(MediaKind, MediaSourceKind), ParsingFailedError> {
+    match s.split_once(' ') {
+        None => Ok((MediaKind::from_name(s)?, MediaSourceKind::Device)),
+        Some((source, kind)) => Ok((
+            MediaKind::from_name(kind)?,
+            MediaSourceKind::from_name(source)?,
+        )),
+    }
+}
+
+/// Exception thrown by a `Room.join()` call.
+#[derive(Clone, Debug, Deserialize, Display, Eq, PartialEq, StdError)]
+#[display(fmt = "{name}: {message}")]
+pub struct JoinError {
+    /// Name of the thrown exception (`RpcClientException`, `FormatException`,
+    /// etc).
+    pub name: String,
+
+    /// Kind of the thrown exception, if it has any (`AuthorizationFailed`,
+    /// `SessionFinished`, etc).
+    pub kind: Option<String>,
+
+    /// Message of the thrown exception.
+    pub message: String,
+}
+
+/// Everything delivered to callbacks registered on an already joined `Room`
+/// via [`Object::<Room>::register_late_callbacks()`].
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LateCallbacks {
+    /// IDs of remote `Member`s of the delivered `Connection`s, in the order
+    /// of their delivery.
+    pub connections: Vec<String>,
+
+    /// Number of the delivered `LocalMediaTrack`s.
+    pub local_tracks: u64,
+
+    /// Number of the delivered `RemoteMediaTrack`s of all the delivered
+    /// `Connection`s.
+    pub remote_tracks: u64,
+}
+
+/// Description of `MediaStreamSettings` to be applied with
+/// [`Object::<Room>::set_local_media_settings()`].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct LocalMediaSettings {
+    /// Settings of an audio track, or [`None`] if audio is disabled.
+    pub audio: Option<AudioTrackSettings>,
+
+    /// Settings of a device video track, or [`None`] if device video is
+    /// disabled.
+    pub device_video: Option<DeviceVideoTrackSettings>,
+}
+
+impl LocalMediaSettings {
+    /// Creates new [`LocalMediaSettings`] enabling the provided media types
+    /// without any additional constraints.
+    #[must_use]
+    pub fn new(video: bool, audio: bool) -> Self {
+        Self {
+            audio: audio.then(AudioTrackSettings::default),
+            device_video: video.then(DeviceVideoTrackSettings::default),
+        }
+    }
+}
+
+/// Settings of an audio track in [`LocalMediaSettings`].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct AudioTrackSettings {
+    /// ID of the device to capture audio from.
+    pub device_id: Option<String>,
+}
+
+/// Settings of a device video track in [`LocalMediaSettings`].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct DeviceVideoTrackSettings {
+    /// ID of the device to capture video from.
+    pub device_id: Option<String>,
+
+    /// Exact width of the captured video.
+    pub width: Option<u32>,
+
+    /// Exact height of the captured video.
+    pub height: Option<u32>,
+}
+
 impl Object<Room> {
     /// Joins a [`Room`] with the provided URI.
     ///
     /// # Errors
     ///
-    /// If failed to execute JS statement.
+    /// - If failed to execute JS statement.
+    /// - With [`Error::Join`] if `Room.join()` throws an exception.
     pub async fn join(&self, uri: String) -> Result<(), Error> {
-        self.execute(Statement::new(
-            // language=JavaScript
-            "
-            async (room) => {
-                const [uri] = args;
-                await room.room.join(uri);
-            }
-            ",
-            [uri.into()],
+        self.join_concurrently(uri, 1).await
+    }
+
+    /// Joins a [`Room`] with the provided URI by calling `Room.join()` the
+    /// provided number of `times` at once, without awaiting the previous calls.
+    ///
+    /// # Errors
+    ///
+    /// - If failed to execute JS statement.
+    /// - With [`Error::Join`] if any of `Room.join()` calls throws an
+    ///   exception.
+    pub async fn join_concurrently(
+        &self,
+        uri: String,
+        times: u64,
+    ) -> Result<(), Error> {
+        let err = self
+            .execute(Statement::new(
+                // language=JavaScript
+                "
+                async (room) => {
+                    const [uri, times] = args;
+                    try {
+                        await Promise.all(Array.from(
+                            { length: times },
+                            () => room.room.join(uri),
+                        ));
+                        return null;
+                    } catch (e) {
+                        const call = (f) => {
+                            return typeof e[f] === 'function' ?
+                                e[f]() : null;
+                        };
+                        let kind = call('kind');
+                        if (kind !== null) {
+                            const kinds = window.rust[
+                                `${e.constructor.name}Kind`
+                            ];
+                            kind = kinds?.[kind] ?? String(kind);
+                        }
+                        return {
+                            name: e.constructor?.name ?? 'Error',
+                            kind: kind,
+                            message: call('message') ?? String(e)
+                        };
+                    }
+                }
+                ",
+                [uri.into(), times.into()],
+            ))
+            .await?;
+        if err.is_null() {
+            return Ok(());
+        }
+        Err(Error::Join(
+            serde_json::from_value(err).map_err(browser::Error::from)?,
         ))
-        .await
-        .map(drop)
     }
 
     /// Disables media publishing for the provided [`MediaKind`] and
@@ -180,6 +372,35 @@ impl Object<Room> {
         .map(drop)
     }
 
+    /// Enables media publishing of both [`MediaKind`]s, enabling video the
+    /// provided `gap_ms` milliseconds after audio.
+    ///
+    /// Resolves once both enablings are completed.
+    ///
+    /// # Errors
+    ///
+    /// If failed to execute JS statement.
+    pub async fn enable_audio_and_video_send(
+        &self,
+        gap_ms: u64,
+    ) -> Result<(), Error> {
+        self.execute(Statement::new(
+            // language=JavaScript
+            "
+            async (r) => {
+                const [gapMs] = args;
+                const video = new Promise((resolve) => {
+                    setTimeout(resolve, gapMs);
+                }).then(() => r.room.enable_video());
+                await Promise.all([r.room.enable_audio(), video]);
+            }
+            ",
+            [gap_ms.into()],
+        ))
+        .await
+        .map(drop)
+    }
+
     /// Disables remote media receiving for the provided [`MediaKind`] and
     /// [`MediaSourceKind`].
     ///
@@ -332,6 +553,39 @@ impl Object<Room> {
         .map(drop)
     }
 
+    /// Exports a snapshot of the media states intended in this [`Room`] via
+    /// `Room.export_state()`.
+    ///
+    /// # Errors
+    ///
+    /// If failed to execute JS statement.
+    pub async fn export_state(&self) -> Result<String, Error> {
+        self.execute(Statement::new(
+            // language=JavaScript
+            "async (r) => r.room.export_state()",
+            [],
+        ))
+        .await?
+        .as_str()
+        .map(ToOwned::to_owned)
+        .ok_or(Error::TypeCast)
+    }
+
+    /// Returns the current [`MediaState`] of this [`Room`].
+    ///
+    /// # Errors
+    ///
+    /// - If failed to execute JS statement.
+    /// - If failed to deserialize the result.
+    pub async fn media_state(&self) -> Result<MediaState, Error> {
+        self.execute_json(Statement::new(
+            // language=JavaScript
+            "async (r) => r.room.media_state()",
+            [],
+        ))
+        .await
+    }
+
     /// Returns a [`ConnectionStore`] of this [`Room`].
     ///
     /// # Errors
@@ -345,21 +599,32 @@ impl Object<Room> {
             "
             async (r) => {
                 let store = {
+                    room: r.room,
                     connections: new Map(),
+                    closedConnections: new Map(),
                     subs: new Map(),
+                    closeSubs: new Map(),
+                    countSubs: [],
                 };
                 r.room.on_new_connection((conn) => {
+                    let id = conn.get_remote_member_id();
                     let closeListener = {
                         isClosed: false,
                         subs: [],
                     };
                     let tracksStore = {
+                        tracks: [],
+                        subs: [],
+                        stoppedSubs: []
+                    };
+                    let removedTracksStore = {
                         tracks: [],
                         subs: []
                     };
                     let connection = {
                         conn: conn,
                         tracksStore: tracksStore,
+                        removedTracksStore: removedTracksStore,
                         closeListener: closeListener,
                     };
                     conn.on_remote_track_added((t) => {
@@ -369,6 +634,8 @@ impl Object<Room> {
                             on_disabled_fire_count: 0,
                             on_muted_fire_count: 0,
                             on_unmuted_fire_count: 0,
+                            mutedOnArrival: t.muted(),
+                            directionOnArrival: t.media_direction(),
                             stopped: false,
                             onEnabledSubs: [],
                             onDisabledSubs: [],
@@ -392,6 +659,8 @@ impl Object<Room> {
                         });
                         track.track.on_stopped(() => {
                             track.stopped = true;
+                            tracksStore.stoppedSubs = tracksStore.stoppedSubs
+                                .filter((sub) => sub(track));
                         });
                         track.track.on_media_direction_changed((dir) => {
                             if (dir == 0) {
@@ -420,18 +689,36 @@ impl Object<Room> {
                             });
                         tracksStore.subs = newStoreSubs;
                     });
+                    conn.on_remote_track_removed((t) => {
+                        removedTracksStore.tracks.push(t);
+                        removedTracksStore.subs = removedTracksStore.subs
+                            .filter((sub) => sub());
+                    });
                     conn.on_close(() => {
                         closeListener.isClosed = true;
                         for (sub of closeListener.subs) {
                             sub();
                         }
+                        if (store.connections.get(id) === connection) {
+                            store.connections.delete(id);
+                        }
+                        store.closedConnections.set(id, connection);
+                        let closeSubs = store.closeSubs.get(id);
+                        if (closeSubs !== undefined) {
+                            store.closeSubs.delete(id);
+                            for (sub of closeSubs) {
+                                sub(connection);
+                            }
+                        }
+                        store.countSubs = store.countSubs
+                            .filter((sub) => sub());
                     });
-                    let id = conn.get_remote_member_id();
                     store.connections.set(id, connection);
                     let sub = store.subs.get(id);
                     if (sub !== undefined) {
                         sub(connection);
                     }
+                    store.countSubs = store.countSubs.filter((sub) => sub());
                 });
                 return store;
             }
@@ -441,6 +728,107 @@ impl Object<Room> {
         .await
     }
 
+    /// Collects [`RtcStats`] of all the `RTCPeerConnection`s created in the
+    /// browser window of this [`Room`].
+    ///
+    /// `getStats()` is sampled twice with [`STATS_SAMPLE_INTERVAL_MS`] between
+    /// samples, so bitrates are calculated over this interval.
+    ///
+    /// # Errors
+    ///
+    /// - If failed to execute JS statement.
+    /// - If failed to deserialize the returned [`RtcStats`].
+    pub async fn get_stats(&self) -> Result<RtcStats, Error> {
+        let stats = self
+            .execute(Statement::new(
+                // language=JavaScript
+                r#"
+                async (room) => {
+                    const [sampleMs] = args;
+                    const sourceKinds = new Map();
+                    for (const t of room.localTracksStore.tracks) {
+                        if (t.stopped) {
+                            continue;
+                        }
+                        const isDisplay = t.track.media_source_kind() ===
+                            window.rust.MediaSourceKind.Display;
+                        sourceKinds.set(
+                            t.track.get_track().id,
+                            isDisplay ? "display" : "device"
+                        );
+                    }
+
+                    const sample = async () => {
+                        const reports = new Map();
+                        const pcs = window.pcMock.connections
+                            .filter((pc) => pc.signalingState !== "closed");
+                        for (const [i, pc] of pcs.entries()) {
+                            const report = await pc.getStats();
+                            report.forEach((s) => {
+                                reports.set(`${i}/${s.id}`, s);
+                            });
+                        }
+                        return { at: performance.now(), reports: reports };
+                    };
+                    const first = await sample();
+                    await new Promise((r) => setTimeout(r, sampleMs));
+                    const second = await sample();
+
+                    const elapsed = (second.at - first.at) / 1000;
+                    const bytesKey = (s) => s.type === "inbound-rtp" ?
+                        "bytesReceived" : "bytesSent";
+                    const bitrate = (id, bytes) => {
+                        const prev = first.reports.get(id);
+                        if (prev === undefined || elapsed <= 0) {
+                            return 0;
+                        }
+                        return Math.max(0, bytes - prev[bytesKey(prev)]) *
+                            8 / elapsed;
+                    };
+
+                    const stats = { inbound: [], outbound: [] };
+                    for (const [id, s] of second.reports) {
+                        if (s.type !== "inbound-rtp" &&
+                            s.type !== "outbound-rtp") {
+                            continue;
+                        }
+                        const kind = s.kind || s.mediaType;
+                        let width = s.frameWidth;
+                        let height = s.frameHeight;
+                        let sourceKind = null;
+                        if (s.type === "outbound-rtp") {
+                            const prefix = id.split("/")[0];
+                            const source = second.reports
+                                .get(`${prefix}/${s.mediaSourceId}`);
+                            if (source !== undefined) {
+                                width = width ?? source.width;
+                                height = height ?? source.height;
+                                sourceKind = sourceKinds
+                                    .get(source.trackIdentifier) ?? null;
+                            }
+                        }
+                        const stream = {
+                            kind: kind,
+                            sourceKind: sourceKind,
+                            bitrate: bitrate(id, s[bytesKey(s)] ?? 0),
+                            frameWidth: width ?? null,
+                            frameHeight: height ?? null
+                        };
+                        if (s.type === "inbound-rtp") {
+                            stats.inbound.push(stream);
+                        } else {
+                            stats.outbound.push(stream);
+                        }
+                    }
+                    return stats;
+                }
+                "#,
+                [STATS_SAMPLE_INTERVAL_MS.into()],
+            ))
+            .await?;
+        Ok(serde_json::from_value(stats).map_err(browser::Error::from)?)
+    }
+
     /// Returns a [`tracks_store::Local`] of this [`Room`].
     ///
     /// # Errors
@@ -514,19 +902,62 @@ impl Object<Room> {
 
     /// Calls `ReconnectHandle.reconnect_with_backoff()`.
     ///
+    /// Once reconnected, the time passed since the last connection loss is
+    /// recorded, and can be retrieved with
+    /// [`Object::<Room>::last_reconnect_duration_ms()`].
+    ///
+    /// # Errors
+    ///
+    /// Should be called only if connection was previously lost and
+    /// a `ReconnectHandle` was obtained, otherwise method will error.
+    pub async fn start_ws_reconnect(
+        &self,
+        maybe_await: AwaitCompletion,
+    ) -> Result<(), Error> {
+        self.start_ws_reconnect_with_backoff(100, 1000, Some(5000), maybe_await)
+            .await
+    }
+
+    /// Calls `ReconnectHandle.reconnect_with_backoff()` with the provided
+    /// starting delay, max delay and max elapsed time (in milliseconds).
+    ///
+    /// Once reconnected, the time passed since the last connection loss is
+    /// recorded, and can be retrieved with
+    /// [`Object::<Room>::last_reconnect_duration_ms()`].
+    ///
     /// # Errors
     ///
     /// Should be called only if connection was previously lost and
     /// a `ReconnectHandle` was obtained, otherwise method will error.
-    pub async fn start_ws_reconnect(&self) -> Result<(), Error> {
+    pub async fn start_ws_reconnect_with_backoff(
+        &self,
+        starting_delay_ms: u64,
+        max_delay_ms: u64,
+        max_elapsed_time_ms: Option<u64>,
+        maybe_await: AwaitCompletion,
+    ) -> Result<(), Error> {
+        self.reconnect_with_backoff(
+            "reconnectHandle",
+            starting_delay_ms,
+            max_delay_ms,
+            max_elapsed_time_ms,
+            maybe_await,
+        )
+        .await
+    }
+
+    /// Calls `ReconnectHandle.cancel()`, giving up reconnecting.
+    ///
+    /// # Errors
+    ///
+    /// Should be called only if connection was previously lost and
+    /// a `ReconnectHandle` was obtained, otherwise method will error.
+    pub async fn cancel_ws_reconnect(&self) -> Result<(), Error> {
         self.execute(Statement::new(
             // language=JavaScript
             "
             async (room) => {
-                await room
-                    .connLossListener
-                    .reconnectHandle
-                    .reconnect_with_backoff(100, 2.0, 1000, 5000);
+                room.connLossListener.reconnectHandle.cancel();
             }
             ",
             [],
@@ -535,109 +966,1177 @@ impl Object<Room> {
         .map(drop)
     }
 
-    /// Enables or disables media type with a `Room.set_local_media_settings()`
-    /// function call.
+    /// Calls `ReconnectHandle.reconnect_with_backoff()` on the
+    /// `ReconnectHandle` passed to the first `Room.on_connection_loss()`
+    /// callback call, regardless of the outage it's called during.
+    ///
+    /// Once reconnected, the time passed since the last connection loss is
+    /// recorded, and can be retrieved with
+    /// [`Object::<Room>::last_reconnect_duration_ms()`].
     ///
     /// # Errors
     ///
-    /// If failed to execute JS statement.
-    pub async fn set_local_media_settings(
+    /// Should be called only if connection was lost at least once and
+    /// a `ReconnectHandle` was obtained, otherwise method will error.
+    pub async fn start_ws_reconnect_with_first_handle(
         &self,
-        video: bool,
-        audio: bool,
-        should_wait: bool,
+        maybe_await: AwaitCompletion,
     ) -> Result<(), Error> {
-        self.forget_local_tracks().await;
+        self.reconnect_with_backoff(
+            "firstReconnectHandle",
+            100,
+            1000,
+            Some(5000),
+            maybe_await,
+        )
+        .await
+    }
+
+    /// Calls `ReconnectHandle.reconnect_with_delay()` on the `ReconnectHandle`
+    /// passed to the first `Room.on_connection_loss()` callback call, and
+    /// returns the name of the `ReconnectOutcome` it resolves with.
+    ///
+    /// # Errors
+    ///
+    /// - If failed to execute JS statement.
+    /// - If failed to parse result as [`String`].
+    /// - If the `ReconnectHandle` reports to be reconnecting afterwards.
+    pub async fn reconnect_with_first_handle(&self) -> Result<String, Error> {
         self.execute(Statement::new(
             // language=JavaScript
             "
             async (room) => {
-                const [video, audio, shouldWait] = args;
-                let constraints = new rust.MediaStreamSettings();
-                if (video) {
-                    let video =
-                        new window.rust.DeviceVideoTrackConstraints();
-                    constraints.device_video(video);
-                }
-                if (audio) {
-                    let audio = new window.rust.AudioTrackConstraints();
-                    constraints.audio(audio);
-                }
-                let promise = room.room.set_local_media_settings(
-                    constraints,
-                    true,
-                    false
-                );
-                if (shouldWait) {
-                    await promise;
+                const handle = room.connLossListener.firstReconnectHandle;
+                const outcome = await handle.reconnect_with_delay(0);
+                if (handle.is_reconnecting()) {
+                    throw new Error('ReconnectHandle is still reconnecting');
                 }
+                return window.rust.ReconnectOutcome[outcome];
             }
             ",
-            [video.into(), audio.into(), should_wait.into()],
+            [],
+        ))
+        .await?
+        .as_str()
+        .ok_or(Error::TypeCast)
+        .map(ToOwned::to_owned)
+    }
+
+    /// Calls `ReconnectHandle.reconnect_with_backoff()` on the
+    /// `ReconnectHandle` stored in the provided field of the connection loss
+    /// listener.
+    async fn reconnect_with_backoff(
+        &self,
+        handle: &str,
+        starting_delay_ms: u64,
+        max_delay_ms: u64,
+        max_elapsed_time_ms: Option<u64>,
+        maybe_await: AwaitCompletion,
+    ) -> Result<(), Error> {
+        self.execute(Statement::new(
+            // language=JavaScript
+            &format!(
+                "
+                async (room) => {{
+                    const [startingDelay, maxDelay, maxElapsed] = args;
+                    const listener = room.connLossListener;
+                    const reconnect = async () => {{
+                        await listener
+                            .{handle}
+                            .reconnect_with_backoff(
+                                startingDelay, 2.0, maxDelay, maxElapsed
+                            );
+                        listener.isLost = false;
+                        listener.lastReconnectMs =
+                            Math.round(performance.now() - listener.lostAt);
+                        for (const sub of listener.reconnectSubs) {{
+                            sub();
+                        }}
+                        listener.reconnectSubs = [];
+                    }};
+                    {maybe_await} reconnect();
+                }}
+                "
+            ),
+            [
+                starting_delay_ms.into(),
+                max_delay_ms.into(),
+                max_elapsed_time_ms.into(),
+            ],
         ))
         .await
         .map(drop)
     }
 
-    /// Waits for the `Room.on_failed_local_stream()` callback to fire the
-    /// provided number of times.
+    /// Waits for the reconnection started with
+    /// [`Object::<Room>::start_ws_reconnect()`] to succeed.
     ///
-    /// # Panics
+    /// Resolves instantly if already reconnected after the last connection
+    /// loss.
+    ///
+    /// # Errors
     ///
     /// If failed to execute JS statement.
-    pub async fn when_failed_local_stream_count(&self, count: u64) {
+    pub async fn wait_for_reconnect(&self) -> Result<(), Error> {
         self.execute(Statement::new(
             // language=JavaScript
             "
             async (room) => {
-                const [count] = args;
-                return await new Promise((resolve) => {
-                    if (room.onFailedLocalStreamListener.count === count) {
-                        resolve();
-                    } else {
-                        room.onFailedLocalStreamListener.subs.push(() => {
-                            let failCount =
-                                room.onFailedLocalStreamListener.count;
-                            if (failCount === count) {
-                                resolve();
-                                return false;
-                            } else {
-                                return true;
-                            }
-                        });
-                    }
-                });
+                const listener = room.connLossListener;
+                if (listener.lastReconnectMs === null) {
+                    await new Promise((resolve) => {
+                        listener.reconnectSubs.push(resolve);
+                    });
+                }
             }
             ",
-            [count.into()],
+            [],
         ))
         .await
         .map(drop)
-        .unwrap();
     }
 
-    /// Removes all local `LocalMediaTrack`s from the JS side.
+    /// Returns duration (in milliseconds) between the last
+    /// `Room.on_connection_loss()` callback firing and the successful
+    /// reconnection, or [`None`] if not reconnected since then.
     ///
-    /// # Panics
+    /// # Errors
     ///
-    /// If failed to execute JS statement.
-    pub async fn forget_local_tracks(&self) {
+    /// - If failed to execute JS statement.
+    /// - If failed to parse result as [`u64`].
+    pub async fn last_reconnect_duration_ms(
+        &self,
+    ) -> Result<Option<u64>, Error> {
+        let duration = self
+            .execute(Statement::new(
+                // language=JavaScript
+                "async (room) => room.connLossListener.lastReconnectMs",
+                [],
+            ))
+            .await?;
+        if duration.is_null() {
+            return Ok(None);
+        }
+        duration.as_u64().map(Some).ok_or(Error::TypeCast)
+    }
+
+    /// Applies the provided [`LocalMediaSettings`] with
+    /// a `Room.set_local_media_settings()` function call.
+    ///
+    /// All the local tracks known so far are forgotten before the call.
+    ///
+    /// If `seamless` is `true`, then only the tracks recaptured from the same
+    /// devices are stopped first, despite the `stop_first`.
+    ///
+    /// # Errors
+    ///
+    /// - If failed to execute JS statement.
+    /// - If the provided [`LocalMediaSettings`] couldn't be applied and the
+    ///   result is awaited.
+    pub async fn set_local_media_settings(
+        &self,
+        settings: &LocalMediaSettings,
+        stop_first: bool,
+        rollback_on_fail: bool,
+        seamless: bool,
+        maybe_await: AwaitCompletion,
+    ) -> Result<(), Error> {
+        self.forget_local_tracks().await;
+        let audio = settings.audio.as_ref();
+        let video = settings.device_video.as_ref();
         self.execute(Statement::new(
             // language=JavaScript
-            "
-            async (room) => {
-                room.localTracksStore.tracks.forEach((t) => t.track.free());
-                room.localTracksStore.tracks = [];
-            }
-            ",
-            [],
+            &format!(
+                "
+                async (room) => {{
+                    const [
+                        audio, audioDeviceId,
+                        video, videoDeviceId, width, height,
+                        stopFirst, rollbackOnFail, seamless
+                    ] = args;
+                    let constraints = new rust.MediaStreamSettings();
+                    if (video) {{
+                        let video =
+                            new window.rust.DeviceVideoTrackConstraints();
+                        if (videoDeviceId !== null) {{
+                            video.device_id(videoDeviceId);
+                        }}
+                        if (width !== null) {{
+                            video.exact_width(width);
+                        }}
+                        if (height !== null) {{
+                            video.exact_height(height);
+                        }}
+                        constraints.device_video(video);
+                    }}
+                    if (audio) {{
+                        let audio = new window.rust.AudioTrackConstraints();
+                        if (audioDeviceId !== null) {{
+                            audio.device_id(audioDeviceId);
+                        }}
+                        constraints.audio(audio);
+                    }}
+                    {maybe_await} room.room.set_local_media_settings(
+                        constraints,
+                        stopFirst,
+                        rollbackOnFail,
+                        seamless
+                    );
+                }}
+                "
+            ),
+            [
+                audio.is_some().into(),
+                audio.and_then(|a| a.device_id.as_deref()).into(),
+                video.is_some().into(),
+                video.and_then(|v| v.device_id.as_deref()).into(),
+                video.and_then(|v| v.width).into(),
+                video.and_then(|v| v.height).into(),
+                stop_first.into(),
+                rollback_on_fail.into(),
+                seamless.into(),
+            ],
         ))
         .await
         .map(drop)
-        .unwrap();
     }
-}
 
-/// Error of parsing a [`MediaKind`] or a [`MediaSourceKind`].
-#[derive(Clone, Copy, Debug)]
-pub struct ParsingFailedError;
+    /// Sets a default microphone of this [`Room`] with a
+    /// `Room.set_default_media_settings()` function call.
+    ///
+    /// # Errors
+    ///
+    /// If failed to execute JS statement.
+    pub async fn set_default_microphone(
+        &self,
+        device_id: &str,
+    ) -> Result<(), Error> {
+        self.execute(Statement::new(
+            // language=JavaScript
+            "
+            async (room) => {
+                const [deviceId] = args;
+                const settings = new window.rust.MediaStreamSettings();
+                const audio = new window.rust.AudioTrackConstraints();
+                audio.device_id(deviceId);
+                settings.audio(audio);
+                settings.device_video(
+                    new window.rust.DeviceVideoTrackConstraints()
+                );
+                room.room.set_default_media_settings(settings);
+            }
+            ",
+            [device_id.into()],
+        ))
+        .await
+        .map(drop)
+    }
+
+    /// Sets the provided `BundlePolicy` of `RTCPeerConnection`s of this
+    /// [`Room`] with a `Room.set_rtc_configuration()` function call.
+    ///
+    /// # Errors
+    ///
+    /// - If failed to execute JS statement.
+    /// - If `RTCPeerConnection`s of this [`Room`] are created already.
+    pub async fn set_bundle_policy(&self, policy: &str) -> Result<(), Error> {
+        self.execute(Statement::new(
+            // language=JavaScript
+            "
+            async (room) => {
+                const [policy] = args;
+                const config = new window.rust.RtcConfiguration();
+                config.bundle_policy(window.rust.BundlePolicy[policy]);
+                room.room.set_rtc_configuration(config);
+            }
+            ",
+            [policy.into()],
+        ))
+        .await
+        .map(drop)
+    }
+
+    /// Applies a `RoomMediaPolicy` with the provided `SendMediaPolicy`s of
+    /// outbound audio and device video to this [`Room`] with a
+    /// `Room.apply_policy()` function call.
+    ///
+    /// # Errors
+    ///
+    /// - If failed to execute JS statement.
+    /// - If any of the required media state changes failed.
+    pub async fn apply_media_policy(
+        &self,
+        audio: &str,
+        video: &str,
+    ) -> Result<(), Error> {
+        self.execute(Statement::new(
+            // language=JavaScript
+            "
+            async (room) => {
+                const [audio, video] = args;
+                const policy = new window.rust.RoomMediaPolicy();
+                policy.send_audio(window.rust.SendMediaPolicy[audio]);
+                policy.send_device_video(window.rust.SendMediaPolicy[video]);
+                await room.room.apply_policy(policy);
+            }
+            ",
+            [audio.into(), video.into()],
+        ))
+        .await
+        .map(drop)
+    }
+
+    /// Starts publishing a display video with a `Room.enable_video()`
+    /// function call, constraining it with `Room.set_local_media_settings()`
+    /// beforehand.
+    ///
+    /// # Errors
+    ///
+    /// - If failed to execute JS statement.
+    /// - If the screen capture request was rejected.
+    pub async fn start_screen_sharing(&self) -> Result<(), Error> {
+        self.execute(Statement::new(
+            // language=JavaScript
+            "
+            async (room) => {
+                let constraints = new rust.MediaStreamSettings();
+                constraints.audio(new window.rust.AudioTrackConstraints());
+                constraints.device_video(
+                    new window.rust.DeviceVideoTrackConstraints()
+                );
+                constraints.display_video(
+                    new window.rust.DisplayVideoTrackConstraints()
+                );
+                await room.room.set_local_media_settings(
+                    constraints,
+                    false,
+                    true
+                );
+                await room.room.enable_video(
+                    window.rust.MediaSourceKind.Display
+                );
+            }
+            ",
+            [],
+        ))
+        .await
+        .map(drop)
+    }
+
+    /// Same as [`Object::start_screen_sharing()`], but offering the current
+    /// browser tab in a one-click manner, with tab switching and system audio
+    /// excluded.
+    ///
+    /// # Errors
+    ///
+    /// - If failed to execute JS statement.
+    /// - If the screen capture request was rejected.
+    pub async fn start_current_tab_sharing(&self) -> Result<(), Error> {
+        self.execute(Statement::new(
+            // language=JavaScript
+            "
+            async (room) => {
+                const inclusion = window.rust.CaptureInclusion;
+                let constraints = new rust.MediaStreamSettings();
+                constraints.audio(new window.rust.AudioTrackConstraints());
+                constraints.device_video(
+                    new window.rust.DeviceVideoTrackConstraints()
+                );
+                constraints.display_video(
+                    new window.rust.DisplayVideoTrackConstraints()
+                        .with_prefer_current_tab(true)
+                        .with_self_browser_surface(inclusion.Include)
+                        .with_surface_switching(inclusion.Exclude)
+                        .with_system_audio(inclusion.Exclude)
+                );
+                await room.room.set_local_media_settings(
+                    constraints,
+                    false,
+                    true
+                );
+                await room.room.enable_video(
+                    window.rust.MediaSourceKind.Display
+                );
+            }
+            ",
+            [],
+        ))
+        .await
+        .map(drop)
+    }
+
+    /// Publishes a video drawn on a `<canvas>` of the provided size instead of
+    /// a camera one, providing it via a
+    /// `MediaStreamSettings.set_custom_video_track()` to a
+    /// `Room.set_local_media_settings()` function call.
+    ///
+    /// # Errors
+    ///
+    /// - If failed to execute JS statement.
+    /// - If the provided track couldn't be published.
+    pub async fn publish_canvas_video(
+        &self,
+        width: u32,
+        height: u32,
+    ) -> Result<(), Error> {
+        self.execute(Statement::new(
+            // language=JavaScript
+            "
+            async (room) => {
+                const [width, height] = args;
+                const canvas = document.createElement('canvas');
+                canvas.width = width;
+                canvas.height = height;
+                const ctx = canvas.getContext('2d');
+                let frame = 0;
+                const draw = () => {
+                    frame++;
+                    ctx.fillStyle = `hsl(${frame % 360}, 50%, 50%)`;
+                    ctx.fillRect(0, 0, width, height);
+                };
+                draw();
+                setInterval(draw, 1000 / 30);
+                const track = canvas.captureStream(30).getVideoTracks()[0];
+                room.canvasVideoTrack = track;
+
+                let constraints = new rust.MediaStreamSettings();
+                constraints.audio(new window.rust.AudioTrackConstraints());
+                constraints.set_custom_video_track(track);
+                await room.room.set_local_media_settings(
+                    constraints,
+                    false,
+                    true
+                );
+            }
+            ",
+            [width.into(), height.into()],
+        ))
+        .await
+        .map(drop)
+    }
+
+    /// Indicates whether the track published via the
+    /// [`Object::publish_canvas_video()`] method is still live.
+    ///
+    /// # Errors
+    ///
+    /// - If failed to execute JS statement.
+    /// - If failed to parse result as [`bool`].
+    pub async fn is_canvas_video_live(&self) -> Result<bool, Error> {
+        self.execute(Statement::new(
+            // language=JavaScript
+            "
+            async (room) => room.canvasVideoTrack.readyState === 'live'
+            ",
+            [],
+        ))
+        .await?
+        .as_bool()
+        .ok_or(Error::TypeCast)
+    }
+
+    /// Waits for the `Room.on_failed_local_media()` callback to fire the
+    /// provided number of times, returning class names of all the errors it
+    /// has been invoked with.
+    ///
+    /// # Errors
+    ///
+    /// - If failed to execute JS statement.
+    /// - If failed to deserialize the returned error names.
+    pub async fn wait_for_failed_local_media_count(
+        &self,
+        count: u64,
+    ) -> Result<Vec<String>, Error> {
+        let errors = self
+            .execute(Statement::new(
+                // language=JavaScript
+                "
+                async (room) => {
+                    const [count] = args;
+                    const listener = room.onFailedLocalStreamListener;
+                    if (listener.count !== count) {
+                        await new Promise((resolve) => {
+                            listener.subs.push(() => {
+                                if (listener.count === count) {
+                                    resolve();
+                                    return false;
+                                }
+                                return true;
+                            });
+                        });
+                    }
+                    return listener.errors;
+                }
+                ",
+                [count.into()],
+            ))
+            .await?;
+        Ok(serde_json::from_value(errors).map_err(browser::Error::from)?)
+    }
+
+    /// Sets the `AudioFirst` `AdaptivePolicy` with the provided patience (in
+    /// milliseconds) with a `Room.set_adaptive_policy()` function call.
+    ///
+    /// # Errors
+    ///
+    /// If failed to execute JS statement.
+    pub async fn set_audio_first_adaptive_policy(
+        &self,
+        patience_ms: u32,
+    ) -> Result<(), Error> {
+        self.execute(Statement::new(
+            // language=JavaScript
+            "
+            async (room) => {
+                const [patience] = args;
+                room.room.set_adaptive_policy(
+                    window.rust.AdaptivePolicy.audio_first(patience)
+                );
+            }
+            ",
+            [patience_ms.into()],
+        ))
+        .await
+        .map(drop)
+    }
+
+    /// Waits for the `Room.on_adaptation()` callback to fire at least the
+    /// provided number of times, returning all the [`Adaptation`]s it has been
+    /// invoked with.
+    ///
+    /// # Errors
+    ///
+    /// - If failed to execute JS statement.
+    /// - If failed to deserialize the returned [`Adaptation`]s.
+    pub async fn wait_for_adaptations(
+        &self,
+        count: u64,
+    ) -> Result<Vec<Adaptation>, Error> {
+        self.execute_json(Statement::new(
+            // language=JavaScript
+            "
+            async (room) => {
+                const [count] = args;
+                const listener = room.adaptationListener;
+                await window.__medea_e2e.waitUntil(
+                    [listener.subs],
+                    () => listener.adaptations.length >= count
+                );
+                return listener.adaptations;
+            }
+            ",
+            [count.into()],
+        ))
+        .await
+    }
+
+    /// Returns number of times the `Room.on_connection_loss()` callback has
+    /// fired.
+    ///
+    /// # Errors
+    ///
+    /// - If failed to execute JS statement.
+    /// - If failed to parse result as [`u64`].
+    pub async fn connection_loss_count(&self) -> Result<u64, Error> {
+        self.execute(Statement::new(
+            // language=JavaScript
+            "async (room) => room.connLossListener.count",
+            [],
+        ))
+        .await?
+        .as_u64()
+        .ok_or(Error::TypeCast)
+    }
+
+    /// Returns reason the `Room.on_close()` callback has fired with, or
+    /// [`None`] if it hasn't fired yet.
+    ///
+    /// # Errors
+    ///
+    /// - If failed to execute JS statement.
+    /// - If failed to parse result as [`String`].
+    pub async fn close_reason(&self) -> Result<Option<String>, Error> {
+        let reason = self
+            .execute(Statement::new(
+                // language=JavaScript
+                "
+                async (room) => {
+                    const listener = room.closeListener;
+                    return listener.isClosed ?
+                        listener.closeReason.reason() :
+                        null;
+                }
+                ",
+                [],
+            ))
+            .await?;
+        if reason.is_null() {
+            return Ok(None);
+        }
+        reason
+            .as_str()
+            .map(|r| Some(r.to_owned()))
+            .ok_or(Error::TypeCast)
+    }
+
+    /// Indicates whether the reason the `Room.on_close()` callback has fired
+    /// with is considered as an error, or returns [`None`] if it hasn't fired
+    /// yet.
+    ///
+    /// # Errors
+    ///
+    /// - If failed to execute JS statement.
+    /// - If failed to parse result as [`bool`].
+    pub async fn is_close_reason_err(&self) -> Result<Option<bool>, Error> {
+        let is_err = self
+            .execute(Statement::new(
+                // language=JavaScript
+                "
+                async (room) => {
+                    const listener = room.closeListener;
+                    return listener.isClosed ?
+                        listener.closeReason.is_err() :
+                        null;
+                }
+                ",
+                [],
+            ))
+            .await?;
+        if is_err.is_null() {
+            return Ok(None);
+        }
+        is_err.as_bool().map(Some).ok_or(Error::TypeCast)
+    }
+
+    /// Registers new `Room.on_new_connection()`, `Room.on_local_track()` and
+    /// `Connection.on_remote_track_added()` callbacks, returning everything
+    /// they're invoked with right away.
+    ///
+    /// Replaces the callbacks registered on the [`Room`] creation, so nothing
+    /// else should be awaited on this [`Room`] afterwards.
+    ///
+    /// # Errors
+    ///
+    /// - If failed to execute JS statement.
+    /// - If failed to deserialize the returned [`LateCallbacks`].
+    pub async fn register_late_callbacks(
+        &self,
+    ) -> Result<LateCallbacks, Error> {
+        let delivered = self
+            .execute(Statement::new(
+                // language=JavaScript
+                "
+                async (room) => {
+                    let delivered = {
+                        connections: [],
+                        localTracks: 0,
+                        remoteTracks: 0
+                    };
+                    room.room.on_new_connection((conn) => {
+                        delivered.connections.push(
+                            conn.get_remote_member_id()
+                        );
+                        conn.on_remote_track_added(() => {
+                            delivered.remoteTracks++;
+                        });
+                    });
+                    room.room.on_local_track(() => {
+                        delivered.localTracks++;
+                    });
+                    return delivered;
+                }
+                ",
+                [],
+            ))
+            .await?;
+        Ok(serde_json::from_value(delivered).map_err(browser::Error::from)?)
+    }
+
+    /// Registers a new `Room.on_member_media_state_changed()` callback,
+    /// recording all the changes it's invoked with.
+    ///
+    /// # Errors
+    ///
+    /// If failed to execute JS statement.
+    pub async fn record_member_media_states(&self) -> Result<(), Error> {
+        self.execute(Statement::new(
+            // language=JavaScript
+            "
+            async (room) => {
+                room.memberMediaStates = [];
+                room.room.on_member_media_state_changed((change) => {
+                    room.memberMediaStates.push({
+                        memberId: change.member_id(),
+                        kind: change.kind(),
+                        sourceKind: change.media_source_kind(),
+                        state: window.rust.MemberMediaState[change.state()]
+                    });
+                    change.free();
+                });
+            }
+            ",
+            [],
+        ))
+        .await
+        .map(drop)
+    }
+
+    /// Returns names of the `MemberMediaState`s recorded by the
+    /// [`Room::record_member_media_states()`] for the media with the provided
+    /// [`MediaKind`] and [`MediaSourceKind`] of the provided remote `Member`,
+    /// in the order of their recording.
+    ///
+    /// # Errors
+    ///
+    /// - If failed to execute JS statement.
+    /// - If failed to deserialize the recorded states.
+    pub async fn recorded_member_media_states(
+        &self,
+        member_id: &str,
+        kind: MediaKind,
+        source_kind: MediaSourceKind,
+    ) -> Result<Vec<String>, Error> {
+        let states = self
+            .execute(Statement::new(
+                // language=JavaScript
+                &format!(
+                    r#"
+                    async (room) => {{
+                        const [memberId] = args;
+                        return room.memberMediaStates
+                            .filter((c) => c.memberId === memberId &&
+                                c.kind === {kind} &&
+                                c.sourceKind === {source_kind})
+                            .map((c) => c.state);
+                    }}
+                    "#,
+                    kind = kind.as_js(),
+                    source_kind = source_kind.as_js(),
+                ),
+                [member_id.into()],
+            ))
+            .await?;
+        Ok(serde_json::from_value(states).map_err(browser::Error::from)?)
+    }
+
+    /// Registers a new `Room.on_forced_media_state()` callback, recording all
+    /// the `ForcedMediaState`s it's invoked with.
+    ///
+    /// # Errors
+    ///
+    /// If failed to execute JS statement.
+    pub async fn record_forced_media_states(&self) -> Result<(), Error> {
+        self.execute(Statement::new(
+            // language=JavaScript
+            "
+            async (room) => {
+                room.forcedMediaStates = [];
+                room.room.on_forced_media_state((forced) => {
+                    room.forcedMediaStates.push({
+                        kind: forced.kind(),
+                        sourceKind: forced.media_source_kind(),
+                        state: window.rust.LocalMediaState[forced.state()],
+                        byServer: forced.by_server()
+                    });
+                    forced.free();
+                });
+            }
+            ",
+            [],
+        ))
+        .await
+        .map(drop)
+    }
+
+    /// Returns [`ForcedMediaState`]s recorded by the
+    /// [`Room::record_forced_media_states()`] for the local media with the
+    /// provided [`MediaKind`] and [`MediaSourceKind`], in the order of their
+    /// recording.
+    ///
+    /// # Errors
+    ///
+    /// - If failed to execute JS statement.
+    /// - If failed to deserialize the recorded [`ForcedMediaState`]s.
+    pub async fn recorded_forced_media_states(
+        &self,
+        kind: MediaKind,
+        source_kind: MediaSourceKind,
+    ) -> Result<Vec<ForcedMediaState>, Error> {
+        self.execute_json(Statement::new(
+            // language=JavaScript
+            &format!(
+                r#"
+                async (room) => {{
+                    return room.forcedMediaStates
+                        .filter((s) => s.kind === {kind} &&
+                            s.sourceKind === {source_kind})
+                        .map((s) => ({{
+                            state: s.state,
+                            byServer: s.byServer
+                        }}));
+                }}
+                "#,
+                kind = kind.as_js(),
+                source_kind = source_kind.as_js(),
+            ),
+            [],
+        ))
+        .await
+    }
+
+    /// Unmutes the provided [`MediaKind`] in this [`Room`], returning a name
+    /// of the `MediaStateTransitionExceptionKind` (or a name of the thrown
+    /// exception, if it's another one) it fails with, if it does.
+    ///
+    /// # Errors
+    ///
+    /// - If failed to execute JS statement.
+    /// - If failed to deserialize the result.
+    pub async fn unmute_media_error(
+        &self,
+        kind: MediaKind,
+    ) -> Result<Option<String>, Error> {
+        let unmute = match kind {
+            MediaKind::Audio => "r.room.unmute_audio()",
+            MediaKind::Video => "r.room.unmute_video()",
+        };
+        self.execute_json(Statement::new(
+            // language=JavaScript
+            &format!(
+                r#"
+                async (r) => {{
+                    try {{
+                        await {unmute};
+                        return null;
+                    }} catch (e) {{
+                        if (e instanceof
+                            window.rust.MediaStateTransitionException) {{
+                            return window.rust
+                                .MediaStateTransitionExceptionKind[e.kind()];
+                        }}
+                        return e.constructor.name;
+                    }}
+                }}
+                "#,
+            ),
+            [],
+        ))
+        .await
+    }
+
+    /// Registers a new `Room.on_join_progress()` callback, recording all the
+    /// `JoinPhase`s it's invoked with.
+    ///
+    /// # Errors
+    ///
+    /// If failed to execute JS statement.
+    pub async fn record_join_progress(&self) -> Result<(), Error> {
+        self.execute(Statement::new(
+            // language=JavaScript
+            "
+            async (room) => {
+                room.joinPhases = [];
+                room.room.on_join_progress((update) => {
+                    room.joinPhases.push({
+                        phase: window.rust.JoinPhase[update.phase()],
+                        timestamp: update.timestamp()
+                    });
+                    update.free();
+                });
+            }
+            ",
+            [],
+        ))
+        .await
+        .map(drop)
+    }
+
+    /// Returns names of the `JoinPhase`s recorded by the
+    /// [`Room::record_join_progress()`], in the order of their recording.
+    ///
+    /// # Errors
+    ///
+    /// - If failed to execute JS statement.
+    /// - If the recorded timestamps go backwards.
+    /// - If failed to deserialize the recorded phases.
+    pub async fn recorded_join_phases(&self) -> Result<Vec<String>, Error> {
+        let phases = self
+            .execute(Statement::new(
+                // language=JavaScript
+                "
+                async (room) => {
+                    room.joinPhases.forEach((p, i, all) => {
+                        if (i > 0 && p.timestamp < all[i - 1].timestamp) {
+                            throw new Error(
+                                `${p.phase} reported before ${all[i - 1].phase}`
+                            );
+                        }
+                    });
+                    return room.joinPhases.map((p) => p.phase);
+                }
+                ",
+                [],
+            ))
+            .await?;
+        Ok(serde_json::from_value(phases).map_err(browser::Error::from)?)
+    }
+
+    /// Removes all local `LocalMediaTrack`s from the JS side.
+    ///
+    /// # Panics
+    ///
+    /// If failed to execute JS statement.
+    pub async fn forget_local_tracks(&self) {
+        self.execute(Statement::new(
+            // language=JavaScript
+            "
+            async (room) => {
+                room.localTracksStore.tracks.forEach((t) => t.track.free());
+                room.localTracksStore.tracks = [];
+            }
+            ",
+            [],
+        ))
+        .await
+        .map(drop)
+        .unwrap();
+    }
+}
+
+/// Error of parsing a [`MediaKind`] or a [`MediaSourceKind`].
+#[derive(Clone, Copy, Debug)]
+pub struct ParsingFailedError;
+
+/// Transition of the outbound video of a [`Room`] reported by its
+/// `Room.on_adaptation()` callback.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Adaptation {
+    /// Maximum bitrate (in bits per second) the outbound video is encoded
+    /// with, if it's limited.
+    pub max_bitrate: Option<u32>,
+
+    /// Indicates whether the outbound video encoding is paused.
+    pub is_video_paused: bool,
+}
+
+impl Adaptation {
+    /// Indicates whether this [`Adaptation`] degrades the outbound video.
+    #[must_use]
+    pub const fn is_degraded(&self) -> bool {
+        self.max_bitrate.is_some() || self.is_video_paused
+    }
+}
+
+/// State of the local media of a [`Room`] reported by its
+/// `Room.on_forced_media_state()` callback.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ForcedMediaState {
+    /// Name of the forced `LocalMediaState`.
+    pub state: String,
+
+    /// Indicates whether the state is forced by a media server.
+    pub by_server: bool,
+}
+
+/// State of the local media of a single [`MediaKind`] and
+/// [`MediaSourceKind`] in a [`MediaState`].
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
+pub struct SendMediaState {
+    /// Indicates whether publishing of the media is enabled.
+    pub enabled: bool,
+
+    /// Indicates whether the media is muted.
+    pub muted: bool,
+}
+
+/// Current media states of a [`Room`] returned by its `Room.media_state()`
+/// method.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
+pub struct MediaState {
+    /// [`SendMediaState`] of the local device audio.
+    pub send_device_audio: SendMediaState,
+
+    /// [`SendMediaState`] of the local display audio.
+    pub send_display_audio: SendMediaState,
+
+    /// [`SendMediaState`] of the local device video.
+    pub send_device_video: SendMediaState,
+
+    /// [`SendMediaState`] of the local display video.
+    pub send_display_video: SendMediaState,
+
+    /// Indicates whether receiving of the remote audio is enabled.
+    pub recv_audio: bool,
+
+    /// Indicates whether receiving of the remote device video is enabled.
+    pub recv_device_video: bool,
+
+    /// Indicates whether receiving of the remote display video is enabled.
+    pub recv_display_video: bool,
+}
+
+impl MediaState {
+    /// Returns [`SendMediaState`] of the local media of the provided
+    /// [`MediaKind`] and [`MediaSourceKind`].
+    #[must_use]
+    pub const fn send(
+        &self,
+        kind: MediaKind,
+        source: MediaSourceKind,
+    ) -> SendMediaState {
+        match (kind, source) {
+            (MediaKind::Audio, MediaSourceKind::Device) => {
+                self.send_device_audio
+            }
+            (MediaKind::Audio, MediaSourceKind::Display) => {
+                self.send_display_audio
+            }
+            (MediaKind::Video, MediaSourceKind::Device) => {
+                self.send_device_video
+            }
+            (MediaKind::Video, MediaSourceKind::Display) => {
+                self.send_display_video
+            }
+        }
+    }
+
+    /// Indicates whether receiving of the remote media of the provided
+    /// [`MediaKind`] and [`MediaSourceKind`] is enabled.
+    #[must_use]
+    pub const fn recv(&self, kind: MediaKind, source: MediaSourceKind) -> bool {
+        match (kind, source) {
+            (MediaKind::Audio, _) => self.recv_audio,
+            (MediaKind::Video, MediaSourceKind::Device) => {
+                self.recv_device_video
+            }
+            (MediaKind::Video, MediaSourceKind::Display) => {
+                self.recv_display_video
+            }
+        }
+    }
+}
+
+/// Statistics of all the `RTCPeerConnection`s of a [`Room`].
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+pub struct RtcStats {
+    /// Statistics of the received RTP streams.
+    pub inbound: Vec<RtpStreamStats>,
+
+    /// Statistics of the sent RTP streams.
+    pub outbound: Vec<RtpStreamStats>,
+}
+
+impl RtcStats {
+    /// Returns the total bitrate (in bits per second) of the sent video with
+    /// the provided [`MediaSourceKind`].
+    ///
+    /// If [`None`] is provided, then the bitrate of all the sent video is
+    /// returned.
+    #[must_use]
+    pub fn outbound_video_bitrate(
+        &self,
+        source_kind: Option<MediaSourceKind>,
+    ) -> f64 {
+        self.outbound_video(source_kind).map(|s| s.bitrate).sum()
+    }
+
+    /// Returns the total bitrate (in bits per second) of the sent audio.
+    #[must_use]
+    pub fn outbound_audio_bitrate(&self) -> f64 {
+        self.outbound
+            .iter()
+            .filter(|s| s.kind == MediaKind::Audio)
+            .map(|s| s.bitrate)
+            .sum()
+    }
+
+    /// Returns the largest frame size (as `(width, height)`) of the sent video
+    /// with the provided [`MediaSourceKind`].
+    ///
+    /// If [`None`] is provided, then all the sent video is considered.
+    #[must_use]
+    pub fn max_outbound_frame_size(
+        &self,
+        source_kind: Option<MediaSourceKind>,
+    ) -> Option<(u64, u64)> {
+        self.outbound_video(source_kind)
+            .filter_map(|s| s.frame_width.zip(s.frame_height))
+            .max_by_key(|(w, h)| w * h)
+    }
+
+    /// Returns an [`Iterator`] over the sent video [`RtpStreamStats`] with the
+    /// provided [`MediaSourceKind`].
+    fn outbound_video(
+        &self,
+        source_kind: Option<MediaSourceKind>,
+    ) -> impl Iterator<Item = &RtpStreamStats> {
+        self.outbound.iter().filter(move |s| {
+            s.kind == MediaKind::Video
+                && source_kind.map_or(true, |k| s.source_kind == Some(k))
+        })
+    }
+}
+
+/// Statistics of a single sent or received RTP stream.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RtpStreamStats {
+    /// [`MediaKind`] of this RTP stream.
+    pub kind: MediaKind,
+
+    /// [`MediaSourceKind`] of this RTP stream.
+    ///
+    /// Known for the sent RTP streams only.
+    pub source_kind: Option<MediaSourceKind>,
+
+    /// Bitrate of this RTP stream in bits per second.
+    pub bitrate: f64,
+
+    /// Width of the last encoded or decoded frame, if any.
+    pub frame_width: Option<u64>,
+
+    /// Height of the last encoded or decoded frame, if any.
+    pub frame_height: Option<u64>,
+}
+
+#[cfg(test)]
+mod spec {
+    use super::{parse_track_kinds, MediaKind, MediaSourceKind};
+
+    #[test]
+    fn converts_media_kinds() {
+        for kind in MediaKind::ALL {
+            assert_eq!(MediaKind::from_name(kind.name()).unwrap(), kind);
+        }
+        assert!(MediaKind::from_name("Audio").is_err());
+        assert!(MediaKind::from_name("audio video").is_err());
+    }
+
+    #[test]
+    fn converts_media_source_kinds() {
+        for kind in MediaSourceKind::ALL {
+            assert_eq!(MediaSourceKind::from_name(kind.name()).unwrap(), kind);
+        }
+        assert!(MediaSourceKind::from_name("screen").is_err());
+    }
+
+    #[test]
+    fn parses_track_kinds() {
+        assert_eq!(
+            parse_track_kinds("audio").unwrap(),
+            (MediaKind::Audio, MediaSourceKind::Device),
+        );
+        assert_eq!(
+            parse_track_kinds("device video").unwrap(),
+            (MediaKind::Video, MediaSourceKind::Device),
+        );
+        assert_eq!(
+            parse_track_kinds("display video").unwrap(),
+            (MediaKind::Video, MediaSourceKind::Display),
+        );
+        assert!(parse_track_kinds("display-video").is_err());
+        assert!(parse_track_kinds("screen video").is_err());
+    }
+}
@@ -3,6 +3,8 @@
 
 use std::marker::PhantomData;
 
+use serde::Deserialize;
+
 use crate::{
     browser::Statement,
     object::{
@@ -21,6 +23,16 @@ pub type Local = TracksStore<LocalTrack>;
 /// Shortcut for a [`TracksStore`] of [`RemoteTrack`]s.
 pub type Remote = TracksStore<RemoteTrack>;
 
+/// Counts of live and stopped tracks in a [`TracksStore`].
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
+pub struct LivenessCounts {
+    /// Number of tracks not stopped yet.
+    pub live: u64,
+
+    /// Number of stopped tracks.
+    pub stopped: u64,
+}
+
 /// Store for [`LocalTrack`]s or [`RemoteTrack`]s.
 #[derive(Debug)]
 pub struct TracksStore<T>(PhantomData<T>);
@@ -31,16 +43,14 @@ impl<T> Object<TracksStore<T>> {
     /// # Errors
     ///
     /// - If failed to execute JS statement.
-    /// - If failed to parse result as [`u64`].
+    /// - If failed to deserialize result as [`u64`].
     pub async fn count(&self) -> Result<u64, Error> {
-        self.execute(Statement::new(
+        self.execute_json(Statement::new(
             // language=JavaScript
             "async (store) => store.tracks.length",
             [],
         ))
-        .await?
-        .as_u64()
-        .ok_or(Error::TypeCast)
+        .await
     }
 
     /// Waits this [`TracksStore`] to contain `count` tracks.
@@ -58,22 +68,10 @@ impl<T> Object<TracksStore<T>> {
             "
             async (store) => {
                 const [neededCount] = args;
-                let currentCount = store.tracks.length;
-                if (currentCount === neededCount) {
-                    return;
-                } else {
-                    let waiter = new Promise((resolve) => {
-                        store.subs.push(() => {
-                            currentCount += 1;
-                            if (currentCount === neededCount) {
-                                resolve();
-                                return false;
-                            }
-                            return true;
-                        });
-                    });
-                    await waiter;
-                }
+                await window.__medea_e2e.waitUntil(
+                    [store.subs],
+                    () => store.tracks.length === neededCount
+                );
             }
             ",
             [count.into()],
@@ -88,54 +86,27 @@ impl<T> Object<TracksStore<T>> {
     /// # Errors
     ///
     /// - If failed to execute JS statement.
-    /// - If failed to parse result as [`bool`].
+    /// - If failed to deserialize result as [`bool`].
     pub async fn has_track(
         &self,
         kind: MediaKind,
         source_kind: Option<MediaSourceKind>,
     ) -> Result<bool, Error> {
-        let source_kind_js =
-            source_kind.map_or("undefined", MediaSourceKind::as_js);
-        let kind_js = Statement::new(
+        self.execute_json(Statement::new(
             // language=JavaScript
             &format!(
-                r#"
-                async (store) => {{
-                    return {{
-                        store: store,
-                        kind: {kind},
-                        sourceKind: {source_kind_js}
-                    }};
-                }}
-                "#,
-                kind = kind.as_js()
+                "
+                async (store) => window.__medea_e2e.findTrack(
+                    store, {kind}, {source_kind}
+                ) !== undefined
+                ",
+                kind = kind.as_js(),
+                source_kind =
+                    source_kind.map_or("undefined", MediaSourceKind::as_js),
             ),
             [],
-        );
-
-        self.execute(kind_js.and_then(Statement::new(
-            // language=JavaScript
-            "
-            async (meta) => {
-                for (track of meta.store.tracks) {
-                    if (track.track.kind() === meta.kind &&
-                        (
-                            track.track.media_source_kind()  ===
-                            meta.sourceKind ||
-                            meta.sourceKind === undefined
-                        )
-                    ) {
-                        return true;
-                    }
-                }
-                return false;
-             }
-            ",
-            [],
-        )))
-        .await?
-        .as_bool()
-        .ok_or(Error::TypeCast)
+        ))
+        .await
     }
 
     /// Returns a track from this [`TracksStore`] with the provided
@@ -149,86 +120,178 @@ impl<T> Object<TracksStore<T>> {
         kind: MediaKind,
         source_kind: MediaSourceKind,
     ) -> Result<Object<T>, Error> {
-        let kind_js = Statement::new(
+        self.execute_and_fetch(get_track_statement(kind, source_kind))
+            .await
+    }
+
+    /// Returns count of tracks by the provided `live` values.
+    ///
+    /// # Errors
+    ///
+    /// - If failed to execute JS statement.
+    /// - If failed to deserialize result as [`LivenessCounts`].
+    pub async fn count_tracks_by_live(&self, live: bool) -> Result<u64, Error> {
+        let counts = self.count_by_liveness().await?;
+        Ok(if live { counts.live } else { counts.stopped })
+    }
+
+    /// Returns [`LivenessCounts`] of the tracks in this [`TracksStore`].
+    ///
+    /// # Errors
+    ///
+    /// - If failed to execute JS statement.
+    /// - If failed to deserialize result as [`LivenessCounts`].
+    pub async fn count_by_liveness(&self) -> Result<LivenessCounts, Error> {
+        self.execute_json(Statement::new(
             // language=JavaScript
-            &format!(
-                r#"
-                async (store) => {{
-                    return {{
-                        store: store,
-                        kind: {kind},
-                        sourceKind: {source_kind}
-                    }};
-                }}
-                "#,
-                source_kind = source_kind.as_js(),
-                kind = kind.as_js()
-            ),
+            "
+            async (store) => {
+                const stopped = store.tracks
+                    .filter((track) => track.stopped)
+                    .length;
+                return {
+                    live: store.tracks.length - stopped,
+                    stopped: stopped
+                };
+            }
+            ",
             [],
-        );
+        ))
+        .await
+    }
 
-        self.execute_and_fetch(kind_js.and_then(Statement::new(
+    /// Waits this [`TracksStore`] to contain `count` tracks with the provided
+    /// `live` value.
+    ///
+    /// # Errors
+    ///
+    /// If failed to execute JS statement.
+    pub async fn wait_for_count_by_live(
+        &self,
+        live: bool,
+        count: u64,
+    ) -> Result<(), Error> {
+        self.execute(Statement::new(
             // language=JavaScript
             "
-            async (meta) => {
-                for (track of meta.store.tracks) {
-                    let kind = track.track.kind();
-                    let sourceKind = track.track.media_source_kind();
-                    if (kind === meta.kind
-                        && sourceKind === meta.sourceKind) {
-                        return track;
-                    }
-                }
-                let waiter = new Promise((resolve) => {
-                    meta.store.subs.push((track) => {
-                        let kind = track.track.kind();
-                        let sourceKind =
-                            track.track.media_source_kind();
-                        if (kind === meta.kind
-                            && sourceKind === meta.sourceKind) {
-                            resolve(track);
-                            return false;
-                        } else {
-                            return true;
-                        }
-                    });
-                });
-                return await waiter;
+            async (store) => {
+                const [live, neededCount] = args;
+                await window.__medea_e2e.waitUntil(
+                    [store.subs, store.stoppedSubs],
+                    () => store.tracks
+                        .filter((t) => live ? !t.stopped : t.stopped)
+                        .length === neededCount
+                );
             }
             ",
+            [live.into(), count.into()],
+        ))
+        .await
+        .map(drop)
+    }
+
+    /// Waits for all the tracks with the provided [`MediaKind`] and
+    /// [`MediaSourceKind`] in this [`TracksStore`] to be stopped.
+    ///
+    /// Waits for such a track to appear if there is none at the moment.
+    ///
+    /// # Errors
+    ///
+    /// If failed to execute JS statement.
+    pub async fn wait_for_track_stopped(
+        &self,
+        kind: MediaKind,
+        source_kind: MediaSourceKind,
+    ) -> Result<(), Error> {
+        self.execute(Statement::new(
+            // language=JavaScript
+            &format!(
+                "
+                async (store) => {{
+                    const ns = window.__medea_e2e;
+                    await ns.waitUntil(
+                        [store.subs, store.stoppedSubs],
+                        () => {{
+                            const tracks = store.tracks.filter((track) => {{
+                                return ns.matchesTrack(
+                                    track, {kind}, {source_kind}
+                                );
+                            }});
+                            return tracks.length > 0
+                                && tracks.every((track) => track.stopped);
+                        }}
+                    );
+                }}
+                ",
+                kind = kind.as_js(),
+                source_kind = source_kind.as_js(),
+            ),
             [],
-        )))
+        ))
         .await
+        .map(drop)
     }
 
-    /// Returns count of tracks by the provided `live` values.
+    /// Returns count of tracks with the provided [`MediaKind`] stored in this
+    /// [`TracksStore`].
     ///
     /// # Errors
     ///
     /// - If failed to execute JS statement.
-    /// - If failed to parse result as [`u64`].
-    pub async fn count_tracks_by_live(&self, live: bool) -> Result<u64, Error> {
-        self.execute(Statement::new(
+    /// - If failed to deserialize result as [`u64`].
+    pub async fn count_by_kind(&self, kind: MediaKind) -> Result<u64, Error> {
+        self.execute_json(Statement::new(
             // language=JavaScript
             &format!(
                 r#"
                 async (store) => {{
-                    let count = 0;
-                    for (track of store.tracks) {{
-                        if ({live} && !track.stopped) {{
-                            count++;
-                        }} else if (!{live} && track.stopped) {{
-                            count++;
-                        }}
-                    }}
-                    return count;
+                    return store.tracks
+                        .filter((track) => track.track.kind() === {kind})
+                        .length;
                 }}
                 "#,
+                kind = kind.as_js(),
             ),
             [],
         ))
-        .await?
-        .as_u64()
-        .ok_or(Error::TypeCast)
+        .await
+    }
+}
+
+/// Returns a [`Statement`] resolving into a track from a [`TracksStore`] with
+/// the provided [`MediaKind`] and [`MediaSourceKind`], awaiting for it to
+/// appear if there is none.
+fn get_track_statement(
+    kind: MediaKind,
+    source_kind: MediaSourceKind,
+) -> Statement {
+    Statement::new(
+        // language=JavaScript
+        &format!(
+            "async (store) => await window.__medea_e2e.waitForTrack(\
+                store, {kind}, {source_kind}\
+            )",
+            kind = kind.as_js(),
+            source_kind = source_kind.as_js(),
+        ),
+        [],
+    )
+}
+
+#[cfg(test)]
+mod spec {
+    use crate::object::room::{MediaKind, MediaSourceKind};
+
+    use super::get_track_statement;
+
+    #[test]
+    fn get_track_statement_uses_helpers() {
+        let stmt =
+            get_track_statement(MediaKind::Video, MediaSourceKind::Display);
+        assert!(stmt.requires_helpers());
+
+        // Inlined matching and subscription code used to take over 1 KiB.
+        let source = stmt.source();
+        assert!(source.len() < 200, "{} bytes:\n{source}", source.len());
     }
 }
@@ -1,7 +1,8 @@
 //! All configurable properties of E2E tests runner.
 
-use std::env;
+use std::{env, time::Duration};
 
+use medea_e2e::browser::Browser;
 use once_cell::sync::Lazy;
 
 /// Generates static config variable which will be lazily obtained from the
@@ -55,15 +56,110 @@ env_var!(
         || "127.0.0.1:30000"
 );
 
+env_var!(
+    /// URL of a TURN server used by ICE servers probing scenarios.
+    ///
+    /// Default: `turn:127.0.0.1:3478`
+    TURN_URL
+        || "turn:127.0.0.1:3478"
+);
+
+env_var!(
+    /// Username of a TURN server used by ICE servers probing scenarios.
+    ///
+    /// Default: `test`
+    TURN_USERNAME
+        || "test"
+);
+
+env_var!(
+    /// Credential of a TURN server used by ICE servers probing scenarios.
+    ///
+    /// Default: `test`
+    TURN_CREDENTIAL
+        || "test"
+);
+
 env_var!(
     /// Path to a Cucumber features which should be run.
     FEATURES_PATH
         || "tests/features"
 );
 
+/// [`Browser`] to run tests in.
+///
+/// Default: chosen by a [WebDriver] server.
+///
+/// [WebDriver]: https://w3.org/TR/webdriver
+pub static BROWSER: Lazy<Option<Browser>> = Lazy::new(|| {
+    env::var("BROWSER")
+        .ok()
+        .filter(|v| !v.is_empty())
+        .map(|v| v.parse().unwrap())
+});
+
+/// Maximum number of scenarios to run concurrently.
+///
+/// Default: `4` if a [WebDriver] server supports multiple sessions, otherwise
+/// `1`.
+///
+/// [WebDriver]: https://w3.org/TR/webdriver
+pub static CONCURRENCY: Lazy<Option<usize>> =
+    Lazy::new(|| env::var("CONCURRENCY").ok().and_then(|v| v.parse().ok()));
+
 /// Indicator whether tests should run in a headless browser's mode.
 ///
 /// Default: `true`
 pub static HEADLESS: Lazy<bool> = Lazy::new(|| {
     env::var("HEADLESS").map_or(true, |v| v.to_ascii_lowercase() == "true")
 });
+
+/// Default maximum duration (in seconds) of a JS statement execution in a
+/// browser.
+///
+/// Default: `60`
+pub static EXECUTE_TIMEOUT: Lazy<Duration> = Lazy::new(|| {
+    Duration::from_secs(
+        env::var("EXECUTE_TIMEOUT")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(60),
+    )
+});
+
+/// Maximum duration (in seconds) of awaiting a `Connection` with a remote
+/// member to be established.
+///
+/// Default: `30`
+pub static CONNECTION_TIMEOUT: Lazy<Duration> = Lazy::new(|| {
+    Duration::from_secs(
+        env::var("CONNECTION_TIMEOUT")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(30),
+    )
+});
+
+/// Maximum duration (in milliseconds) of gathering ICE candidates while
+/// probing ICE servers.
+///
+/// Default: `5000`
+pub static ICE_PROBE_TIMEOUT_MS: Lazy<u64> = Lazy::new(|| {
+    env::var("ICE_PROBE_TIMEOUT_MS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(5000)
+});
+
+/// Maximum duration (in seconds) of awaiting media statistics of a member to
+/// satisfy an expected condition.
+///
+/// Default: `30`
+pub static STATS_TIMEOUT: Lazy<Duration> = Lazy::new(|| {
+    Duration::from_secs(
+        env::var("STATS_TIMEOUT")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(30),
+    )
+});
@@ -0,0 +1,7 @@
+Feature: Adaptive policy
+
+  Scenario: `AudioFirst` policy degrades quality-limited video
+    Given room with joined members Alice and Bob
+    When Alice's `Room` has `AudioFirst` adaptive policy
+    And Alice's outbound video bitrate is capped at 10000 bps
+    Then Alice's `Room.on_adaptation()` fires with degraded video
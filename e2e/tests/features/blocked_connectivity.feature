@@ -0,0 +1,13 @@
+Feature: Blocked P2P connectivity
+
+  Scenario: Relayed connection fails while blocked and recovers after unblocking
+    Given media is force relayed
+    And members Alice and Bob
+    And P2P connectivity between Alice and Bob is blocked
+    When Alice joins the room
+    And Bob joins the room
+    Then Alice's peer connection fails
+    When P2P connectivity between Alice and Bob is unblocked
+    Then Alice's peer connection connects
+    And Bob's peer connection connects
+    And Bob's audio remote track from Alice is enabled
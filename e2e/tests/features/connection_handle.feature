@@ -0,0 +1,10 @@
+Feature: `ConnectionHandle` lifetime
+
+  Scenario: Freed `ConnectionHandle` doesn't affect connection
+    Given room with joined member Alice
+    And member Bob with disabled video publishing
+    When Bob joins the room
+    And Alice frees her connection handle with Bob
+    And Bob enables video and awaits it completes
+    Then Alice's device video remote track from Bob is enabled
+    And Alice's audio remote track from Bob is enabled
@@ -0,0 +1,23 @@
+Feature: `RoomHandle.connections()` enumeration
+
+  Scenario: Established connections are enumerated
+    Given room with joined members Alice and Bob
+    And member Carol
+    When Carol joins the room
+    Then Alice's room enumerates 2 connections
+    And Carol's room enumerates 2 connections
+
+  Scenario: Freeing enumerated handles doesn't affect connection
+    Given room with joined member Alice
+    And member Bob with disabled video publishing
+    When Bob joins the room
+    Then Alice's room enumerates 1 connection
+    When Bob enables video and awaits it completes
+    Then Alice's device video remote track from Bob is enabled
+    And Alice's audio remote track from Bob is enabled
+
+  Scenario: Closed connection isn't enumerated
+    Given room with joined members Alice and Bob
+    When Bob's room closed by client
+    Then Alice's connection with Bob closes
+    And Alice's room enumerates 0 connections
@@ -68,3 +68,11 @@ Feature: Create endpoint
     When Control API deletes Bob's publish endpoint
     And Control API starts Bob's media publishing to Alice
     Then Alice has 2 live remote tracks from Bob
+
+  Scenario: Viewer becomes a publisher
+    Given room with joined member Alice and Bob with no WebRTC endpoints
+    When Control API starts Alice's media publishing to Bob
+    And Control API adds publish endpoint to Bob
+    And Control API adds play endpoint to Alice from Bob
+    Then Alice has audio and video remote tracks from Bob
+    And Bob has audio and video remote tracks from Alice
@@ -0,0 +1,12 @@
+Feature: Custom video track
+
+  Scenario: Partner receives video of a custom track
+    Given room with joined member Alice and Bob
+    When Alice publishes 320x240 canvas video
+    Then Bob receives 320x240 device video from Alice
+
+  Scenario: Custom track is not stopped once room is closed
+    Given room with joined member Alice and Bob
+    When Alice publishes 320x240 canvas video
+    And Alice's room closed by client
+    Then Alice's canvas video track is live
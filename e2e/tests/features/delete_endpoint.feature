@@ -15,6 +15,11 @@ Feature: Delete endpoint
       | tracks |
       | 3      |
 
+  Scenario: Remote video track stops when WebRtcPublishEndpoint is deleted
+    Given room with joined member Alice and Bob
+    When Control API deletes Alice's publish endpoint
+    Then Bob's remote device video track from Alice stops
+
   Scenario Outline: Control API deletes WebRtcPlayEndpoint
     Given room with joined member Alice and Bob
     When Control API deletes Alice's play endpoint with Bob
@@ -0,0 +1,20 @@
+Feature: Media input devices capabilities
+
+  Scenario: Camera capabilities are reported without capturing it
+    Given room with member Alice
+    And Alice's cameras report 1280x720 resolution at 30 fps
+    Then Alice's camera capabilities are 1280x720 at 30 fps facing user
+    And Alice makes no `getUserMedia()` requests
+
+  Scenario: Camera is captured briefly if its capabilities are not reported
+    Given room with member Alice
+    And Alice's cameras report no capabilities
+    Then Alice's camera capabilities report its frame rate range
+    And Alice makes 1 `getUserMedia()` request
+
+  Scenario: Queried camera capabilities are cached
+    Given room with member Alice
+    And Alice's cameras report no capabilities
+    Then Alice's camera capabilities report its frame rate range
+    And Alice's camera capabilities report its frame rate range
+    And Alice makes 1 `getUserMedia()` request
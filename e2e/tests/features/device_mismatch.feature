@@ -0,0 +1,19 @@
+Feature: Captured device mismatch
+
+  Scenario: Ideal device is recaptured with exact constraint
+    Given room with member Alice
+    And Alice's browser ignores ideal microphone deviceId
+    And Alice's default microphone is `fake-mic`
+    And joined member Bob
+    When Alice joins the room
+    Then Alice's audio local track is captured from `fake-mic`
+    And Alice's audio local track reports no device mismatch
+
+  Scenario: Mismatch is reported once exact constraint doesn't help
+    Given room with member Alice
+    And Alice's browser ignores microphone deviceId
+    And Alice's default microphone is `fake-mic`
+    And joined member Bob
+    When Alice joins the room
+    Then Alice's audio local track is captured from `default`
+    And Alice's audio local track reports device mismatch of `fake-mic` with `default`
@@ -113,3 +113,44 @@ Feature: Remote media disabling
     And Alice enables audio receiving from Carol
     Then Alice's audio remote track from Bob is enabled
     And Alice's audio remote track from Carol is enabled
+
+  Scenario: Remote audio disabled via `Room` arrives disabled from newly joined member
+    Given room with joined members Alice and Bob
+    And member Carol
+    When Alice disables remote audio
+    And Carol joins the room
+    Then Alice's audio remote track from Carol arrives disabled
+    And Alice's audio remote track from Bob is disabled
+    And Alice's device video remote track from Carol is enabled
+
+  Scenario: Remote video disabled via `Room` arrives disabled from newly joined member
+    Given room with joined members Alice and Bob
+    And member Carol
+    When Alice disables remote video
+    And Carol joins the room
+    Then Alice's device video remote track from Carol arrives disabled
+    And Alice's audio remote track from Carol is enabled
+
+  Scenario: Remote audio enabled back via `Room` arrives enabled from newly joined member
+    Given room with joined members Alice and Bob
+    And member Carol
+    When Alice disables remote audio
+    And Alice enables remote audio
+    And Carol joins the room
+    Then Alice's audio remote track from Carol arrives enabled
+
+  @mesh
+  Scenario: Video not received from concrete `Connection` is never sent to it
+    Given room with joined members Alice, Bob and Carol with disabled video publishing
+    When Alice stops receiving video from Bob
+    And Bob enables video and awaits it completes
+    Then Bob sends video to Carol
+    And Bob doesn't send video to Alice
+
+  @mesh
+  Scenario: Video received back from concrete `Connection` is sent to it
+    Given room with joined members Alice, Bob and Carol with disabled video publishing
+    When Alice stops receiving video from Bob
+    And Bob enables video and awaits it completes
+    And Alice starts receiving video from Bob
+    Then Bob sends video to Alice
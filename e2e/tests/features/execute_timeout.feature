@@ -0,0 +1,5 @@
+Feature: JS statements execution timeout
+
+  Scenario: Never resolving statement fails with timeout
+    Given room with member Alice
+    Then never resolving statement on Alice's room times out
@@ -0,0 +1,29 @@
+Feature: Media state forced by server
+
+  Scenario: Member is notified about audio muted by server
+    Given room with joined members Alice and Bob
+    When Alice records forced media states
+    And Medea forces Alice's audio to be muted
+    Then Alice observes `Muted` server-forced states of local audio
+    And Alice's media state is consistent with her local tracks
+
+  Scenario: Member is notified about video muted and unmuted by server
+    Given room with joined members Alice and Bob
+    When Alice records forced media states
+    And Medea forces Alice's video to be muted
+    And Medea forces Alice's video to be unmuted
+    Then Alice observes `Muted, Unmuted` server-forced states of local device video
+
+  Scenario: Unmuting audio muted by server is rejected
+    Given room with joined members Alice and Bob
+    When Medea forces Alice's audio to be muted
+    Then Alice's audio local track is muted
+    When Medea ignores Alice's `UpdateTracks` commands
+    Then Alice's audio unmuting fails with `ForbiddenByServer`
+
+  Scenario: Media state is consistent with local tracks
+    Given room with joined members Alice and Bob
+    When Alice mutes video and awaits it completes
+    Then Alice's media state is consistent with her local tracks
+    When Alice disables audio and awaits it completes
+    Then Alice's media state is consistent with her local tracks
@@ -0,0 +1,10 @@
+Feature: Remote video freeze detection
+
+  @chrome
+  Scenario: Remote video freezes while its sender pauses encoding
+    Given room with joined members Alice and Bob
+    And Bob's device video remote track from Alice watches freezes
+    When Alice pauses video encoding
+    Then Bob's device video remote track from Alice freezes
+    When Alice resumes video encoding
+    Then Bob's device video remote track from Alice unfreezes
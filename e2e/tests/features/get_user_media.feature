@@ -7,6 +7,14 @@ Feature: `getUserMedia()` requests
     When Alice joins the room
     Then Alice's `Room.on_failed_local_stream()` fires 1 time
 
+  Scenario: Member joins Room and its `getUserMedia()` error is reported
+    Given room with member Alice
+    And Alice's `getUserMedia()` errors
+    And joined member Bob
+    When Alice joins the room
+    Then Alice's `Room.on_failed_local_media()` fires 1 time with `LocalMediaInitException`
+    And Alice's `Room.on_close()` doesn't fire
+
   @mesh
   Scenario: Member tries to enable media publishing and its `getUserMedia()` errors
     Given room with joined member Alice and Bob with disabled media publishing
@@ -27,3 +35,49 @@ Feature: `getUserMedia()` requests
     When Alice switches device with latency
     And Alice disables video and awaits it completes
     Then `on_disabled` callback fires 1 time on Bob's remote device video track from Alice
+
+  Scenario: `getUserMedia()` rejects with `NotAllowedError`
+    Given room with member Alice
+    And Alice's `getUserMedia()` audio fails with `NotAllowedError`
+    And joined member Bob
+    When Alice joins the room
+    Then Alice's `Room.on_failed_local_stream()` fires 1 time
+
+  @mesh
+  Scenario: `getUserMedia()` recovers after `NotReadableError`
+    Given room with joined member Alice and Bob with disabled media publishing
+    And Alice's `getUserMedia()` audio fails with `NotReadableError` 1 time
+    When Alice enables audio and awaits it errors
+    And Alice enables audio and awaits it completes
+    Then Alice's `Room.on_failed_local_stream()` fires 1 time
+    And Alice has local audio
+
+  Scenario: `getUserMedia()` rejects with `OverconstrainedError`
+    Given room with member Alice
+    And Alice's `getUserMedia()` device video fails with `OverconstrainedError` on `deviceId`
+    And joined member Bob
+    When Alice joins the room
+    Then Alice's `Room.on_failed_local_stream()` fires 1 time
+
+  Scenario: Media settings are rolled back when switching to unknown camera fails
+    Given room with joined member Alice and Bob
+    When Alice switches to a non-existent camera with rollback
+    Then Alice's previous media settings are restored
+
+  @mesh
+  Scenario: `getUserMedia()` succeeds once denied permission is granted again
+    Given room with joined member Alice and Bob with disabled media publishing
+    And Alice's camera permission is denied
+    When Alice enables video and awaits it errors
+    And Alice's camera permission is granted
+    And Alice enables video and awaits it completes
+    Then Alice has local device video
+    And Alice's `Room.on_failed_local_stream()` fires 1 time
+
+  Scenario: Preview tracks are reused when joining Room
+    Given room with member Alice
+    And Alice previews local media
+    And joined member Bob
+    When Alice joins the room
+    Then Alice's preview tracks are published
+    And Alice makes one `getUserMedia()` request per media kind
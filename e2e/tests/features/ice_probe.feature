@@ -0,0 +1,9 @@
+Feature: ICE servers probing
+
+  Scenario: Probing the test TURN server reports a relay candidate
+    Given member Alice
+    Then Alice's probe of the test TURN server reports relay
+
+  Scenario: Relay-only filter of the probe counts non-relay candidates
+    Given member Alice
+    Then Alice's relay-only probe of the test TURN server filters out non-relay candidates
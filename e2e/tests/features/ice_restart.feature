@@ -0,0 +1,9 @@
+Feature: ICE restart
+
+  Scenario: Member restarts ICE on media connection loss
+    Given room with joined members Alice and Bob
+    When Alice loses media connection
+    Then Alice restarts ICE
+    And Alice's WS connection is not reconnected
+    When Alice restores media connection
+    Then Alice's WS connection is not reconnected
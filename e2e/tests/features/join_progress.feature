@@ -0,0 +1,15 @@
+Feature: `on_join_progress` callback
+
+  Scenario: All the join phases are reported in order
+    Given room with member Alice
+    And joined member Bob
+    When Alice records join progress
+    And Alice joins the room
+    Then Alice observes `ConnectingSignaling, Authorized, CapturingMedia, Negotiating, ConnectingIce, Joined` join phases
+
+  Scenario: Failed join reports the phase it has failed in
+    Given room with member Alice
+    When Alice records join progress
+    And Alice tries to join the room via unreachable signaling
+    Then Alice's join fails in `connecting_signaling` phase
+    And Alice observes `ConnectingSignaling` join phases
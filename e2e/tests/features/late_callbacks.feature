@@ -0,0 +1,7 @@
+Feature: Late callbacks registration
+
+  Scenario: Callbacks registered after joining receive existing state
+    Given room with joined members Alice and Bob
+    Then Alice's device video remote track from Bob is enabled
+    And Alice's audio remote track from Bob is enabled
+    And Alice's late registered callbacks receive connection with Bob, 2 local tracks and 2 remote tracks
@@ -0,0 +1,34 @@
+Feature: Media state seen by late joiners
+
+  Scenario: Late joiner receives muted audio track
+    Given room with joined members Alice and Carol
+    And member Bob
+    When Alice mutes audio and awaits it completes
+    And Bob joins the room
+    Then Bob's audio remote track from Alice arrives muted
+    And `on_muted` callback fires 0 times on Bob's remote audio track from Alice
+
+  Scenario: Late joiner receives muted video track
+    Given room with joined members Alice and Carol
+    And member Bob
+    When Alice mutes video and awaits it completes
+    And Bob joins the room
+    Then Bob's device video remote track from Alice arrives muted
+    And `on_muted` callback fires 0 times on Bob's remote device video track from Alice
+
+  Scenario: Late joiner receives disabled video track
+    Given room with joined members Alice and Carol
+    And member Bob
+    When Alice disables video and awaits it completes
+    And Bob joins the room
+    Then Bob's device video remote track from Alice arrives disabled
+    And `on_disabled` callback fires 0 times on Bob's remote device video track from Alice
+
+  Scenario: Late joiner receives unmuted track once it's unmuted
+    Given room with joined members Alice and Carol
+    And member Bob
+    When Alice mutes audio and awaits it completes
+    And Bob joins the room
+    And Alice unmutes audio and awaits it completes
+    Then Bob's audio remote track from Alice arrives muted
+    And `on_unmuted` callback fires 1 time on Bob's remote audio track from Alice
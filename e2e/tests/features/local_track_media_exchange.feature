@@ -0,0 +1,13 @@
+Feature: Media exchange callbacks of local tracks
+
+  Scenario: `on_disabled` callback fires before disabling completes
+    Given room with joined members Alice and Bob
+    And Alice's audio local track records media exchange changes
+    When Alice disables audio and awaits it completes
+    Then Alice's audio local track has reported `disabled` media exchange changes
+
+  Scenario: `on_disabled` callback doesn't fire for another track
+    Given room with joined members Alice and Bob
+    And Alice's video local track records media exchange changes
+    When Alice disables audio and awaits it completes
+    Then Alice's video local track has reported `` media exchange changes
@@ -20,6 +20,22 @@ Feature: Media enabling/disabling
     Then Alice doesn't have device video remote track from Bob
     And Alice's audio remote track from Bob is enabled
 
+  @mesh
+  Scenario: Camera is not requested when video is disabled before call
+    Given room with joined member Alice
+    And member Bob with disabled video publishing
+    When Bob joins the room
+    Then Alice's audio remote track from Bob is enabled
+    And Bob's `getUserMedia()` requests have no video
+
+  @mesh
+  Scenario: Microphone is not requested when audio is disabled before call
+    Given room with joined member Alice
+    And member Bob with disabled audio publishing
+    When Bob joins the room
+    Then Alice's device video remote track from Bob is enabled
+    And Bob's `getUserMedia()` requests have no audio
+
   @mesh
   Scenario: Member disables audio before call
     Given room with joined member Alice
@@ -0,0 +1,20 @@
+Feature: Media exchange state of a connection
+
+  Scenario: Media exchange state follows disabling and enabling on both sides
+    Given room with joined members Alice and Bob
+    Then Alice's audio exchange with Bob is: we send, they receive, they send, we receive
+    And Bob's audio exchange with Alice is: we send, they receive, they send, we receive
+
+    When Bob disables audio receiving from Alice
+    Then Bob's audio exchange with Alice is: we send, they receive, they send, we don't receive
+    And Alice's audio exchange with Bob is: we send, they don't receive, they send, we receive
+
+    When Alice disables audio and awaits it completes
+    Then Alice's audio exchange with Bob is: we don't send, they don't receive, they send, we receive
+    And Bob's audio exchange with Alice is: we send, they receive, they don't send, we don't receive
+
+    When Alice enables audio and awaits it completes
+    And Bob enables audio receiving from Alice
+    Then Alice's audio exchange with Bob is: we send, they receive, they send, we receive
+    And Bob's audio exchange with Alice is: we send, they receive, they send, we receive
+
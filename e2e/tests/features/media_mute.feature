@@ -47,3 +47,26 @@ Feature: Media muting
     Then `on_muted` callback fires 1 time on Alice's remote audio track from Bob
     When Bob unmutes audio and awaits it completes
     Then `on_unmuted` callback fires 1 time on Alice's remote audio track from Bob
+
+  Scenario: Muting video doesn't recreate local track
+    Given room with joined members Alice and Bob
+    When Alice mutes video and awaits it completes
+    Then Alice's device video local track is muted
+    And Alice's local device video is enabled
+    And Alice's local device video track was not recreated
+
+  Scenario: Remote audio goes silent when member mutes audio during call
+    Given room with joined members Alice and Bob
+    Then Bob hears audio from Alice
+    When Alice mutes audio and awaits it completes
+    Then Bob doesn't hear audio from Alice
+    When Alice unmutes audio and awaits it completes
+    Then Bob hears audio from Alice
+
+  Scenario: Rapid mute toggling keeps partner in sync
+    Given room with joined members Alice and Bob
+    When Alice mutes audio and awaits it completes
+    And Alice clicks audio unmute button 10 times rapidly
+    Then Bob doesn't hear audio from Alice
+    When Alice unmutes audio and awaits it completes
+    Then Bob hears audio from Alice
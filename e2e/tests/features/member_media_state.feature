@@ -0,0 +1,15 @@
+Feature: `on_member_media_state_changed` callback
+
+  Scenario: Current media states are replayed on registration
+    Given room with joined members Alice and Bob
+    When Alice records members' media states
+    Then Alice observes `Enabled, Unmuted` media states of Bob's audio
+    And Alice observes `Enabled, Unmuted` media states of Bob's device video
+
+  Scenario: Partner's device video disabling and enabling is observed in order
+    Given room with joined members Alice and Bob
+    When Alice records members' media states
+    And Bob disables video and awaits it completes
+    And Bob enables video and awaits it completes
+    Then Alice observes `Enabled, Unmuted, Disabled, Enabled` media states of Bob's device video
+    And Alice observes `Enabled, Unmuted` media states of Bob's audio
@@ -0,0 +1,11 @@
+Feature: Negotiation batching
+
+  Scenario: Simultaneously enabled audio and video are negotiated once
+    Given room with joined members Alice and Bob
+    When Alice disables audio and awaits it completes
+    And Alice disables video and awaits it completes
+    And Alice enables audio and video within 20 ms
+    Then Alice sends 1 `UpdateTracks` command
+    And Alice sends 1 SDP offer or answer
+    And Bob's audio remote track from Alice is enabled
+    And Bob's device video remote track from Alice is enabled
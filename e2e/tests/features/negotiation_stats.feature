@@ -0,0 +1,12 @@
+Feature: Connection negotiation stats
+
+  @mesh
+  Scenario: Every media toggle is counted as a single negotiation
+    Given room with joined members Alice and Bob
+    Then Alice's connection with Bob counts 1 negotiation
+    And Bob's connection with Alice counts 1 negotiation
+    When Alice disables video and awaits it completes
+    And Alice enables video and awaits it completes
+    And Alice disables audio and awaits it completes
+    Then Alice's connection with Bob counts 4 negotiations
+    And Bob's connection with Alice counts 4 negotiations
@@ -0,0 +1,25 @@
+Feature: Missing media input devices
+
+  Scenario: `MediaManagerHandle.has_device()` reports missing microphone
+    Given room with member Alice
+    And Alice has no audio input devices
+    Then Alice's `MediaManagerHandle.has_device()` returns false for audio input
+    And Alice's `MediaManagerHandle.has_device()` returns true for video input
+
+  Scenario: Optional audio is skipped when there are no microphones
+    Given room with member Alice
+    And Alice has no audio input devices
+    And joined member Bob
+    When Alice joins the room
+    Then Alice has 1 local tracks
+    And Alice has local device video
+    And Alice's `getUserMedia()` requests have no audio
+    And Alice's `Room.on_failed_local_stream()` fires 0 times
+
+  Scenario: Required audio fails without `getUserMedia()` when there are no microphones
+    Given room with joined member Alice and Bob with no WebRTC endpoints
+    And Alice has no audio input devices
+    When Control API adds required audio publish endpoint to Alice
+    And Control API adds play endpoint to Bob from Alice
+    Then Alice's `Room.on_failed_local_stream()` fires 1 time
+    And Alice makes no `getUserMedia()` requests
@@ -0,0 +1,10 @@
+Feature: Receive options of SDP offers
+
+  @mesh
+  Scenario: SDP offer of a member not receiving video has no video m-line
+    Given room with joined member Bob
+    And member Alice with no publish WebRTC endpoints
+    When Alice disables remote video
+    And Alice joins the room
+    Then Alice's audio remote track from Bob is enabled
+    And Alice's SDP offers have no video m-line
@@ -0,0 +1,57 @@
+Feature: WebSocket reconnection
+
+  Scenario: Member reconnects in time after clean WS drop
+    Given room with joined member Alice and Bob
+    When Alice loses WS connection
+    Then Alice's WS connection is lost
+    When Alice's WS connection is restored after 500 ms
+    Then Alice reconnects within 3 seconds
+    And Alice's `Room.on_connection_loss()` fires 1 time
+    And Alice's `Room.on_close()` doesn't fire
+
+  Scenario: Member reconnects in time after WS drop with latency
+    Given room with joined member Alice and Bob
+    And Alice's WS messages are delayed by 200 ms
+    When Alice loses WS connection
+    Then Alice's WS connection is lost
+    When Alice's WS connection is restored after 500 ms
+    Then Alice reconnects within 3 seconds
+
+  Scenario: Member reconnects once online bypassing backoff delay
+    Given room with joined member Alice and Bob
+    When Alice loses WS connection
+    Then Alice's WS connection is lost
+    When Alice's WS connection is restored after 500 ms with 5000 ms backoff
+    And Alice goes online after 1000 ms
+    Then Alice reconnects within 3 seconds
+
+  Scenario: Member reconnects once page becomes visible bypassing backoff delay
+    Given room with joined member Alice and Bob
+    When Alice loses WS connection
+    Then Alice's WS connection is lost
+    When Alice's WS connection is restored after 500 ms with 5000 ms backoff
+    And Alice's page becomes visible after 1000 ms
+    Then Alice reconnects within 3 seconds
+
+  Scenario: Member doesn't try to reconnect while offline
+    Given room with joined member Alice and Bob
+    When Alice goes offline
+    And Alice loses WS connection
+    Then Alice's WS connection is lost
+    When Alice's WS connection is restored after 500 ms
+    Then Alice doesn't try to reconnect within 2 seconds
+    When Alice goes online
+    Then Alice reconnects within 5 seconds
+
+  Scenario: Member reconnects across outages with the same ReconnectHandle
+    Given room with joined member Alice and Bob
+    When Alice loses WS connection
+    Then Alice's WS connection is lost
+    When Alice's WS connection is restored after 500 ms with the first `ReconnectHandle`
+    Then Alice reconnects within 3 seconds
+    And Alice's first `ReconnectHandle` reports AlreadyConnected
+    When Alice loses WS connection
+    Then Alice's WS connection is lost
+    When Alice's WS connection is restored after 500 ms with the first `ReconnectHandle`
+    Then Alice reconnects within 3 seconds
+    And Alice's `Room.on_connection_loss()` fires 2 times
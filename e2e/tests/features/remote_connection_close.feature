@@ -14,3 +14,10 @@ Feature: Remote Connection closing
     Given room with joined members Alice and Bob
     When Bob's room closed by client
     Then Alice's connection with Bob closes
+
+  Scenario: Closed connection is no longer counted
+    Given room with joined members Alice and Bob
+    Then Alice has 1 connection
+    When Control API removes member Bob
+    Then Alice's connection with Bob closes
+    And Alice has 0 connections
@@ -0,0 +1,6 @@
+Feature: Freeing RemoteMediaTrack
+
+  Scenario: Attached remote track keeps rendering after its handle is freed
+    Given room with joined members Alice and Bob
+    Then Alice's device video remote track from Bob keeps rendering once attached and freed
+    And Bob's device video remote track from Alice is enabled
@@ -0,0 +1,21 @@
+Feature: Remote tracks removal
+
+  Scenario Outline: Removal is reported once per track on endpoint deletion
+    Given room with joined member Alice and Bob
+    When Control API deletes Alice's publish endpoint
+    Then Bob's connection with Alice reports <tracks> removed remote tracks
+
+    @mesh
+    Examples:
+      | tracks |
+      | 2      |
+
+    @sfu
+    Examples:
+      | tracks |
+      | 3      |
+
+  Scenario: Disabled remote track is not removed
+    Given room with joined member Alice and Bob
+    When Alice disables video and awaits it completes
+    Then Bob's connection with Alice reports 0 removed remote tracks
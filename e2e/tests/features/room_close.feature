@@ -14,8 +14,25 @@ Feature: Room closing
     Given room with joined member Alice
     When Control API removes member Alice
     Then Alice's `on_close` room's callback fires with `Evicted` reason
+    And Alice's `Room.on_close()` has fired with `Evicted`
 
   Scenario: `Room.on_close()` fires when room is removed by Control API
     Given room with joined member Alice
     When Control API removes the room
     Then Alice's `on_close` room's callback fires with `Evicted` reason
+
+  Scenario: `Room.on_close()` fires when reconnecting runs out of time
+    Given room with joined member Alice
+    When Alice loses WS connection
+    Then Alice's WS connection is lost
+    When Alice tries to reconnect for 1000 ms
+    Then Alice's `on_close` room's callback fires with `ConnectionLost` reason
+    And Alice's `Room.on_close()` reason is an error
+
+  Scenario: `Room.on_close()` fires when reconnecting is given up
+    Given room with joined member Alice
+    When Alice loses WS connection
+    Then Alice's WS connection is lost
+    When Alice gives up reconnecting
+    Then Alice's `on_close` room's callback fires with `ConnectionLost` reason
+    And Alice's `Room.on_close()` reason is an error
@@ -27,3 +27,31 @@ Feature: Room joining
     When Carol joins the room
     Then Alice receives connection with Carol
     And Bob receives connection with Carol
+
+  Scenario: Member joins nonexistent room
+    Given room with member Alice
+    When Alice tries to join nonexistent room
+    Then Alice's join fails with AuthorizationFailed
+
+  Scenario: Member joins with bad token
+    Given room with member Alice
+    When Alice tries to join the room with bad token
+    Then Alice's join fails with AuthorizationFailed
+
+  Scenario: Member joins the room twice
+    Given room with joined member Alice
+    When Alice tries to join the room
+    Then Alice's join succeeds
+    And Alice's WS connection is not reconnected
+
+  Scenario: Member joins the room twice at once
+    Given room with member Alice
+    When Alice joins the room twice at once
+    Then Alice's join succeeds
+    And Alice opens single WS connection
+
+  Scenario: Member joins closed room
+    Given room with joined member Alice
+    When Alice's room closed by client
+    And Alice tries to join the room
+    Then Alice's join fails with RoomClosed
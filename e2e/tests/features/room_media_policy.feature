@@ -0,0 +1,17 @@
+Feature: Room media policy
+
+  Scenario: Member joins with muted audio and disabled video
+    Given room with joined member Alice
+    And member Bob
+    When Bob applies media policy with muted audio and disabled video
+    And Bob joins the room
+    Then Alice's audio remote track from Bob arrives muted
+    And Alice's device video remote track from Bob arrives disabled
+    And `on_unmuted` callback fires 0 times on Alice's remote audio track from Bob
+    And `on_enabled` callback fires 0 times on Alice's remote device video track from Bob
+
+  Scenario: Policy applied after join changes only differing states
+    Given room with joined members Alice and Bob
+    When Bob applies media policy with muted audio and enabled video
+    Then `on_muted` callback fires 1 time on Alice's remote audio track from Bob
+    And `on_disabled` callback fires 0 times on Alice's remote device video track from Bob
@@ -0,0 +1,17 @@
+Feature: Room state snapshot
+
+  Scenario: Restored room keeps audio muted
+    Given room with joined members Alice and Bob
+    When Alice mutes audio and awaits it completes
+    And Alice reloads the page restoring the room state
+    Then Bob's connection with Alice closes
+    And Bob's audio remote track from Alice arrives muted
+    And `on_unmuted` callback fires 0 times on Bob's remote audio track from Alice
+
+  Scenario: Restored room keeps video disabled
+    Given room with joined members Alice and Bob
+    When Alice disables video and awaits it completes
+    And Alice reloads the page restoring the room state
+    Then Bob's connection with Alice closes
+    And Bob's device video remote track from Alice arrives disabled
+    And `on_enabled` callback fires 0 times on Bob's remote device video track from Alice
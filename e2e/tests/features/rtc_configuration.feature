@@ -0,0 +1,17 @@
+Feature: RTCPeerConnection configuration
+
+  Scenario: Members connect with `Balanced` bundle policy
+    Given room with joined member Alice
+    And member Bob
+    When Bob sets `Balanced` bundle policy
+    And Bob joins the room
+    Then Alice receives connection with Bob
+    And Bob receives connection with Alice
+    And Bob's audio remote track from Alice is enabled
+    And Bob's device video remote track from Alice is enabled
+    And Bob's peer connections use `Balanced` bundle policy
+
+  Scenario: Configuration cannot be changed once peers are created
+    Given room with joined members Alice and Bob
+    When Alice fails to set `Balanced` bundle policy
+    Then Alice's peer connections use `MaxBundle` bundle policy
@@ -0,0 +1,53 @@
+Feature: Screen sharing
+
+  @sfu
+  Scenario: Member cancels screen sharing request
+    Given room with joined member Alice and Bob
+    And Alice cancels the next screen sharing request
+    When Alice fails to start screen sharing
+    And Alice starts screen sharing
+    Then Alice has local display video
+
+  @sfu
+  Scenario: Member stops screen sharing mid-call
+    Given room with joined member Alice and Bob
+    And Alice's screen resolution is 640x480
+    When Alice starts screen sharing
+    And Alice stops screen sharing
+    Then Alice's display video local track ends
+
+  Scenario: Captured display surface is exposed on both sides
+    Given room with joined member Alice and Bob
+    And Alice's captured screen surface is window
+    When Alice starts screen sharing
+    Then Alice's display video local track surface is Window
+    And Alice's device video local track surface is none
+    And Bob's display video remote track from Alice surface is Window
+
+  Scenario: Screen sharing stopped via browser disables display video sending
+    Given room with joined member Alice and Bob
+    When Alice starts screen sharing
+    And Alice stops screen sharing
+    Then Bob's display video remote track from Alice is disabled
+
+  Scenario: Screen sharing restarts after being stopped via browser
+    Given room with joined member Alice and Bob
+    When Alice starts screen sharing
+    And Alice stops screen sharing
+    And Alice starts screen sharing
+    Then Alice has local display video
+    And Bob's display video remote track from Alice is enabled
+
+  Scenario: Member publishes and stops publishing screen
+    Given room with joined member Alice and Bob
+    When Alice publishes her screen
+    Then Alice has local display video
+    And Bob's display video remote track from Alice is enabled
+    When Alice stops publishing her screen
+    Then Bob's display video remote track from Alice is disabled
+
+  Scenario: Current tab sharing options are passed to the browser
+    Given room with joined member Alice and Bob
+    When Alice shares her current tab
+    Then Alice has local display video
+    And Alice's screen capture request prefers her current tab
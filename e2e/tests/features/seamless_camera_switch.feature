@@ -0,0 +1,13 @@
+Feature: Seamless camera switching
+
+  Scenario: Remote video isn't muted while camera is switched seamlessly
+    Given room with member Alice
+    And Alice has cameras `cam-a` and `cam-b`
+    And joined member Bob
+    When Alice joins the room
+    Then Alice sends video from `cam-a`
+    When Bob watches his device video remote track from Alice for mutes
+    And Alice switches her camera to `cam-b` seamlessly
+    Then Alice sends video from `cam-b`
+    And Bob's device video remote track from Alice has never been muted
+
@@ -0,0 +1,6 @@
+Feature: Connection setup timings
+
+  @mesh
+  Scenario: Setup timings are populated once connected
+    Given room with joined members Alice and Bob
+    Then Alice and Bob connection setup timings cover all phases
@@ -0,0 +1,12 @@
+Feature: Silence detection of local audio
+
+  Scenario: Silent microphone is detected
+    Given room with member Alice
+    And Alice's microphone is silent
+    And joined member Bob
+    When Alice joins the room
+    Then Alice's audio local track is detected silent
+
+  Scenario: Sounding microphone is not detected silent
+    Given room with joined members Alice and Bob
+    Then Alice's audio local track isn't detected silent
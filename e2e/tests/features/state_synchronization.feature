@@ -27,6 +27,21 @@ Feature: State synchronization
     And Alice restores WS connection
     Then Bob's audio remote track from Alice is enabled
 
+  Scenario: Local track disabled while disconnect isn't resumed after restore
+    Given room with joined member Alice and Bob
+    When Alice loses WS connection
+    And Alice disables audio and ignores the result
+    And Alice restores WS connection
+    Then Bob's audio remote track from Alice is disabled
+    And Alice doesn't send audio
+
+  Scenario: Local track muted while disconnect isn't unmuted after restore
+    Given room with joined member Alice and Bob
+    When Alice loses WS connection
+    And Alice mutes audio and ignores the result
+    And Alice restores WS connection
+    Then Alice's audio local track is muted
+
   Scenario: Audio endpoint added while disconnected
     Given room with joined member Alice and Bob with no WebRTC endpoints
     When Alice loses WS connection
@@ -0,0 +1,21 @@
+Feature: Media statistics
+
+  Scenario: Outbound video bitrate stays below the cap
+    Given room with joined members Alice and Bob
+    Then Alice's outbound video bitrate is below 5000 kbps
+
+  @chrome
+  Scenario: Outbound video has expected resolution
+    Given room with joined members Alice and Bob
+    Then Alice's outbound device video resolution is at least 320x240
+
+  @chrome
+  Scenario: Outbound video follows video quality preset
+    Given room with joined members Alice and Bob
+    When Alice sets video quality to medium
+    Then Alice's outbound device video resolution is at least 640x480
+
+  @chrome
+  Scenario: Requested keyframe is encoded
+    Given room with joined members Alice and Bob
+    Then Alice's device video local track encodes keyframe on request
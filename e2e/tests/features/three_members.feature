@@ -0,0 +1,5 @@
+Feature: Rooms with three members
+
+  Scenario: Every member receives tracks from every other member
+    Given room with joined members Alice, Bob and Carol
+    Then every member has audio and video remote tracks from every other member
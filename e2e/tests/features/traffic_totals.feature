@@ -0,0 +1,11 @@
+Feature: Connection traffic totals
+
+  @mesh
+  Scenario: Traffic totals stop growing when media is disabled
+    Given room with joined members Alice and Bob
+    Then Alice's traffic with Bob grows
+    When Alice disables audio and awaits it completes
+    And Alice disables video and awaits it completes
+    And Bob disables audio and awaits it completes
+    And Bob disables video and awaits it completes
+    Then Alice's traffic with Bob stops growing
@@ -0,0 +1,15 @@
+Feature: Video transform
+
+  Scenario: Partner receives rotated video
+    Given room with joined member Alice and Bob
+    When Alice publishes 320x240 canvas video
+    And Alice rotates her device video by 90 degrees
+    Then Bob receives 240x320 device video from Alice
+
+  Scenario: Partner receives original video once transform is reset
+    Given room with joined member Alice and Bob
+    When Alice publishes 320x240 canvas video
+    And Alice rotates her device video by 270 degrees and mirrors it
+    Then Bob receives 240x320 device video from Alice
+    When Alice rotates her device video by 0 degrees
+    Then Bob receives 320x240 device video from Alice
@@ -0,0 +1,13 @@
+Feature: WebRTC availability
+
+  Scenario: `Jason.is_supported()` reports available WebRTC
+    Given room with member Alice
+    Then Alice's `Jason.is_supported()` reports peer connection as available
+
+  Scenario: Member joins with WebRTC disabled
+    Given room with member Alice
+    And Alice's browser has WebRTC disabled
+    When Alice tries to join the room
+    Then Alice's join fails with WebRtcUnavailable
+    And Alice's `Jason.is_supported()` reports peer connection as unavailable
+    And Alice doesn't open WS connection
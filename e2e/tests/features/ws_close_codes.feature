@@ -0,0 +1,31 @@
+Feature: WebSocket close codes
+
+  Scenario: Room is closed when Medea finishes session
+    Given room with joined member Alice and Bob
+    When Medea closes Alice's WS with code 1000 and `Finished` reason
+    Then Alice's `on_close` room's callback fires with `Finished` reason
+    And Alice's `Room.on_connection_loss()` fires 0 times
+
+  Scenario: Room is closed when Medea rejects connection
+    Given room with joined member Alice and Bob
+    When Medea closes Alice's WS with code 1000 and `Rejected` reason
+    Then Alice's `on_close` room's callback fires with `Rejected` reason
+    And Alice's `Room.on_connection_loss()` fires 0 times
+
+  Scenario: Connection is lost on normal close code without reason
+    Given room with joined member Alice and Bob
+    When Medea closes Alice's WS with code 1000
+    Then Alice's `Room.on_connection_loss()` fires 1 time
+    And Alice's `Room.on_close()` doesn't fire
+
+  Scenario: Connection is lost on custom close code
+    Given room with joined member Alice and Bob
+    When Medea closes Alice's WS with code 4001
+    Then Alice's `Room.on_connection_loss()` fires 1 time
+    And Alice's `Room.on_close()` doesn't fire
+
+  Scenario: Connection is lost when WS drops abruptly
+    Given room with joined member Alice and Bob
+    When Alice's WS drops abruptly
+    Then Alice's `Room.on_connection_loss()` fires 1 time
+    And Alice's `Room.on_close()` doesn't fire
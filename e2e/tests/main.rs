@@ -5,23 +5,49 @@ mod control;
 mod steps;
 mod world;
 
+use cucumber::{event::ScenarioFinished, writer::Stats as _};
+use futures::FutureExt as _;
+
 pub use self::world::World;
 
 #[tokio::main]
 async fn main() {
-    let concurrent = if supports_multiple_webdriver_clients().await {
+    let concurrent = if let Some(concurrency) = *conf::CONCURRENCY {
+        concurrency
+    } else if supports_multiple_webdriver_clients().await {
         4
     } else {
         1
     };
 
-    <World as cucumber::World>::cucumber()
+    let writer = <World as cucumber::World>::cucumber()
         .with_writer(cucumber::writer::Libtest::or_basic())
         .repeat_failed()
         .fail_on_skipped()
         .max_concurrent_scenarios(concurrent)
-        .run_and_exit(conf::FEATURES_PATH.as_str())
+        .after(|_, _, _, ev, world| {
+            async move {
+                if let (ScenarioFinished::StepFailed(..), Some(world)) =
+                    (ev, world)
+                {
+                    world.dump_console_logs().await;
+                }
+            }
+            .boxed_local()
+        })
+        .run(conf::FEATURES_PATH.as_str())
         .await;
+
+    world::SESSIONS.blocking_close_all();
+
+    if writer.execution_has_failed() {
+        panic!(
+            "{} step(s) failed, {} parsing error(s), {} hook error(s)",
+            writer.failed_steps(),
+            writer.parsing_errors(),
+            writer.hook_errors(),
+        );
+    }
 }
 
 /// Indicates whether `WebDriver` implementation supports multiple simultaneous
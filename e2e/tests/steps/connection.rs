@@ -1,6 +1,12 @@
-use cucumber::{then, when};
+use std::time::Duration;
 
-use crate::{steps::parse_media_kind, World};
+use cucumber::{given, then, when};
+use medea_e2e::object::{
+    connection::MediaExchangeDirections, room::parse_track_kinds,
+};
+use tokio::time::sleep;
+
+use crate::{conf, steps::parse_media_kind, World};
 
 #[then(regex = r"^(\S+) receives connection with (\S+)$")]
 async fn then_member_receives_connection(
@@ -11,9 +17,25 @@ async fn then_member_receives_connection(
     let member = world.get_member(&id).unwrap();
     member
         .connections()
-        .wait_for_connection(responder_id.clone())
+        .wait_for_connection(responder_id, *conf::CONNECTION_TIMEOUT)
+        .await
+        .unwrap();
+}
+
+#[when(regex = r"^(\S+) frees (?:her|his) connection handle with (\S+)$")]
+async fn when_member_frees_connection_handle(
+    world: &mut World,
+    id: String,
+    partner_id: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    let connection = member
+        .connections()
+        .wait_for_connection(partner_id, *conf::CONNECTION_TIMEOUT)
         .await
         .unwrap();
+    let is_thrown = connection.free_and_replace_handle().await.unwrap();
+    assert!(is_thrown, "freed `ConnectionHandle` didn't throw");
 }
 
 #[then(regex = r"^(\S+) doesn't receive connection with (\S+)$")]
@@ -38,9 +60,38 @@ async fn then_connection_closes(
     partner_id: String,
 ) {
     let member = world.get_member(&id).unwrap();
-    let connection =
-        member.connections().get(partner_id).await.unwrap().unwrap();
-    connection.wait_for_close().await.unwrap();
+    member
+        .connections()
+        .wait_for_close(partner_id)
+        .await
+        .unwrap();
+}
+
+#[then(regex = r"^(\S+) has (\d+) connection(?:s)?$")]
+async fn then_member_has_connections(
+    world: &mut World,
+    id: String,
+    count: u64,
+) {
+    let member = world.get_member(&id).unwrap();
+    let connections = member.connections();
+    connections.wait_for_connection_count(count).await.unwrap();
+    assert_eq!(connections.count().await.unwrap(), count);
+}
+
+#[then(regex = r"^(\S+)'s room enumerates (\d+) connection(?:s)?$")]
+async fn then_room_enumerates_connections(
+    world: &mut World,
+    id: String,
+    count: u64,
+) {
+    let member = world.get_member(&id).unwrap();
+    let connections = member.connections();
+    connections.wait_for_connection_count(count).await.unwrap();
+    assert!(
+        connections.matches_room_connections().await.unwrap(),
+        "`RoomHandle.connections()` doesn't match {count} connection(s)",
+    );
 }
 
 #[when(regex = r"^(\S+) (enables|disables) (audio|video) receiving from (\S+)")]
@@ -63,3 +114,340 @@ async fn when_connection_changes_remote_media_state(
         connection.disable_remote_media(kind).await.unwrap();
     }
 }
+
+#[when(regex = r"^(\S+) (starts|stops) receiving video from (\S+)$")]
+async fn when_connection_sets_receive_video(
+    world: &mut World,
+    id: String,
+    action: String,
+    partner_id: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    let connection = member
+        .connections()
+        .wait_for_connection(partner_id, *conf::CONNECTION_TIMEOUT)
+        .await
+        .unwrap();
+    connection
+        .set_receive_video(action == "starts")
+        .await
+        .unwrap();
+}
+
+#[when(regex = r"^(\S+) loses media connection$")]
+async fn when_member_loses_media_connection(world: &mut World, id: String) {
+    let member = world.get_member(&id).unwrap();
+    member.block_media_traffic().await;
+}
+
+#[when(regex = r"^(\S+) restores media connection$")]
+async fn when_member_restores_media_connection(world: &mut World, id: String) {
+    let member = world.get_member(&id).unwrap();
+    member.unblock_media_traffic().await;
+}
+
+#[then(regex = r"^(\S+) restarts ICE$")]
+async fn then_member_restarts_ice(world: &mut World, id: String) {
+    let member = world.get_member(&id).unwrap();
+    member.peer_connection_mock().wait_for_ice_restarts(1).await;
+}
+
+#[given(regex = r"^media is force relayed$")]
+async fn given_media_is_force_relayed(world: &mut World) {
+    world.force_relay();
+}
+
+#[given(regex = r"^P2P connectivity between (\S+) and (\S+) is blocked$")]
+async fn given_connectivity_is_blocked(
+    world: &mut World,
+    id: String,
+    partner_id: String,
+) {
+    for id in [id, partner_id] {
+        let member = world.get_member(&id).unwrap();
+        member.peer_connection_mock().block_connectivity().await;
+    }
+}
+
+#[when(regex = r"^P2P connectivity between (\S+) and (\S+) is unblocked$")]
+async fn when_connectivity_is_unblocked(
+    world: &mut World,
+    id: String,
+    partner_id: String,
+) {
+    for id in [id, partner_id] {
+        let member = world.get_member(&id).unwrap();
+        member.peer_connection_mock().unblock_connectivity().await;
+    }
+}
+
+#[then(regex = r"^(\S+)'s peer connection (fails|connects)$")]
+async fn then_peer_connection_state(
+    world: &mut World,
+    id: String,
+    state: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    let state = if state == "fails" {
+        "failed"
+    } else {
+        "connected"
+    };
+    member
+        .peer_connection_mock()
+        .wait_for_connection_state(state)
+        .await;
+}
+
+#[then(regex = r"^(\S+)'s SDP offers have no (audio|video) m-line$")]
+async fn then_sdp_offers_have_no_m_line(
+    world: &mut World,
+    id: String,
+    kind: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    let m_line = format!("m={kind} ");
+    for offer in member.peer_connection_mock().wait_for_offers().await {
+        assert!(!offer.contains(&m_line), "SDP offer has {kind}: {offer}");
+    }
+}
+
+#[when(regex = "^(\\S+) (sets|fails to set) \
+                 `(Balanced|MaxCompat|MaxBundle)` bundle policy$")]
+async fn when_member_sets_bundle_policy(
+    world: &mut World,
+    id: String,
+    result: String,
+    policy: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    let res = member.room().set_bundle_policy(&policy).await;
+    if result == "sets" {
+        res.unwrap();
+    } else {
+        assert!(res.is_err(), "bundle policy should fail to be set");
+    }
+}
+
+#[then(regex = "^(\\S+)'s peer connections use \
+                 `(Balanced|MaxCompat|MaxBundle)` bundle policy$")]
+async fn then_peer_connections_use_bundle_policy(
+    world: &mut World,
+    id: String,
+    policy: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    let expected = match policy.as_str() {
+        "Balanced" => "balanced",
+        "MaxCompat" => "max-compat",
+        _ => "max-bundle",
+    };
+    let policies = member.peer_connection_mock().bundle_policies().await;
+    assert!(!policies.is_empty(), "{id} has no peer connections");
+    for p in policies {
+        assert_eq!(p, expected);
+    }
+}
+
+#[then(regex = r"^(\S+) and (\S+) connection setup timings cover all phases$")]
+async fn then_setup_timings_cover_all_phases(
+    world: &mut World,
+    id: String,
+    partner_id: String,
+) {
+    let mut offering_sides = 0;
+    for (id, partner_id) in [(&id, &partner_id), (&partner_id, &id)] {
+        let member = world.get_member(id).unwrap();
+        let timings = member
+            .connections()
+            .wait_for_connection(partner_id.clone(), *conf::CONNECTION_TIMEOUT)
+            .await
+            .unwrap()
+            .wait_for_setup_timings()
+            .await
+            .unwrap();
+
+        for phase in [
+            "gum",
+            "set_local_description",
+            "set_remote_description",
+            "ice_connected",
+        ] {
+            assert!(
+                matches!(timings.get(phase), Some(Some(_))),
+                "`{phase}` of {id} is not populated: {timings:?}",
+            );
+        }
+        let offering_phases = ["create_offer", "server_rtt"]
+            .into_iter()
+            .filter(|p| matches!(timings.get(*p), Some(Some(_))))
+            .count();
+        match offering_phases {
+            0 => (),
+            2 => offering_sides += 1,
+            _ => panic!("{id} has partial offering phases: {timings:?}"),
+        }
+    }
+    assert_eq!(offering_sides, 1, "exactly one side should be an offerer");
+}
+
+#[then(regex = r"^(\S+)'s traffic with (\S+) grows$")]
+async fn then_traffic_grows(world: &mut World, id: String, partner_id: String) {
+    let member = world.get_member(&id).unwrap();
+    let conn = member
+        .connections()
+        .wait_for_connection(partner_id, *conf::CONNECTION_TIMEOUT)
+        .await
+        .unwrap();
+
+    let initial = conn.traffic_totals().await.unwrap();
+    for _ in 0..20 {
+        sleep(Duration::from_millis(500)).await;
+        let current = conn.traffic_totals().await.unwrap();
+        if current["bytes_sent"] > initial["bytes_sent"]
+            && current["bytes_received"] > initial["bytes_received"]
+        {
+            return;
+        }
+    }
+    panic!("traffic of {id} doesn't grow");
+}
+
+#[then(regex = r"^(\S+)'s traffic with (\S+) stops growing$")]
+async fn then_traffic_stops_growing(
+    world: &mut World,
+    id: String,
+    partner_id: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    let conn = member
+        .connections()
+        .wait_for_connection(partner_id, *conf::CONNECTION_TIMEOUT)
+        .await
+        .unwrap();
+
+    // Let in-flight media and stats scraping settle down.
+    sleep(Duration::from_secs(3)).await;
+    let settled = conn.traffic_totals().await.unwrap();
+    sleep(Duration::from_secs(3)).await;
+    let current = conn.traffic_totals().await.unwrap();
+    assert_eq!(current, settled, "traffic of {id} still grows");
+}
+
+#[then(regex = r"^(\S+)'s connection with (\S+) counts (\d+) negotiations?$")]
+async fn then_connection_counts_negotiations(
+    world: &mut World,
+    id: String,
+    partner_id: String,
+    count: u64,
+) {
+    let member = world.get_member(&id).unwrap();
+    let conn = member
+        .connections()
+        .wait_for_connection(partner_id, *conf::CONNECTION_TIMEOUT)
+        .await
+        .unwrap();
+
+    for _ in 0..20 {
+        if conn.negotiations_count().await.unwrap() >= count {
+            break;
+        }
+        sleep(Duration::from_millis(500)).await;
+    }
+    // Lets any excessive renegotiation to happen.
+    sleep(Duration::from_secs(1)).await;
+    assert_eq!(conn.negotiations_count().await.unwrap(), count);
+}
+
+#[then(regex = "^(\\S+)'s (audio|(?:device|display) video) exchange with \
+                 (\\S+) is: (we (?:send|don't send), \
+                 they (?:receive|don't receive), they (?:send|don't send), \
+                 we (?:receive|don't receive))$")]
+async fn then_media_exchange_state_is(
+    world: &mut World,
+    id: String,
+    kind: String,
+    partner_id: String,
+    directions: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    let conn = member
+        .connections()
+        .wait_for_connection(partner_id, *conf::CONNECTION_TIMEOUT)
+        .await
+        .unwrap();
+    let (media_kind, source_kind) = parse_track_kinds(&kind).unwrap();
+    let [we_send, they_receive, they_send, we_receive] = <[_; 4]>::try_from(
+        directions
+            .split(", ")
+            .map(|d| !d.contains("don't"))
+            .collect::<Vec<_>>(),
+    )
+    .unwrap();
+    let expected = MediaExchangeDirections {
+        we_send,
+        they_receive,
+        they_send,
+        we_receive,
+    };
+
+    let mut actual = None;
+    for _ in 0..20 {
+        let state = conn
+            .media_exchange_state(media_kind, source_kind)
+            .await
+            .unwrap();
+        actual = Some(state);
+        if state == expected {
+            break;
+        }
+        sleep(Duration::from_millis(500)).await;
+    }
+    assert_eq!(actual, Some(expected), "{id}'s {kind} exchange state");
+}
+
+#[then(regex = "^(\\S+)'s connection with (\\S+) reports (\\d+) \
+                 removed remote tracks?$")]
+async fn then_connection_reports_removed_tracks(
+    world: &mut World,
+    id: String,
+    partner_id: String,
+    count: u64,
+) {
+    let member = world.get_member(&id).unwrap();
+    let conn = member
+        .connections()
+        .wait_for_connection(partner_id, *conf::CONNECTION_TIMEOUT)
+        .await
+        .unwrap();
+    conn.wait_for_removed_count(count).await.unwrap();
+    // Give a chance for any excessive removal to be reported.
+    sleep(Duration::from_millis(500)).await;
+    assert_eq!(conn.removed_tracks_count().await.unwrap(), count);
+}
+
+#[then(regex = r"^(\S+) (sends|doesn't send) video to (\S+)$")]
+async fn then_member_sends_video(
+    world: &mut World,
+    id: String,
+    sends: String,
+    partner_id: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    let conn = member
+        .connections()
+        .wait_for_connection(partner_id, *conf::CONNECTION_TIMEOUT)
+        .await
+        .unwrap();
+
+    // Let in-flight media and stats scraping settle down.
+    sleep(Duration::from_secs(3)).await;
+    let initial = conn.traffic_totals().await.unwrap()["video_bytes"];
+    sleep(Duration::from_secs(3)).await;
+    let current = conn.traffic_totals().await.unwrap()["video_bytes"];
+    if sends == "sends" {
+        assert!(current > initial, "{id} doesn't send video");
+    } else {
+        assert_eq!(current, initial, "{id} still sends video");
+    }
+}
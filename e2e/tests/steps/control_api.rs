@@ -6,7 +6,7 @@ use medea_control_api_mock::proto::{
 };
 use tokio::time::{sleep, timeout};
 
-use crate::world::{MembersPair, PairedMember, World};
+use crate::world::{MembersPair, PairedMember, PublishEndpointOpts, World};
 
 #[when(regex = r"^Control API removes member (\S+)$")]
 async fn when_control_api_removes_member(world: &mut World, id: String) {
@@ -192,3 +192,47 @@ async fn when_control_api_deletes_play_endpoint(
     world.delete_play_endpoint(&id, &partner_id).await;
     sleep(Duration::from_millis(200)).await;
 }
+
+#[when(regex = "^Control API adds (?:an? )?(required )?(audio |video )?\
+                 publish endpoint to (\\S+)$")]
+async fn when_control_api_adds_publish_endpoint(
+    world: &mut World,
+    required: String,
+    kind: String,
+    id: String,
+) {
+    let publish_policy = if required.is_empty() {
+        proto::PublishPolicy::Optional
+    } else {
+        proto::PublishPolicy::Required
+    };
+    let all_kinds = kind.is_empty();
+    let audio = (all_kinds || kind.contains("audio"))
+        .then_some(AudioSettings { publish_policy });
+    let video = (all_kinds || kind.contains("video"))
+        .then_some(VideoSettings { publish_policy });
+    world
+        .create_publish_endpoint(&id, PublishEndpointOpts { audio, video })
+        .await
+        .unwrap();
+}
+
+#[when(regex = "^Control API adds (?:a )?play endpoint to (\\S+) \
+                 from (\\S+)$")]
+async fn when_control_api_adds_play_endpoint(
+    world: &mut World,
+    id: String,
+    src_id: String,
+) {
+    world.create_play_endpoint(&id, &src_id).await.unwrap();
+}
+
+#[when(regex = r"^Control API deletes (\S+)'s endpoint `(\S+)`$")]
+async fn when_control_api_deletes_endpoint(
+    world: &mut World,
+    id: String,
+    endpoint_id: String,
+) {
+    world.delete_endpoint(&id, &endpoint_id).await.unwrap();
+    sleep(Duration::from_millis(200)).await;
+}
@@ -0,0 +1,25 @@
+use cucumber::then;
+
+use crate::World;
+
+#[then(regex = r"^(\S+)'s probe of the test TURN server reports relay$")]
+async fn then_turn_probe_reports_relay(world: &mut World, id: String) {
+    let report = world.probe_turn_server(&id, false).await.unwrap();
+
+    assert!(report.relay_candidates > 0, "{report:?}");
+    assert!(report.servers.iter().all(|s| s.is_reachable), "{report:?}");
+}
+
+#[then(regex = "^(\\S+)'s relay-only probe of the test TURN server filters \
+                 out non-relay candidates$")]
+async fn then_relay_only_probe_filters_candidates(
+    world: &mut World,
+    id: String,
+) {
+    let report = world.probe_turn_server(&id, true).await.unwrap();
+
+    assert!(report.relay_candidates > 0, "{report:?}");
+    assert_eq!(report.host_candidates, 0, "{report:?}");
+    assert_eq!(report.srflx_candidates, 0, "{report:?}");
+    assert!(report.filtered_candidates > 0, "{report:?}");
+}
@@ -2,12 +2,13 @@ use std::time::Duration;
 
 use cucumber::{given, then, when};
 use medea_e2e::object::{
-    remote_track::MediaDirection, AwaitCompletion, MediaSourceKind,
+    remote_track::MediaDirection, room::parse_track_kinds, AwaitCompletion,
+    MediaSourceKind,
 };
 
-use crate::World;
+use crate::{conf, World};
 
-use super::{parse_media_kind, parse_media_kinds};
+use super::parse_media_kind;
 
 #[given(regex = r"^(\S+)'s `getUserMedia\(\)` request has added latency$")]
 async fn given_gum_delay(world: &mut World, id: String) {
@@ -30,7 +31,7 @@ async fn then_local_track_mute_state(
     not_muted: String,
 ) {
     let member = world.get_member(&id).unwrap();
-    let (media_kind, source_kind) = parse_media_kinds(&kind).unwrap();
+    let (media_kind, source_kind) = parse_track_kinds(&kind).unwrap();
     let track = member
         .room()
         .local_tracks()
@@ -42,11 +43,56 @@ async fn then_local_track_mute_state(
     assert_eq!(not_muted.is_empty(), track.muted().await.unwrap());
 }
 
+#[then(regex = "^(\\S+)'s local (audio|(?:device|display) video) is \
+                 (enabled|disabled)$")]
+async fn then_local_media_is_enabled(
+    world: &mut World,
+    id: String,
+    kind: String,
+    state: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    let (media_kind, source_kind) = parse_track_kinds(&kind).unwrap();
+    let track = member
+        .room()
+        .local_tracks()
+        .await
+        .unwrap()
+        .get_track(media_kind, source_kind)
+        .await
+        .unwrap();
+    if state == "enabled" {
+        track.wait_for_enabled().await.unwrap();
+    } else {
+        track.wait_for_disabled().await.unwrap();
+    }
+}
+
+#[then(regex = "^(\\S+)'s local (audio|(?:device|display) video) track \
+                 was not recreated$")]
+async fn then_local_track_was_not_recreated(
+    world: &mut World,
+    id: String,
+    kind: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    let (media_kind, source_kind) = parse_track_kinds(&kind).unwrap();
+    let track = member
+        .room()
+        .local_tracks()
+        .await
+        .unwrap()
+        .get_track(media_kind, source_kind)
+        .await
+        .unwrap();
+    assert_eq!(track.free_count().await.unwrap(), 0);
+}
+
 #[then(regex = "^(\\S+)'s (audio|(?:device|display) video) local track is \
                  stopped$")]
 async fn then_track_is_stopped(world: &mut World, id: String, kind: String) {
     let member = world.get_member(&id).unwrap();
-    let (media_kind, source_kind) = parse_media_kinds(&kind).unwrap();
+    let (media_kind, source_kind) = parse_track_kinds(&kind).unwrap();
     let is_stopped = member
         .room()
         .local_tracks()
@@ -61,6 +107,21 @@ async fn then_track_is_stopped(world: &mut World, id: String, kind: String) {
     assert!(is_stopped);
 }
 
+#[then(regex = "^(\\S+)'s (audio|(?:device|display) video) local track \
+                 ends$")]
+async fn then_local_track_ends(world: &mut World, id: String, kind: String) {
+    let member = world.get_member(&id).unwrap();
+    let (media_kind, source_kind) = parse_track_kinds(&kind).unwrap();
+    member
+        .room()
+        .local_tracks()
+        .await
+        .unwrap()
+        .wait_for_track_stopped(media_kind, source_kind)
+        .await
+        .unwrap();
+}
+
 #[then(regex = "^(\\S+)'s (audio|video) from (\\S+) has \
                  `(SendRecv|SendOnly|RecvOnly|Inactive)` direction$")]
 async fn then_remote_media_direction_is(
@@ -81,7 +142,7 @@ async fn then_remote_media_direction_is(
     let member = world.get_member(&id).unwrap();
     let connection = member
         .connections()
-        .wait_for_connection(remote_id)
+        .wait_for_connection(remote_id, *conf::CONNECTION_TIMEOUT)
         .await
         .unwrap();
     let tracks_store = connection.tracks_store().await.unwrap();
@@ -163,6 +224,43 @@ async fn when_enables_or_mutes(
     }
 }
 
+#[then(regex = r"^(\S+)'s (audio|video) unmuting fails with `(\S+)`$")]
+async fn then_unmuting_fails_with(
+    world: &mut World,
+    id: String,
+    audio_or_video: String,
+    kind: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    let media_kind = parse_media_kind(&audio_or_video).unwrap();
+    let err = member.room().unmute_media_error(media_kind).await.unwrap();
+    assert_eq!(err.as_deref(), Some(kind.as_str()));
+}
+
+#[when(regex = "^(\\S+) clicks (audio|video) (mute|unmute) button (\\d+) \
+                 times rapidly$")]
+async fn when_clicks_mute_button_rapidly(
+    world: &mut World,
+    id: String,
+    kind: String,
+    action: String,
+    times: u32,
+) {
+    let member = world.get_member(&id).unwrap();
+    let media_kind = parse_media_kind(&kind);
+
+    // The first click performs the provided action, while the next ones
+    // toggle it back and forth without awaiting the previous ones.
+    let mut muted = action == "mute";
+    for _ in 0..times {
+        member
+            .toggle_mute(media_kind, None, muted, AwaitCompletion::Dont)
+            .await
+            .unwrap();
+        muted = !muted;
+    }
+}
+
 #[when(regex = "^(\\S+) switches device with latency$")]
 async fn when_member_switches_device_with_latency(
     world: &mut World,
@@ -183,8 +281,10 @@ async fn when_member_enables_remote_track(
     kind: String,
 ) {
     let member = world.get_member(&id).unwrap();
-    let media_kind = kind.parse().unwrap();
-    let source_kind = kind.parse().ok();
+    let (media_kind, _) = parse_track_kinds(&kind).unwrap();
+    let source_kind = kind
+        .split_once(' ')
+        .map(|(source, _)| MediaSourceKind::from_name(source).unwrap());
 
     if toggle == "enables" {
         member
@@ -200,3 +300,35 @@ async fn when_member_enables_remote_track(
             .unwrap();
     }
 }
+
+#[then(regex = "^(\\S+)'s media state is consistent with \
+                 (?:her|his|their) local tracks$")]
+async fn then_media_state_is_consistent(world: &mut World, id: String) {
+    let member = world.get_member(&id).unwrap();
+    let state = member.media_state().await.unwrap();
+    let tracks = member.room().local_tracks().await.unwrap();
+    for kind in ["audio", "device video", "display video"] {
+        let (media_kind, source_kind) = parse_track_kinds(kind).unwrap();
+        if !tracks
+            .has_track(media_kind, Some(source_kind))
+            .await
+            .unwrap()
+        {
+            continue;
+        }
+        let track = tracks.get_track(media_kind, source_kind).await.unwrap();
+        let expected = state.send(media_kind, source_kind);
+
+        assert_eq!(
+            track.muted().await.unwrap(),
+            expected.muted,
+            "{id}'s {kind} local track mismatches {expected:?}",
+        );
+        if !expected.enabled {
+            assert!(
+                !track.enabled().await.unwrap(),
+                "{id}'s {kind} local track is sent while disabled",
+            );
+        }
+    }
+}
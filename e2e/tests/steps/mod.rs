@@ -1,33 +1,29 @@
 mod connection;
 mod control_api;
+mod diagnostics;
 mod media_state;
 mod room;
+mod stats;
 mod track;
 mod websocket;
 
 use std::{convert::Infallible, str::FromStr};
 
-use async_recursion::async_recursion;
 use cucumber::given;
-use medea_e2e::object::{
-    room::ParsingFailedError, AwaitCompletion, MediaKind, MediaSourceKind,
-};
+use futures::future;
+use medea_e2e::object::{AwaitCompletion, MediaKind};
 
-use crate::world::{member::Builder as MemberBuilder, World};
+use crate::world::{member::Builder as MemberBuilder, Member, World};
 
-#[allow(clippy::too_many_arguments, clippy::too_many_lines)]
-#[given(regex = "^(?:room with )?(joined )?member(?:s)? (\\S+)\
-                  (?:(?:, | and )(\\S+)(?: and (\\S+)?)?)?\
+#[given(regex = "^(?:room with )?(joined )?member(?:s)? \
+                  (\\S+(?:(?:, | and )\\S+)*)\
                   (?: with (no (play |publish )?WebRTC endpoints\
                           |(?:disabled|muted) (media|audio|video) \
                                               (publishing|playing)?))?$")]
-#[async_recursion(?Send)]
 async fn new_given_member(
     world: &mut World,
     joined: Matched,
-    first_member_id: String,
-    second_member_id: String,
-    third_member_id: String,
+    member_ids: String,
     media_settings: MediaSettings,
     not_endpoint_direction: Direction,
     disabled_media_type: DisabledMediaType,
@@ -43,21 +39,40 @@ async fn new_given_member(
         && (all_endpoints_disabled
             || not_endpoint_direction == Direction::Play);
 
-    let member_builder = MemberBuilder {
-        id: first_member_id.clone(),
-        is_send: !is_send_disabled,
-        is_recv: !is_recv_disabled,
-    };
-    world.create_member(member_builder).await.unwrap();
+    let member_ids = parse_member_ids(&member_ids);
+    let builders = member_ids
+        .iter()
+        .map(|id| MemberBuilder {
+            id: id.clone(),
+            is_send: !is_send_disabled,
+            is_recv: !is_recv_disabled,
+        })
+        .collect();
+    world.create_members(builders).await.unwrap();
+    future::join_all(member_ids.iter().map(|id| {
+        apply_media_settings(
+            world.get_member(id).unwrap(),
+            media_settings,
+            disabled_media_type,
+            disabled_direction,
+        )
+    }))
+    .await;
+
     if joined.0 {
-        world.join_room(&first_member_id).await.unwrap();
-        world
-            .wait_for_interconnection(&first_member_id)
-            .await
-            .unwrap();
+        world.join_rooms(&member_ids).await.unwrap();
+        world.wait_for_full_interconnection().await.unwrap();
     }
+}
 
-    let member = world.get_member(&first_member_id).unwrap();
+/// Applies the provided [`MediaSettings`] to the provided [`Member`].
+#[allow(clippy::too_many_lines)]
+async fn apply_media_settings(
+    member: &Member,
+    media_settings: MediaSettings,
+    disabled_media_type: DisabledMediaType,
+    disabled_direction: Direction,
+) {
     let is_audio = disabled_media_type == DisabledMediaType::Audio
         || disabled_media_type == DisabledMediaType::All;
     let is_video = disabled_media_type == DisabledMediaType::Video
@@ -142,21 +157,14 @@ async fn new_given_member(
         }
         _ => (),
     }
+}
 
-    if !second_member_id.is_empty() {
-        new_given_member(
-            world,
-            joined,
-            second_member_id,
-            third_member_id,
-            String::new(),
-            media_settings,
-            not_endpoint_direction,
-            disabled_media_type,
-            disabled_direction,
-        )
-        .await;
-    }
+/// Parses a list of [`Member`] IDs separated with `, ` or ` and `.
+fn parse_member_ids(text: &str) -> Vec<String> {
+    text.split(", ")
+        .flat_map(|s| s.split(" and "))
+        .map(ToOwned::to_owned)
+        .collect()
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -239,30 +247,16 @@ impl FromStr for Direction {
     }
 }
 
-/// Tries to find `audio`, `video` or `all` in the provided text.
+/// Parses a [`MediaKind`] from its name, or `all`.
+///
+/// If `all` is provided, then [`None`] will be returned.
 ///
-/// If `audio` or `video` found, then [`Some`] [`MediaKind`] will be returned.
-/// If `all` found, then [`None`] will be returned.
-/// Otherwise, this function will panic.
+/// # Panics
+///
+/// If the provided text is neither a [`MediaKind`] name, nor `all`.
 fn parse_media_kind(text: &str) -> Option<MediaKind> {
-    match text {
-        "audio" => Some(MediaKind::Audio),
-        "video" => Some(MediaKind::Video),
-        "all" => None,
-        _ => {
-            panic!("Unknown media kind: {text}")
-        }
-    }
-}
-
-/// Parses a [`MediaKind`] and a [`MediaSourceKind`] from the provided [`str`].
-fn parse_media_kinds(
-    s: &str,
-) -> Result<(MediaKind, MediaSourceKind), ParsingFailedError> {
-    let media_kind = s.parse()?;
-    let source_kind = match media_kind {
-        MediaKind::Audio => MediaSourceKind::Device,
-        MediaKind::Video => s.parse()?,
-    };
-    Ok((media_kind, source_kind))
+    (text != "all").then(|| {
+        MediaKind::from_name(text)
+            .unwrap_or_else(|_| panic!("Unknown media kind: {text}"))
+    })
 }
@@ -1,4 +1,11 @@
+use std::time::Duration;
+
 use cucumber::{given, then, when};
+use medea_e2e::{
+    browser::{mock::MediaRequestError, Statement},
+    object::{self, room::parse_track_kinds},
+};
+use serde_json::json;
 
 use crate::World;
 
@@ -18,6 +25,68 @@ async fn when_member_joins_room(world: &mut World, id: String) {
     world.join_room(&id).await.unwrap();
 }
 
+#[when(regex = r"^(\S+) joins the room twice at once$")]
+async fn when_member_joins_room_twice(world: &mut World, id: String) {
+    world.join_room_twice(&id).await.unwrap();
+}
+
+#[when(regex = "^(\\S+) tries to join (the room|nonexistent room)\
+                 ( with bad token)?$")]
+async fn when_member_tries_to_join_room(
+    world: &mut World,
+    id: String,
+    room: String,
+    bad_token: String,
+) {
+    let room_id = (room == "nonexistent room").then_some("nonexistent-room");
+    let token = if bad_token.is_empty() {
+        "test"
+    } else {
+        "bad-token"
+    };
+    world.try_join_room(&id, room_id, token).await.unwrap();
+}
+
+#[when(regex = r"^(\S+) tries to join the room via unreachable signaling$")]
+async fn when_member_tries_to_join_unreachable(world: &mut World, id: String) {
+    world.try_join_room_unreachable(&id).await.unwrap();
+}
+
+#[then(regex = r"^(\S+)'s join fails in `(\S+)` phase$")]
+async fn then_join_fails_in_phase(
+    world: &mut World,
+    id: String,
+    phase: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    let err = member.join_error().expect("`Room.join()` should fail");
+    assert!(
+        err.message.contains(&format!("`{phase}` join phase")),
+        "unexpected `Room.join()` error: {err}",
+    );
+    assert!(!member.is_joined());
+}
+
+#[then(regex = r"^(\S+)'s join fails with (\S+)$")]
+async fn then_join_fails_with(world: &mut World, id: String, expected: String) {
+    let member = world.get_member(&id).unwrap();
+    let err = member.join_error().expect("`Room.join()` should fail");
+    assert!(
+        err.kind.as_deref() == Some(expected.as_str()) || err.name == expected,
+        "unexpected `Room.join()` error: {err}",
+    );
+    assert!(!member.is_joined());
+}
+
+#[then(regex = r"^(\S+)'s join succeeds$")]
+async fn then_join_succeeds(world: &mut World, id: String) {
+    let member = world.get_member(&id).unwrap();
+    if let Some(err) = member.join_error() {
+        panic!("unexpected `Room.join()` error: {err}");
+    }
+    assert!(member.is_joined());
+}
+
 #[when(regex = r"^(\S+)'s room closed by client$")]
 async fn when_room_closed_by_client(world: &mut World, id: String) {
     world.close_room(&id).await.unwrap();
@@ -28,6 +97,34 @@ async fn when_jason_object_disposes(world: &mut World, id: String) {
     world.dispose_jason(&id).await.unwrap();
 }
 
+#[when(regex = r"^(\S+) reloads the page restoring the room state$")]
+async fn when_member_reloads_restoring_room_state(
+    world: &mut World,
+    id: String,
+) {
+    world.reload_restoring_room_state(&id).await.unwrap();
+}
+
+#[when(regex = "^(\\S+) applies media policy with (enabled|muted|disabled) \
+                 audio and (enabled|muted|disabled) video$")]
+async fn when_member_applies_media_policy(
+    world: &mut World,
+    id: String,
+    audio: String,
+    video: String,
+) {
+    let capitalize = |state: &str| {
+        let (first, rest) = state.split_at(1);
+        format!("{}{rest}", first.to_uppercase())
+    };
+    let member = world.get_member(&id).unwrap();
+    member
+        .room()
+        .apply_media_policy(&capitalize(&audio), &capitalize(&video))
+        .await
+        .unwrap();
+}
+
 #[given(regex = r"^(\S+)'s `getUserMedia\(\)` (audio |video )?errors$")]
 async fn given_member_gum_will_error(
     world: &mut World,
@@ -44,6 +141,461 @@ async fn given_member_gum_will_error(
     media_devices.mock_gum(video, audio).await;
 }
 
+#[given(regex = "^(\\S+)'s `getUserMedia\\(\\)` \
+                  (audio|(?:device|display) video) fails with \
+                  `(NotAllowedError|NotReadableError|OverconstrainedError)`\
+                  (?: on `(\\S+)`)?(?: (\\d+) time(?:s)?)?$")]
+async fn given_member_gum_fails_with(
+    world: &mut World,
+    id: String,
+    kind: String,
+    error_name: String,
+    constraint: String,
+    times: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    let (media_kind, source_kind) = parse_track_kinds(&kind).unwrap();
+    let error = match error_name.as_str() {
+        "NotAllowedError" => MediaRequestError::NotAllowed,
+        "NotReadableError" => MediaRequestError::NotReadable,
+        _ => MediaRequestError::Overconstrained { constraint },
+    };
+    let times = if times.is_empty() {
+        1
+    } else {
+        times.parse().unwrap()
+    };
+    member
+        .media_devices_mock()
+        .fail_get_user_media(media_kind, source_kind, error, times)
+        .await;
+}
+
+#[then(regex = r"^(\S+)'s `getUserMedia\(\)` requests have no (audio|video)$")]
+async fn then_gum_requests_have_no_media(
+    world: &mut World,
+    id: String,
+    kind: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    let media_kind = kind.parse().unwrap();
+    let requests = member.media_devices_mock().get_user_media_requests().await;
+    assert!(
+        !requests.is_empty(),
+        "no `getUserMedia()` requests were made"
+    );
+    for kinds in requests {
+        assert!(
+            !kinds.contains(&media_kind),
+            "`getUserMedia()` requested {kind}: {kinds:?}",
+        );
+    }
+}
+
+#[then(regex = r"^(\S+) makes no `getUserMedia\(\)` requests$")]
+async fn then_no_gum_requests(world: &mut World, id: String) {
+    let member = world.get_member(&id).unwrap();
+    let requests = member.media_devices_mock().get_user_media_requests().await;
+    assert!(
+        requests.is_empty(),
+        "unexpected `getUserMedia()` requests: {requests:?}",
+    );
+}
+
+#[then(regex = r"^(\S+) makes one `getUserMedia\(\)` request per media kind$")]
+async fn then_one_gum_request_per_kind(world: &mut World, id: String) {
+    let member = world.get_member(&id).unwrap();
+    let requests = member.media_devices_mock().get_user_media_requests().await;
+    for kind in [object::MediaKind::Audio, object::MediaKind::Video] {
+        let count = requests.iter().filter(|r| r.contains(&kind)).count();
+        assert_eq!(
+            count, 1,
+            "`getUserMedia()` requested {kind:?} {count} times: {requests:?}",
+        );
+    }
+}
+
+#[given(regex = r"^(\S+) previews local media$")]
+async fn given_member_previews_local_media(world: &mut World, id: String) {
+    world.init_preview_tracks(&id).await.unwrap();
+}
+
+#[then(regex = r"^(\S+)'s preview tracks are published$")]
+async fn then_preview_tracks_are_published(world: &mut World, id: String) {
+    for _ in 0..20 {
+        let published = world.preview_tracks_published(&id).await.unwrap();
+        if !published.is_empty() && published.iter().all(|p| *p) {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    }
+    panic!("{id}'s preview tracks are not published");
+}
+
+#[given(regex = r"^(\S+) has no (audio|video) input devices$")]
+async fn given_member_has_no_input_devices(
+    world: &mut World,
+    id: String,
+    kind: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    let media_kind = kind.parse().unwrap();
+    member
+        .media_devices_mock()
+        .hide_input_devices(media_kind)
+        .await;
+}
+
+#[given(regex = r"^(\S+) has cameras `(\S+)` and `(\S+)`$")]
+async fn given_member_has_cameras(
+    world: &mut World,
+    id: String,
+    first: String,
+    second: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    member
+        .media_devices_mock()
+        .mock_cameras(&[first, second])
+        .await;
+}
+
+#[given(regex = r"^(\S+)'s browser ignores (ideal |)microphone deviceId$")]
+async fn given_member_browser_ignores_device_id(
+    world: &mut World,
+    id: String,
+    ideal: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    member
+        .media_devices_mock()
+        .ignore_device_id(object::MediaKind::Audio, ideal.is_empty())
+        .await;
+}
+
+#[given(regex = r"^(\S+)'s default microphone is `(\S+)`$")]
+async fn given_member_default_microphone(
+    world: &mut World,
+    id: String,
+    device_id: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    member
+        .room()
+        .set_default_microphone(&device_id)
+        .await
+        .unwrap();
+}
+
+#[given(regex = r"^(\S+)'s (camera|microphone) permission is denied$")]
+async fn given_member_permission_is_denied(
+    world: &mut World,
+    id: String,
+    device: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    let media_kind = parse_permission_kind(&device);
+    member
+        .media_devices_mock()
+        .set_permission(media_kind, false)
+        .await;
+}
+
+#[when(regex = r"^(\S+)'s (camera|microphone) permission is granted$")]
+async fn when_member_permission_is_granted(
+    world: &mut World,
+    id: String,
+    device: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    let media_kind = parse_permission_kind(&device);
+    member
+        .media_devices_mock()
+        .set_permission(media_kind, true)
+        .await;
+}
+
+/// Parses an [`object::MediaKind`] captured by the provided device.
+fn parse_permission_kind(device: &str) -> object::MediaKind {
+    if device == "camera" {
+        object::MediaKind::Video
+    } else {
+        object::MediaKind::Audio
+    }
+}
+
+#[given(regex = r"^(\S+)'s microphone is silent$")]
+async fn given_member_microphone_is_silent(world: &mut World, id: String) {
+    let member = world.get_member(&id).unwrap();
+    member.media_devices_mock().silence_audio().await;
+}
+
+#[given(regex = r"^(\S+)'s browser has WebRTC disabled$")]
+async fn given_member_browser_has_webrtc_disabled(
+    world: &mut World,
+    id: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    member.peer_connection_mock().disable_webrtc().await;
+}
+
+#[then(regex = "^(\\S+)'s `Jason.is_supported\\(\\)` reports peer \
+                 connection as (available|unavailable)$")]
+async fn then_jason_reports_peer_connection_support(
+    world: &mut World,
+    id: String,
+    availability: String,
+) {
+    let support = world.webrtc_support(&id).await.unwrap();
+    assert_eq!(support.peer_connection, availability == "available");
+}
+
+#[then(regex = "^(\\S+)'s `MediaManagerHandle.has_device\\(\\)` returns \
+                 (true|false) for (audio|video) input$")]
+async fn then_media_manager_has_device(
+    world: &mut World,
+    id: String,
+    expected: bool,
+    kind: String,
+) {
+    let has = world
+        .has_input_device(&id, kind.parse().unwrap())
+        .await
+        .unwrap();
+    assert_eq!(has, expected);
+}
+
+#[given(regex = "^(\\S+)'s cameras report (\\d+)x(\\d+) resolution at \
+                  (\\d+) fps$")]
+async fn given_member_cameras_report_capabilities(
+    world: &mut World,
+    id: String,
+    width: u32,
+    height: u32,
+    fps: u32,
+) {
+    let member = world.get_member(&id).unwrap();
+    member
+        .media_devices_mock()
+        .set_capabilities(
+            object::MediaKind::Video,
+            json!({
+                "width": { "min": 1, "max": width },
+                "height": { "min": 1, "max": height },
+                "frameRate": { "min": 1, "max": fps },
+                "facingMode": ["user"],
+            }),
+        )
+        .await;
+}
+
+#[given(regex = r"^(\S+)'s cameras report no capabilities$")]
+async fn given_member_cameras_report_no_capabilities(
+    world: &mut World,
+    id: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    member
+        .media_devices_mock()
+        .set_capabilities(object::MediaKind::Video, json!({}))
+        .await;
+}
+
+#[then(
+    regex = "^(\\S+)'s camera capabilities are (\\d+)x(\\d+) at (\\d+) fps \
+                 facing user$"
+)]
+async fn then_camera_capabilities_are(
+    world: &mut World,
+    id: String,
+    width: u32,
+    height: u32,
+    fps: u32,
+) {
+    let caps = world.camera_capabilities(&id).await.unwrap();
+    assert_eq!(caps.width_range, Some([1.0, f64::from(width)]));
+    assert_eq!(caps.height_range, Some([1.0, f64::from(height)]));
+    assert_eq!(caps.frame_rate_range, Some([1.0, f64::from(fps)]));
+    assert_eq!(caps.facing_modes, ["User"]);
+}
+
+#[then(regex = r"^(\S+)'s camera capabilities report its frame rate range$")]
+async fn then_camera_capabilities_report_frame_rate(
+    world: &mut World,
+    id: String,
+) {
+    let caps = world.camera_capabilities(&id).await.unwrap();
+    assert!(caps.frame_rate_range.is_some(), "{caps:?}");
+}
+
+#[then(regex = r"^(\S+) makes (\d+) `getUserMedia\(\)` requests?$")]
+async fn then_gum_requests_count(world: &mut World, id: String, count: usize) {
+    let member = world.get_member(&id).unwrap();
+    let requests = member.media_devices_mock().get_user_media_requests().await;
+    assert_eq!(requests.len(), count, "{requests:?}");
+}
+
+#[given(regex = r"^(\S+)'s screen resolution is (\d+)x(\d+)$")]
+async fn given_member_screen_resolution(
+    world: &mut World,
+    id: String,
+    width: u32,
+    height: u32,
+) {
+    let member = world.get_member(&id).unwrap();
+    member
+        .display_media_mock()
+        .set_resolution(width, height)
+        .await;
+}
+
+#[given(regex = "^(\\S+)'s captured screen surface is \
+                  (monitor|window|browser)$")]
+async fn given_member_display_surface(
+    world: &mut World,
+    id: String,
+    surface: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    member
+        .display_media_mock()
+        .set_display_surface(&surface)
+        .await;
+}
+
+#[given(regex = r"^(\S+) cancels the next screen sharing request$")]
+async fn given_member_cancels_screen_sharing(world: &mut World, id: String) {
+    let member = world.get_member(&id).unwrap();
+    member.display_media_mock().cancel_next_request().await;
+}
+
+#[when(regex = r"^(\S+) (starts|fails to start) screen sharing$")]
+async fn when_member_starts_screen_sharing(
+    world: &mut World,
+    id: String,
+    result: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    let res = member.publish_screen().await;
+    if result == "starts" {
+        res.unwrap();
+    } else {
+        assert!(res.is_err(), "screen sharing should fail to start");
+    }
+}
+
+#[when(regex = r"^(\S+) publishes (?:her|his|their) screen$")]
+async fn when_member_publishes_screen(world: &mut World, id: String) {
+    let member = world.get_member(&id).unwrap();
+    member.publish_screen().await.unwrap();
+}
+
+#[when(regex = r"^(\S+) shares (?:her|his|their) current tab$")]
+async fn when_member_shares_current_tab(world: &mut World, id: String) {
+    let member = world.get_member(&id).unwrap();
+    member.publish_current_tab().await.unwrap();
+}
+
+#[when(regex = r"^(\S+) publishes (\d+)x(\d+) canvas video$")]
+async fn when_member_publishes_canvas_video(
+    world: &mut World,
+    id: String,
+    width: u32,
+    height: u32,
+) {
+    let member = world.get_member(&id).unwrap();
+    member
+        .room()
+        .publish_canvas_video(width, height)
+        .await
+        .unwrap();
+}
+
+#[then(regex = r"^(\S+)'s canvas video track is (live|ended)$")]
+async fn then_canvas_video_track_state(
+    world: &mut World,
+    id: String,
+    state: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    // Lets the library stop the tracks it owns.
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    let is_live = member.room().is_canvas_video_live().await.unwrap();
+    assert_eq!(is_live, state == "live");
+}
+
+#[then(regex = "^(\\S+)'s screen capture request prefers \
+                 (?:her|his|their) current tab$")]
+async fn then_screen_capture_request_prefers_current_tab(
+    world: &mut World,
+    id: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    let options = member.last_screen_capture_options().await;
+    assert_eq!(options["preferCurrentTab"], true, "{options}");
+    assert_eq!(options["selfBrowserSurface"], "include", "{options}");
+    assert_eq!(options["surfaceSwitching"], "exclude", "{options}");
+    assert_eq!(options["systemAudio"], "exclude", "{options}");
+}
+
+#[when(regex = "^(\\S+) (?:stops screen sharing|stops publishing \
+                 (?:her|his|their) screen)$")]
+async fn when_member_stops_screen_sharing(world: &mut World, id: String) {
+    let member = world.get_member(&id).unwrap();
+    member.stop_screen().await;
+}
+
+#[when(regex = r"^(\S+) switches (?:her|his|their) microphone to `(\S+)`$")]
+async fn when_member_switches_microphone(
+    world: &mut World,
+    id: String,
+    device_id: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    member.switch_microphone(device_id).await.unwrap();
+}
+
+#[when(regex = "^(\\S+) switches (?:her|his|their) camera to `(\\S+)`\
+                 ( seamlessly)?$")]
+async fn when_member_switches_camera(
+    world: &mut World,
+    id: String,
+    device_id: String,
+    seamlessly: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    member
+        .switch_camera(device_id, !seamlessly.is_empty())
+        .await
+        .unwrap();
+}
+
+#[then(regex = r"^(\S+) sends video from `(\S+)`$")]
+async fn then_member_sends_video_from(
+    world: &mut World,
+    id: String,
+    device_id: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    member
+        .peer_connection_mock()
+        .wait_for_sent_device_ids(object::MediaKind::Video, &[device_id])
+        .await;
+}
+
+#[when(regex = r"^(\S+) sets video quality to (low|medium|high)$")]
+async fn when_member_sets_video_quality(
+    world: &mut World,
+    id: String,
+    preset: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    member
+        .set_video_quality(preset.parse().unwrap())
+        .await
+        .unwrap();
+}
+
 #[when(regex = "^(\\S+) enables (video|audio|video and audio) in local \
                  media settings$")]
 async fn when_member_enables_via_local_media_settings(
@@ -54,11 +606,28 @@ async fn when_member_enables_via_local_media_settings(
     let member = world.get_member(&id).unwrap();
     let video = kind.contains("video");
     let audio = kind.contains("audio");
-    member
-        .room()
-        .set_local_media_settings(video, audio, true)
-        .await
-        .unwrap();
+    member.set_local_media(video, audio).await.unwrap();
+}
+
+#[when(regex = r"^(\S+) switches to a non-existent camera with rollback$")]
+async fn when_member_switches_to_non_existent_camera(
+    world: &mut World,
+    id: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    let res = member
+        .switch_video_device_to("non-existent".to_owned(), true)
+        .await;
+    assert!(res.is_err(), "switching to non-existent camera should fail");
+}
+
+#[then(regex = r"^(\S+)'s previous media settings are restored$")]
+async fn then_previous_media_settings_are_restored(
+    world: &mut World,
+    id: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    member.wait_for_video_devices_restored().await;
 }
 
 #[then(regex = "^(\\S+)'s `Room.on_failed_local_stream\\(\\)` fires (\\d+) \
@@ -69,5 +638,252 @@ async fn then_room_failed_local_stream_fires(
     times: u64,
 ) {
     let member = world.get_member(&id).unwrap();
-    member.room().when_failed_local_stream_count(times).await;
+    _ = member
+        .room()
+        .wait_for_failed_local_media_count(times)
+        .await
+        .unwrap();
+}
+
+#[then(regex = "^(\\S+)'s `Room.on_failed_local_media\\(\\)` fires (\\d+) \
+                 times? with `(\\S+)`$")]
+async fn then_room_failed_local_media_fires_with(
+    world: &mut World,
+    id: String,
+    times: u64,
+    error: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    let errors = member
+        .room()
+        .wait_for_failed_local_media_count(times)
+        .await
+        .unwrap();
+    assert!(
+        errors.iter().all(|e| *e == error),
+        "{id}'s `Room.on_failed_local_media()` fired with: {errors:?}",
+    );
+}
+
+#[then(regex = r"^(\S+)'s `Room.on_close\(\)` doesn't fire$")]
+async fn then_room_on_close_doesnt_fire(world: &mut World, id: String) {
+    let member = world.get_member(&id).unwrap();
+    assert_eq!(member.room().close_reason().await.unwrap(), None);
+}
+
+#[then(regex = r"^(\S+)'s `Room.on_close\(\)` has fired with `(\S+)`$")]
+async fn then_room_on_close_has_fired(
+    world: &mut World,
+    id: String,
+    reason: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    assert_eq!(member.room().close_reason().await.unwrap(), Some(reason));
+}
+
+#[then(regex = r"^(\S+)'s `Room.on_close\(\)` reason is an error$")]
+async fn then_room_on_close_reason_is_err(world: &mut World, id: String) {
+    let member = world.get_member(&id).unwrap();
+    assert_eq!(
+        member.room().is_close_reason_err().await.unwrap(),
+        Some(true)
+    );
+}
+
+#[then(regex = r"^never resolving statement on (\S+)'s room times out$")]
+async fn then_never_resolving_statement_times_out(
+    world: &mut World,
+    id: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    let err = member
+        .room()
+        .execute_with_timeout(
+            Statement::new(
+                // language=JavaScript
+                "async (room) => await new Promise(() => {})",
+                [],
+            ),
+            Duration::from_millis(500),
+        )
+        .await
+        .unwrap_err();
+
+    assert!(
+        matches!(err, object::Error::Timeout(_)),
+        "unexpected: {err}"
+    );
+    let message = err.to_string();
+    assert!(
+        message.contains("async (room) => await new Promise(() => {})"),
+        "statement source is missing: {message}",
+    );
+    assert!(
+        message.contains("Room"),
+        "object type is missing: {message}"
+    );
+}
+
+#[then(regex = "^(\\S+)'s late registered callbacks receive connection \
+                 with (\\S+), (\\d+) local tracks? and (\\d+) remote \
+                 tracks?$")]
+async fn then_late_callbacks_receive(
+    world: &mut World,
+    id: String,
+    partner_id: String,
+    local_tracks: u64,
+    remote_tracks: u64,
+) {
+    let member = world.get_member(&id).unwrap();
+    let delivered = member.room().register_late_callbacks().await.unwrap();
+
+    assert_eq!(delivered.connections, [partner_id]);
+    assert_eq!(delivered.local_tracks, local_tracks);
+    assert_eq!(delivered.remote_tracks, remote_tracks);
+}
+
+#[when(regex = r"^(\S+) records members' media states$")]
+async fn when_member_records_media_states(world: &mut World, id: String) {
+    let member = world.get_member(&id).unwrap();
+    member.room().record_member_media_states().await.unwrap();
+}
+
+#[then(regex = "^(\\S+) observes `([^`]+)` media states of (\\S+)'s \
+                 (audio|(?:device|display) video)$")]
+async fn then_member_observes_media_states(
+    world: &mut World,
+    id: String,
+    states: String,
+    partner_id: String,
+    kind: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    let (media_kind, source_kind) = parse_track_kinds(&kind).unwrap();
+    let expected: Vec<_> = states.split(", ").map(ToOwned::to_owned).collect();
+
+    let mut recorded = Vec::new();
+    for _ in 0..50 {
+        recorded = member
+            .room()
+            .recorded_member_media_states(&partner_id, media_kind, source_kind)
+            .await
+            .unwrap();
+        if recorded == expected {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    panic!("{id} observed {recorded:?} media states instead of {expected:?}");
+}
+
+#[when(regex = r"^(\S+) records forced media states$")]
+async fn when_member_records_forced_media_states(
+    world: &mut World,
+    id: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    member.room().record_forced_media_states().await.unwrap();
+}
+
+#[then(regex = "^(\\S+) observes `([^`]+)` server-forced states of local \
+                 (audio|(?:device|display) video)$")]
+async fn then_member_observes_forced_media_states(
+    world: &mut World,
+    id: String,
+    states: String,
+    kind: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    let (media_kind, source_kind) = parse_track_kinds(&kind).unwrap();
+    let expected: Vec<_> = states.split(", ").map(ToOwned::to_owned).collect();
+
+    let mut recorded = Vec::new();
+    for _ in 0..50 {
+        recorded = member
+            .room()
+            .recorded_forced_media_states(media_kind, source_kind)
+            .await
+            .unwrap();
+        let observed: Vec<_> =
+            recorded.iter().map(|s| s.state.clone()).collect();
+        if observed == expected {
+            assert!(recorded.iter().all(|s| s.by_server));
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    panic!("{id} observed {recorded:?} forced states instead of {expected:?}");
+}
+
+#[when(regex = r"^(\S+) records join progress$")]
+async fn when_member_records_join_progress(world: &mut World, id: String) {
+    let member = world.get_member(&id).unwrap();
+    member.room().record_join_progress().await.unwrap();
+}
+
+#[then(regex = r"^(\S+) observes `([^`]+)` join phases$")]
+async fn then_member_observes_join_phases(
+    world: &mut World,
+    id: String,
+    phases: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    let expected: Vec<_> = phases.split(", ").map(ToOwned::to_owned).collect();
+
+    let mut recorded = Vec::new();
+    for _ in 0..50 {
+        recorded = member.room().recorded_join_phases().await.unwrap();
+        if recorded == expected {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    panic!("{id} observed {recorded:?} join phases instead of {expected:?}");
+}
+
+#[when(regex = r"^(\S+)'s `Room` has `AudioFirst` adaptive policy$")]
+async fn when_room_has_audio_first_policy(world: &mut World, id: String) {
+    let member = world.get_member(&id).unwrap();
+    member
+        .room()
+        .set_audio_first_adaptive_policy(1000)
+        .await
+        .unwrap();
+}
+
+#[when(regex = r"^(\S+)'s outbound video bitrate is capped at (\d+) bps$")]
+async fn when_outbound_video_bitrate_is_capped(
+    world: &mut World,
+    id: String,
+    bitrate: u32,
+) {
+    let member = world.get_member(&id).unwrap();
+    member
+        .peer_connection_mock()
+        .cap_video_bitrate(bitrate)
+        .await;
+}
+
+#[when(regex = r"^(\S+) (pauses|resumes) video encoding$")]
+async fn when_member_pauses_video_encoding(
+    world: &mut World,
+    id: String,
+    action: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    member
+        .peer_connection_mock()
+        .set_video_encoding_paused(action == "pauses")
+        .await;
+}
+
+#[then(regex = r"^(\S+)'s `Room.on_adaptation\(\)` fires with degraded video$")]
+async fn then_on_adaptation_fires_with_degraded_video(
+    world: &mut World,
+    id: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    let adaptations = member.room().wait_for_adaptations(1).await.unwrap();
+
+    assert!(adaptations[0].is_degraded(), "{adaptations:?}");
 }
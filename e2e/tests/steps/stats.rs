@@ -0,0 +1,56 @@
+use cucumber::then;
+use medea_e2e::object::MediaSourceKind;
+
+use crate::{conf, World};
+
+#[then(regex = r"^(\S+)'s outbound video bitrate is below (\d+) kbps$")]
+async fn then_outbound_video_bitrate_is_below(
+    world: &mut World,
+    id: String,
+    kbps: u64,
+) {
+    let member = world.get_member(&id).unwrap();
+    member
+        .wait_for_outbound_video_bitrate_below(
+            kbps * 1000,
+            *conf::STATS_TIMEOUT,
+        )
+        .await
+        .unwrap();
+}
+
+#[then(regex = r"^(\S+) doesn't send audio$")]
+async fn then_member_doesnt_send_audio(world: &mut World, id: String) {
+    let member = world.get_member(&id).unwrap();
+    // Each sample covers a separate interval, so any transiently sent audio
+    // is caught.
+    for _ in 0..10 {
+        let stats = member.room().get_stats().await.unwrap();
+        assert!(
+            stats.outbound_audio_bitrate() == 0.0,
+            "{id} sends audio: {stats:?}",
+        );
+    }
+}
+
+#[then(regex = "^(\\S+)'s outbound (device|display) video resolution \
+                 is at least (\\d+)x(\\d+)$")]
+async fn then_outbound_video_resolution_is_at_least(
+    world: &mut World,
+    id: String,
+    source_kind: String,
+    width: u64,
+    height: u64,
+) {
+    let member = world.get_member(&id).unwrap();
+    let source_kind = source_kind.parse::<MediaSourceKind>().unwrap();
+    member
+        .wait_for_outbound_video_frame_size(
+            source_kind,
+            width,
+            height,
+            *conf::STATS_TIMEOUT,
+        )
+        .await
+        .unwrap();
+}
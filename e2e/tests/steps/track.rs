@@ -1,10 +1,35 @@
 use std::time::Duration;
 
-use cucumber::then;
-use medea_e2e::object::{MediaKind, MediaSourceKind};
-use tokio::time::sleep;
+use cucumber::{given, then, when};
+use medea_e2e::object::{room::parse_track_kinds, MediaKind, MediaSourceKind};
+use tokio::time::{sleep, timeout};
 
-use crate::{steps::parse_media_kinds, world::World};
+use crate::{conf, world::World};
+
+/// Audio level above which a remote audio track is considered audible.
+///
+/// Mocked audio tracks carry a full-scale sine tone having an [RMS][1] of
+/// about `0.7`, so this leaves a wide margin for codec losses.
+///
+/// [1]: https://en.wikipedia.org/wiki/Root_mean_square
+const AUDIBLE_LEVEL: f64 = 0.1;
+
+/// Audio level below which a remote audio track is considered silent.
+const SILENT_LEVEL: f64 = 0.01;
+
+/// Audio level (in [dBFS][1]) below which a local audio track is detected as
+/// silent.
+///
+/// [1]: https://en.wikipedia.org/wiki/DBFS
+const SILENCE_THRESHOLD_DB: f64 = -60.0;
+
+/// Duration (in milliseconds) a local audio track should stay silent for to
+/// be detected as silent.
+const SILENCE_DURATION_MS: u32 = 500;
+
+/// Duration (in milliseconds) a remote video track should stop decoding new
+/// frames for to be detected as frozen.
+const FREEZE_TIMEOUT_MS: u32 = 2000;
 
 #[then(regex = r"^(\S+) has (\d+) local track(?:s)?$")]
 async fn then_member_has_local_tracks(
@@ -30,7 +55,7 @@ async fn then_member_has_remote_track(
     let member = world.get_member(&id).unwrap();
     let connection = member
         .connections()
-        .wait_for_connection(remote_id)
+        .wait_for_connection(remote_id, *conf::CONNECTION_TIMEOUT)
         .await
         .unwrap();
     let tracks_store = connection.tracks_store().await.unwrap();
@@ -54,11 +79,11 @@ async fn then_has_local_track(world: &mut World, id: String, kind: String) {
     let member = world.get_member(&id).unwrap();
     let room = member.room();
     let tracks = room.local_tracks().await.unwrap();
-    let media_kind = kind.parse().unwrap();
+    let (media_kind, _) = parse_track_kinds(&kind).unwrap();
 
     let mut source_kinds = Vec::with_capacity(2);
-    if let Ok(kind) = kind.parse() {
-        source_kinds.push(kind);
+    if let Some((source, _)) = kind.split_once(' ') {
+        source_kinds.push(MediaSourceKind::from_name(source).unwrap());
     } else {
         if media_kind == MediaKind::Video {
             source_kinds.push(MediaSourceKind::Display);
@@ -70,6 +95,217 @@ async fn then_has_local_track(world: &mut World, id: String, kind: String) {
     }
 }
 
+#[then(regex = "^(\\S+)'s (device|display) video local track encodes \
+                 keyframe on request$")]
+async fn then_local_track_encodes_keyframe_on_request(
+    world: &mut World,
+    id: String,
+    source_kind: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    let track = member
+        .room()
+        .local_tracks()
+        .await
+        .unwrap()
+        .get_track(MediaKind::Video, source_kind.parse().unwrap())
+        .await
+        .unwrap();
+
+    let before = track.keyframes_encoded().await.unwrap();
+    track.request_keyframe().await.unwrap();
+    timeout(*conf::STATS_TIMEOUT, async {
+        while track.keyframes_encoded().await.unwrap() <= before {
+            sleep(Duration::from_millis(100)).await;
+        }
+    })
+    .await
+    .unwrap();
+}
+
+#[then(regex = r"^(\S+)'s audio local track (is|isn't) detected silent$")]
+async fn then_local_audio_track_detected_silent(
+    world: &mut World,
+    id: String,
+    is: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    let track = member
+        .room()
+        .local_tracks()
+        .await
+        .unwrap()
+        .get_track(MediaKind::Audio, MediaSourceKind::Device)
+        .await
+        .unwrap();
+
+    track
+        .enable_silence_detection(SILENCE_THRESHOLD_DB, SILENCE_DURATION_MS)
+        .await
+        .unwrap();
+    if is == "is" {
+        timeout(Duration::from_secs(5), async {
+            while track.silence_changes().await.unwrap().is_empty() {
+                sleep(Duration::from_millis(100)).await;
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(track.silence_changes().await.unwrap(), [true]);
+    } else {
+        sleep(Duration::from_secs(2)).await;
+        let changes = track.silence_changes().await.unwrap();
+        assert!(changes.is_empty(), "silence changes reported: {changes:?}");
+    }
+}
+
+#[then(regex = "^(\\S+)'s (device|display) video local track surface is \
+                 (Monitor|Window|Browser|Unknown|none)$")]
+async fn then_local_track_display_surface(
+    world: &mut World,
+    id: String,
+    source_kind: String,
+    surface: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    let track = member
+        .room()
+        .local_tracks()
+        .await
+        .unwrap()
+        .get_track(MediaKind::Video, source_kind.parse().unwrap())
+        .await
+        .unwrap();
+
+    let expected = (surface != "none").then_some(surface);
+    assert_eq!(track.display_surface().await.unwrap(), expected);
+}
+
+#[then(regex = r"^(\S+)'s audio local track is captured from `(\S+)`$")]
+async fn then_local_track_is_captured_from(
+    world: &mut World,
+    id: String,
+    device_id: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    let track = member
+        .room()
+        .local_tracks()
+        .await
+        .unwrap()
+        .get_track(MediaKind::Audio, MediaSourceKind::Device)
+        .await
+        .unwrap();
+    assert_eq!(track.device_id().await.unwrap(), device_id);
+}
+
+#[then(regex = "^(\\S+)'s audio local track reports device mismatch of \
+                 `(\\S+)` with `(\\S+)`$")]
+async fn then_local_track_reports_device_mismatch(
+    world: &mut World,
+    id: String,
+    requested: String,
+    actual: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    let track = member
+        .room()
+        .local_tracks()
+        .await
+        .unwrap()
+        .get_track(MediaKind::Audio, MediaSourceKind::Device)
+        .await
+        .unwrap();
+    assert_eq!(
+        track.device_mismatch().await.unwrap(),
+        Some((requested, actual)),
+    );
+}
+
+#[then(regex = r"^(\S+)'s audio local track reports no device mismatch$")]
+async fn then_local_track_reports_no_device_mismatch(
+    world: &mut World,
+    id: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    let track = member
+        .room()
+        .local_tracks()
+        .await
+        .unwrap()
+        .get_track(MediaKind::Audio, MediaSourceKind::Device)
+        .await
+        .unwrap();
+    assert_eq!(track.device_mismatch().await.unwrap(), None);
+}
+
+#[then(regex = "^(\\S+)'s display video remote track from (\\S+) surface \
+                 is (Monitor|Window|Browser|Unknown)$")]
+async fn then_remote_track_display_surface(
+    world: &mut World,
+    id: String,
+    partner_id: String,
+    surface: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    let partner_connection = member
+        .connections()
+        .wait_for_connection(partner_id, *conf::CONNECTION_TIMEOUT)
+        .await
+        .unwrap();
+    let track = partner_connection
+        .tracks_store()
+        .await
+        .unwrap()
+        .get_track(MediaKind::Video, MediaSourceKind::Display)
+        .await
+        .unwrap();
+
+    timeout(
+        *conf::CONNECTION_TIMEOUT,
+        track.wait_for_display_surface(&surface),
+    )
+    .await
+    .expect("display surface isn't reported in time")
+    .unwrap();
+}
+
+#[then(regex = "^every member has (audio|video|audio and video) remote \
+                 track(?:s)? from every other member$")]
+async fn then_every_member_has_remote_tracks_from_everyone(
+    world: &mut World,
+    kind: String,
+) {
+    world
+        .for_each_pair(|member, partner| {
+            let kind = kind.clone();
+            async move {
+                let tracks_store = member
+                    .connections()
+                    .wait_for_connection(
+                        partner.id().to_owned(),
+                        *conf::CONNECTION_TIMEOUT,
+                    )
+                    .await?
+                    .tracks_store()
+                    .await?;
+                if kind.contains("audio") {
+                    tracks_store
+                        .get_track(MediaKind::Audio, MediaSourceKind::Device)
+                        .await?;
+                }
+                if kind.contains("video") {
+                    tracks_store
+                        .get_track(MediaKind::Video, MediaSourceKind::Device)
+                        .await?;
+                }
+                Ok(())
+            }
+        })
+        .await
+        .unwrap();
+}
+
 #[then(regex = "^(\\S+)'s remote (audio|(?:device|display) video) track \
                  from (\\S+) disables$")]
 async fn then_remote_track_stops(
@@ -79,7 +315,7 @@ async fn then_remote_track_stops(
     remote_id: String,
 ) {
     let member = world.get_member(&id).unwrap();
-    let (media_kind, source_kind) = parse_media_kinds(&kind).unwrap();
+    let (media_kind, source_kind) = parse_track_kinds(&kind).unwrap();
 
     let conn = member.connections().get(remote_id).await.unwrap().unwrap();
     let track = conn
@@ -106,7 +342,7 @@ async fn then_callback_fires_on_remote_track(
     let member = world.get_member(&id).unwrap();
     let remote_conn =
         member.connections().get(remote_id).await.unwrap().unwrap();
-    let (media_kind, source_kind) = parse_media_kinds(&kind).unwrap();
+    let (media_kind, source_kind) = parse_track_kinds(&kind).unwrap();
     let track = remote_conn
         .tracks_store()
         .await
@@ -136,6 +372,63 @@ async fn then_callback_fires_on_remote_track(
     }
 }
 
+#[when(regex = "^(\\S+) watches (?:her|his|their) \
+                 (audio|(?:device|display) video) remote track from (\\S+) \
+                 for mutes$")]
+async fn when_member_watches_remote_track_mutes(
+    world: &mut World,
+    id: String,
+    kind: String,
+    remote_id: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    let (media_kind, source_kind) = parse_track_kinds(&kind).unwrap();
+    let track = member
+        .connections()
+        .wait_for_connection(remote_id, *conf::CONNECTION_TIMEOUT)
+        .await
+        .unwrap()
+        .tracks_store()
+        .await
+        .unwrap()
+        .get_track(media_kind, source_kind)
+        .await
+        .unwrap();
+    track.watch_mutes().await.unwrap();
+}
+
+#[then(regex = "^(\\S+)'s (audio|(?:display|device) video) remote track \
+                 from (\\S+) has never been muted$")]
+async fn then_remote_track_has_never_been_muted(
+    world: &mut World,
+    id: String,
+    kind: String,
+    remote_id: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    let (media_kind, source_kind) = parse_track_kinds(&kind).unwrap();
+    let track = member
+        .connections()
+        .get(remote_id)
+        .await
+        .unwrap()
+        .unwrap()
+        .tracks_store()
+        .await
+        .unwrap()
+        .get_track(media_kind, source_kind)
+        .await
+        .unwrap();
+
+    // Let a possible mute of the stopped RTP stream reach the receiver.
+    sleep(Duration::from_secs(2)).await;
+    assert_eq!(
+        track.mutes_count().await.unwrap(),
+        0,
+        "{id}'s remote {kind} track has been muted",
+    );
+}
+
 #[then(regex = "^(\\S+)'s (audio|(?:display|device) video) remote track \
                  from (\\S+) is (enabled|disabled)$")]
 async fn then_remote_media_track(
@@ -148,12 +441,12 @@ async fn then_remote_media_track(
     let member = world.get_member(&id).unwrap();
     let partner_connection = member
         .connections()
-        .wait_for_connection(partner_id)
+        .wait_for_connection(partner_id, *conf::CONNECTION_TIMEOUT)
         .await
         .unwrap();
     let tracks_with_partner = partner_connection.tracks_store().await.unwrap();
 
-    let (media_kind, source_kind) = parse_media_kinds(&kind).unwrap();
+    let (media_kind, source_kind) = parse_track_kinds(&kind).unwrap();
     let track = tracks_with_partner
         .get_track(media_kind, source_kind)
         .await
@@ -167,6 +460,43 @@ async fn then_remote_media_track(
     };
 }
 
+#[then(regex = "^(\\S+)'s (audio|(?:display|device) video) remote track \
+                 from (\\S+) arrives (muted|unmuted|enabled|disabled)$")]
+async fn then_remote_track_arrives(
+    world: &mut World,
+    id: String,
+    kind: String,
+    partner_id: String,
+    state: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    let partner_connection = member
+        .connections()
+        .wait_for_connection(partner_id, *conf::CONNECTION_TIMEOUT)
+        .await
+        .unwrap();
+    let (media_kind, source_kind) = parse_track_kinds(&kind).unwrap();
+    let track = partner_connection
+        .tracks_store()
+        .await
+        .unwrap()
+        .get_track(media_kind, source_kind)
+        .await
+        .unwrap();
+
+    let is_state = match state.as_str() {
+        "muted" => track.muted_on_arrival().await.unwrap(),
+        "unmuted" => !track.muted_on_arrival().await.unwrap(),
+        "enabled" => !track.disabled_on_arrival().await.unwrap(),
+        "disabled" => track.disabled_on_arrival().await.unwrap(),
+        _ => unreachable!(),
+    };
+    assert!(
+        is_state,
+        "{id}'s remote {kind} track doesn't arrive {state}"
+    );
+}
+
 #[then(regex = "^(\\S+) doesn't have (audio|(?:device|display) video) \
                  remote track from (\\S+)$")]
 async fn then_doesnt_have_remote_track(
@@ -178,11 +508,11 @@ async fn then_doesnt_have_remote_track(
     let member = world.get_member(&id).unwrap();
     let partner_connection = member
         .connections()
-        .wait_for_connection(partner_id)
+        .wait_for_connection(partner_id, *conf::CONNECTION_TIMEOUT)
         .await
         .unwrap();
     let tracks_with_partner = partner_connection.tracks_store().await.unwrap();
-    let (media_kind, source_kind) = parse_media_kinds(&kind).unwrap();
+    let (media_kind, source_kind) = parse_track_kinds(&kind).unwrap();
 
     assert!(!tracks_with_partner
         .has_track(media_kind, Some(source_kind))
@@ -199,7 +529,7 @@ async fn then_member_doesnt_have_remote_tracks_with(
     let member = world.get_member(&id).unwrap();
     let connection = member
         .connections()
-        .wait_for_connection(partner_id)
+        .wait_for_connection(partner_id, *conf::CONNECTION_TIMEOUT)
         .await
         .unwrap();
     let tracks_store = connection.tracks_store().await.unwrap();
@@ -214,8 +544,8 @@ async fn then_member_doesnt_have_live_local_tracks(
 ) {
     let member = world.get_member(&id).unwrap();
     let local_tracks = member.room().local_tracks().await.unwrap();
-    let count = local_tracks.count_tracks_by_live(true).await.unwrap();
-    assert_eq!(count, 0);
+    let counts = local_tracks.count_by_liveness().await.unwrap();
+    assert_eq!(counts.live, 0, "{id} has live local tracks: {counts:?}");
 }
 
 #[then(regex = r"^(\S+) has (\d+) (live|stopped) remote tracks from (\S+)$")]
@@ -229,19 +559,261 @@ async fn then_member_has_n_remote_tracks_from(
     let member = world.get_member(&id).unwrap();
     let connection = member
         .connections()
-        .wait_for_connection(remote_id)
+        .wait_for_connection(remote_id, *conf::CONNECTION_TIMEOUT)
         .await
         .unwrap();
     let tracks_store = connection.tracks_store().await.unwrap();
     let live = live_or_stopped == "live";
 
-    let mut actual_count = 0;
-    for _ in 0..5 {
-        actual_count = tracks_store.count_tracks_by_live(live).await.unwrap();
-        if actual_count != expected_count {
-            sleep(Duration::from_millis(300)).await;
-        }
+    tracks_store
+        .wait_for_count_by_live(live, expected_count)
+        .await
+        .unwrap();
+    let actual_count = tracks_store.count_tracks_by_live(live).await.unwrap();
+    assert_eq!(actual_count, expected_count);
+}
+
+#[then(regex = "^(\\S+)'s remote (audio|(?:device|display) video) track \
+                 from (\\S+) stops$")]
+async fn then_remote_track_is_stopped(
+    world: &mut World,
+    id: String,
+    kind: String,
+    remote_id: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    let (media_kind, source_kind) = parse_track_kinds(&kind).unwrap();
+    let connection = member
+        .connections()
+        .wait_for_connection(remote_id, *conf::CONNECTION_TIMEOUT)
+        .await
+        .unwrap();
+    connection
+        .tracks_store()
+        .await
+        .unwrap()
+        .wait_for_track_stopped(media_kind, source_kind)
+        .await
+        .unwrap();
+}
+
+#[then(regex = r"^(\S+) (hears|doesn't hear) audio from (\S+)$")]
+async fn then_member_hears_audio_from(
+    world: &mut World,
+    id: String,
+    hears: String,
+    remote_id: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    let track = member
+        .connections()
+        .wait_for_connection(remote_id, *conf::CONNECTION_TIMEOUT)
+        .await
+        .unwrap()
+        .tracks_store()
+        .await
+        .unwrap()
+        .get_track(MediaKind::Audio, MediaSourceKind::Device)
+        .await
+        .unwrap();
+    if hears == "hears" {
+        track
+            .wait_for_audio_level_above(AUDIBLE_LEVEL)
+            .await
+            .unwrap();
+    } else {
+        track
+            .wait_for_audio_level_below(SILENT_LEVEL)
+            .await
+            .unwrap();
     }
+}
 
-    assert_eq!(actual_count, expected_count);
+#[then(regex = "^(\\S+)'s (device|display) video remote track from (\\S+) \
+                 keeps rendering once attached and freed$")]
+async fn then_remote_track_renders_once_freed(
+    world: &mut World,
+    id: String,
+    source_kind: String,
+    partner_id: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    let connection = member
+        .connections()
+        .wait_for_connection(partner_id, *conf::CONNECTION_TIMEOUT)
+        .await
+        .unwrap();
+    let source_kind = if source_kind == "device" {
+        MediaSourceKind::Device
+    } else {
+        MediaSourceKind::Display
+    };
+    let track = connection
+        .tracks_store()
+        .await
+        .unwrap()
+        .get_track(MediaKind::Video, source_kind)
+        .await
+        .unwrap();
+
+    track.attach_and_free().await.unwrap();
+    // Lets any consequences of the freeing to happen.
+    sleep(Duration::from_secs(1)).await;
+    for _ in 0..3 {
+        assert!(track.attached_video_renders().await.unwrap());
+    }
+}
+
+#[given(regex = "^(\\S+)'s device video remote track from (\\S+) \
+                  watches freezes$")]
+async fn given_remote_track_watches_freezes(
+    world: &mut World,
+    id: String,
+    partner_id: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    let connection = member
+        .connections()
+        .wait_for_connection(partner_id, *conf::CONNECTION_TIMEOUT)
+        .await
+        .unwrap();
+    let track = connection
+        .tracks_store()
+        .await
+        .unwrap()
+        .get_track(MediaKind::Video, MediaSourceKind::Device)
+        .await
+        .unwrap();
+
+    track.watch_freezes(FREEZE_TIMEOUT_MS).await.unwrap();
+}
+
+#[then(regex = "^(\\S+)'s device video remote track from (\\S+) \
+                 (freezes|unfreezes)$")]
+async fn then_remote_track_freezes(
+    world: &mut World,
+    id: String,
+    partner_id: String,
+    change: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    let connection = member
+        .connections()
+        .wait_for_connection(partner_id, *conf::CONNECTION_TIMEOUT)
+        .await
+        .unwrap();
+    let track = connection
+        .tracks_store()
+        .await
+        .unwrap()
+        .get_track(MediaKind::Video, MediaSourceKind::Device)
+        .await
+        .unwrap();
+
+    let unfreeze = change == "unfreezes";
+    timeout(
+        *conf::STATS_TIMEOUT,
+        track.wait_for_on_freeze_fire_count(1, unfreeze),
+    )
+    .await
+    .unwrap()
+    .unwrap();
+    assert_eq!(track.is_frozen().await.unwrap(), !unfreeze);
+}
+
+#[then(regex = r"^(\S+) receives (\d+)x(\d+) device video from (\S+)$")]
+async fn then_member_receives_video_of_size(
+    world: &mut World,
+    id: String,
+    width: u32,
+    height: u32,
+    partner_id: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    let connection = member
+        .connections()
+        .wait_for_connection(partner_id, *conf::CONNECTION_TIMEOUT)
+        .await
+        .unwrap();
+    let track = connection
+        .tracks_store()
+        .await
+        .unwrap()
+        .get_track(MediaKind::Video, MediaSourceKind::Device)
+        .await
+        .unwrap();
+
+    timeout(
+        *conf::CONNECTION_TIMEOUT,
+        track.wait_for_video_size(width, height),
+    )
+    .await
+    .unwrap()
+    .unwrap();
+}
+
+#[given(regex = "^(\\S+)'s (audio|video) local track records media \
+                  exchange changes$")]
+async fn given_local_track_records_media_exchange_changes(
+    world: &mut World,
+    id: String,
+    kind: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    let track = member
+        .room()
+        .local_tracks()
+        .await
+        .unwrap()
+        .get_track(kind.parse().unwrap(), MediaSourceKind::Device)
+        .await
+        .unwrap();
+
+    track.record_media_exchange_changes().await.unwrap();
+}
+
+#[then(regex = "^(\\S+)'s (audio|video) local track has reported \
+                 `(\\S*)` media exchange changes$")]
+async fn then_local_track_reported_media_exchange_changes(
+    world: &mut World,
+    id: String,
+    kind: String,
+    changes: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    let track = member
+        .room()
+        .local_tracks()
+        .await
+        .unwrap()
+        .get_track(kind.parse().unwrap(), MediaSourceKind::Device)
+        .await
+        .unwrap();
+
+    let expected: Vec<_> =
+        changes.split(',').filter(|c| !c.is_empty()).collect();
+    assert_eq!(track.media_exchange_changes().await.unwrap(), expected);
+}
+
+#[when(regex = "^(\\S+) rotates (?:her|his|their) device video by (\\d+) \
+                 degrees( and mirrors it|)$")]
+async fn when_member_rotates_device_video(
+    world: &mut World,
+    id: String,
+    rotation: u16,
+    mirror: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    let track = member
+        .room()
+        .local_tracks()
+        .await
+        .unwrap()
+        .get_track(MediaKind::Video, MediaSourceKind::Device)
+        .await
+        .unwrap();
+    track
+        .set_transform(rotation, !mirror.is_empty())
+        .await
+        .unwrap();
 }
@@ -1,4 +1,8 @@
-use cucumber::{then, when};
+use std::time::Duration;
+
+use cucumber::{given, then, when};
+use medea_e2e::object::AwaitCompletion;
+use tokio::time::sleep;
 
 use crate::World;
 
@@ -8,11 +12,196 @@ async fn ws_connection_loss(world: &mut World, id: String) {
     member.ws_mock().enable_connection_loss(9999).await;
 }
 
+#[when(regex = "^Medea closes (\\S+)'s WS with code (\\d+)\
+                 (?: and `(\\S+)` reason)?$")]
+async fn ws_closed_by_server(
+    world: &mut World,
+    id: String,
+    code: u16,
+    reason: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    let description = if reason.is_empty() {
+        String::new()
+    } else {
+        format!(r#"{{"reason":"{reason}"}}"#)
+    };
+    member.ws_mock().close(code, &description).await;
+}
+
+#[when(regex = r"^(\S+)'s WS drops abruptly$")]
+async fn ws_drops_abruptly(world: &mut World, id: String) {
+    let member = world.get_member(&id).unwrap();
+    member.ws_mock().abort().await;
+}
+
+#[when(regex = r"^Medea forces (\S+)'s (audio|video) to be (muted|unmuted)$")]
+async fn ws_forces_mute_state(
+    world: &mut World,
+    id: String,
+    kind: String,
+    state: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    let kind = if kind == "audio" { "Audio" } else { "Video" };
+    member
+        .ws_mock()
+        .force_mute_state(kind, state == "muted")
+        .await;
+}
+
+#[when(regex = r"^Medea ignores (\S+)'s `(\S+)` commands$")]
+async fn ws_ignores_commands(world: &mut World, id: String, name: String) {
+    let member = world.get_member(&id).unwrap();
+    member.ws_mock().drop_commands(Some(&name)).await;
+}
+
 #[when(regex = r"^(\S+) restores WS connection$")]
 async fn ws_connection_restore(world: &mut World, id: String) {
     let member = world.get_member(&id).unwrap();
     member.ws_mock().disable_connection_loss().await;
-    member.room().start_ws_reconnect().await.unwrap();
+    member
+        .room()
+        .start_ws_reconnect(AwaitCompletion::Do)
+        .await
+        .unwrap();
+}
+
+#[when(regex = r"^(\S+)'s WS connection is restored after (\d+) ms$")]
+async fn ws_connection_restore_after(world: &mut World, id: String, ms: u64) {
+    let member = world.get_member(&id).unwrap();
+    member.ws_mock().restore_after(ms).await;
+    member
+        .room()
+        .start_ws_reconnect(AwaitCompletion::Dont)
+        .await
+        .unwrap();
+}
+
+#[when(regex = "^(\\S+)'s WS connection is restored after (\\d+) ms \
+                 with the first `ReconnectHandle`$")]
+async fn ws_connection_restore_after_with_first_handle(
+    world: &mut World,
+    id: String,
+    ms: u64,
+) {
+    let member = world.get_member(&id).unwrap();
+    member.ws_mock().restore_after(ms).await;
+    member
+        .room()
+        .start_ws_reconnect_with_first_handle(AwaitCompletion::Dont)
+        .await
+        .unwrap();
+}
+
+#[then(regex = r"^(\S+)'s first `ReconnectHandle` reports (\S+)$")]
+async fn first_reconnect_handle_reports(
+    world: &mut World,
+    id: String,
+    outcome: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    assert_eq!(
+        member.room().reconnect_with_first_handle().await.unwrap(),
+        outcome,
+    );
+}
+
+#[when(regex = "^(\\S+)'s WS connection is restored after (\\d+) ms \
+                 with (\\d+) ms backoff$")]
+async fn ws_connection_restore_after_with_backoff(
+    world: &mut World,
+    id: String,
+    ms: u64,
+    backoff_ms: u64,
+) {
+    let member = world.get_member(&id).unwrap();
+    member.ws_mock().restore_after(ms).await;
+    member
+        .room()
+        .start_ws_reconnect_with_backoff(
+            backoff_ms,
+            backoff_ms,
+            None,
+            AwaitCompletion::Dont,
+        )
+        .await
+        .unwrap();
+}
+
+#[when(regex = r"^(\S+) tries to reconnect for (\d+) ms$")]
+async fn ws_reconnect_with_max_elapsed_time(
+    world: &mut World,
+    id: String,
+    ms: u64,
+) {
+    let member = world.get_member(&id).unwrap();
+    member
+        .room()
+        .start_ws_reconnect_with_backoff(
+            100,
+            100,
+            Some(ms),
+            AwaitCompletion::Dont,
+        )
+        .await
+        .unwrap();
+}
+
+#[when(regex = r"^(\S+) gives up reconnecting$")]
+async fn ws_reconnect_cancel(world: &mut World, id: String) {
+    let member = world.get_member(&id).unwrap();
+    member.room().cancel_ws_reconnect().await.unwrap();
+}
+
+#[when(regex = r"^(\S+) goes (online|offline)(?: after (\d+) ms)?$")]
+async fn goes_online(
+    world: &mut World,
+    id: String,
+    state: String,
+    after_ms: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    if !after_ms.is_empty() {
+        sleep(Duration::from_millis(after_ms.parse().unwrap())).await;
+    }
+    member.network_mock().set_online(state == "online").await;
+}
+
+#[when(regex = r"^(\S+)'s page becomes visible after (\d+) ms$")]
+async fn page_becomes_visible(world: &mut World, id: String, ms: u64) {
+    let member = world.get_member(&id).unwrap();
+    sleep(Duration::from_millis(ms)).await;
+    member.network_mock().show_page().await;
+}
+
+#[then(regex = r"^(\S+) doesn't try to reconnect within (\d+) seconds?$")]
+async fn doesnt_try_to_reconnect(world: &mut World, id: String, secs: u64) {
+    let member = world.get_member(&id).unwrap();
+    sleep(Duration::from_secs(secs)).await;
+    assert_eq!(member.ws_mock().created_count().await, 1);
+}
+
+#[given(regex = r"^(\S+)'s WS messages are delayed by (\d+) ms$")]
+async fn ws_messages_delayed(world: &mut World, id: String, ms: u64) {
+    let member = world.get_member(&id).unwrap();
+    member.ws_mock().set_send_latency(ms).await;
+}
+
+#[then(regex = r"^(\S+) reconnects within (\d+) seconds?$")]
+async fn reconnects_within(world: &mut World, id: String, secs: u64) {
+    let member = world.get_member(&id).unwrap();
+    member.room().wait_for_reconnect().await.unwrap();
+    let duration = member
+        .room()
+        .last_reconnect_duration_ms()
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(
+        duration <= secs * 1000,
+        "{id} reconnected in {duration} ms, expected at most {secs} s",
+    );
 }
 
 #[then(regex = r"^(\S+)'s WS connection is lost$")]
@@ -20,3 +209,68 @@ async fn connection_is_lost(world: &mut World, id: String) {
     let member = world.get_member(&id).unwrap();
     member.room().wait_for_connection_loss().await.unwrap();
 }
+
+#[then(regex = r"^(\S+)'s `Room.on_connection_loss\(\)` fires (\d+) times?$")]
+async fn connection_loss_fires(world: &mut World, id: String, times: u64) {
+    let member = world.get_member(&id).unwrap();
+    if times > 0 {
+        member.room().wait_for_connection_loss().await.unwrap();
+    }
+    assert_eq!(member.room().connection_loss_count().await.unwrap(), times);
+}
+
+#[then(regex = r"^(\S+)'s WS connection is not reconnected$")]
+async fn connection_is_not_reconnected(world: &mut World, id: String) {
+    let member = world.get_member(&id).unwrap();
+    assert_eq!(member.ws_mock().created_count().await, 1);
+}
+
+#[when(regex = r"^(\S+) enables audio and video within (\d+) ms$")]
+async fn enables_audio_and_video_within(
+    world: &mut World,
+    id: String,
+    gap_ms: u64,
+) {
+    let member = world.get_member(&id).unwrap();
+    member.ws_mock().clear_sent().await;
+    member
+        .room()
+        .enable_audio_and_video_send(gap_ms)
+        .await
+        .unwrap();
+}
+
+#[then(regex = r"^(\S+) sends (\d+) `(\S+)` commands?$")]
+async fn sends_commands(
+    world: &mut World,
+    id: String,
+    count: u64,
+    name: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    // Lets the renegotiation, caused by the sent commands, to finish.
+    sleep(Duration::from_secs(1)).await;
+    assert_eq!(member.ws_mock().sent_commands_count(&name).await, count);
+}
+
+#[then(regex = r"^(\S+) sends (\d+) SDP offers? or answers?$")]
+async fn sends_sdp(world: &mut World, id: String, count: u64) {
+    let member = world.get_member(&id).unwrap();
+    sleep(Duration::from_secs(1)).await;
+    let ws = member.ws_mock();
+    let sent = ws.sent_commands_count("MakeSdpOffer").await
+        + ws.sent_commands_count("MakeSdpAnswer").await;
+    assert_eq!(sent, count);
+}
+
+#[then(regex = r"^(\S+) doesn't open WS connection$")]
+async fn connection_is_not_opened(world: &mut World, id: String) {
+    let member = world.get_member(&id).unwrap();
+    assert_eq!(member.ws_mock().created_count().await, 0);
+}
+
+#[then(regex = r"^(\S+) opens single WS connection$")]
+async fn single_connection_is_opened(world: &mut World, id: String) {
+    let member = world.get_member(&id).unwrap();
+    assert_eq!(member.ws_mock().created_count().await, 1);
+}
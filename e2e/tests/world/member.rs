@@ -1,23 +1,47 @@
 //! Medea media server member representation.
 
-use std::{cell::RefCell, collections::HashMap, fmt, time::Duration};
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    fmt,
+    str::FromStr,
+    time::{Duration, Instant},
+};
 
 use derive_more::{Display, Error, From};
 use medea_e2e::{
-    browser::{mock, Statement, Window},
+    browser::{self, mock, ConsoleLog, Statement, Window},
     object::{
-        self, connections_store::ConnectionStore, AwaitCompletion, MediaKind,
-        MediaSourceKind, Object, Room,
+        self,
+        connections_store::ConnectionStore,
+        room::{MediaState, ParsingFailedError, RtcStats},
+        AudioTrackSettings, AwaitCompletion, DeviceVideoTrackSettings,
+        JoinError, LocalMediaSettings, MediaKind, MediaSourceKind, Object,
+        Room,
     },
 };
+use tokio::time::sleep;
 
 use crate::conf;
 
+/// Initial delay between [`RtcStats`] polls in [`Member::wait_for_stats()`].
+const STATS_POLL_INITIAL_BACKOFF: Duration = Duration::from_millis(250);
+
+/// Maximum delay between [`RtcStats`] polls in [`Member::wait_for_stats()`].
+const STATS_POLL_MAX_BACKOFF: Duration = Duration::from_secs(2);
+
 /// All errors which can happen while working with a [`Member`].
 #[derive(Debug, Display, Error, From)]
 pub enum Error {
     /// [`Room`] or a [`ConnectionStore`] object errored.
     Object(object::Error),
+
+    /// [`Window`] of a [`Member`] errored.
+    Browser(browser::Error),
+
+    /// [`RtcStats`] didn't satisfy the expected condition in time.
+    #[display(fmt = "`RtcStats` didn't satisfy condition in time: {:?}", _0)]
+    StatsTimeout(#[error(not(source))] RtcStats),
 }
 
 /// Shortcut for a [`Result`] containing an [`Error`](enum@Error).
@@ -26,6 +50,46 @@ pub enum Error {
 #[allow(clippy::absolute_paths)]
 type Result<T> = std::result::Result<T, Error>;
 
+/// Preset of a device video resolution set by
+/// [`Member::set_video_quality()`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum VideoQuality {
+    /// `320x240` resolution.
+    Low,
+
+    /// `640x480` resolution.
+    Medium,
+
+    /// `1280x720` resolution.
+    High,
+}
+
+impl VideoQuality {
+    /// Returns the `(width, height)` resolution of this [`VideoQuality`].
+    #[must_use]
+    pub const fn resolution(self) -> (u32, u32) {
+        match self {
+            Self::Low => (320, 240),
+            Self::Medium => (640, 480),
+            Self::High => (1280, 720),
+        }
+    }
+}
+
+impl FromStr for VideoQuality {
+    type Err = ParsingFailedError;
+
+    #[allow(clippy::absolute_paths)]
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "low" => Ok(Self::Low),
+            "medium" => Ok(Self::Medium),
+            "high" => Ok(Self::High),
+            _ => Err(ParsingFailedError),
+        }
+    }
+}
+
 /// Builder of a [`Member`].
 pub struct Builder {
     /// ID with which a [`Member`] will be created.
@@ -53,8 +117,10 @@ impl Builder {
             is_send: self.is_send,
             is_recv: self.is_recv,
             is_joined: false,
+            join_error: None,
             send_state: RefCell::new(send_state),
             recv_state: RefCell::new(recv_state),
+            video_devices_before_switch: RefCell::default(),
             room,
             connection_store,
             window,
@@ -77,18 +143,28 @@ pub struct Member {
     /// server.
     is_joined: bool,
 
-    /// Media publishing state of this [`Member`].
+    /// [`JoinError`] of the last rejected [`Member::try_join_room()`] call.
+    join_error: Option<JoinError>,
+
+    /// Media publishing state of this [`Member`] configured on a media
+    /// server.
     ///
     /// If value is `true` then this [`MediaKind`] and [`MediaSourceKind`] is
-    /// enabled.
+    /// published by this [`Member`]'s endpoints. Whether it's enabled by the
+    /// [`Room`] itself is queried via [`Member::media_state()`].
     send_state: RefCell<HashMap<(MediaKind, MediaSourceKind), bool>>,
 
-    /// Media receiving state of this [`Member`].
+    /// Media receiving state of this [`Member`] configured on a media server.
     ///
     /// If value is `true` then this [`MediaKind`] and [`MediaSourceKind`] is
-    /// enabled.
+    /// received by this [`Member`]'s endpoints. Whether it's enabled by the
+    /// [`Room`] itself is queried via [`Member::media_state()`].
     recv_state: RefCell<HashMap<(MediaKind, MediaSourceKind), bool>>,
 
+    /// Device IDs of the video sent by this [`Member`] before the last
+    /// [`Member::switch_video_device_to()`] call.
+    video_devices_before_switch: RefCell<Vec<String>>,
+
     /// [`Room`]'s [`Object`] that this [`Member`] is intended to join.
     room: Object<Room>,
 
@@ -150,17 +226,93 @@ impl Member {
 
     /// Joins a [`Room`] with the provided ID.
     pub async fn join_room(&mut self, room_id: &str) -> Result<()> {
-        self.room
-            .join(format!(
-                "{}/{room_id}/{}?token=test",
-                *conf::CLIENT_API_ADDR,
-                self.id,
-            ))
-            .await?;
+        if let Some(e) = self.try_join_room(room_id, "test").await? {
+            return Err(object::Error::Join(e).into());
+        }
+        Ok(())
+    }
+
+    /// Tries to join a [`Room`] with the provided ID using the provided
+    /// authorization `token`.
+    ///
+    /// Returns the [`JoinError`] if the join was rejected, remembering it as
+    /// [`Member::join_error()`]. This [`Member`] is considered joined only if
+    /// the join succeeds.
+    ///
+    /// # Errors
+    ///
+    /// If failed to execute JS statement.
+    pub async fn try_join_room(
+        &mut self,
+        room_id: &str,
+        token: &str,
+    ) -> Result<Option<JoinError>> {
+        self.try_join(format!(
+            "{}/{room_id}/{}?token={token}",
+            *conf::CLIENT_API_ADDR,
+            self.id,
+        ))
+        .await
+    }
+
+    /// Joins a [`Room`] with the provided ID by calling `Room.join()` twice at
+    /// once.
+    pub async fn join_room_twice(&mut self, room_id: &str) -> Result<()> {
+        let uri = format!(
+            "{}/{room_id}/{}?token=test",
+            *conf::CLIENT_API_ADDR,
+            self.id,
+        );
+        self.room.join_concurrently(uri, 2).await?;
+        self.join_error = None;
         self.is_joined = true;
         Ok(())
     }
 
+    /// Tries to join a [`Room`] with the provided ID via a Client API address
+    /// nothing listens on.
+    ///
+    /// See [`Member::try_join_room()`] for details.
+    ///
+    /// # Errors
+    ///
+    /// If failed to execute JS statement.
+    pub async fn try_join_room_unreachable(
+        &mut self,
+        room_id: &str,
+    ) -> Result<Option<JoinError>> {
+        self.try_join(format!(
+            "ws://127.0.0.1:1/ws/{room_id}/{}?token=test",
+            self.id,
+        ))
+        .await
+    }
+
+    /// Tries to join a [`Room`] with the provided URI, remembering the
+    /// [`JoinError`] if the join was rejected.
+    async fn try_join(&mut self, uri: String) -> Result<Option<JoinError>> {
+        let res = self.room.join(uri).await;
+        self.join_error = match res {
+            Ok(()) => {
+                self.is_joined = true;
+                None
+            }
+            Err(object::Error::Join(e)) => {
+                self.is_joined = false;
+                Some(e)
+            }
+            Err(e) => return Err(e.into()),
+        };
+        Ok(self.join_error.clone())
+    }
+
+    /// Returns the [`JoinError`] of the last [`Member::try_join_room()`]
+    /// call, if it was rejected.
+    #[must_use]
+    pub const fn join_error(&self) -> Option<&JoinError> {
+        self.join_error.as_ref()
+    }
+
     /// Updates [`Member::send_state`].
     pub fn update_send_media_state(
         &self,
@@ -193,33 +345,85 @@ impl Member {
         }
     }
 
+    /// Returns the current [`MediaState`] of this [`Member`]'s [`Room`].
+    ///
+    /// # Errors
+    ///
+    /// If failed to query the [`Room`].
+    pub async fn media_state(&self) -> Result<MediaState> {
+        Ok(self.room.media_state().await?)
+    }
+
+    /// Indicates whether this [`Member`] publishes the media of the provided
+    /// [`MediaKind`] and [`MediaSourceKind`], according to both its
+    /// [`Member::send_state`] and the provided [`MediaState`] of its [`Room`].
+    fn is_sending(
+        &self,
+        state: &MediaState,
+        kind: MediaKind,
+        source: MediaSourceKind,
+    ) -> bool {
+        self.send_state
+            .borrow()
+            .get(&(kind, source))
+            .copied()
+            .unwrap_or(false)
+            && state.send(kind, source).enabled
+    }
+
+    /// Indicates whether this [`Member`] receives the media of the provided
+    /// [`MediaKind`] and [`MediaSourceKind`], according to both its
+    /// [`Member::recv_state`] and the provided [`MediaState`] of its [`Room`].
+    fn is_receiving(
+        &self,
+        state: &MediaState,
+        kind: MediaKind,
+        source: MediaSourceKind,
+    ) -> bool {
+        self.recv_state
+            .borrow()
+            .get(&(kind, source))
+            .copied()
+            .unwrap_or(false)
+            && state.recv(kind, source)
+    }
+
     /// Returns count of [`LocalTrack`]s and [`RemoteTrack`]s of this [`Member`]
     /// with a provided partner [`Member`].
     ///
+    /// # Errors
+    ///
+    /// If failed to query the [`MediaState`] of any of the [`Member`]s.
+    ///
     /// [`LocalTrack`]: crate::object::local_track::LocalTrack
     /// [`RemoteTrack`]: crate::object::remote_track::RemoteTrack
-    #[must_use]
-    pub fn count_of_tracks_between_members(&self, other: &Self) -> (u64, u64) {
-        let send_count = self
-            .send_state
-            .borrow()
-            .iter()
-            .filter(|(key, enabled)| {
-                other.recv_state.borrow().get(key).copied().unwrap_or(false)
-                    && **enabled
-            })
-            .count() as u64;
-        let recv_count = self
-            .recv_state
-            .borrow()
-            .iter()
-            .filter(|(key, enabled)| {
-                other.send_state.borrow().get(key).copied().unwrap_or(false)
-                    && **enabled
-            })
-            .count() as u64;
+    pub async fn count_of_tracks_between_members(
+        &self,
+        other: &Self,
+    ) -> Result<(u64, u64)> {
+        let (this_state, other_state) =
+            (self.media_state().await?, other.media_state().await?);
+
+        let (mut send_count, mut recv_count) = (0, 0);
+        let all_kinds = MediaKind::ALL.into_iter().flat_map(|kind| {
+            MediaSourceKind::ALL
+                .into_iter()
+                .map(move |source| (kind, source))
+        });
+        for (kind, source) in all_kinds {
+            if self.is_sending(&this_state, kind, source)
+                && other.is_receiving(&other_state, kind, source)
+            {
+                send_count += 1;
+            }
+            if self.is_receiving(&this_state, kind, source)
+                && other.is_sending(&other_state, kind, source)
+            {
+                recv_count += 1;
+            }
+        }
 
-        (send_count, recv_count)
+        Ok((send_count, recv_count))
     }
 
     /// Toggles media state of this [`Member`]'s [`Room`].
@@ -230,7 +434,6 @@ impl Member {
         enabled: bool,
         maybe_await: AwaitCompletion,
     ) -> Result<()> {
-        self.update_send_media_state(kind, source, enabled);
         if enabled {
             if let Some(kind) = kind {
                 self.room
@@ -298,7 +501,6 @@ impl Member {
         source: Option<MediaSourceKind>,
         enabled: bool,
     ) -> Result<()> {
-        self.update_recv_media_state(kind, source, enabled);
         if enabled {
             if let Some(kind) = kind {
                 self.room.enable_remote_media(kind, source).await?;
@@ -326,10 +528,244 @@ impl Member {
     /// Emulates video device switching.
     pub async fn switch_video_device(&self) -> Result<()> {
         self.room
-            .set_local_media_settings(false, true, true)
+            .set_local_media_settings(
+                &LocalMediaSettings::new(false, true),
+                true,
+                false,
+                false,
+                AwaitCompletion::Do,
+            )
+            .await?;
+        self.room
+            .set_local_media_settings(
+                &LocalMediaSettings::new(true, true),
+                true,
+                false,
+                false,
+                AwaitCompletion::Dont,
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Switches the video device of this [`Member`] to the one with the
+    /// provided `device_id`, remembering the currently sent video devices, so
+    /// they can be checked with [`Member::wait_for_video_devices_restored()`]
+    /// later.
+    ///
+    /// # Errors
+    ///
+    /// If the new media settings couldn't be applied.
+    pub async fn switch_video_device_to(
+        &self,
+        device_id: String,
+        rollback_on_fail: bool,
+    ) -> Result<()> {
+        let sent = self
+            .window
+            .peer_connection_mock()
+            .sent_device_ids(MediaKind::Video)
+            .await;
+        *self.video_devices_before_switch.borrow_mut() = sent;
+
+        let settings = LocalMediaSettings {
+            device_video: Some(DeviceVideoTrackSettings {
+                device_id: Some(device_id),
+                ..DeviceVideoTrackSettings::default()
+            }),
+            ..LocalMediaSettings::new(false, true)
+        };
+        self.room
+            .set_local_media_settings(
+                &settings,
+                true,
+                rollback_on_fail,
+                false,
+                AwaitCompletion::Do,
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Waits for this [`Member`] to send video from the same devices as it
+    /// was before the last [`Member::switch_video_device_to()`] call.
+    pub async fn wait_for_video_devices_restored(&self) {
+        let expected = self.video_devices_before_switch.borrow().clone();
+        self.window
+            .peer_connection_mock()
+            .wait_for_sent_device_ids(MediaKind::Video, &expected)
+            .await;
+    }
+
+    /// Starts publishing a display video of this [`Member`].
+    ///
+    /// # Errors
+    ///
+    /// If the screen capture request was rejected.
+    pub async fn publish_screen(&self) -> Result<()> {
+        self.room.start_screen_sharing().await?;
+        self.update_send_media_state(
+            Some(MediaKind::Video),
+            Some(MediaSourceKind::Display),
+            true,
+        );
+        Ok(())
+    }
+
+    /// Starts publishing the current browser tab of this [`Member`] as a
+    /// display video.
+    ///
+    /// # Errors
+    ///
+    /// If the screen capture request was rejected.
+    pub async fn publish_current_tab(&self) -> Result<()> {
+        self.room.start_current_tab_sharing().await?;
+        self.update_send_media_state(
+            Some(MediaKind::Video),
+            Some(MediaSourceKind::Display),
+            true,
+        );
+        Ok(())
+    }
+
+    /// Returns the options of the last screen capture request of this
+    /// [`Member`], if any.
+    pub async fn last_screen_capture_options(&self) -> serde_json::Value {
+        self.window
+            .display_media_mock()
+            .last_request_options()
+            .await
+    }
+
+    /// Stops publishing a display video of this [`Member`], emulating a user
+    /// stopping the screen capture via browser UI.
+    pub async fn stop_screen(&self) {
+        self.window.display_media_mock().end_current_capture().await;
+        self.update_send_media_state(
+            Some(MediaKind::Video),
+            Some(MediaSourceKind::Display),
+            false,
+        );
+    }
+
+    /// Switches the microphone of this [`Member`] to the one with the
+    /// provided `device_id`, keeping its device video (if it's published).
+    ///
+    /// # Errors
+    ///
+    /// If the new media settings couldn't be applied.
+    pub async fn switch_microphone(&self, device_id: String) -> Result<()> {
+        let settings = LocalMediaSettings {
+            audio: Some(AudioTrackSettings {
+                device_id: Some(device_id),
+            }),
+            ..self.published_media_settings().await?
+        };
+        self.apply_local_media_settings(&settings).await
+    }
+
+    /// Switches the camera of this [`Member`] to the one with the provided
+    /// `device_id`, keeping its audio (if it's published).
+    ///
+    /// If `seamless` is `true`, then the current camera track is stopped only
+    /// after the new one replaces it in the senders.
+    ///
+    /// # Errors
+    ///
+    /// If the new media settings couldn't be applied.
+    pub async fn switch_camera(
+        &self,
+        device_id: String,
+        seamless: bool,
+    ) -> Result<()> {
+        let settings = LocalMediaSettings {
+            device_video: Some(DeviceVideoTrackSettings {
+                device_id: Some(device_id),
+                ..DeviceVideoTrackSettings::default()
+            }),
+            ..self.published_media_settings().await?
+        };
+        self.room
+            .set_local_media_settings(
+                &settings,
+                true,
+                false,
+                seamless,
+                AwaitCompletion::Do,
+            )
             .await?;
+        Ok(())
+    }
+
+    /// Sets the resolution of the device video captured by this [`Member`] to
+    /// the provided [`VideoQuality`] preset, keeping its audio (if it's
+    /// published).
+    ///
+    /// # Errors
+    ///
+    /// If the new media settings couldn't be applied.
+    pub async fn set_video_quality(&self, preset: VideoQuality) -> Result<()> {
+        let (width, height) = preset.resolution();
+        let settings = LocalMediaSettings {
+            device_video: Some(DeviceVideoTrackSettings {
+                width: Some(width),
+                height: Some(height),
+                ..DeviceVideoTrackSettings::default()
+            }),
+            ..self.published_media_settings().await?
+        };
+        self.apply_local_media_settings(&settings).await
+    }
+
+    /// Applies [`LocalMediaSettings`] capturing the provided media types
+    /// without any additional constraints.
+    ///
+    /// # Errors
+    ///
+    /// If the new media settings couldn't be applied.
+    pub async fn set_local_media(
+        &self,
+        video: bool,
+        audio: bool,
+    ) -> Result<()> {
+        let settings = LocalMediaSettings::new(video, audio);
+        self.apply_local_media_settings(&settings).await
+    }
+
+    /// Returns [`LocalMediaSettings`] capturing the device media currently
+    /// published by this [`Member`].
+    ///
+    /// # Errors
+    ///
+    /// If failed to query the [`MediaState`] of this [`Member`]'s [`Room`].
+    async fn published_media_settings(&self) -> Result<LocalMediaSettings> {
+        let state = self.media_state().await?;
+        let is_sending =
+            |kind| self.is_sending(&state, kind, MediaSourceKind::Device);
+        Ok(LocalMediaSettings::new(
+            is_sending(MediaKind::Video),
+            is_sending(MediaKind::Audio),
+        ))
+    }
+
+    /// Applies the provided [`LocalMediaSettings`] to the [`Room`] of this
+    /// [`Member`], stopping the current tracks first and awaiting the
+    /// completion.
+    ///
+    /// Doesn't touch [`Member::send_state`], since only the capture
+    /// constraints are changed, not the published media.
+    async fn apply_local_media_settings(
+        &self,
+        settings: &LocalMediaSettings,
+    ) -> Result<()> {
         self.room
-            .set_local_media_settings(true, true, false)
+            .set_local_media_settings(
+                settings,
+                true,
+                false,
+                false,
+                AwaitCompletion::Do,
+            )
             .await?;
         Ok(())
     }
@@ -345,11 +781,14 @@ impl Member {
                         var gUM = navigator.mediaDevices.getUserMedia.bind(
                             navigator.mediaDevices
                         );
-                        navigator.mediaDevices.getUserMedia =
+                        window.mockProperty(
+                            navigator.mediaDevices,
+                            "getUserMedia",
                             async function (cons) {
                                 await new Promise(r => setTimeout(r, duration));
                                 return await gUM(cons);
-                            };
+                            }
+                        );
                     }
                 "#,
                 [u64::try_from(latency.as_millis()).unwrap().into()],
@@ -358,6 +797,102 @@ impl Member {
             .unwrap();
     }
 
+    /// Emulates a loss of the media traffic of this [`Member`], while keeping
+    /// its WebSocket connection alive.
+    pub async fn block_media_traffic(&self) {
+        self.window
+            .peer_connection_mock()
+            .block_media_traffic()
+            .await;
+    }
+
+    /// Restores the media traffic of this [`Member`] blocked by the
+    /// [`Member::block_media_traffic()`].
+    pub async fn unblock_media_traffic(&self) {
+        self.window
+            .peer_connection_mock()
+            .unblock_media_traffic()
+            .await;
+    }
+
+    /// Returns [`ConsoleLog`]s captured in the [`Window`] of this [`Member`]
+    /// since the last call of this method.
+    pub async fn take_console_logs(&self) -> Result<Vec<ConsoleLog>> {
+        Ok(self.window.take_console_logs().await?)
+    }
+
+    /// Polls [`RtcStats`] of this [`Member`]'s [`Room`] with an exponential
+    /// backoff until the provided `predicate` is satisfied.
+    ///
+    /// # Errors
+    ///
+    /// - If failed to collect [`RtcStats`].
+    /// - With [`Error::StatsTimeout`] (containing the last collected
+    ///   [`RtcStats`]) if the `predicate` isn't satisfied in `timeout`.
+    pub async fn wait_for_stats<F>(
+        &self,
+        timeout: Duration,
+        mut predicate: F,
+    ) -> Result<RtcStats>
+    where
+        F: FnMut(&RtcStats) -> bool,
+    {
+        let deadline = Instant::now() + timeout;
+        let mut backoff = STATS_POLL_INITIAL_BACKOFF;
+        loop {
+            let stats = self.room.get_stats().await?;
+            if predicate(&stats) {
+                return Ok(stats);
+            }
+            if Instant::now() + backoff > deadline {
+                return Err(Error::StatsTimeout(stats));
+            }
+            sleep(backoff).await;
+            backoff = (backoff * 2).min(STATS_POLL_MAX_BACKOFF);
+        }
+    }
+
+    /// Waits until the bitrate of the video sent by this [`Member`] is below
+    /// the provided `bps` (bits per second).
+    ///
+    /// # Errors
+    ///
+    /// See [`Member::wait_for_stats()`] for details.
+    pub async fn wait_for_outbound_video_bitrate_below(
+        &self,
+        bps: u64,
+        timeout: Duration,
+    ) -> Result<RtcStats> {
+        #[allow(clippy::cast_precision_loss)] // bitrates are small enough
+        let bps = bps as f64;
+        self.wait_for_stats(timeout, |stats| {
+            !stats.outbound.is_empty()
+                && stats.outbound_video_bitrate(None) < bps
+        })
+        .await
+    }
+
+    /// Waits until the video with the provided [`MediaSourceKind`] sent by this
+    /// [`Member`] has a frame size of at least `width`x`height`.
+    ///
+    /// # Errors
+    ///
+    /// See [`Member::wait_for_stats()`] for details.
+    pub async fn wait_for_outbound_video_frame_size(
+        &self,
+        source_kind: MediaSourceKind,
+        width: u64,
+        height: u64,
+        timeout: Duration,
+    ) -> Result<RtcStats> {
+        self.wait_for_stats(timeout, |stats| {
+            stats
+                .max_outbound_frame_size(Some(source_kind))
+                .map_or(false, |(w, h)| w >= width && h >= height)
+        })
+        .await
+    }
+
     /// Returns reference to the Storage of [`Connection`]s thrown by this
     /// [`Member`]'s [`Room`].
     ///
@@ -373,6 +908,28 @@ impl Member {
         &self.room
     }
 
+    /// Replaces the [`Room`] of this [`Member`] with the provided one, as
+    /// happens on a page refresh.
+    ///
+    /// This [`Member`] is considered not joined afterwards.
+    ///
+    /// # Errors
+    ///
+    /// If failed to create a [`ConnectionStore`] for the provided [`Room`].
+    pub async fn replace_room(&mut self, room: Object<Room>) -> Result<()> {
+        self.connection_store = room.connections_store().await?;
+        self.room = room;
+        self.is_joined = false;
+        self.join_error = None;
+        Ok(())
+    }
+
+    /// Returns the [`Window`] this [`Member`] exists in.
+    #[must_use]
+    pub const fn window(&self) -> &Window {
+        &self.window
+    }
+
     /// Returns WebAPI `WebSocket` mock object for [`Window`] of this
     /// [`Member`].
     #[must_use]
@@ -388,6 +945,28 @@ impl Member {
     pub fn media_devices_mock(&self) -> mock::MediaDevices {
         self.window.media_devices_mock()
     }
+
+    /// Returns a [MediaDevices.getDisplayMedia()][1] mock for [`Window`] of
+    /// this [`Member`].
+    ///
+    /// [1]: https://w3.org/TR/screen-capture#dom-mediadevices-getdisplaymedia
+    #[must_use]
+    pub fn display_media_mock(&self) -> mock::DisplayMedia {
+        self.window.display_media_mock()
+    }
+
+    /// Returns a `RTCPeerConnection` mock for [`Window`] of this [`Member`].
+    #[must_use]
+    pub fn peer_connection_mock(&self) -> mock::RtcPeerConnection {
+        self.window.peer_connection_mock()
+    }
+
+    /// Returns network availability and page visibility mock for [`Window`]
+    /// of this [`Member`].
+    #[must_use]
+    pub fn network_mock(&self) -> mock::Network {
+        self.window.network_mock()
+    }
 }
 
 /// Returns list of [`MediaKind`]s and [`MediaSourceKind`] based on the provided
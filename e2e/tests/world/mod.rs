@@ -4,18 +4,23 @@
 
 pub mod member;
 
-use std::{collections::HashMap, env, fmt, time::Duration};
+use std::{collections::HashMap, env, fmt, future::Future, time::Duration};
 
 use derive_more::{Display, Error, From};
+use futures::future;
 use medea_control_api_mock::{
     callback::{CallbackEvent, CallbackItem},
     proto,
     proto::PublishPolicy,
 };
 use medea_e2e::{
-    browser::{self, WebDriverClientBuilder, WindowFactory},
-    object::{self, Jason, MediaKind, MediaSourceKind, Object},
+    browser::{self, SessionPool, WebDriverClientBuilder, WindowFactory},
+    object::{
+        self, CameraCapabilities, IceProbeReport, Jason, JoinError, MediaKind,
+        MediaSourceKind, Object, WebRtcSupport,
+    },
 };
+use once_cell::sync::Lazy;
 use tokio::time::interval;
 use uuid::Uuid;
 
@@ -37,6 +42,11 @@ macro_rules! control_api_path {
     };
 }
 
+/// [`SessionPool`] of [WebDriver] sessions shared by all the [`World`]s.
+///
+/// [WebDriver]: https://w3.org/TR/webdriver
+pub static SESSIONS: Lazy<SessionPool> = Lazy::new(SessionPool::new);
+
 /// All errors which can happen while working with [`World`].
 #[derive(Debug, Display, Error, From)]
 pub enum Error {
@@ -45,11 +55,16 @@ pub enum Error {
     Member(member::Error),
     Browser(browser::Error),
     MemberNotFound(#[error(not(source))] String),
+    ControlApi(#[error(not(source))] String),
 }
 
 #[allow(clippy::absolute_paths)]
 type Result<T> = std::result::Result<T, Error>;
 
+/// Media states of a [`Member`] by their [`MediaKind`]s and
+/// [`MediaSourceKind`]s.
+type MediaStates = HashMap<(MediaKind, MediaSourceKind), bool>;
+
 /// [`World`][1] used by all E2E tests.
 ///
 /// [1]: cucumber::World
@@ -68,6 +83,10 @@ pub struct World {
     /// All [`Jason`] [`Object`]s created in this [`World`].
     jasons: HashMap<String, Object<Jason>>,
 
+    /// Indicator whether all the `Endpoint`s created in this [`World`] force
+    /// their media to be relayed via a TURN server.
+    is_force_relayed: bool,
+
     /// [WebDriver] client that all [`Object`]s of this [`World`] will be
     /// created with.
     ///
@@ -95,6 +114,14 @@ impl World {
     pub async fn try_new() -> Result<Self> {
         let room_id = Uuid::new_v4().to_string();
 
+        let mut webdriver = WebDriverClientBuilder::new(&conf::WEBDRIVER_ADDR)
+            .headless_firefox(*conf::HEADLESS)
+            .headless_chrome(*conf::HEADLESS)
+            .execute_timeout(*conf::EXECUTE_TIMEOUT);
+        if let Some(browser) = *conf::BROWSER {
+            webdriver = webdriver.browser(browser);
+        }
+
         let control_client = control::Client::new(&conf::CONTROL_API_ADDR);
         control_client
             .create(
@@ -109,17 +136,21 @@ impl World {
         Ok(Self {
             room_id,
             control_client,
-            window_factory: WebDriverClientBuilder::new(&conf::WEBDRIVER_ADDR)
-                .headless_firefox(*conf::HEADLESS)
-                .headless_chrome(*conf::HEADLESS)
-                .connect(&conf::FILE_SERVER_HOST)
-                .await?
-                .into(),
+            window_factory: SESSIONS
+                .take(webdriver, &conf::FILE_SERVER_HOST)
+                .await?,
             members: HashMap::new(),
             jasons: HashMap::new(),
+            is_force_relayed: false,
         })
     }
 
+    /// Makes all the `Endpoint`s created in this [`World`] afterwards force
+    /// their media to be relayed via a TURN server.
+    pub fn force_relay(&mut self) {
+        self.is_force_relayed = true;
+    }
+
     /// Creates a new [`Member`] from the provided [`MemberBuilder`].
     ///
     /// `Room` for this [`Member`] will be created, but joining won't be done.
@@ -128,13 +159,82 @@ impl World {
     ///
     /// - If the performed request to Control API fails.
     /// - If initializing the [`Member`] fails on JS side.
-    #[allow(clippy::too_many_lines)]
     pub async fn create_member(
         &mut self,
         builder: MemberBuilder,
     ) -> Result<()> {
+        self.create_members(vec![builder]).await
+    }
+
+    /// Creates new [`Member`]s from the provided [`MemberBuilder`]s.
+    ///
+    /// Control API elements of the [`Member`]s are created one by one in the
+    /// provided order, since the `Endpoint`s of every [`Member`] depend on the
+    /// ones created before it. Browser windows of the [`Member`]s, their
+    /// [`Jason`]s and `Room`s are created concurrently, which saves a
+    /// [WebDriver] round trip latency per each [`Member`] except the first
+    /// one.
+    ///
+    /// `Room`s for these [`Member`]s will be created, but joining won't be
+    /// done.
+    ///
+    /// # Errors
+    ///
+    /// - If the performed request to Control API fails.
+    /// - If initializing any of the [`Member`]s fails on JS side.
+    ///
+    /// [WebDriver]: https://w3.org/TR/webdriver
+    pub async fn create_members(
+        &mut self,
+        builders: Vec<MemberBuilder>,
+    ) -> Result<()> {
+        let mut states = Vec::with_capacity(builders.len());
+        for (i, builder) in builders.iter().enumerate() {
+            states.push(self.register_member(builder, &builders[..i]).await?);
+        }
+
+        let factory = &self.window_factory;
+        let spawned = builders
+            .into_iter()
+            .zip(states)
+            .map(|(b, (send, recv))| spawn_member(factory, b, send, recv));
+        for (jason, member) in future::try_join_all(spawned).await? {
+            self.jasons.insert(member.id().to_owned(), jason);
+            self.members.insert(member.id().to_owned(), member);
+        }
+
+        Ok(())
+    }
+
+    /// Creates Control API elements of a [`Member`] from the provided
+    /// [`MemberBuilder`], interconnecting it with all the existing [`Member`]s
+    /// and the provided `registered` ones, which are not spawned yet.
+    ///
+    /// Returns the expected media publishing and receiving states of the
+    /// [`Member`].
+    ///
+    /// # Errors
+    ///
+    /// If the performed request to Control API fails.
+    #[allow(clippy::too_many_lines)]
+    async fn register_member(
+        &self,
+        builder: &MemberBuilder,
+        registered: &[MemberBuilder],
+    ) -> Result<(MediaStates, MediaStates)> {
         let is_sfu = env::var("SFU").is_ok();
 
+        let peers = self
+            .members
+            .values()
+            .map(|m| (m.id(), m.is_send(), m.is_recv()))
+            .chain(
+                registered
+                    .iter()
+                    .map(|b| (b.id.as_str(), b.is_send, b.is_recv)),
+            )
+            .collect::<Vec<_>>();
+
         let mut pipeline = HashMap::new();
         let mut send_state = HashMap::new();
         let mut recv_state = HashMap::new();
@@ -158,7 +258,7 @@ impl World {
                         } else {
                             proto::P2pMode::Always
                         },
-                        force_relay: false,
+                        force_relay: self.is_force_relayed,
                         audio_settings: proto::AudioSettings::default(),
                         video_settings: proto::VideoSettings::default(),
                     },
@@ -174,23 +274,22 @@ impl World {
                 recv_state
                     .insert((MediaKind::Video, MediaSourceKind::Display), true);
             }
-            self.members.values().filter(|m| m.is_send()).for_each(|m| {
-                let endpoint_id = format!("play-{}", m.id());
+            for (id, ..) in peers.iter().filter(|(_, is_send, _)| *is_send) {
+                let endpoint_id = format!("play-{id}");
                 pipeline.insert(
                     endpoint_id.clone(),
                     proto::Endpoint::WebRtcPlayEndpoint(
                         proto::WebRtcPlayEndpoint {
                             id: endpoint_id,
                             src: format!(
-                                "local://{}/{}/publish",
+                                "local://{}/{id}/publish",
                                 self.room_id,
-                                m.id(),
                             ),
-                            force_relay: false,
+                            force_relay: self.is_force_relayed,
                         },
                     ),
                 );
-            });
+            }
         }
 
         self.control_client
@@ -212,45 +311,24 @@ impl World {
             .await?;
 
         if builder.is_send {
-            let recv_endpoints = self
-                .members
-                .values()
-                .filter_map(|m| {
-                    m.is_recv().then(|| {
-                        let endpoint_id = format!("play-{}", builder.id);
-                        let id = format!(
-                            "{}/{}/{endpoint_id}",
-                            self.room_id,
-                            m.id(),
-                        );
-                        let elem = proto::Element::WebRtcPlayEndpoint(
-                            proto::WebRtcPlayEndpoint {
-                                id: endpoint_id,
-                                src: format!(
-                                    "local://{}/{}/publish",
-                                    self.room_id, builder.id,
-                                ),
-                                force_relay: false,
-                            },
-                        );
-                        (id, elem)
-                    })
-                })
-                .collect::<HashMap<_, _>>();
-            for (path, element) in recv_endpoints {
+            for (id, ..) in peers.iter().filter(|(.., is_recv)| *is_recv) {
+                let endpoint_id = format!("play-{}", builder.id);
+                let path = format!("{}/{id}/{endpoint_id}", self.room_id);
+                let element = proto::Element::WebRtcPlayEndpoint(
+                    proto::WebRtcPlayEndpoint {
+                        id: endpoint_id,
+                        src: format!(
+                            "local://{}/{}/publish",
+                            self.room_id, builder.id,
+                        ),
+                        force_relay: self.is_force_relayed,
+                    },
+                );
                 self.control_client.create(&path, element).await?;
             }
         }
-        let window = self.window_factory.new_window().await;
-        let jason = Object::spawn(Jason, window.clone()).await?;
-        let room = jason.init_room().await?;
-        let member =
-            builder.build(room, window, send_state, recv_state).await?;
 
-        self.jasons.insert(member.id().to_owned(), jason);
-        self.members.insert(member.id().to_owned(), member);
-
-        Ok(())
+        Ok((send_state, recv_state))
     }
 
     /// Returns reference to a [`Member`] with the provided ID.
@@ -261,6 +339,37 @@ impl World {
         self.members.get(member_id)
     }
 
+    /// Prints all the [`ConsoleLog`]s captured in the browser windows of all
+    /// the [`Member`]s of this [`World`] to the `stderr`.
+    ///
+    /// [`ConsoleLog`]: medea_e2e::browser::ConsoleLog
+    pub async fn dump_console_logs(&self) {
+        for member in self.members.values() {
+            let logs = match member.take_console_logs().await {
+                Ok(logs) => logs,
+                Err(e) => {
+                    eprintln!(
+                        "Failed to take console logs of `{}` member: {e}",
+                        member.id(),
+                    );
+                    continue;
+                }
+            };
+            if logs.is_empty() {
+                continue;
+            }
+            eprintln!("Console logs of `{}` member:", member.id());
+            for log in logs {
+                eprintln!("  [{}] {}", log.level, log.message);
+                if let Some(stack) = log.stack {
+                    for line in stack.lines() {
+                        eprintln!("      {line}");
+                    }
+                }
+            }
+        }
+    }
+
     /// Joins a [`Member`] with the provided ID to the `Room` created for this
     /// [`World`].
     ///
@@ -277,6 +386,90 @@ impl World {
         Ok(())
     }
 
+    /// Joins a [`Member`] with the provided ID to the `Room` created for this
+    /// [`World`] by calling `Room.join()` twice at once.
+    ///
+    /// # Errors
+    ///
+    /// - If the specified [`Member`] doesn't exist in this [`World`].
+    /// - If joining the `Room` fails on JS side.
+    pub async fn join_room_twice(&mut self, member_id: &str) -> Result<()> {
+        let member = self
+            .members
+            .get_mut(member_id)
+            .ok_or_else(|| Error::MemberNotFound(member_id.to_owned()))?;
+        member.join_room_twice(&self.room_id).await?;
+        Ok(())
+    }
+
+    /// Tries to join a `Room` with the provided ID (or the `Room` of this
+    /// [`World`] if [`None`]) by a [`Member`] with the provided ID, using the
+    /// provided authorization `token`.
+    ///
+    /// See [`Member::try_join_room()`] for details.
+    ///
+    /// # Errors
+    ///
+    /// - If the specified [`Member`] doesn't exist in this [`World`].
+    /// - If failed to execute JS statement.
+    pub async fn try_join_room(
+        &mut self,
+        member_id: &str,
+        room_id: Option<&str>,
+        token: &str,
+    ) -> Result<Option<JoinError>> {
+        let member = self
+            .members
+            .get_mut(member_id)
+            .ok_or_else(|| Error::MemberNotFound(member_id.to_owned()))?;
+        let room_id = room_id.unwrap_or(&self.room_id);
+        Ok(member.try_join_room(room_id, token).await?)
+    }
+
+    /// Tries to join the `Room` by the provided [`Member`] via a Client API
+    /// address nothing listens on.
+    ///
+    /// See [`Member::try_join_room_unreachable()`] for details.
+    ///
+    /// # Errors
+    ///
+    /// - If the specified [`Member`] doesn't exist in this [`World`].
+    /// - If failed to execute JS statement.
+    pub async fn try_join_room_unreachable(
+        &mut self,
+        member_id: &str,
+    ) -> Result<Option<JoinError>> {
+        let member = self
+            .members
+            .get_mut(member_id)
+            .ok_or_else(|| Error::MemberNotFound(member_id.to_owned()))?;
+        Ok(member.try_join_room_unreachable(&self.room_id).await?)
+    }
+
+    /// Joins the `Room` by all the [`Member`]s with the provided IDs
+    /// concurrently.
+    ///
+    /// # Errors
+    ///
+    /// - If any of the specified [`Member`]s doesn't exist in this [`World`].
+    /// - If joining the `Room` fails on JS side.
+    pub async fn join_rooms(&mut self, member_ids: &[String]) -> Result<()> {
+        if let Some(id) =
+            member_ids.iter().find(|id| !self.members.contains_key(*id))
+        {
+            return Err(Error::MemberNotFound(id.clone()));
+        }
+        let room_id = &self.room_id;
+        future::try_join_all(
+            self.members
+                .values_mut()
+                .filter(|m| member_ids.iter().any(|id| id == m.id()))
+                .map(|m| m.join_room(room_id)),
+        )
+        .await?;
+        Ok(())
+    }
+
     /// Waits until a [`Member`] with the provided ID will connect with his
     /// responders.
     ///
@@ -296,30 +489,54 @@ impl World {
         });
         let member = self.members.get(member_id).unwrap();
         for partner in interconnected_members {
-            let (send_count, recv_count) =
-                member.count_of_tracks_between_members(partner);
-            let conn = member
-                .connections()
-                .wait_for_connection(partner.id().to_owned())
-                .await?;
-            conn.tracks_store()
-                .await?
-                .wait_for_count(recv_count)
-                .await?;
-
-            let partner_conn = partner
-                .connections()
-                .wait_for_connection(member_id.to_owned())
-                .await?;
-            partner_conn
-                .tracks_store()
-                .await?
-                .wait_for_count(send_count)
-                .await?;
+            wait_for_tracks_from(member, partner).await?;
+            wait_for_tracks_from(partner, member).await?;
         }
         Ok(())
     }
 
+    /// Waits until all the joined [`Member`]s of this [`World`] will connect
+    /// with each other.
+    ///
+    /// # Errors
+    ///
+    /// If waiting fails on JS side.
+    pub async fn wait_for_full_interconnection(&self) -> Result<()> {
+        self.for_each_pair(|member, partner| async move {
+            let is_interconnected = member.is_joined()
+                && partner.is_joined()
+                && (member.is_recv() || member.is_send())
+                && (partner.is_recv() || partner.is_send());
+            if is_interconnected {
+                wait_for_tracks_from(member, partner).await?;
+            }
+            Ok(())
+        })
+        .await
+    }
+
+    /// Runs the provided `f` for every ordered pair of different [`Member`]s
+    /// of this [`World`] concurrently.
+    ///
+    /// # Errors
+    ///
+    /// With the first error returned by `f`.
+    pub async fn for_each_pair<'a, F, Fut>(&'a self, f: F) -> Result<()>
+    where
+        F: Fn(&'a Member, &'a Member) -> Fut,
+        Fut: Future<Output = Result<()>>,
+    {
+        let pairs = self.members.values().flat_map(|member| {
+            self.members
+                .values()
+                .filter(move |partner| partner.id() != member.id())
+                .map(move |partner| (member, partner))
+        });
+        future::try_join_all(pairs.map(|(member, partner)| f(member, partner)))
+            .await
+            .map(drop)
+    }
+
     /// Closes a [`Room`] of the provided [`Member`].
     ///
     /// # Errors
@@ -340,6 +557,128 @@ impl World {
         Ok(())
     }
 
+    /// Probes the test TURN server with the [`Jason`] object of the provided
+    /// [`Member`], filtering out all the non-relay candidates if `relay_only`
+    /// is `true`.
+    ///
+    /// # Errors
+    ///
+    /// If the probe fails on JS side.
+    ///
+    /// # Panics
+    ///
+    /// If no [`Jason`] objects exist for the provided [`Member`] in this
+    /// [`World`].
+    pub async fn probe_turn_server(
+        &self,
+        member_id: &str,
+        relay_only: bool,
+    ) -> Result<IceProbeReport> {
+        let jason = self.jasons.get(member_id).unwrap();
+        Ok(jason
+            .probe_ice_server(
+                &conf::TURN_URL,
+                &conf::TURN_USERNAME,
+                &conf::TURN_CREDENTIAL,
+                *conf::ICE_PROBE_TIMEOUT_MS,
+                relay_only,
+            )
+            .await?)
+    }
+
+    /// Indicates whether the [`Jason`] object of the provided [`Member`]
+    /// reports at least one input device of the provided [`MediaKind`].
+    ///
+    /// # Errors
+    ///
+    /// If the check fails on JS side.
+    ///
+    /// # Panics
+    ///
+    /// If no [`Jason`] objects exist for the provided [`Member`] in this
+    /// [`World`].
+    pub async fn has_input_device(
+        &self,
+        member_id: &str,
+        kind: MediaKind,
+    ) -> Result<bool> {
+        let jason = self.jasons.get(member_id).unwrap();
+        Ok(jason.has_input_device(kind).await?)
+    }
+
+    /// Returns [`CameraCapabilities`] of the first camera reported by the
+    /// [`Jason`] object of the provided [`Member`].
+    ///
+    /// # Errors
+    ///
+    /// If querying the capabilities fails on JS side.
+    ///
+    /// # Panics
+    ///
+    /// If no [`Jason`] objects exist for the provided [`Member`] in this
+    /// [`World`].
+    pub async fn camera_capabilities(
+        &self,
+        member_id: &str,
+    ) -> Result<CameraCapabilities> {
+        let jason = self.jasons.get(member_id).unwrap();
+        Ok(jason.camera_capabilities().await?)
+    }
+
+    /// Obtains preview local tracks via the [`Jason`] object of the provided
+    /// [`Member`].
+    ///
+    /// # Errors
+    ///
+    /// If obtaining tracks fails on JS side.
+    ///
+    /// # Panics
+    ///
+    /// If no [`Jason`] objects exist for the provided [`Member`] in this
+    /// [`World`].
+    pub async fn init_preview_tracks(&self, member_id: &str) -> Result<()> {
+        let jason = self.jasons.get(member_id).unwrap();
+        Ok(jason.init_preview_tracks().await?)
+    }
+
+    /// Returns publishing states of the preview local tracks obtained by the
+    /// [`Jason`] object of the provided [`Member`].
+    ///
+    /// # Errors
+    ///
+    /// If the check fails on JS side.
+    ///
+    /// # Panics
+    ///
+    /// If no [`Jason`] objects exist for the provided [`Member`] in this
+    /// [`World`].
+    pub async fn preview_tracks_published(
+        &self,
+        member_id: &str,
+    ) -> Result<Vec<bool>> {
+        let jason = self.jasons.get(member_id).unwrap();
+        Ok(jason.preview_tracks_published().await?)
+    }
+
+    /// Returns [`WebRtcSupport`] reported by the [`Jason`] object of the
+    /// provided [`Member`].
+    ///
+    /// # Errors
+    ///
+    /// If the check fails on JS side.
+    ///
+    /// # Panics
+    ///
+    /// If no [`Jason`] objects exist for the provided [`Member`] in this
+    /// [`World`].
+    pub async fn webrtc_support(
+        &self,
+        member_id: &str,
+    ) -> Result<WebRtcSupport> {
+        let jason = self.jasons.get(member_id).unwrap();
+        Ok(jason.webrtc_support().await?)
+    }
+
     /// Waits for the [`Member`]'s [`Room`] being closed.
     ///
     /// # Errors
@@ -607,6 +946,36 @@ impl World {
         Ok(())
     }
 
+    /// Simulates a page refresh of the provided [`Member`]: exports a snapshot
+    /// of its `Room` state, disposes its [`Jason`] object, and rejoins the
+    /// `Room` restored from the snapshot in a new [`Jason`] object.
+    ///
+    /// # Errors
+    ///
+    /// - If failed to export or restore the snapshot on JS side.
+    /// - If the restored `Room` fails to be joined.
+    ///
+    /// # Panics
+    ///
+    /// If the provided [`Member`] doesn't exist in this [`World`], or there
+    /// are no [`Jason`] objects present in this [`World`] for it.
+    pub async fn reload_restoring_room_state(
+        &mut self,
+        member_id: &str,
+    ) -> Result<()> {
+        let member = self.members.get_mut(member_id).unwrap();
+        let snapshot = member.room().export_state().await?;
+        self.jasons.remove(member_id).unwrap().dispose().await?;
+
+        let jason = Object::spawn(Jason, member.window().clone()).await?;
+        let room = jason.restore_room(&snapshot).await?;
+        member.replace_room(room).await?;
+        self.jasons.insert(member_id.to_owned(), jason);
+
+        member.join_room(&self.room_id).await?;
+        Ok(())
+    }
+
     /// Deletes a Control API element of a `WebRtcPublishEndpoint` with the
     /// provided ID.
     ///
@@ -642,6 +1011,149 @@ impl World {
         assert!(resp.error.is_none());
     }
 
+    /// Creates a `WebRtcPublishEndpoint` for the [`Member`] with the provided
+    /// ID, updating its expected media publishing state.
+    ///
+    /// # Errors
+    ///
+    /// If the performed request to Control API fails.
+    ///
+    /// # Panics
+    ///
+    /// If the [`Member`] with the provided ID doesn't exist in this [`World`].
+    pub async fn create_publish_endpoint(
+        &mut self,
+        member_id: &str,
+        opts: PublishEndpointOpts,
+    ) -> Result<()> {
+        let is_sfu = env::var("SFU").is_ok();
+        let endpoint = proto::WebRtcPublishEndpoint {
+            id: "publish".to_owned(),
+            p2p: if is_sfu {
+                proto::P2pMode::Never
+            } else {
+                proto::P2pMode::Always
+            },
+            force_relay: self.is_force_relayed,
+            audio_settings: opts.audio.unwrap_or(proto::AudioSettings {
+                publish_policy: PublishPolicy::Disabled,
+            }),
+            video_settings: opts.video.unwrap_or(proto::VideoSettings {
+                publish_policy: PublishPolicy::Disabled,
+            }),
+        };
+        let resp = self
+            .control_client
+            .create(
+                &control_api_path!(self.room_id, member_id, endpoint.id),
+                endpoint.into(),
+            )
+            .await?;
+        if let Some(e) = resp.error {
+            return Err(Error::ControlApi(e.text));
+        }
+
+        let member = self.members.get_mut(member_id).unwrap();
+        if opts.audio.is_some() {
+            member.update_send_media_state(Some(MediaKind::Audio), None, true);
+        }
+        if opts.video.is_some() {
+            member.update_send_media_state(Some(MediaKind::Video), None, true);
+            if is_sfu {
+                member.update_send_media_state(
+                    Some(MediaKind::Video),
+                    Some(MediaSourceKind::Display),
+                    true,
+                );
+            }
+        }
+        member.set_is_send(true);
+        Ok(())
+    }
+
+    /// Creates a `WebRtcPlayEndpoint` for the [`Member`] with the provided ID,
+    /// receiving media from the `WebRtcPublishEndpoint` of the provided
+    /// `src_member_id`, and updates its expected media receiving state.
+    ///
+    /// # Errors
+    ///
+    /// If the performed request to Control API fails.
+    ///
+    /// # Panics
+    ///
+    /// If the [`Member`] with the provided ID doesn't exist in this [`World`].
+    pub async fn create_play_endpoint(
+        &mut self,
+        member_id: &str,
+        src_member_id: &str,
+    ) -> Result<()> {
+        let endpoint = proto::WebRtcPlayEndpoint {
+            id: format!("play-{src_member_id}"),
+            src: format!("local://{}/{src_member_id}/publish", self.room_id),
+            force_relay: self.is_force_relayed,
+        };
+        let resp = self
+            .control_client
+            .create(
+                &control_api_path!(self.room_id, member_id, endpoint.id),
+                endpoint.into(),
+            )
+            .await?;
+        if let Some(e) = resp.error {
+            return Err(Error::ControlApi(e.text));
+        }
+
+        let member = self.members.get_mut(member_id).unwrap();
+        member.update_recv_media_state(None, None, true);
+        if env::var("SFU").is_ok() {
+            member.update_recv_media_state(
+                Some(MediaKind::Video),
+                Some(MediaSourceKind::Display),
+                true,
+            );
+        }
+        member.set_is_recv(true);
+        Ok(())
+    }
+
+    /// Deletes the Control API element of an `Endpoint` with the provided
+    /// `endpoint_id` of the [`Member`] with the provided ID.
+    ///
+    /// Deleting the `publish` endpoint also disables the expected media
+    /// publishing state of the [`Member`]. The expected media receiving state
+    /// is left untouched, since the [`Member`] may still have other
+    /// `WebRtcPlayEndpoint`s.
+    ///
+    /// # Errors
+    ///
+    /// If the performed request to Control API fails.
+    pub async fn delete_endpoint(
+        &mut self,
+        member_id: &str,
+        endpoint_id: &str,
+    ) -> Result<()> {
+        let resp = self
+            .control_client
+            .delete(&control_api_path!(self.room_id, member_id, endpoint_id))
+            .await?;
+        if let Some(e) = resp.error {
+            return Err(Error::ControlApi(e.text));
+        }
+
+        if endpoint_id == "publish" {
+            if let Some(member) = self.members.get_mut(member_id) {
+                member.update_send_media_state(None, None, false);
+                member.update_send_media_state(
+                    Some(MediaKind::Video),
+                    Some(MediaSourceKind::Display),
+                    false,
+                );
+                member.set_is_send(false);
+            }
+        }
+        Ok(())
+    }
+
     /// Deletes a Control API element of the [`Member`] with the provided ID.
     ///
     /// # Panics
@@ -719,6 +1231,42 @@ impl World {
     }
 }
 
+/// Spawns a new [`Member`] from the provided [`MemberBuilder`] in a new
+/// browser window created by the provided [`WindowFactory`].
+///
+/// # Errors
+///
+/// If initializing the [`Member`] fails on JS side.
+async fn spawn_member(
+    factory: &WindowFactory,
+    builder: MemberBuilder,
+    send_state: MediaStates,
+    recv_state: MediaStates,
+) -> Result<(Object<Jason>, Member)> {
+    let window = factory.new_window().await;
+    window.start_console_capture().await?;
+    let jason = Object::spawn(Jason, window.clone()).await?;
+    let room = jason.init_room().await?;
+    let member = builder.build(room, window, send_state, recv_state).await?;
+    Ok((jason, member))
+}
+
+/// Waits until the provided [`Member`] will receive all the expected tracks
+/// from the provided `partner`.
+async fn wait_for_tracks_from(member: &Member, partner: &Member) -> Result<()> {
+    let (_, recv_count) =
+        member.count_of_tracks_between_members(partner).await?;
+    member
+        .connections()
+        .wait_for_connection(partner.id().to_owned(), *conf::CONNECTION_TIMEOUT)
+        .await?
+        .tracks_store()
+        .await?
+        .wait_for_count(recv_count)
+        .await?;
+    Ok(())
+}
+
 /// `Member`s pairing configuration.
 ///
 /// Based on this configuration [`World`] can dynamically create `Endpoint`s for
@@ -731,6 +1279,21 @@ pub struct MembersPair {
     pub right: PairedMember,
 }
 
+/// Options of a `WebRtcPublishEndpoint` created via
+/// [`World::create_publish_endpoint()`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PublishEndpointOpts {
+    /// Audio settings of the endpoint.
+    ///
+    /// Audio publishing is disabled if [`None`].
+    pub audio: Option<proto::AudioSettings>,
+
+    /// Video settings of the endpoint.
+    ///
+    /// Video publishing is disabled if [`None`].
+    pub video: Option<proto::VideoSettings>,
+}
+
 /// `Endpoint`s configuration of a `Member`.
 pub struct PairedMember {
     /// Unique ID of this [`PairedMember`].
@@ -27,6 +27,18 @@ enum LocalMediaInitExceptionKind {
   /// [2]: https://tinyurl.com/rnxcavf
   /// [3]: https://w3.org/TR/screen-capture#dom-mediadevices-getdisplaymedia
   localTrackIsEnded,
+
+  /// Occurs if there are no media input devices of a required kind, so no
+  /// [getUserMedia()][1] request is made at all.
+  ///
+  /// [1]: https://tinyurl.com/w3-streams#dom-mediadevices-getusermedia
+  noDevices,
+
+  /// Occurs if the provided media constraints can never be satisfied, so no
+  /// media is requested at all.
+  ///
+  /// The error message lists all the invalid constraints.
+  invalidConstraints,
 }
 
 /// Possible error kinds of a [RpcClientException].
@@ -45,6 +57,14 @@ enum RpcClientExceptionKind {
 
   /// RPC session has been finished. This is a terminal state.
   sessionFinished,
+
+  /// RPC session cannot be started, since WebRTC is unavailable on the current
+  /// platform (e.g. disabled by a browser policy).
+  webRtcUnavailable,
+
+  /// `Room` is closed, so cannot be joined anymore, and a new one should be
+  /// created instead.
+  roomClosed,
 }
 
 /// Kind of a [MediaStateTransitionException].
@@ -54,6 +74,10 @@ enum MediaStateTransitionExceptionKind {
 
   /// Requested state transition is not allowed by `Sender`'s settings.
   prohibitedState,
+
+  /// Media state of a `Sender` has been forced by a media server (by a
+  /// moderator, for example), which rejects the requested state transition.
+  forbiddenByServer,
 }
 
 /// Exception thrown when cannot get info of available media devices.
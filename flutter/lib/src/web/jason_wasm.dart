@@ -33,6 +33,8 @@ class LocalMediaInitExceptionKind {
   external static num get GetUserMediaFailed;
   external static num get GetDisplayMediaFailed;
   external static num get LocalTrackIsEnded;
+  external static num get NoDevices;
+  external static num get InvalidConstraints;
 }
 
 @JS()
@@ -40,6 +42,8 @@ class RpcClientExceptionKind {
   external static num get ConnectionLost;
   external static num get AuthorizationFailed;
   external static num get SessionFinished;
+  external static num get WebRtcUnavailable;
+  external static num get RoomClosed;
 }
 
 @JS()
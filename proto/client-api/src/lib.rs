@@ -371,6 +371,58 @@ pub enum PeerMetrics {
 
     /// `PeerConnection`'s RTC stats.
     RtcStats(Vec<RtcStat>),
+
+    /// `PeerConnection`'s SDP negotiations stats.
+    NegotiationStats(NegotiationStats),
+
+    /// `PeerConnection`'s audio-video synchronization stats.
+    AvSyncStats(AvSyncStats),
+}
+
+/// Audio-video synchronization stats of the media received by a
+/// `PeerConnection`.
+#[cfg_attr(feature = "client", derive(Serialize))]
+#[cfg_attr(feature = "server", derive(Deserialize))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct AvSyncStats {
+    /// Estimated drift of the received video relative to the received audio
+    /// in milliseconds, positive when the video is played out ahead of the
+    /// audio.
+    pub drift_ms: Option<i32>,
+
+    /// Average jitter buffer delay of the received audio in milliseconds.
+    pub audio_jitter_buffer_delay_ms: Option<u64>,
+
+    /// Average jitter buffer delay of the received video in milliseconds.
+    pub video_jitter_buffer_delay_ms: Option<u64>,
+
+    /// Jitter buffer target of the received audio in milliseconds, if set.
+    pub audio_jitter_buffer_target_ms: Option<u64>,
+
+    /// Jitter buffer target of the received video in milliseconds, if set.
+    pub video_jitter_buffer_target_ms: Option<u64>,
+}
+
+/// Stats of SDP offer/answer exchanges performed by a `PeerConnection` since
+/// its creation.
+#[cfg_attr(feature = "client", derive(Serialize))]
+#[cfg_attr(feature = "server", derive(Deserialize))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct NegotiationStats {
+    /// Number of the completed SDP offer/answer exchanges.
+    pub count: u32,
+
+    /// Duration of the last completed SDP offer/answer exchange in
+    /// milliseconds.
+    pub last_duration_ms: Option<u64>,
+
+    /// Number of the performed rollbacks to a stable signaling state.
+    pub rollbacks: u32,
+
+    /// Number of the rollbacks performed by re-applying the last stable SDP
+    /// offer/answer exchange, since a platform has rejected an explicit
+    /// rollback.
+    pub fallback_rollbacks: u32,
 }
 
 /// `PeerConnection`'s ICE connection state.
@@ -756,6 +808,12 @@ pub struct TrackPatchCommand {
     /// Muting and unmuting can be performed without adding/removing tracks
     /// from transceivers, hence renegotiation is not required.
     pub muted: Option<bool>,
+
+    /// [`DisplaySurface`] captured by this [`Track`]'s media.
+    ///
+    /// Sent only for [`MediaSourceKind::Display`] [`Track`]s, once their
+    /// media is acquired.
+    pub display_surface: Option<DisplaySurface>,
 }
 
 /// Patch of a [`Track`] which Media Server can send with an
@@ -787,6 +845,10 @@ pub struct TrackPatchEvent {
     /// Muting and unmuting can be performed without adding/removing tracks
     /// from transceivers, hence renegotiation is not required.
     pub muted: Option<bool>,
+
+    /// [`DisplaySurface`] captured by this [`Track`]'s media on its sender
+    /// side.
+    pub display_surface: Option<DisplaySurface>,
 }
 
 /// Media exchange direction of a `Track`.
@@ -839,6 +901,7 @@ impl From<TrackPatchCommand> for TrackPatchEvent {
                 }
             }),
             receivers: None,
+            display_surface: from.display_surface,
         }
     }
 }
@@ -852,6 +915,7 @@ impl TrackPatchEvent {
             muted: None,
             media_direction: None,
             receivers: None,
+            display_surface: None,
         }
     }
 
@@ -875,6 +939,10 @@ impl TrackPatchEvent {
         if let Some(receivers) = &another.receivers {
             self.receivers = Some(receivers.clone());
         }
+
+        if let Some(surface) = another.display_surface {
+            self.display_surface = Some(surface);
+        }
     }
 }
 
@@ -978,6 +1046,27 @@ pub enum MediaSourceKind {
     Display,
 }
 
+/// Type of a display surface captured by a [`MediaSourceKind::Display`]
+/// [`Track`].
+///
+/// Representation of a [DisplayCaptureSurfaceType][1].
+///
+/// [1]: https://w3.org/TR/screen-capture#dom-displaycapturesurfacetype
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum DisplaySurface {
+    /// Whole monitor is captured.
+    Monitor,
+
+    /// Single application window is captured.
+    Window,
+
+    /// Single browser tab is captured.
+    Browser,
+
+    /// Captured surface type is unknown.
+    Unknown,
+}
+
 /// Estimated connection quality.
 #[cfg_attr(feature = "client", derive(Deserialize))]
 #[cfg_attr(feature = "server", derive(Serialize))]
@@ -5,6 +5,7 @@
 #![allow(clippy::module_name_repetitions)]
 
 use std::{
+    collections::BTreeMap,
     hash::{Hash, Hasher},
     time::{Duration, SystemTime, SystemTimeError},
 };
@@ -691,7 +692,7 @@ pub struct RtcSctpTransportStats {
 /// [1]: https://w3.org/TR/webrtc-stats/#transportstats-dict%2A
 /// [2]: https://w3.org/TR/mediacapture-streams#mediastreamtrack
 #[serde_with::skip_serializing_none]
-#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RtcTransportStats {
     /// Total number of packets sent over this transport.
@@ -718,8 +719,67 @@ pub struct RtcTransportStats {
     /// [1]: https://w3.org/TR/webrtc#dom-icetransport-role
     /// [2]: https://w3.org/TR/webrtc#dom-rtcdtlstransport-icetransport
     pub ice_role: Option<IceRole>,
+
+    /// Set to the current value of the [`state` attribute][1] of the
+    /// underlying [RTCDtlsTransport].
+    ///
+    /// [RTCDtlsTransport]: https://w3.org/TR/webrtc#dom-rtcdtlstransport
+    /// [1]: https://w3.org/TR/webrtc#dom-rtcdtlstransport-state
+    pub dtls_state: Option<DtlsTransportState>,
+
+    /// Number of times that the selected candidate pair of this transport has
+    /// changed.
+    ///
+    /// Going from not having a selected candidate pair to having a selected
+    /// candidate pair, or the other way around, also increases this counter.
+    pub selected_candidate_pair_changes: Option<u64>,
+
+    /// Descriptive name of the protection profile used for the SRTP
+    /// transport, as defined in the "Profile" column of the [IANA DTLS-SRTP
+    /// protection profile registry][1].
+    ///
+    /// [1]: https://iana.org/assignments/srtp-protection/srtp-protection.xhtml
+    pub srtp_cipher: Option<String>,
+
+    /// Descriptive name of the cipher suite used for the DTLS transport, as
+    /// defined in the "Description" column of the [IANA TLS cipher suites
+    /// registry][1].
+    ///
+    /// [1]: https://iana.org/assignments/tls-parameters#tls-parameters-4
+    pub dtls_cipher: Option<String>,
+}
+
+/// State of a [RTCDtlsTransport].
+///
+/// [RTCDtlsTransport]: https://w3.org/TR/webrtc#dom-rtcdtlstransport
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KnownDtlsTransportState {
+    /// DTLS has not started negotiating yet.
+    New,
+
+    /// DTLS is in the process of negotiating a secure connection and verifying
+    /// the remote fingerprint.
+    Connecting,
+
+    /// DTLS has completed negotiation of a secure connection and verified the
+    /// remote fingerprint.
+    Connected,
+
+    /// Transport has been closed intentionally as the result of receipt of a
+    /// `close_notify` alert, or calling [close()][1].
+    ///
+    /// [1]: https://w3.org/TR/webrtc#dom-rtcpeerconnection-close
+    Closed,
+
+    /// Transport has failed as the result of an error (such as receipt of an
+    /// error alert or failure to validate the remote fingerprint).
+    Failed,
 }
 
+/// Non-exhaustive version of [`KnownDtlsTransportState`].
+pub type DtlsTransportState = NonExhaustive<KnownDtlsTransportState>;
+
 /// Variants of [ICE roles][1].
 ///
 /// More info in the [RFC 5245].
@@ -1036,6 +1096,7 @@ pub enum RtcInboundRtpStreamMediaType {
     Video {
         /// Total number of frames correctly decoded for this RTP stream, i.e.
         /// frames that would be displayed if no frames are dropped.
+        #[serde(rename = "framesDecoded")]
         frames_decoded: Option<u64>,
 
         /// Total number of key frames, such as key frames in VP8 [RFC 6386] or
@@ -1121,6 +1182,11 @@ pub struct RtcInboundRtpStreamStats {
     /// ID of the stats object representing the receiving track.
     pub track_id: Option<String>,
 
+    /// [`id` property][1] of the receiving track.
+    ///
+    /// [1]: https://w3.org/TR/mediacapture-streams#dom-mediastreamtrack-id
+    pub track_identifier: Option<String>,
+
     /// Fields which should be in the [`RtcStat`] based on `mediaType`.
     #[serde(flatten)]
     pub media_specific_stats: RtcInboundRtpStreamMediaType,
@@ -1160,6 +1226,42 @@ pub struct RtcInboundRtpStreamStats {
     ///
     /// [`jitterBufferDelay`]: https://tinyurl.com/qvoojt5
     pub jitter_buffer_emitted_count: Option<u64>,
+
+    /// Sum of the time, in seconds, each audio sample or video frame takes
+    /// from the time the first packet is received by the jitter buffer to the
+    /// time it exits the jitter buffer.
+    ///
+    /// The average jitter buffer delay can be calculated by dividing this
+    /// value with the [`jitterBufferEmittedCount`][1].
+    ///
+    /// [1]: https://w3.org/TR/webrtc-stats/#dom-rtcinboundrtpstreamstats
+    pub jitter_buffer_delay: Option<Float>,
+
+    /// Estimated playout time of this receiver's track in sender NTP time.
+    ///
+    /// Can be used to estimate how much audio and video is out of sync for
+    /// two tracks from the same source.
+    pub estimated_playout_timestamp: Option<HighResTimeStamp>,
+
+    /// Identifies the decoder implementation used for this stream (e.g.
+    /// `libvpx`, `FFmpeg` or `ExternalDecoder`).
+    ///
+    /// May change during the stream lifetime, as a browser may fall back to
+    /// another decoder.
+    ///
+    /// [Full doc on W3C][1].
+    ///
+    /// [1]: https://w3.org/TR/webrtc-stats/#dom-rtcinboundrtpstreamstats
+    pub decoder_implementation: Option<String>,
+
+    /// Indicator whether the decoder currently used for this stream is
+    /// considered power efficient by a browser, which usually means it's a
+    /// hardware one.
+    ///
+    /// [Full doc on W3C][1].
+    ///
+    /// [1]: https://w3.org/TR/webrtc-stats/#dom-rtcinboundrtpstreamstats
+    pub power_efficient_decoder: Option<bool>,
 }
 
 /// Statistics related to a specific [MediaStreamTrack][1]'s attachment to an
@@ -1258,9 +1360,35 @@ pub enum RtcOutboundRtpStreamMediaType {
         ///
         /// [1]: https://tinyurl.com/rrmkrfk
         frames_per_second: Option<u64>,
+
+        /// Current reason for limiting the resolution and/or frame rate of
+        /// this RTP stream.
+        #[serde(rename = "qualityLimitationReason")]
+        quality_limitation_reason: Option<QualityLimitationReason>,
     },
 }
 
+/// [RTCQualityLimitationReason][1] of an outbound video RTP stream.
+///
+/// [1]: https://w3.org/TR/webrtc-stats#dom-rtcqualitylimitationreason
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum QualityLimitationReason {
+    /// Resolution and/or frame rate is not limited.
+    None,
+
+    /// Resolution and/or frame rate is primarily limited due to CPU load.
+    Cpu,
+
+    /// Resolution and/or frame rate is primarily limited due to congestion
+    /// cues during bandwidth estimation.
+    Bandwidth,
+
+    /// Resolution and/or frame rate is primarily limited for a reason other
+    /// than the above.
+    Other,
+}
+
 /// Statistics for an outbound [RTP] stream that is currently sent with this
 /// [RTCPeerConnection] object.
 ///
@@ -1285,7 +1413,7 @@ pub enum RtcOutboundRtpStreamMediaType {
 /// [4]: https://tinyurl.com/rkuvpl4
 /// [5]: https://w3.org/TR/webrtc-stats/#outboundrtpstats-dict%2A
 #[serde_with::skip_serializing_none]
-#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[derive(Clone, Debug, Deserialize, Hash, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RtcOutboundRtpStreamStats {
     /// ID of the stats object representing the current track attachment to the
@@ -1305,6 +1433,37 @@ pub struct RtcOutboundRtpStreamStats {
     /// ID of the stats object representing the track currently
     /// attached to the sender of this stream.
     pub media_source_id: Option<String>,
+
+    /// Total time (in seconds) this stream has spent in each
+    /// [`QualityLimitationReason`] state, keyed by the reason name (`none`,
+    /// `cpu`, `bandwidth` or `other`).
+    ///
+    /// Reported for video streams only, and not by all the browsers.
+    ///
+    /// [Full doc on W3C][1].
+    ///
+    /// [1]: https://w3.org/TR/webrtc-stats/#outboundrtpstats-dict%2A
+    pub quality_limitation_durations: Option<BTreeMap<String, Float>>,
+
+    /// Identifies the encoder implementation used for this stream (e.g.
+    /// `libvpx`, `OpenH264` or `ExternalEncoder`).
+    ///
+    /// May change during the stream lifetime, as a browser may fall back to
+    /// another encoder.
+    ///
+    /// [Full doc on W3C][1].
+    ///
+    /// [1]: https://w3.org/TR/webrtc-stats/#dom-rtcoutboundrtpstreamstats
+    pub encoder_implementation: Option<String>,
+
+    /// Indicator whether the encoder currently used for this stream is
+    /// considered power efficient by a browser, which usually means it's a
+    /// hardware one.
+    ///
+    /// [Full doc on W3C][1].
+    ///
+    /// [1]: https://w3.org/TR/webrtc-stats/#dom-rtcoutboundrtpstreamstats
+    pub power_efficient_encoder: Option<bool>,
 }
 
 /// Properties of a `candidate` in [Section 15.1 of RFC 5245][1].
@@ -37,11 +37,13 @@ pub use dart_sys::Dart_Handle;
 
 pub use crate::{
     connection::ConnectionHandle,
+    error_report::NonFatalError,
     jason::Jason,
     media::{
         track::{local::LocalMediaTrack, remote::Track as RemoteMediaTrack},
         MediaManagerHandle,
     },
+    peer::{Adaptation, CodecFallback, ForcedMediaState, SdpEvent},
     room::{RoomCloseReason, RoomHandle},
     rpc::ReconnectHandle,
 };
@@ -802,10 +804,11 @@ pub fn media_manager_handle_on_device_change(
     cb: DartOpaque,
 ) -> anyhow::Result<SyncReturn<()>> {
     let manager = MediaManagerHandle::clone(&manager);
+    let cb: platform::Function<()> = unsafe {
+        platform::Function::new(cb.try_unwrap().unwrap().into_raw().cast())
+    };
     manager
-        .on_device_change(unsafe {
-            platform::Function::new(cb.try_unwrap().unwrap().into_raw().cast())
-        })
+        .on_device_change(move |_| cb.call0())
         .map_err(|err| anyhow::anyhow!("{:?}", DartError::from(err)))?;
 
     Ok(SyncReturn(()))
@@ -843,7 +846,7 @@ pub fn reconnect_handle_reconnect_with_delay(
 ) -> SyncReturn<DartOpaque> {
     let reconnect_handle = ReconnectHandle::clone(&reconnect_handle);
     let result = async move {
-        reconnect_handle.reconnect_with_delay(delay_ms).await?;
+        _ = reconnect_handle.reconnect_with_delay(delay_ms).await?;
         Ok::<_, Error>(())
     }
     .into_dart_future()
@@ -884,7 +887,7 @@ pub fn reconnect_handle_reconnect_with_backoff(
 ) -> SyncReturn<DartOpaque> {
     let reconnect_handle = ReconnectHandle::clone(&reconnect_handle);
     let result = async move {
-        reconnect_handle
+        _ = reconnect_handle
             .reconnect_with_backoff(
                 starting_delay,
                 multiplier,
@@ -1018,6 +1021,26 @@ pub fn remote_media_track_media_direction(
 
 //------------------------------------------------------------------------------
 
+impl ForeignClass for Adaptation {}
+
+//------------------------------------------------------------------------------
+
+impl ForeignClass for CodecFallback {}
+
+//------------------------------------------------------------------------------
+
+impl ForeignClass for ForcedMediaState {}
+
+//------------------------------------------------------------------------------
+
+impl ForeignClass for NonFatalError {}
+
+//------------------------------------------------------------------------------
+
+impl ForeignClass for SdpEvent {}
+
+//------------------------------------------------------------------------------
+
 impl ForeignClass for RoomCloseReason {}
 
 /// Returns the [`RoomCloseReason`] from the [`ForeignClass`] address.
@@ -1098,6 +1121,7 @@ pub fn room_handle_set_local_media_settings(
                 settings.into(),
                 stop_first,
                 rollback_on_fail,
+                false,
             )
             .await?;
         Ok::<_, ConstraintsUpdateError>(())
@@ -1115,11 +1139,11 @@ pub fn room_handle_set_local_media_settings(
 pub fn room_handle_mute_audio(
     room_handle: RustOpaque<RoomHandle>,
 ) -> SyncReturn<DartOpaque> {
-    let room_handle = RoomHandle::clone(&room_handle);
+    let fut = room_handle.mute_audio();
 
     SyncReturn(
         async move {
-            room_handle.mute_audio().await?;
+            _ = fut.await?;
 
             Ok::<_, Traced<room::ChangeMediaStateError>>(())
         }
@@ -1135,11 +1159,11 @@ pub fn room_handle_mute_audio(
 pub fn room_handle_unmute_audio(
     room_handle: RustOpaque<RoomHandle>,
 ) -> SyncReturn<DartOpaque> {
-    let room_handle = RoomHandle::clone(&room_handle);
+    let fut = room_handle.unmute_audio();
 
     SyncReturn(
         async move {
-            room_handle.unmute_audio().await?;
+            _ = fut.await?;
 
             Ok::<_, Traced<room::ChangeMediaStateError>>(())
         }
@@ -1155,11 +1179,11 @@ pub fn room_handle_unmute_audio(
 pub fn room_handle_enable_audio(
     room_handle: RustOpaque<RoomHandle>,
 ) -> SyncReturn<DartOpaque> {
-    let room_handle = RoomHandle::clone(&room_handle);
+    let fut = room_handle.enable_audio();
 
     SyncReturn(
         async move {
-            room_handle.enable_audio().await?;
+            _ = fut.await?;
 
             Ok::<_, Traced<room::ChangeMediaStateError>>(())
         }
@@ -1175,11 +1199,11 @@ pub fn room_handle_enable_audio(
 pub fn room_handle_disable_audio(
     room_handle: RustOpaque<RoomHandle>,
 ) -> SyncReturn<DartOpaque> {
-    let room_handle = RoomHandle::clone(&room_handle);
+    let fut = room_handle.disable_audio();
 
     SyncReturn(
         async move {
-            room_handle.disable_audio().await?;
+            _ = fut.await?;
 
             Ok::<_, Traced<room::ChangeMediaStateError>>(())
         }
@@ -1201,11 +1225,11 @@ pub fn room_handle_mute_video(
     room_handle: RustOpaque<RoomHandle>,
     source_kind: Option<MediaSourceKind>,
 ) -> anyhow::Result<SyncReturn<DartOpaque>> {
-    let room_handle = RoomHandle::clone(&room_handle);
+    let fut = room_handle.mute_video(source_kind);
 
     Ok(SyncReturn(
         async move {
-            room_handle.mute_video(source_kind).await?;
+            _ = fut.await?;
 
             Ok::<_, Traced<room::ChangeMediaStateError>>(())
         }
@@ -1227,11 +1251,11 @@ pub fn room_handle_unmute_video(
     room_handle: RustOpaque<RoomHandle>,
     source_kind: Option<MediaSourceKind>,
 ) -> anyhow::Result<SyncReturn<DartOpaque>> {
-    let room_handle = RoomHandle::clone(&room_handle);
+    let fut = room_handle.unmute_video(source_kind);
 
     Ok(SyncReturn(
         async move {
-            room_handle.unmute_video(source_kind).await?;
+            _ = fut.await?;
 
             Ok::<_, Traced<room::ChangeMediaStateError>>(())
         }
@@ -1253,11 +1277,11 @@ pub fn room_handle_enable_video(
     room_handle: RustOpaque<RoomHandle>,
     source_kind: Option<MediaSourceKind>,
 ) -> anyhow::Result<SyncReturn<DartOpaque>> {
-    let room_handle = RoomHandle::clone(&room_handle);
+    let fut = room_handle.enable_video(source_kind);
 
     Ok(SyncReturn(
         async move {
-            room_handle.enable_video(source_kind).await?;
+            _ = fut.await?;
 
             Ok::<_, Traced<room::ChangeMediaStateError>>(())
         }
@@ -1279,11 +1303,11 @@ pub fn room_handle_disable_video(
     room_handle: RustOpaque<RoomHandle>,
     source_kind: Option<MediaSourceKind>,
 ) -> anyhow::Result<SyncReturn<DartOpaque>> {
-    let room_handle = RoomHandle::clone(&room_handle);
+    let fut = room_handle.disable_video(source_kind);
 
     Ok(SyncReturn(
         async move {
-            room_handle.disable_video(source_kind).await?;
+            _ = fut.await?;
 
             Ok::<_, Traced<room::ChangeMediaStateError>>(())
         }
@@ -1299,11 +1323,11 @@ pub fn room_handle_disable_video(
 pub fn room_handle_enable_remote_audio(
     room_handle: RustOpaque<RoomHandle>,
 ) -> SyncReturn<DartOpaque> {
-    let room_handle = RoomHandle::clone(&room_handle);
+    let fut = room_handle.enable_remote_audio();
 
     SyncReturn(
         async move {
-            room_handle.enable_remote_audio().await?;
+            _ = fut.await?;
 
             Ok::<_, Traced<room::ChangeMediaStateError>>(())
         }
@@ -1319,11 +1343,11 @@ pub fn room_handle_enable_remote_audio(
 pub fn room_handle_disable_remote_audio(
     room_handle: RustOpaque<RoomHandle>,
 ) -> SyncReturn<DartOpaque> {
-    let room_handle = RoomHandle::clone(&room_handle);
+    let fut = room_handle.disable_remote_audio();
 
     SyncReturn(
         async move {
-            room_handle.disable_remote_audio().await?;
+            _ = fut.await?;
 
             Ok::<_, Traced<room::ChangeMediaStateError>>(())
         }
@@ -1345,11 +1369,11 @@ pub fn room_handle_enable_remote_video(
     room_handle: RustOpaque<RoomHandle>,
     source_kind: Option<MediaSourceKind>,
 ) -> anyhow::Result<SyncReturn<DartOpaque>> {
-    let room_handle = RoomHandle::clone(&room_handle);
+    let fut = room_handle.enable_remote_video(source_kind);
 
     Ok(SyncReturn(
         async move {
-            room_handle.enable_remote_video(source_kind).await?;
+            _ = fut.await?;
 
             Ok::<_, Traced<room::ChangeMediaStateError>>(())
         }
@@ -1371,11 +1395,11 @@ pub fn room_handle_disable_remote_video(
     room_handle: RustOpaque<RoomHandle>,
     source_kind: Option<MediaSourceKind>,
 ) -> anyhow::Result<SyncReturn<DartOpaque>> {
-    let room_handle = RoomHandle::clone(&room_handle);
+    let fut = room_handle.disable_remote_video(source_kind);
 
     Ok(SyncReturn(
         async move {
-            room_handle.disable_remote_video(source_kind).await?;
+            _ = fut.await?;
 
             Ok::<_, Traced<room::ChangeMediaStateError>>(())
         }
@@ -55,8 +55,9 @@ pub use crate::media::MediaDirection;
 
 pub use self::{
     api::{
-        ConnectionHandle, Jason, LocalMediaTrack, MediaManagerHandle,
-        ReconnectHandle, RemoteMediaTrack, RoomCloseReason, RoomHandle,
+        Adaptation, CodecFallback, ConnectionHandle, ForcedMediaState, Jason,
+        LocalMediaTrack, MediaManagerHandle, NonFatalError, ReconnectHandle,
+        RemoteMediaTrack, RoomCloseReason, RoomHandle, SdpEvent,
     },
     utils::DartError as Error,
 };
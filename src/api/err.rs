@@ -13,9 +13,9 @@ use crate::{
     api::Error,
     connection,
     media::{
-        self, EnumerateDevicesError, EnumerateDisplaysError,
-        GetDisplayMediaError, GetUserMediaError, InitLocalTracksError,
-        InvalidOutputAudioDeviceIdError, MicVolumeError,
+        self, DeviceCapabilitiesError, EnumerateDevicesError,
+        EnumerateDisplaysError, GetDisplayMediaError, GetUserMediaError,
+        InitLocalTracksError, InvalidOutputAudioDeviceIdError, MicVolumeError,
     },
     peer::{
         sender::CreateError, InsertLocalTracksError, LocalMediaError,
@@ -26,6 +26,9 @@ use crate::{
     utils::Caused,
 };
 
+#[cfg(target_family = "wasm")]
+use crate::utils::AbortableError;
+
 /// Error thrown when the operation wasn't allowed by the current state of the
 /// object.
 #[cfg_attr(target_family = "wasm", wasm_bindgen)]
@@ -98,6 +101,18 @@ pub enum LocalMediaInitExceptionKind {
     /// [2]: https://tinyurl.com/rnxcavf
     /// [3]: https://w3.org/TR/screen-capture#dom-mediadevices-getdisplaymedia
     LocalTrackIsEnded,
+
+    /// Occurs if there are no media input devices of a required kind, so no
+    /// [getUserMedia()][1] request is made at all.
+    ///
+    /// [1]: https://tinyurl.com/w3-streams#dom-mediadevices-getusermedia
+    NoDevices,
+
+    /// Occurs if the provided media constraints can never be satisfied, so no
+    /// media is requested at all.
+    ///
+    /// The error message lists all the invalid constraints.
+    InvalidConstraints,
 }
 
 /// Exception thrown when accessing media devices.
@@ -279,6 +294,16 @@ pub enum RpcClientExceptionKind {
 
     /// RPC session has been finished. This is a terminal state.
     SessionFinished,
+
+    /// RPC session cannot be started, since [WebRTC] is unavailable on the
+    /// current platform (e.g. disabled by a browser policy).
+    ///
+    /// [WebRTC]: https://w3.org/TR/webrtc
+    WebRtcUnavailable,
+
+    /// `Room` is closed, so cannot be joined anymore, and a new one should be
+    /// created instead.
+    RoomClosed,
 }
 
 /// Exceptions thrown from a RPC client that implements messaging with media
@@ -425,6 +450,181 @@ impl FormatException {
     }
 }
 
+/// Exception thrown when the requested operation isn't supported by the
+/// platform, the track or a media server.
+#[cfg_attr(target_family = "wasm", wasm_bindgen)]
+#[derive(Debug)]
+pub struct NotSupportedException {
+    /// Error message describing the problem.
+    message: Cow<'static, str>,
+
+    /// Stacktrace of this [`NotSupportedException`].
+    trace: Trace,
+}
+
+impl NotSupportedException {
+    /// Creates a new [`NotSupportedException`] from the provided error
+    /// `message` and `trace`.
+    #[must_use]
+    pub fn new<T: Into<Cow<'static, str>>>(message: T, trace: Trace) -> Self {
+        Self {
+            message: message.into(),
+            trace,
+        }
+    }
+}
+
+#[cfg_attr(target_family = "wasm", wasm_bindgen)]
+impl NotSupportedException {
+    /// Returns an error message describing the problem.
+    #[must_use]
+    pub fn message(&self) -> String {
+        self.message.to_string()
+    }
+
+    /// Returns stacktrace of this [`NotSupportedException`].
+    #[must_use]
+    pub fn trace(&self) -> String {
+        self.trace.to_string()
+    }
+}
+
+/// Exception thrown when an operation is aborted via its `AbortHandle` before
+/// completion.
+#[cfg_attr(target_family = "wasm", wasm_bindgen)]
+#[derive(Debug)]
+pub struct AbortedException(Cow<'static, str>);
+
+impl AbortedException {
+    /// Creates a new [`AbortedException`] with the provided `message`
+    /// describing the aborted operation.
+    #[must_use]
+    pub fn new<T: Into<Cow<'static, str>>>(message: T) -> Self {
+        Self(message.into())
+    }
+}
+
+#[cfg_attr(target_family = "wasm", wasm_bindgen)]
+impl AbortedException {
+    /// Returns an error message describing the aborted operation.
+    #[must_use]
+    pub fn message(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+/// Possible error kinds of a [`RtcPeerConnectionException`].
+#[cfg_attr(target_family = "wasm", wasm_bindgen)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u8)]
+pub enum RtcPeerConnectionExceptionKind {
+    /// Failed operation may succeed being retried once the
+    /// [RTCPeerConnection][1] is rolled back to its stable state.
+    ///
+    /// This usually means that its signaling state has been changed by a
+    /// concurrent negotiation (glare).
+    ///
+    /// [1]: https://w3.org/TR/webrtc#dom-rtcpeerconnection
+    Transient,
+
+    /// Failed operation cannot succeed being retried.
+    Fatal,
+}
+
+/// Exception thrown when an operation of a [RTCPeerConnection][1] fails.
+///
+/// [1]: https://w3.org/TR/webrtc#dom-rtcpeerconnection
+#[cfg_attr(target_family = "wasm", wasm_bindgen)]
+#[derive(Debug)]
+pub struct RtcPeerConnectionException {
+    /// Concrete error kind of this [`RtcPeerConnectionException`].
+    kind: RtcPeerConnectionExceptionKind,
+
+    /// [`platform::RtcPeerConnectionOperation`] this
+    /// [`RtcPeerConnectionException`] originates from.
+    operation: platform::RtcPeerConnectionOperation,
+
+    /// Name of the exception thrown by a platform, if any.
+    name: Option<String>,
+
+    /// Error message describing the problem.
+    message: Cow<'static, str>,
+
+    /// [`platform::Error`] causing this [`RtcPeerConnectionException`].
+    cause: Option<platform::Error>,
+
+    /// Stacktrace of this [`RtcPeerConnectionException`].
+    trace: Trace,
+}
+
+impl RtcPeerConnectionException {
+    /// Creates a new [`RtcPeerConnectionException`] from the provided
+    /// [`platform::RtcPeerConnectionError`] and `trace`.
+    #[must_use]
+    pub fn new(err: platform::RtcPeerConnectionError, trace: Trace) -> Self {
+        let kind = if err.is_transient() {
+            RtcPeerConnectionExceptionKind::Transient
+        } else {
+            RtcPeerConnectionExceptionKind::Fatal
+        };
+        Self {
+            kind,
+            operation: err.operation(),
+            name: err.exception_name(),
+            message: err.to_string().into(),
+            cause: err.cause(),
+            trace,
+        }
+    }
+}
+
+#[cfg_attr(target_family = "wasm", wasm_bindgen)]
+impl RtcPeerConnectionException {
+    /// Returns concrete error kind of this [`RtcPeerConnectionException`].
+    #[allow(clippy::missing_const_for_fn)] // because of `wasm_bindgen`
+    #[must_use]
+    pub fn kind(&self) -> RtcPeerConnectionExceptionKind {
+        self.kind
+    }
+
+    /// Returns name of the failed [RTCPeerConnection][1] operation (like
+    /// `setRemoteDescription`).
+    ///
+    /// [1]: https://w3.org/TR/webrtc#dom-rtcpeerconnection
+    #[must_use]
+    pub fn operation(&self) -> String {
+        self.operation.to_string()
+    }
+
+    /// Returns name of the exception thrown by a platform (usually, a
+    /// [DOMException][1] name, like `InvalidStateError`), if any.
+    ///
+    /// [1]: https://webidl.spec.whatwg.org#idl-DOMException
+    #[must_use]
+    pub fn name(&self) -> Option<String> {
+        self.name.clone()
+    }
+
+    /// Returns an error message describing the problem.
+    #[must_use]
+    pub fn message(&self) -> String {
+        self.message.to_string()
+    }
+
+    /// Returns [`platform::Error`] causing this
+    /// [`RtcPeerConnectionException`].
+    #[must_use]
+    pub fn cause(&self) -> Option<platform::Error> {
+        self.cause.clone()
+    }
+
+    /// Returns stacktrace of this [`RtcPeerConnectionException`].
+    #[must_use]
+    pub fn trace(&self) -> String {
+        self.trace.to_string()
+    }
+}
+
 /// Kind of a [`MediaStateTransitionException`].
 #[cfg_attr(target_family = "wasm", wasm_bindgen)]
 #[derive(Clone, Copy, Debug)]
@@ -439,6 +639,12 @@ pub enum MediaStateTransitionExceptionKind {
     ///
     /// [`Sender`]: crate::peer::media::Sender
     ProhibitedState,
+
+    /// Media state of a [`Sender`] has been forced by a media server (by a
+    /// moderator, for example), which rejects the requested state transition.
+    ///
+    /// [`Sender`]: crate::peer::media::Sender
+    ForbiddenByServer,
 }
 
 /// Exception thrown when the requested media state transition could not be
@@ -577,6 +783,13 @@ impl From<Traced<room::HandleDetachedError>> for Error {
     }
 }
 
+impl From<Traced<room::SetRtcConfigurationError>> for Error {
+    fn from(err: Traced<room::SetRtcConfigurationError>) -> Self {
+        let (err, trace) = err.split();
+        StateError::new(err.to_string(), trace).into()
+    }
+}
+
 impl From<Traced<EnumerateDevicesError>> for Error {
     fn from(err: Traced<EnumerateDevicesError>) -> Self {
         let (err, stacktrace) = err.split();
@@ -606,6 +819,114 @@ impl From<Traced<EnumerateDisplaysError>> for Error {
     }
 }
 
+impl From<Traced<media::MediaStreamSettingsParseError>> for Error {
+    fn from(err: Traced<media::MediaStreamSettingsParseError>) -> Self {
+        FormatException::new(err.into_inner().to_string()).into()
+    }
+}
+
+// Flutter platform has no `NotSupportedException` counterpart yet.
+#[cfg(target_family = "wasm")]
+impl From<Traced<media::RequestKeyFrameError>> for Error {
+    fn from(err: Traced<media::RequestKeyFrameError>) -> Self {
+        use media::RequestKeyFrameError as E;
+
+        let (err, trace) = err.split();
+        let message = err.to_string();
+        match err {
+            E::NotSupported(_) => {
+                NotSupportedException::new(message, trace).into()
+            }
+            E::Failed(e) => {
+                InternalException::new(message, Some(e), trace).into()
+            }
+        }
+    }
+}
+
+// Flutter platform has no `NotSupportedException` counterpart yet.
+#[cfg(target_family = "wasm")]
+impl From<Traced<media::JitterBufferTargetError>> for Error {
+    fn from(err: Traced<media::JitterBufferTargetError>) -> Self {
+        use media::JitterBufferTargetError as E;
+
+        let (err, trace) = err.split();
+        let message = err.to_string();
+        match err {
+            E::NotSupported(_) => {
+                NotSupportedException::new(message, trace).into()
+            }
+            E::Failed(e) => {
+                InternalException::new(message, Some(e), trace).into()
+            }
+        }
+    }
+}
+
+// Flutter platform has no `NotSupportedException` counterpart yet.
+#[cfg(target_family = "wasm")]
+impl From<Traced<media::SilenceDetectionError>> for Error {
+    fn from(err: Traced<media::SilenceDetectionError>) -> Self {
+        use media::SilenceDetectionError as E;
+
+        let (err, trace) = err.split();
+        let message = err.to_string();
+        match err {
+            E::NotSupported(_) => {
+                NotSupportedException::new(message, trace).into()
+            }
+            E::Failed(e) => {
+                InternalException::new(message, Some(e), trace).into()
+            }
+        }
+    }
+}
+
+// Flutter platform has no `NotSupportedException` counterpart yet.
+#[cfg(target_family = "wasm")]
+impl From<Traced<media::TrackTransformError>> for Error {
+    fn from(err: Traced<media::TrackTransformError>) -> Self {
+        use media::TrackTransformError as E;
+
+        let (err, trace) = err.split();
+        let message = err.to_string();
+        match err {
+            E::NotSupported(_) => {
+                NotSupportedException::new(message, trace).into()
+            }
+            E::Failed(e) => {
+                InternalException::new(message, Some(e), trace).into()
+            }
+        }
+    }
+}
+
+// Flutter platform has no `AbortedException` counterpart yet.
+#[cfg(target_family = "wasm")]
+impl<E> From<AbortableError<E>> for Error
+where
+    AbortableError<E>: ToString,
+    Self: From<E>,
+{
+    fn from(err: AbortableError<E>) -> Self {
+        match err {
+            AbortableError::Aborted => {
+                AbortedException::new(err.to_string()).into()
+            }
+            AbortableError::Failed(err) => err.into(),
+        }
+    }
+}
+
+// Flutter platform has no `RtcPeerConnectionException` counterpart yet.
+#[cfg(target_family = "wasm")]
+impl From<Traced<platform::RtcPeerConnectionError>> for Error {
+    fn from(err: Traced<platform::RtcPeerConnectionError>) -> Self {
+        let (err, trace) = err.split();
+        RtcPeerConnectionException::new(err, trace).into()
+    }
+}
+
 impl From<Traced<InvalidOutputAudioDeviceIdError>> for Error {
     fn from(err: Traced<InvalidOutputAudioDeviceIdError>) -> Self {
         let (_, trace) = err.split();
@@ -657,6 +978,43 @@ impl From<Traced<InitLocalTracksError>> for Error {
             | Err::GetDisplayMediaFailed(Gdm::LocalTrackIsEnded(_)) => {
                 (Kind::LocalTrackIsEnded, None)
             }
+            Err::NoDevices(_) => (Kind::NoDevices, None),
+            Err::InvalidConstraints(_) => (Kind::InvalidConstraints, None),
+        };
+
+        LocalMediaInitException::new(kind, message, cause, stacktrace).into()
+    }
+}
+
+impl From<Traced<DeviceCapabilitiesError>> for Error {
+    fn from(err: Traced<DeviceCapabilitiesError>) -> Self {
+        use DeviceCapabilitiesError as Err;
+        use GetUserMediaError as Gum;
+        use LocalMediaInitExceptionKind as Kind;
+
+        let (err, stacktrace) = err.split();
+        let message = err.to_string();
+
+        let (kind, cause) = match err {
+            Err::Detached => {
+                return StateError::new(message, stacktrace).into()
+            }
+            Err::EnumerateDevicesFailed(err) => {
+                return EnumerateDevicesException::new(err, stacktrace).into()
+            }
+            Err::NoSuchDevice(_) => (Kind::NoDevices, None),
+            Err::GetUserMediaFailed(Gum::PlatformRequestFailed(
+                platform::GetUserMediaError::Audio(cause),
+            )) => (Kind::GetUserMediaAudioFailed, Some(cause)),
+            Err::GetUserMediaFailed(Gum::PlatformRequestFailed(
+                platform::GetUserMediaError::Video(cause),
+            )) => (Kind::GetUserMediaVideoFailed, Some(cause)),
+            Err::GetUserMediaFailed(Gum::PlatformRequestFailed(
+                platform::GetUserMediaError::Unknown(cause),
+            )) => (Kind::GetUserMediaFailed, Some(cause)),
+            Err::GetUserMediaFailed(Gum::LocalTrackIsEnded(_)) => {
+                (Kind::LocalTrackIsEnded, None)
+            }
         };
 
         LocalMediaInitException::new(kind, message, cause, stacktrace).into()
@@ -678,37 +1036,42 @@ impl From<Traced<ReconnectError>> for Error {
 
 impl From<Traced<SessionError>> for Error {
     fn from(err: Traced<SessionError>) -> Self {
-        use ConnectionLostReason as Reason;
-        use RpcClientExceptionKind as Kind;
-        use SessionError as SE;
-
         let (err, trace) = err.split();
         let message = err.to_string();
+        session_error(err, message, trace)
+    }
+}
 
-        let mut cause = None;
-        let kind = match err {
-            SE::SessionFinished(_) => Some(Kind::SessionFinished),
-            SE::NoCredentials
-            | SE::SessionUnexpectedlyDropped
-            | SE::NewConnectionInfo => None,
-            SE::RpcClient(e) => {
-                cause = e.cause();
-                None
-            }
-            SE::AuthorizationFailed => Some(Kind::AuthorizationFailed),
-            SE::ConnectionLost(reason) => {
-                if let Reason::ConnectError(e) = reason {
-                    cause = e.into_inner().cause();
-                };
-                Some(Kind::ConnectionLost)
-            }
-        };
-
-        if let Some(rpc_kind) = kind {
-            RpcClientException::new(rpc_kind, message, cause, trace).into()
-        } else {
-            InternalException::new(message, cause, trace).into()
+/// Converts the provided [`SessionError`] into an [`Error`] with the provided
+/// `message` and `trace`.
+fn session_error(err: SessionError, message: String, trace: Trace) -> Error {
+    use ConnectionLostReason as Reason;
+    use RpcClientExceptionKind as Kind;
+    use SessionError as SE;
+
+    let mut cause = None;
+    let kind = match err {
+        SE::SessionFinished(_) => Some(Kind::SessionFinished),
+        SE::NoCredentials
+        | SE::SessionUnexpectedlyDropped
+        | SE::NewConnectionInfo => None,
+        SE::RpcClient(e) => {
+            cause = e.cause();
+            None
         }
+        SE::AuthorizationFailed => Some(Kind::AuthorizationFailed),
+        SE::ConnectionLost(reason) => {
+            if let Reason::ConnectError(e) = reason {
+                cause = e.into_inner().cause();
+            };
+            Some(Kind::ConnectionLost)
+        }
+    };
+
+    if let Some(rpc_kind) = kind {
+        RpcClientException::new(rpc_kind, message, cause, trace).into()
+    } else {
+        InternalException::new(message, cause, trace).into()
     }
 }
 
@@ -718,20 +1081,39 @@ impl From<Traced<room::RoomJoinError>> for Error {
         let message = err.to_string();
 
         match err {
-            room::RoomJoinError::Detached
-            | room::RoomJoinError::CallbackNotSet(_) => {
+            room::RoomJoinError::CallbackNotSet(_) => {
                 StateError::new(message, trace).into()
             }
             room::RoomJoinError::ConnectionInfoParse(_) => {
                 FormatException::new(message).into()
             }
-            room::RoomJoinError::SessionError(err) => {
-                Traced::compose(err, trace).into()
+            room::RoomJoinError::SessionError { err, .. } => {
+                session_error(err, message, trace)
             }
+            room::RoomJoinError::WebRtcUnavailable => RpcClientException::new(
+                RpcClientExceptionKind::WebRtcUnavailable,
+                message,
+                None,
+                trace,
+            )
+            .into(),
+            room::RoomJoinError::RoomClosed => RpcClientException::new(
+                RpcClientExceptionKind::RoomClosed,
+                message,
+                None,
+                trace,
+            )
+            .into(),
         }
     }
 }
 
+impl From<Traced<room::RoomSnapshotParseError>> for Error {
+    fn from(err: Traced<room::RoomSnapshotParseError>) -> Self {
+        FormatException::new(err.into_inner().to_string()).into()
+    }
+}
+
 impl From<Traced<connection::ChangeMediaStateError>> for Error {
     fn from(err: Traced<connection::ChangeMediaStateError>) -> Self {
         let (err, trace) = err.split();
@@ -790,6 +1172,14 @@ impl From<Traced<room::ChangeMediaStateError>> for Error {
                 )
                 .into()
             }
+            room::ChangeMediaStateError::ForbiddenByServer(_) => {
+                MediaStateTransitionException::new(
+                    message,
+                    trace,
+                    MediaStateTransitionExceptionKind::ForbiddenByServer,
+                )
+                .into()
+            }
             room::ChangeMediaStateError::InsertLocalTracksError(_) => {
                 InternalException::new(message, None, trace).into()
             }
@@ -0,0 +1,74 @@
+//! Aborting of long-running operations.
+
+use std::future::Future;
+
+use futures::future::{self, AbortRegistration};
+use js_sys::Promise;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::future_to_promise;
+
+/// Handle aborting a long-running operation, which is started by an
+/// `*_abortable()` method.
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct AbortHandle(future::AbortHandle);
+
+#[wasm_bindgen]
+impl AbortHandle {
+    /// Aborts the operation, making its `Promise` reject with an
+    /// [`AbortedException`][0], and releasing the resources acquired by it so
+    /// far.
+    ///
+    /// Does nothing if the operation has completed already.
+    ///
+    /// [0]: crate::api::err::AbortedException
+    pub fn abort(&self) {
+        self.0.abort();
+    }
+
+    /// Indicates whether the [`AbortHandle::abort()`] has been called.
+    #[must_use]
+    pub fn is_aborted(&self) -> bool {
+        self.0.is_aborted()
+    }
+}
+
+/// `Promise` of a long-running operation along with an [`AbortHandle`]
+/// aborting it.
+#[wasm_bindgen]
+#[derive(Debug)]
+pub struct AbortablePromise {
+    /// `Promise` of the operation.
+    promise: Promise,
+
+    /// [`AbortHandle`] aborting the operation.
+    abort_handle: AbortHandle,
+}
+
+#[wasm_bindgen]
+impl AbortablePromise {
+    /// Returns `Promise` of the operation.
+    #[must_use]
+    pub fn promise(&self) -> Promise {
+        self.promise.clone()
+    }
+
+    /// Returns an [`AbortHandle`] aborting the operation.
+    #[must_use]
+    pub fn abort_handle(&self) -> AbortHandle {
+        self.abort_handle.clone()
+    }
+}
+
+/// Starts the operation returned by the provided `op` function, which should be
+/// aborted once the provided [`AbortRegistration`] is aborted.
+pub fn abortable<F>(op: impl FnOnce(AbortRegistration) -> F) -> AbortablePromise
+where
+    F: Future<Output = Result<JsValue, JsValue>> + 'static,
+{
+    let (abort_handle, registration) = future::AbortHandle::new_pair();
+    AbortablePromise {
+        promise: future_to_promise(op(registration)),
+        abort_handle: AbortHandle(abort_handle),
+    }
+}
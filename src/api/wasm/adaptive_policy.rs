@@ -0,0 +1,91 @@
+//! Adaptation of outbound video to the quality limitations.
+
+use std::time::Duration;
+
+use derive_more::{Display, From, Into};
+use wasm_bindgen::prelude::*;
+
+use crate::peer;
+
+/// Policy of adapting outbound video of a `Room` to the quality limitations
+/// (CPU load or bandwidth) reported by a browser.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, From, Into)]
+pub struct AdaptivePolicy(peer::AdaptivePolicy);
+
+#[wasm_bindgen]
+impl AdaptivePolicy {
+    /// Creates a new [`AdaptivePolicy`] never adapting outbound video.
+    #[must_use]
+    pub fn disabled() -> Self {
+        peer::AdaptivePolicy::Disabled.into()
+    }
+
+    /// Creates a new [`AdaptivePolicy`] degrading outbound video in favor of
+    /// audio.
+    ///
+    /// Once outbound video stays quality-limited for `patience_ms`
+    /// milliseconds, its maximum bitrate is lowered to 1000, 500 and 250 kbps
+    /// one step at a time, and finally its encoding is paused. Once it stays
+    /// unlimited for `patience_ms` milliseconds, it's restored one step at a
+    /// time in the reverse order.
+    #[must_use]
+    pub fn audio_first(patience_ms: u32) -> Self {
+        peer::AdaptivePolicy::AudioFirst {
+            patience: Duration::from_millis(patience_ms.into()),
+        }
+        .into()
+    }
+}
+
+/// Reason of outbound video being quality-limited. Representation of a
+/// [RTCQualityLimitationReason][1] value.
+///
+/// [1]: https://w3.org/TR/webrtc-stats#dom-rtcqualitylimitationreason
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, Display, Eq, PartialEq)]
+pub enum QualityLimitationReason {
+    /// Video is limited due to CPU load.
+    Cpu,
+
+    /// Video is limited due to congestion cues during bandwidth estimation.
+    Bandwidth,
+}
+
+impl From<peer::LimitationReason> for QualityLimitationReason {
+    fn from(that: peer::LimitationReason) -> Self {
+        match that {
+            peer::LimitationReason::Cpu => Self::Cpu,
+            peer::LimitationReason::Bandwidth => Self::Bandwidth,
+        }
+    }
+}
+
+/// Transition of outbound video to another quality level, performed by an
+/// [`AdaptivePolicy`].
+#[wasm_bindgen]
+#[derive(Debug, From)]
+pub struct Adaptation(peer::Adaptation);
+
+#[wasm_bindgen]
+impl Adaptation {
+    /// Returns maximum bitrate (in bits per second) outbound video is encoded
+    /// with now, if it's limited.
+    #[must_use]
+    pub fn max_bitrate(&self) -> Option<u32> {
+        self.0.level.max_bitrate()
+    }
+
+    /// Indicates whether outbound video encoding is paused now.
+    #[must_use]
+    pub fn is_video_paused(&self) -> bool {
+        !self.0.level.is_active()
+    }
+
+    /// Returns [`QualityLimitationReason`] caused a downgrade, or `undefined`
+    /// if outbound video is being restored.
+    #[must_use]
+    pub fn reason(&self) -> Option<QualityLimitationReason> {
+        self.0.reason.map(Into::into)
+    }
+}
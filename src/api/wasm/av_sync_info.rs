@@ -0,0 +1,63 @@
+//! Audio-video synchronization estimates of the media received from a
+//! specific remote `Member`.
+
+use derive_more::From;
+use wasm_bindgen::prelude::*;
+
+use crate::peer;
+
+/// Audio-video synchronization estimates of the media received via a
+/// connection with a specific remote `Member`.
+///
+/// Every value is `undefined` if the browser doesn't report the stats it's
+/// estimated from.
+#[wasm_bindgen]
+#[derive(Debug, From)]
+pub struct AvSyncInfo(peer::AvSyncInfo);
+
+#[wasm_bindgen]
+impl AvSyncInfo {
+    /// Returns estimated drift of the received video relative to the received
+    /// audio in milliseconds, positive when the video is played out ahead of
+    /// the audio.
+    #[must_use]
+    pub fn drift_ms(&self) -> Option<i32> {
+        self.0.drift_ms
+    }
+
+    /// Returns average jitter buffer delay of the received audio in
+    /// milliseconds.
+    #[must_use]
+    pub fn audio_jitter_buffer_delay_ms(&self) -> Option<f64> {
+        self.0
+            .audio_jitter_buffer_delay
+            .map(|d| d.as_secs_f64() * 1000.0)
+    }
+
+    /// Returns average jitter buffer delay of the received video in
+    /// milliseconds.
+    #[must_use]
+    pub fn video_jitter_buffer_delay_ms(&self) -> Option<f64> {
+        self.0
+            .video_jitter_buffer_delay
+            .map(|d| d.as_secs_f64() * 1000.0)
+    }
+
+    /// Returns jitter buffer target of the received audio in milliseconds, if
+    /// it's set via the `RemoteMediaTrack.set_jitter_buffer_target()`.
+    #[must_use]
+    pub fn audio_jitter_buffer_target_ms(&self) -> Option<f64> {
+        self.0
+            .audio_jitter_buffer_target
+            .map(|d| d.as_secs_f64() * 1000.0)
+    }
+
+    /// Returns jitter buffer target of the received video in milliseconds, if
+    /// it's set via the `RemoteMediaTrack.set_jitter_buffer_target()`.
+    #[must_use]
+    pub fn video_jitter_buffer_target_ms(&self) -> Option<f64> {
+        self.0
+            .video_jitter_buffer_target
+            .map(|d| d.as_secs_f64() * 1000.0)
+    }
+}
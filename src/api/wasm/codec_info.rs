@@ -0,0 +1,70 @@
+//! Encoder and decoder implementations used for media tracks.
+
+use derive_more::From;
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    api::MediaKind,
+    media,
+    peer::{self, TrackDirection},
+};
+
+/// Implementation of an encoder used for a `LocalMediaTrack`, or of a decoder
+/// used for a `RemoteMediaTrack`, as reported by the browser.
+#[wasm_bindgen]
+#[derive(Debug, From)]
+pub struct CodecInfo(media::CodecInfo);
+
+#[wasm_bindgen]
+impl CodecInfo {
+    /// Returns name of the implementation (e.g. `libvpx` or
+    /// `ExternalEncoder`).
+    #[must_use]
+    pub fn implementation(&self) -> String {
+        self.0.implementation.clone()
+    }
+
+    /// Indicates whether the implementation is considered power efficient
+    /// (usually meaning a hardware one) by the browser, if reported.
+    #[must_use]
+    pub fn power_efficient(&self) -> Option<bool> {
+        self.0.power_efficient
+    }
+}
+
+/// Fallback of an encoder or a decoder used for media in a `Room` from a
+/// hardware implementation to a software one.
+///
+/// Usually happens once a hardware encoder or decoder fails, and leads to a
+/// higher CPU usage.
+#[wasm_bindgen]
+#[derive(Debug, From)]
+pub struct CodecFallback(peer::CodecFallback);
+
+#[wasm_bindgen]
+impl CodecFallback {
+    /// Returns [`MediaKind`] of the media the implementation has changed for.
+    #[must_use]
+    pub fn kind(&self) -> MediaKind {
+        self.0.kind.into()
+    }
+
+    /// Indicates whether an encoder of the sent media has fallen back, or a
+    /// decoder of the received one otherwise.
+    #[must_use]
+    pub fn is_encoder(&self) -> bool {
+        self.0.direction == TrackDirection::Send
+    }
+
+    /// Returns [`CodecInfo`] of the hardware implementation used before.
+    #[must_use]
+    pub fn previous(&self) -> CodecInfo {
+        self.0.from.clone().into()
+    }
+
+    /// Returns [`CodecInfo`] of the software implementation used now.
+    #[must_use]
+    pub fn current(&self) -> CodecInfo {
+        self.0.to.clone().into()
+    }
+}
@@ -12,12 +12,27 @@ use crate::{api, connection};
 /// Like all the handles it contains a weak reference to the object that is
 /// managed by Rust, so its methods will fail if a weak reference could not be
 /// upgraded.
+///
+/// Freeing (or garbage collecting) a [`ConnectionHandle`] never affects the
+/// underlying connection, the callbacks set via it, or any other
+/// [`ConnectionHandle`] to the same connection.
+///
+/// Stats reported via a [`ConnectionHandle`] may lag behind for up to a
+/// second. In SFU mode all the connections share a single peer connection, so
+/// they all report the same stats.
 #[wasm_bindgen]
 #[derive(Debug, From)]
 pub struct ConnectionHandle(connection::ConnectionHandle);
 
 #[wasm_bindgen]
 impl ConnectionHandle {
+    /// Returns a new [`ConnectionHandle`] to the same connection, which stays
+    /// usable after this [`ConnectionHandle`] is freed.
+    #[must_use]
+    pub fn clone_handle(&self) -> Self {
+        Self(self.0.clone())
+    }
+
     /// Sets callback, invoked when this [`Connection`] is closed.
     ///
     /// # Errors
@@ -57,9 +72,240 @@ impl ConnectionHandle {
             .map_err(Into::into)
     }
 
+    /// Returns milliseconds spent on every setup phase (`gum`, `create_offer`,
+    /// `set_local_description`, `server_rtt`, `set_remote_description` and
+    /// `ice_connected`) of this [`Connection`] as a plain JS object, or `null`
+    /// if it's not connected yet.
+    ///
+    /// Phases which didn't happen on this side (e.g. `create_offer` on an
+    /// answering side) are `null`.
+    ///
+    /// # Errors
+    ///
+    /// With a [`StateError`] if an underlying object has been disposed, e.g.
+    /// `free` was called on this [`ConnectionHandle`], or on a [`Jason`], or on
+    /// a [`RoomHandle`] that implicitly owns native object behind this
+    /// [`ConnectionHandle`].
+    ///
+    /// [`Connection`]: connection::Connection
+    /// [`Jason`]: api::Jason
+    /// [`RoomHandle`]: api::RoomHandle
+    /// [`StateError`]: crate::api::err::StateError
+    pub fn setup_timings(&self) -> Result<JsValue, JsValue> {
+        let report = self
+            .0
+            .setup_timings()
+            .map_err(api::Error::from)
+            .map_err(JsValue::from)?;
+        report.map_or(Ok(JsValue::NULL), |r| js_sys::JSON::parse(&r.to_json()))
+    }
+
+    /// Returns [`TrafficTotals`] transferred via this [`Connection`] so far,
+    /// accumulated across ICE restarts.
+    ///
+    /// Traffic is accounted only after the first call of this method, but
+    /// includes the bytes transferred before it.
+    ///
+    /// # Errors
+    ///
+    /// With a [`StateError`] if an underlying object has been disposed, e.g.
+    /// `free` was called on this [`ConnectionHandle`], or on a [`Jason`], or on
+    /// a [`RoomHandle`] that implicitly owns native object behind this
+    /// [`ConnectionHandle`].
+    ///
+    /// [`Connection`]: connection::Connection
+    /// [`Jason`]: api::Jason
+    /// [`RoomHandle`]: api::RoomHandle
+    /// [`StateError`]: crate::api::err::StateError
+    /// [`TrafficTotals`]: api::TrafficTotals
+    pub fn traffic_totals(&self) -> Result<api::TrafficTotals, JsValue> {
+        self.0
+            .traffic_totals()
+            .map(api::TrafficTotals::from)
+            .map_err(api::Error::from)
+            .map_err(Into::into)
+    }
+
+    /// Returns [`NegotiationStats`] of this [`Connection`]: number of the
+    /// performed SDP negotiations and duration of the last one.
+    ///
+    /// The stats survive ICE restarts, and are reset only when the underlying
+    /// peer connection is recreated.
+    ///
+    /// # Errors
+    ///
+    /// With a [`StateError`] if an underlying object has been disposed, e.g.
+    /// `free` was called on this [`ConnectionHandle`], or on a [`Jason`], or on
+    /// a [`RoomHandle`] that implicitly owns native object behind this
+    /// [`ConnectionHandle`].
+    ///
+    /// [`Connection`]: connection::Connection
+    /// [`Jason`]: api::Jason
+    /// [`NegotiationStats`]: api::NegotiationStats
+    /// [`RoomHandle`]: api::RoomHandle
+    /// [`StateError`]: crate::api::err::StateError
+    pub fn negotiation_stats(&self) -> Result<api::NegotiationStats, JsValue> {
+        self.0
+            .negotiation_stats()
+            .map(api::NegotiationStats::from)
+            .map_err(api::Error::from)
+            .map_err(Into::into)
+    }
+
+    /// Returns [`QualitySummary`] of this [`Connection`]: total time its
+    /// outbound media has spent in each quality limitation state.
+    ///
+    /// Remains available after this [`Connection`] is closed, summarizing the
+    /// whole call.
+    ///
+    /// [`Connection`]: connection::Connection
+    /// [`QualitySummary`]: api::QualitySummary
+    #[must_use]
+    pub fn quality_summary(&self) -> api::QualitySummary {
+        self.0.quality_summary().into()
+    }
+
+    /// Returns [`IceGatheringInfo`] of the current ICE candidates gathering of
+    /// this [`Connection`]: numbers of the gathered candidates of every type,
+    /// and start and completion timestamps.
+    ///
+    /// Every ICE restart starts a new gathering, so the previous one is
+    /// available via the [`ConnectionHandle::previous_gathering_info()`].
+    ///
+    /// # Errors
+    ///
+    /// With a [`StateError`] if an underlying object has been disposed, e.g.
+    /// `free` was called on this [`ConnectionHandle`], or on a [`Jason`], or on
+    /// a [`RoomHandle`] that implicitly owns native object behind this
+    /// [`ConnectionHandle`].
+    ///
+    /// [`Connection`]: connection::Connection
+    /// [`IceGatheringInfo`]: api::IceGatheringInfo
+    /// [`Jason`]: api::Jason
+    /// [`RoomHandle`]: api::RoomHandle
+    /// [`StateError`]: crate::api::err::StateError
+    pub fn ice_gathering_info(&self) -> Result<api::IceGatheringInfo, JsValue> {
+        self.0
+            .ice_gathering_info()
+            .map(api::IceGatheringInfo::from)
+            .map_err(api::Error::from)
+            .map_err(Into::into)
+    }
+
+    /// Returns [`IceGatheringInfo`] of the ICE candidates gathering of this
+    /// [`Connection`] preceding the last ICE restart, or `undefined` if there
+    /// was no ICE restart.
+    ///
+    /// # Errors
+    ///
+    /// With a [`StateError`] if an underlying object has been disposed, e.g.
+    /// `free` was called on this [`ConnectionHandle`], or on a [`Jason`], or on
+    /// a [`RoomHandle`] that implicitly owns native object behind this
+    /// [`ConnectionHandle`].
+    ///
+    /// [`Connection`]: connection::Connection
+    /// [`IceGatheringInfo`]: api::IceGatheringInfo
+    /// [`Jason`]: api::Jason
+    /// [`RoomHandle`]: api::RoomHandle
+    /// [`StateError`]: crate::api::err::StateError
+    pub fn previous_gathering_info(
+        &self,
+    ) -> Result<Option<api::IceGatheringInfo>, JsValue> {
+        self.0
+            .previous_gathering_info()
+            .map(|info| info.map(api::IceGatheringInfo::from))
+            .map_err(api::Error::from)
+            .map_err(Into::into)
+    }
+
+    /// Returns [`TransportInfo`] of this [`Connection`]: ICE role, DTLS state
+    /// and ciphers of its transport, or `undefined` if they haven't been
+    /// reported yet.
+    ///
+    /// # Errors
+    ///
+    /// With a [`StateError`] if an underlying object has been disposed, e.g.
+    /// `free` was called on this [`ConnectionHandle`], or on a [`Jason`], or on
+    /// a [`RoomHandle`] that implicitly owns native object behind this
+    /// [`ConnectionHandle`].
+    ///
+    /// [`Connection`]: connection::Connection
+    /// [`Jason`]: api::Jason
+    /// [`RoomHandle`]: api::RoomHandle
+    /// [`StateError`]: crate::api::err::StateError
+    /// [`TransportInfo`]: api::TransportInfo
+    pub fn transport_info(
+        &self,
+    ) -> Result<Option<api::TransportInfo>, JsValue> {
+        self.0
+            .transport_info()
+            .map(|info| info.map(api::TransportInfo::from))
+            .map_err(api::Error::from)
+            .map_err(Into::into)
+    }
+
+    /// Returns [`AvSyncInfo`] of this [`Connection`]: estimated drift between
+    /// the received audio and video, and their jitter buffer delays, useful
+    /// for quantifying lip-sync issues.
+    ///
+    /// # Errors
+    ///
+    /// With a [`StateError`] if an underlying object has been disposed, e.g.
+    /// `free` was called on this [`ConnectionHandle`], or on a [`Jason`], or on
+    /// a [`RoomHandle`] that implicitly owns native object behind this
+    /// [`ConnectionHandle`].
+    ///
+    /// [`AvSyncInfo`]: api::AvSyncInfo
+    /// [`Connection`]: connection::Connection
+    /// [`Jason`]: api::Jason
+    /// [`RoomHandle`]: api::RoomHandle
+    /// [`StateError`]: crate::api::err::StateError
+    pub fn av_sync_info(&self) -> Result<api::AvSyncInfo, JsValue> {
+        self.0
+            .av_sync_info()
+            .map(api::AvSyncInfo::from)
+            .map_err(api::Error::from)
+            .map_err(Into::into)
+    }
+
+    /// Returns the current [`MediaExchangeDirections`] of the provided
+    /// [`MediaKind`] and [`MediaSourceKind`] between this and the remote
+    /// `Member`, useful for UI badges like "Bob can't see you".
+    ///
+    /// Changes made via this [`ConnectionHandle`] are reflected right away,
+    /// and are rolled back if a media server doesn't approve them.
+    ///
+    /// # Errors
+    ///
+    /// With a [`StateError`] if an underlying object has been disposed, e.g.
+    /// `free` was called on this [`ConnectionHandle`], or on a [`Jason`], or on
+    /// a [`RoomHandle`] that implicitly owns native object behind this
+    /// [`ConnectionHandle`].
+    ///
+    /// [`Jason`]: api::Jason
+    /// [`MediaExchangeDirections`]: api::MediaExchangeDirections
+    /// [`MediaKind`]: api::MediaKind
+    /// [`MediaSourceKind`]: api::MediaSourceKind
+    /// [`RoomHandle`]: api::RoomHandle
+    /// [`StateError`]: crate::api::err::StateError
+    pub fn media_exchange_state(
+        &self,
+        kind: api::MediaKind,
+        source_kind: api::MediaSourceKind,
+    ) -> Result<api::MediaExchangeDirections, JsValue> {
+        self.0
+            .media_exchange_state(kind.into(), source_kind.into())
+            .map(api::MediaExchangeDirections::from)
+            .map_err(api::Error::from)
+            .map_err(Into::into)
+    }
+
     /// Sets callback, invoked when a new [`RemoteMediaTrack`] is added to this
     /// [`Connection`].
     ///
+    /// The callback is immediately invoked for all the alive
+    /// [`RemoteMediaTrack`]s added already, in the order of their arrival.
+    ///
     /// # Errors
     ///
     /// With a [`StateError`] if an underlying object has been disposed, e.g.
@@ -82,9 +328,41 @@ impl ConnectionHandle {
             .map_err(Into::into)
     }
 
+    /// Sets callback, invoked when a [`RemoteMediaTrack`] of this
+    /// [`Connection`] is gone permanently (its transceiver is stopped or this
+    /// [`Connection`] is closed).
+    ///
+    /// Isn't invoked for temporarily disabled or muted tracks, so it's the
+    /// right place to release any UI related to the track.
+    ///
+    /// # Errors
+    ///
+    /// With a [`StateError`] if an underlying object has been disposed, e.g.
+    /// `free` was called on this [`ConnectionHandle`], or on a [`Jason`], or on
+    /// a [`RoomHandle`] that implicitly owns native object behind this
+    /// [`ConnectionHandle`].
+    ///
+    /// [`Connection`]: connection::Connection
+    /// [`Jason`]: api::Jason
+    /// [`RemoteMediaTrack`]: crate::api::RemoteMediaTrack
+    /// [`RoomHandle`]: api::RoomHandle
+    /// [`StateError`]: crate::api::err::StateError
+    pub fn on_remote_track_removed(
+        &self,
+        cb: js_sys::Function,
+    ) -> Result<(), JsValue> {
+        self.0
+            .on_remote_track_removed(cb.into())
+            .map_err(api::Error::from)
+            .map_err(Into::into)
+    }
+
     /// Sets callback, invoked when connection quality score is updated by a
     /// server.
     ///
+    /// The callback is immediately invoked with the current connection quality
+    /// score, if any.
+    ///
     /// # Errors
     ///
     /// With a [`StateError`] if an underlying object has been disposed, e.g.
@@ -194,4 +472,36 @@ impl ConnectionHandle {
             Ok(JsValue::UNDEFINED)
         })
     }
+
+    /// Enables or disables inbound video in this [`ConnectionHandle`].
+    ///
+    /// Affects only video with the specific [`MediaSourceKind`], if specified.
+    ///
+    /// The preference is applied right away to the video not negotiated yet,
+    /// so no media of it is received until enabled back (e.g. for the
+    /// off-screen participants).
+    ///
+    /// # Errors
+    ///
+    /// With a [`StateError`] if the underlying pointer has been freed.
+    ///
+    /// With a [`MediaStateTransitionException`][0] if an opposite state was
+    /// requested meanwhile or a media server didn't approve this state
+    /// transition.
+    ///
+    /// [`StateError`]: crate::api::err::StateError
+    /// [0]: crate::api::err::MediaStateTransitionException
+    pub fn set_receive_video(
+        &self,
+        enabled: bool,
+        source_kind: Option<api::MediaSourceKind>,
+    ) -> Promise {
+        let fut = self
+            .0
+            .set_receive_video(enabled, source_kind.map(Into::into));
+        future_to_promise(async move {
+            fut.await.map_err(api::Error::from)?;
+            Ok(JsValue::UNDEFINED)
+        })
+    }
 }
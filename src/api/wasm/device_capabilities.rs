@@ -0,0 +1,123 @@
+//! Capabilities of a media input device.
+
+use derive_more::From;
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    api::{FacingMode, MediaKind},
+    media,
+};
+
+/// Range of values supported by a media input device.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, From)]
+pub struct CapabilityRange(media::CapabilityRange);
+
+#[wasm_bindgen]
+impl CapabilityRange {
+    /// Returns the minimum supported value.
+    #[must_use]
+    pub fn min(&self) -> f64 {
+        self.0.min
+    }
+
+    /// Returns the maximum supported value.
+    #[must_use]
+    pub fn max(&self) -> f64 {
+        self.0.max
+    }
+}
+
+/// Capabilities of a media input device, reported before capturing any media
+/// from it.
+///
+/// Getters not applicable to the [`MediaKind`] of the device return nothing.
+#[wasm_bindgen]
+#[derive(Debug, From)]
+pub struct DeviceCapabilities(media::DeviceCapabilities);
+
+#[wasm_bindgen]
+impl DeviceCapabilities {
+    /// Returns [`MediaKind`] of the device.
+    #[must_use]
+    pub fn kind(&self) -> MediaKind {
+        match &self.0 {
+            media::DeviceCapabilities::Audio(_) => media::MediaKind::Audio,
+            media::DeviceCapabilities::Video(_) => media::MediaKind::Video,
+        }
+        .into()
+    }
+
+    /// Returns supported range of a video width in pixels, if reported.
+    #[must_use]
+    pub fn width_range(&self) -> Option<CapabilityRange> {
+        self.video()?.width_range.map(Into::into)
+    }
+
+    /// Returns supported range of a video height in pixels, if reported.
+    #[must_use]
+    pub fn height_range(&self) -> Option<CapabilityRange> {
+        self.video()?.height_range.map(Into::into)
+    }
+
+    /// Returns supported range of a video frame rate in frames per second, if
+    /// reported.
+    #[must_use]
+    pub fn frame_rate_range(&self) -> Option<CapabilityRange> {
+        self.video()?.frame_rate_range.map(Into::into)
+    }
+
+    /// Returns [`FacingMode`]s supported by a video device.
+    #[must_use]
+    pub fn facing_modes(&self) -> js_sys::Array {
+        self.video()
+            .map(|v| v.facing_modes.as_slice())
+            .unwrap_or_default()
+            .iter()
+            .map(|m| JsValue::from(FacingMode::from(*m)))
+            .collect()
+    }
+
+    /// Returns supported range of an audio sample rate in samples per second,
+    /// if reported.
+    #[must_use]
+    pub fn sample_rates(&self) -> Option<CapabilityRange> {
+        self.audio()?.sample_rates.map(Into::into)
+    }
+
+    /// Returns supported range of an audio channel count, if reported.
+    #[must_use]
+    pub fn channel_counts(&self) -> Option<CapabilityRange> {
+        self.audio()?.channel_counts.map(Into::into)
+    }
+
+    /// Returns echo cancellation modes (enabled or not) supported by an audio
+    /// device.
+    #[must_use]
+    pub fn echo_cancellation_modes(&self) -> js_sys::Array {
+        self.audio()
+            .map(|a| a.echo_cancellation_modes.as_slice())
+            .unwrap_or_default()
+            .iter()
+            .map(|m| JsValue::from_bool(*m))
+            .collect()
+    }
+}
+
+impl DeviceCapabilities {
+    /// Returns [`media::AudioCapabilities`] if the device is an audio one.
+    const fn audio(&self) -> Option<&media::AudioCapabilities> {
+        match &self.0 {
+            media::DeviceCapabilities::Audio(audio) => Some(audio),
+            media::DeviceCapabilities::Video(_) => None,
+        }
+    }
+
+    /// Returns [`media::VideoCapabilities`] if the device is a video one.
+    const fn video(&self) -> Option<&media::VideoCapabilities> {
+        match &self.0 {
+            media::DeviceCapabilities::Video(video) => Some(video),
+            media::DeviceCapabilities::Audio(_) => None,
+        }
+    }
+}
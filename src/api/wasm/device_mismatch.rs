@@ -0,0 +1,35 @@
+//! Warning about a local track being captured from another device than the
+//! requested one.
+
+use derive_more::From;
+use wasm_bindgen::prelude::*;
+
+use crate::media;
+
+/// Non-fatal warning about a `LocalMediaTrack` being captured from another
+/// device than the requested one.
+///
+/// Some platforms capture the default device instead of the one requested via
+/// an ideal [deviceId][1] constraint. Such a `LocalMediaTrack` is still used,
+/// once capturing with an exact [deviceId][1] constraint doesn't help.
+///
+/// [1]: https://w3.org/TR/mediacapture-streams#def-constraint-deviceId
+#[wasm_bindgen]
+#[derive(Debug, From)]
+pub struct DeviceMismatch(media::DeviceMismatch);
+
+#[wasm_bindgen]
+impl DeviceMismatch {
+    /// Returns ID of the requested device.
+    #[must_use]
+    pub fn requested_device_id(&self) -> String {
+        self.0.requested.clone()
+    }
+
+    /// Returns ID of the device the `LocalMediaTrack` is actually captured
+    /// from.
+    #[must_use]
+    pub fn actual_device_id(&self) -> String {
+        self.0.actual.clone()
+    }
+}
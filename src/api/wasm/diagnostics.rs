@@ -0,0 +1,200 @@
+//! Pre-call diagnostics of a network environment.
+
+use std::time::Duration;
+
+use derive_more::From;
+use js_sys::{Array, Promise};
+use medea_client_api_proto::IceServer;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::future_to_promise;
+
+use crate::{
+    api::{err::FormatException, Error, IceCandidateFilter},
+    platform,
+};
+
+/// Pre-call diagnostics of a network environment.
+#[wasm_bindgen]
+#[derive(Clone, Debug, Default)]
+pub struct Diagnostics {
+    /// [`platform::IceCandidateFilter`] applied to the gathered candidates.
+    ice_candidate_filter: platform::IceCandidateFilter,
+}
+
+#[wasm_bindgen]
+impl Diagnostics {
+    /// Sets [`IceCandidateFilter`] to be applied to the candidates gathered by
+    /// [`Diagnostics::probe_ice_servers()`], so the effect of the one used in
+    /// a `Room` can be previewed.
+    ///
+    /// The filtered out candidates are counted separately in the
+    /// [`IceProbeReport`].
+    pub fn set_ice_candidate_filter(&mut self, filter: &IceCandidateFilter) {
+        self.ice_candidate_filter = filter.clone().into();
+    }
+
+    /// Probes reachability of the provided [ICE] servers, gathering candidates
+    /// from each of their URLs for no longer than the provided `timeout_ms`.
+    ///
+    /// The provided `servers` should be an array of [RTCIceServer][1]-like
+    /// objects (`{ urls, username, credential }`).
+    ///
+    /// Resolves with an [`IceProbeReport`] once gathering is finished.
+    ///
+    /// # Errors
+    ///
+    /// With a [`FormatException`] if the provided `servers` have bad format.
+    ///
+    /// [`FormatException`]: crate::api::err::FormatException
+    /// [ICE]: https://webrtcglossary.com/ice
+    /// [1]: https://w3.org/TR/webrtc#dom-rtciceserver
+    pub fn probe_ice_servers(
+        &self,
+        servers: JsValue,
+        timeout_ms: u32,
+    ) -> Promise {
+        let servers = parse_ice_servers(&servers).map_err(Error::from);
+        let filter = self.ice_candidate_filter.clone();
+
+        future_to_promise(async move {
+            let report = platform::probe_ice_servers(
+                servers?,
+                Duration::from_millis(timeout_ms.into()),
+                &filter,
+            )
+            .await;
+            Ok(IceProbeReport::from(report).into())
+        })
+    }
+}
+
+/// Parses [`IceServer`]s from the provided JS array of [RTCIceServer][1]-like
+/// objects.
+///
+/// # Errors
+///
+/// With a [`FormatException`] if the provided `servers` have bad format.
+///
+/// [1]: https://w3.org/TR/webrtc#dom-rtciceserver
+fn parse_ice_servers(
+    servers: &JsValue,
+) -> Result<Vec<IceServer>, FormatException> {
+    if !Array::is_array(servers) {
+        return Err(FormatException::new("ICE servers should be an array"));
+    }
+    Array::from(servers)
+        .iter()
+        .map(|server| {
+            let urls = platform::get_property_by_name(&server, "urls", |v| {
+                if let Some(url) = v.as_string() {
+                    Some(vec![url])
+                } else if Array::is_array(&v) {
+                    Array::from(&v).iter().map(|u| u.as_string()).collect()
+                } else {
+                    None
+                }
+            })
+            .filter(|urls| !urls.is_empty())
+            .ok_or_else(|| {
+                FormatException::new("ICE server should have `urls`")
+            })?;
+            Ok(IceServer {
+                urls,
+                username: platform::get_property_by_name(
+                    &server,
+                    "username",
+                    |v| v.as_string(),
+                ),
+                credential: platform::get_property_by_name(
+                    &server,
+                    "credential",
+                    |v| v.as_string(),
+                ),
+            })
+        })
+        .collect()
+}
+
+/// Summary of probing [ICE] servers.
+///
+/// [ICE]: https://webrtcglossary.com/ice
+#[wasm_bindgen]
+#[derive(Debug, From)]
+pub struct IceProbeReport(platform::IceProbeReport);
+
+#[wasm_bindgen]
+impl IceProbeReport {
+    /// Returns number of distinct gathered `host` candidates.
+    #[must_use]
+    pub fn host_candidates(&self) -> usize {
+        self.0.host_candidates
+    }
+
+    /// Returns number of distinct gathered `srflx` candidates.
+    #[must_use]
+    pub fn srflx_candidates(&self) -> usize {
+        self.0.srflx_candidates
+    }
+
+    /// Returns number of distinct gathered `relay` candidates.
+    #[must_use]
+    pub fn relay_candidates(&self) -> usize {
+        self.0.relay_candidates
+    }
+
+    /// Returns number of distinct gathered candidates filtered out by the
+    /// [`IceCandidateFilter`], which aren't counted as candidates of any
+    /// type.
+    #[must_use]
+    pub fn filtered_candidates(&self) -> usize {
+        self.0.filtered_candidates
+    }
+
+    /// Returns [`IceServerProbe`]s of every probed [ICE] server URL.
+    ///
+    /// [ICE]: https://webrtcglossary.com/ice
+    #[must_use]
+    pub fn servers(&self) -> Array {
+        self.0
+            .servers
+            .iter()
+            .cloned()
+            .map(|p| JsValue::from(IceServerProbe::from(p)))
+            .collect()
+    }
+
+    /// Returns duration of the whole candidates gathering in milliseconds.
+    #[must_use]
+    pub fn gathering_duration_ms(&self) -> f64 {
+        self.0.gathering_duration.as_secs_f64() * 1000.0
+    }
+}
+
+/// Result of probing a single [ICE] server URL.
+///
+/// [ICE]: https://webrtcglossary.com/ice
+#[wasm_bindgen]
+#[derive(Debug, From)]
+pub struct IceServerProbe(platform::IceServerProbe);
+
+#[wasm_bindgen]
+impl IceServerProbe {
+    /// Returns the probed URL.
+    #[must_use]
+    pub fn url(&self) -> String {
+        self.0.url.clone()
+    }
+
+    /// Indicates whether a `srflx` (for a STUN server) or a `relay` (for a
+    /// TURN server) candidate has been gathered from the probed URL in time.
+    #[must_use]
+    pub fn is_reachable(&self) -> bool {
+        self.0.is_reachable
+    }
+
+    /// Returns an error occurred while probing the URL, if any.
+    #[must_use]
+    pub fn error(&self) -> Option<String> {
+        self.0.error.clone()
+    }
+}
@@ -0,0 +1,44 @@
+//! Options of a screen capturing request shaping the screen picker.
+
+use derive_more::From;
+use wasm_bindgen::prelude::*;
+
+use crate::{api::CaptureInclusion, media};
+
+/// Options of a [getDisplayMedia()][1] request a display `LocalMediaTrack`
+/// was captured with.
+///
+/// The options are passed to the browser as is, and ignored by the ones not
+/// supporting them.
+///
+/// [1]: https://w3.org/TR/screen-capture/#dom-mediadevices-getdisplaymedia
+#[wasm_bindgen]
+#[derive(Debug, From)]
+pub struct DisplayCaptureOptions(media::DisplayCaptureOptions);
+
+#[wasm_bindgen]
+impl DisplayCaptureOptions {
+    /// Returns the `preferCurrentTab` option, if it was set.
+    #[must_use]
+    pub fn prefer_current_tab(&self) -> Option<bool> {
+        self.0.prefer_current_tab
+    }
+
+    /// Returns the `selfBrowserSurface` option, if it was set.
+    #[must_use]
+    pub fn self_browser_surface(&self) -> Option<CaptureInclusion> {
+        self.0.self_browser_surface.map(Into::into)
+    }
+
+    /// Returns the `surfaceSwitching` option, if it was set.
+    #[must_use]
+    pub fn surface_switching(&self) -> Option<CaptureInclusion> {
+        self.0.surface_switching.map(Into::into)
+    }
+
+    /// Returns the `systemAudio` option, if it was set.
+    #[must_use]
+    pub fn system_audio(&self) -> Option<CaptureInclusion> {
+        self.0.system_audio.map(Into::into)
+    }
+}
@@ -9,10 +9,11 @@ use wasm_bindgen::{
 };
 
 use crate::api::err::{
-    EnumerateDevicesException, FormatException, InternalException,
-    InvalidOutputAudioDeviceIdException, LocalMediaInitException,
-    MediaSettingsUpdateException, MediaStateTransitionException,
-    MicVolumeException, RpcClientException, StateError,
+    AbortedException, EnumerateDevicesException, FormatException,
+    InternalException, InvalidOutputAudioDeviceIdException,
+    LocalMediaInitException, MediaSettingsUpdateException,
+    MediaStateTransitionException, MicVolumeException, NotSupportedException,
+    RpcClientException, RtcPeerConnectionException, StateError,
 };
 
 /// Wrapper around [`JsValue`] which represents a JS error.
@@ -66,3 +67,6 @@ impl_from_into_jsval_for_error!(MediaStateTransitionException);
 impl_from_into_jsval_for_error!(MediaSettingsUpdateException);
 impl_from_into_jsval_for_error!(InvalidOutputAudioDeviceIdException);
 impl_from_into_jsval_for_error!(MicVolumeException);
+impl_from_into_jsval_for_error!(NotSupportedException);
+impl_from_into_jsval_for_error!(AbortedException);
+impl_from_into_jsval_for_error!(RtcPeerConnectionException);
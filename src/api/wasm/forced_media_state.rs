@@ -0,0 +1,47 @@
+//! Media state of the local media forced by a media server.
+
+use derive_more::From;
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    api::{LocalMediaState, MediaKind, MediaSourceKind},
+    peer,
+};
+
+/// State of the local media forced by a media server (by a moderator, for
+/// example), rather than changed via a [`RoomHandle`].
+///
+/// [`RoomHandle`]: crate::api::RoomHandle
+#[wasm_bindgen]
+#[derive(Debug, From)]
+pub struct ForcedMediaState(peer::ForcedMediaState);
+
+#[wasm_bindgen]
+impl ForcedMediaState {
+    /// Returns [`MediaKind`] of the forced media.
+    #[must_use]
+    pub fn kind(&self) -> MediaKind {
+        self.0.kind.into()
+    }
+
+    /// Returns [`MediaSourceKind`] of the forced media.
+    #[must_use]
+    pub fn media_source_kind(&self) -> MediaSourceKind {
+        self.0.source_kind.into()
+    }
+
+    /// Returns the [`LocalMediaState`] forced by the media server.
+    #[must_use]
+    pub fn state(&self) -> LocalMediaState {
+        self.0.state.into()
+    }
+
+    /// Indicates whether the media state is forced by a media server, which is
+    /// always `true`.
+    #[allow(clippy::missing_const_for_fn)] // required by `wasm_bindgen`
+    #[allow(clippy::unused_self)] // kept as a method for API consistency
+    #[must_use]
+    pub fn by_server(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,163 @@
+//! Filtering of [ICE candidates][1] exchanged with remote peers.
+//!
+//! [1]: https://w3.org/TR/webrtc#rtcicecandidate-interface
+
+use std::rc::Rc;
+
+use derive_more::{From, Into};
+use wasm_bindgen::prelude::*;
+
+use crate::{api::IceCandidateType, platform};
+
+/// Filter of [ICE candidates][1] exchanged with remote peers.
+///
+/// Malformed candidates are never filtered out, since their attributes are
+/// unknown.
+///
+/// [1]: https://w3.org/TR/webrtc#rtcicecandidate-interface
+#[wasm_bindgen]
+#[derive(Clone, Debug, From, Into)]
+pub struct IceCandidateFilter(platform::IceCandidateFilter);
+
+#[wasm_bindgen]
+impl IceCandidateFilter {
+    /// Creates a new [`IceCandidateFilter`] allowing all the candidates.
+    #[must_use]
+    pub fn all() -> Self {
+        platform::IceCandidateFilter::All.into()
+    }
+
+    /// Creates a new [`IceCandidateFilter`] filtering out the candidates with
+    /// [mDNS] hostnames.
+    ///
+    /// [mDNS]: https://tools.ietf.org/html/rfc6762
+    #[must_use]
+    pub fn no_mdns() -> Self {
+        platform::IceCandidateFilter::NoMdns.into()
+    }
+
+    /// Creates a new [`IceCandidateFilter`] filtering out the candidates with
+    /// link-local addresses.
+    #[must_use]
+    pub fn no_link_local() -> Self {
+        platform::IceCandidateFilter::NoLinkLocal.into()
+    }
+
+    /// Creates a new [`IceCandidateFilter`] filtering out the candidates with
+    /// IPv6 addresses.
+    #[must_use]
+    pub fn no_ipv6() -> Self {
+        platform::IceCandidateFilter::NoIpv6.into()
+    }
+
+    /// Creates a new [`IceCandidateFilter`] allowing only the
+    /// [`IceCandidateType::Relay`] candidates.
+    #[must_use]
+    pub fn relay_only() -> Self {
+        platform::IceCandidateFilter::RelayOnly.into()
+    }
+
+    /// Creates a new [`IceCandidateFilter`] allowing only the candidates for
+    /// which the provided `predicate` returns a truthy value.
+    ///
+    /// The `predicate` is invoked with the [`IceCandidateAttributes`] of a
+    /// candidate. Candidates are allowed if the `predicate` throws.
+    #[must_use]
+    pub fn custom(predicate: js_sys::Function) -> Self {
+        platform::IceCandidateFilter::Custom(Rc::new(move |candidate| {
+            let candidate = IceCandidateAttributes::from(candidate.clone());
+            predicate
+                .call1(&JsValue::NULL, &candidate.into())
+                .map_or(true, |allowed| allowed.is_truthy())
+        }))
+        .into()
+    }
+}
+
+/// Attributes of an [ICE candidate][1] provided to a custom
+/// [`IceCandidateFilter`].
+///
+/// [1]: https://w3.org/TR/webrtc#rtcicecandidate-interface
+#[wasm_bindgen]
+#[derive(Debug, From)]
+pub struct IceCandidateAttributes(platform::IceCandidateAttributes);
+
+#[wasm_bindgen]
+impl IceCandidateAttributes {
+    /// Returns foundation of the candidate.
+    #[must_use]
+    pub fn foundation(&self) -> String {
+        self.0.foundation.clone()
+    }
+
+    /// Returns ID of the component (`1` for RTP and `2` for RTCP) the
+    /// candidate is gathered for.
+    #[must_use]
+    pub fn component(&self) -> u16 {
+        self.0.component
+    }
+
+    /// Returns lowercased transport protocol of the candidate.
+    #[must_use]
+    pub fn protocol(&self) -> String {
+        self.0.protocol.clone()
+    }
+
+    /// Returns priority of the candidate.
+    #[must_use]
+    pub fn priority(&self) -> u32 {
+        self.0.priority
+    }
+
+    /// Returns IP address or [mDNS] hostname of the candidate.
+    ///
+    /// [mDNS]: https://tools.ietf.org/html/rfc6762
+    #[must_use]
+    pub fn address(&self) -> String {
+        self.0.address.clone()
+    }
+
+    /// Returns port of the candidate.
+    #[must_use]
+    pub fn port(&self) -> u16 {
+        self.0.port
+    }
+
+    /// Returns [`IceCandidateType`] of the candidate.
+    #[must_use]
+    pub fn candidate_type(&self) -> IceCandidateType {
+        self.0.kind.into()
+    }
+
+    /// Returns address the candidate is derived from, if any.
+    #[must_use]
+    pub fn related_address(&self) -> Option<String> {
+        self.0.related_address.clone()
+    }
+
+    /// Returns port the candidate is derived from, if any.
+    #[must_use]
+    pub fn related_port(&self) -> Option<u16> {
+        self.0.related_port
+    }
+
+    /// Indicates whether the address of the candidate is an [mDNS] hostname.
+    ///
+    /// [mDNS]: https://tools.ietf.org/html/rfc6762
+    #[must_use]
+    pub fn is_mdns(&self) -> bool {
+        self.0.is_mdns()
+    }
+
+    /// Indicates whether the address of the candidate is a link-local one.
+    #[must_use]
+    pub fn is_link_local(&self) -> bool {
+        self.0.is_link_local()
+    }
+
+    /// Indicates whether the address of the candidate is an IPv6 one.
+    #[must_use]
+    pub fn is_ipv6(&self) -> bool {
+        self.0.is_ipv6()
+    }
+}
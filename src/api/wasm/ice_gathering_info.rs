@@ -0,0 +1,61 @@
+//! Info about ICE candidates gathering of a connection with a specific remote
+//! `Member`.
+
+use derive_more::From;
+use wasm_bindgen::prelude::*;
+
+use crate::platform;
+
+/// Info about a single generation of ICE candidates gathering of a connection
+/// with a specific remote `Member`.
+#[wasm_bindgen]
+#[derive(Debug, From)]
+pub struct IceGatheringInfo(platform::IceGatheringInfo);
+
+#[wasm_bindgen]
+impl IceGatheringInfo {
+    /// Returns number of the gathered `host` candidates.
+    #[must_use]
+    pub fn host_candidates(&self) -> u32 {
+        self.0.host_candidates
+    }
+
+    /// Returns number of the gathered `srflx` (server reflexive) candidates.
+    #[must_use]
+    pub fn srflx_candidates(&self) -> u32 {
+        self.0.srflx_candidates
+    }
+
+    /// Returns number of the gathered `prflx` (peer reflexive) candidates.
+    #[must_use]
+    pub fn prflx_candidates(&self) -> u32 {
+        self.0.prflx_candidates
+    }
+
+    /// Returns number of the gathered `relay` candidates.
+    #[must_use]
+    pub fn relay_candidates(&self) -> u32 {
+        self.0.relay_candidates
+    }
+
+    /// Returns UNIX timestamp (in milliseconds) the gathering has started at,
+    /// if it has.
+    #[must_use]
+    pub fn started_at(&self) -> Option<f64> {
+        self.0.started_at.map(|t| t.as_secs_f64() * 1000.0)
+    }
+
+    /// Returns UNIX timestamp (in milliseconds) the gathering has completed
+    /// at, if it has.
+    #[must_use]
+    pub fn completed_at(&self) -> Option<f64> {
+        self.0.completed_at.map(|t| t.as_secs_f64() * 1000.0)
+    }
+
+    /// Returns duration of the gathering in milliseconds, if it has started
+    /// and completed.
+    #[must_use]
+    pub fn duration_ms(&self) -> Option<f64> {
+        self.0.duration().map(|d| d.as_secs_f64() * 1000.0)
+    }
+}
@@ -1,11 +1,17 @@
 //! General JS side library interface.
 
+use std::rc::Rc;
+
 use derive_more::From;
 use wasm_bindgen::prelude::*;
 
 use crate::{
-    api::{MediaManagerHandle, RoomHandle},
-    jason,
+    api::{
+        Diagnostics, Error, JasonConfig, MediaManagerHandle, RoomHandle,
+        WebRtcSupport,
+    },
+    jason, platform,
+    room::RoomSnapshot,
 };
 
 /// General JS side library interface.
@@ -25,18 +31,80 @@ impl Jason {
         Self(jason::Jason::new())
     }
 
+    /// Instantiates a new [`Jason`] interface configured with the provided
+    /// [`JasonConfig`].
+    #[must_use]
+    pub fn with_config(config: &JasonConfig) -> Self {
+        Self(jason::Jason::with_config((*config).into()))
+    }
+
+    /// Instantiates a new [`Jason`] interface connecting to a media server
+    /// via sockets created with the provided JS class (or constructor
+    /// function) instead of the [WebSocket].
+    ///
+    /// The provided `constructor` is called with a server URL and should
+    /// return an object implementing the [WebSocket] interface, which allows
+    /// to mock a media server in tests.
+    ///
+    /// [WebSocket]: https://developer.mozilla.org/docs/Web/API/WebSocket
+    #[must_use]
+    pub fn with_transport_factory(constructor: js_sys::Function) -> Self {
+        Self(jason::Jason::with_transport_factory(move || {
+            Rc::new(platform::WebSocketRpcTransport::with_constructor(
+                constructor.clone(),
+            ))
+        }))
+    }
+
+    /// Returns a [`WebRtcSupport`] report of the current browser, useful to
+    /// be checked before joining a `Room`.
+    ///
+    /// Detected on the first call and cached afterwards.
+    #[must_use]
+    pub fn is_supported() -> WebRtcSupport {
+        platform::webrtc_available().into()
+    }
+
     /// Creates a new `Room` and returns its [`RoomHandle`].
     #[must_use]
     pub fn init_room(&self) -> RoomHandle {
         self.0.init_room().into()
     }
 
+    /// Creates a new `Room` with the media states intended in the provided
+    /// JSON snapshot (exported via [`RoomHandle::export_state()`])
+    /// pre-applied, and returns its [`RoomHandle`].
+    ///
+    /// The returned [`RoomHandle`] should be joined to the snapshot's
+    /// `room_url` with fresh credentials.
+    ///
+    /// # Errors
+    ///
+    /// With a [`FormatException`] if the provided snapshot is malformed.
+    ///
+    /// [`FormatException`]: crate::api::err::FormatException
+    pub fn restore_room(&self, snapshot: &str) -> Result<RoomHandle, JsValue> {
+        let snapshot = snapshot
+            .parse::<RoomSnapshot>()
+            .map_err(Error::from)
+            .map_err(JsValue::from)?;
+        Ok(self.0.restore_room(&snapshot).into())
+    }
+
     /// Returns a [`MediaManagerHandle`].
     #[must_use]
     pub fn media_manager(&self) -> MediaManagerHandle {
         self.0.media_manager().into()
     }
 
+    /// Returns [`Diagnostics`] of a network environment, useful to be run
+    /// before joining a `Room`.
+    #[allow(clippy::unused_self)] // kept as a method for API consistency
+    #[must_use]
+    pub fn diagnostics(&self) -> Diagnostics {
+        Diagnostics::default()
+    }
+
     /// Closes the provided [`RoomHandle`].
     pub fn close_room(&self, room_to_delete: RoomHandle) {
         self.0.close_room(room_to_delete.into());
@@ -0,0 +1,56 @@
+//! Options of a [`Jason`].
+//!
+//! [`Jason`]: crate::api::Jason
+
+#![allow(clippy::new_without_default)]
+
+use derive_more::{From, Into};
+use wasm_bindgen::prelude::*;
+
+use crate::jason;
+
+/// Options of a [`Jason`].
+///
+/// [`Jason`]: crate::api::Jason
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, From, Into)]
+pub struct JasonConfig(jason::JasonConfig);
+
+#[wasm_bindgen]
+impl JasonConfig {
+    /// Creates new [`JasonConfig`] with the default options.
+    #[must_use]
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        jason::JasonConfig::default().into()
+    }
+
+    /// Sets whether reconnections to a media server should be performed
+    /// immediately once the network becomes available (`online` event) or
+    /// the page becomes visible (`visibilitychange` event), and be paused
+    /// while the network is unavailable (`offline` event).
+    ///
+    /// Enabled by default.
+    pub fn reconnect_on_network_events(&mut self, enabled: bool) {
+        self.0.reconnect_on_network_events = enabled;
+    }
+
+    /// Sets the maximum number of the lifecycle events retained by every
+    /// `Room` for the `RoomHandle.dump_events()`.
+    ///
+    /// Defaults to `500` events. Can be changed for a specific `Room` via
+    /// `RoomHandle.set_event_log_capacity()`.
+    pub fn event_log_capacity(&mut self, capacity: u32) {
+        self.0.event_log_capacity =
+            usize::try_from(capacity).unwrap_or(usize::MAX);
+    }
+
+    /// Sets whether the periodic stats collection of every `Room` should be
+    /// paused while the page is hidden (`visibilitychange` event), saving CPU
+    /// in background tabs.
+    ///
+    /// Disabled by default.
+    pub fn pause_stats_when_hidden(&mut self, enabled: bool) {
+        self.0.pause_stats_when_hidden = enabled;
+    }
+}
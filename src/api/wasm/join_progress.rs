@@ -0,0 +1,65 @@
+//! Progress of joining a `Room`.
+
+use derive_more::{Display, From};
+use wasm_bindgen::prelude::*;
+
+use crate::peer;
+
+/// Phase of joining a `Room`.
+///
+/// Phases are always reported in the order they're declared in.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, Display, Eq, PartialEq)]
+pub enum JoinPhase {
+    /// Connecting to a media server and authorizing on it.
+    ConnectingSignaling,
+
+    /// Authorized on a media server and waiting for it to start a call.
+    Authorized,
+
+    /// Acquiring local media.
+    CapturingMedia,
+
+    /// Negotiating SDP with a remote `Member`.
+    Negotiating,
+
+    /// Waiting for ICE to connect.
+    ConnectingIce,
+
+    /// Media is flowing.
+    Joined,
+}
+
+impl From<peer::JoinPhase> for JoinPhase {
+    fn from(that: peer::JoinPhase) -> Self {
+        match that {
+            peer::JoinPhase::ConnectingSignaling => Self::ConnectingSignaling,
+            peer::JoinPhase::Authorized => Self::Authorized,
+            peer::JoinPhase::CapturingMedia => Self::CapturingMedia,
+            peer::JoinPhase::Negotiating => Self::Negotiating,
+            peer::JoinPhase::ConnectingIce => Self::ConnectingIce,
+            peer::JoinPhase::Joined => Self::Joined,
+        }
+    }
+}
+
+/// [`JoinPhase`] reached while joining a `Room`.
+#[wasm_bindgen]
+#[derive(Debug, From)]
+pub struct JoinProgressUpdate(peer::JoinProgressUpdate);
+
+#[wasm_bindgen]
+impl JoinProgressUpdate {
+    /// Returns the reached [`JoinPhase`].
+    #[must_use]
+    pub fn phase(&self) -> JoinPhase {
+        self.0.phase.into()
+    }
+
+    /// Returns time the [`JoinPhase`] has been reached at, in milliseconds
+    /// since the Unix epoch.
+    #[must_use]
+    pub fn timestamp(&self) -> f64 {
+        self.0.at.as_secs_f64() * 1000.0
+    }
+}
@@ -2,14 +2,20 @@
 //!
 //! [1]: https://w3.org/TR/mediacapture-streams#dom-mediastreamtrack
 
+use std::time::Duration;
+
 use derive_more::From;
 use js_sys::Promise;
 use wasm_bindgen::prelude::*;
 use wasm_bindgen_futures::future_to_promise;
 
 use crate::{
-    api::{MediaKind, MediaSourceKind},
-    media::track::local,
+    api::{
+        err::FormatException, CodecInfo, ContentHint, DeviceMismatch,
+        DisplayCaptureOptions, DisplaySurface, Error, MediaKind,
+        MediaSourceKind,
+    },
+    media::{track::local, VideoRotation, VideoTransform},
 };
 
 /// Wrapper around a local [MediaStreamTrack][1].
@@ -59,4 +65,185 @@ impl LocalMediaTrack {
     pub fn media_source_kind(&self) -> MediaSourceKind {
         self.0.media_source_kind().into()
     }
+
+    /// Returns a [`DisplaySurface`] captured by this [`LocalMediaTrack`], or
+    /// [`None`] if it isn't a [`MediaSourceKind::Display`] one.
+    #[must_use]
+    pub fn display_surface(&self) -> Option<DisplaySurface> {
+        self.0.display_surface().map(Into::into)
+    }
+
+    /// Returns a [`ContentHint`] of this [`LocalMediaTrack`] accepted by the
+    /// browser, if any.
+    #[must_use]
+    pub fn content_hint(&self) -> Option<ContentHint> {
+        self.0.content_hint().map(Into::into)
+    }
+
+    /// Sets the provided [`ContentHint`] to this [`LocalMediaTrack`].
+    ///
+    /// The browser ignores the [`ContentHint`]s not applicable to the
+    /// [`MediaKind`] of this [`LocalMediaTrack`].
+    pub fn set_content_hint(&self, hint: ContentHint) {
+        self.0.set_content_hint(hint.into());
+    }
+
+    /// Returns [`DisplayCaptureOptions`] this [`LocalMediaTrack`] was
+    /// requested with, or [`None`] if it isn't a [`MediaSourceKind::Display`]
+    /// one.
+    #[must_use]
+    pub fn display_capture_options(&self) -> Option<DisplayCaptureOptions> {
+        self.0.display_capture_options().map(Into::into)
+    }
+
+    /// Sets a callback to invoke when media exchange of this
+    /// [`LocalMediaTrack`] is enabled in a `Room`.
+    ///
+    /// The callback is invoked before the corresponding `RoomHandle` method
+    /// call resolves.
+    pub fn on_enabled(&self, cb: js_sys::Function) {
+        self.0.on_enabled(cb.into());
+    }
+
+    /// Sets a callback to invoke when media exchange of this
+    /// [`LocalMediaTrack`] is disabled in a `Room`, either via a `RoomHandle`
+    /// or by a media server.
+    ///
+    /// The callback is invoked before the corresponding `RoomHandle` method
+    /// call resolves.
+    pub fn on_disabled(&self, cb: js_sys::Function) {
+        self.0.on_disabled(cb.into());
+    }
+
+    /// Indicates whether this [`LocalMediaTrack`] is being published to some
+    /// `Room`.
+    ///
+    /// Tracks obtained via `MediaManagerHandle.init_local_tracks()` (e.g. for
+    /// a preview) are adopted by a `Room` on joining if their constraints fit,
+    /// so no new capture is performed.
+    #[must_use]
+    pub fn is_published(&self) -> bool {
+        self.0.is_published()
+    }
+
+    /// Returns a [`DeviceMismatch`] warning if this [`LocalMediaTrack`] is
+    /// captured from another device than the requested one.
+    #[must_use]
+    pub fn device_mismatch(&self) -> Option<DeviceMismatch> {
+        self.0.device_mismatch().map(Into::into)
+    }
+
+    /// Returns [`CodecInfo`] of the encoder last reported by the browser for
+    /// this [`LocalMediaTrack`] being published, if any.
+    ///
+    /// Is updated whenever the browser switches to another encoder
+    /// implementation.
+    #[must_use]
+    pub fn encoder_info(&self) -> Option<CodecInfo> {
+        self.0.encoder_info().map(Into::into)
+    }
+
+    /// Forces this [`LocalMediaTrack`] to be encoded as a new keyframe by all
+    /// the senders it's being published with.
+    ///
+    /// # Errors
+    ///
+    /// With a [`NotSupportedException`] if this [`LocalMediaTrack`] isn't a
+    /// video one.
+    ///
+    /// With an [`InternalException`] if a keyframe request fails.
+    ///
+    /// [`InternalException`]: crate::api::err::InternalException
+    /// [`NotSupportedException`]: crate::api::err::NotSupportedException
+    pub fn request_keyframe(&self) -> Promise {
+        let this = self.0.clone();
+        future_to_promise(async move {
+            this.request_keyframe()
+                .await
+                .map(|()| JsValue::UNDEFINED)
+                .map_err(Error::from)
+                .map_err(Into::into)
+        })
+    }
+
+    /// Rotates this [`LocalMediaTrack`] clockwise by the provided
+    /// `rotation_deg` and mirrors it horizontally if `mirror` is `true`, as
+    /// it's being sent to the remote `Member`s.
+    ///
+    /// Replaces the previously applied transformation, so zero `rotation_deg`
+    /// without `mirror` restores the original video being sent. Local preview
+    /// of this [`LocalMediaTrack`] stays untransformed.
+    ///
+    /// # Errors
+    ///
+    /// With a [`FormatException`] if the provided `rotation_deg` isn't a
+    /// multiple of 90.
+    ///
+    /// With a [`NotSupportedException`] if this [`LocalMediaTrack`] isn't a
+    /// video one, or the platform doesn't allow processing tracks.
+    ///
+    /// With an [`InternalException`] if failed to apply the transformation.
+    ///
+    /// [`FormatException`]: crate::api::err::FormatException
+    /// [`InternalException`]: crate::api::err::InternalException
+    /// [`NotSupportedException`]: crate::api::err::NotSupportedException
+    pub fn set_transform(&self, rotation_deg: u16, mirror: bool) -> Promise {
+        let this = self.0.clone();
+        future_to_promise(async move {
+            let rotation = VideoRotation::from_degrees(rotation_deg)
+                .ok_or_else(|| {
+                    FormatException::new(format!(
+                        "Rotation should be a multiple of 90 degrees, but got \
+                         {rotation_deg}",
+                    ))
+                })?;
+            this.set_transform(VideoTransform { rotation, mirror })
+                .await
+                .map(|()| JsValue::UNDEFINED)
+                .map_err(Error::from)
+                .map_err(Into::into)
+        })
+    }
+
+    /// Enables silence detection of this [`LocalMediaTrack`], replacing the
+    /// previously enabled one, if any.
+    ///
+    /// The provided callback is invoked with `true` once the captured audio
+    /// level stays below the provided `threshold_db` (in [dBFS][1]) for
+    /// `duration_ms` milliseconds, and with `false` once the sound resumes.
+    ///
+    /// Silence detection is stopped by
+    /// [`LocalMediaTrack::disable_silence_detection()`] or once this
+    /// [`LocalMediaTrack`] is freed.
+    ///
+    /// # Errors
+    ///
+    /// With a [`NotSupportedException`] if this [`LocalMediaTrack`] isn't an
+    /// audio one.
+    ///
+    /// With an [`InternalException`] if failed to start metering audio level.
+    ///
+    /// [`InternalException`]: crate::api::err::InternalException
+    /// [`NotSupportedException`]: crate::api::err::NotSupportedException
+    /// [1]: https://en.wikipedia.org/wiki/DBFS
+    pub fn enable_silence_detection(
+        &self,
+        threshold_db: f64,
+        duration_ms: u32,
+        cb: js_sys::Function,
+    ) -> Result<(), JsValue> {
+        self.0
+            .enable_silence_detection(
+                threshold_db,
+                Duration::from_millis(duration_ms.into()),
+                cb.into(),
+            )
+            .map_err(Error::from)
+            .map_err(Into::into)
+    }
+
+    /// Disables silence detection of this [`LocalMediaTrack`], if any.
+    pub fn disable_silence_detection(&self) {
+        self.0.disable_silence_detection();
+    }
 }
@@ -41,6 +41,15 @@ impl MediaDeviceDetails {
         self.0.label()
     }
 
+    /// Indicates whether the label of the represented device is redacted
+    /// (empty), which happens until a media access permission is granted.
+    ///
+    /// Allows to offer a user granting the access to see device names.
+    #[must_use]
+    pub fn is_label_redacted(&self) -> bool {
+        self.0.is_label_redacted()
+    }
+
     /// Returns a group identifier of the represented device.
     ///
     /// Two devices have the same group identifier if they belong to the same
@@ -0,0 +1,41 @@
+//! Difference between two lists of [MediaDeviceInfo][1]s.
+//!
+//! [1]: https://w3.org/TR/mediacapture-streams#device-info
+
+use derive_more::From;
+use wasm_bindgen::prelude::*;
+
+use crate::{api::MediaDeviceDetails, media, platform};
+
+/// Difference between the previously known and the current lists of
+/// [MediaDeviceInfo][1]s, provided on a `devicechange` event.
+///
+/// [1]: https://w3.org/TR/mediacapture-streams#device-info
+#[wasm_bindgen]
+#[derive(Debug, From)]
+pub struct MediaDevicesDiff(media::MediaDevicesDiff);
+
+#[wasm_bindgen]
+impl MediaDevicesDiff {
+    /// Returns [`MediaDeviceDetails`] of the appeared devices.
+    #[must_use]
+    pub fn added(&self) -> js_sys::Array {
+        to_array(&self.0.added)
+    }
+
+    /// Returns [`MediaDeviceDetails`] of the disappeared devices.
+    #[must_use]
+    pub fn removed(&self) -> js_sys::Array {
+        to_array(&self.0.removed)
+    }
+}
+
+/// Converts the provided [`platform::MediaDeviceInfo`]s into a JS array of
+/// [`MediaDeviceDetails`].
+fn to_array(devices: &[platform::MediaDeviceInfo]) -> js_sys::Array {
+    devices
+        .iter()
+        .cloned()
+        .map(|info| JsValue::from(MediaDeviceDetails::from(info)))
+        .collect()
+}
@@ -0,0 +1,42 @@
+//! Directions of the media exchange between this and a remote `Member`.
+
+use derive_more::From;
+use wasm_bindgen::prelude::*;
+
+use crate::connection;
+
+/// Directions of the media exchange of a single media kind and source between
+/// this and a remote `Member`.
+#[wasm_bindgen]
+#[derive(Debug, From)]
+pub struct MediaExchangeDirections(connection::MediaExchangeDirections);
+
+#[wasm_bindgen]
+impl MediaExchangeDirections {
+    /// Indicates whether this `Member` intends to send the media to the remote
+    /// one.
+    #[must_use]
+    pub fn we_send(&self) -> bool {
+        self.0.we_send
+    }
+
+    /// Indicates whether the remote `Member` has receiving of the media sent
+    /// by this `Member` enabled.
+    #[must_use]
+    pub fn they_receive(&self) -> bool {
+        self.0.they_receive
+    }
+
+    /// Indicates whether the remote `Member` sends the media to this `Member`.
+    #[must_use]
+    pub fn they_send(&self) -> bool {
+        self.0.they_send
+    }
+
+    /// Indicates whether this `Member` intends to receive the media from the
+    /// remote one.
+    #[must_use]
+    pub fn we_receive(&self) -> bool {
+        self.0.we_receive
+    }
+}
@@ -2,14 +2,20 @@
 //!
 //! [`MediaManager`]: media::MediaManager
 
+use std::time::Duration;
+
 use derive_more::From;
 use js_sys::Promise;
 use wasm_bindgen::prelude::*;
 use wasm_bindgen_futures::future_to_promise;
 
 use crate::{
-    api::{LocalMediaTrack, MediaDeviceDetails, MediaStreamSettings},
-    media,
+    api::{
+        abort_handle, AbortablePromise, DeviceCapabilities, LocalMediaTrack,
+        MediaDeviceDetails, MediaDeviceKind, MediaDevicesDiff, MediaKind,
+        MediaStreamSettings,
+    },
+    media, platform,
 };
 
 use super::Error;
@@ -59,17 +65,182 @@ impl MediaManagerHandle {
         future_to_promise(async move {
             this.enumerate_devices()
                 .await
-                .map(|devices| {
-                    devices
-                        .into_iter()
-                        .fold(js_sys::Array::new(), |devices_info, info| {
-                            _ = devices_info.push(&JsValue::from(
-                                MediaDeviceDetails::from(info),
-                            ));
-                            devices_info
-                        })
-                        .into()
-                })
+                .map(devices_to_js)
+                .map_err(Error::from)
+                .map_err(Into::into)
+        })
+    }
+
+    /// Returns a list of [`MediaDeviceDetails`] objects representing available
+    /// media input and output devices, guaranteeing that no
+    /// [getUserMedia()][1] request is made, so no permission prompt is shown.
+    ///
+    /// Device labels may be empty, as they stay redacted until a media access
+    /// permission is granted (see [`MediaDeviceDetails::is_label_redacted()`]).
+    ///
+    /// # Errors
+    ///
+    /// Same as [`MediaManagerHandle::enumerate_devices()`].
+    ///
+    /// [1]: https://tinyurl.com/w3-streams#dom-mediadevices-getusermedia
+    pub fn enumerate_devices_quiet(&self) -> Promise {
+        let this = self.0.clone();
+
+        future_to_promise(async move {
+            this.enumerate_devices_quiet()
+                .await
+                .map(devices_to_js)
+                .map_err(Error::from)
+                .map_err(Into::into)
+        })
+    }
+
+    /// Same as [`MediaManagerHandle::enumerate_devices()`], but returns an
+    /// [`AbortablePromise`], whose [`AbortHandle`] aborts the enumeration.
+    ///
+    /// # Errors
+    ///
+    /// With an [`AbortedException`][0] if the enumeration is aborted.
+    ///
+    /// Otherwise, the same as [`MediaManagerHandle::enumerate_devices()`].
+    ///
+    /// [`AbortHandle`]: crate::api::AbortHandle
+    /// [0]: crate::api::err::AbortedException
+    pub fn enumerate_devices_abortable(&self) -> AbortablePromise {
+        let this = self.0.clone();
+
+        abort_handle::abortable(|abort| async move {
+            this.enumerate_devices_abortable(abort)
+                .await
+                .map(devices_to_js)
+                .map_err(Error::from)
+                .map_err(Into::into)
+        })
+    }
+
+    /// Returns the cached list of [`MediaDeviceDetails`] objects, enumerating
+    /// devices only if there is no cache yet.
+    ///
+    /// The cache is refreshed automatically on `devicechange` events and after
+    /// the first successful [getUserMedia()][1] request (since device labels
+    /// are unavailable before a permission is granted).
+    ///
+    /// # Errors
+    ///
+    /// With a [`StateError`] if an underlying object has been disposed, e.g.
+    /// `free` was called on this [`MediaManagerHandle`], or on a [`Jason`] that
+    /// implicitly owns native object behind this [`MediaManagerHandle`].
+    ///
+    /// With a [`EnumerateDevicesException`][0] if a request of platform media
+    /// devices access failed.
+    ///
+    /// [`Jason`]: crate::api::Jason
+    /// [`StateError`]: crate::api::err::StateError
+    /// [0]: crate::api::err::EnumerateDevicesException
+    /// [1]: https://tinyurl.com/w3-streams#dom-mediadevices-getusermedia
+    pub fn enumerate_devices_cached(&self) -> Promise {
+        let this = self.0.clone();
+
+        future_to_promise(async move {
+            this.enumerate_devices_cached()
+                .await
+                .map(devices_to_js)
+                .map_err(Error::from)
+                .map_err(Into::into)
+        })
+    }
+
+    /// Enumerates [`MediaDeviceDetails`] objects anew, replacing the ones
+    /// cached for the [`MediaManagerHandle::enumerate_devices_cached()`].
+    ///
+    /// # Errors
+    ///
+    /// With a [`StateError`] if an underlying object has been disposed, e.g.
+    /// `free` was called on this [`MediaManagerHandle`], or on a [`Jason`] that
+    /// implicitly owns native object behind this [`MediaManagerHandle`].
+    ///
+    /// With a [`EnumerateDevicesException`][0] if a request of platform media
+    /// devices access failed.
+    ///
+    /// [`Jason`]: crate::api::Jason
+    /// [`StateError`]: crate::api::err::StateError
+    /// [0]: crate::api::err::EnumerateDevicesException
+    pub fn refresh_devices(&self) -> Promise {
+        let this = self.0.clone();
+
+        future_to_promise(async move {
+            this.refresh_devices()
+                .await
+                .map(devices_to_js)
+                .map_err(Error::from)
+                .map_err(Into::into)
+        })
+    }
+
+    /// Indicates whether at least one media device of the provided
+    /// [`MediaDeviceKind`] is available.
+    ///
+    /// Uses the same cache as the
+    /// [`MediaManagerHandle::enumerate_devices_cached()`] does.
+    ///
+    /// # Errors
+    ///
+    /// With a [`StateError`] if an underlying object has been disposed, e.g.
+    /// `free` was called on this [`MediaManagerHandle`], or on a [`Jason`] that
+    /// implicitly owns native object behind this [`MediaManagerHandle`].
+    ///
+    /// With a [`EnumerateDevicesException`][0] if a request of platform media
+    /// devices access failed.
+    ///
+    /// [`Jason`]: crate::api::Jason
+    /// [`StateError`]: crate::api::err::StateError
+    /// [0]: crate::api::err::EnumerateDevicesException
+    pub fn has_device(&self, kind: MediaDeviceKind) -> Promise {
+        let this = self.0.clone();
+
+        future_to_promise(async move {
+            this.has_device(kind.into())
+                .await
+                .map(JsValue::from)
+                .map_err(Error::from)
+                .map_err(Into::into)
+        })
+    }
+
+    /// Returns [`DeviceCapabilities`] of the media input device with the
+    /// provided ID, without starting a capture from it.
+    ///
+    /// Uses [InputDeviceInfo.getCapabilities()][1] if it's available.
+    /// Otherwise, the device is captured briefly, releasing the captured track
+    /// right away.
+    ///
+    /// Queried [`DeviceCapabilities`] are cached until the next `devicechange`
+    /// event.
+    ///
+    /// # Errors
+    ///
+    /// With a [`StateError`] if an underlying object has been disposed, e.g.
+    /// `free` was called on this [`MediaManagerHandle`], or on a [`Jason`] that
+    /// implicitly owns native object behind this [`MediaManagerHandle`].
+    ///
+    /// With a [`EnumerateDevicesException`][0] if a request of platform media
+    /// devices access failed.
+    ///
+    /// With a [`LocalMediaInitException`] if there is no media input device
+    /// with the provided ID, or capturing it failed.
+    ///
+    /// [`Jason`]: crate::api::Jason
+    /// [`LocalMediaInitException`]: crate::api::err::LocalMediaInitException
+    /// [`StateError`]: crate::api::err::StateError
+    /// [0]: crate::api::err::EnumerateDevicesException
+    /// [1]: https://tinyurl.com/w3-streams#dom-inputdeviceinfo-getcapabilities
+    pub fn device_capabilities(&self, device_id: String) -> Promise {
+        let this = self.0.clone();
+
+        future_to_promise(async move {
+            this.device_capabilities(device_id)
+                .await
+                .map(|caps| DeviceCapabilities::from(caps).into())
                 .map_err(Error::from)
                 .map_err(Into::into)
         })
@@ -109,14 +280,92 @@ impl MediaManagerHandle {
         })
     }
 
+    /// Resets the capture state of the provided [`MediaKind`], so the next
+    /// media request captures it from scratch, without relying on anything
+    /// remembered while the access to it was denied.
+    ///
+    /// Happens automatically once the [Permissions API][1] reports the access
+    /// to the media being granted again.
+    ///
+    /// # Errors
+    ///
+    /// With a [`StateError`] if an underlying object has been disposed, e.g.
+    /// `free` was called on this [`MediaManagerHandle`], or on a [`Jason`] that
+    /// implicitly owns native object behind this [`MediaManagerHandle`].
+    ///
+    /// [`Jason`]: crate::api::Jason
+    /// [`StateError`]: crate::api::err::StateError
+    /// [1]: https://w3.org/TR/permissions
+    pub fn reset_capture_state(&self, kind: MediaKind) -> Promise {
+        let this = self.0.clone();
+
+        future_to_promise(async move {
+            this.reset_capture_state(kind.into())
+                .await
+                .map(|()| JsValue::UNDEFINED)
+                .map_err(Error::from)
+                .map_err(Into::into)
+        })
+    }
+
+    /// Configures retrying of [getUserMedia()][1] requests failed with a
+    /// `NotReadableError` or an `AbortError` (like when a camera is briefly
+    /// held by another tab).
+    ///
+    /// Failed requests are retried up to `max_retries` times, waiting
+    /// `initial_delay_ms` milliseconds before the first retry and doubling the
+    /// delay before every next one. By default, they're retried up to 3 times
+    /// in 300, 600 and 1200 milliseconds. Passing `0` as `max_retries`
+    /// disables retrying.
+    ///
+    /// # Errors
+    ///
+    /// With a [`StateError`] if an underlying object has been disposed, e.g.
+    /// `free` was called on this [`MediaManagerHandle`], or on a [`Jason`] that
+    /// implicitly owns native object behind this [`MediaManagerHandle`].
+    ///
+    /// [`Jason`]: crate::api::Jason
+    /// [`StateError`]: crate::api::err::StateError
+    /// [1]: https://tinyurl.com/w3-streams#dom-mediadevices-getusermedia
+    pub fn set_get_user_media_retries(
+        &self,
+        max_retries: u8,
+        initial_delay_ms: u32,
+    ) -> Result<(), JsValue> {
+        self.0
+            .set_get_user_media_retry_policy(media::GetUserMediaRetryPolicy {
+                max_retries,
+                initial_delay: Duration::from_millis(initial_delay_ms.into()),
+            })
+            .map_err(Error::from)
+            .map_err(Into::into)
+    }
+
     /// Subscribes onto the [`MediaManagerHandle`]'s `devicechange` event.
+    ///
+    /// The provided callback is invoked with a [`MediaDevicesDiff`] against
+    /// the devices cached for the
+    /// [`MediaManagerHandle::enumerate_devices_cached()`].
     pub fn on_device_change(
         &self,
         cb: js_sys::Function,
     ) -> Result<(), JsValue> {
         let this = self.0.clone();
-        this.on_device_change(cb.into())
-            .map_err(Error::from)
-            .map_err(Into::into)
+        let cb = platform::Function::<MediaDevicesDiff>::from(cb);
+        this.on_device_change(move |diff| {
+            cb.call1(MediaDevicesDiff::from(diff));
+        })
+        .map_err(Error::from)
+        .map_err(Into::into)
     }
 }
+
+/// Converts the provided [`platform::MediaDeviceInfo`]s into a JS array of
+/// [`MediaDeviceDetails`].
+fn devices_to_js(devices: Vec<platform::MediaDeviceInfo>) -> JsValue {
+    devices
+        .into_iter()
+        .map(|info| JsValue::from(MediaDeviceDetails::from(info)))
+        .collect::<js_sys::Array>()
+        .into()
+}
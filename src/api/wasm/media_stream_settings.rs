@@ -7,7 +7,11 @@
 use derive_more::{From, Into};
 use wasm_bindgen::prelude::*;
 
-use crate::{api::FacingMode, media};
+use crate::{
+    api::{self, CaptureInclusion, ContentHint, FacingMode},
+    media::{self, MediaKind, MediaSourceKind},
+    platform,
+};
 
 /// [MediaStreamConstraints][1] wrapper.
 ///
@@ -25,6 +29,64 @@ impl MediaStreamSettings {
         media::MediaStreamSettings::new().into()
     }
 
+    /// Creates new [`MediaStreamSettings`] capturing only a microphone.
+    ///
+    /// Audio is captured with `echoCancellation`, `noiseSuppression` and
+    /// `autoGainControl` enabled, while no video is captured.
+    #[must_use]
+    pub fn audio_only() -> Self {
+        media::MediaStreamSettings::audio_only().into()
+    }
+
+    /// Creates new [`MediaStreamSettings`] of a standard definition call.
+    ///
+    /// Audio is captured as in [`MediaStreamSettings::audio_only()`], along
+    /// with a camera video of ideal `640`x`480` resolution and ideal `30`
+    /// frame rate.
+    #[must_use]
+    pub fn sd() -> Self {
+        media::MediaStreamSettings::sd().into()
+    }
+
+    /// Creates new [`MediaStreamSettings`] of a high definition call.
+    ///
+    /// Audio is captured as in [`MediaStreamSettings::audio_only()`], along
+    /// with a camera video of ideal `1280`x`720` resolution and ideal `30`
+    /// frame rate.
+    #[must_use]
+    pub fn hd() -> Self {
+        media::MediaStreamSettings::hd().into()
+    }
+
+    /// Creates new [`MediaStreamSettings`] of a screen sharing with a
+    /// microphone.
+    ///
+    /// Audio is captured as in [`MediaStreamSettings::audio_only()`], along
+    /// with a display video of ideal `1920`x`1080` resolution and ideal `15`
+    /// frame rate, while no camera video is captured.
+    #[must_use]
+    pub fn screen_share_with_audio() -> Self {
+        media::MediaStreamSettings::screen_share_with_audio().into()
+    }
+
+    /// Creates new [`MediaStreamSettings`] from the provided JSON string.
+    ///
+    /// A preset may be named in it (for example, `{"preset": "hd"}`), being
+    /// one of `audio_only`, `sd`, `hd` or `screen_share_with_audio`.
+    ///
+    /// # Errors
+    ///
+    /// With a [`FormatException`] if the provided JSON is malformed or names
+    /// an unknown preset.
+    ///
+    /// [`FormatException`]: crate::api::err::FormatException
+    pub fn from_json(json: &str) -> Result<Self, JsValue> {
+        json.parse::<media::MediaStreamSettings>()
+            .map(Self::from)
+            .map_err(api::Error::from)
+            .map_err(JsValue::from)
+    }
+
     /// Specifies the nature and settings of an audio [MediaStreamTrack][1].
     ///
     /// [1]: https://w3.org/TR/mediacapture-streams#mediastreamtrack
@@ -43,6 +105,140 @@ impl MediaStreamSettings {
     pub fn display_video(&mut self, constraints: DisplayVideoTrackConstraints) {
         self.0.display_video(constraints.into());
     }
+
+    /// Sets the provided audio [MediaStreamTrack][1] to be published instead
+    /// of capturing a microphone.
+    ///
+    /// The provided [MediaStreamTrack][1] stays owned by the caller: it's
+    /// never stopped by the library, and the constraints set via
+    /// [`MediaStreamSettings::audio()`] don't apply to it. No
+    /// `RoomHandle.on_local_track()` callback is fired for it.
+    ///
+    /// # Errors
+    ///
+    /// With a [`FormatException`] if the provided [MediaStreamTrack][1] isn't
+    /// an audio one.
+    ///
+    /// [`FormatException`]: crate::api::err::FormatException
+    /// [1]: https://w3.org/TR/mediacapture-streams#mediastreamtrack
+    pub fn set_custom_audio_track(
+        &mut self,
+        track: web_sys::MediaStreamTrack,
+    ) -> Result<(), JsValue> {
+        let track = custom_track(track, MediaKind::Audio)?;
+        self.0.set_custom_audio_track(track);
+        Ok(())
+    }
+
+    /// Sets the provided video [MediaStreamTrack][1] to be published as a
+    /// device video instead of capturing a camera.
+    ///
+    /// The provided [MediaStreamTrack][1] stays owned by the caller: it's
+    /// never stopped by the library, and the constraints set via
+    /// [`MediaStreamSettings::device_video()`] don't apply to it. No
+    /// `RoomHandle.on_local_track()` callback is fired for it.
+    ///
+    /// # Errors
+    ///
+    /// With a [`FormatException`] if the provided [MediaStreamTrack][1] isn't
+    /// a video one.
+    ///
+    /// [`FormatException`]: crate::api::err::FormatException
+    /// [1]: https://w3.org/TR/mediacapture-streams#mediastreamtrack
+    pub fn set_custom_video_track(
+        &mut self,
+        track: web_sys::MediaStreamTrack,
+    ) -> Result<(), JsValue> {
+        let track = custom_track(track, MediaKind::Video)?;
+        self.0.set_custom_video_track(track);
+        Ok(())
+    }
+
+    /// Same as [`MediaStreamSettings::audio()`], but consumes this
+    /// [`MediaStreamSettings`] and returns it, so the calls can be chained.
+    #[must_use]
+    pub fn with_audio(mut self, constraints: AudioTrackConstraints) -> Self {
+        self.audio(constraints);
+        self
+    }
+
+    /// Same as [`MediaStreamSettings::device_video()`], but consumes this
+    /// [`MediaStreamSettings`] and returns it, so the calls can be chained.
+    #[must_use]
+    pub fn with_device_video(
+        mut self,
+        constraints: DeviceVideoTrackConstraints,
+    ) -> Self {
+        self.device_video(constraints);
+        self
+    }
+
+    /// Same as [`MediaStreamSettings::display_video()`], but consumes this
+    /// [`MediaStreamSettings`] and returns it, so the calls can be chained.
+    #[must_use]
+    pub fn with_display_video(
+        mut self,
+        constraints: DisplayVideoTrackConstraints,
+    ) -> Self {
+        self.display_video(constraints);
+        self
+    }
+
+    /// Same as [`MediaStreamSettings::set_custom_audio_track()`], but consumes
+    /// this [`MediaStreamSettings`] and returns it, so the calls can be
+    /// chained.
+    ///
+    /// # Errors
+    ///
+    /// See [`MediaStreamSettings::set_custom_audio_track()`] for details.
+    pub fn with_custom_audio_track(
+        mut self,
+        track: web_sys::MediaStreamTrack,
+    ) -> Result<Self, JsValue> {
+        self.set_custom_audio_track(track)?;
+        Ok(self)
+    }
+
+    /// Same as [`MediaStreamSettings::set_custom_video_track()`], but consumes
+    /// this [`MediaStreamSettings`] and returns it, so the calls can be
+    /// chained.
+    ///
+    /// # Errors
+    ///
+    /// See [`MediaStreamSettings::set_custom_video_track()`] for details.
+    pub fn with_custom_video_track(
+        mut self,
+        track: web_sys::MediaStreamTrack,
+    ) -> Result<Self, JsValue> {
+        self.set_custom_video_track(track)?;
+        Ok(self)
+    }
+}
+
+/// Wraps the provided [MediaStreamTrack][1] into a
+/// [`platform::MediaStreamTrack`] of a [`MediaSourceKind::Device`].
+///
+/// # Errors
+///
+/// With a [`FormatException`] if the provided [MediaStreamTrack][1] isn't of
+/// the provided [`MediaKind`].
+///
+/// [`FormatException`]: api::err::FormatException
+/// [1]: https://w3.org/TR/mediacapture-streams#mediastreamtrack
+fn custom_track(
+    track: web_sys::MediaStreamTrack,
+    kind: MediaKind,
+) -> Result<platform::MediaStreamTrack, api::err::FormatException> {
+    let track =
+        platform::MediaStreamTrack::new(track, Some(MediaSourceKind::Device));
+    if track.kind() == kind {
+        Ok(track)
+    } else {
+        Err(api::err::FormatException::new(format!(
+            "Expected {kind} MediaStreamTrack, but got {} one",
+            track.kind(),
+        )))
+    }
 }
 
 /// Constraints applicable to audio tracks.
@@ -65,6 +261,75 @@ impl AudioTrackConstraints {
     pub fn device_id(&mut self, device_id: String) {
         self.0.device_id(device_id);
     }
+
+    /// Sets a [`ContentHint`] to be applied to the captured audio track.
+    pub fn content_hint(&mut self, hint: ContentHint) {
+        self.0.content_hint(hint.into());
+    }
+
+    /// Sets an [autoGainControl][1] constraint.
+    ///
+    /// [1]: https://w3.org/TR/mediacapture-streams#dfn-autogaincontrol
+    pub fn auto_gain_control(&mut self, enabled: bool) {
+        self.0.auto_gain_control(enabled);
+    }
+
+    /// Sets an [echoCancellation][1] constraint.
+    ///
+    /// [1]: https://w3.org/TR/mediacapture-streams#dfn-echocancellation
+    pub fn echo_cancellation(&mut self, enabled: bool) {
+        self.0.echo_cancellation(enabled);
+    }
+
+    /// Sets a [noiseSuppression][1] constraint.
+    ///
+    /// [1]: https://w3.org/TR/mediacapture-streams#dfn-noisesuppression
+    pub fn noise_suppression(&mut self, enabled: bool) {
+        self.0.noise_suppression(enabled);
+    }
+
+    /// Same as [`AudioTrackConstraints::device_id()`], but consumes this
+    /// [`AudioTrackConstraints`] and returns it, so the calls can be chained.
+    #[must_use]
+    pub fn with_device_id(mut self, device_id: String) -> Self {
+        self.device_id(device_id);
+        self
+    }
+
+    /// Same as [`AudioTrackConstraints::content_hint()`], but consumes this
+    /// [`AudioTrackConstraints`] and returns it, so the calls can be chained.
+    #[must_use]
+    pub fn with_content_hint(mut self, hint: ContentHint) -> Self {
+        self.content_hint(hint);
+        self
+    }
+
+    /// Same as [`AudioTrackConstraints::auto_gain_control()`], but consumes
+    /// this [`AudioTrackConstraints`] and returns it, so the calls can be
+    /// chained.
+    #[must_use]
+    pub fn with_auto_gain_control(mut self, enabled: bool) -> Self {
+        self.auto_gain_control(enabled);
+        self
+    }
+
+    /// Same as [`AudioTrackConstraints::echo_cancellation()`], but consumes
+    /// this [`AudioTrackConstraints`] and returns it, so the calls can be
+    /// chained.
+    #[must_use]
+    pub fn with_echo_cancellation(mut self, enabled: bool) -> Self {
+        self.echo_cancellation(enabled);
+        self
+    }
+
+    /// Same as [`AudioTrackConstraints::noise_suppression()`], but consumes
+    /// this [`AudioTrackConstraints`] and returns it, so the calls can be
+    /// chained.
+    #[must_use]
+    pub fn with_noise_suppression(mut self, enabled: bool) -> Self {
+        self.noise_suppression(enabled);
+        self
+    }
 }
 
 /// Constraints applicable to video tracks that are sourced from some media
@@ -147,6 +412,119 @@ impl DeviceVideoTrackConstraints {
     pub fn width_in_range(&mut self, min: u32, max: u32) {
         self.0.width_in_range(min, max);
     }
+
+    /// Sets an exact [frameRate][1] constraint.
+    ///
+    /// [1]: https://w3.org/TR/mediacapture-streams#dfn-framerate
+    pub fn exact_frame_rate(&mut self, frame_rate: u32) {
+        self.0.exact_frame_rate(frame_rate);
+    }
+
+    /// Sets an ideal [frameRate][1] constraint.
+    ///
+    /// [1]: https://w3.org/TR/mediacapture-streams#dfn-framerate
+    pub fn ideal_frame_rate(&mut self, frame_rate: u32) {
+        self.0.ideal_frame_rate(frame_rate);
+    }
+
+    /// Same as [`DeviceVideoTrackConstraints::device_id()`], but consumes this
+    /// [`DeviceVideoTrackConstraints`] and returns it, so the calls can be
+    /// chained.
+    #[must_use]
+    pub fn with_device_id(mut self, device_id: String) -> Self {
+        self.device_id(device_id);
+        self
+    }
+
+    /// Same as [`DeviceVideoTrackConstraints::exact_facing_mode()`], but
+    /// consumes this [`DeviceVideoTrackConstraints`] and returns it, so the
+    /// calls can be chained.
+    #[must_use]
+    pub fn with_exact_facing_mode(mut self, facing_mode: FacingMode) -> Self {
+        self.exact_facing_mode(facing_mode);
+        self
+    }
+
+    /// Same as [`DeviceVideoTrackConstraints::ideal_facing_mode()`], but
+    /// consumes this [`DeviceVideoTrackConstraints`] and returns it, so the
+    /// calls can be chained.
+    #[must_use]
+    pub fn with_ideal_facing_mode(mut self, facing_mode: FacingMode) -> Self {
+        self.ideal_facing_mode(facing_mode);
+        self
+    }
+
+    /// Same as [`DeviceVideoTrackConstraints::exact_height()`], but consumes
+    /// this [`DeviceVideoTrackConstraints`] and returns it, so the calls can be
+    /// chained.
+    #[must_use]
+    pub fn with_exact_height(mut self, height: u32) -> Self {
+        self.exact_height(height);
+        self
+    }
+
+    /// Same as [`DeviceVideoTrackConstraints::ideal_height()`], but consumes
+    /// this [`DeviceVideoTrackConstraints`] and returns it, so the calls can be
+    /// chained.
+    #[must_use]
+    pub fn with_ideal_height(mut self, height: u32) -> Self {
+        self.ideal_height(height);
+        self
+    }
+
+    /// Same as [`DeviceVideoTrackConstraints::height_in_range()`], but consumes
+    /// this [`DeviceVideoTrackConstraints`] and returns it, so the calls can be
+    /// chained.
+    #[must_use]
+    pub fn with_height_in_range(mut self, min: u32, max: u32) -> Self {
+        self.height_in_range(min, max);
+        self
+    }
+
+    /// Same as [`DeviceVideoTrackConstraints::exact_width()`], but consumes
+    /// this [`DeviceVideoTrackConstraints`] and returns it, so the calls can be
+    /// chained.
+    #[must_use]
+    pub fn with_exact_width(mut self, width: u32) -> Self {
+        self.exact_width(width);
+        self
+    }
+
+    /// Same as [`DeviceVideoTrackConstraints::ideal_width()`], but consumes
+    /// this [`DeviceVideoTrackConstraints`] and returns it, so the calls can be
+    /// chained.
+    #[must_use]
+    pub fn with_ideal_width(mut self, width: u32) -> Self {
+        self.ideal_width(width);
+        self
+    }
+
+    /// Same as [`DeviceVideoTrackConstraints::width_in_range()`], but consumes
+    /// this [`DeviceVideoTrackConstraints`] and returns it, so the calls can be
+    /// chained.
+    #[must_use]
+    pub fn with_width_in_range(mut self, min: u32, max: u32) -> Self {
+        self.width_in_range(min, max);
+        self
+    }
+
+    /// Same as [`DeviceVideoTrackConstraints::exact_frame_rate()`], but
+    /// consumes this [`DeviceVideoTrackConstraints`] and returns it, so the
+    /// calls can be chained.
+    #[must_use]
+    pub fn with_exact_frame_rate(mut self, frame_rate: u32) -> Self {
+        self.exact_frame_rate(frame_rate);
+        self
+    }
+
+    /// Same as [`DeviceVideoTrackConstraints::ideal_frame_rate()`], but
+    /// consumes this [`DeviceVideoTrackConstraints`] and returns it, so the
+    /// calls can be chained.
+    #[must_use]
+    pub fn with_ideal_frame_rate(mut self, frame_rate: u32) -> Self {
+        self.ideal_frame_rate(frame_rate);
+        self
+    }
 }
 
 /// Constraints applicable to video tracks sourced from a screen capturing.
@@ -164,6 +542,51 @@ impl DisplayVideoTrackConstraints {
         media::DisplayVideoTrackConstraints::new().into()
     }
 
+    /// Sets a [`ContentHint`] to be applied to the captured video track.
+    ///
+    /// [`ContentHint::Detail`] is applied by default.
+    pub fn content_hint(&mut self, hint: ContentHint) {
+        self.0.content_hint(hint.into());
+    }
+
+    /// Sets the `preferCurrentTab` option, offering the current browser tab
+    /// as the most prominent capture option (one-click "share this tab").
+    ///
+    /// Ignored by the browsers not supporting it.
+    pub fn prefer_current_tab(&mut self, prefer: bool) {
+        self.0.prefer_current_tab(prefer);
+    }
+
+    /// Sets the [selfBrowserSurface][1] option, including or excluding the
+    /// current browser tab from the offered capture options.
+    ///
+    /// Ignored by the browsers not supporting it.
+    ///
+    /// [1]: https://w3.org/TR/screen-capture/#dom-displaymediastreamoptions
+    pub fn self_browser_surface(&mut self, inclusion: CaptureInclusion) {
+        self.0.self_browser_surface(inclusion.into());
+    }
+
+    /// Sets the [surfaceSwitching][1] option, allowing or disallowing a user
+    /// to dynamically switch the shared browser tab.
+    ///
+    /// Ignored by the browsers not supporting it.
+    ///
+    /// [1]: https://w3.org/TR/screen-capture/#dom-displaymediastreamoptions
+    pub fn surface_switching(&mut self, inclusion: CaptureInclusion) {
+        self.0.surface_switching(inclusion.into());
+    }
+
+    /// Sets the [systemAudio][1] option, offering or not offering a system
+    /// audio to a user along with a captured screen.
+    ///
+    /// Ignored by the browsers not supporting it.
+    ///
+    /// [1]: https://w3.org/TR/screen-capture/#dom-displaymediastreamoptions
+    pub fn system_audio(&mut self, inclusion: CaptureInclusion) {
+        self.0.system_audio(inclusion.into());
+    }
+
     /// Sets an exact [height][1] constraint.
     ///
     /// [1]: https://tinyurl.com/w3-streams#def-constraint-height
@@ -205,4 +628,109 @@ impl DisplayVideoTrackConstraints {
     pub fn ideal_frame_rate(&mut self, frame_rate: u32) {
         self.0.ideal_frame_rate(frame_rate);
     }
+
+    /// Same as [`DisplayVideoTrackConstraints::content_hint()`], but consumes
+    /// this [`DisplayVideoTrackConstraints`] and returns it, so the calls can
+    /// be chained.
+    #[must_use]
+    pub fn with_content_hint(mut self, hint: ContentHint) -> Self {
+        self.content_hint(hint);
+        self
+    }
+
+    /// Same as [`DisplayVideoTrackConstraints::exact_height()`], but consumes
+    /// this [`DisplayVideoTrackConstraints`] and returns it, so the calls can
+    /// be chained.
+    #[must_use]
+    pub fn with_exact_height(mut self, height: u32) -> Self {
+        self.exact_height(height);
+        self
+    }
+
+    /// Same as [`DisplayVideoTrackConstraints::ideal_height()`], but consumes
+    /// this [`DisplayVideoTrackConstraints`] and returns it, so the calls can
+    /// be chained.
+    #[must_use]
+    pub fn with_ideal_height(mut self, height: u32) -> Self {
+        self.ideal_height(height);
+        self
+    }
+
+    /// Same as [`DisplayVideoTrackConstraints::exact_width()`], but consumes
+    /// this [`DisplayVideoTrackConstraints`] and returns it, so the calls can
+    /// be chained.
+    #[must_use]
+    pub fn with_exact_width(mut self, width: u32) -> Self {
+        self.exact_width(width);
+        self
+    }
+
+    /// Same as [`DisplayVideoTrackConstraints::ideal_width()`], but consumes
+    /// this [`DisplayVideoTrackConstraints`] and returns it, so the calls can
+    /// be chained.
+    #[must_use]
+    pub fn with_ideal_width(mut self, width: u32) -> Self {
+        self.ideal_width(width);
+        self
+    }
+
+    /// Same as [`DisplayVideoTrackConstraints::exact_frame_rate()`], but
+    /// consumes this [`DisplayVideoTrackConstraints`] and returns it, so the
+    /// calls can be chained.
+    #[must_use]
+    pub fn with_exact_frame_rate(mut self, frame_rate: u32) -> Self {
+        self.exact_frame_rate(frame_rate);
+        self
+    }
+
+    /// Same as [`DisplayVideoTrackConstraints::ideal_frame_rate()`], but
+    /// consumes this [`DisplayVideoTrackConstraints`] and returns it, so the
+    /// calls can be chained.
+    #[must_use]
+    pub fn with_ideal_frame_rate(mut self, frame_rate: u32) -> Self {
+        self.ideal_frame_rate(frame_rate);
+        self
+    }
+
+    /// Same as [`DisplayVideoTrackConstraints::prefer_current_tab()`], but
+    /// consumes this [`DisplayVideoTrackConstraints`] and returns it, so the
+    /// calls can be chained.
+    #[must_use]
+    pub fn with_prefer_current_tab(mut self, prefer: bool) -> Self {
+        self.prefer_current_tab(prefer);
+        self
+    }
+
+    /// Same as [`DisplayVideoTrackConstraints::self_browser_surface()`], but
+    /// consumes this [`DisplayVideoTrackConstraints`] and returns it, so the
+    /// calls can be chained.
+    #[must_use]
+    pub fn with_self_browser_surface(
+        mut self,
+        inclusion: CaptureInclusion,
+    ) -> Self {
+        self.self_browser_surface(inclusion);
+        self
+    }
+
+    /// Same as [`DisplayVideoTrackConstraints::surface_switching()`], but
+    /// consumes this [`DisplayVideoTrackConstraints`] and returns it, so the
+    /// calls can be chained.
+    #[must_use]
+    pub fn with_surface_switching(
+        mut self,
+        inclusion: CaptureInclusion,
+    ) -> Self {
+        self.surface_switching(inclusion);
+        self
+    }
+
+    /// Same as [`DisplayVideoTrackConstraints::system_audio()`], but consumes
+    /// this [`DisplayVideoTrackConstraints`] and returns it, so the calls can
+    /// be chained.
+    #[must_use]
+    pub fn with_system_audio(mut self, inclusion: CaptureInclusion) -> Self {
+        self.system_audio(inclusion);
+        self
+    }
 }
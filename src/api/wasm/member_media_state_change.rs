@@ -0,0 +1,41 @@
+//! Change of a remote `Member`'s media state.
+
+use derive_more::From;
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    api::{MediaKind, MediaSourceKind, MemberMediaState},
+    connection,
+};
+
+/// Change of a state of some remote `Member`'s media, made by this `Member`.
+#[wasm_bindgen]
+#[derive(Debug, From)]
+pub struct MemberMediaStateChange(connection::MemberMediaStateChange);
+
+#[wasm_bindgen]
+impl MemberMediaStateChange {
+    /// Returns ID of the remote `Member` whose media state has changed.
+    #[must_use]
+    pub fn member_id(&self) -> String {
+        self.0.member_id.0.clone()
+    }
+
+    /// Returns [`MediaKind`] of the changed media.
+    #[must_use]
+    pub fn kind(&self) -> MediaKind {
+        self.0.kind.into()
+    }
+
+    /// Returns [`MediaSourceKind`] of the changed media.
+    #[must_use]
+    pub fn media_source_kind(&self) -> MediaSourceKind {
+        self.0.source_kind.into()
+    }
+
+    /// Returns the new [`MemberMediaState`] of the media.
+    #[must_use]
+    pub fn state(&self) -> MemberMediaState {
+        self.0.state.into()
+    }
+}
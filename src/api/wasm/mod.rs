@@ -3,38 +3,92 @@
 //!
 //! [`Jason`]: crate::api::Jason
 
+pub mod abort_handle;
+pub mod adaptive_policy;
+pub mod av_sync_info;
+pub mod codec_info;
 pub mod connection_handle;
+pub mod device_capabilities;
+pub mod device_mismatch;
+pub mod diagnostics;
+pub mod display_capture_options;
 pub mod err;
+pub mod forced_media_state;
+pub mod ice_candidate_filter;
+pub mod ice_gathering_info;
 pub mod jason;
+pub mod jason_config;
+pub mod join_progress;
 pub mod local_media_track;
 pub mod media_device_details;
+pub mod media_devices_diff;
+pub mod media_exchange_directions;
 pub mod media_manager_handle;
 pub mod media_stream_settings;
+pub mod member_media_state_change;
+pub mod negotiation_stats;
+pub mod non_fatal_error;
+pub mod quality_summary;
 pub mod reconnect_handle;
 pub mod remote_media_track;
 pub mod room_close_reason;
 pub mod room_handle;
+pub mod room_media_policy;
+pub mod rtc_configuration;
+pub mod sdp_event;
+pub mod session_metrics;
+pub mod traffic_totals;
+pub mod transceiver_info;
+pub mod transport_info;
+pub mod webrtc_support;
 
 use derive_more::Display;
 use wasm_bindgen::prelude::*;
 
-use crate::media;
+use crate::{api, connection, media, peer, platform, room, rpc};
 
 pub use self::{
+    abort_handle::{AbortHandle, AbortablePromise},
+    adaptive_policy::{Adaptation, AdaptivePolicy, QualityLimitationReason},
+    av_sync_info::AvSyncInfo,
+    codec_info::{CodecFallback, CodecInfo},
     connection_handle::ConnectionHandle,
+    device_capabilities::{CapabilityRange, DeviceCapabilities},
+    device_mismatch::DeviceMismatch,
+    diagnostics::{Diagnostics, IceProbeReport, IceServerProbe},
+    display_capture_options::DisplayCaptureOptions,
     err::Error,
+    forced_media_state::ForcedMediaState,
+    ice_candidate_filter::{IceCandidateAttributes, IceCandidateFilter},
+    ice_gathering_info::IceGatheringInfo,
     jason::Jason,
+    jason_config::JasonConfig,
+    join_progress::{JoinPhase, JoinProgressUpdate},
     local_media_track::LocalMediaTrack,
     media_device_details::MediaDeviceDetails,
+    media_devices_diff::MediaDevicesDiff,
+    media_exchange_directions::MediaExchangeDirections,
     media_manager_handle::MediaManagerHandle,
     media_stream_settings::{
         AudioTrackConstraints, DeviceVideoTrackConstraints,
         DisplayVideoTrackConstraints, MediaStreamSettings,
     },
+    member_media_state_change::MemberMediaStateChange,
+    negotiation_stats::NegotiationStats,
+    non_fatal_error::{NonFatalError, NonFatalErrorKind},
+    quality_summary::QualitySummary,
     reconnect_handle::ReconnectHandle,
     remote_media_track::RemoteMediaTrack,
     room_close_reason::RoomCloseReason,
     room_handle::RoomHandle,
+    room_media_policy::{RoomMediaPolicy, SendMediaPolicy},
+    rtc_configuration::RtcConfiguration,
+    sdp_event::{SdpDirection, SdpEvent, SdpKind},
+    session_metrics::SessionMetrics,
+    traffic_totals::TrafficTotals,
+    transceiver_info::TransceiverInfo,
+    transport_info::TransportInfo,
+    webrtc_support::WebRtcSupport,
 };
 
 /// [MediaStreamTrack.kind][1] representation.
@@ -96,6 +150,16 @@ impl From<media::MediaDeviceKind> for MediaDeviceKind {
     }
 }
 
+impl From<MediaDeviceKind> for media::MediaDeviceKind {
+    fn from(that: MediaDeviceKind) -> Self {
+        match that {
+            MediaDeviceKind::AudioInput => Self::AudioInput,
+            MediaDeviceKind::VideoInput => Self::VideoInput,
+            MediaDeviceKind::AudioOutput => Self::AudioOutput,
+        }
+    }
+}
+
 /// Media source type.
 #[wasm_bindgen]
 #[derive(Clone, Copy, Debug, Display, Eq, PartialEq)]
@@ -125,6 +189,237 @@ impl From<MediaSourceKind> for media::MediaSourceKind {
     }
 }
 
+impl MediaKind {
+    /// All the [`MediaKind`]s.
+    const ALL: [Self; 2] = [Self::Audio, Self::Video];
+
+    /// Returns the stable lowercase name of this [`MediaKind`].
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Audio => "audio",
+            Self::Video => "video",
+        }
+    }
+}
+
+impl MediaSourceKind {
+    /// All the [`MediaSourceKind`]s.
+    const ALL: [Self; 2] = [Self::Device, Self::Display];
+
+    /// Returns the stable lowercase name of this [`MediaSourceKind`].
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Device => "device",
+            Self::Display => "display",
+        }
+    }
+}
+
+// Static methods cannot be exported on `#[wasm_bindgen]` enums, so the string
+// conversions are exported as free functions.
+
+/// Parses a [`MediaKind`] from its lowercase name (`audio` or `video`).
+///
+/// # Errors
+///
+/// With a [`FormatException`] listing the accepted names, if the provided one
+/// is unknown.
+///
+/// [`FormatException`]: api::err::FormatException
+#[wasm_bindgen]
+pub fn media_kind_from_string(name: &str) -> Result<MediaKind, JsValue> {
+    parse_name("MediaKind", name, &MediaKind::ALL, MediaKind::name)
+        .map_err(Into::into)
+}
+
+/// Returns the stable lowercase name of the provided [`MediaKind`]
+/// (`audio` or `video`).
+#[must_use]
+#[wasm_bindgen]
+pub fn media_kind_to_string(kind: MediaKind) -> String {
+    kind.name().into()
+}
+
+/// Parses a [`MediaSourceKind`] from its lowercase name (`device` or
+/// `display`).
+///
+/// # Errors
+///
+/// With a [`FormatException`] listing the accepted names, if the provided one
+/// is unknown.
+///
+/// [`FormatException`]: api::err::FormatException
+#[wasm_bindgen]
+pub fn media_source_kind_from_string(
+    name: &str,
+) -> Result<MediaSourceKind, JsValue> {
+    parse_name(
+        "MediaSourceKind",
+        name,
+        &MediaSourceKind::ALL,
+        MediaSourceKind::name,
+    )
+    .map_err(Into::into)
+}
+
+/// Returns the stable lowercase name of the provided [`MediaSourceKind`]
+/// (`device` or `display`).
+#[must_use]
+#[wasm_bindgen]
+pub fn media_source_kind_to_string(kind: MediaSourceKind) -> String {
+    kind.name().into()
+}
+
+/// Returns the one of the provided `variants` of the `ty` enum, which is
+/// named as the provided `name`.
+///
+/// # Errors
+///
+/// With a [`FormatException`] listing the names of all the `variants`, if
+/// none of them matches.
+///
+/// [`FormatException`]: api::err::FormatException
+fn parse_name<T: Copy>(
+    ty: &str,
+    name: &str,
+    variants: &[T],
+    name_of: fn(T) -> &'static str,
+) -> Result<T, api::err::FormatException> {
+    variants
+        .iter()
+        .copied()
+        .find(|v| name_of(*v) == name)
+        .ok_or_else(|| {
+            let expected: Vec<_> = variants
+                .iter()
+                .map(|v| format!("`{}`", name_of(*v)))
+                .collect();
+            api::err::FormatException::new(format!(
+                "Unknown `{ty}` name: `{name}`, expected one of: {}",
+                expected.join(", "),
+            ))
+        })
+}
+
+/// Type of a display surface captured by a [`MediaSourceKind::Display`]
+/// track. Representation of a [DisplayCaptureSurfaceType][1].
+///
+/// [1]: https://w3.org/TR/screen-capture#dom-displaycapturesurfacetype
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, Display, Eq, PartialEq)]
+pub enum DisplaySurface {
+    /// Whole monitor is captured.
+    Monitor,
+
+    /// Single application window is captured.
+    Window,
+
+    /// Single browser tab is captured.
+    Browser,
+
+    /// Captured surface type is unknown.
+    Unknown,
+}
+
+impl From<media::DisplaySurface> for DisplaySurface {
+    fn from(that: media::DisplaySurface) -> Self {
+        use media::DisplaySurface as S;
+
+        match that {
+            S::Monitor => Self::Monitor,
+            S::Window => Self::Window,
+            S::Browser => Self::Browser,
+            S::Unknown => Self::Unknown,
+        }
+    }
+}
+
+/// Hint of a kind of the content carried by a track. Representation of a
+/// [contentHint][1] value.
+///
+/// [1]: https://w3.org/TR/mst-content-hint
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, Display, Eq, PartialEq)]
+pub enum ContentHint {
+    /// Audio track carries speech.
+    Speech,
+
+    /// Audio track carries speech to be processed by a machine.
+    SpeechRecognition,
+
+    /// Audio track carries music.
+    Music,
+
+    /// Video track carries motion.
+    Motion,
+
+    /// Video track carries details.
+    Detail,
+
+    /// Video track carries text.
+    Text,
+}
+
+impl From<media::ContentHint> for ContentHint {
+    fn from(that: media::ContentHint) -> Self {
+        use media::ContentHint as H;
+
+        match that {
+            H::Speech => Self::Speech,
+            H::SpeechRecognition => Self::SpeechRecognition,
+            H::Music => Self::Music,
+            H::Motion => Self::Motion,
+            H::Detail => Self::Detail,
+            H::Text => Self::Text,
+        }
+    }
+}
+
+impl From<ContentHint> for media::ContentHint {
+    fn from(val: ContentHint) -> Self {
+        match val {
+            ContentHint::Speech => Self::Speech,
+            ContentHint::SpeechRecognition => Self::SpeechRecognition,
+            ContentHint::Music => Self::Music,
+            ContentHint::Motion => Self::Motion,
+            ContentHint::Detail => Self::Detail,
+            ContentHint::Text => Self::Text,
+        }
+    }
+}
+
+/// Indicator whether some kind of capture is included into or excluded from
+/// a [getDisplayMedia()][1] request.
+///
+/// [1]: https://w3.org/TR/screen-capture/#dom-mediadevices-getdisplaymedia
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, Display, Eq, PartialEq)]
+pub enum CaptureInclusion {
+    /// Capture is offered to a user.
+    Include,
+
+    /// Capture is not offered to a user.
+    Exclude,
+}
+
+impl From<media::CaptureInclusion> for CaptureInclusion {
+    fn from(that: media::CaptureInclusion) -> Self {
+        match that {
+            media::CaptureInclusion::Include => Self::Include,
+            media::CaptureInclusion::Exclude => Self::Exclude,
+        }
+    }
+}
+
+impl From<CaptureInclusion> for media::CaptureInclusion {
+    fn from(val: CaptureInclusion) -> Self {
+        match val {
+            CaptureInclusion::Include => Self::Include,
+            CaptureInclusion::Exclude => Self::Exclude,
+        }
+    }
+}
+
 /// Liveness state of a [`MediaStreamTrack`][1].
 ///
 /// [1]: crate::platform::MediaStreamTrack
@@ -251,3 +546,210 @@ impl From<MediaDirection> for JsValue {
         Self::from(val as u8)
     }
 }
+
+/// Outcome of a media state change requested via a [`RoomHandle`].
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, Display, Eq, PartialEq)]
+pub enum MediaStateOutcome {
+    /// Requested media state has been applied.
+    Applied,
+
+    /// Requested media state change has been superseded by a newer opposite
+    /// one before being approved by a media server.
+    Superseded,
+}
+
+impl From<room::MediaStateOutcome> for MediaStateOutcome {
+    fn from(val: room::MediaStateOutcome) -> Self {
+        match val {
+            room::MediaStateOutcome::Applied => Self::Applied,
+            room::MediaStateOutcome::Superseded => Self::Superseded,
+        }
+    }
+}
+
+impl From<MediaStateOutcome> for JsValue {
+    #[allow(clippy::as_conversions)]
+    fn from(val: MediaStateOutcome) -> Self {
+        Self::from(val as u8)
+    }
+}
+
+/// Outcome of a reconnection requested via a [`ReconnectHandle`].
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, Display, Eq, PartialEq)]
+pub enum ReconnectOutcome {
+    /// Connection with a media server has been restored.
+    Reconnected,
+
+    /// Connection with a media server has been established already, so no
+    /// reconnection attempt has been performed.
+    AlreadyConnected,
+}
+
+impl From<rpc::ReconnectOutcome> for ReconnectOutcome {
+    fn from(val: rpc::ReconnectOutcome) -> Self {
+        match val {
+            rpc::ReconnectOutcome::Reconnected => Self::Reconnected,
+            rpc::ReconnectOutcome::AlreadyConnected => Self::AlreadyConnected,
+        }
+    }
+}
+
+impl From<ReconnectOutcome> for JsValue {
+    #[allow(clippy::as_conversions)]
+    fn from(val: ReconnectOutcome) -> Self {
+        Self::from(val as u8)
+    }
+}
+
+/// State of a remote `Member`'s media, controlled by this `Member`.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, Display, Eq, PartialEq)]
+pub enum MemberMediaState {
+    /// Media is enabled by the remote `Member`.
+    Enabled,
+
+    /// Media is disabled by the remote `Member`.
+    Disabled,
+
+    /// Media is muted by the remote `Member`.
+    Muted,
+
+    /// Media is unmuted by the remote `Member`.
+    Unmuted,
+}
+
+impl From<connection::MemberMediaState> for MemberMediaState {
+    fn from(val: connection::MemberMediaState) -> Self {
+        use connection::MemberMediaState as S;
+
+        match val {
+            S::Enabled => Self::Enabled,
+            S::Disabled => Self::Disabled,
+            S::Muted => Self::Muted,
+            S::Unmuted => Self::Unmuted,
+        }
+    }
+}
+
+/// State of the local media.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, Display, Eq, PartialEq)]
+pub enum LocalMediaState {
+    /// Media is enabled.
+    Enabled,
+
+    /// Media is disabled.
+    Disabled,
+
+    /// Media is muted.
+    Muted,
+
+    /// Media is unmuted.
+    Unmuted,
+}
+
+impl From<peer::MediaState> for LocalMediaState {
+    fn from(val: peer::MediaState) -> Self {
+        use peer::{media_exchange_state, mute_state, MediaState as S};
+
+        match val {
+            S::MediaExchange(media_exchange_state::Stable::Enabled) => {
+                Self::Enabled
+            }
+            S::MediaExchange(media_exchange_state::Stable::Disabled) => {
+                Self::Disabled
+            }
+            S::Mute(mute_state::Stable::Muted) => Self::Muted,
+            S::Mute(mute_state::Stable::Unmuted) => Self::Unmuted,
+        }
+    }
+}
+
+/// Policy of bundling media onto transports. Representation of a
+/// [RTCBundlePolicy][1].
+///
+/// [1]: https://w3.org/TR/webrtc#dom-rtcbundlepolicy
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, Display, Eq, PartialEq)]
+pub enum BundlePolicy {
+    /// Separate transport is negotiated for each media type in use.
+    Balanced,
+
+    /// Separate transport is negotiated for each track.
+    MaxCompat,
+
+    /// All the media is bundled onto a single transport.
+    MaxBundle,
+}
+
+impl From<BundlePolicy> for platform::BundlePolicy {
+    fn from(val: BundlePolicy) -> Self {
+        match val {
+            BundlePolicy::Balanced => Self::Balanced,
+            BundlePolicy::MaxCompat => Self::MaxCompat,
+            BundlePolicy::MaxBundle => Self::MaxBundle,
+        }
+    }
+}
+
+/// Policy of considering ICE candidates. Representation of a
+/// [RTCIceTransportPolicy][1].
+///
+/// [1]: https://w3.org/TR/webrtc#dom-rtcicetransportpolicy
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, Display, Eq, PartialEq)]
+pub enum IceTransportPolicy {
+    /// All ICE candidates are considered.
+    All,
+
+    /// Only relayed ICE candidates (passed through a TURN server) are
+    /// considered.
+    Relay,
+}
+
+impl From<IceTransportPolicy> for platform::IceTransportPolicy {
+    fn from(val: IceTransportPolicy) -> Self {
+        match val {
+            IceTransportPolicy::All => Self::All,
+            IceTransportPolicy::Relay => Self::Relay,
+        }
+    }
+}
+
+/// Type of an [ICE candidate][1]. Representation of a
+/// [RTCIceCandidateType][2].
+///
+/// [1]: https://w3.org/TR/webrtc#rtcicecandidate-interface
+/// [2]: https://w3.org/TR/webrtc#dom-rtcicecandidatetype
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, Display, Eq, PartialEq)]
+pub enum IceCandidateType {
+    /// Candidate with an actual local address of a host.
+    Host,
+
+    /// Candidate with an address assigned by a NAT, discovered via a STUN
+    /// server.
+    ServerReflexive,
+
+    /// Candidate with an address assigned by a NAT, discovered via a remote
+    /// peer.
+    PeerReflexive,
+
+    /// Candidate with an address allocated on a TURN server.
+    Relay,
+}
+
+impl From<platform::IceCandidateType> for IceCandidateType {
+    fn from(that: platform::IceCandidateType) -> Self {
+        use platform::IceCandidateType as T;
+
+        match that {
+            T::Host => Self::Host,
+            T::ServerReflexive => Self::ServerReflexive,
+            T::PeerReflexive => Self::PeerReflexive,
+            T::Relay => Self::Relay,
+        }
+    }
+}
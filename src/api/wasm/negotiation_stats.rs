@@ -0,0 +1,43 @@
+//! Stats of SDP negotiations performed by a connection with a specific remote
+//! `Member`.
+
+use derive_more::From;
+use wasm_bindgen::prelude::*;
+
+use crate::peer;
+
+/// Stats of SDP offer/answer exchanges performed by a connection with a
+/// specific remote `Member`.
+#[wasm_bindgen]
+#[derive(Debug, From)]
+pub struct NegotiationStats(peer::NegotiationStats);
+
+#[wasm_bindgen]
+impl NegotiationStats {
+    /// Returns number of the completed SDP offer/answer exchanges.
+    #[must_use]
+    pub fn count(&self) -> u32 {
+        self.0.count
+    }
+
+    /// Returns wall-clock duration of the last completed SDP offer/answer
+    /// exchange in milliseconds, if any.
+    #[must_use]
+    pub fn last_duration_ms(&self) -> Option<f64> {
+        self.0.last_duration.map(|d| d.as_secs_f64() * 1000.0)
+    }
+
+    /// Returns number of the performed rollbacks to a stable signaling state.
+    #[must_use]
+    pub fn rollbacks(&self) -> u32 {
+        self.0.rollbacks
+    }
+
+    /// Returns number of the rollbacks performed by re-applying the last
+    /// stable SDP offer/answer exchange, since a browser has rejected an
+    /// explicit rollback.
+    #[must_use]
+    pub fn fallback_rollbacks(&self) -> u32 {
+        self.0.fallback_rollbacks
+    }
+}
@@ -0,0 +1,84 @@
+//! Non-fatal error happened in a `Room`.
+
+use derive_more::From;
+use wasm_bindgen::prelude::*;
+
+use crate::error_report;
+
+/// Kind of a [`NonFatalError`].
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum NonFatalErrorKind {
+    /// Failed to collect or parse peer connection statistics.
+    Stats,
+
+    /// Failed to add a remote ICE candidate, so it's ignored.
+    IceCandidate,
+
+    /// Failed to update encoding parameters of an outbound media.
+    Encoding,
+
+    /// Failed to add a remote track.
+    RemoteTrack,
+
+    /// Failed to update a media state after a local track has ended.
+    LocalTrack,
+
+    /// Negotiation has ended up in an unexpected state.
+    Negotiation,
+
+    /// Failed to handle an event from a media server or a peer connection.
+    EventHandling,
+}
+
+impl From<error_report::NonFatalErrorKind> for NonFatalErrorKind {
+    fn from(that: error_report::NonFatalErrorKind) -> Self {
+        use error_report::NonFatalErrorKind as K;
+
+        match that {
+            K::Stats => Self::Stats,
+            K::IceCandidate => Self::IceCandidate,
+            K::Encoding => Self::Encoding,
+            K::RemoteTrack => Self::RemoteTrack,
+            K::LocalTrack => Self::LocalTrack,
+            K::Negotiation => Self::Negotiation,
+            K::EventHandling => Self::EventHandling,
+        }
+    }
+}
+
+/// Error happened in a `Room` without breaking it, so it's only reported via
+/// the `RoomHandle.on_error()` callback.
+#[wasm_bindgen]
+#[derive(Debug, From)]
+pub struct NonFatalError(error_report::NonFatalError);
+
+#[wasm_bindgen]
+impl NonFatalError {
+    /// Returns [`NonFatalErrorKind`] of this [`NonFatalError`].
+    #[must_use]
+    pub fn kind(&self) -> NonFatalErrorKind {
+        self.0.kind.into()
+    }
+
+    /// Returns message describing this [`NonFatalError`].
+    #[must_use]
+    pub fn message(&self) -> String {
+        self.0.message.clone()
+    }
+
+    /// Returns stacktrace of this [`NonFatalError`].
+    #[must_use]
+    pub fn trace(&self) -> String {
+        self.0.trace.to_string()
+    }
+
+    /// Indicates whether this error is fatal, which is never the case for a
+    /// [`NonFatalError`].
+    #[allow(clippy::missing_const_for_fn)] // required by `wasm_bindgen`
+    #[allow(clippy::unused_self)] // kept as a method for API consistency
+    #[must_use]
+    pub fn fatal(&self) -> bool {
+        false
+    }
+}
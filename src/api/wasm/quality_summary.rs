@@ -0,0 +1,44 @@
+//! Total time outbound media of a connection with a specific remote `Member`
+//! has spent limited in quality.
+
+use derive_more::From;
+use wasm_bindgen::prelude::*;
+
+use crate::peer;
+
+/// Total time the outbound media of a connection with a specific remote
+/// `Member` has spent in each quality limitation state.
+///
+/// All the durations are in seconds.
+#[wasm_bindgen]
+#[derive(Debug, From)]
+pub struct QualitySummary(peer::QualitySummary);
+
+#[wasm_bindgen]
+impl QualitySummary {
+    /// Returns time spent without any quality limitation.
+    #[must_use]
+    pub fn none(&self) -> f64 {
+        self.0.none.as_secs_f64()
+    }
+
+    /// Returns time spent limited due to CPU load.
+    #[must_use]
+    pub fn cpu(&self) -> f64 {
+        self.0.cpu.as_secs_f64()
+    }
+
+    /// Returns time spent limited due to congestion cues during bandwidth
+    /// estimation.
+    #[must_use]
+    pub fn bandwidth(&self) -> f64 {
+        self.0.bandwidth.as_secs_f64()
+    }
+
+    /// Returns time spent limited for a reason other than CPU load or
+    /// bandwidth.
+    #[must_use]
+    pub fn other(&self) -> f64 {
+        self.0.other.as_secs_f64()
+    }
+}
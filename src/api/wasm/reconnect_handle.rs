@@ -7,12 +7,14 @@ use wasm_bindgen_futures::future_to_promise;
 
 use crate::rpc;
 
-use super::Error;
+use super::{Error, ReconnectOutcome};
 
 /// Handle that JS side can reconnect to a media server with when a connection
 /// is lost.
 ///
 /// This handle is passed into a [`RoomHandle.on_connection_loss`] callback.
+/// The same handle is passed on every connection loss of a `Room`, so it may be
+/// stored and used across repeated outages.
 ///
 /// Like all the handles it contains a weak reference to the object that is
 /// managed by Rust, so its methods will fail if a weak reference could not be
@@ -25,11 +27,29 @@ pub struct ReconnectHandle(rpc::ReconnectHandle);
 
 #[wasm_bindgen]
 impl ReconnectHandle {
+    /// Indicates whether a reconnection is being performed via this
+    /// [`ReconnectHandle`] currently.
+    #[must_use]
+    pub fn is_reconnecting(&self) -> bool {
+        self.0.is_reconnecting()
+    }
+
+    /// Returns number of the attempts performed during the current (or the
+    /// last) reconnection via this [`ReconnectHandle`].
+    #[must_use]
+    pub fn attempt_number(&self) -> u32 {
+        self.0.attempt_number()
+    }
+
     /// Tries to reconnect after the provided delay in milliseconds.
     ///
-    /// If [`RpcSession`] is already reconnecting then a new reconnection
-    /// attempt won't be performed. Instead, it will wait for the first
-    /// reconnection attempt result and use it.
+    /// Resolves with a [`ReconnectOutcome`], being
+    /// [`ReconnectOutcome::AlreadyConnected`] right away if the connection is
+    /// established already.
+    ///
+    /// If a reconnection is being performed via this [`ReconnectHandle`]
+    /// already, then a new one won't be started. Instead, the result of the
+    /// ongoing one will be used.
     ///
     /// # Errors
     ///
@@ -38,15 +58,15 @@ impl ReconnectHandle {
     /// With a [`StateError`] if the underlying pointer has been freed.
     ///
     /// [`RpcClientException`]: crate::api::err::RpcClientException
-    /// [`RpcSession`]: rpc::RpcSession
     /// [`StateError`]: crate::api::err::StateError
     pub fn reconnect_with_delay(&self, delay_ms: u32) -> Promise {
         let this = self.0.clone();
         future_to_promise(async move {
-            this.reconnect_with_delay(delay_ms)
+            let outcome = this
+                .reconnect_with_delay(delay_ms)
                 .await
                 .map_err(Error::from)?;
-            Ok(JsValue::UNDEFINED)
+            Ok(ReconnectOutcome::from(outcome).into())
         })
     }
 
@@ -67,11 +87,16 @@ impl ReconnectHandle {
     /// `0.0`. This might cause a busy loop, so it's not recommended.
     ///
     /// Max elapsed time can be limited with an optional `max_elapsed_time_ms`
-    /// argument.
+    /// argument. Once it's reached, reconnecting is given up like via
+    /// [`ReconnectHandle::cancel()`].
+    ///
+    /// Resolves with a [`ReconnectOutcome`], being
+    /// [`ReconnectOutcome::AlreadyConnected`] right away if the connection is
+    /// established already.
     ///
-    /// If [`RpcSession`] is already reconnecting then new reconnection attempt
-    /// won't be performed. Instead, it will wait for the first reconnection
-    /// attempt result and use it here.
+    /// If a reconnection is being performed via this [`ReconnectHandle`]
+    /// already, then a new one won't be started. Instead, the result of the
+    /// ongoing one will be used.
     ///
     /// # Errors
     ///
@@ -91,15 +116,31 @@ impl ReconnectHandle {
     ) -> Promise {
         let this = self.0.clone();
         future_to_promise(async move {
-            this.reconnect_with_backoff(
-                starting_delay_ms,
-                multiplier.into(),
-                max_delay,
-                max_elapsed_time_ms,
-            )
-            .await
-            .map_err(Error::from)?;
-            Ok(JsValue::UNDEFINED)
+            let outcome = this
+                .reconnect_with_backoff(
+                    starting_delay_ms,
+                    multiplier.into(),
+                    max_delay,
+                    max_elapsed_time_ms,
+                )
+                .await
+                .map_err(Error::from)?;
+            Ok(ReconnectOutcome::from(outcome).into())
         })
     }
+
+    /// Gives up reconnecting to a media server, so the `Room` is closed and
+    /// its [`RoomHandle.on_close`] callback is invoked with a
+    /// [`RoomCloseReason`] having `ConnectionLost` reason and being an error.
+    ///
+    /// The ongoing reconnection (if any) fails once its current attempt is
+    /// over.
+    ///
+    /// Does nothing if the connection is established.
+    ///
+    /// [`RoomCloseReason`]: crate::api::RoomCloseReason
+    /// [`RoomHandle.on_close`]: crate::api::RoomHandle.on_close
+    pub fn cancel(&self) {
+        self.0.cancel();
+    }
 }
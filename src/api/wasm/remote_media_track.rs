@@ -2,11 +2,17 @@
 //!
 //! [1]: https://w3.org/TR/mediacapture-streams/#dom-mediastreamtrack
 
+use std::time::Duration;
+
 use derive_more::{From, Into};
+use futures::FutureExt as _;
 use wasm_bindgen::prelude::*;
 
 use crate::{
-    api::{MediaDirection, MediaKind, MediaSourceKind},
+    api::{
+        CodecInfo, DisplaySurface, Error, MediaDirection, MediaKind,
+        MediaSourceKind,
+    },
     media::track::remote,
 };
 
@@ -19,12 +25,24 @@ pub struct RemoteMediaTrack(remote::Track);
 
 #[wasm_bindgen]
 impl RemoteMediaTrack {
-    /// Returns the underlying [MediaStreamTrack][1].
+    /// Returns a clone of the underlying [MediaStreamTrack][1].
+    ///
+    /// Lifetime of the returned [MediaStreamTrack][1] is independent of this
+    /// [`RemoteMediaTrack`], so it keeps rendering even after this
+    /// [`RemoteMediaTrack`] is freed. It's stopped only when the underlying
+    /// [MediaStreamTrack][1] is stopped or ended, which is signalled by the
+    /// [`RemoteMediaTrack::on_stopped()`] callback.
+    ///
+    /// The same [MediaStreamTrack][1] is returned on subsequent calls.
     ///
     /// [1]: https://w3.org/TR/mediacapture-streams/#dom-mediastreamtrack
     #[must_use]
     pub fn get_track(&self) -> web_sys::MediaStreamTrack {
-        Clone::clone(self.0.get_track().as_ref())
+        // PANIC: Unwrapping is OK here, because forking a track is done
+        //        synchronously on web platform.
+        #[allow(clippy::unwrap_used)]
+        let track = self.0.get_track_clone().now_or_never().unwrap();
+        Clone::clone(AsRef::<web_sys::MediaStreamTrack>::as_ref(&*track))
     }
 
     /// Indicates whether this [`RemoteMediaTrack`] is muted.
@@ -44,10 +62,51 @@ impl RemoteMediaTrack {
     }
 
     /// Sets callback to invoke when this [`RemoteMediaTrack`] is stopped.
+    ///
+    /// The [MediaStreamTrack][1] returned by the
+    /// [`RemoteMediaTrack::get_track()`] is stopped by then as well.
+    ///
+    /// [1]: https://w3.org/TR/mediacapture-streams/#dom-mediastreamtrack
     pub fn on_stopped(&self, cb: js_sys::Function) {
         self.0.on_stopped(cb.into());
     }
 
+    /// Sets callback to invoke when this [`RemoteMediaTrack`] stops decoding
+    /// new frames while being enabled and unmuted (for example, because of a
+    /// packet loss), even though the [MediaStreamTrack][1] stays live.
+    ///
+    /// Freezes are detected only while either this or the
+    /// [`RemoteMediaTrack::on_unfreeze()`] callback is set.
+    ///
+    /// [1]: https://w3.org/TR/mediacapture-streams/#dom-mediastreamtrack
+    pub fn on_freeze(&self, cb: js_sys::Function) {
+        self.0.on_freeze(cb.into());
+    }
+
+    /// Sets callback to invoke when this [`RemoteMediaTrack`] resumes decoding
+    /// new frames after being frozen.
+    pub fn on_unfreeze(&self, cb: js_sys::Function) {
+        self.0.on_unfreeze(cb.into());
+    }
+
+    /// Indicates whether this [`RemoteMediaTrack`] is frozen (stopped
+    /// decoding new frames while being enabled and unmuted).
+    ///
+    /// Always `false` unless the [`RemoteMediaTrack::on_freeze()`] or the
+    /// [`RemoteMediaTrack::on_unfreeze()`] callback is set.
+    #[must_use]
+    pub fn is_frozen(&self) -> bool {
+        self.0.is_frozen()
+    }
+
+    /// Sets the period (in milliseconds) of not decoding new frames this
+    /// [`RemoteMediaTrack`] is considered frozen after.
+    ///
+    /// Defaults to `2000` milliseconds.
+    pub fn set_freeze_timeout(&self, ms: u32) {
+        self.0.set_freeze_timeout(Duration::from_millis(ms.into()));
+    }
+
     /// Sets callback to invoke whenever this [`RemoteMediaTrack`]'s general
     /// [`MediaDirection`] changes.
     pub fn on_media_direction_changed(&self, cb: js_sys::Function) {
@@ -73,10 +132,96 @@ impl RemoteMediaTrack {
         self.0.media_source_kind().into()
     }
 
+    /// Returns a [`DisplaySurface`] captured by this [`RemoteMediaTrack`], or
+    /// [`None`] if it isn't a [`MediaSourceKind::Display`] one.
+    ///
+    /// [`DisplaySurface::Unknown`] is returned until the partner reports the
+    /// actual [`DisplaySurface`].
+    #[must_use]
+    pub fn display_surface(&self) -> Option<DisplaySurface> {
+        self.0.display_surface().map(Into::into)
+    }
+
     /// Returns the current general [`MediaDirection`] of this
     /// [`RemoteMediaTrack`].
     #[must_use]
     pub fn media_direction(&self) -> MediaDirection {
         self.0.media_direction().into()
     }
+
+    /// Requests the sender of this [`RemoteMediaTrack`] to encode a new
+    /// keyframe.
+    ///
+    /// # Errors
+    ///
+    /// With a [`NotSupportedException`] always, since neither the browsers
+    /// allow sending a [PLI][1] from a receiver, nor a media server provides a
+    /// command for requesting a keyframe.
+    ///
+    /// [`NotSupportedException`]: crate::api::err::NotSupportedException
+    /// [1]: https://tools.ietf.org/html/rfc4585#section-6.3.1
+    pub fn request_keyframe(&self) -> Result<(), JsValue> {
+        self.0
+            .request_keyframe()
+            .map_err(Error::from)
+            .map_err(Into::into)
+    }
+
+    /// Indicates whether the browser allows setting a jitter buffer target of
+    /// this [`RemoteMediaTrack`] via the
+    /// [`RemoteMediaTrack::set_jitter_buffer_target()`].
+    #[must_use]
+    pub fn is_jitter_buffer_target_supported(&self) -> bool {
+        self.0.is_jitter_buffer_target_supported()
+    }
+
+    /// Sets the target duration (in milliseconds) of media to be held in the
+    /// jitter buffer of this [`RemoteMediaTrack`], mapped to the
+    /// [jitterBufferTarget][1] (or the legacy `playoutDelayHint`).
+    ///
+    /// Values outside of the `[0, 4000]` range are clamped to it.
+    ///
+    /// Returns the clamped target being set.
+    ///
+    /// # Errors
+    ///
+    /// With a [`NotSupportedException`] if the browser doesn't allow setting a
+    /// jitter buffer target.
+    ///
+    /// With an [`InternalException`] if the browser rejects the target.
+    ///
+    /// [`InternalException`]: crate::api::err::InternalException
+    /// [`NotSupportedException`]: crate::api::err::NotSupportedException
+    /// [1]: https://w3.org/TR/webrtc-extensions
+    pub fn set_jitter_buffer_target(&self, ms: f64) -> Result<f64, JsValue> {
+        let max = remote::Track::MAX_JITTER_BUFFER_TARGET.as_secs_f64();
+        let target = Duration::from_secs_f64((ms / 1000.0).max(0.0).min(max));
+        self.0
+            .set_jitter_buffer_target(target)
+            .map(|t| t.as_secs_f64() * 1000.0)
+            .map_err(Error::from)
+            .map_err(Into::into)
+    }
+
+    /// Returns the effective jitter buffer target (in milliseconds) of this
+    /// [`RemoteMediaTrack`], as it's reported by the browser.
+    ///
+    /// Returns `undefined` if the browser doesn't allow setting a jitter
+    /// buffer target, or no target is set.
+    #[must_use]
+    pub fn jitter_buffer_target(&self) -> Option<f64> {
+        self.0
+            .jitter_buffer_target()
+            .map(|d| d.as_secs_f64() * 1000.0)
+    }
+
+    /// Returns [`CodecInfo`] of the decoder last reported by the browser for
+    /// this [`RemoteMediaTrack`], if any.
+    ///
+    /// Is updated whenever the browser switches to another decoder
+    /// implementation.
+    #[must_use]
+    pub fn decoder_info(&self) -> Option<CodecInfo> {
+        self.0.decoder_info().map(Into::into)
+    }
 }
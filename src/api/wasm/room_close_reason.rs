@@ -5,7 +5,7 @@
 use derive_more::From;
 use wasm_bindgen::prelude::*;
 
-use crate::room;
+use crate::{api::SessionMetrics, room};
 
 /// Reason of why a [`Room`] is closed.
 ///
@@ -43,4 +43,12 @@ impl RoomCloseReason {
     pub fn is_err(&self) -> bool {
         self.0.is_err()
     }
+
+    /// Returns [`SessionMetrics`] of the [`Room`] accumulated by its closing.
+    ///
+    /// [`Room`]: room::Room
+    #[must_use]
+    pub fn session_metrics(&self) -> SessionMetrics {
+        self.0.session_metrics().into()
+    }
 }
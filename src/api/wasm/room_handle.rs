@@ -2,14 +2,21 @@
 //!
 //! [`Room`]: room::Room
 
+use std::time::Duration;
+
 use derive_more::{From, Into};
 use js_sys::Promise;
 use wasm_bindgen::{prelude::*, JsValue};
 use wasm_bindgen_futures::future_to_promise;
 
 use crate::{
-    api::{MediaSourceKind, MediaStreamSettings},
-    room,
+    api::{
+        abort_handle, AbortablePromise, AdaptivePolicy, ConnectionHandle,
+        IceCandidateFilter, JoinProgressUpdate, MediaSourceKind,
+        MediaStateOutcome, MediaStreamSettings, MemberMediaStateChange,
+        RoomMediaPolicy, RtcConfiguration, SessionMetrics, TransceiverInfo,
+    },
+    platform, room,
 };
 
 use super::Error;
@@ -45,11 +52,14 @@ impl RoomHandle {
     /// With a [`FormatException`] if the provided `token` string has bad
     /// format.
     ///
-    /// With a [`RpcClientException`] if could not connect to a media server.
+    /// With a [`RpcClientException`] if could not connect to a media server,
+    /// or having a `WebRtcUnavailable` [`RpcClientExceptionKind`] (without any
+    /// connection attempts) if WebRTC is unavailable in the current browser.
     ///
     /// [`FormatException`]: crate::api::err::FormatException
     /// [`Room`]: room::Room
     /// [`RpcClientException`]: crate::api::err::RpcClientException
+    /// [`RpcClientExceptionKind`]: crate::api::err::RpcClientExceptionKind
     /// [`StateError`]: crate::api::err::StateError
     pub fn join(&self, token: String) -> Promise {
         let this = self.0.clone();
@@ -60,9 +70,38 @@ impl RoomHandle {
         })
     }
 
+    /// Same as [`RoomHandle::join()`], but returns an [`AbortablePromise`],
+    /// whose [`AbortHandle`] aborts joining.
+    ///
+    /// Aborting closes this [`Room`] (so it cannot be joined anymore),
+    /// releasing its connection with a media server.
+    ///
+    /// # Errors
+    ///
+    /// With an [`AbortedException`][0] if joining is aborted.
+    ///
+    /// Otherwise, the same as [`RoomHandle::join()`].
+    ///
+    /// [`AbortHandle`]: crate::api::AbortHandle
+    /// [`Room`]: room::Room
+    /// [0]: crate::api::err::AbortedException
+    pub fn join_abortable(&self, token: String) -> AbortablePromise {
+        let this = self.0.clone();
+
+        abort_handle::abortable(|abort| async move {
+            this.join_abortable(token, abort)
+                .await
+                .map_err(Error::from)?;
+            Ok(JsValue::UNDEFINED)
+        })
+    }
+
     /// Sets callback, invoked when a new [`Connection`] with some remote
     /// `Member` is established.
     ///
+    /// The callback is immediately invoked for all the already established
+    /// [`Connection`]s in the order of their creation.
+    ///
     /// # Errors
     ///
     /// With a [`StateError`] if the underlying pointer has been freed.
@@ -79,6 +118,104 @@ impl RoomHandle {
             .map_err(Into::into)
     }
 
+    /// Returns an array of new [`ConnectionHandle`]s to all the currently
+    /// established connections with remote `Member`s, in the order of their
+    /// creation.
+    ///
+    /// Freeing the returned [`ConnectionHandle`]s doesn't affect the
+    /// connections.
+    ///
+    /// # Errors
+    ///
+    /// With a [`StateError`] if the underlying pointer has been freed.
+    ///
+    /// [`StateError`]: crate::api::err::StateError
+    pub fn connections(&self) -> Result<js_sys::Array, JsValue> {
+        self.0
+            .connections()
+            .map(|handles| {
+                handles
+                    .into_iter()
+                    .map(|h| JsValue::from(ConnectionHandle::from(h)))
+                    .collect()
+            })
+            .map_err(Error::from)
+            .map_err(Into::into)
+    }
+
+    /// Returns a new [`ConnectionHandle`] to the currently established
+    /// connection with the provided remote `Member`, or `null` if there is no
+    /// such connection.
+    ///
+    /// Freeing the returned [`ConnectionHandle`] doesn't affect the
+    /// connection.
+    ///
+    /// # Errors
+    ///
+    /// With a [`StateError`] if the underlying pointer has been freed.
+    ///
+    /// [`StateError`]: crate::api::err::StateError
+    pub fn connection(
+        &self,
+        remote_member_id: String,
+    ) -> Result<Option<ConnectionHandle>, JsValue> {
+        self.0
+            .connection(&remote_member_id.into())
+            .map(|handle| handle.map(ConnectionHandle::from))
+            .map_err(Error::from)
+            .map_err(Into::into)
+    }
+
+    /// Sets callback, invoked with a [`JoinProgressUpdate`] whenever joining
+    /// this [`Room`] reaches its next phase.
+    ///
+    /// The callback is immediately invoked with all the already reached
+    /// phases.
+    ///
+    /// # Errors
+    ///
+    /// With a [`StateError`] if the underlying pointer has been freed.
+    ///
+    /// [`Room`]: room::Room
+    /// [`StateError`]: crate::api::err::StateError
+    pub fn on_join_progress(
+        &self,
+        cb: js_sys::Function,
+    ) -> Result<(), JsValue> {
+        let cb = platform::Function::<JoinProgressUpdate>::from(cb);
+        self.0
+            .on_join_progress(move |update| {
+                cb.call1(JoinProgressUpdate::from(update));
+            })
+            .map_err(Error::from)
+            .map_err(Into::into)
+    }
+
+    /// Sets callback, invoked with a [`MemberMediaStateChange`] whenever media
+    /// of any remote `Member` is enabled, disabled, muted or unmuted by this
+    /// `Member`.
+    ///
+    /// The callback is immediately invoked with the current states of all the
+    /// remote `Member`s' media.
+    ///
+    /// # Errors
+    ///
+    /// With a [`StateError`] if the underlying pointer has been freed.
+    ///
+    /// [`StateError`]: crate::api::err::StateError
+    pub fn on_member_media_state_changed(
+        &self,
+        cb: js_sys::Function,
+    ) -> Result<(), JsValue> {
+        let cb = platform::Function::<MemberMediaStateChange>::from(cb);
+        self.0
+            .on_member_media_state_changed(move |change| {
+                cb.call1(MemberMediaStateChange::from(change));
+            })
+            .map_err(Error::from)
+            .map_err(Into::into)
+    }
+
     /// Sets `on_close` callback, invoked when this [`Room`] is closed,
     /// providing a [`RoomCloseReason`].
     ///
@@ -104,6 +241,9 @@ impl RoomHandle {
     /// 2. `enable_audio`/`enable_video` is called.
     /// 3. [`MediaStreamSettings`] is updated via `set_local_media_settings`.
     ///
+    /// The callback is immediately invoked for all the alive
+    /// [`LocalMediaTrack`]s added already, in the order of their addition.
+    ///
     /// # Errors
     ///
     /// With a [`StateError`] if the underlying pointer has been freed.
@@ -154,6 +294,142 @@ impl RoomHandle {
             .map_err(Into::into)
     }
 
+    /// Sets `on_adaptation` callback, invoked with an [`Adaptation`] whenever
+    /// outbound video is adapted according to the [`AdaptivePolicy`] set via
+    /// [`RoomHandle::set_adaptive_policy()`].
+    ///
+    /// # Errors
+    ///
+    /// With a [`StateError`] if the underlying pointer has been freed.
+    ///
+    /// [`Adaptation`]: crate::api::Adaptation
+    /// [`StateError`]: crate::api::err::StateError
+    pub fn on_adaptation(&self, cb: js_sys::Function) -> Result<(), JsValue> {
+        self.0
+            .on_adaptation(cb.into())
+            .map_err(Error::from)
+            .map_err(Into::into)
+    }
+
+    /// Sets `on_codec_fallback` callback, invoked with a [`CodecFallback`]
+    /// whenever an encoder or a decoder of media in this `Room` falls back
+    /// from a hardware implementation to a software one.
+    ///
+    /// # Errors
+    ///
+    /// With a [`StateError`] if the underlying pointer has been freed.
+    ///
+    /// [`CodecFallback`]: crate::api::CodecFallback
+    /// [`StateError`]: crate::api::err::StateError
+    pub fn on_codec_fallback(
+        &self,
+        cb: js_sys::Function,
+    ) -> Result<(), JsValue> {
+        self.0
+            .on_codec_fallback(cb.into())
+            .map_err(Error::from)
+            .map_err(Into::into)
+    }
+
+    /// Sets `on_error` callback, invoked with a [`NonFatalError`] whenever an
+    /// error happens in this `Room` without breaking it.
+    ///
+    /// Identical errors (having the same kind and message) are reported at
+    /// most once per 10 seconds.
+    ///
+    /// # Errors
+    ///
+    /// With a [`StateError`] if the underlying pointer has been freed.
+    ///
+    /// [`NonFatalError`]: crate::api::NonFatalError
+    /// [`StateError`]: crate::api::err::StateError
+    pub fn on_error(&self, cb: js_sys::Function) -> Result<(), JsValue> {
+        self.0
+            .on_error(cb.into())
+            .map_err(Error::from)
+            .map_err(Into::into)
+    }
+
+    /// Sets `on_forced_media_state` callback, invoked with a
+    /// [`ForcedMediaState`] whenever a media server forces a state of the
+    /// local media (a moderator mutes it, for example), rather than it's
+    /// changed via this [`RoomHandle`].
+    ///
+    /// Subsequent attempts to change the forced state via this [`RoomHandle`]
+    /// rejected by the media server fail with a
+    /// [`MediaStateTransitionException`][0] of a `ForbiddenByServer` kind.
+    ///
+    /// # Errors
+    ///
+    /// With a [`StateError`] if the underlying pointer has been freed.
+    ///
+    /// [`ForcedMediaState`]: crate::api::ForcedMediaState
+    /// [`StateError`]: crate::api::err::StateError
+    /// [0]: crate::api::err::MediaStateTransitionException
+    pub fn on_forced_media_state(
+        &self,
+        cb: js_sys::Function,
+    ) -> Result<(), JsValue> {
+        self.0
+            .on_forced_media_state(cb.into())
+            .map_err(Error::from)
+            .map_err(Into::into)
+    }
+
+    /// Sets `on_sdp` callback, invoked with an [`SdpEvent`] for every SDP
+    /// offer or answer applied as a local or remote description, for debugging
+    /// interoperability issues.
+    ///
+    /// If `redact` is `true`, then ICE credentials (`ice-ufrag` and `ice-pwd`)
+    /// and candidate IP addresses are masked in the provided SDPs, so they can
+    /// be logged safely.
+    ///
+    /// The provided SDPs are read-only copies, so the callback cannot affect
+    /// the negotiation.
+    ///
+    /// # Errors
+    ///
+    /// With a [`StateError`] if the underlying pointer has been freed.
+    ///
+    /// [`SdpEvent`]: crate::api::SdpEvent
+    /// [`StateError`]: crate::api::err::StateError
+    pub fn on_sdp(
+        &self,
+        cb: js_sys::Function,
+        redact: bool,
+    ) -> Result<(), JsValue> {
+        self.0
+            .on_sdp(cb.into(), redact)
+            .map_err(Error::from)
+            .map_err(Into::into)
+    }
+
+    /// Returns a snapshot of all the negotiated transceivers in this [`Room`]
+    /// as an array of [`TransceiverInfo`]s, for debugging purposes.
+    ///
+    /// Resolves to an empty array if there are no negotiated transceivers yet.
+    ///
+    /// # Errors
+    ///
+    /// With a [`StateError`] if the underlying pointer has been freed.
+    ///
+    /// [`Room`]: room::Room
+    /// [`StateError`]: crate::api::err::StateError
+    pub fn transceivers_info(&self) -> Promise {
+        let infos = self.0.transceivers_info().map_err(Error::from);
+
+        future_to_promise(async move {
+            Ok(infos?
+                .await
+                .into_iter()
+                .fold(js_sys::Array::new(), |infos, info| {
+                    _ = infos.push(&JsValue::from(TransceiverInfo::from(info)));
+                    infos
+                })
+                .into())
+        })
+    }
+
     /// Updates this [`Room`]s [`MediaStreamSettings`]. This affects all
     /// [`PeerConnection`]s in this [`Room`]. If [`MediaStreamSettings`] is
     /// configured for some [`Room`], then this [`Room`] can only send media
@@ -177,6 +453,12 @@ impl RoomHandle {
     /// If recovering from fail state isn't possible then affected media types
     /// will be disabled.
     ///
+    /// If optional `seamless` option is set to `true` along with the
+    /// `stop_first`, then only the affected [`LocalMediaTrack`]s recaptured
+    /// from the same device are dropped first. The other ones are replaced in
+    /// the senders by the newly captured ones and dropped only then, so the
+    /// remote side sees no gap in the video while switching cameras.
+    ///
     /// # Errors
     ///
     /// With a [`StateError`] if the underlying pointer has been freed.
@@ -195,6 +477,7 @@ impl RoomHandle {
         settings: &MediaStreamSettings,
         stop_first: bool,
         rollback_on_fail: bool,
+        seamless: Option<bool>,
     ) -> Promise {
         let this = self.0.clone();
         let settings = settings.clone();
@@ -204,6 +487,46 @@ impl RoomHandle {
                 settings.into(),
                 stop_first,
                 rollback_on_fail,
+                seamless.unwrap_or_default(),
+            )
+            .await
+            .map_err(Error::from)?;
+            Ok(JsValue::UNDEFINED)
+        })
+    }
+
+    /// Same as [`RoomHandle::set_local_media_settings()`], but returns an
+    /// [`AbortablePromise`], whose [`AbortHandle`] aborts the update.
+    ///
+    /// Aborting stops the [`LocalMediaTrack`]s being acquired at the moment,
+    /// while the ones already applied are kept.
+    ///
+    /// # Errors
+    ///
+    /// With an [`AbortedException`][0] if the update is aborted.
+    ///
+    /// Otherwise, the same as [`RoomHandle::set_local_media_settings()`].
+    ///
+    /// [`AbortHandle`]: crate::api::AbortHandle
+    /// [`LocalMediaTrack`]: crate::api::LocalMediaTrack
+    /// [0]: crate::api::err::AbortedException
+    pub fn set_local_media_settings_abortable(
+        &self,
+        settings: &MediaStreamSettings,
+        stop_first: bool,
+        rollback_on_fail: bool,
+        seamless: Option<bool>,
+    ) -> AbortablePromise {
+        let this = self.0.clone();
+        let settings = settings.clone();
+
+        abort_handle::abortable(|abort| async move {
+            this.set_local_media_settings_abortable(
+                settings.into(),
+                stop_first,
+                rollback_on_fail,
+                seamless.unwrap_or_default(),
+                abort,
             )
             .await
             .map_err(Error::from)?;
@@ -211,15 +534,299 @@ impl RoomHandle {
         })
     }
 
+    /// Sets the default [`MediaStreamSettings`] of this [`Room`], applied to
+    /// all the subsequent local media acquisitions.
+    ///
+    /// The constraints set via [`RoomHandle::set_local_media_settings()`] win
+    /// over the default ones field-by-field.
+    ///
+    /// # Errors
+    ///
+    /// With a [`StateError`] if the underlying pointer has been freed.
+    ///
+    /// [`Room`]: room::Room
+    /// [`StateError`]: crate::api::err::StateError
+    pub fn set_default_media_settings(
+        &self,
+        settings: &MediaStreamSettings,
+    ) -> Result<(), JsValue> {
+        self.0
+            .set_default_media_settings(settings.clone().into())
+            .map_err(Error::from)
+            .map_err(Into::into)
+    }
+
+    /// Returns the effective [`MediaStreamSettings`] of this [`Room`], being
+    /// the ones set via [`RoomHandle::set_local_media_settings()`] merged with
+    /// the ones set via [`RoomHandle::set_default_media_settings()`].
+    ///
+    /// # Errors
+    ///
+    /// With a [`StateError`] if the underlying pointer has been freed.
+    ///
+    /// [`Room`]: room::Room
+    /// [`StateError`]: crate::api::err::StateError
+    pub fn effective_media_settings(
+        &self,
+    ) -> Result<MediaStreamSettings, JsValue> {
+        self.0
+            .effective_media_settings()
+            .map(Into::into)
+            .map_err(Error::from)
+            .map_err(Into::into)
+    }
+
+    /// Exports a JSON snapshot of the media states intended in this [`Room`]
+    /// (enabled and muted states, chosen devices), to be restored via
+    /// [`Jason::restore_room()`] after a page refresh.
+    ///
+    /// The snapshot contains no credentials.
+    ///
+    /// # Errors
+    ///
+    /// With a [`StateError`] if the underlying pointer has been freed.
+    ///
+    /// [`Jason::restore_room()`]: crate::api::Jason::restore_room
+    /// [`Room`]: room::Room
+    /// [`StateError`]: crate::api::err::StateError
+    pub fn export_state(&self) -> Result<String, JsValue> {
+        self.0
+            .export_state()
+            .map(|snapshot| snapshot.to_json())
+            .map_err(Error::from)
+            .map_err(Into::into)
+    }
+
+    /// Returns the current media states of this [`Room`] as a plain JS
+    /// object, synchronously.
+    ///
+    /// The object has `send_device_audio`, `send_display_audio`,
+    /// `send_device_video` and `send_display_video` fields, each being an
+    /// object with `enabled` and `muted` boolean fields, and `recv_audio`,
+    /// `recv_device_video` and `recv_display_video` boolean fields.
+    ///
+    /// The states are updated before any callback reporting their change is
+    /// invoked, so are always consistent with the callbacks.
+    ///
+    /// # Errors
+    ///
+    /// With a [`StateError`] if the underlying pointer has been freed.
+    ///
+    /// [`Room`]: room::Room
+    /// [`StateError`]: crate::api::err::StateError
+    pub fn media_state(&self) -> Result<JsValue, JsValue> {
+        self.0
+            .media_state()
+            .map_err(Error::from)
+            .map_err(JsValue::from)
+            .and_then(|state| js_sys::JSON::parse(&state.to_json()))
+    }
+
+    /// Returns a JSON array of the last `limit` (or all, if not provided)
+    /// lifecycle events happened in this [`Room`] (joins, negotiations,
+    /// state transitions, errors, reconnects) with their timestamps, to be
+    /// attached to bug reports.
+    ///
+    /// SDP bodies, credentials and tokens are redacted.
+    ///
+    /// # Errors
+    ///
+    /// With a [`StateError`] if the underlying pointer has been freed.
+    ///
+    /// [`Room`]: room::Room
+    /// [`StateError`]: crate::api::err::StateError
+    pub fn dump_events(&self, limit: Option<u32>) -> Result<String, JsValue> {
+        let limit = limit.map(|l| usize::try_from(l).unwrap_or(usize::MAX));
+        self.0
+            .dump_events(limit)
+            .map_err(Error::from)
+            .map_err(Into::into)
+    }
+
+    /// Returns [`SessionMetrics`] of this [`Room`]: numbers of the connection
+    /// losses, ICE restarts, session restores and new sessions, and total
+    /// time spent disconnected, accumulated across all its reconnects.
+    ///
+    /// Every change of the [`SessionMetrics`] is recorded to the
+    /// [`RoomHandle::dump_events()`] as well.
+    ///
+    /// # Errors
+    ///
+    /// With a [`StateError`] if the underlying pointer has been freed.
+    ///
+    /// [`Room`]: room::Room
+    /// [`StateError`]: crate::api::err::StateError
+    pub fn session_metrics(&self) -> Result<SessionMetrics, JsValue> {
+        self.0
+            .session_metrics()
+            .map(SessionMetrics::from)
+            .map_err(Error::from)
+            .map_err(Into::into)
+    }
+
+    /// Sets the maximum number of the lifecycle events retained by this
+    /// [`Room`] for the [`RoomHandle::dump_events()`].
+    ///
+    /// Defaults to `500` events.
+    ///
+    /// # Errors
+    ///
+    /// With a [`StateError`] if the underlying pointer has been freed.
+    ///
+    /// [`Room`]: room::Room
+    /// [`StateError`]: crate::api::err::StateError
+    pub fn set_event_log_capacity(&self, capacity: u32) -> Result<(), JsValue> {
+        self.0
+            .set_event_log_capacity(
+                usize::try_from(capacity).unwrap_or(usize::MAX),
+            )
+            .map_err(Error::from)
+            .map_err(Into::into)
+    }
+
+    /// Returns an estimate of the memory (in bytes) held by the lifecycle
+    /// events retained by this [`Room`] for the
+    /// [`RoomHandle::dump_events()`].
+    ///
+    /// # Errors
+    ///
+    /// With a [`StateError`] if the underlying pointer has been freed.
+    ///
+    /// [`Room`]: room::Room
+    /// [`StateError`]: crate::api::err::StateError
+    pub fn event_log_memory_usage(&self) -> Result<u32, JsValue> {
+        self.0
+            .event_log_memory_usage()
+            .map(|bytes| u32::try_from(bytes).unwrap_or(u32::MAX))
+            .map_err(Error::from)
+            .map_err(Into::into)
+    }
+
+    /// Sets the window (in milliseconds) during which simultaneous track
+    /// changes (enabling, disabling, muting or unmuting) are collected into a
+    /// single renegotiation.
+    ///
+    /// Defaults to `50` milliseconds. Doesn't delay the initial negotiation.
+    ///
+    /// # Errors
+    ///
+    /// With a [`StateError`] if the underlying pointer has been freed.
+    ///
+    /// [`StateError`]: crate::api::err::StateError
+    pub fn set_negotiation_debounce(&self, ms: u32) -> Result<(), JsValue> {
+        self.0
+            .set_negotiation_debounce(Duration::from_millis(ms.into()))
+            .map_err(Error::from)
+            .map_err(Into::into)
+    }
+
+    /// Enables or disables the periodic stats collection in this [`Room`],
+    /// setting its interval in milliseconds (`1000` if not provided).
+    ///
+    /// While disabled, no stats are collected at all: stats-driven callbacks
+    /// stop firing and all the stats-driven accounting is frozen. Cumulative
+    /// counters (like `TrafficTotals`) continue from their last values once
+    /// re-enabled, with the gap flagged in the
+    /// `TrafficTotals.collection_gaps()`.
+    ///
+    /// # Errors
+    ///
+    /// With a [`StateError`] if the underlying pointer has been freed.
+    ///
+    /// [`StateError`]: crate::api::err::StateError
+    pub fn set_stats_collection(
+        &self,
+        enabled: bool,
+        interval_ms: Option<u32>,
+    ) -> Result<(), JsValue> {
+        self.0
+            .set_stats_collection(
+                enabled,
+                interval_ms.map(|ms| Duration::from_millis(ms.into())),
+            )
+            .map_err(Error::from)
+            .map_err(Into::into)
+    }
+
+    /// Sets [`AdaptivePolicy`] of outbound video in this [`Room`].
+    ///
+    /// Outbound video isn't adapted by default. Setting
+    /// [`AdaptivePolicy::disabled()`] restores outbound video degraded by the
+    /// previous [`AdaptivePolicy`].
+    ///
+    /// # Errors
+    ///
+    /// With a [`StateError`] if the underlying pointer has been freed.
+    ///
+    /// [`StateError`]: crate::api::err::StateError
+    pub fn set_adaptive_policy(
+        &self,
+        policy: &AdaptivePolicy,
+    ) -> Result<(), JsValue> {
+        self.0
+            .set_adaptive_policy((*policy).into())
+            .map_err(Error::from)
+            .map_err(Into::into)
+    }
+
+    /// Sets [`IceCandidateFilter`] applied to the locally gathered ICE
+    /// candidates before sending them to a media server, and to the remote
+    /// ones before adding them to [RTCPeerConnection][1]s of this [`Room`].
+    ///
+    /// Should be called before [`RoomHandle::join()`], since the candidates
+    /// exchanged already aren't affected.
+    ///
+    /// # Errors
+    ///
+    /// With a [`StateError`] if the underlying pointer has been freed.
+    ///
+    /// [`StateError`]: crate::api::err::StateError
+    /// [1]: https://w3.org/TR/webrtc#dom-rtcpeerconnection
+    pub fn set_ice_candidate_filter(
+        &self,
+        filter: &IceCandidateFilter,
+    ) -> Result<(), JsValue> {
+        self.0
+            .set_ice_candidate_filter(filter.clone().into())
+            .map_err(Error::from)
+            .map_err(Into::into)
+    }
+
+    /// Sets [`RtcConfiguration`] of the [RTCPeerConnection][1]s of this
+    /// [`Room`].
+    ///
+    /// Should be called before [`RoomHandle::join()`], since the
+    /// [RTCPeerConnection][1]s once created cannot be reconfigured.
+    ///
+    /// # Errors
+    ///
+    /// With a [`StateError`] if the underlying pointer has been freed, or if
+    /// any [RTCPeerConnection][1] of this [`Room`] has been created already.
+    ///
+    /// [`StateError`]: crate::api::err::StateError
+    /// [1]: https://w3.org/TR/webrtc#dom-rtcpeerconnection
+    pub fn set_rtc_configuration(
+        &self,
+        config: &RtcConfiguration,
+    ) -> Result<(), JsValue> {
+        self.0
+            .set_rtc_configuration((*config).into())
+            .map_err(Error::from)
+            .map_err(Into::into)
+    }
+
     /// Mutes outbound audio in this [`Room`].
     ///
+    /// Resolves with a [`MediaStateOutcome`], being
+    /// [`MediaStateOutcome::Superseded`] if a newer opposite change has been
+    /// requested before this one is approved by a media server.
+    ///
     /// # Errors
     ///
     /// With a [`StateError`] if the underlying pointer has been freed.
     ///
-    /// With a [`MediaStateTransitionException`][0] if
-    /// [`RoomHandle::unmute_audio()`] was called while muting or a media server
-    /// didn't approve this state transition.
+    /// With a [`MediaStateTransitionException`][0] if a media server didn't
+    /// approve this state transition.
     ///
     /// [`Room`]: room::Room
     /// [`StateError`]: crate::api::err::StateError
@@ -229,20 +836,23 @@ impl RoomHandle {
 
         let fut = this.mute_audio();
         future_to_promise(async move {
-            fut.await.map_err(Error::from)?;
-            Ok(JsValue::UNDEFINED)
+            let outcome = fut.await.map_err(Error::from)?;
+            Ok(MediaStateOutcome::from(outcome).into())
         })
     }
 
     /// Unmutes outbound audio in this [`Room`].
     ///
+    /// Resolves with a [`MediaStateOutcome`], being
+    /// [`MediaStateOutcome::Superseded`] if a newer opposite change has been
+    /// requested before this one is approved by a media server.
+    ///
     /// # Errors
     ///
     /// With a [`StateError`] if the underlying pointer has been freed.
     ///
-    /// With a [`MediaStateTransitionException`][0] if
-    /// [`RoomHandle::mute_audio()`] was called while unmuting or a media server
-    /// didn't approve this state transition.
+    /// With a [`MediaStateTransitionException`][0] if a media server didn't
+    /// approve this state transition.
     ///
     /// [`Room`]: room::Room
     /// [`StateError`]: crate::api::err::StateError
@@ -252,20 +862,23 @@ impl RoomHandle {
 
         let fut = this.unmute_audio();
         future_to_promise(async move {
-            fut.await.map_err(Error::from)?;
-            Ok(JsValue::UNDEFINED)
+            let outcome = fut.await.map_err(Error::from)?;
+            Ok(MediaStateOutcome::from(outcome).into())
         })
     }
 
     /// Mutes outbound video in this [`Room`].
     ///
+    /// Resolves with a [`MediaStateOutcome`], being
+    /// [`MediaStateOutcome::Superseded`] if a newer opposite change has been
+    /// requested before this one is approved by a media server.
+    ///
     /// # Errors
     ///
     /// With a [`StateError`] if the underlying pointer has been freed.
     ///
-    /// With a [`MediaStateTransitionException`][0] if
-    /// [`RoomHandle::unmute_video()`] was called while muting or a media server
-    /// didn't approve this state transition.
+    /// With a [`MediaStateTransitionException`][0] if a media server didn't
+    /// approve this state transition.
     ///
     /// [`Room`]: room::Room
     /// [`StateError`]: crate::api::err::StateError
@@ -275,20 +888,23 @@ impl RoomHandle {
 
         let fut = this.mute_video(source_kind.map(Into::into));
         future_to_promise(async move {
-            fut.await.map_err(Error::from)?;
-            Ok(JsValue::UNDEFINED)
+            let outcome = fut.await.map_err(Error::from)?;
+            Ok(MediaStateOutcome::from(outcome).into())
         })
     }
 
     /// Unmutes outbound video in this [`Room`].
     ///
+    /// Resolves with a [`MediaStateOutcome`], being
+    /// [`MediaStateOutcome::Superseded`] if a newer opposite change has been
+    /// requested before this one is approved by a media server.
+    ///
     /// # Errors
     ///
     /// With a [`StateError`] if the underlying pointer has been freed.
     ///
-    /// With a [`MediaStateTransitionException`][0] if
-    /// [`RoomHandle::mute_video()`] was called while unmuting or a media server
-    /// didn't approve this state transition.
+    /// With a [`MediaStateTransitionException`][0] if a media server didn't
+    /// approve this state transition.
     ///
     /// [`Room`]: room::Room
     /// [`StateError`]: crate::api::err::StateError
@@ -301,20 +917,23 @@ impl RoomHandle {
 
         let fut = this.unmute_video(source_kind.map(Into::into));
         future_to_promise(async move {
-            fut.await.map_err(Error::from)?;
-            Ok(JsValue::UNDEFINED)
+            let outcome = fut.await.map_err(Error::from)?;
+            Ok(MediaStateOutcome::from(outcome).into())
         })
     }
 
     /// Disables outbound audio in this [`Room`].
     ///
+    /// Resolves with a [`MediaStateOutcome`], being
+    /// [`MediaStateOutcome::Superseded`] if a newer opposite change has been
+    /// requested before this one is approved by a media server.
+    ///
     /// # Errors
     ///
     /// With a [`StateError`] if the underlying pointer has been freed.
     ///
-    /// With a [`MediaStateTransitionException`][0] if
-    /// [`RoomHandle::enable_audio()`] was called while disabling or a media
-    /// server didn't approve this state transition.
+    /// With a [`MediaStateTransitionException`][0] if a media server didn't
+    /// approve this state transition.
     ///
     /// [`Room`]: room::Room
     /// [`StateError`]: crate::api::err::StateError
@@ -324,20 +943,23 @@ impl RoomHandle {
 
         let fut = this.disable_audio();
         future_to_promise(async move {
-            fut.await.map_err(Error::from)?;
-            Ok(JsValue::UNDEFINED)
+            let outcome = fut.await.map_err(Error::from)?;
+            Ok(MediaStateOutcome::from(outcome).into())
         })
     }
 
     /// Enables outbound audio in this [`Room`].
     ///
+    /// Resolves with a [`MediaStateOutcome`], being
+    /// [`MediaStateOutcome::Superseded`] if a newer opposite change has been
+    /// requested before this one is approved by a media server.
+    ///
     /// # Errors
     ///
     /// With a [`StateError`] if the underlying pointer has been freed.
     ///
-    /// With a [`MediaStateTransitionException`][0] if
-    /// [`RoomHandle::disable_audio()`] was called while enabling or a media
-    /// server didn't approve this state transition.
+    /// With a [`MediaStateTransitionException`][0] if a media server didn't
+    /// approve this state transition.
     ///
     /// With a [`LocalMediaInitException`] if a request of platform media
     /// devices access failed.
@@ -351,8 +973,8 @@ impl RoomHandle {
 
         let fut = this.enable_audio();
         future_to_promise(async move {
-            fut.await.map_err(Error::from)?;
-            Ok(JsValue::UNDEFINED)
+            let outcome = fut.await.map_err(Error::from)?;
+            Ok(MediaStateOutcome::from(outcome).into())
         })
     }
 
@@ -360,13 +982,16 @@ impl RoomHandle {
     ///
     /// Affects only video with a specific [`MediaSourceKind`] if specified.
     ///
+    /// Resolves with a [`MediaStateOutcome`], being
+    /// [`MediaStateOutcome::Superseded`] if a newer opposite change has been
+    /// requested before this one is approved by a media server.
+    ///
     /// # Errors
     ///
     /// With a [`StateError`] if the underlying pointer has been freed.
     ///
-    /// With a [`MediaStateTransitionException`][0] if
-    /// [`RoomHandle::enable_video()`] was called while disabling or a media
-    /// server didn't approve this state transition.
+    /// With a [`MediaStateTransitionException`][0] if a media server didn't
+    /// approve this state transition.
     ///
     /// [`StateError`]: crate::api::err::StateError
     /// [0]: crate::api::err::MediaStateTransitionException
@@ -378,8 +1003,8 @@ impl RoomHandle {
 
         let fut = this.disable_video(source_kind.map(Into::into));
         future_to_promise(async move {
-            fut.await.map_err(Error::from)?;
-            Ok(JsValue::UNDEFINED)
+            let outcome = fut.await.map_err(Error::from)?;
+            Ok(MediaStateOutcome::from(outcome).into())
         })
     }
 
@@ -387,13 +1012,16 @@ impl RoomHandle {
     ///
     /// Affects only video with a specific [`MediaSourceKind`] if specified.
     ///
+    /// Resolves with a [`MediaStateOutcome`], being
+    /// [`MediaStateOutcome::Superseded`] if a newer opposite change has been
+    /// requested before this one is approved by a media server.
+    ///
     /// # Errors
     ///
     /// With a [`StateError`] if the underlying pointer has been freed.
     ///
-    /// With a [`MediaStateTransitionException`][0] if
-    /// [`RoomHandle::disable_video()`] was called while enabling or a media
-    /// server didn't approve this state transition.
+    /// With a [`MediaStateTransitionException`][0] if a media server didn't
+    /// approve this state transition.
     ///
     /// With a [`LocalMediaInitException`] if a request of platform media
     /// devices access failed.
@@ -409,20 +1037,27 @@ impl RoomHandle {
 
         let fut = this.enable_video(source_kind.map(Into::into));
         future_to_promise(async move {
-            fut.await.map_err(Error::from)?;
-            Ok(JsValue::UNDEFINED)
+            let outcome = fut.await.map_err(Error::from)?;
+            Ok(MediaStateOutcome::from(outcome).into())
         })
     }
 
     /// Disables inbound audio in this [`Room`].
     ///
+    /// The preference is remembered and applied to all the connections
+    /// created afterwards (e.g. with the `Member`s joined later), until
+    /// toggled back, so it's safe to be used as a room-level mute.
+    ///
+    /// Resolves with a [`MediaStateOutcome`], being
+    /// [`MediaStateOutcome::Superseded`] if a newer opposite change has been
+    /// requested before this one is approved by a media server.
+    ///
     /// # Errors
     ///
     /// With a [`StateError`] if the underlying pointer has been freed.
     ///
-    /// With a [`MediaStateTransitionException`][0] if
-    /// [`RoomHandle::enable_remote_audio()`] was called while disabling or a
-    /// media server didn't approve this state transition.
+    /// With a [`MediaStateTransitionException`][0] if a media server didn't
+    /// approve this state transition.
     ///
     /// [`Room`]: room::Room
     /// [`StateError`]: crate::api::err::StateError
@@ -432,22 +1067,29 @@ impl RoomHandle {
 
         let fut = this.disable_remote_audio();
         future_to_promise(async move {
-            fut.await.map_err(Error::from)?;
-            Ok(JsValue::UNDEFINED)
+            let outcome = fut.await.map_err(Error::from)?;
+            Ok(MediaStateOutcome::from(outcome).into())
         })
     }
 
     /// Disables inbound video in this [`Room`].
     ///
+    /// The preference is remembered and applied to all the connections
+    /// created afterwards (e.g. with the `Member`s joined later), until
+    /// toggled back, so it's safe to be used as a room-level mute.
+    ///
     /// Affects only video with the specific [`MediaSourceKind`], if specified.
     ///
+    /// Resolves with a [`MediaStateOutcome`], being
+    /// [`MediaStateOutcome::Superseded`] if a newer opposite change has been
+    /// requested before this one is approved by a media server.
+    ///
     /// # Errors
     ///
     /// With a [`StateError`] if the underlying pointer has been freed.
     ///
-    /// With a [`MediaStateTransitionException`][0] if
-    /// [`RoomHandle::enable_remote_video()`] was called while disabling or a
-    /// media server didn't approve this state transition.
+    /// With a [`MediaStateTransitionException`][0] if a media server didn't
+    /// approve this state transition.
     ///
     /// [`Room`]: room::Room
     /// [`StateError`]: crate::api::err::StateError
@@ -460,20 +1102,27 @@ impl RoomHandle {
 
         let fut = this.disable_remote_video(source_kind.map(Into::into));
         future_to_promise(async move {
-            fut.await.map_err(Error::from)?;
-            Ok(JsValue::UNDEFINED)
+            let outcome = fut.await.map_err(Error::from)?;
+            Ok(MediaStateOutcome::from(outcome).into())
         })
     }
 
     /// Enables inbound audio in this [`Room`].
     ///
+    /// The preference is remembered and applied to all the connections
+    /// created afterwards (e.g. with the `Member`s joined later), until
+    /// toggled back, so it's safe to be used as a room-level mute.
+    ///
+    /// Resolves with a [`MediaStateOutcome`], being
+    /// [`MediaStateOutcome::Superseded`] if a newer opposite change has been
+    /// requested before this one is approved by a media server.
+    ///
     /// # Errors
     ///
     /// With a [`StateError`] if the underlying pointer has been freed.
     ///
-    /// With a [`MediaStateTransitionException`][0] if
-    /// [`RoomHandle::disable_remote_audio()`] was called while enabling or a
-    /// media server didn't approve this state transition.
+    /// With a [`MediaStateTransitionException`][0] if a media server didn't
+    /// approve this state transition.
     ///
     /// [`Room`]: room::Room
     /// [`StateError`]: crate::api::err::StateError
@@ -483,22 +1132,29 @@ impl RoomHandle {
 
         let fut = this.enable_remote_audio();
         future_to_promise(async move {
-            fut.await.map_err(Error::from)?;
-            Ok(JsValue::UNDEFINED)
+            let outcome = fut.await.map_err(Error::from)?;
+            Ok(MediaStateOutcome::from(outcome).into())
         })
     }
 
     /// Enables inbound video in this [`Room`].
     ///
+    /// The preference is remembered and applied to all the connections
+    /// created afterwards (e.g. with the `Member`s joined later), until
+    /// toggled back, so it's safe to be used as a room-level mute.
+    ///
     /// Affects only video with the specific [`MediaSourceKind`], if specified.
     ///
+    /// Resolves with a [`MediaStateOutcome`], being
+    /// [`MediaStateOutcome::Superseded`] if a newer opposite change has been
+    /// requested before this one is approved by a media server.
+    ///
     /// # Errors
     ///
     /// With a [`StateError`] if the underlying pointer has been freed.
     ///
-    /// With a [`MediaStateTransitionException`][0] if
-    /// [`RoomHandle::disable_remote_video()`] was called while enabling or a
-    /// media server didn't approve this state transition.
+    /// With a [`MediaStateTransitionException`][0] if a media server didn't
+    /// approve this state transition.
     ///
     /// [`Room`]: room::Room
     /// [`StateError`]: crate::api::err::StateError
@@ -510,6 +1166,31 @@ impl RoomHandle {
         let this = self.0.clone();
 
         let fut = this.enable_remote_video(source_kind.map(Into::into));
+        future_to_promise(async move {
+            let outcome = fut.await.map_err(Error::from)?;
+            Ok(MediaStateOutcome::from(outcome).into())
+        })
+    }
+
+    /// Applies the provided [`RoomMediaPolicy`] to this [`Room`], performing
+    /// only the media state changes it requires.
+    ///
+    /// Can be called both before and after [`RoomHandle::join()`]. Media
+    /// state changes requested afterwards via the other [`RoomHandle`]
+    /// methods override the corresponding [`RoomMediaPolicy`] fields.
+    ///
+    /// # Errors
+    ///
+    /// With a [`StateError`] if the underlying pointer has been freed.
+    ///
+    /// With a [`MediaStateTransitionException`][0] if a media server didn't
+    /// approve any of the required state transitions.
+    ///
+    /// [`Room`]: room::Room
+    /// [`StateError`]: crate::api::err::StateError
+    /// [0]: crate::api::err::MediaStateTransitionException
+    pub fn apply_policy(&self, policy: &RoomMediaPolicy) -> Promise {
+        let fut = self.0.apply_policy((*policy).into());
         future_to_promise(async move {
             fut.await.map_err(Error::from)?;
             Ok(JsValue::UNDEFINED)
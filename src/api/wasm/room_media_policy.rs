@@ -0,0 +1,77 @@
+//! Declarative policy of the media states of a `Room`.
+
+#![allow(clippy::new_without_default)]
+
+use derive_more::{From, Into};
+use wasm_bindgen::prelude::*;
+
+use crate::media_policy;
+
+/// Intended state of an outbound media in a [`RoomMediaPolicy`].
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SendMediaPolicy {
+    /// Media is published and unmuted.
+    Enabled,
+
+    /// Media is published, but muted.
+    Muted,
+
+    /// Media is not published at all.
+    Disabled,
+}
+
+impl From<SendMediaPolicy> for media_policy::SendMediaPolicy {
+    fn from(that: SendMediaPolicy) -> Self {
+        match that {
+            SendMediaPolicy::Enabled => Self::Enabled,
+            SendMediaPolicy::Muted => Self::Muted,
+            SendMediaPolicy::Disabled => Self::Disabled,
+        }
+    }
+}
+
+/// Declarative policy of the media states of a `Room`, applied via the
+/// `RoomHandle.apply_policy()`.
+///
+/// Media states not set in the policy are left untouched.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, From, Into)]
+pub struct RoomMediaPolicy(media_policy::RoomMediaPolicy);
+
+#[wasm_bindgen]
+impl RoomMediaPolicy {
+    /// Creates a new [`RoomMediaPolicy`] leaving all the media states
+    /// untouched.
+    #[must_use]
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        media_policy::RoomMediaPolicy::default().into()
+    }
+
+    /// Sets the intended [`SendMediaPolicy`] of outbound audio.
+    pub fn send_audio(&mut self, policy: SendMediaPolicy) {
+        self.0.send_audio = Some(policy.into());
+    }
+
+    /// Sets the intended [`SendMediaPolicy`] of outbound device video.
+    pub fn send_device_video(&mut self, policy: SendMediaPolicy) {
+        self.0.send_device_video = Some(policy.into());
+    }
+
+    /// Sets the intended [`SendMediaPolicy`] of outbound display video.
+    pub fn send_display_video(&mut self, policy: SendMediaPolicy) {
+        self.0.send_display_video = Some(policy.into());
+    }
+
+    /// Sets whether receiving of inbound audio is intended.
+    pub fn recv_audio(&mut self, enabled: bool) {
+        self.0.recv_audio = Some(enabled);
+    }
+
+    /// Sets whether receiving of inbound video (both device and display) is
+    /// intended.
+    pub fn recv_video(&mut self, enabled: bool) {
+        self.0.recv_video = Some(enabled);
+    }
+}
@@ -0,0 +1,48 @@
+//! [RTCConfiguration][1] options configurable by a client.
+//!
+//! [1]: https://w3.org/TR/webrtc#dom-rtcconfiguration
+
+#![allow(clippy::new_without_default)]
+
+use derive_more::{From, Into};
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    api::{BundlePolicy, IceTransportPolicy},
+    platform,
+};
+
+/// Options of [RTCPeerConnection][1]s created in a [`Room`].
+///
+/// [`Room`]: crate::room::Room
+/// [1]: https://w3.org/TR/webrtc#dom-rtcpeerconnection
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, From, Into)]
+pub struct RtcConfiguration(platform::RtcConfiguration);
+
+#[wasm_bindgen]
+impl RtcConfiguration {
+    /// Creates new [`RtcConfiguration`] with the [`BundlePolicy::MaxBundle`]
+    /// and no other options configured.
+    #[must_use]
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        platform::RtcConfiguration::default().into()
+    }
+
+    /// Sets the [`BundlePolicy`] to negotiate media with.
+    pub fn bundle_policy(&mut self, policy: BundlePolicy) {
+        self.0.bundle_policy = policy.into();
+    }
+
+    /// Sets size of the prefetched ICE candidate pool.
+    pub fn ice_candidate_pool_size(&mut self, size: u8) {
+        self.0.ice_candidate_pool_size = Some(size);
+    }
+
+    /// Sets the [`IceTransportPolicy`] overriding the one requested by a media
+    /// server.
+    pub fn ice_transport_policy(&mut self, policy: IceTransportPolicy) {
+        self.0.ice_transport_policy = Some(policy.into());
+    }
+}
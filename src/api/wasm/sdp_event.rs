@@ -0,0 +1,76 @@
+//! SDP description applied to a `PeerConnection`, reported for debugging.
+
+use derive_more::{Display, From};
+use wasm_bindgen::prelude::*;
+
+use crate::peer;
+
+/// Side of a `PeerConnection` an SDP description is applied as.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, Display, Eq, PartialEq)]
+pub enum SdpDirection {
+    /// SDP is applied as a local description.
+    Local,
+
+    /// SDP is applied as a remote description.
+    Remote,
+}
+
+impl From<peer::SdpDirection> for SdpDirection {
+    fn from(that: peer::SdpDirection) -> Self {
+        match that {
+            peer::SdpDirection::Local => Self::Local,
+            peer::SdpDirection::Remote => Self::Remote,
+        }
+    }
+}
+
+/// Type of an SDP description. Representation of a [RTCSdpType][1] value.
+///
+/// [1]: https://w3.org/TR/webrtc#dom-rtcsdptype
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, Display, Eq, PartialEq)]
+pub enum SdpKind {
+    /// SDP offer.
+    Offer,
+
+    /// SDP answer.
+    Answer,
+}
+
+impl From<peer::SdpKind> for SdpKind {
+    fn from(that: peer::SdpKind) -> Self {
+        match that {
+            peer::SdpKind::Offer => Self::Offer,
+            peer::SdpKind::Answer => Self::Answer,
+        }
+    }
+}
+
+/// Read-only copy of an SDP description successfully applied to a
+/// `PeerConnection`, provided to the `RoomHandle.on_sdp()` callback.
+#[wasm_bindgen]
+#[derive(Debug, From)]
+pub struct SdpEvent(peer::SdpEvent);
+
+#[wasm_bindgen]
+impl SdpEvent {
+    /// Returns [`SdpDirection`] the SDP is applied as.
+    #[must_use]
+    pub fn direction(&self) -> SdpDirection {
+        self.0.direction.into()
+    }
+
+    /// Returns [`SdpKind`] of the SDP.
+    #[must_use]
+    pub fn kind(&self) -> SdpKind {
+        self.0.kind.into()
+    }
+
+    /// Returns the applied SDP, redacted if requested in the
+    /// `RoomHandle.on_sdp()` call.
+    #[must_use]
+    pub fn sdp(&self) -> String {
+        self.0.sdp.clone()
+    }
+}
@@ -0,0 +1,52 @@
+//! Counters of the connection losses and recoveries happened during a `Room`
+//! lifetime.
+
+use derive_more::From;
+use wasm_bindgen::prelude::*;
+
+use crate::session_metrics;
+
+/// Counters of the connection losses and recoveries happened during a `Room`
+/// lifetime, accumulated across all its reconnects.
+#[wasm_bindgen]
+#[derive(Debug, From)]
+pub struct SessionMetrics(session_metrics::SessionMetrics);
+
+#[wasm_bindgen]
+impl SessionMetrics {
+    /// Returns number of the losses of a connection to a media server.
+    #[must_use]
+    pub fn connection_losses(&self) -> u32 {
+        self.0.connection_losses
+    }
+
+    /// Returns total time (in milliseconds) spent without a connection to a
+    /// media server after its losses, including the ongoing one, if any.
+    #[must_use]
+    pub fn disconnected_ms(&self) -> f64 {
+        self.0.disconnected.as_secs_f64() * 1000.0
+    }
+
+    /// Returns number of the ICE restarts requested by a media server.
+    #[must_use]
+    pub fn ice_restarts(&self) -> u32 {
+        self.0.ice_restarts
+    }
+
+    /// Returns number of the lost sessions restored by reconnecting to a media
+    /// server.
+    #[must_use]
+    pub fn session_restores(&self) -> u32 {
+        self.0.session_restores
+    }
+
+    /// Returns number of the new sessions started by joining the `Room`.
+    ///
+    /// Rejoining a `Room` after a connection loss restores its lost session
+    /// too, so it's counted in the [`SessionMetrics::session_restores()`] as
+    /// well.
+    #[must_use]
+    pub fn new_sessions(&self) -> u32 {
+        self.0.new_sessions
+    }
+}
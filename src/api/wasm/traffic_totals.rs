@@ -0,0 +1,54 @@
+//! Numbers of bytes transferred via a connection with a specific remote
+//! `Member`.
+
+use derive_more::From;
+use wasm_bindgen::prelude::*;
+
+use crate::peer;
+
+/// Numbers of bytes transferred in RTP streams of a connection with a specific
+/// remote `Member`.
+///
+/// All the byte counters are `bigint`s on JS side.
+#[wasm_bindgen]
+#[derive(Debug, From)]
+pub struct TrafficTotals(peer::TrafficTotals);
+
+#[wasm_bindgen]
+impl TrafficTotals {
+    /// Returns number of bytes sent in all the outbound RTP streams.
+    #[must_use]
+    pub fn bytes_sent(&self) -> u64 {
+        self.0.bytes_sent
+    }
+
+    /// Returns number of bytes received in all the inbound RTP streams.
+    #[must_use]
+    pub fn bytes_received(&self) -> u64 {
+        self.0.bytes_received
+    }
+
+    /// Returns number of bytes sent and received in all the audio RTP
+    /// streams.
+    #[must_use]
+    pub fn audio_bytes(&self) -> u64 {
+        self.0.audio_bytes
+    }
+
+    /// Returns number of bytes sent and received in all the video RTP
+    /// streams.
+    #[must_use]
+    pub fn video_bytes(&self) -> u64 {
+        self.0.video_bytes
+    }
+
+    /// Returns number of the stats collection pauses the counted traffic
+    /// spans.
+    ///
+    /// Its change between two [`TrafficTotals`] flags that their difference
+    /// covers a pause, so is unfit for bitrate calculations.
+    #[must_use]
+    pub fn collection_gaps(&self) -> u32 {
+        self.0.collection_gaps
+    }
+}
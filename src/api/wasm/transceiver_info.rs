@@ -0,0 +1,86 @@
+//! Debugging snapshot of a [RTCRtpTransceiver][1].
+//!
+//! [1]: https://w3.org/TR/webrtc#dom-rtcrtptransceiver
+
+use derive_more::From;
+use wasm_bindgen::prelude::*;
+
+use crate::{api::MediaKind, peer, platform::TransceiverDirection};
+
+/// Debugging snapshot of a [RTCRtpTransceiver][1] used by a [`Room`].
+///
+/// [`Room`]: crate::room::Room
+/// [1]: https://w3.org/TR/webrtc#dom-rtcrtptransceiver
+#[wasm_bindgen]
+#[derive(Debug, From)]
+pub struct TransceiverInfo(peer::TransceiverInfo);
+
+#[wasm_bindgen]
+impl TransceiverInfo {
+    /// Returns ID of the `PeerConnection` owning the represented transceiver.
+    #[must_use]
+    pub fn peer_id(&self) -> u32 {
+        self.0.peer_id.0
+    }
+
+    /// Returns [`mid`] of the represented transceiver.
+    ///
+    /// [`mid`]: https://w3.org/TR/webrtc#dom-rtptransceiver-mid
+    #[must_use]
+    pub fn mid(&self) -> String {
+        self.0.mid.clone()
+    }
+
+    /// Returns [`MediaKind`] of the represented transceiver.
+    #[must_use]
+    pub fn kind(&self) -> MediaKind {
+        self.0.kind.into()
+    }
+
+    /// Returns the [currentDirection][1] negotiated for the represented
+    /// transceiver (`sendrecv`, `sendonly`, `recvonly`, `inactive` or
+    /// `stopped`).
+    ///
+    /// Returns `undefined` if the represented transceiver hasn't been
+    /// negotiated yet.
+    ///
+    /// [1]: https://w3.org/TR/webrtc#dom-rtptransceiver-currentdirection
+    #[must_use]
+    pub fn direction(&self) -> Option<String> {
+        use TransceiverDirection as D;
+
+        self.0.direction.map(|dir| {
+            let dir = if dir.contains(D::STOPPED) {
+                "stopped"
+            } else if dir.contains(D::SEND | D::RECV) {
+                "sendrecv"
+            } else if dir.contains(D::SEND) {
+                "sendonly"
+            } else if dir.contains(D::RECV) {
+                "recvonly"
+            } else {
+                "inactive"
+            };
+            dir.to_owned()
+        })
+    }
+
+    /// Returns ID of the sent track using the represented transceiver, if any.
+    #[must_use]
+    pub fn sender_track_id(&self) -> Option<u32> {
+        self.0.sender_track_id.map(|id| id.0)
+    }
+
+    /// Returns ID of the received track using the represented transceiver, if
+    /// any.
+    #[must_use]
+    pub fn receiver_track_id(&self) -> Option<u32> {
+        self.0.receiver_track_id.map(|id| id.0)
+    }
+
+    /// Indicates whether the represented transceiver is stopped.
+    #[must_use]
+    pub fn is_stopped(&self) -> bool {
+        self.0.is_stopped
+    }
+}
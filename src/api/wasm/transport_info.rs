@@ -0,0 +1,72 @@
+//! Diagnostic information about a transport of a connection with a specific
+//! remote `Member`.
+
+use derive_more::From;
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+use crate::peer;
+
+/// Diagnostic information about the ICE and DTLS transport of a connection
+/// with a specific remote `Member`.
+///
+/// Every value is optional, since not all the browsers report them.
+#[wasm_bindgen]
+#[derive(Debug, From)]
+pub struct TransportInfo(peer::TransportInfo);
+
+#[wasm_bindgen]
+impl TransportInfo {
+    /// Returns [role][1] of this side in the ICE negotiation (`unknown`,
+    /// `controlling` or `controlled`).
+    ///
+    /// [1]: https://w3.org/TR/webrtc#dom-rtcicerole
+    #[must_use]
+    pub fn ice_role(&self) -> Option<String> {
+        self.0.ice_role.as_ref().and_then(as_string)
+    }
+
+    /// Returns [state][1] of the DTLS transport (`new`, `connecting`,
+    /// `connected`, `closed` or `failed`).
+    ///
+    /// [1]: https://w3.org/TR/webrtc#dom-rtcdtlstransportstate
+    #[must_use]
+    pub fn dtls_state(&self) -> Option<String> {
+        self.0.dtls_state.as_ref().and_then(as_string)
+    }
+
+    /// Returns number of times the selected ICE candidate pair has changed.
+    ///
+    /// It's a `bigint` on JS side.
+    #[must_use]
+    pub fn selected_candidate_pair_changes(&self) -> Option<u64> {
+        self.0.selected_candidate_pair_changes
+    }
+
+    /// Returns name of the protection profile used for the SRTP transport.
+    #[must_use]
+    pub fn srtp_cipher(&self) -> Option<String> {
+        self.0.srtp_cipher.clone()
+    }
+
+    /// Returns name of the cipher suite used for the DTLS transport.
+    #[must_use]
+    pub fn dtls_cipher(&self) -> Option<String> {
+        self.0.dtls_cipher.clone()
+    }
+
+    /// Returns this [`TransportInfo`] as a JSON string.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        self.0.to_json()
+    }
+}
+
+/// Returns the string the provided value is serialized into, as it's
+/// represented in the WebRTC specification.
+fn as_string<T: Serialize>(val: &T) -> Option<String> {
+    match serde_json::to_value(val) {
+        Ok(serde_json::Value::String(s)) => Some(s),
+        _ => None,
+    }
+}
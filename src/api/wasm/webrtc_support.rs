@@ -0,0 +1,48 @@
+//! Availability of WebRTC functionality in the current browser.
+
+use derive_more::From;
+use wasm_bindgen::prelude::*;
+
+use crate::platform;
+
+/// Report about availability of WebRTC functionality in the current browser.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, From)]
+pub struct WebRtcSupport(platform::WebRtcSupport);
+
+#[wasm_bindgen]
+impl WebRtcSupport {
+    /// Indicates whether a [RTCPeerConnection][1] can be created, so a `Room`
+    /// can be joined.
+    ///
+    /// [1]: https://w3.org/TR/webrtc#dom-rtcpeerconnection
+    #[must_use]
+    pub fn peer_connection(&self) -> bool {
+        self.0.peer_connection
+    }
+
+    /// Indicates whether a [getUserMedia()][1] request can be made.
+    ///
+    /// [1]: https://tinyurl.com/w3-streams#dom-mediadevices-getusermedia
+    #[must_use]
+    pub fn get_user_media(&self) -> bool {
+        self.0.get_user_media
+    }
+
+    /// Indicates whether a [getDisplayMedia()][1] request can be made.
+    ///
+    /// [1]: https://w3.org/TR/screen-capture#dom-mediadevices-getdisplaymedia
+    #[must_use]
+    pub fn get_display_media(&self) -> bool {
+        self.0.get_display_media
+    }
+
+    /// Indicates whether [insertable streams][1] of encoded media are
+    /// available.
+    ///
+    /// [1]: https://w3.org/TR/webrtc-encoded-transform
+    #[must_use]
+    pub fn insertable_streams(&self) -> bool {
+        self.0.insertable_streams
+    }
+}
@@ -3,6 +3,7 @@
 use std::{
     cell::{Cell, RefCell},
     collections::{HashMap, HashSet},
+    fmt,
     future::Future,
     rc::{Rc, Weak},
 };
@@ -12,15 +13,20 @@ use futures::{
     future, future::LocalBoxFuture, stream::LocalBoxStream, FutureExt as _,
     StreamExt as _,
 };
-use medea_client_api_proto::{ConnectionQualityScore, MemberId, TrackId};
+use medea_client_api_proto::{
+    self as proto, ConnectionQualityScore, MemberId, TrackId,
+};
 use tracerr::Traced;
 
 use crate::{
     api,
     media::{track::remote, MediaKind, MediaSourceKind, RecvConstraints},
     peer::{
-        media_exchange_state, receiver, MediaState, MediaStateControllable,
-        ProhibitedStateError, TransceiverSide,
+        media_exchange_state, receiver, sender, AvSyncCounter, AvSyncInfo,
+        MediaState, MediaStateControllable, NegotiationCounter,
+        NegotiationStats, ProhibitedStateError, QualityLimitationCounter,
+        QualitySummary, SetupTimings, SetupTimingsReport, TrafficCounter,
+        TrafficTotals, TransceiverSide, TransportInfo, TransportMonitor,
     },
     platform,
     utils::{Caused, TaskHandle},
@@ -57,6 +63,122 @@ pub enum ChangeMediaStateError {
 /// Alias of [`Result`]s related to [`MediaState`] update functions.
 type ChangeMediaStateResult = Result<(), Traced<ChangeMediaStateError>>;
 
+/// State of a remote `Member`'s media, controlled by this `Member`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MemberMediaState {
+    /// Media is enabled by the remote `Member`.
+    Enabled,
+
+    /// Media is disabled by the remote `Member`.
+    Disabled,
+
+    /// Media is muted by the remote `Member`.
+    Muted,
+
+    /// Media is unmuted by the remote `Member`.
+    Unmuted,
+}
+
+impl MemberMediaState {
+    /// Returns [`MemberMediaState::Enabled`] or
+    /// [`MemberMediaState::Disabled`] according to the provided `enabled`
+    /// indicator.
+    #[must_use]
+    pub const fn from_enabled(enabled: bool) -> Self {
+        if enabled {
+            Self::Enabled
+        } else {
+            Self::Disabled
+        }
+    }
+
+    /// Returns [`MemberMediaState::Muted`] or [`MemberMediaState::Unmuted`]
+    /// according to the provided `muted` indicator.
+    #[must_use]
+    pub const fn from_muted(muted: bool) -> Self {
+        if muted {
+            Self::Muted
+        } else {
+            Self::Unmuted
+        }
+    }
+}
+
+/// Change of a [`MemberMediaState`] of some remote `Member`'s media.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MemberMediaStateChange {
+    /// ID of the remote `Member` whose media state has changed.
+    pub member_id: MemberId,
+
+    /// [`MediaKind`] of the changed media.
+    pub kind: MediaKind,
+
+    /// [`MediaSourceKind`] of the changed media.
+    pub source_kind: MediaSourceKind,
+
+    /// New [`MemberMediaState`] of the media.
+    pub state: MemberMediaState,
+}
+
+/// Directions of the media exchange of a single [`MediaKind`] and
+/// [`MediaSourceKind`] between this and a remote `Member`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct MediaExchangeDirections {
+    /// Indicator whether this `Member` intends to send the media to the
+    /// remote one.
+    pub we_send: bool,
+
+    /// Indicator whether the remote `Member` has receiving of the media sent
+    /// by this `Member` enabled, as communicated by a media server.
+    pub they_receive: bool,
+
+    /// Indicator whether the remote `Member` sends the media to this `Member`,
+    /// as communicated by a media server.
+    pub they_send: bool,
+
+    /// Indicator whether this `Member` intends to receive the media from the
+    /// remote one.
+    pub we_receive: bool,
+}
+
+/// Shared callback invoked with [`MemberMediaStateChange`]s of all the
+/// [`Connection`]s of a `Room`.
+#[derive(Clone, Default)]
+struct MemberMediaStateNotifier(
+    Rc<RefCell<Option<Box<dyn FnMut(MemberMediaStateChange)>>>>,
+);
+
+impl fmt::Debug for MemberMediaStateNotifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("MemberMediaStateNotifier")
+            .field(&self.0.borrow().is_some())
+            .finish()
+    }
+}
+
+impl MemberMediaStateNotifier {
+    /// Sets the callback to be invoked with [`MemberMediaStateChange`]s.
+    fn set<F>(&self, f: F)
+    where
+        F: FnMut(MemberMediaStateChange) + 'static,
+    {
+        drop(self.0.replace(Some(Box::new(f))));
+    }
+
+    /// Invokes the callback (if any) with the provided
+    /// [`MemberMediaStateChange`].
+    fn notify(&self, change: MemberMediaStateChange) {
+        // Taken out to allow resubscribing from inside the callback.
+        let cb = self.0.borrow_mut().take();
+        if let Some(mut cb) = cb {
+            cb(change);
+            if self.0.borrow().is_none() {
+                drop(self.0.replace(Some(cb)));
+            }
+        }
+    }
+}
+
 /// Service which manages [`Connection`]s with remote `Member`s.
 #[derive(Debug)]
 pub struct Connections {
@@ -69,12 +191,20 @@ pub struct Connections {
     /// Remote [`MemberId`] to [`Connection`] with that `Member`.
     connections: RefCell<HashMap<MemberId, Connection>>,
 
+    /// Number of [`Connection`]s created by these [`Connections`], used to
+    /// order them by creation.
+    created_count: Cell<u64>,
+
     /// Global constraints to the [`remote::Track`]s of the Jason.
     room_recv_constraints: Rc<RecvConstraints>,
 
     /// Callback invoked on remote `Member` media arrival.
     #[cfg_attr(not(target_family = "wasm"), allow(unused_qualifications))]
     on_new_connection: platform::Callback<api::ConnectionHandle>,
+
+    /// Callback invoked on [`MemberMediaStateChange`]s in all the
+    /// [`Connection`]s.
+    on_member_media_state_changed: MemberMediaStateNotifier,
 }
 
 impl Connections {
@@ -84,19 +214,61 @@ impl Connections {
             tracks: RefCell::default(),
             members_to_tracks: RefCell::default(),
             connections: RefCell::default(),
+            created_count: Cell::new(0),
             room_recv_constraints,
             on_new_connection: platform::Callback::default(),
+            on_member_media_state_changed: MemberMediaStateNotifier::default(),
         }
     }
 
     /// Sets callback, which will be invoked when new [`Connection`] is
     /// established.
+    ///
+    /// The callback is immediately invoked for all the already existing
+    /// [`Connection`]s in the order of their creation.
     #[cfg_attr(not(target_family = "wasm"), allow(unused_qualifications))]
     pub fn on_new_connection(
         &self,
         f: platform::Function<api::ConnectionHandle>,
     ) {
         self.on_new_connection.set_func(f);
+
+        for conn in self.all() {
+            self.on_new_connection.call1(conn.new_handle());
+        }
+    }
+
+    /// Sets callback, which will be invoked whenever a [`MemberMediaState`]
+    /// of any remote `Member`'s media changes.
+    ///
+    /// The callback is immediately invoked with the current
+    /// [`MemberMediaState`]s of all the remote `Member`s' media, in the order
+    /// of their [`Connection`]s creation.
+    pub fn on_member_media_state_changed<F>(&self, f: F)
+    where
+        F: FnMut(MemberMediaStateChange) + 'static,
+    {
+        self.on_member_media_state_changed.set(f);
+
+        for change in self.all().iter().flat_map(Connection::media_states) {
+            self.on_member_media_state_changed.notify(change);
+        }
+    }
+
+    /// Creates a new [`Connection`] with the provided remote `Member`,
+    /// invoking the `on_new_connection` callback.
+    fn new_connection(&self, remote_id: MemberId) -> Connection {
+        let seq = self.created_count.get();
+        self.created_count.set(seq + 1);
+
+        let conn = Connection::new(
+            remote_id,
+            &self.room_recv_constraints,
+            seq,
+            self.on_member_media_state_changed.clone(),
+        );
+        self.on_new_connection.call1(conn.new_handle());
+        conn
     }
 
     /// Adds or updates information about related [`Track`]s with the provided
@@ -139,11 +311,7 @@ impl Connections {
                     .insert(*track_id);
 
                 if !connections.contains_key(&mid) {
-                    let connection = Connection::new(
-                        mid.clone(),
-                        &self.room_recv_constraints,
-                    );
-                    self.on_new_connection.call1(connection.new_handle());
+                    let connection = self.new_connection(mid.clone());
                     drop(connections.insert(mid.clone(), connection));
                 }
                 _ = partners.insert(mid);
@@ -160,7 +328,7 @@ impl Connections {
                         if tracks.is_empty() {
                             _ = connections
                                 .remove(partner)
-                                .map(|conn| conn.0.on_close.call0());
+                                .map(|conn| conn.0.close());
                         }
                     }
                 }
@@ -199,11 +367,7 @@ impl Connections {
                 .or_default()
                 .insert(track_id);
             if !connections.contains_key(partner) {
-                let connection = Connection::new(
-                    partner.clone(),
-                    &self.room_recv_constraints,
-                );
-                self.on_new_connection.call1(connection.new_handle());
+                let connection = self.new_connection(partner.clone());
                 drop(connections.insert(partner.clone(), connection));
             }
         }
@@ -240,7 +404,7 @@ impl Connections {
                             .connections
                             .borrow_mut()
                             .remove(&p)
-                            .map(|conn| conn.0.on_close.call0());
+                            .map(|conn| conn.0.close());
                     }
                 }
             }
@@ -251,6 +415,16 @@ impl Connections {
     pub fn get(&self, remote_member_id: &MemberId) -> Option<Connection> {
         self.connections.borrow().get(remote_member_id).cloned()
     }
+
+    /// Returns all the currently existing [`Connection`]s in the order of
+    /// their creation.
+    #[must_use]
+    pub fn all(&self) -> Vec<Connection> {
+        let mut all: Vec<_> =
+            self.connections.borrow().values().cloned().collect();
+        all.sort_by_key(|c| c.0.seq);
+        all
+    }
 }
 
 /// Error of [`ConnectionHandle`]'s [`Weak`] pointer being detached.
@@ -261,9 +435,29 @@ pub struct HandleDetachedError;
 
 /// External handler to a [`Connection`] with a remote `Member`.
 ///
-/// Actually, represents a [`Weak`]-based handle to `InnerConnection`.
-#[derive(Debug)]
-pub struct ConnectionHandle(Weak<InnerConnection>);
+/// Actually, represents a [`Weak`]-based handle to `InnerConnection`, so
+/// dropping it never affects the [`Connection`] itself, its callbacks, or any
+/// other [`ConnectionHandle`] to it.
+///
+/// Stats reported via a [`ConnectionHandle`] are accounted on the
+/// periodically scraped stats of the `PeerConnection` its [`Connection`] is
+/// established via, so may lag behind for up to a second. In SFU mode a single
+/// `PeerConnection` is shared by all the [`Connection`]s, so they all report
+/// the same stats.
+#[derive(Clone, Debug)]
+pub struct ConnectionHandle {
+    /// [`Weak`] pointer to the `InnerConnection`.
+    inner: Weak<InnerConnection>,
+
+    /// [`QualityLimitationCounter`] of the `PeerConnection` the [`Connection`]
+    /// is established via, retained by this [`ConnectionHandle`], so its last
+    /// [`QualitySummary`] is available even after the [`Connection`] is
+    /// closed.
+    quality_limitation: QualityLimitationSlot,
+}
+
+/// Shared slot of a [`QualityLimitationCounter`] of a [`Connection`].
+type QualityLimitationSlot = Rc<RefCell<Option<Rc<QualityLimitationCounter>>>>;
 
 /// Actual data of a connection with a specific remote `Member`.
 ///
@@ -276,27 +470,109 @@ struct InnerConnection {
     /// Current [`ConnectionQualityScore`] of this [`Connection`].
     quality_score: Cell<Option<ConnectionQualityScore>>,
 
+    /// Sequence number of this [`Connection`] creation.
+    seq: u64,
+
     /// Callback invoked when a [`remote::Track`] is received.
     on_remote_track_added: platform::Callback<api::RemoteMediaTrack>,
 
+    /// Callback invoked when a [`remote::Track`] is gone permanently.
+    on_remote_track_removed: platform::Callback<api::RemoteMediaTrack>,
+
+    /// All the [`remote::Track`]s received in this [`Connection`] and not
+    /// removed yet, in the order of their arrival.
+    remote_tracks: RefCell<Vec<remote::WeakTrack>>,
+
+    /// [`TaskHandle`]s for the spawned stop listeners of the
+    /// [`remote::Track`]s of this [`Connection`].
+    remote_tracks_task_handles: RefCell<Vec<TaskHandle>>,
+
     /// Individual [`RecvConstraints`] of this [`Connection`].
     recv_constraints: Rc<RecvConstraints>,
 
     /// All [`receiver::State`]s related to this [`InnerConnection`].
     receivers: RefCell<Vec<Rc<receiver::State>>>,
 
+    /// All [`sender::State`]s sending media to the remote `Member` of this
+    /// [`InnerConnection`].
+    senders: RefCell<Vec<Weak<sender::State>>>,
+
     /// Callback invoked when a [`ConnectionQualityScore`] is updated.
     on_quality_score_update: platform::Callback<u8>,
 
     /// Callback invoked when this [`Connection`] is closed.
     on_close: platform::Callback<()>,
 
+    /// [`SetupTimings`] of the `PeerConnection` this [`Connection`] is
+    /// established via.
+    setup_timings: RefCell<Option<Rc<SetupTimings>>>,
+
+    /// [`TrafficCounter`] of the `PeerConnection` this [`Connection`] is
+    /// established via.
+    traffic: RefCell<Option<Rc<TrafficCounter>>>,
+
+    /// [`TransportMonitor`] of the `PeerConnection` this [`Connection`] is
+    /// established via.
+    transport: RefCell<Option<Rc<TransportMonitor>>>,
+
+    /// [`NegotiationCounter`] of the `PeerConnection` this [`Connection`] is
+    /// established via.
+    negotiations: RefCell<Option<Rc<NegotiationCounter>>>,
+
+    /// [`QualityLimitationCounter`] of the `PeerConnection` this
+    /// [`Connection`] is established via, shared with its
+    /// [`ConnectionHandle`]s.
+    quality_limitation: QualityLimitationSlot,
+
+    /// [`AvSyncCounter`] of the `PeerConnection` this [`Connection`] is
+    /// established via.
+    av_sync: RefCell<Option<Rc<AvSyncCounter>>>,
+
+    /// [`platform::IceGatheringCounter`] of the `PeerConnection` this
+    /// [`Connection`] is established via.
+    ice_gathering: RefCell<Option<Rc<platform::IceGatheringCounter>>>,
+
+    /// Callback invoked on [`MemberMediaStateChange`]s of the media
+    /// received in this [`Connection`].
+    on_member_media_state_changed: MemberMediaStateNotifier,
+
+    /// [`TaskHandle`]s for the spawned [`MemberMediaState`] changes listeners
+    /// of the [`receiver::State`]s of this [`Connection`].
+    receivers_task_handles: RefCell<Vec<TaskHandle>>,
+
     /// [`TaskHandle`]s for the spawned changes listeners of this
     /// [`Connection`].
     _task_handles: Vec<TaskHandle>,
 }
 
 impl InnerConnection {
+    /// Removes the provided [`remote::Track`] from this [`Connection`],
+    /// invoking `on_remote_track_removed` callback, unless it has been
+    /// removed already.
+    fn remove_remote_track(&self, track: remote::Track) {
+        let mut tracks = self.remote_tracks.borrow_mut();
+        let before = tracks.len();
+        tracks.retain(|t| !track.is_referenced_by(t));
+        let is_removed = tracks.len() != before;
+        drop(tracks);
+
+        if is_removed {
+            self.on_remote_track_removed.call1(track);
+        }
+    }
+
+    /// Closes this [`Connection`], invoking `on_remote_track_removed` callback
+    /// for all its [`remote::Track`]s not removed yet, and then `on_close`
+    /// callback.
+    fn close(&self) {
+        let tracks = self.remote_tracks.take();
+        for track in tracks.iter().filter_map(remote::WeakTrack::upgrade) {
+            self.on_remote_track_removed.call1(track);
+        }
+        drop(self.remote_tracks_task_handles.take());
+        self.on_close.call0();
+    }
+
     /// Changes [`MediaState`] of the provided [`MediaKind`] to the provided
     /// [`MediaState`].
     ///
@@ -310,6 +586,59 @@ impl InnerConnection {
         desired_state: MediaState,
         kind: MediaKind,
         source_kind: Option<MediaSourceKind>,
+    ) -> ChangeMediaStateResult {
+        // Applied optimistically, so the intention is observable right away,
+        // and rolled back if not approved.
+        let rollback = match desired_state {
+            MediaState::MediaExchange(desired) => {
+                let rollback = (*self.recv_constraints).clone();
+                self.recv_constraints.set_enabled(
+                    desired == media_exchange_state::Stable::Enabled,
+                    kind,
+                    source_kind.map(Into::into),
+                );
+                Some(rollback)
+            }
+            MediaState::Mute(_) => None,
+        };
+
+        let result = self
+            .transit_receivers(desired_state, kind, source_kind)
+            .await;
+        if let (Err(_), Some(rollback)) = (&result, rollback) {
+            self.recv_constraints.set_enabled(
+                rollback.is_audio_enabled(),
+                MediaKind::Audio,
+                None,
+            );
+            self.recv_constraints.set_enabled(
+                rollback.is_video_device_enabled(),
+                MediaKind::Video,
+                Some(MediaSourceKind::Device.into()),
+            );
+            self.recv_constraints.set_enabled(
+                rollback.is_video_display_enabled(),
+                MediaKind::Video,
+                Some(MediaSourceKind::Display.into()),
+            );
+        }
+        result
+    }
+
+    /// Transits the [`receiver::State`]s of the provided [`MediaKind`] and
+    /// [`MediaSourceKind`] into the provided [`MediaState`], awaiting them to
+    /// become stable.
+    ///
+    /// # Errors
+    ///
+    /// With [`ChangeMediaStateError::TransitionIntoOppositeState`] if an
+    /// opposite [`MediaState`] was requested meanwhile or a media server
+    /// didn't approve this state transition.
+    async fn transit_receivers(
+        &self,
+        desired_state: MediaState,
+        kind: MediaKind,
+        source_kind: Option<MediaSourceKind>,
     ) -> ChangeMediaStateResult {
         let receivers = self.receivers.borrow().clone();
         let mut change_tasks = Vec::new();
@@ -333,21 +662,61 @@ impl InnerConnection {
                 .map_err(tracerr::from_and_wrap!())?,
         );
 
-        if let MediaState::MediaExchange(desired_state) = desired_state {
-            self.recv_constraints.set_enabled(
-                desired_state == media_exchange_state::Stable::Enabled,
-                kind,
-                source_kind.map(Into::into),
-            );
-        }
-
         Ok(())
     }
+
+    /// Returns the current [`MediaExchangeDirections`] of the provided
+    /// [`MediaKind`] and [`MediaSourceKind`].
+    fn media_exchange_directions(
+        &self,
+        kind: MediaKind,
+        source_kind: MediaSourceKind,
+    ) -> MediaExchangeDirections {
+        let source_kind = source_kind.into();
+        let senders: Vec<_> = self
+            .senders
+            .borrow()
+            .iter()
+            .filter_map(Weak::upgrade)
+            .filter(|s| {
+                s.kind() == kind
+                    && s.source_kind() == source_kind
+                    && s.receivers().contains(&self.remote_id)
+            })
+            .collect();
+        let they_send = self.receivers.borrow().iter().any(|r| {
+            r.kind() == kind
+                && r.source_kind() == source_kind
+                && proto::MediaDirection::from(r.media_direction())
+                    .is_send_enabled()
+        });
+        let we_receive = match (kind, source_kind) {
+            (MediaKind::Audio, _) => self.recv_constraints.is_audio_enabled(),
+            (MediaKind::Video, proto::MediaSourceKind::Device) => {
+                self.recv_constraints.is_video_device_enabled()
+            }
+            (MediaKind::Video, proto::MediaSourceKind::Display) => {
+                self.recv_constraints.is_video_display_enabled()
+            }
+        };
+
+        MediaExchangeDirections {
+            we_send: senders.iter().any(|s| s.is_enabled_intended()),
+            they_receive: senders
+                .iter()
+                .any(|s| s.media_direction().is_recv_enabled()),
+            they_send,
+            we_receive,
+        }
+    }
 }
 
 impl ConnectionHandle {
     /// Sets callback, invoked when this `Connection` will close.
     ///
+    /// Nothing is replayed here, since a closed `Connection` detaches all its
+    /// [`ConnectionHandle`]s.
+    ///
     /// # Errors
     ///
     /// See [`HandleDetachedError`] for details.
@@ -355,7 +724,7 @@ impl ConnectionHandle {
         &self,
         f: platform::Function<()>,
     ) -> Result<(), Traced<HandleDetachedError>> {
-        self.0
+        self.inner
             .upgrade()
             .ok_or_else(|| tracerr::new!(HandleDetachedError))
             .map(|inner| inner.on_close.set_func(f))
@@ -369,15 +738,216 @@ impl ConnectionHandle {
     pub fn get_remote_member_id(
         &self,
     ) -> Result<String, Traced<HandleDetachedError>> {
-        self.0
+        self.inner
             .upgrade()
             .ok_or_else(|| tracerr::new!(HandleDetachedError))
             .map(|inner| inner.remote_id.0.clone())
     }
 
+    /// Returns [`SetupTimingsReport`] of the `PeerConnection` this
+    /// [`Connection`] is established via.
+    ///
+    /// Returns [`None`] until the `PeerConnection` is connected.
+    ///
+    /// # Errors
+    ///
+    /// See [`HandleDetachedError`] for details.
+    pub fn setup_timings(
+        &self,
+    ) -> Result<Option<SetupTimingsReport>, Traced<HandleDetachedError>> {
+        self.inner
+            .upgrade()
+            .ok_or_else(|| tracerr::new!(HandleDetachedError))
+            .map(|inner| {
+                inner
+                    .setup_timings
+                    .borrow()
+                    .as_ref()
+                    .and_then(|t| t.report())
+            })
+    }
+
+    /// Returns [`TrafficTotals`] of the `PeerConnection` this [`Connection`]
+    /// is established via, accumulated across ICE restarts.
+    ///
+    /// Traffic is accounted only after the first call of this method, but
+    /// includes the bytes transferred before it.
+    ///
+    /// # Errors
+    ///
+    /// See [`HandleDetachedError`] for details.
+    pub fn traffic_totals(
+        &self,
+    ) -> Result<TrafficTotals, Traced<HandleDetachedError>> {
+        self.inner
+            .upgrade()
+            .ok_or_else(|| tracerr::new!(HandleDetachedError))
+            .map(|inner| {
+                inner.traffic.borrow().as_ref().map_or_else(
+                    TrafficTotals::default,
+                    |traffic| {
+                        traffic.arm();
+                        traffic.totals()
+                    },
+                )
+            })
+    }
+
+    /// Returns [`NegotiationStats`] of the `PeerConnection` this
+    /// [`Connection`] is established via.
+    ///
+    /// The [`NegotiationStats`] are accumulated across ICE restarts, and are
+    /// reset only when the `PeerConnection` is recreated.
+    ///
+    /// # Errors
+    ///
+    /// See [`HandleDetachedError`] for details.
+    pub fn negotiation_stats(
+        &self,
+    ) -> Result<NegotiationStats, Traced<HandleDetachedError>> {
+        self.inner
+            .upgrade()
+            .ok_or_else(|| tracerr::new!(HandleDetachedError))
+            .map(|inner| {
+                inner
+                    .negotiations
+                    .borrow()
+                    .as_ref()
+                    .map_or_else(NegotiationStats::default, |n| n.stats())
+            })
+    }
+
+    /// Returns [`QualitySummary`] of the `PeerConnection` this [`Connection`]
+    /// is established via: total time its outbound media has spent in each
+    /// quality limitation state, accumulated across ICE restarts.
+    ///
+    /// Remains available after the [`Connection`] is closed, returning the
+    /// summary of the whole call.
+    #[must_use]
+    pub fn quality_summary(&self) -> QualitySummary {
+        self.quality_limitation
+            .borrow()
+            .as_ref()
+            .map_or_else(QualitySummary::default, |q| q.summary())
+    }
+
+    /// Returns [`platform::IceGatheringInfo`] of the current ICE candidates
+    /// gathering of the `PeerConnection` this [`Connection`] is established
+    /// via.
+    ///
+    /// Every ICE restart starts a new gathering, so the previous one is
+    /// available via the [`ConnectionHandle::previous_gathering_info()`].
+    ///
+    /// # Errors
+    ///
+    /// See [`HandleDetachedError`] for details.
+    pub fn ice_gathering_info(
+        &self,
+    ) -> Result<platform::IceGatheringInfo, Traced<HandleDetachedError>> {
+        self.inner
+            .upgrade()
+            .ok_or_else(|| tracerr::new!(HandleDetachedError))
+            .map(|inner| {
+                inner
+                    .ice_gathering
+                    .borrow()
+                    .as_ref()
+                    .map_or_else(platform::IceGatheringInfo::default, |g| {
+                        g.info()
+                    })
+            })
+    }
+
+    /// Returns [`platform::IceGatheringInfo`] of the ICE candidates gathering
+    /// of the `PeerConnection` this [`Connection`] is established via,
+    /// preceding the last ICE restart, or [`None`] if there was no ICE
+    /// restart.
+    ///
+    /// # Errors
+    ///
+    /// See [`HandleDetachedError`] for details.
+    pub fn previous_gathering_info(
+        &self,
+    ) -> Result<Option<platform::IceGatheringInfo>, Traced<HandleDetachedError>>
+    {
+        self.inner
+            .upgrade()
+            .ok_or_else(|| tracerr::new!(HandleDetachedError))
+            .map(|inner| {
+                inner
+                    .ice_gathering
+                    .borrow()
+                    .as_ref()
+                    .and_then(|g| g.previous_info())
+            })
+    }
+
+    /// Returns [`TransportInfo`] of the `PeerConnection` this [`Connection`]
+    /// is established via, or [`None`] if it hasn't been reported yet.
+    ///
+    /// # Errors
+    ///
+    /// See [`HandleDetachedError`] for details.
+    pub fn transport_info(
+        &self,
+    ) -> Result<Option<TransportInfo>, Traced<HandleDetachedError>> {
+        self.inner
+            .upgrade()
+            .ok_or_else(|| tracerr::new!(HandleDetachedError))
+            .map(|inner| {
+                inner.transport.borrow().as_ref().and_then(|t| t.info())
+            })
+    }
+
+    /// Returns [`AvSyncInfo`] of the media received via the `PeerConnection`
+    /// this [`Connection`] is established via: estimated audio-video drift and
+    /// jitter buffer delays of each [`MediaKind`].
+    ///
+    /// # Errors
+    ///
+    /// See [`HandleDetachedError`] for details.
+    pub fn av_sync_info(
+        &self,
+    ) -> Result<AvSyncInfo, Traced<HandleDetachedError>> {
+        self.inner
+            .upgrade()
+            .ok_or_else(|| tracerr::new!(HandleDetachedError))
+            .map(|inner| {
+                inner
+                    .av_sync
+                    .borrow()
+                    .as_ref()
+                    .map_or_else(AvSyncInfo::default, |s| s.info())
+            })
+    }
+
+    /// Returns the current [`MediaExchangeDirections`] of the provided
+    /// [`MediaKind`] and [`MediaSourceKind`] between this and the remote
+    /// `Member`.
+    ///
+    /// Changes made via this [`ConnectionHandle`] are reflected right away,
+    /// and are rolled back if a media server doesn't approve them.
+    ///
+    /// # Errors
+    ///
+    /// See [`HandleDetachedError`] for details.
+    pub fn media_exchange_state(
+        &self,
+        kind: MediaKind,
+        source_kind: MediaSourceKind,
+    ) -> Result<MediaExchangeDirections, Traced<HandleDetachedError>> {
+        self.inner
+            .upgrade()
+            .ok_or_else(|| tracerr::new!(HandleDetachedError))
+            .map(|inner| inner.media_exchange_directions(kind, source_kind))
+    }
+
     /// Sets callback, invoked when a new [`remote::Track`] is added to this
     /// [`Connection`].
     ///
+    /// The callback is immediately invoked for all the alive
+    /// [`remote::Track`]s added already, in the order of their arrival.
+    ///
     /// # Errors
     ///
     /// See [`HandleDetachedError`] for details.
@@ -385,15 +955,51 @@ impl ConnectionHandle {
         &self,
         f: platform::Function<api::RemoteMediaTrack>,
     ) -> Result<(), Traced<HandleDetachedError>> {
-        self.0
+        let inner = self
+            .inner
+            .upgrade()
+            .ok_or_else(|| tracerr::new!(HandleDetachedError))?;
+        inner.on_remote_track_added.set_func(f);
+
+        let existing: Vec<_> = {
+            let mut tracks = inner.remote_tracks.borrow_mut();
+            tracks.retain(|t| t.upgrade().is_some());
+            tracks
+                .iter()
+                .filter_map(remote::WeakTrack::upgrade)
+                .collect()
+        };
+        for track in existing {
+            inner.on_remote_track_added.call1(track);
+        }
+        Ok(())
+    }
+
+    /// Sets callback, invoked when a [`remote::Track`] of this [`Connection`]
+    /// is gone permanently (its transceiver is stopped or this
+    /// [`Connection`] is closed).
+    ///
+    /// Temporarily disabled or muted [`remote::Track`]s are not removed.
+    ///
+    /// # Errors
+    ///
+    /// See [`HandleDetachedError`] for details.
+    pub fn on_remote_track_removed(
+        &self,
+        f: platform::Function<api::RemoteMediaTrack>,
+    ) -> Result<(), Traced<HandleDetachedError>> {
+        self.inner
             .upgrade()
             .ok_or_else(|| tracerr::new!(HandleDetachedError))
-            .map(|inner| inner.on_remote_track_added.set_func(f))
+            .map(|inner| inner.on_remote_track_removed.set_func(f))
     }
 
     /// Sets callback, invoked when a connection quality score is updated by
     /// a server.
     ///
+    /// The callback is immediately invoked with the current connection quality
+    /// score, if any.
+    ///
     /// # Errors
     ///
     /// See [`HandleDetachedError`] for details.
@@ -401,10 +1007,17 @@ impl ConnectionHandle {
         &self,
         f: platform::Function<u8>,
     ) -> Result<(), Traced<HandleDetachedError>> {
-        self.0
+        let inner = self
+            .inner
             .upgrade()
-            .ok_or_else(|| tracerr::new!(HandleDetachedError))
-            .map(|inner| inner.on_quality_score_update.set_func(f))
+            .ok_or_else(|| tracerr::new!(HandleDetachedError))?;
+        inner.on_quality_score_update.set_func(f);
+
+        if let Some(score) = inner.quality_score.get() {
+            #[allow(clippy::as_conversions)]
+            inner.on_quality_score_update.call1(score as u8);
+        }
+        Ok(())
     }
 
     /// Enables inbound video in this [`Connection`].
@@ -449,6 +1062,42 @@ impl ConnectionHandle {
         )
     }
 
+    /// Enables or disables inbound video in this [`Connection`], affecting
+    /// only video with the provided [`MediaSourceKind`], if any.
+    ///
+    /// Unlike [`ConnectionHandle::enable_remote_video()`] and
+    /// [`ConnectionHandle::disable_remote_video()`], the preference is recorded
+    /// right away, so it's applied to the video tracks not negotiated yet: they
+    /// are created disabled, making no media flow until enabled back (which
+    /// renegotiates them, if required).
+    ///
+    /// # Errors
+    ///
+    /// With [`ChangeMediaStateError::Detached`] if an inner [`Weak`] pointer
+    /// upgrade fails.
+    ///
+    /// With [`ChangeMediaStateError::TransitionIntoOppositeState`] if an
+    /// opposite state was requested while transiting the existing video tracks
+    /// or a media server didn't approve this state transition.
+    pub fn set_receive_video(
+        &self,
+        enabled: bool,
+        source_kind: Option<MediaSourceKind>,
+    ) -> impl Future<Output = ChangeMediaStateResult> + 'static {
+        if let Some(inner) = self.inner.upgrade() {
+            inner.recv_constraints.set_enabled(
+                enabled,
+                MediaKind::Video,
+                source_kind.map(Into::into),
+            );
+        }
+        self.change_media_state(
+            media_exchange_state::Stable::from(enabled).into(),
+            MediaKind::Video,
+            source_kind,
+        )
+    }
+
     /// Enables inbound audio in this [`Connection`].
     ///
     /// # Errors
@@ -501,7 +1150,7 @@ impl ConnectionHandle {
         source_kind: Option<MediaSourceKind>,
     ) -> LocalBoxFuture<'static, ChangeMediaStateResult> {
         let inner = self
-            .0
+            .inner
             .upgrade()
             .ok_or_else(|| tracerr::new!(ChangeMediaStateError::Detached));
         let inner = match inner {
@@ -522,14 +1171,17 @@ impl ConnectionHandle {
 pub struct Connection(Rc<InnerConnection>);
 
 impl Connection {
-    /// Instantiates a new [`Connection`] for the given `Member`.
+    /// Instantiates a new [`Connection`] for the given `Member`, being the
+    /// `seq`-th created one.
     ///
     /// Based on the provided [`RecvConstraints`] individual [`RecvConstraints`]
     /// of this [`Connection`] will automatically synchronize.
     #[must_use]
-    pub fn new(
+    fn new(
         remote_id: MemberId,
         room_recv_constraints: &Rc<RecvConstraints>,
+        seq: u64,
+        on_member_media_state_changed: MemberMediaStateNotifier,
     ) -> Self {
         // Clone initial incoming media constraints.
         let recv_constraints = Rc::new(room_recv_constraints.as_ref().clone());
@@ -556,12 +1208,26 @@ impl Connection {
                 ),
             ],
             remote_id,
+            seq,
             quality_score: Cell::default(),
             on_quality_score_update: platform::Callback::default(),
             recv_constraints,
             on_close: platform::Callback::default(),
             on_remote_track_added: platform::Callback::default(),
+            on_remote_track_removed: platform::Callback::default(),
+            remote_tracks: RefCell::default(),
+            remote_tracks_task_handles: RefCell::default(),
             receivers: RefCell::default(),
+            senders: RefCell::default(),
+            on_member_media_state_changed,
+            receivers_task_handles: RefCell::default(),
+            setup_timings: RefCell::default(),
+            traffic: RefCell::default(),
+            transport: RefCell::default(),
+            negotiations: RefCell::default(),
+            quality_limitation: Rc::default(),
+            av_sync: RefCell::default(),
+            ice_gathering: RefCell::default(),
         }))
     }
 
@@ -591,6 +1257,13 @@ impl Connection {
         TaskHandle::from(abort)
     }
 
+    /// Returns individual [`RecvConstraints`] of this [`Connection`], which
+    /// new [`receiver::State`]s of it should be created with.
+    #[must_use]
+    pub fn recv_constraints(&self) -> &RecvConstraints {
+        &self.0.recv_constraints
+    }
+
     /// Stores provided [`receiver::State`] in this [`Connection`].
     ///
     /// Updates [`MediaExchangeState`] of the provided [`receiver::State`] based
@@ -598,30 +1271,161 @@ impl Connection {
     ///
     /// [`MediaExchangeState`]: crate::peer::MediaExchangeState
     pub fn add_receiver(&self, receiver: Rc<receiver::State>) {
-        let enabled_in_cons = match &receiver.kind() {
-            MediaKind::Audio => self.0.recv_constraints.is_audio_enabled(),
-            MediaKind::Video => {
-                self.0.recv_constraints.is_video_device_enabled()
-                    || self.0.recv_constraints.is_video_display_enabled()
-            }
-        };
+        let enabled_in_cons = self
+            .0
+            .recv_constraints
+            .is_enabled_for(&receiver.media_type());
         receiver
             .media_exchange_state_controller()
             .transition_to(enabled_in_cons.into());
 
+        let task = self.spawn_media_state_listener(&receiver);
+        self.0.receivers_task_handles.borrow_mut().push(task);
         self.0.receivers.borrow_mut().push(receiver);
     }
 
+    /// Stores the provided [`sender::State`] sending media to the remote
+    /// `Member` of this [`Connection`].
+    pub fn add_sender(&self, sender: &Rc<sender::State>) {
+        let sender = Rc::downgrade(sender);
+        let mut senders = self.0.senders.borrow_mut();
+        senders.retain(|s| s.strong_count() > 0);
+        if !senders.iter().any(|s| s.ptr_eq(&sender)) {
+            senders.push(sender);
+        }
+    }
+
+    /// Spawns a listener of the [`MemberMediaState`] changes of the provided
+    /// [`receiver::State`], invoking the `on_member_media_state_changed`
+    /// callback on them.
+    ///
+    /// Returns [`TaskHandle`] for the spawned changes listener.
+    fn spawn_media_state_listener(
+        &self,
+        receiver: &receiver::State,
+    ) -> TaskHandle {
+        let mut changes = receiver.on_member_media_state_change();
+        let notifier = self.0.on_member_media_state_changed.clone();
+        let member_id = self.0.remote_id.clone();
+        let kind = receiver.kind();
+        let source_kind = receiver.source_kind().into();
+        let (fut, abort) = future::abortable(async move {
+            while let Some(state) = changes.next().await {
+                notifier.notify(MemberMediaStateChange {
+                    member_id: member_id.clone(),
+                    kind,
+                    source_kind,
+                    state,
+                });
+            }
+        });
+        platform::spawn(fut.map(drop));
+
+        TaskHandle::from(abort)
+    }
+
+    /// Returns the current [`MemberMediaState`]s of all the media received in
+    /// this [`Connection`].
+    fn media_states(&self) -> Vec<MemberMediaStateChange> {
+        self.0
+            .receivers
+            .borrow()
+            .iter()
+            .flat_map(|r| {
+                let (enabled, muted) = r.member_media_state();
+                [enabled, muted].map(|state| MemberMediaStateChange {
+                    member_id: self.0.remote_id.clone(),
+                    kind: r.kind(),
+                    source_kind: r.source_kind().into(),
+                    state,
+                })
+            })
+            .collect()
+    }
+
+    /// Sets [`SetupTimings`] of the `PeerConnection` this [`Connection`] is
+    /// established via.
+    pub fn set_setup_timings(&self, timings: Rc<SetupTimings>) {
+        drop(self.0.setup_timings.replace(Some(timings)));
+    }
+
+    /// Sets [`TrafficCounter`] of the `PeerConnection` this [`Connection`] is
+    /// established via.
+    pub fn set_traffic_counter(&self, traffic: Rc<TrafficCounter>) {
+        drop(self.0.traffic.replace(Some(traffic)));
+    }
+
+    /// Sets [`TransportMonitor`] of the `PeerConnection` this [`Connection`]
+    /// is established via.
+    pub fn set_transport_monitor(&self, transport: Rc<TransportMonitor>) {
+        drop(self.0.transport.replace(Some(transport)));
+    }
+
+    /// Sets [`NegotiationCounter`] of the `PeerConnection` this [`Connection`]
+    /// is established via.
+    pub fn set_negotiation_counter(
+        &self,
+        negotiations: Rc<NegotiationCounter>,
+    ) {
+        drop(self.0.negotiations.replace(Some(negotiations)));
+    }
+
+    /// Sets [`QualityLimitationCounter`] of the `PeerConnection` this
+    /// [`Connection`] is established via.
+    pub fn set_quality_limitation_counter(
+        &self,
+        quality_limitation: Rc<QualityLimitationCounter>,
+    ) {
+        drop(self.0.quality_limitation.replace(Some(quality_limitation)));
+    }
+
+    /// Sets [`AvSyncCounter`] of the `PeerConnection` this [`Connection`] is
+    /// established via.
+    pub fn set_av_sync_counter(&self, av_sync: Rc<AvSyncCounter>) {
+        drop(self.0.av_sync.replace(Some(av_sync)));
+    }
+
+    /// Sets [`platform::IceGatheringCounter`] of the `PeerConnection` this
+    /// [`Connection`] is established via.
+    pub fn set_ice_gathering_counter(
+        &self,
+        ice_gathering: Rc<platform::IceGatheringCounter>,
+    ) {
+        drop(self.0.ice_gathering.replace(Some(ice_gathering)));
+    }
+
     /// Invokes `on_remote_track_added` callback with the provided
-    /// [`remote::Track`].
+    /// [`remote::Track`], and spawns a listener invoking
+    /// `on_remote_track_removed` callback once it's stopped.
     pub fn add_remote_track(&self, track: remote::Track) {
+        self.0.remote_tracks.borrow_mut().push(track.downgrade());
+
+        let weak_inner = Rc::downgrade(&self.0);
+        let (fut, abort) = future::abortable({
+            let track = track.clone();
+            async move {
+                track.when_stopped().await;
+                if let Some(inner) = weak_inner.upgrade() {
+                    inner.remove_remote_track(track);
+                }
+            }
+        });
+        platform::spawn(fut.map(drop));
+        self.0
+            .remote_tracks_task_handles
+            .borrow_mut()
+            .push(TaskHandle::from(abort));
+
         self.0.on_remote_track_added.call1(track);
     }
 
     /// Creates a new external handle to this [`Connection`].
     #[must_use]
     pub fn new_handle(&self) -> ConnectionHandle {
-        ConnectionHandle(Rc::downgrade(&self.0))
+        ConnectionHandle {
+            inner: Rc::downgrade(&self.0),
+            quality_limitation: Rc::clone(&self.0.quality_limitation),
+        }
     }
 
     /// Updates [`ConnectionQualityScore`] of this [`Connection`].
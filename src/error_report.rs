@@ -0,0 +1,149 @@
+//! Reporting of non-fatal errors happened in a [`Room`].
+//!
+//! [`Room`]: crate::room::Room
+
+use std::{cell::RefCell, collections::HashMap, fmt, time::Duration};
+
+use derive_more::Display;
+use tracerr::{Trace, Traced};
+
+use crate::platform;
+
+/// Kind of a [`NonFatalError`].
+#[derive(Clone, Copy, Debug, Display, Eq, Hash, PartialEq)]
+pub enum NonFatalErrorKind {
+    /// Failed to collect or parse [`platform::RtcStats`].
+    Stats,
+
+    /// Failed to add a remote ICE candidate, so it's ignored.
+    IceCandidate,
+
+    /// Failed to update encoding parameters of an outbound media.
+    Encoding,
+
+    /// Failed to add a remote track.
+    RemoteTrack,
+
+    /// Failed to update a media state after a local track has ended.
+    LocalTrack,
+
+    /// Negotiation has ended up in an unexpected state.
+    Negotiation,
+
+    /// Failed to handle an event from a media server or a peer connection.
+    EventHandling,
+}
+
+/// Error happened in a [`Room`] without breaking it, so it's only reported.
+///
+/// [`Room`]: crate::room::Room
+#[derive(Clone, Debug)]
+pub struct NonFatalError {
+    /// [`NonFatalErrorKind`] of this [`NonFatalError`].
+    pub kind: NonFatalErrorKind,
+
+    /// Message describing this [`NonFatalError`].
+    pub message: String,
+
+    /// [`Trace`] of this [`NonFatalError`].
+    pub trace: Trace,
+}
+
+impl NonFatalError {
+    /// Creates a new [`NonFatalError`] of the provided [`NonFatalErrorKind`]
+    /// out of the provided [`Traced`] error.
+    #[must_use]
+    pub fn new<E: fmt::Display>(
+        kind: NonFatalErrorKind,
+        err: Traced<E>,
+    ) -> Self {
+        let (err, trace) = err.split();
+        Self {
+            kind,
+            message: err.to_string(),
+            trace,
+        }
+    }
+}
+
+impl fmt::Display for NonFatalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} error: {}", self.kind, self.message)
+    }
+}
+
+/// Rate limiter of identical [`NonFatalError`]s.
+///
+/// [`NonFatalError`]s are identical if they have the same
+/// [`NonFatalErrorKind`] and message.
+#[derive(Debug, Default)]
+pub struct ErrorRateLimiter {
+    /// Times the identical [`NonFatalError`]s have been passed the last time
+    /// at.
+    passed_at: RefCell<HashMap<(NonFatalErrorKind, String), Duration>>,
+}
+
+impl ErrorRateLimiter {
+    /// Minimal interval between passing identical [`NonFatalError`]s.
+    pub const INTERVAL: Duration = Duration::from_secs(10);
+
+    /// Indicates whether the provided [`NonFatalError`] should be passed, so
+    /// no identical one has been passed during the last
+    /// [`ErrorRateLimiter::INTERVAL`].
+    #[must_use]
+    pub fn pass(&self, err: &NonFatalError) -> bool {
+        self.pass_at(err, platform::now())
+    }
+
+    /// Indicates whether the provided [`NonFatalError`] should be passed at
+    /// the provided time.
+    fn pass_at(&self, err: &NonFatalError, now: Duration) -> bool {
+        let mut passed_at = self.passed_at.borrow_mut();
+        passed_at.retain(|_, at| now.saturating_sub(*at) < Self::INTERVAL);
+
+        let key = (err.kind, err.message.clone());
+        if passed_at.contains_key(&key) {
+            return false;
+        }
+        drop(passed_at.insert(key, now));
+        true
+    }
+}
+
+#[cfg(test)]
+mod spec {
+    use std::time::Duration;
+
+    use super::{ErrorRateLimiter, NonFatalError, NonFatalErrorKind};
+
+    /// Returns a [`NonFatalError`] of the provided [`NonFatalErrorKind`] and
+    /// message.
+    fn error(kind: NonFatalErrorKind, message: &str) -> NonFatalError {
+        NonFatalError::new(kind, tracerr::new!(message.to_owned()))
+    }
+
+    #[test]
+    fn passes_identical_errors_once_per_interval() {
+        let limiter = ErrorRateLimiter::default();
+        let err = error(NonFatalErrorKind::Stats, "undefined");
+
+        assert!(limiter.pass_at(&err, Duration::from_secs(100)));
+        assert!(!limiter.pass_at(&err, Duration::from_secs(101)));
+        assert!(!limiter.pass_at(&err, Duration::from_secs(109)));
+        assert!(limiter.pass_at(&err, Duration::from_secs(110)));
+        assert!(!limiter.pass_at(&err, Duration::from_secs(111)));
+    }
+
+    #[test]
+    fn passes_different_errors_independently() {
+        use NonFatalErrorKind::{Encoding, Stats};
+
+        let limiter = ErrorRateLimiter::default();
+        let (first, second) = (Duration::from_secs(1), Duration::from_secs(2));
+
+        assert!(limiter.pass_at(&error(Stats, "a"), first));
+        assert!(limiter.pass_at(&error(Stats, "b"), first));
+        assert!(limiter.pass_at(&error(Encoding, "a"), first));
+        assert!(!limiter.pass_at(&error(Encoding, "a"), second));
+    }
+}
@@ -0,0 +1,337 @@
+//! Bounded log of the lifecycle events happened in a [`Room`], retrievable for
+//! bug reports.
+//!
+//! [`Room`]: crate::room::Room
+
+use std::{
+    cell::{Cell, RefCell},
+    collections::VecDeque,
+    fmt, mem,
+    time::Duration,
+};
+
+use serde::Serialize;
+
+use crate::platform;
+
+/// Names of the fields whose values are removed from the logged events.
+const REDACTED_FIELDS: [&str; 5] =
+    ["sdp_offer", "sdp_answer", "credential", "token", "password"];
+
+/// Replacement of the redacted values.
+const REDACTED: &str = "<redacted>";
+
+/// Kind of an [`Entry`] in an [`EventLog`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventKind {
+    /// Progress of joining a [`Room`].
+    ///
+    /// [`Room`]: crate::room::Room
+    Join,
+
+    /// Event received from a media server.
+    Rpc,
+
+    /// Event emitted by a [`PeerConnection`].
+    ///
+    /// [`PeerConnection`]: crate::peer::PeerConnection
+    Peer,
+
+    /// Connection to a media server has been lost.
+    ConnectionLost,
+
+    /// Connection to a media server has been restored.
+    Reconnected,
+
+    /// [`SessionMetrics`] of a [`Room`] have changed.
+    ///
+    /// [`Room`]: crate::room::Room
+    /// [`SessionMetrics`]: crate::session_metrics::SessionMetrics
+    SessionMetrics,
+
+    /// Error happened while handling an event.
+    Error,
+}
+
+/// Single entry of an [`EventLog`].
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct Entry {
+    /// Time the event has happened at, in milliseconds since the Unix epoch.
+    pub timestamp: u64,
+
+    /// [`EventKind`] of the event.
+    pub kind: EventKind,
+
+    /// Redacted description of the event.
+    pub event: String,
+}
+
+/// Bounded log of the lifecycle events happened in a [`Room`].
+///
+/// Once full, the oldest [`Entry`]s are evicted. SDP bodies, credentials and
+/// tokens are redacted before an event is stored.
+///
+/// [`Room`]: crate::room::Room
+#[derive(Debug)]
+pub struct EventLog {
+    /// Maximum number of the stored [`Entry`]s.
+    capacity: Cell<usize>,
+
+    /// Stored [`Entry`]s, from the oldest to the newest.
+    entries: RefCell<VecDeque<Entry>>,
+}
+
+impl Default for EventLog {
+    fn default() -> Self {
+        Self {
+            capacity: Cell::new(Self::DEFAULT_CAPACITY),
+            entries: RefCell::default(),
+        }
+    }
+}
+
+impl EventLog {
+    /// Default maximum number of the stored [`Entry`]s.
+    pub const DEFAULT_CAPACITY: usize = 500;
+    /// Number of the last [`Entry`]s included into messages of the fatal
+    /// errors.
+    pub const ERROR_DUMP_LIMIT: usize = 50;
+
+    /// Sets the maximum number of the stored [`Entry`]s, evicting the oldest
+    /// ones if there are more of them already.
+    pub fn set_capacity(&self, capacity: usize) {
+        self.capacity.set(capacity);
+        self.evict();
+    }
+
+    /// Records the provided event of the provided [`EventKind`].
+    pub fn record<E: fmt::Debug + ?Sized>(&self, kind: EventKind, event: &E) {
+        self.record_at(platform::now(), kind, &format!("{event:?}"));
+    }
+
+    /// Returns an estimate of the memory (in bytes) held by the stored
+    /// [`Entry`]s.
+    ///
+    /// Accounts the allocated capacities rather than the actual lengths, so
+    /// the estimate may exceed the size of the [`EventLog::dump()`].
+    #[must_use]
+    pub fn memory_usage_estimate(&self) -> usize {
+        let entries = self.entries.borrow();
+        entries.capacity() * mem::size_of::<Entry>()
+            + entries.iter().map(|e| e.event.capacity()).sum::<usize>()
+    }
+
+    /// Returns JSON array of the last `limit` recorded [`Entry`]s (or all of
+    /// them if [`None`]), from the oldest to the newest.
+    #[must_use]
+    pub fn dump(&self, limit: Option<usize>) -> String {
+        let entries = self.entries.borrow();
+        let skip = limit.map_or(0, |l| entries.len().saturating_sub(l));
+        let dumped: Vec<_> = entries.iter().skip(skip).collect();
+        serde_json::to_string(&dumped).unwrap_or_default()
+    }
+
+    /// Records the provided event description of the provided [`EventKind`]
+    /// happened at the provided time.
+    fn record_at(&self, at: Duration, kind: EventKind, event: &str) {
+        self.entries.borrow_mut().push_back(Entry {
+            timestamp: u64::try_from(at.as_millis()).unwrap_or(u64::MAX),
+            kind,
+            event: redact(event),
+        });
+        self.evict();
+    }
+
+    /// Evicts the oldest [`Entry`]s exceeding the capacity.
+    ///
+    /// Releases the memory of the evicted [`Entry`]s once the capacity is
+    /// lowered.
+    fn evict(&self) {
+        let mut entries = self.entries.borrow_mut();
+        let capacity = self.capacity.get();
+        let excess = entries.len().saturating_sub(capacity);
+        drop(entries.drain(..excess));
+        if entries.capacity() > capacity.saturating_mul(2) {
+            entries.shrink_to(capacity);
+        }
+    }
+}
+
+/// Redacts the provided [`fmt::Debug`] representation of an event.
+///
+/// Removes values of the [`REDACTED_FIELDS`], any string literals looking
+/// like an SDP, and `token` query parameters of any URLs.
+fn redact(event: &str) -> String {
+    let mut redacted = String::with_capacity(event.len());
+    let mut rest = event;
+    while let Some(start) = rest.find('"') {
+        let (before, literal) = rest.split_at(start);
+        redacted.push_str(before);
+        let len = literal_len(literal);
+        let (literal, after) = literal.split_at(len);
+        let content = literal.trim_matches('"');
+        if is_redacted_field(&redacted) || content.starts_with("v=0") {
+            redacted.push('"');
+            redacted.push_str(REDACTED);
+            redacted.push('"');
+        } else {
+            redacted.push_str(literal);
+        }
+        rest = after;
+    }
+    redacted.push_str(rest);
+    redact_token_params(&redacted)
+}
+
+/// Returns length of the string literal (including the quotes) the provided
+/// string starts with, considering the escaped quotes.
+fn literal_len(s: &str) -> usize {
+    let mut escaped = false;
+    for (i, c) in s.char_indices().skip(1) {
+        match c {
+            '\\' if !escaped => escaped = true,
+            '"' if !escaped => return i + 1,
+            _ => escaped = false,
+        }
+    }
+    s.len()
+}
+
+/// Indicates whether the provided [`fmt::Debug`] representation ends right
+/// before a value of any of the [`REDACTED_FIELDS`].
+fn is_redacted_field(preceding: &str) -> bool {
+    let preceding = preceding.trim_end();
+    let preceding = preceding
+        .strip_suffix('(')
+        .and_then(|p| p.trim_end().strip_suffix("Some"))
+        .unwrap_or(preceding)
+        .trim_end();
+    preceding.strip_suffix(':').map_or(false, |p| {
+        let field = p.trim_end();
+        REDACTED_FIELDS.iter().any(|f| {
+            field.strip_suffix(f).map_or(false, |p| {
+                !p.ends_with(|c: char| c.is_alphanumeric() || c == '_')
+            })
+        })
+    })
+}
+
+/// Redacts values of the `token` query parameters in the provided string.
+fn redact_token_params(s: &str) -> String {
+    let mut redacted = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(start) = rest.find("token=") {
+        let (before, param) = rest.split_at(start + "token=".len());
+        redacted.push_str(before);
+        redacted.push_str(REDACTED);
+        let end = param
+            .find(|c: char| c == '&' || c == '"' || c.is_whitespace())
+            .unwrap_or(param.len());
+        rest = param.split_at(end).1;
+    }
+    redacted.push_str(rest);
+    redacted
+}
+
+#[cfg(test)]
+mod spec {
+    use std::{mem, time::Duration};
+
+    use super::{redact, Entry, EventKind, EventLog};
+
+    /// Returns `event` descriptions of the provided [`EventLog::dump()`].
+    fn events(dump: &str) -> Vec<String> {
+        let dump: Vec<serde_json::Value> = serde_json::from_str(dump).unwrap();
+        dump.into_iter()
+            .map(|e| e["event"].as_str().unwrap().to_owned())
+            .collect()
+    }
+
+    #[test]
+    fn dumps_in_order() {
+        let log = EventLog::default();
+        for (i, event) in ["first", "second", "third"].iter().enumerate() {
+            log.record_at(
+                Duration::from_millis(u64::try_from(i).unwrap()),
+                EventKind::Rpc,
+                event,
+            );
+        }
+
+        assert_eq!(events(&log.dump(None)), ["first", "second", "third"]);
+        assert_eq!(events(&log.dump(Some(2))), ["second", "third"]);
+        assert_eq!(events(&log.dump(Some(10))).len(), 3);
+
+        let dump: serde_json::Value =
+            serde_json::from_str(&log.dump(Some(1))).unwrap();
+        assert_eq!(
+            dump,
+            serde_json::json!([
+                {"timestamp": 2, "kind": "rpc", "event": "third"},
+            ]),
+        );
+    }
+
+    #[test]
+    fn evicts_oldest_entries() {
+        let log = EventLog::default();
+        for i in 0..(EventLog::DEFAULT_CAPACITY + 10) {
+            log.record_at(Duration::ZERO, EventKind::Peer, &i.to_string());
+        }
+
+        let dumped = events(&log.dump(None));
+        assert_eq!(dumped.len(), EventLog::DEFAULT_CAPACITY);
+        assert_eq!(dumped[0], "10");
+
+        log.set_capacity(2);
+        assert_eq!(
+            events(&log.dump(None)),
+            [
+                (EventLog::DEFAULT_CAPACITY + 8).to_string(),
+                (EventLog::DEFAULT_CAPACITY + 9).to_string(),
+            ],
+        );
+    }
+
+    #[test]
+    fn redacts_sdp_and_credentials() {
+        assert_eq!(
+            redact(r#"Made { id: Id(1), sdp_answer: "v=0\r\n\"x\"" }"#),
+            r#"Made { id: Id(1), sdp_answer: "<redacted>" }"#,
+        );
+        assert_eq!(
+            redact(r#"Server { username: Some("u"), credential: Some("c") }"#),
+            r#"Server { username: Some("u"), credential: Some("<redacted>") }"#,
+        );
+        assert_eq!(
+            redact(r#"Join { url: "wss://a/r/m?token=777&x=1" }"#),
+            r#"Join { url: "wss://a/r/m?token=<redacted>&x=1" }"#,
+        );
+        assert_eq!(
+            redact("Failed to connect to wss://a/r/m?token=777"),
+            "Failed to connect to wss://a/r/m?token=<redacted>",
+        );
+        assert_eq!(redact(r#"Offer("v=0\r\n")"#), r#"Offer("<redacted>")"#);
+        assert_eq!(
+            redact(r#"Left { member_id: "sdp_offer" }"#),
+            r#"Left { member_id: "sdp_offer" }"#,
+        );
+    }
+
+    #[test]
+    fn stays_bounded_in_long_call() {
+        let log = EventLog::default();
+        log.set_capacity(100);
+        let event = "x".repeat(64);
+        // Slack for the capacity of the entries buffer growing by doubling.
+        let bound = 2 * 100 * (mem::size_of::<Entry>() + event.len());
+
+        for _ in 0..10_000 {
+            log.record_at(Duration::ZERO, EventKind::Peer, &event);
+
+            assert!(log.entries.borrow().len() <= 100);
+            assert!(log.memory_usage_estimate() <= bound);
+        }
+    }
+}
@@ -1,18 +1,53 @@
 //! General library interface.
 
 use futures::FutureExt as _;
-use std::{cell::RefCell, rc::Rc};
+use std::{cell::RefCell, fmt, rc::Rc};
 
 use crate::platform;
 
 use crate::{
+    event_log::EventLog,
     media::{MediaManager, MediaManagerHandle},
-    room::{Room, RoomHandle},
+    room::{Room, RoomHandle, RoomSnapshot},
     rpc::{
         ClientDisconnect, RpcSession, WebSocketRpcClient, WebSocketRpcSession,
     },
 };
 
+/// Options of a [`Jason`].
+#[derive(Clone, Copy, Debug)]
+pub struct JasonConfig {
+    /// Indicator whether reconnections to a media server should react to the
+    /// network availability and page visibility changes: be performed
+    /// immediately once the network becomes available or the page becomes
+    /// visible, and be paused while the network is unavailable.
+    ///
+    /// Enabled by default.
+    pub reconnect_on_network_events: bool,
+
+    /// Maximum number of the lifecycle events retained by every [`Room`] for
+    /// the [`RoomHandle::dump_events()`].
+    ///
+    /// [`EventLog::DEFAULT_CAPACITY`] by default.
+    pub event_log_capacity: usize,
+
+    /// Indicator whether the periodic stats collection of every [`Room`]
+    /// should be paused while the page is hidden (`visibilitychange` event).
+    ///
+    /// Disabled by default.
+    pub pause_stats_when_hidden: bool,
+}
+
+impl Default for JasonConfig {
+    fn default() -> Self {
+        Self {
+            reconnect_on_network_events: true,
+            event_log_capacity: EventLog::DEFAULT_CAPACITY,
+            pause_stats_when_hidden: false,
+        }
+    }
+}
+
 /// General library interface.
 ///
 /// Responsible for managing shared transports, local media and room
@@ -20,8 +55,11 @@ use crate::{
 #[derive(Debug)]
 pub struct Jason(Rc<RefCell<Inner>>);
 
+/// Factory producing [`platform::RpcTransport`]s for all the sessions created
+/// by a [`Jason`].
+type TransportFactory = Rc<dyn Fn() -> Rc<dyn platform::RpcTransport>>;
+
 /// Inner representation if a [`Jason`].
-#[derive(Debug)]
 struct Inner {
     /// [`Jason`]s [`MediaManager`].
     ///
@@ -36,20 +74,70 @@ struct Inner {
     ///
     /// Only one [`WebSocketRpcClient`] is supported at the moment.
     rpc: Rc<WebSocketRpcClient>,
+
+    /// [`JasonConfig`] of [`Room`]s initialized by this [`Jason`].
+    config: JasonConfig,
+
+    /// [`TransportFactory`] of the [`WebSocketRpcClient`]s created by this
+    /// [`Jason`].
+    transport_factory: TransportFactory,
+}
+
+impl fmt::Debug for Inner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Inner")
+            .field("media_manager", &self.media_manager)
+            .field("rooms", &self.rooms)
+            .field("rpc", &self.rpc)
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
 }
 
 impl Jason {
     /// Instantiates a new [`Jason`] interface to interact with this library.
     #[must_use]
     pub fn new() -> Self {
+        Self::with_config(JasonConfig::default())
+    }
+
+    /// Instantiates a new [`Jason`] interface to interact with this library,
+    /// configured with the provided [`JasonConfig`].
+    #[must_use]
+    pub fn with_config(config: JasonConfig) -> Self {
+        Self::init(config, default_transport_factory())
+    }
+
+    /// Instantiates a new [`Jason`] interface to interact with this library,
+    /// which sessions communicate with a media server via the
+    /// [`platform::RpcTransport`]s created by the provided `factory` instead
+    /// of the default WebSocket ones.
+    ///
+    /// Intended for testing the RPC layer without a real media server (see
+    /// `testing` module).
+    #[must_use]
+    pub fn with_transport_factory<F>(factory: F) -> Self
+    where
+        F: Fn() -> Rc<dyn platform::RpcTransport> + 'static,
+    {
+        Self::init(JasonConfig::default(), Rc::new(factory))
+    }
+
+    /// Instantiates a new [`Jason`] configured with the provided
+    /// [`JasonConfig`] and [`TransportFactory`].
+    fn init(config: JasonConfig, transport_factory: TransportFactory) -> Self {
         platform::set_panic_hook();
         if !log::logger().enabled(&log::Metadata::builder().build()) {
             platform::init_logger();
         }
 
-        Self::with_rpc_client(Rc::new(WebSocketRpcClient::new(Box::new(
-            || Rc::new(platform::WebSocketRpcTransport::new()),
-        ))))
+        let this = Self::with_rpc_client(new_rpc_client(&transport_factory));
+        {
+            let mut inner = this.0.borrow_mut();
+            inner.config = config;
+            inner.transport_factory = transport_factory;
+        }
+        this
     }
 
     /// Creates a new [`Room`] and returns its [`RoomHandle`].
@@ -59,6 +147,20 @@ impl Jason {
         self.inner_init_room(WebSocketRpcSession::new(rpc))
     }
 
+    /// Creates a new [`Room`] with the media states intended in the provided
+    /// [`RoomSnapshot`] pre-applied, and returns its [`RoomHandle`].
+    ///
+    /// The returned [`RoomHandle`] should be joined to the
+    /// [`RoomSnapshot::room_url`] with fresh credentials.
+    #[must_use]
+    pub fn restore_room(&self, snapshot: &RoomSnapshot) -> RoomHandle {
+        let handle = self.init_room();
+        if let Some(room) = self.0.borrow().rooms.last() {
+            room.restore_state(snapshot);
+        }
+        handle
+    }
+
     /// Returns a [`MediaManagerHandle`].
     #[must_use]
     pub fn media_manager(&self) -> MediaManagerHandle {
@@ -79,14 +181,10 @@ impl Jason {
 
         if let Some(i) = index {
             let this = &mut self.0.borrow_mut();
-            let room = this.rooms.swap_remove(i);
-            room.set_close_reason(ClientDisconnect::RoomClosed.into());
-            drop(room);
-            if this.rooms.is_empty() {
-                this.rpc = Rc::new(WebSocketRpcClient::new(Box::new(|| {
-                    Rc::new(platform::WebSocketRpcTransport::new())
-                })));
-            }
+            this.rooms
+                .swap_remove(i)
+                .close(ClientDisconnect::RoomClosed.into());
+            this.release_rpc_if_idle();
         }
     }
 
@@ -106,6 +204,8 @@ impl Jason {
             rpc,
             rooms: Vec::new(),
             media_manager: Rc::new(MediaManager::default()),
+            config: JasonConfig::default(),
+            transport_factory: default_transport_factory(),
         })))
     }
 
@@ -113,6 +213,13 @@ impl Jason {
     fn inner_init_room(&self, rpc: Rc<dyn RpcSession>) -> RoomHandle {
         let on_normal_close = rpc.on_normal_close();
         let room = Room::new(rpc, Rc::clone(&self.0.borrow().media_manager));
+        room.set_reconnect_on_network_events(
+            self.0.borrow().config.reconnect_on_network_events,
+        );
+        room.set_event_log_capacity(self.0.borrow().config.event_log_capacity);
+        room.set_pause_stats_when_hidden(
+            self.0.borrow().config.pause_stats_when_hidden,
+        );
 
         let weak_room = room.downgrade();
         let weak_inner = Rc::downgrade(&self.0);
@@ -125,6 +232,7 @@ impl Jason {
                     inner.rooms.iter().position(|r| r.ptr_eq(&this_room));
                 if let Some(i) = index {
                     inner.rooms.remove(i).close(reason);
+                    inner.release_rpc_if_idle();
                 }
                 Some(())
             })();
@@ -136,6 +244,30 @@ impl Jason {
     }
 }
 
+impl Inner {
+    /// Replaces the [`WebSocketRpcClient`] of this [`Inner`] with a new one, if
+    /// there are no [`Room`]s left, so the connection with a media server is
+    /// released.
+    fn release_rpc_if_idle(&mut self) {
+        if self.rooms.is_empty() {
+            self.rpc = new_rpc_client(&self.transport_factory);
+        }
+    }
+}
+
+/// Returns a [`TransportFactory`] of the default WebSocket
+/// [`platform::RpcTransport`]s.
+fn default_transport_factory() -> TransportFactory {
+    Rc::new(|| Rc::new(platform::WebSocketRpcTransport::new()))
+}
+
+/// Creates a new [`WebSocketRpcClient`] using the provided
+/// [`TransportFactory`].
+fn new_rpc_client(factory: &TransportFactory) -> Rc<WebSocketRpcClient> {
+    let factory = Rc::clone(factory);
+    Rc::new(WebSocketRpcClient::new(Box::new(move || factory())))
+}
+
 impl Default for Jason {
     fn default() -> Self {
         Self::new()
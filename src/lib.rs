@@ -146,9 +146,15 @@
 pub mod utils;
 pub mod api;
 pub mod connection;
+pub mod error_report;
+pub mod event_log;
 pub mod jason;
 pub mod media;
+pub mod media_policy;
 pub mod peer;
 pub mod platform;
 pub mod room;
 pub mod rpc;
+pub mod session_metrics;
+#[cfg(feature = "testing")]
+pub mod testing;
@@ -0,0 +1,72 @@
+//! [MediaTrackCapabilities][1] of media input devices.
+//!
+//! [1]: https://w3.org/TR/mediacapture-streams#media-track-capabilities
+
+use super::FacingMode;
+
+/// Range of values supported by a media input device.
+///
+/// Representation of a [DoubleRange][1] or a [ULongRange][2].
+///
+/// [1]: https://w3.org/TR/mediacapture-streams#dom-doublerange
+/// [2]: https://w3.org/TR/mediacapture-streams#dom-ulongrange
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CapabilityRange {
+    /// Minimum supported value.
+    pub min: f64,
+
+    /// Maximum supported value.
+    pub max: f64,
+}
+
+/// Capabilities of a video input device.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct VideoCapabilities {
+    /// Supported range of a [width][1] in pixels.
+    ///
+    /// [1]: https://w3.org/TR/mediacapture-streams#def-constraint-width
+    pub width_range: Option<CapabilityRange>,
+
+    /// Supported range of a [height][1] in pixels.
+    ///
+    /// [1]: https://w3.org/TR/mediacapture-streams#def-constraint-height
+    pub height_range: Option<CapabilityRange>,
+
+    /// Supported range of a [frameRate][1] in frames per second.
+    ///
+    /// [1]: https://w3.org/TR/mediacapture-streams#def-constraint-frameRate
+    pub frame_rate_range: Option<CapabilityRange>,
+
+    /// Supported [`FacingMode`]s.
+    pub facing_modes: Vec<FacingMode>,
+}
+
+/// Capabilities of an audio input device.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AudioCapabilities {
+    /// Supported range of a [sampleRate][1] in samples per second.
+    ///
+    /// [1]: https://w3.org/TR/mediacapture-streams#def-constraint-sampleRate
+    pub sample_rates: Option<CapabilityRange>,
+
+    /// Supported range of a [channelCount][1].
+    ///
+    /// [1]: https://w3.org/TR/mediacapture-streams#def-constraint-channelCount
+    pub channel_counts: Option<CapabilityRange>,
+
+    /// Supported [echoCancellation][1] modes.
+    ///
+    /// [1]: https://tinyurl.com/w3-streams#def-constraint-echoCancellation
+    pub echo_cancellation_modes: Vec<bool>,
+}
+
+/// Capabilities of a media input device, reported before capturing any media
+/// from it.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DeviceCapabilities {
+    /// Capabilities of an audio input device.
+    Audio(AudioCapabilities),
+
+    /// Capabilities of a video input device.
+    Video(VideoCapabilities),
+}
@@ -1,6 +1,6 @@
 //! Media tracks and streams constraints functionality.
 
-use std::{cell::RefCell, rc::Rc};
+use std::{cell::RefCell, fmt, rc::Rc, str::FromStr};
 
 use derive_more::Display;
 use futures::stream::LocalBoxStream;
@@ -9,13 +9,19 @@ use medea_client_api_proto::{
     MediaType as ProtoTrackConstraints, MediaType, VideoSettings,
 };
 use medea_reactive::ObservableCell;
+use serde::Deserialize;
+use tracerr::Traced;
 
 use crate::{
-    media::{track::MediaStreamTrackState, MediaKind},
+    media::{
+        track::{local, MediaStreamTrackState},
+        ContentHint, MediaKind,
+    },
     peer::{
         media_exchange_state, mute_state, LocalStreamUpdateCriteria, MediaState,
     },
     platform,
+    utils::Caused,
 };
 
 /// Describes directions that a camera can face, as seen from a user's
@@ -48,7 +54,14 @@ pub enum FacingMode {
 ///
 /// [`PeerConnection`]: crate::peer::PeerConnection
 #[derive(Clone, Debug, Default)]
-pub struct LocalTracksConstraints(Rc<RefCell<MediaStreamSettings>>);
+pub struct LocalTracksConstraints {
+    /// [`MediaStreamSettings`] provided by a user.
+    settings: Rc<RefCell<MediaStreamSettings>>,
+
+    /// Default [`MediaStreamSettings`] filling the constraints not set in the
+    /// [`LocalTracksConstraints::settings`].
+    defaults: Rc<RefCell<Option<MediaStreamSettings>>>,
+}
 
 /// Constraints to the media received from remote. Used to disable or enable
 /// media receiving.
@@ -132,6 +145,18 @@ impl RecvConstraints {
         self.is_video_display_enabled.get()
     }
 
+    /// Indicates whether receiving of the media with the provided
+    /// [`MediaType`] is enabled, respecting its [`MediaSourceKind`].
+    pub fn is_enabled_for(&self, media_type: &MediaType) -> bool {
+        match media_type {
+            MediaType::Audio(_) => self.is_audio_enabled(),
+            MediaType::Video(video) => match video.source_kind {
+                MediaSourceKind::Device => self.is_video_device_enabled(),
+                MediaSourceKind::Display => self.is_video_display_enabled(),
+            },
+        }
+    }
+
     /// Returns [`LocalBoxStream`] into which all `is_audio_enabled` updates
     /// will be sent.
     pub fn on_audio_enabled_change(&self) -> LocalBoxStream<'static, bool> {
@@ -158,7 +183,10 @@ impl RecvConstraints {
 #[cfg(feature = "mockable")]
 impl From<MediaStreamSettings> for LocalTracksConstraints {
     fn from(from: MediaStreamSettings) -> Self {
-        Self(Rc::new(RefCell::new(from)))
+        Self {
+            settings: Rc::new(RefCell::new(from)),
+            defaults: Rc::default(),
+        }
     }
 }
 
@@ -171,19 +199,41 @@ impl LocalTracksConstraints {
         &self,
         settings: &MediaStreamSettings,
     ) -> LocalStreamUpdateCriteria {
-        self.0.borrow().calculate_kinds_diff(settings)
+        self.settings.borrow().calculate_kinds_diff(settings)
     }
 
     /// Constrains the underlying [`MediaStreamSettings`] with the given `other`
     /// [`MediaStreamSettings`].
     pub fn constrain(&self, other: MediaStreamSettings) {
-        self.0.borrow_mut().constrain(other);
+        self.settings.borrow_mut().constrain(other);
     }
 
     /// Clones the underlying [`MediaStreamSettings`].
     #[must_use]
     pub fn inner(&self) -> MediaStreamSettings {
-        self.0.borrow().clone()
+        self.settings.borrow().clone()
+    }
+
+    /// Sets the default [`MediaStreamSettings`], filling the constraints which
+    /// are not set in the underlying [`MediaStreamSettings`].
+    ///
+    /// See [`MediaStreamSettings::merge_defaults()`] for details.
+    pub fn set_defaults(&self, defaults: MediaStreamSettings) {
+        drop(self.defaults.replace(Some(defaults)));
+    }
+
+    /// Returns the underlying [`MediaStreamSettings`] merged with the default
+    /// ones (if any), which should be used for acquiring new
+    /// [`local::Track`]s.
+    ///
+    /// [`local::Track`]: crate::media::track::local::Track
+    #[must_use]
+    pub fn effective(&self) -> MediaStreamSettings {
+        let mut settings = self.inner();
+        if let Some(defaults) = self.defaults.borrow().as_ref() {
+            settings.merge_defaults(defaults);
+        }
+        settings
     }
 
     /// Changes the underlying [`MediaStreamSettings`] basing on the provided
@@ -194,9 +244,11 @@ impl LocalTracksConstraints {
         kind: MediaKind,
         source_kind: Option<MediaSourceKind>,
     ) {
-        self.0
-            .borrow_mut()
-            .set_track_media_state(state, kind, source_kind);
+        self.settings.borrow_mut().set_track_media_state(
+            state,
+            kind,
+            source_kind,
+        );
     }
 
     /// Enables/disables provided [`LocalStreamUpdateCriteria`] based on
@@ -206,7 +258,7 @@ impl LocalTracksConstraints {
         state: media_exchange_state::Stable,
         kinds: LocalStreamUpdateCriteria,
     ) {
-        self.0
+        self.settings
             .borrow_mut()
             .set_media_exchange_state_by_kinds(state, kinds);
     }
@@ -215,14 +267,14 @@ impl LocalTracksConstraints {
     /// [`MediaStreamSettings`].
     #[must_use]
     pub fn enabled(&self, kind: MediaType) -> bool {
-        self.0.borrow().enabled(kind)
+        self.settings.borrow().enabled(kind)
     }
 
     /// Indicates whether provided [`MediaType`] is muted in the underlying
     /// [`MediaStreamSettings`].
     #[must_use]
     pub fn muted(&self, kind: MediaType) -> bool {
-        self.0.borrow().muted(kind)
+        self.settings.borrow().muted(kind)
     }
 
     /// Indicates whether the provided [`MediaKind`] and [`MediaSourceKind`] are
@@ -233,7 +285,7 @@ impl LocalTracksConstraints {
         kind: MediaKind,
         source: Option<MediaSourceKind>,
     ) -> bool {
-        self.0
+        self.settings
             .borrow()
             .is_track_enabled_and_constrained(kind, source)
     }
@@ -246,7 +298,7 @@ impl LocalTracksConstraints {
         kind: MediaKind,
         source: Option<MediaSourceKind>,
     ) -> bool {
-        self.0.borrow().is_track_enabled(kind, source)
+        self.settings.borrow().is_track_enabled(kind, source)
     }
 }
 
@@ -276,6 +328,43 @@ impl Default for AudioMediaTracksSettings {
     }
 }
 
+/// [`local::Track`] provided by an application to be published instead of a
+/// captured one.
+///
+/// Is compared by the identity of the underlying [`local::Track`].
+#[derive(Clone, Debug)]
+struct CustomTrack(Rc<local::Track>);
+
+impl CustomTrack {
+    /// Wraps the provided [`platform::MediaStreamTrack`] into a
+    /// [`local::Track::custom()`] of [`MediaSourceKind::Device`].
+    fn new(track: platform::MediaStreamTrack) -> Self {
+        Self(Rc::new(local::Track::custom(
+            track,
+            MediaSourceKind::Device,
+        )))
+    }
+
+    /// Indicates whether the provided [`local::Track`] is this [`CustomTrack`]
+    /// or its fork, being alive and of the provided [`MediaKind`].
+    async fn is_satisfied_by(
+        &self,
+        track: &local::Track,
+        kind: MediaKind,
+    ) -> bool {
+        track.is_derived_from(&self.0)
+            && satisfies_track(track.as_ref(), kind).await
+    }
+}
+
+impl PartialEq for CustomTrack {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for CustomTrack {}
+
 /// Indicates whether the provided [`platform::MediaStreamTrack`] satisfies any
 /// constraints with the provided [`MediaKind`].
 async fn satisfies_track(
@@ -286,6 +375,20 @@ async fn satisfies_track(
         && track.ready_state().await == MediaStreamTrackState::Live
 }
 
+/// Converts the provided [deviceId][1] constraint into an ideal one, so it
+/// doesn't fail media acquisition if the device is not available.
+///
+/// [1]: https://w3.org/TR/mediacapture-streams#def-constraint-deviceId
+fn ideal_device_id(
+    device_id: Option<ConstrainString<String>>,
+) -> Option<ConstrainString<String>> {
+    device_id.map(|id| match id {
+        ConstrainString::Exact(id) | ConstrainString::Ideal(id) => {
+            ConstrainString::Ideal(id)
+        }
+    })
+}
+
 /// [MediaStreamConstraints][1] for the video media type.
 ///
 /// [1]: https://w3.org/TR/mediacapture-streams#dom-mediastreamconstraints
@@ -371,6 +474,20 @@ impl VideoTrackConstraints<DeviceVideoTrackConstraints> {
             false
         }
     }
+
+    /// Indicates whether the provided [`local::Track`] satisfies these
+    /// [`VideoTrackConstraints`], considering the constraints it was captured
+    /// with.
+    ///
+    /// Returns `false` if these [`VideoTrackConstraints`] don't have any
+    /// constraints configured.
+    pub async fn is_satisfied_by(&self, track: &local::Track) -> bool {
+        if let Some(constraints) = &self.constraints {
+            self.enabled() && constraints.is_satisfied_by(track).await
+        } else {
+            false
+        }
+    }
 }
 
 impl VideoTrackConstraints<DisplayVideoTrackConstraints> {
@@ -389,8 +506,56 @@ impl VideoTrackConstraints<DisplayVideoTrackConstraints> {
             false
         }
     }
+
+    /// Indicates whether the provided [`local::Track`] satisfies these
+    /// [`VideoTrackConstraints`], considering the constraints it was captured
+    /// with.
+    ///
+    /// Returns `false` if these [`VideoTrackConstraints`] don't have any
+    /// constraints configured.
+    pub async fn is_satisfied_by(&self, track: &local::Track) -> bool {
+        if let Some(constraints) = &self.constraints {
+            self.enabled() && constraints.is_satisfied_by(track).await
+        } else {
+            false
+        }
+    }
+}
+
+/// Preset of [`MediaStreamSettings`] covering the most common configurations.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum MediaStreamSettingsPreset {
+    /// [`MediaStreamSettings::audio_only()`].
+    AudioOnly,
+
+    /// [`MediaStreamSettings::sd()`].
+    Sd,
+
+    /// [`MediaStreamSettings::hd()`].
+    Hd,
+
+    /// [`MediaStreamSettings::screen_share_with_audio()`].
+    ScreenShareWithAudio,
+}
+
+/// JSON representation of [`MediaStreamSettings`].
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MediaStreamSettingsJson {
+    /// [`MediaStreamSettingsPreset`] to build [`MediaStreamSettings`] from.
+    ///
+    /// [`MediaStreamSettings::new()`] are built if [`None`].
+    #[serde(default)]
+    preset: Option<MediaStreamSettingsPreset>,
 }
 
+/// Error of parsing [`MediaStreamSettings`] from a JSON string.
+#[derive(Caused, Clone, Debug, Display, Eq, PartialEq)]
+#[cause(error = platform::Error)]
+#[display(fmt = "Failed to parse MediaStreamSettings: {}", _0)]
+pub struct MediaStreamSettingsParseError(String);
+
 /// [MediaStreamConstraints][1] wrapper.
 ///
 /// [1]: https://w3.org/TR/mediacapture-streams#dom-mediastreamconstraints
@@ -454,19 +619,201 @@ impl MediaStreamSettings {
         self.display_video.set(constraints);
     }
 
-    /// Indicates whether the provided [`platform::MediaStreamTrack`] satisfies
-    /// some of the [`VideoTrackConstraints`] from this [`MediaStreamSettings`].
-    ///
-    /// Unconstrains [`VideoTrackConstraints`] which this
-    /// [`platform::MediaStreamTrack`] satisfies.
-    pub async fn unconstrain_if_satisfies_video<T>(&mut self, track: T) -> bool
-    where
-        T: AsRef<platform::MediaStreamTrack>,
-    {
-        if self.device_video.satisfies(&track).await {
+    /// Sets the provided audio [`platform::MediaStreamTrack`] to be published
+    /// instead of capturing a microphone, enabling audio.
+    ///
+    /// The provided [`platform::MediaStreamTrack`] stays owned by the caller:
+    /// it's never stopped by the library, and the constraints set via
+    /// [`MediaStreamSettings::audio()`] don't apply to it.
+    pub fn set_custom_audio_track(
+        &mut self,
+        track: platform::MediaStreamTrack,
+    ) {
+        self.audio.enabled = true;
+        self.audio.constraints.custom_track = Some(CustomTrack::new(track));
+    }
+
+    /// Sets the provided video [`platform::MediaStreamTrack`] to be published
+    /// as a [`MediaSourceKind::Device`] video instead of capturing a camera.
+    ///
+    /// The provided [`platform::MediaStreamTrack`] stays owned by the caller:
+    /// it's never stopped by the library, and the constraints set via
+    /// [`MediaStreamSettings::device_video()`] don't apply to it.
+    pub fn set_custom_video_track(
+        &mut self,
+        track: platform::MediaStreamTrack,
+    ) {
+        self.device_video
+            .constraints
+            .get_or_insert_with(DeviceVideoTrackConstraints::default)
+            .custom_track = Some(CustomTrack::new(track));
+    }
+
+    /// Takes the [`local::Track`]s set via the
+    /// [`MediaStreamSettings::set_custom_audio_track()`] and
+    /// [`MediaStreamSettings::set_custom_video_track()`] out of this
+    /// [`MediaStreamSettings`], if they're enabled.
+    ///
+    /// Disables the media kinds of the taken [`local::Track`]s, so they're not
+    /// captured anymore.
+    pub fn take_custom_tracks(&mut self) -> Vec<Rc<local::Track>> {
+        let mut tracks = Vec::new();
+        if self.is_audio_enabled() {
+            if let Some(track) = self.audio.constraints.custom_track.take() {
+                self.set_audio_publish(false);
+                tracks.push(track.0);
+            }
+        }
+        if self.is_device_video_enabled() {
+            let custom = self
+                .device_video
+                .constraints
+                .as_mut()
+                .and_then(|c| c.custom_track.take());
+            if let Some(track) = custom {
+                self.device_video.unconstrain();
+                tracks.push(track.0);
+            }
+        }
+        tracks
+    }
+
+    /// Creates new [`MediaStreamSettings`] capturing only a microphone.
+    ///
+    /// Audio is captured with `echoCancellation`, `noiseSuppression` and
+    /// `autoGainControl` enabled, while no video is captured.
+    #[must_use]
+    pub fn audio_only() -> Self {
+        let mut settings = Self::new();
+        let mut audio = AudioTrackConstraints::new();
+        audio.echo_cancellation(true);
+        audio.noise_suppression(true);
+        audio.auto_gain_control(true);
+        settings.audio(audio);
+        settings
+    }
+
+    /// Creates new [`MediaStreamSettings`] of a standard definition call.
+    ///
+    /// Audio is captured as in [`MediaStreamSettings::audio_only()`], along
+    /// with a camera video of ideal `640`x`480` resolution and ideal `30`
+    /// frame rate.
+    #[must_use]
+    pub fn sd() -> Self {
+        Self::with_device_video(640, 480, 30)
+    }
+
+    /// Creates new [`MediaStreamSettings`] of a high definition call.
+    ///
+    /// Audio is captured as in [`MediaStreamSettings::audio_only()`], along
+    /// with a camera video of ideal `1280`x`720` resolution and ideal `30`
+    /// frame rate.
+    #[must_use]
+    pub fn hd() -> Self {
+        Self::with_device_video(1280, 720, 30)
+    }
+
+    /// Creates new [`MediaStreamSettings`] of a screen sharing with a
+    /// microphone.
+    ///
+    /// Audio is captured as in [`MediaStreamSettings::audio_only()`], along
+    /// with a display video of ideal `1920`x`1080` resolution and ideal `15`
+    /// frame rate, while no camera video is captured.
+    #[must_use]
+    pub fn screen_share_with_audio() -> Self {
+        let mut settings = Self::audio_only();
+        let mut display = DisplayVideoTrackConstraints::new();
+        display.ideal_width(1920);
+        display.ideal_height(1080);
+        display.ideal_frame_rate(15);
+        settings.display_video(display);
+        settings
+    }
+
+    /// Creates new [`MediaStreamSettings::audio_only()`] along with a camera
+    /// video of the provided ideal resolution and frame rate.
+    fn with_device_video(width: u32, height: u32, frame_rate: u32) -> Self {
+        let mut settings = Self::audio_only();
+        let mut device = DeviceVideoTrackConstraints::new();
+        device.ideal_width(width);
+        device.ideal_height(height);
+        device.ideal_frame_rate(frame_rate);
+        settings.device_video(device);
+        settings
+    }
+
+    /// Checks sanity of the constraints set in this [`MediaStreamSettings`],
+    /// so the ones never satisfiable by any media source don't reach a
+    /// [getUserMedia()][1] or [getDisplayMedia()][2] request.
+    ///
+    /// Only obviously broken values are rejected, while unusual ones (like 8K
+    /// resolutions) are left for the request to decide.
+    ///
+    /// # Errors
+    ///
+    /// With an [`InvalidConstraintsError`] listing all the
+    /// [`InvalidConstraint`]s found.
+    ///
+    /// [1]: https://tinyurl.com/w3-streams#dom-mediadevices-getusermedia
+    /// [2]: https://w3.org/TR/screen-capture#dom-mediadevices-getdisplaymedia
+    pub fn validate(&self) -> Result<(), InvalidConstraintsError> {
+        let mut constraints = Vec::new();
+        let mut check = |field, reason: Option<InvalidConstraintReason>| {
+            if let Some(reason) = reason {
+                constraints.push(InvalidConstraint { field, reason });
+            }
+        };
+
+        check(
+            "audio.device_id",
+            ConstrainString::validate(&self.audio.constraints.device_id),
+        );
+        if let Some(video) = &self.device_video.constraints {
+            check(
+                "device_video.device_id",
+                ConstrainString::validate(&video.device_id),
+            );
+            check("device_video.width", ConstrainU32::validate(video.width));
+            check("device_video.height", ConstrainU32::validate(video.height));
+            check(
+                "device_video.frame_rate",
+                ConstrainU32::validate(video.frame_rate),
+            );
+        }
+        if let Some(video) = &self.display_video.constraints {
+            check(
+                "display_video.device_id",
+                ConstrainString::validate(&video.device_id),
+            );
+            check("display_video.width", ConstrainU32::validate(video.width));
+            check("display_video.height", ConstrainU32::validate(video.height));
+            check(
+                "display_video.frame_rate",
+                ConstrainU32::validate(video.frame_rate),
+            );
+        }
+
+        if constraints.is_empty() {
+            Ok(())
+        } else {
+            Err(InvalidConstraintsError { constraints })
+        }
+    }
+
+    /// Indicates whether the provided [`local::Track`] satisfies some of the
+    /// [`VideoTrackConstraints`] from this [`MediaStreamSettings`], considering
+    /// the constraints it was captured with.
+    ///
+    /// Unconstrains [`VideoTrackConstraints`] which this [`local::Track`]
+    /// satisfies.
+    pub async fn unconstrain_if_satisfies_video(
+        &mut self,
+        track: &local::Track,
+    ) -> bool {
+        if self.device_video.is_satisfied_by(track).await {
             self.device_video.unconstrain();
             true
-        } else if self.display_video.satisfies(&track).await {
+        } else if self.display_video.is_satisfied_by(track).await {
             self.display_video.unconstrain();
             true
         } else {
@@ -474,6 +821,39 @@ impl MediaStreamSettings {
         }
     }
 
+    /// Fills the constraints which are not set in this
+    /// [`MediaStreamSettings`] with the provided `defaults`, so the
+    /// constraints set here always win.
+    ///
+    /// Device IDs taken from the `defaults` are applied as ideal constraints,
+    /// so a disappeared default device doesn't break media acquisition.
+    ///
+    /// Enabled and muted states are not affected, as well as the video
+    /// sources unconstrained in this [`MediaStreamSettings`].
+    pub fn merge_defaults(&mut self, defaults: &Self) {
+        let mut audio = defaults.audio.constraints.clone();
+        audio.device_id = ideal_device_id(audio.device_id);
+        self.audio.constraints.merge(audio);
+
+        if let (Some(video), Some(default)) = (
+            self.device_video.constraints.as_mut(),
+            defaults.device_video.constraints.as_ref(),
+        ) {
+            let mut default = default.clone();
+            default.device_id = ideal_device_id(default.device_id);
+            video.merge(default);
+        }
+
+        if let (Some(video), Some(default)) = (
+            self.display_video.constraints.as_mut(),
+            defaults.display_video.constraints.as_ref(),
+        ) {
+            let mut default = default.clone();
+            default.device_id = ideal_device_id(default.device_id);
+            video.merge(default);
+        }
+    }
+
     /// Returns [`LocalStreamUpdateCriteria`] with [`MediaKind`] and
     /// [`MediaSourceKind`] which are different in the provided
     /// [`MediaStreamSettings`].
@@ -496,6 +876,39 @@ impl MediaStreamSettings {
         kinds
     }
 
+    /// Returns [`LocalStreamUpdateCriteria`] out of the provided `kinds`,
+    /// which captured [`local::Track`]s have to be stopped before capturing
+    /// new ones with these [`MediaStreamSettings`].
+    ///
+    /// Captured [`local::Track`]s are described by their [`MediaKind`],
+    /// [`MediaSourceKind`] and device ID.
+    ///
+    /// A [`local::Track`] has to be stopped first only if it's recaptured from
+    /// the same device, since the device may be captured exclusively. Display
+    /// [`local::Track`]s, and the ones without a device requested, are always
+    /// stopped first, as the device to be captured is unknown.
+    #[must_use]
+    pub fn stop_first_kinds(
+        &self,
+        kinds: LocalStreamUpdateCriteria,
+        captured: &[(MediaKind, MediaSourceKind, String)],
+    ) -> LocalStreamUpdateCriteria {
+        let mut stop_first = LocalStreamUpdateCriteria::empty();
+        for (kind, source, device_id) in captured {
+            if !kinds.has(*kind, *source) {
+                continue;
+            }
+            let is_same_device = *source == MediaSourceKind::Display
+                || self
+                    .device_id(*kind, *source)
+                    .map_or(true, |id| id == *device_id);
+            if is_same_device {
+                stop_first.add(*kind, *source);
+            }
+        }
+        stop_first
+    }
+
     /// Returns only audio constraints.
     #[must_use]
     pub const fn get_audio(&self) -> &AudioTrackConstraints {
@@ -729,6 +1142,97 @@ impl MediaStreamSettings {
         }
     }
 
+    /// Indicates whether the given [`MediaKind`] and [`MediaSourceKind`] are
+    /// muted in this [`MediaStreamSettings`].
+    #[must_use]
+    pub const fn is_track_muted(
+        &self,
+        kind: MediaKind,
+        source: MediaSourceKind,
+    ) -> bool {
+        match (kind, source) {
+            (MediaKind::Audio, _) => self.audio.muted,
+            (MediaKind::Video, MediaSourceKind::Device) => {
+                self.device_video.muted
+            }
+            (MediaKind::Video, MediaSourceKind::Display) => {
+                self.display_video.muted
+            }
+        }
+    }
+
+    /// Returns ID of the device the given [`MediaKind`] and
+    /// [`MediaSourceKind`] are constrained to in this [`MediaStreamSettings`],
+    /// regardless of whether it's an exact or an ideal constraint.
+    #[must_use]
+    pub fn device_id(
+        &self,
+        kind: MediaKind,
+        source: MediaSourceKind,
+    ) -> Option<String> {
+        self.device_id_constraint(kind, source).map(|id| match id {
+            ConstrainString::Exact(id) | ConstrainString::Ideal(id) => {
+                id.clone()
+            }
+        })
+    }
+
+    /// Returns [deviceId][1] constraint of the given [`MediaKind`] and
+    /// [`MediaSourceKind`] in this [`MediaStreamSettings`], if any.
+    ///
+    /// [1]: https://w3.org/TR/mediacapture-streams#def-constraint-deviceId
+    #[must_use]
+    pub fn device_id_constraint(
+        &self,
+        kind: MediaKind,
+        source: MediaSourceKind,
+    ) -> Option<&ConstrainString<String>> {
+        match (kind, source) {
+            (MediaKind::Audio, _) => self.audio.constraints.device_id.as_ref(),
+            (MediaKind::Video, MediaSourceKind::Device) => self
+                .device_video
+                .constraints
+                .as_ref()
+                .and_then(|c| c.device_id.as_ref()),
+            (MediaKind::Video, MediaSourceKind::Display) => self
+                .display_video
+                .constraints
+                .as_ref()
+                .and_then(|c| c.device_id.as_ref()),
+        }
+    }
+
+    /// Returns [`platform::MediaStreamConstraints`] requesting only the device
+    /// media of the given [`MediaKind`] with these [`MediaStreamSettings`],
+    /// having its [deviceId][1] constraint made an exact one.
+    ///
+    /// Returns [`None`] if no device is requested for the given [`MediaKind`].
+    ///
+    /// [1]: https://w3.org/TR/mediacapture-streams#def-constraint-deviceId
+    #[must_use]
+    pub fn exact_device_constraints(
+        &self,
+        kind: MediaKind,
+    ) -> Option<platform::MediaStreamConstraints> {
+        let device_id = self.device_id(kind, MediaSourceKind::Device)?;
+        let mut caps = platform::MediaStreamConstraints::new();
+        match kind {
+            MediaKind::Audio => {
+                let mut audio = self.audio.constraints.clone();
+                audio.custom_track = None;
+                audio.device_id(device_id);
+                caps.audio(audio);
+            }
+            MediaKind::Video => {
+                let mut video = self.device_video.constraints.clone()?;
+                video.custom_track = None;
+                video.device_id(device_id);
+                caps.video(video);
+            }
+        }
+        Some(caps)
+    }
+
     /// Constrains this [`MediaStreamSettings`] with the given `other`
     /// [`MediaStreamSettings`].
     fn constrain(&mut self, other: Self) {
@@ -741,6 +1245,88 @@ impl MediaStreamSettings {
     }
 }
 
+impl From<MediaStreamSettingsPreset> for MediaStreamSettings {
+    fn from(preset: MediaStreamSettingsPreset) -> Self {
+        match preset {
+            MediaStreamSettingsPreset::AudioOnly => Self::audio_only(),
+            MediaStreamSettingsPreset::Sd => Self::sd(),
+            MediaStreamSettingsPreset::Hd => Self::hd(),
+            MediaStreamSettingsPreset::ScreenShareWithAudio => {
+                Self::screen_share_with_audio()
+            }
+        }
+    }
+}
+
+impl FromStr for MediaStreamSettings {
+    type Err = Traced<MediaStreamSettingsParseError>;
+
+    /// Parses [`MediaStreamSettings`] from a JSON string, optionally naming
+    /// a [`MediaStreamSettingsPreset`] to build them from (for example,
+    /// `{"preset": "hd"}`).
+    fn from_str(json: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str::<MediaStreamSettingsJson>(json)
+            .map(|json| json.preset.map_or_else(Self::new, Self::from))
+            .map_err(|e| {
+                tracerr::new!(MediaStreamSettingsParseError(e.to_string()))
+            })
+    }
+}
+
+/// Reason of an [`InvalidConstraint`].
+#[derive(Clone, Copy, Debug, Display, Eq, PartialEq)]
+pub enum InvalidConstraintReason {
+    /// Value is required to be `0`, which no media source can satisfy.
+    #[display(fmt = "cannot be 0")]
+    Zero,
+
+    /// Minimum of a range is greater than its maximum.
+    #[display(fmt = "has minimum {} greater than maximum {}", min, max)]
+    InvertedRange {
+        /// Minimum of the range.
+        min: u32,
+
+        /// Maximum of the range.
+        max: u32,
+    },
+
+    /// String value is empty.
+    #[display(fmt = "cannot be empty")]
+    Empty,
+}
+
+/// Constraint of [`MediaStreamSettings`] which can never be satisfied.
+#[derive(Clone, Copy, Debug, Display, Eq, PartialEq)]
+#[display(fmt = "`{}` {}", field, reason)]
+pub struct InvalidConstraint {
+    /// Path of the invalid field (e.g. `device_video.width`).
+    pub field: &'static str,
+
+    /// [`InvalidConstraintReason`] of the field being invalid.
+    pub reason: InvalidConstraintReason,
+}
+
+/// Error returned from the [`MediaStreamSettings::validate()`] method.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvalidConstraintsError {
+    /// All the [`InvalidConstraint`]s of the validated
+    /// [`MediaStreamSettings`].
+    pub constraints: Vec<InvalidConstraint>,
+}
+
+impl fmt::Display for InvalidConstraintsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid media constraints: ")?;
+        for (i, constraint) in self.constraints.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{constraint}")?;
+        }
+        Ok(())
+    }
+}
+
 /// Wrapper around [MediaStreamConstraints][1] that specifies concrete media
 /// source (device or display), and allows to group two requests with different
 /// sources.
@@ -873,7 +1459,9 @@ impl From<VideoSettings> for VideoSource {
                     facing_mode: None,
                     width: None,
                     height: None,
+                    frame_rate: None,
                     required: settings.required,
+                    custom_track: None,
                 })
             }
             MediaSourceKind::Display => {
@@ -883,6 +1471,8 @@ impl From<VideoSettings> for VideoSource {
                     frame_rate: None,
                     required: settings.required,
                     device_id: None,
+                    content_hint: None,
+                    capture_options: DisplayCaptureOptions::default(),
                 })
             }
         }
@@ -973,6 +1563,27 @@ pub struct AudioTrackConstraints {
     /// If `true` then without this [`AudioTrackConstraints`] call session
     /// can't be started.
     pub required: bool,
+
+    /// [`ContentHint`] applied to the captured audio track.
+    pub content_hint: Option<ContentHint>,
+
+    /// Indicator whether [automatic gain control][1] is desired.
+    ///
+    /// [1]: https://w3.org/TR/mediacapture-streams#dfn-autogaincontrol
+    pub auto_gain_control: Option<bool>,
+
+    /// Indicator whether [echo cancellation][1] is desired.
+    ///
+    /// [1]: https://w3.org/TR/mediacapture-streams#dfn-echocancellation
+    pub echo_cancellation: Option<bool>,
+
+    /// Indicator whether [noise suppression][1] is desired.
+    ///
+    /// [1]: https://w3.org/TR/mediacapture-streams#dfn-noisesuppression
+    pub noise_suppression: Option<bool>,
+
+    /// [`CustomTrack`] to be published instead of a captured one.
+    custom_track: Option<CustomTrack>,
 }
 
 impl AudioTrackConstraints {
@@ -989,6 +1600,32 @@ impl AudioTrackConstraints {
         self.device_id = Some(ConstrainString::Exact(device_id));
     }
 
+    /// Sets a [`ContentHint`] to be applied to the captured audio track.
+    pub fn content_hint(&mut self, hint: ContentHint) {
+        self.content_hint = Some(hint);
+    }
+
+    /// Sets an [autoGainControl][1] constraint.
+    ///
+    /// [1]: https://w3.org/TR/mediacapture-streams#dfn-autogaincontrol
+    pub fn auto_gain_control(&mut self, enabled: bool) {
+        self.auto_gain_control = Some(enabled);
+    }
+
+    /// Sets an [echoCancellation][1] constraint.
+    ///
+    /// [1]: https://w3.org/TR/mediacapture-streams#dfn-echocancellation
+    pub fn echo_cancellation(&mut self, enabled: bool) {
+        self.echo_cancellation = Some(enabled);
+    }
+
+    /// Sets a [noiseSuppression][1] constraint.
+    ///
+    /// [1]: https://w3.org/TR/mediacapture-streams#dfn-noisesuppression
+    pub fn noise_suppression(&mut self, enabled: bool) {
+        self.noise_suppression = Some(enabled);
+    }
+
     /// Checks whether the provided [`platform::MediaStreamTrack`] satisfies
     /// contained constraints.
     pub async fn satisfies<T: AsRef<platform::MediaStreamTrack>>(
@@ -1004,6 +1641,53 @@ impl AudioTrackConstraints {
         // TODO returns Result<bool, Error>
     }
 
+    /// Checks whether the provided [`local::Track`] satisfies these
+    /// [`AudioTrackConstraints`], either with its actual settings, or with the
+    /// constraints it was captured with being equal or stricter ones.
+    ///
+    /// Allows reusing [`local::Track`]s whose settings are not reported by
+    /// the platform.
+    ///
+    /// If a custom track is set via the
+    /// [`MediaStreamSettings::set_custom_audio_track()`], then only it or its
+    /// forks satisfy these [`AudioTrackConstraints`].
+    pub async fn is_satisfied_by(&self, track: &local::Track) -> bool {
+        if let Some(custom) = &self.custom_track {
+            return custom.is_satisfied_by(track, MediaKind::Audio).await;
+        }
+        if self.satisfies(track).await {
+            return true;
+        }
+        let is_covered = matches!(
+            track.capture_constraints(),
+            Some(TrackConstraints::Audio(captured))
+                if self.is_covered_by(&captured)
+        );
+        is_covered && satisfies_track(track.as_ref(), MediaKind::Audio).await
+    }
+
+    /// Indicates whether these [`AudioTrackConstraints`] are equal to or looser
+    /// than the `captured` ones.
+    #[must_use]
+    pub fn is_covered_by(&self, captured: &Self) -> bool {
+        let flag_is_covered = |this: Option<bool>, captured: Option<bool>| {
+            this.is_none() || this == captured
+        };
+        ConstrainString::is_covered_by(&self.device_id, &captured.device_id)
+            && flag_is_covered(
+                self.auto_gain_control,
+                captured.auto_gain_control,
+            )
+            && flag_is_covered(
+                self.echo_cancellation,
+                captured.echo_cancellation,
+            )
+            && flag_is_covered(
+                self.noise_suppression,
+                captured.noise_suppression,
+            )
+    }
+
     /// Merges these [`AudioTrackConstraints`] with `another` ones, meaning that
     /// if some constraints are not set on these ones, then they will be applied
     /// from `another`.
@@ -1014,6 +1698,27 @@ impl AudioTrackConstraints {
         if !self.required && another.required {
             self.required = another.required;
         }
+        if self.content_hint.is_none() && another.content_hint.is_some() {
+            self.content_hint = another.content_hint;
+        }
+        if self.auto_gain_control.is_none()
+            && another.auto_gain_control.is_some()
+        {
+            self.auto_gain_control = another.auto_gain_control;
+        }
+        if self.echo_cancellation.is_none()
+            && another.echo_cancellation.is_some()
+        {
+            self.echo_cancellation = another.echo_cancellation;
+        }
+        if self.noise_suppression.is_none()
+            && another.noise_suppression.is_some()
+        {
+            self.noise_suppression = another.noise_suppression;
+        }
+        if self.custom_track.is_none() && another.custom_track.is_some() {
+            self.custom_track = another.custom_track;
+        }
     }
 
     /// Returns an importance of these [`AudioTrackConstraints`].
@@ -1031,6 +1736,11 @@ impl From<ProtoAudioConstraints> for AudioTrackConstraints {
         Self {
             required: caps.required,
             device_id: None,
+            content_hint: None,
+            auto_gain_control: None,
+            echo_cancellation: None,
+            noise_suppression: None,
+            custom_track: None,
         }
     }
 }
@@ -1079,6 +1789,41 @@ impl ConstrainU32 {
             }
         }
     }
+
+    /// Checks whether `this` [`ConstrainU32`] is equal to or looser than the
+    /// `captured` one, meaning that any value satisfying the `captured`
+    /// [`ConstrainU32`] satisfies `this` one as well.
+    fn is_covered_by(this: Option<Self>, captured: Option<Self>) -> bool {
+        match (this, captured) {
+            (None | Some(Self::Ideal(_)), _) => true,
+            (Some(Self::Exact(exact)), Some(Self::Exact(val))) => val == exact,
+            (Some(Self::Range(start, end)), Some(Self::Exact(val))) => {
+                val >= start && val <= end
+            }
+            (Some(Self::Range(start, end)), Some(Self::Range(from, to))) => {
+                from >= start && to <= end
+            }
+            (
+                Some(Self::Exact(_) | Self::Range(_, _)),
+                None | Some(Self::Ideal(_) | Self::Range(_, _)),
+            ) => false,
+        }
+    }
+
+    /// Returns an [`InvalidConstraintReason`] if `this` [`ConstrainU32`] can
+    /// never be satisfied.
+    const fn validate(this: Option<Self>) -> Option<InvalidConstraintReason> {
+        match this {
+            Some(Self::Exact(0) | Self::Range(0, 0)) => {
+                Some(InvalidConstraintReason::Zero)
+            }
+            Some(Self::Range(min, max)) if min > max => {
+                Some(InvalidConstraintReason::InvertedRange { min, max })
+            }
+            None
+            | Some(Self::Exact(_) | Self::Ideal(_) | Self::Range(_, _)) => None,
+        }
+    }
 }
 
 /// Representation of the [ConstrainDOMString][1].
@@ -1107,6 +1852,32 @@ impl<T: AsRef<str>> ConstrainString<T> {
                 .map_or(false, |val| val.as_ref() == constrain.as_ref()),
         }
     }
+
+    /// Checks whether `this` [`ConstrainString`] is equal to or looser than
+    /// the `captured` one, meaning that any value satisfying the `captured`
+    /// [`ConstrainString`] satisfies `this` one as well.
+    fn is_covered_by(this: &Option<Self>, captured: &Option<Self>) -> bool {
+        match (this, captured) {
+            (None | Some(Self::Ideal(_)), _) => true,
+            (Some(Self::Exact(constrain)), Some(Self::Exact(val))) => {
+                val.as_ref() == constrain.as_ref()
+            }
+            (Some(Self::Exact(_)), None | Some(Self::Ideal(_))) => false,
+        }
+    }
+
+    /// Returns an [`InvalidConstraintReason`] if `this` [`ConstrainString`] can
+    /// never be satisfied.
+    fn validate(this: &Option<Self>) -> Option<InvalidConstraintReason> {
+        match this {
+            Some(Self::Exact(val) | Self::Ideal(val))
+                if val.as_ref().is_empty() =>
+            {
+                Some(InvalidConstraintReason::Empty)
+            }
+            None | Some(Self::Exact(_) | Self::Ideal(_)) => None,
+        }
+    }
 }
 
 /// Constraints applicable to video tracks that are sourced from some media
@@ -1131,6 +1902,14 @@ pub struct DeviceVideoTrackConstraints {
 
     /// Width of the video in pixels.
     pub width: Option<ConstrainU32>,
+
+    /// [Frame rate][1] of the video.
+    ///
+    /// [1]: https://w3.org/TR/mediacapture-streams#dfn-framerate
+    pub frame_rate: Option<ConstrainU32>,
+
+    /// [`CustomTrack`] to be published instead of a captured one.
+    custom_track: Option<CustomTrack>,
 }
 
 /// Constraints applicable to video tracks that are sourced from screen-capture.
@@ -1205,6 +1984,20 @@ impl DeviceVideoTrackConstraints {
         self.width = Some(ConstrainU32::Range(min, max));
     }
 
+    /// Sets exact [frameRate][1] constraint.
+    ///
+    /// [1]: https://w3.org/TR/mediacapture-streams#dfn-framerate
+    pub fn exact_frame_rate(&mut self, frame_rate: u32) {
+        self.frame_rate = Some(ConstrainU32::Exact(frame_rate));
+    }
+
+    /// Sets ideal [frameRate][1] constraint.
+    ///
+    /// [1]: https://w3.org/TR/mediacapture-streams#dfn-framerate
+    pub fn ideal_frame_rate(&mut self, frame_rate: u32) {
+        self.frame_rate = Some(ConstrainU32::Ideal(frame_rate));
+    }
+
     /// Checks whether the provided [`platform::MediaStreamTrack`] satisfies
     /// contained [`DeviceVideoTrackConstraints`].
     pub async fn satisfies<T: AsRef<platform::MediaStreamTrack>>(
@@ -1226,6 +2019,45 @@ impl DeviceVideoTrackConstraints {
             && !track.guess_is_from_display()
     }
 
+    /// Checks whether the provided [`local::Track`] satisfies these
+    /// [`DeviceVideoTrackConstraints`], either with its actual settings, or
+    /// with the constraints it was captured with being equal or stricter ones.
+    ///
+    /// Allows reusing [`local::Track`]s whose settings are not reported by
+    /// the platform.
+    ///
+    /// If a custom track is set via the
+    /// [`MediaStreamSettings::set_custom_video_track()`], then only it or its
+    /// forks satisfy these [`DeviceVideoTrackConstraints`].
+    pub async fn is_satisfied_by(&self, track: &local::Track) -> bool {
+        if let Some(custom) = &self.custom_track {
+            return custom.is_satisfied_by(track, MediaKind::Video).await;
+        }
+        if self.satisfies(track).await {
+            return true;
+        }
+        let is_covered = matches!(
+            track.capture_constraints(),
+            Some(TrackConstraints::Video(VideoSource::Device(captured)))
+                if self.is_covered_by(&captured)
+        );
+        is_covered && satisfies_track(track.as_ref(), MediaKind::Video).await
+    }
+
+    /// Indicates whether these [`DeviceVideoTrackConstraints`] are equal to or
+    /// looser than the `captured` ones.
+    #[must_use]
+    pub fn is_covered_by(&self, captured: &Self) -> bool {
+        ConstrainString::is_covered_by(&self.device_id, &captured.device_id)
+            && ConstrainString::is_covered_by(
+                &self.facing_mode,
+                &captured.facing_mode,
+            )
+            && ConstrainU32::is_covered_by(self.height, captured.height)
+            && ConstrainU32::is_covered_by(self.width, captured.width)
+            && ConstrainU32::is_covered_by(self.frame_rate, captured.frame_rate)
+    }
+
     /// Merges these [`DeviceVideoTrackConstraints`] with `another` ones,
     /// meaning that if some constraints are not set on these ones, then they
     /// will be applied from `another`.
@@ -1245,6 +2077,12 @@ impl DeviceVideoTrackConstraints {
         if self.width.is_none() && another.width.is_some() {
             self.width = another.width;
         }
+        if self.frame_rate.is_none() && another.frame_rate.is_some() {
+            self.frame_rate = another.frame_rate;
+        }
+        if self.custom_track.is_none() && another.custom_track.is_some() {
+            self.custom_track = another.custom_track;
+        }
     }
 
     /// Returns an importance of these [`DeviceVideoTrackConstraints`].
@@ -1257,6 +2095,82 @@ impl DeviceVideoTrackConstraints {
     }
 }
 
+/// Indicator whether some kind of capture is included into or excluded from
+/// a [getDisplayMedia()][1] request.
+///
+/// [1]: https://w3.org/TR/screen-capture/#dom-mediadevices-getdisplaymedia
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CaptureInclusion {
+    /// Capture is offered to a user.
+    Include,
+
+    /// Capture is not offered to a user.
+    Exclude,
+}
+
+impl CaptureInclusion {
+    /// Returns a value of this [`CaptureInclusion`] in a
+    /// [getDisplayMedia()][1] request.
+    ///
+    /// [1]: https://w3.org/TR/screen-capture/#dom-mediadevices-getdisplaymedia
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Include => "include",
+            Self::Exclude => "exclude",
+        }
+    }
+}
+
+/// Options of a [getDisplayMedia()][1] request, shaping the screen picker
+/// shown to a user, rather than the captured video itself.
+///
+/// Platforms not supporting some of the options ignore them.
+///
+/// [1]: https://w3.org/TR/screen-capture/#dom-mediadevices-getdisplaymedia
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct DisplayCaptureOptions {
+    /// Indicator whether the current browser tab should be offered as the
+    /// most prominent capture option.
+    pub prefer_current_tab: Option<bool>,
+
+    /// Indicator whether the current browser tab should be offered to a user
+    /// at all.
+    pub self_browser_surface: Option<CaptureInclusion>,
+
+    /// Indicator whether a user should be allowed to dynamically switch the
+    /// shared browser tab.
+    pub surface_switching: Option<CaptureInclusion>,
+
+    /// Indicator whether a system audio should be offered to a user along
+    /// with a captured screen.
+    pub system_audio: Option<CaptureInclusion>,
+}
+
+impl DisplayCaptureOptions {
+    /// Indicates whether none of these [`DisplayCaptureOptions`] is set.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.prefer_current_tab.is_none()
+            && self.self_browser_surface.is_none()
+            && self.surface_switching.is_none()
+            && self.system_audio.is_none()
+    }
+
+    /// Merges these [`DisplayCaptureOptions`] with `another` ones, meaning
+    /// that if some options are not set on these ones, then they will be
+    /// applied from `another`.
+    pub fn merge(&mut self, another: Self) {
+        self.prefer_current_tab =
+            self.prefer_current_tab.or(another.prefer_current_tab);
+        self.self_browser_surface =
+            self.self_browser_surface.or(another.self_browser_surface);
+        self.surface_switching =
+            self.surface_switching.or(another.surface_switching);
+        self.system_audio = self.system_audio.or(another.system_audio);
+    }
+}
+
 /// Constraints applicable to video tracks sourced from a screen capturing.
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct DisplayVideoTrackConstraints {
@@ -1283,6 +2197,16 @@ pub struct DisplayVideoTrackConstraints {
     ///
     /// [1]: https://w3.org/TR/mediacapture-streams#dfn-framerate
     pub frame_rate: Option<ConstrainU32>,
+
+    /// [`ContentHint`] applied to the captured video track.
+    ///
+    /// [`ContentHint::Detail`] is applied if [`None`].
+    pub content_hint: Option<ContentHint>,
+
+    /// [`DisplayCaptureOptions`] of the [getDisplayMedia()][1] request.
+    ///
+    /// [1]: https://w3.org/TR/screen-capture/#dom-mediadevices-getdisplaymedia
+    pub capture_options: DisplayCaptureOptions,
 }
 
 impl DisplayVideoTrackConstraints {
@@ -1293,6 +2217,41 @@ impl DisplayVideoTrackConstraints {
         Self::default()
     }
 
+    /// Sets a [`ContentHint`] to be applied to the captured video track.
+    pub fn content_hint(&mut self, hint: ContentHint) {
+        self.content_hint = Some(hint);
+    }
+
+    /// Sets the `preferCurrentTab` option, offering the current browser tab
+    /// as the most prominent capture option.
+    pub fn prefer_current_tab(&mut self, prefer: bool) {
+        self.capture_options.prefer_current_tab = Some(prefer);
+    }
+
+    /// Sets the [selfBrowserSurface][1] option, including or excluding the
+    /// current browser tab from the offered capture options.
+    ///
+    /// [1]: https://w3.org/TR/screen-capture/#dom-displaymediastreamoptions
+    pub fn self_browser_surface(&mut self, inclusion: CaptureInclusion) {
+        self.capture_options.self_browser_surface = Some(inclusion);
+    }
+
+    /// Sets the [surfaceSwitching][1] option, allowing or disallowing a user
+    /// to dynamically switch the shared browser tab.
+    ///
+    /// [1]: https://w3.org/TR/screen-capture/#dom-displaymediastreamoptions
+    pub fn surface_switching(&mut self, inclusion: CaptureInclusion) {
+        self.capture_options.surface_switching = Some(inclusion);
+    }
+
+    /// Sets the [systemAudio][1] option, offering or not offering a system
+    /// audio to a user along with a captured screen.
+    ///
+    /// [1]: https://w3.org/TR/screen-capture/#dom-displaymediastreamoptions
+    pub fn system_audio(&mut self, inclusion: CaptureInclusion) {
+        self.capture_options.system_audio = Some(inclusion);
+    }
+
     /// Checks whether the provided [`platform::MediaStreamTrack`] satisfies
     /// contained [`DisplayVideoTrackConstraints`].
     pub async fn satisfies<T: AsRef<platform::MediaStreamTrack>>(
@@ -1310,6 +2269,34 @@ impl DisplayVideoTrackConstraints {
             && track.guess_is_from_display()
     }
 
+    /// Checks whether the provided [`local::Track`] satisfies these
+    /// [`DisplayVideoTrackConstraints`], either with its actual settings, or
+    /// with the constraints it was captured with being equal or stricter ones.
+    ///
+    /// Allows reusing [`local::Track`]s whose settings are not reported by
+    /// the platform.
+    pub async fn is_satisfied_by(&self, track: &local::Track) -> bool {
+        if self.satisfies(track).await {
+            return true;
+        }
+        let is_covered = matches!(
+            track.capture_constraints(),
+            Some(TrackConstraints::Video(VideoSource::Display(captured)))
+                if self.is_covered_by(&captured)
+        );
+        is_covered && satisfies_track(track.as_ref(), MediaKind::Video).await
+    }
+
+    /// Indicates whether these [`DisplayVideoTrackConstraints`] are equal to
+    /// or looser than the `captured` ones.
+    #[must_use]
+    pub fn is_covered_by(&self, captured: &Self) -> bool {
+        ConstrainString::is_covered_by(&self.device_id, &captured.device_id)
+            && ConstrainU32::is_covered_by(self.height, captured.height)
+            && ConstrainU32::is_covered_by(self.width, captured.width)
+            && ConstrainU32::is_covered_by(self.frame_rate, captured.frame_rate)
+    }
+
     /// Merges these [`DisplayVideoTrackConstraints`] with `another` ones,
     /// meaning that if some constraints are not set on these ones, then they
     /// will be applied from `another`.
@@ -1329,6 +2316,10 @@ impl DisplayVideoTrackConstraints {
         if self.frame_rate.is_none() && another.frame_rate.is_some() {
             self.frame_rate = another.frame_rate;
         }
+        if self.content_hint.is_none() && another.content_hint.is_some() {
+            self.content_hint = another.content_hint;
+        }
+        self.capture_options.merge(another.capture_options);
     }
 
     /// Sets an exact [height][1] constraint.
@@ -1389,3 +2380,355 @@ impl DisplayVideoTrackConstraints {
         self.required
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        AudioTrackConstraints, CaptureInclusion, ConstrainU32,
+        DeviceVideoTrackConstraints, DisplayCaptureOptions,
+        DisplayVideoTrackConstraints, InvalidConstraint,
+        InvalidConstraintReason, LocalStreamUpdateCriteria, MediaKind,
+        MediaSourceKind, MediaStreamSettings, MediaType, RecvConstraints,
+        VideoSettings,
+    };
+
+    /// Returns fields of the [`InvalidConstraint`]s of the provided
+    /// [`MediaStreamSettings`].
+    fn invalid_fields(
+        settings: &MediaStreamSettings,
+    ) -> Vec<(&'static str, InvalidConstraintReason)> {
+        settings.validate().map_or_else(
+            |e| {
+                e.constraints
+                    .into_iter()
+                    .map(|InvalidConstraint { field, reason }| (field, reason))
+                    .collect()
+            },
+            |()| Vec::new(),
+        )
+    }
+
+    #[test]
+    fn accepts_empty_settings() {
+        let mut settings = MediaStreamSettings::new();
+        settings.audio(AudioTrackConstraints::new());
+        settings.device_video(DeviceVideoTrackConstraints::new());
+        settings.display_video(DisplayVideoTrackConstraints::new());
+
+        assert_eq!(settings.validate(), Ok(()));
+    }
+
+    #[test]
+    fn accepts_legal_extremes() {
+        let mut device = DeviceVideoTrackConstraints::new();
+        device.exact_width(7680);
+        device.exact_height(4320);
+        device.device_id("camera".into());
+        let mut display = DisplayVideoTrackConstraints::new();
+        display.ideal_width(u32::MAX);
+        display.ideal_height(0);
+        display.exact_frame_rate(240);
+        let mut settings = MediaStreamSettings::new();
+        settings.device_video(device);
+        settings.display_video(display);
+
+        assert_eq!(settings.validate(), Ok(()));
+
+        let mut device = DeviceVideoTrackConstraints::new();
+        device.width_in_range(0, 7680);
+        device.height_in_range(4320, 4320);
+        let mut settings = MediaStreamSettings::new();
+        settings.device_video(device);
+
+        assert_eq!(settings.validate(), Ok(()));
+    }
+
+    #[test]
+    fn rejects_zero_values() {
+        let mut device = DeviceVideoTrackConstraints::new();
+        device.exact_width(0);
+        device.height_in_range(0, 0);
+        let mut display = DisplayVideoTrackConstraints::new();
+        display.exact_height(0);
+        display.exact_frame_rate(0);
+        let mut settings = MediaStreamSettings::new();
+        settings.device_video(device);
+        settings.display_video(display);
+
+        assert_eq!(
+            invalid_fields(&settings),
+            [
+                ("device_video.width", InvalidConstraintReason::Zero),
+                ("device_video.height", InvalidConstraintReason::Zero),
+                ("display_video.height", InvalidConstraintReason::Zero),
+                ("display_video.frame_rate", InvalidConstraintReason::Zero),
+            ],
+        );
+    }
+
+    #[test]
+    fn rejects_inverted_ranges() {
+        let mut device = DeviceVideoTrackConstraints::new();
+        device.width_in_range(1920, 1280);
+        device.height_in_range(720, 1080);
+        let mut settings = MediaStreamSettings::new();
+        settings.device_video(device);
+
+        assert_eq!(
+            invalid_fields(&settings),
+            [(
+                "device_video.width",
+                InvalidConstraintReason::InvertedRange {
+                    min: 1920,
+                    max: 1280,
+                },
+            )],
+        );
+    }
+
+    #[test]
+    fn rejects_empty_device_ids() {
+        let mut audio = AudioTrackConstraints::new();
+        audio.device_id(String::new());
+        let mut device = DeviceVideoTrackConstraints::new();
+        device.device_id(String::new());
+        let mut display = DisplayVideoTrackConstraints::new();
+        display.device_id(String::new());
+        let mut settings = MediaStreamSettings::new();
+        settings.audio(audio);
+        settings.device_video(device);
+        settings.display_video(display);
+
+        assert_eq!(
+            invalid_fields(&settings),
+            [
+                ("audio.device_id", InvalidConstraintReason::Empty),
+                ("device_video.device_id", InvalidConstraintReason::Empty),
+                ("display_video.device_id", InvalidConstraintReason::Empty),
+            ],
+        );
+    }
+
+    #[test]
+    fn lists_all_invalid_fields_in_message() {
+        let mut audio = AudioTrackConstraints::new();
+        audio.device_id(String::new());
+        let mut device = DeviceVideoTrackConstraints::new();
+        device.exact_width(0);
+        device.height_in_range(1080, 720);
+        let mut settings = MediaStreamSettings::new();
+        settings.audio(audio);
+        settings.device_video(device);
+
+        assert_eq!(
+            settings.validate().unwrap_err().to_string(),
+            "Invalid media constraints: `audio.device_id` cannot be empty, \
+             `device_video.width` cannot be 0, `device_video.height` has \
+             minimum 1080 greater than maximum 720",
+        );
+    }
+
+    #[test]
+    fn audio_only_preset() {
+        let settings = MediaStreamSettings::audio_only();
+
+        assert!(settings.is_audio_enabled());
+        let audio = settings.get_audio();
+        assert_eq!(audio.auto_gain_control, Some(true));
+        assert_eq!(audio.echo_cancellation, Some(true));
+        assert_eq!(audio.noise_suppression, Some(true));
+        assert_eq!(audio.device_id, None);
+        assert!(!settings.is_device_video_enabled());
+        assert!(!settings.is_display_video_enabled());
+        assert_eq!(settings.validate(), Ok(()));
+    }
+
+    #[test]
+    fn camera_presets() {
+        for (settings, width, height) in [
+            (MediaStreamSettings::sd(), 640, 480),
+            (MediaStreamSettings::hd(), 1280, 720),
+        ] {
+            assert_eq!(
+                settings.get_audio(),
+                MediaStreamSettings::audio_only().get_audio(),
+            );
+            let device = settings.get_device_video().unwrap();
+            assert_eq!(device.width, Some(ConstrainU32::Ideal(width)));
+            assert_eq!(device.height, Some(ConstrainU32::Ideal(height)));
+            assert_eq!(device.frame_rate, Some(ConstrainU32::Ideal(30)));
+            assert!(!settings.is_display_video_enabled());
+            assert_eq!(settings.validate(), Ok(()));
+        }
+    }
+
+    #[test]
+    fn screen_share_with_audio_preset() {
+        let settings = MediaStreamSettings::screen_share_with_audio();
+
+        assert_eq!(
+            settings.get_audio(),
+            MediaStreamSettings::audio_only().get_audio(),
+        );
+        let display = settings.get_display_video().unwrap();
+        assert_eq!(display.width, Some(ConstrainU32::Ideal(1920)));
+        assert_eq!(display.height, Some(ConstrainU32::Ideal(1080)));
+        assert_eq!(display.frame_rate, Some(ConstrainU32::Ideal(15)));
+        assert!(!settings.is_device_video_enabled());
+        assert_eq!(settings.validate(), Ok(()));
+    }
+
+    #[test]
+    fn presets_can_be_tweaked() {
+        let mut settings = MediaStreamSettings::hd();
+        let mut device = DeviceVideoTrackConstraints::new();
+        device.device_id("camera".into());
+        device.exact_frame_rate(60);
+        settings.device_video(device);
+
+        let device = settings.get_device_video().unwrap();
+        assert_eq!(device.width, None);
+        assert_eq!(device.frame_rate, Some(ConstrainU32::Exact(60)));
+        assert_eq!(
+            settings.get_audio(),
+            MediaStreamSettings::audio_only().get_audio(),
+        );
+    }
+
+    #[test]
+    fn parses_presets_from_json() {
+        for (json, expected) in [
+            (
+                r#"{"preset": "audio_only"}"#,
+                MediaStreamSettings::audio_only(),
+            ),
+            (r#"{"preset": "sd"}"#, MediaStreamSettings::sd()),
+            (r#"{"preset": "hd"}"#, MediaStreamSettings::hd()),
+            (
+                r#"{"preset": "screen_share_with_audio"}"#,
+                MediaStreamSettings::screen_share_with_audio(),
+            ),
+            ("{}", MediaStreamSettings::new()),
+        ] {
+            assert_eq!(json.parse::<MediaStreamSettings>().unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_json() {
+        for json in [r#"{"preset": "4k"}"#, r#"{"audio": true}"#, "hd"] {
+            assert!(json.parse::<MediaStreamSettings>().is_err());
+        }
+    }
+
+    #[test]
+    fn merges_display_capture_options() {
+        let mut display = DisplayVideoTrackConstraints::new();
+        display.prefer_current_tab(true);
+        display.surface_switching(CaptureInclusion::Exclude);
+
+        let mut defaults = DisplayVideoTrackConstraints::new();
+        defaults.prefer_current_tab(false);
+        defaults.self_browser_surface(CaptureInclusion::Include);
+        display.merge(defaults);
+
+        assert_eq!(
+            display.capture_options,
+            DisplayCaptureOptions {
+                prefer_current_tab: Some(true),
+                self_browser_surface: Some(CaptureInclusion::Include),
+                surface_switching: Some(CaptureInclusion::Exclude),
+                system_audio: None,
+            },
+        );
+        assert!(!display.capture_options.is_empty());
+        assert!(DisplayCaptureOptions::default().is_empty());
+    }
+
+    #[test]
+    fn recv_constraints_respect_source_kind() {
+        let video = |source_kind| {
+            MediaType::Video(VideoSettings {
+                required: false,
+                source_kind,
+            })
+        };
+        let cons = RecvConstraints::default();
+        cons.set_enabled(
+            false,
+            MediaKind::Video,
+            Some(MediaSourceKind::Display),
+        );
+
+        assert!(cons.is_enabled_for(&video(MediaSourceKind::Device)));
+        assert!(!cons.is_enabled_for(&video(MediaSourceKind::Display)));
+
+        cons.set_enabled(false, MediaKind::Video, None);
+        assert!(!cons.is_enabled_for(&video(MediaSourceKind::Device)));
+    }
+
+    /// Returns [`MediaStreamSettings`] requesting the camera with the provided
+    /// device ID, if any.
+    fn camera_settings(device_id: Option<&str>) -> MediaStreamSettings {
+        let mut video = DeviceVideoTrackConstraints::new();
+        if let Some(id) = device_id {
+            video.device_id(id.into());
+        }
+        let mut settings = MediaStreamSettings::new();
+        settings.device_video(video);
+        settings
+    }
+
+    /// Returns a captured camera track description of the provided device ID.
+    fn camera(device_id: &str) -> (MediaKind, MediaSourceKind, String) {
+        (MediaKind::Video, MediaSourceKind::Device, device_id.into())
+    }
+
+    #[test]
+    fn stops_first_when_recapturing_same_device() {
+        let kinds = LocalStreamUpdateCriteria::all();
+        let settings = camera_settings(Some("cam-a"));
+
+        let stop_first = settings.stop_first_kinds(kinds, &[camera("cam-a")]);
+
+        assert!(stop_first.has(MediaKind::Video, MediaSourceKind::Device));
+    }
+
+    #[test]
+    fn doesnt_stop_first_when_switching_device() {
+        let kinds = LocalStreamUpdateCriteria::all();
+        let settings = camera_settings(Some("cam-b"));
+
+        let stop_first = settings.stop_first_kinds(kinds, &[camera("cam-a")]);
+
+        assert_eq!(stop_first, LocalStreamUpdateCriteria::empty());
+    }
+
+    #[test]
+    fn stops_first_when_device_is_unknown() {
+        let kinds = LocalStreamUpdateCriteria::all();
+
+        let stop_first =
+            camera_settings(None).stop_first_kinds(kinds, &[camera("cam-a")]);
+        assert!(stop_first.has(MediaKind::Video, MediaSourceKind::Device));
+
+        let stop_first = camera_settings(Some("cam-b")).stop_first_kinds(
+            kinds,
+            &[(MediaKind::Video, MediaSourceKind::Display, "screen".into())],
+        );
+        assert!(stop_first.has(MediaKind::Video, MediaSourceKind::Display));
+    }
+
+    #[test]
+    fn stops_first_only_provided_kinds() {
+        let kinds = LocalStreamUpdateCriteria::from_kinds(
+            MediaKind::Audio,
+            Some(MediaSourceKind::Device),
+        );
+        let settings = camera_settings(Some("cam-a"));
+
+        let stop_first = settings.stop_first_kinds(kinds, &[camera("cam-a")]);
+
+        assert_eq!(stop_first, LocalStreamUpdateCriteria::empty());
+    }
+}
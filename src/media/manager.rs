@@ -1,27 +1,36 @@
 //! Acquiring and storing [`local::Track`]s.
 
 use std::{
-    cell::RefCell,
+    cell::{Cell, RefCell},
     collections::HashMap,
+    fmt,
     rc::{Rc, Weak},
+    time::Duration,
 };
 
 use derive_more::{Display, From};
+use futures::future::AbortRegistration;
 use medea_client_api_proto::MediaSourceKind;
 use tracerr::Traced;
 
 use crate::{
     media::{
-        track::MediaStreamTrackState, MediaKind, MediaStreamSettings,
-        MultiSourceTracksConstraints,
+        constraints::ConstrainString, track::MediaStreamTrackState,
+        ContentHint, DeviceCapabilities, DeviceMismatch,
+        InvalidConstraintsError, MediaDeviceKind, MediaKind,
+        MediaStreamSettings, MultiSourceTracksConstraints, TrackConstraints,
+        VideoSource,
     },
     platform,
-    utils::Caused,
+    utils::{self, AbortableError, Caused},
 };
 
 use super::track::local;
 
-/// Errors returned from the [`MediaManagerHandle::enumerate_devices()`] method.
+/// Errors returned from the [`MediaManagerHandle::enumerate_devices()`],
+/// [`MediaManagerHandle::enumerate_devices_quiet()`],
+/// [`MediaManagerHandle::enumerate_devices_cached()`] and
+/// [`MediaManagerHandle::refresh_devices()`] methods.
 #[derive(Caused, Clone, Debug, Display, From)]
 #[cause(error = platform::Error)]
 pub enum EnumerateDevicesError {
@@ -48,6 +57,32 @@ pub enum EnumerateDisplaysError {
     Detached,
 }
 
+/// Errors returned from the [`MediaManagerHandle::device_capabilities()`]
+/// method.
+#[derive(Caused, Clone, Debug, Display, From)]
+#[cause(error = platform::Error)]
+pub enum DeviceCapabilitiesError {
+    /// [`MediaManagerHandle`]'s inner [`Weak`] pointer cannot be upgraded.
+    #[display(fmt = "MediaManagerHandle is in detached state")]
+    Detached,
+
+    /// Occurs if the `enumerateDevices` request fails.
+    #[display(fmt = "MediaDevices.enumerateDevices() failed: {}", _0)]
+    EnumerateDevicesFailed(platform::Error),
+
+    /// Occurs if there is no media input device with the provided ID.
+    #[display(fmt = "No media input device with `{}` ID", _0)]
+    #[from(ignore)]
+    NoSuchDevice(String),
+
+    /// Occurs if the [getUserMedia()][1] request capturing the device to
+    /// query its capabilities fails.
+    ///
+    /// [1]: https://tinyurl.com/w3-streams#dom-mediadevices-getusermedia
+    #[display(fmt = "Failed to query device capabilities: {}", _0)]
+    GetUserMediaFailed(#[cause] GetUserMediaError),
+}
+
 /// Errors returned from the [`MediaManagerHandle::init_local_tracks()`] method.
 #[derive(Caused, Clone, Debug, Display, From)]
 #[cause(error = platform::Error)]
@@ -67,6 +102,27 @@ pub enum InitLocalTracksError {
     /// [1]: https://w3.org/TR/screen-capture#dom-mediadevices-getdisplaymedia
     #[display(fmt = "Failed to get local tracks: {}", _0)]
     GetDisplayMediaFailed(#[cause] GetDisplayMediaError),
+
+    /// Occurs if there are no media input devices of a [`MediaKind`] required
+    /// by the provided [`MediaStreamSettings`].
+    #[display(fmt = "Failed to get local tracks: {}", _0)]
+    NoDevices(NoDevicesError),
+
+    /// Occurs if the provided [`MediaStreamSettings`] contain constraints
+    /// which can never be satisfied, so no media is requested at all.
+    #[display(fmt = "Failed to get local tracks: {}", _0)]
+    InvalidConstraints(InvalidConstraintsError),
+}
+
+/// Error occurring when no media input devices of some [`MediaKind`] are
+/// available, so no [getUserMedia()][1] request is made at all.
+///
+/// [1]: https://tinyurl.com/w3-streams#dom-mediadevices-getusermedia
+#[derive(Clone, Copy, Debug, Display)]
+#[display(fmt = "No {} input devices available", kind)]
+pub struct NoDevicesError {
+    /// [`MediaKind`] of the missing media input devices.
+    pub kind: MediaKind,
 }
 
 /// Error returned from the [`MediaManagerHandle::set_output_audio_id`] method.
@@ -175,24 +231,336 @@ impl From<LocalTrackIsEndedError> for GetDisplayMediaError {
 #[derive(Debug, Default)]
 pub struct MediaManager(Rc<InnerMediaManager>);
 
+/// Policy of retrying [getUserMedia()][1] requests failed with a
+/// [transient][2] error.
+///
+/// Delays between the attempts grow exponentially, starting from the
+/// `initial_delay`.
+///
+/// [1]: https://tinyurl.com/w3-streams#dom-mediadevices-getusermedia
+/// [2]: platform::GetUserMediaError::is_transient
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct GetUserMediaRetryPolicy {
+    /// Maximum number of retries, where `0` disables retrying at all.
+    pub max_retries: u8,
+
+    /// Delay before the first retry.
+    pub initial_delay: Duration,
+}
+
+impl GetUserMediaRetryPolicy {
+    /// [`GetUserMediaRetryPolicy`] never retrying failed requests.
+    pub const DISABLED: Self = Self {
+        max_retries: 0,
+        initial_delay: Duration::ZERO,
+    };
+
+    /// Returns delays before every retry of this [`GetUserMediaRetryPolicy`].
+    fn delays(self) -> impl Iterator<Item = Duration> {
+        (0..u32::from(self.max_retries))
+            .map(move |i| self.initial_delay.saturating_mul(1 << i.min(31)))
+    }
+}
+
+impl Default for GetUserMediaRetryPolicy {
+    /// Retries up to 3 times in 300, 600 and 1200 milliseconds.
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_delay: Duration::from_millis(300),
+        }
+    }
+}
+
+/// Action to take on a [`local::Track`] captured via [getUserMedia()][1],
+/// depending on whether it's captured from the requested device.
+///
+/// [1]: https://tinyurl.com/w3-streams#dom-mediadevices-getusermedia
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum DeviceCheck {
+    /// [`local::Track`] is captured from the requested device, or no specific
+    /// device is requested.
+    Matches,
+
+    /// Capturing should be retried with an exact [deviceId][1] constraint.
+    ///
+    /// [1]: https://w3.org/TR/mediacapture-streams#def-constraint-deviceId
+    RetryExact,
+
+    /// [`local::Track`] is captured from another device, which should be
+    /// reported with a [`DeviceMismatch`].
+    Mismatch,
+}
+
+impl DeviceCheck {
+    /// Decides on a [`local::Track`] captured from the device with the
+    /// provided `actual` ID with the provided `requested` [deviceId][1]
+    /// constraint.
+    ///
+    /// Only an ideal constraint is retried, and only once.
+    ///
+    /// [1]: https://w3.org/TR/mediacapture-streams#def-constraint-deviceId
+    fn new(
+        requested: Option<&ConstrainString<String>>,
+        actual: &str,
+        is_retried: bool,
+    ) -> Self {
+        match requested {
+            None => Self::Matches,
+            Some(ConstrainString::Exact(id) | ConstrainString::Ideal(id))
+                if id == actual =>
+            {
+                Self::Matches
+            }
+            Some(ConstrainString::Ideal(_)) if !is_retried => Self::RetryExact,
+            Some(ConstrainString::Exact(_) | ConstrainString::Ideal(_)) => {
+                Self::Mismatch
+            }
+        }
+    }
+}
+
+/// Difference between two lists of [`platform::MediaDeviceInfo`]s.
+#[derive(Clone, Debug, Default)]
+pub struct MediaDevicesDiff {
+    /// [`platform::MediaDeviceInfo`]s appeared in the newer list.
+    pub added: Vec<platform::MediaDeviceInfo>,
+
+    /// [`platform::MediaDeviceInfo`]s disappeared from the newer list.
+    pub removed: Vec<platform::MediaDeviceInfo>,
+}
+
+impl MediaDevicesDiff {
+    /// Computes a [`MediaDevicesDiff`] between the provided `old` and `new`
+    /// lists of [`platform::MediaDeviceInfo`]s.
+    ///
+    /// Devices are identified by their [`MediaDeviceKind`] and ID, so a device
+    /// changing its ID (like it happens once a permission is granted) is
+    /// reported both as removed and added.
+    ///
+    /// [`MediaDeviceKind`]: crate::media::MediaDeviceKind
+    fn new(
+        old: &[platform::MediaDeviceInfo],
+        new: &[platform::MediaDeviceInfo],
+    ) -> Self {
+        let (added, removed) = diff_by(old, new, |d| (d.kind(), d.device_id()));
+        Self { added, removed }
+    }
+}
+
+/// Returns items of the `new` list missing in the `old` one, and items of the
+/// `old` list missing in the `new` one, comparing them by the provided `key`.
+///
+/// Items with equal keys are matched one-to-one, so the ones with duplicated
+/// keys (like empty device IDs before a permission is granted) are diffed by
+/// their count.
+fn diff_by<T, K, F>(old: &[T], new: &[T], key: F) -> (Vec<T>, Vec<T>)
+where
+    T: Clone,
+    K: PartialEq,
+    F: Fn(&T) -> K,
+{
+    let mut unmatched: Vec<_> = old.iter().map(|t| (key(t), t)).collect();
+    let mut added = Vec::new();
+    for item in new {
+        let item_key = key(item);
+        if let Some(pos) = unmatched.iter().position(|(k, _)| *k == item_key) {
+            _ = unmatched.remove(pos);
+        } else {
+            added.push(item.clone());
+        }
+    }
+    let removed = unmatched.into_iter().map(|(_, t)| t.clone()).collect();
+    (added, removed)
+}
+
+/// Returns [`TrackConstraints`] of the provided [`MediaStreamSettings`] the
+/// provided [`local::Track`] is captured with.
+fn capture_constraints(
+    caps: &MediaStreamSettings,
+    track: &local::Track,
+) -> Option<TrackConstraints> {
+    match (track.kind(), track.media_source_kind()) {
+        (MediaKind::Audio, _) => {
+            Some(TrackConstraints::Audio(caps.get_audio().clone()))
+        }
+        (MediaKind::Video, MediaSourceKind::Device) => caps
+            .get_device_video()
+            .cloned()
+            .map(|c| TrackConstraints::Video(VideoSource::Device(c))),
+        (MediaKind::Video, MediaSourceKind::Display) => caps
+            .get_display_video()
+            .cloned()
+            .map(|c| TrackConstraints::Video(VideoSource::Display(c))),
+    }
+}
+
 /// Actual data of [`MediaManager`].
-#[derive(Debug, Default)]
+#[derive(Default)]
 struct InnerMediaManager {
     /// Obtained tracks storage
     tracks: RefCell<HashMap<String, Weak<local::Track>>>,
 
     /// Media devices platform controller.
     media_devices: platform::MediaDevices,
+
+    /// Last enumerated [`platform::MediaDeviceInfo`]s, if any.
+    devices: RefCell<Option<Vec<platform::MediaDeviceInfo>>>,
+
+    /// [`DeviceCapabilities`] queried so far, by IDs of their devices.
+    ///
+    /// Cleared on every `devicechange` event.
+    capabilities: RefCell<HashMap<String, DeviceCapabilities>>,
+
+    /// Indicator whether the `devicechange` event is listened already.
+    is_device_change_listened: Cell<bool>,
+
+    /// Indicator whether any [getUserMedia()][1] request has succeeded
+    /// already.
+    ///
+    /// [1]: https://tinyurl.com/w3-streams#dom-mediadevices-getusermedia
+    is_gum_succeeded: Cell<bool>,
+
+    /// Indicator whether changes of media permissions are listened already.
+    is_permission_change_listened: Cell<bool>,
+
+    /// [`GetUserMediaRetryPolicy`] of [getUserMedia()][1] requests.
+    ///
+    /// [1]: https://tinyurl.com/w3-streams#dom-mediadevices-getusermedia
+    gum_retry_policy: Cell<GetUserMediaRetryPolicy>,
+
+    /// Callback to be invoked with a [`MediaDevicesDiff`] on every
+    /// `devicechange` event.
+    on_device_change: RefCell<Option<Box<dyn FnMut(MediaDevicesDiff)>>>,
+}
+
+impl fmt::Debug for InnerMediaManager {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InnerMediaManager")
+            .field("tracks", &self.tracks)
+            .field("media_devices", &self.media_devices)
+            .field("devices", &self.devices)
+            .field("capabilities", &self.capabilities)
+            .field("is_gum_succeeded", &self.is_gum_succeeded)
+            .field("gum_retry_policy", &self.gum_retry_policy)
+            .finish_non_exhaustive()
+    }
 }
 
 impl InnerMediaManager {
-    /// Subscribes onto the `devicechange` event of this [`InnerMediaManager`].
-    pub fn on_device_change(&self, cb: platform::Function<()>) {
+    /// Subscribes the provided callback onto the `devicechange` event of this
+    /// [`InnerMediaManager`], providing it with a [`MediaDevicesDiff`] against
+    /// the cached [`platform::MediaDeviceInfo`]s.
+    fn on_device_change<F>(self: &Rc<Self>, cb: F)
+    where
+        F: FnMut(MediaDevicesDiff) + 'static,
+    {
+        drop(self.on_device_change.replace(Some(Box::new(cb))));
+        self.listen_device_change();
+
+        if self.devices.borrow().is_none() {
+            // Populate the cache to diff the next `devicechange` against it.
+            let this = Rc::downgrade(self);
+            platform::spawn(async move {
+                if let Some(this) = this.upgrade() {
+                    if let Err(e) = this.update_devices().await {
+                        log::error!("Failed to enumerate media devices: {e}");
+                    }
+                }
+            });
+        }
+    }
+
+    /// Starts listening to the `devicechange` event to refresh the cached
+    /// [`platform::MediaDeviceInfo`]s, if not yet.
+    fn listen_device_change(self: &Rc<Self>) {
+        if self.is_device_change_listened.replace(true) {
+            return;
+        }
+
+        let this = Rc::downgrade(self);
         self.media_devices.on_device_change(Some(move || {
-            cb.call0();
+            let this = Weak::clone(&this);
+            platform::spawn(async move {
+                if let Some(this) = this.upgrade() {
+                    this.handle_device_change().await;
+                }
+            });
+        }));
+    }
+
+    /// Starts listening to media permissions being granted to reset the
+    /// capture state of the granted media, if not yet.
+    fn listen_permission_change(self: &Rc<Self>) {
+        if self.is_permission_change_listened.replace(true) {
+            return;
+        }
+
+        let this = Rc::downgrade(self);
+        self.media_devices.on_permission_granted(Some(move |kind| {
+            let this = Weak::clone(&this);
+            platform::spawn(async move {
+                if let Some(this) = this.upgrade() {
+                    this.reset_capture_state(kind).await;
+                }
+            });
         }));
     }
 
+    /// Forgets everything remembered about capturing media of the provided
+    /// [`MediaKind`] while the access to it was denied, so the next request
+    /// captures it from scratch.
+    ///
+    /// The cached [`platform::MediaDeviceInfo`]s are refreshed if they have
+    /// been enumerated without the access to the media input devices of the
+    /// provided [`MediaKind`] (so these devices are missing, or have no IDs
+    /// and labels), notifying the `on_device_change` callback about the
+    /// revealed devices.
+    async fn reset_capture_state(&self, kind: MediaKind) {
+        let device_kind = match kind {
+            MediaKind::Audio => MediaDeviceKind::AudioInput,
+            MediaKind::Video => MediaDeviceKind::VideoInput,
+        };
+        let is_stale = self.devices.borrow().as_ref().map_or(false, |devs| {
+            let devs: Vec<_> =
+                devs.iter().filter(|d| d.kind() == device_kind).collect();
+            devs.is_empty()
+                || devs
+                    .iter()
+                    .any(|d| d.device_id().is_empty() || d.label().is_empty())
+        });
+
+        // Device labels are to be refreshed once the access is granted.
+        self.is_gum_succeeded.set(false);
+        if is_stale {
+            self.handle_device_change().await;
+        }
+    }
+
+    /// Refreshes the cached [`platform::MediaDeviceInfo`]s on a
+    /// `devicechange` event, forgets the queried [`DeviceCapabilities`], and
+    /// invokes the [`InnerMediaManager`]'s
+    /// `on_device_change` callback with a resulting [`MediaDevicesDiff`].
+    async fn handle_device_change(&self) {
+        self.capabilities.borrow_mut().clear();
+        let diff = match self.update_devices().await {
+            Ok(diff) => diff,
+            Err(e) => {
+                log::error!("Failed to enumerate media devices: {e}");
+                return;
+            }
+        };
+
+        // Taken out to allow resubscribing from inside the callback.
+        let cb = self.on_device_change.borrow_mut().take();
+        if let Some(mut cb) = cb {
+            cb(diff);
+            if self.on_device_change.borrow().is_none() {
+                drop(self.on_device_change.replace(Some(cb)));
+            }
+        }
+    }
+
     /// Returns a list of [`platform::MediaDeviceInfo`] objects.
     async fn enumerate_devices(
         &self,
@@ -203,6 +571,163 @@ impl InnerMediaManager {
             .map_err(tracerr::wrap!())
     }
 
+    /// Returns the cached list of [`platform::MediaDeviceInfo`] objects,
+    /// enumerating them if there is no cache yet.
+    async fn enumerate_devices_cached(
+        self: &Rc<Self>,
+    ) -> Result<Vec<platform::MediaDeviceInfo>, Traced<platform::Error>> {
+        let cached = self.devices.borrow().clone();
+        if let Some(devices) = cached {
+            return Ok(devices);
+        }
+        self.refresh_devices().await.map_err(tracerr::wrap!())
+    }
+
+    /// Enumerates [`platform::MediaDeviceInfo`] objects anew, caching them
+    /// and keeping the cache refreshed on `devicechange` events.
+    async fn refresh_devices(
+        self: &Rc<Self>,
+    ) -> Result<Vec<platform::MediaDeviceInfo>, Traced<platform::Error>> {
+        self.listen_device_change();
+        _ = self.update_devices().await.map_err(tracerr::wrap!())?;
+        Ok(self.devices.borrow().clone().unwrap_or_default())
+    }
+
+    /// Indicates whether at least one media device of the provided
+    /// [`MediaDeviceKind`] is available, enumerating devices only if there is
+    /// no cache yet.
+    async fn has_device(
+        self: &Rc<Self>,
+        kind: MediaDeviceKind,
+    ) -> Result<bool, Traced<platform::Error>> {
+        Ok(self
+            .enumerate_devices_cached()
+            .await
+            .map_err(tracerr::wrap!())?
+            .iter()
+            .any(|d| d.kind() == kind))
+    }
+
+    /// Disables publishing of the device media in the provided
+    /// [`MediaStreamSettings`], which has no media input devices available,
+    /// so no [getUserMedia()][1] request is made for it.
+    ///
+    /// Devices are enumerated only if there is no cache yet. If the
+    /// enumeration fails, then the provided [`MediaStreamSettings`] are left
+    /// untouched, letting the [getUserMedia()][1] request decide.
+    ///
+    /// # Errors
+    ///
+    /// With a [`NoDevicesError`] if media of a missing [`MediaKind`] is
+    /// required by the provided [`MediaStreamSettings`].
+    ///
+    /// [1]: https://tinyurl.com/w3-streams#dom-mediadevices-getusermedia
+    async fn skip_absent_devices(
+        &self,
+        caps: &mut MediaStreamSettings,
+    ) -> Result<(), Traced<NoDevicesError>> {
+        if !caps.is_audio_enabled() && !caps.is_device_video_enabled() {
+            return Ok(());
+        }
+
+        let cached = self.devices.borrow().clone();
+        let devices = if let Some(devices) = cached {
+            devices
+        } else {
+            match self.enumerate_devices().await {
+                Ok(devices) => devices,
+                Err(e) => {
+                    log::error!("Failed to enumerate media devices: {e}");
+                    return Ok(());
+                }
+            }
+        };
+        let has =
+            |kind: MediaDeviceKind| devices.iter().any(|d| d.kind() == kind);
+
+        if caps.is_audio_enabled() && !has(MediaDeviceKind::AudioInput) {
+            if caps.get_audio().required() {
+                return Err(tracerr::new!(NoDevicesError {
+                    kind: MediaKind::Audio,
+                }));
+            }
+            caps.set_audio_publish(false);
+        }
+        if caps.is_device_video_enabled() && !has(MediaDeviceKind::VideoInput) {
+            if caps.get_device_video().map_or(false, |v| v.required()) {
+                return Err(tracerr::new!(NoDevicesError {
+                    kind: MediaKind::Video,
+                }));
+            }
+            caps.set_video_publish(false, Some(MediaSourceKind::Device));
+        }
+
+        Ok(())
+    }
+
+    /// Replaces the cached [`platform::MediaDeviceInfo`]s with the newly
+    /// enumerated ones, returning a [`MediaDevicesDiff`] between them.
+    async fn update_devices(
+        &self,
+    ) -> Result<MediaDevicesDiff, Traced<platform::Error>> {
+        let devices = self.enumerate_devices().await?;
+        let diff = MediaDevicesDiff::new(
+            self.devices.borrow().as_deref().unwrap_or_default(),
+            &devices,
+        );
+        drop(self.devices.replace(Some(devices)));
+        Ok(diff)
+    }
+
+    /// Returns [`DeviceCapabilities`] of the media input device with the
+    /// provided ID, querying them only if they aren't cached yet.
+    ///
+    /// # Errors
+    ///
+    /// See [`DeviceCapabilitiesError`] for details.
+    async fn device_capabilities(
+        self: &Rc<Self>,
+        device_id: String,
+    ) -> Result<DeviceCapabilities, Traced<DeviceCapabilitiesError>> {
+        let cached = self.capabilities.borrow().get(&device_id).cloned();
+        if let Some(capabilities) = cached {
+            return Ok(capabilities);
+        }
+
+        let devices = self
+            .enumerate_devices_cached()
+            .await
+            .map_err(tracerr::map_from_and_wrap!())?;
+        let (device, kind) = devices
+            .into_iter()
+            .find_map(|d| {
+                let kind = match d.kind() {
+                    MediaDeviceKind::AudioInput => MediaKind::Audio,
+                    MediaDeviceKind::VideoInput => MediaKind::Video,
+                    MediaDeviceKind::AudioOutput => return None,
+                };
+                (d.device_id() == device_id).then_some((d, kind))
+            })
+            .ok_or_else(|| {
+                tracerr::new!(DeviceCapabilitiesError::NoSuchDevice(
+                    device_id.clone(),
+                ))
+            })?;
+
+        let capabilities = self
+            .media_devices
+            .device_capabilities(&device, kind)
+            .await
+            .map_err(tracerr::map_from_and_wrap!(=> GetUserMediaError))
+            .map_err(tracerr::map_from_and_wrap!())?;
+        drop(
+            self.capabilities
+                .borrow_mut()
+                .insert(device_id, capabilities.clone()),
+        );
+        Ok(capabilities)
+    }
+
     /// Returns a list of [`platform::MediaDisplayInfo`] objects.
     async fn enumerate_displays(
         &self,
@@ -218,6 +743,15 @@ impl InnerMediaManager {
     /// earlier, or new tracks, acquired via [getUserMedia()][1] or/and
     /// [getDisplayMedia()][2] requests.
     ///
+    /// [`ContentHint`]s of the provided [`MediaStreamSettings`] are applied to
+    /// the new tracks, with [`ContentHint::Detail`] being the default one for
+    /// display video.
+    ///
+    /// Custom tracks set via the
+    /// [`MediaStreamSettings::set_custom_audio_track()`] and
+    /// [`MediaStreamSettings::set_custom_video_track()`] are returned as is,
+    /// without requesting media of their kinds, and are never stored.
+    ///
     /// # Errors
     ///
     /// With [`InitLocalTracksError::GetUserMediaFailed`] if [getUserMedia()][1]
@@ -226,22 +760,34 @@ impl InnerMediaManager {
     /// With [`InitLocalTracksError::GetDisplayMediaFailed`] if
     /// [getDisplayMedia()][2] request failed.
     ///
+    /// With [`InitLocalTracksError::NoDevices`] if there are no media input
+    /// devices of a required [`MediaKind`].
+    ///
     /// [1]: https://tinyurl.com/w3-streams#dom-mediadevices-getusermedia
     /// [2]: https://w3.org/TR/screen-capture#dom-mediadevices-getdisplaymedia
     async fn get_tracks(
-        &self,
+        self: &Rc<Self>,
         mut caps: MediaStreamSettings,
     ) -> Result<Vec<(Rc<local::Track>, bool)>, Traced<InitLocalTracksError>>
     {
-        let tracks_from_storage = self
-            .get_from_storage(&mut caps)
-            .await
+        let audio_hint = caps.get_audio().content_hint;
+        let display_hint = caps
+            .get_display_video()
+            .and_then(|c| c.content_hint)
+            .unwrap_or(ContentHint::Detail);
+        let requested = caps.clone();
+        let custom_tracks = caps.take_custom_tracks();
+        let tracks_from_storage = custom_tracks
             .into_iter()
+            .chain(self.get_from_storage(&mut caps).await)
             .map(|t| (t, false));
-        match caps.into() {
-            None => Ok(tracks_from_storage.collect()),
+        self.skip_absent_devices(&mut caps)
+            .await
+            .map_err(tracerr::map_from_and_wrap!())?;
+        let mut tracks: Vec<_> = match caps.into() {
+            None => tracks_from_storage.collect(),
             Some(MultiSourceTracksConstraints::Display(caps)) => {
-                Ok(tracks_from_storage
+                tracks_from_storage
                     .chain(
                         self.get_display_media(caps)
                             .await
@@ -249,10 +795,10 @@ impl InnerMediaManager {
                             .into_iter()
                             .map(|t| (t, true)),
                     )
-                    .collect())
+                    .collect()
             }
             Some(MultiSourceTracksConstraints::Device(caps)) => {
-                Ok(tracks_from_storage
+                tracks_from_storage
                     .chain(
                         self.get_user_media(caps)
                             .await
@@ -260,7 +806,7 @@ impl InnerMediaManager {
                             .into_iter()
                             .map(|t| (t, true)),
                     )
-                    .collect())
+                    .collect()
             }
             Some(MultiSourceTracksConstraints::DeviceAndDisplay(
                 device_caps,
@@ -274,24 +820,109 @@ impl InnerMediaManager {
                     .get_display_media(display_caps)
                     .await
                     .map_err(tracerr::map_from_and_wrap!())?;
-                Ok(tracks_from_storage
+                tracks_from_storage
                     .chain(
                         device_tracks
                             .into_iter()
                             .chain(display_tracks.into_iter())
                             .map(|t| (t, true)),
                     )
-                    .collect())
+                    .collect()
+            }
+        };
+
+        for (track, is_new) in &mut tracks {
+            if *is_new && track.media_source_kind() == MediaSourceKind::Device {
+                *track = self.ensure_device(&requested, Rc::clone(track)).await;
             }
         }
+
+        for (track, _) in tracks.iter().filter(|(_, is_new)| *is_new) {
+            match (track.kind(), track.media_source_kind()) {
+                (MediaKind::Audio, _) => {
+                    if let Some(hint) = audio_hint {
+                        track.set_content_hint(hint);
+                    }
+                }
+                (MediaKind::Video, MediaSourceKind::Display) => {
+                    track.set_content_hint(display_hint);
+                }
+                (MediaKind::Video, MediaSourceKind::Device) => {}
+            }
+            if let Some(caps) = capture_constraints(&requested, track) {
+                track.set_capture_constraints(caps);
+            }
+        }
+
+        Ok(tracks)
+    }
+
+    /// Ensures the provided [`local::Track`] captured via [getUserMedia()][1]
+    /// is captured from the device requested in the provided
+    /// [`MediaStreamSettings`].
+    ///
+    /// Some platforms capture the default device instead of the one requested
+    /// via an ideal [deviceId][2] constraint, so capturing is retried once
+    /// with an exact one. If the resulting [`local::Track`] is still captured
+    /// from another device, then it's marked with a [`DeviceMismatch`].
+    ///
+    /// [1]: https://tinyurl.com/w3-streams#dom-mediadevices-getusermedia
+    /// [2]: https://w3.org/TR/mediacapture-streams#def-constraint-deviceId
+    async fn ensure_device(
+        self: &Rc<Self>,
+        requested: &MediaStreamSettings,
+        mut track: Rc<local::Track>,
+    ) -> Rc<local::Track> {
+        let kind = track.kind();
+        let constraint =
+            requested.device_id_constraint(kind, MediaSourceKind::Device);
+        let check = DeviceCheck::new(constraint, &track.device_id(), false);
+        if check == DeviceCheck::RetryExact {
+            if let Some(caps) = requested.exact_device_constraints(kind) {
+                match self.get_user_media(caps).await {
+                    Ok(retried) => {
+                        if let Some(retried) =
+                            retried.into_iter().find(|t| t.kind() == kind)
+                        {
+                            // The track of the wrong device is stopped once
+                            // dropped.
+                            track = retried;
+                        }
+                    }
+                    Err(e) => {
+                        log::warn!("Failed to capture the exact device: {e}");
+                    }
+                }
+            }
+        }
+
+        let actual = track.device_id();
+        if DeviceCheck::new(constraint, &actual, true) == DeviceCheck::Mismatch
+        {
+            if let Some(requested) =
+                requested.device_id(kind, MediaSourceKind::Device)
+            {
+                log::warn!(
+                    "Captured device `{actual}` instead of the requested \
+                     `{requested}`",
+                );
+                track.set_device_mismatch(DeviceMismatch { requested, actual });
+            }
+        }
+        track
     }
 
     /// Tries to find [`local::Track`]s that satisfies [`MediaStreamSettings`],
     /// from tracks that were acquired earlier to avoid redundant
     /// [getUserMedia()][1]/[getDisplayMedia()][2] calls.
     ///
+    /// Device [`local::Track`]s are reused only if they're actually captured
+    /// from the requested devices, regardless of whether the [deviceId][3]
+    /// constraints are exact or ideal ones.
+    ///
     /// [1]: https://tinyurl.com/w3-streams#dom-mediadevices-getusermedia
     /// [2]: https://w3.org/TR/screen-capture#dom-mediadevices-getdisplaymedia
+    /// [3]: https://w3.org/TR/mediacapture-streams#def-constraint-deviceId
     async fn get_from_storage(
         &self,
         caps: &mut MediaStreamSettings,
@@ -309,12 +940,18 @@ impl InnerMediaManager {
             .borrow()
             .iter()
             .map(|(_, track)| Weak::upgrade(track).unwrap())
+            .filter(|track| {
+                track.media_source_kind() != MediaSourceKind::Device
+                    || caps
+                        .device_id(track.kind(), MediaSourceKind::Device)
+                        .map_or(true, |id| id == track.device_id())
+            })
             .collect();
 
         let mut tracks = Vec::new();
         if caps.is_audio_enabled() {
             for track in &storage {
-                if caps.get_audio().satisfies(track.as_ref()).await {
+                if caps.get_audio().is_satisfied_by(track).await {
                     caps.set_audio_publish(false);
                     tracks.push(Rc::clone(track));
                     break;
@@ -323,7 +960,7 @@ impl InnerMediaManager {
         }
 
         for track in storage {
-            if caps.unconstrain_if_satisfies_video(track.as_ref()).await {
+            if caps.unconstrain_if_satisfies_video(&track).await {
                 tracks.push(track);
             }
         }
@@ -336,21 +973,55 @@ impl InnerMediaManager {
     /// refs.
     ///
     /// [1]: https://tinyurl.com/w3-streams#dom-mediadevices-getusermedia
+    ///
+    /// Requests failed with a [transient][2] error are retried according to
+    /// the [`GetUserMediaRetryPolicy`] of this [`InnerMediaManager`]. Dropping
+    /// the returned [`Future`] cancels any further retries.
+    ///
+    /// Once the request fails, changes of media permissions start being
+    /// listened, so the capture state is reset as soon as the access is
+    /// granted again.
+    ///
+    /// [2]: platform::GetUserMediaError::is_transient
+    /// [`Future`]: std::future::Future
     async fn get_user_media(
-        &self,
+        self: &Rc<Self>,
         caps: platform::MediaStreamConstraints,
     ) -> Result<Vec<Rc<local::Track>>, Traced<GetUserMediaError>> {
-        let tracks = self
-            .media_devices
-            .get_user_media(caps)
-            .await
-            .map_err(tracerr::map_from_and_wrap!())?;
+        let mut delays = self.gum_retry_policy.get().delays();
+        let result = loop {
+            let result = self.media_devices.get_user_media(caps.clone()).await;
+            let Err(err) = &result else {
+                break result;
+            };
+            let err: &platform::GetUserMediaError = err.as_ref();
+            match delays.next() {
+                Some(delay) if err.is_transient() => {
+                    log::warn!("Retrying getUserMedia() in {delay:?}: {err}");
+                    platform::delay_for(delay).await;
+                }
+                _ => break result,
+            }
+        };
+        if result.is_err() {
+            self.listen_permission_change();
+        }
+        let tracks = result.map_err(tracerr::map_from_and_wrap!())?;
 
         let tracks = self
             .parse_and_save_tracks(tracks, MediaSourceKind::Device)
             .await
             .map_err(tracerr::map_from_and_wrap!())?;
 
+        // Devices labels become available once a permission is granted.
+        if !self.is_gum_succeeded.replace(true)
+            && self.devices.borrow().is_some()
+        {
+            if let Err(e) = self.update_devices().await {
+                log::error!("Failed to enumerate media devices: {e}");
+            }
+        }
+
         Ok(tracks)
     }
 
@@ -481,6 +1152,9 @@ impl MediaManager {
     /// With [`InitLocalTracksError::GetDisplayMediaFailed`] if
     /// [getDisplayMedia()][2] request failed.
     ///
+    /// With [`InitLocalTracksError::NoDevices`] if there are no media input
+    /// devices of a required [`MediaKind`].
+    ///
     /// [1]: https://tinyurl.com/w3-streams#dom-mediadevices-getusermedia
     /// [2]: https://w3.org/TR/screen-capture/#dom-mediadevices-getdisplaymedia
     pub async fn get_tracks<I: Into<MediaStreamSettings>>(
@@ -537,6 +1211,143 @@ impl MediaManagerHandle {
             .map_err(tracerr::map_from_and_wrap!())
     }
 
+    /// Returns a list of [`platform::MediaDeviceInfo`] objects representing
+    /// available media input and output devices, guaranteeing that no
+    /// [getUserMedia()][1] request is made, so no permission prompt is shown.
+    ///
+    /// Device labels stay redacted until a media access permission is granted
+    /// (see [`platform::MediaDeviceInfo::is_label_redacted()`]). Neither reads
+    /// nor updates the cache of the
+    /// [`MediaManagerHandle::enumerate_devices_cached()`].
+    ///
+    /// # Errors
+    ///
+    /// See [`EnumerateDevicesError`] for details.
+    ///
+    /// [1]: https://tinyurl.com/w3-streams#dom-mediadevices-getusermedia
+    pub async fn enumerate_devices_quiet(
+        &self,
+    ) -> Result<Vec<platform::MediaDeviceInfo>, Traced<EnumerateDevicesError>>
+    {
+        let this = self
+            .0
+            .upgrade()
+            .ok_or_else(|| tracerr::new!(EnumerateDevicesError::Detached))?;
+        this.enumerate_devices()
+            .await
+            .map_err(tracerr::map_from_and_wrap!())
+    }
+
+    /// Same as [`MediaManagerHandle::enumerate_devices()`], but can be aborted
+    /// via an [`AbortHandle`] of the provided [`AbortRegistration`].
+    ///
+    /// # Errors
+    ///
+    /// With [`AbortableError::Aborted`] if the enumeration is aborted.
+    ///
+    /// With [`AbortableError::Failed`] if
+    /// [`MediaManagerHandle::enumerate_devices()`] fails.
+    ///
+    /// [`AbortHandle`]: futures::future::AbortHandle
+    pub async fn enumerate_devices_abortable(
+        &self,
+        abort: AbortRegistration,
+    ) -> Result<
+        Vec<platform::MediaDeviceInfo>,
+        AbortableError<Traced<EnumerateDevicesError>>,
+    > {
+        utils::abortable(self.enumerate_devices(), abort).await
+    }
+
+    /// Returns the cached list of [`platform::MediaDeviceInfo`] objects,
+    /// enumerating them only if there is no cache yet.
+    ///
+    /// The cache is refreshed automatically on `devicechange` events and after
+    /// the first successful [getUserMedia()][1] request (since device labels
+    /// are unavailable before a permission is granted).
+    ///
+    /// # Errors
+    ///
+    /// See [`EnumerateDevicesError`] for details.
+    ///
+    /// [1]: https://tinyurl.com/w3-streams#dom-mediadevices-getusermedia
+    pub async fn enumerate_devices_cached(
+        &self,
+    ) -> Result<Vec<platform::MediaDeviceInfo>, Traced<EnumerateDevicesError>>
+    {
+        let this = self
+            .0
+            .upgrade()
+            .ok_or_else(|| tracerr::new!(EnumerateDevicesError::Detached))?;
+        this.enumerate_devices_cached()
+            .await
+            .map_err(tracerr::map_from_and_wrap!())
+    }
+
+    /// Enumerates [`platform::MediaDeviceInfo`] objects anew, replacing the
+    /// ones cached for the [`MediaManagerHandle::enumerate_devices_cached()`].
+    ///
+    /// # Errors
+    ///
+    /// See [`EnumerateDevicesError`] for details.
+    pub async fn refresh_devices(
+        &self,
+    ) -> Result<Vec<platform::MediaDeviceInfo>, Traced<EnumerateDevicesError>>
+    {
+        let this = self
+            .0
+            .upgrade()
+            .ok_or_else(|| tracerr::new!(EnumerateDevicesError::Detached))?;
+        this.refresh_devices()
+            .await
+            .map_err(tracerr::map_from_and_wrap!())
+    }
+
+    /// Indicates whether at least one media device of the provided
+    /// [`MediaDeviceKind`] is available.
+    ///
+    /// Uses the same cache as the
+    /// [`MediaManagerHandle::enumerate_devices_cached()`] does.
+    ///
+    /// # Errors
+    ///
+    /// See [`EnumerateDevicesError`] for details.
+    pub async fn has_device(
+        &self,
+        kind: MediaDeviceKind,
+    ) -> Result<bool, Traced<EnumerateDevicesError>> {
+        let this = self
+            .0
+            .upgrade()
+            .ok_or_else(|| tracerr::new!(EnumerateDevicesError::Detached))?;
+        this.has_device(kind)
+            .await
+            .map_err(tracerr::map_from_and_wrap!())
+    }
+
+    /// Returns [`DeviceCapabilities`] of the media input device with the
+    /// provided ID, without starting a capture from it.
+    ///
+    /// If the platform cannot report them otherwise, the device is captured
+    /// briefly, releasing the captured track right away.
+    ///
+    /// Queried [`DeviceCapabilities`] are cached until the next
+    /// `devicechange` event.
+    ///
+    /// # Errors
+    ///
+    /// See [`DeviceCapabilitiesError`] for details.
+    pub async fn device_capabilities(
+        &self,
+        device_id: String,
+    ) -> Result<DeviceCapabilities, Traced<DeviceCapabilitiesError>> {
+        let this = self
+            .0
+            .upgrade()
+            .ok_or_else(|| tracerr::new!(DeviceCapabilitiesError::Detached))?;
+        this.device_capabilities(device_id).await
+    }
+
     /// Returns a list of [`platform::MediaDisplayInfo`] objects representing
     /// available displays.
     ///
@@ -559,6 +1370,9 @@ impl MediaManagerHandle {
     /// Returns [`local::LocalMediaTrack`]s objects, built from the provided
     /// [`MediaStreamSettings`].
     ///
+    /// The provided [`MediaStreamSettings`] are validated before any media is
+    /// requested.
+    ///
     /// # Errors
     ///
     /// See [`InitLocalTracksError`] for details.
@@ -573,6 +1387,8 @@ impl MediaManagerHandle {
             .0
             .upgrade()
             .ok_or_else(|| tracerr::new!(InitLocalTracksError::Detached))?;
+        caps.validate()
+            .map_err(|e| tracerr::new!(InitLocalTracksError::from(e)))?;
         this.get_tracks(caps)
             .await
             .map(|tracks| {
@@ -655,15 +1471,64 @@ impl MediaManagerHandle {
             .map_err(tracerr::map_from_and_wrap!())
     }
 
-    /// Subscribes onto the `devicechange` event of this [`MediaManagerHandle`].
+    /// Resets the capture state of the provided [`MediaKind`], so the next
+    /// media request captures it from scratch, without relying on anything
+    /// remembered while the access to it was denied.
+    ///
+    /// Happens automatically once a permission to access the media is granted
+    /// again, if the platform allows observing it.
+    ///
+    /// # Errors
+    ///
+    /// If the underlying [`MediaManagerHandle`] is dropped.
+    pub async fn reset_capture_state(
+        &self,
+        kind: MediaKind,
+    ) -> Result<(), Traced<HandleDetachedError>> {
+        let this = self
+            .0
+            .upgrade()
+            .ok_or_else(|| tracerr::new!(HandleDetachedError))?;
+        this.reset_capture_state(kind).await;
+        Ok(())
+    }
+
+    /// Sets the [`GetUserMediaRetryPolicy`] of the subsequent
+    /// [getUserMedia()][1] requests.
     ///
     /// # Errors
     ///
     /// If the underlying [`MediaManagerHandle`] is dropped.
-    pub fn on_device_change(
+    ///
+    /// [1]: https://tinyurl.com/w3-streams#dom-mediadevices-getusermedia
+    pub fn set_get_user_media_retry_policy(
         &self,
-        cb: platform::Function<()>,
+        policy: GetUserMediaRetryPolicy,
     ) -> Result<(), Traced<HandleDetachedError>> {
+        let this = self
+            .0
+            .upgrade()
+            .ok_or_else(|| tracerr::new!(HandleDetachedError))?;
+        this.gum_retry_policy.set(policy);
+        Ok(())
+    }
+
+    /// Subscribes onto the `devicechange` event of this [`MediaManagerHandle`].
+    ///
+    /// The provided callback is invoked with a [`MediaDevicesDiff`] against
+    /// the [`platform::MediaDeviceInfo`]s cached for the
+    /// [`MediaManagerHandle::enumerate_devices_cached()`].
+    ///
+    /// # Errors
+    ///
+    /// If the underlying [`MediaManagerHandle`] is dropped.
+    pub fn on_device_change<F>(
+        &self,
+        cb: F,
+    ) -> Result<(), Traced<HandleDetachedError>>
+    where
+        F: FnMut(MediaDevicesDiff) + 'static,
+    {
         let this = self
             .0
             .upgrade()
@@ -672,3 +1537,105 @@ impl MediaManagerHandle {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::media::constraints::ConstrainString;
+
+    use super::{diff_by, DeviceCheck, GetUserMediaRetryPolicy};
+
+    #[test]
+    fn diffs_added_and_removed() {
+        let old = [("audio", "mic"), ("video", "cam"), ("audio", "headset")];
+        let new = [("audio", "mic"), ("video", "usb-cam"), ("video", "cam")];
+
+        let (added, removed) = diff_by(&old, &new, |d| *d);
+
+        assert_eq!(added, [("video", "usb-cam")]);
+        assert_eq!(removed, [("audio", "headset")]);
+    }
+
+    #[test]
+    fn diffs_by_key_only() {
+        let old = [("mic", "Default"), ("cam", "")];
+        let new = [("mic", "Default"), ("cam", "HD Webcam")];
+
+        let (added, removed) = diff_by(&old, &new, |(id, _)| *id);
+
+        assert!(added.is_empty());
+        assert!(removed.is_empty());
+    }
+
+    #[test]
+    fn diffs_duplicated_keys_by_count() {
+        let old = ["", "", "mic"];
+        let new = ["", "mic", "mic"];
+
+        let (added, removed) = diff_by(&old, &new, |d| *d);
+
+        assert_eq!(added, ["mic"]);
+        assert_eq!(removed, [""]);
+    }
+
+    #[test]
+    fn diffs_empty_lists() {
+        let devices = ["mic", "cam"];
+
+        let (added, removed) = diff_by(&[], &devices, |d| *d);
+        assert_eq!(added, devices);
+        assert!(removed.is_empty());
+
+        let (added, removed) = diff_by(&devices, &[], |d| *d);
+        assert!(added.is_empty());
+        assert_eq!(removed, devices);
+    }
+
+    #[test]
+    fn retries_with_exponential_delays() {
+        let delays: Vec<_> = GetUserMediaRetryPolicy::default()
+            .delays()
+            .map(|d| d.as_millis())
+            .collect();
+        assert_eq!(delays, [300, 600, 1200]);
+
+        let delays: Vec<_> = GetUserMediaRetryPolicy {
+            max_retries: 2,
+            initial_delay: Duration::from_millis(50),
+        }
+        .delays()
+        .collect();
+        assert_eq!(
+            delays,
+            [Duration::from_millis(50), Duration::from_millis(100)],
+        );
+
+        assert_eq!(GetUserMediaRetryPolicy::DISABLED.delays().count(), 0);
+    }
+
+    #[test]
+    fn decides_on_captured_device() {
+        let ideal = ConstrainString::Ideal("mic".to_owned());
+        let exact = ConstrainString::Exact("mic".to_owned());
+
+        for (requested, actual, is_retried, expected) in [
+            (None, "default", false, DeviceCheck::Matches),
+            (None, "default", true, DeviceCheck::Matches),
+            (Some(&ideal), "mic", false, DeviceCheck::Matches),
+            (Some(&ideal), "mic", true, DeviceCheck::Matches),
+            (Some(&ideal), "default", false, DeviceCheck::RetryExact),
+            (Some(&ideal), "default", true, DeviceCheck::Mismatch),
+            (Some(&exact), "mic", false, DeviceCheck::Matches),
+            (Some(&exact), "default", false, DeviceCheck::Mismatch),
+            (Some(&exact), "default", true, DeviceCheck::Mismatch),
+        ] {
+            assert_eq!(
+                DeviceCheck::new(requested, actual, is_retried),
+                expected,
+                "requested: {requested:?}, actual: {actual}, \
+                 retried: {is_retried}",
+            );
+        }
+    }
+}
@@ -2,6 +2,7 @@
 //!
 //! [1]: https://w3.org/TR/mediacapture-streams
 
+mod capabilities;
 pub mod constraints;
 mod manager;
 pub mod track;
@@ -10,26 +11,39 @@ use derive_more::Display;
 
 #[doc(inline)]
 pub use self::{
+    capabilities::{
+        AudioCapabilities, CapabilityRange, DeviceCapabilities,
+        VideoCapabilities,
+    },
     constraints::{
-        AudioMediaTracksSettings, AudioTrackConstraints,
-        DeviceVideoTrackConstraints, DisplayVideoTrackConstraints, FacingMode,
+        AudioMediaTracksSettings, AudioTrackConstraints, CaptureInclusion,
+        DeviceVideoTrackConstraints, DisplayCaptureOptions,
+        DisplayVideoTrackConstraints, FacingMode, InvalidConstraint,
+        InvalidConstraintReason, InvalidConstraintsError,
         LocalTracksConstraints, MediaStreamSettings,
+        MediaStreamSettingsParseError, MediaStreamSettingsPreset,
         MultiSourceTracksConstraints, RecvConstraints, TrackConstraints,
         VideoSource, VideoTrackConstraints,
     },
     manager::{
-        EnumerateDevicesError, EnumerateDisplaysError, GetDisplayMediaError,
-        GetUserMediaError, HandleDetachedError, InitLocalTracksError,
-        InvalidOutputAudioDeviceIdError, MediaManager, MediaManagerHandle,
-        MicVolumeError,
+        DeviceCapabilitiesError, EnumerateDevicesError, EnumerateDisplaysError,
+        GetDisplayMediaError, GetUserMediaError, GetUserMediaRetryPolicy,
+        HandleDetachedError, InitLocalTracksError,
+        InvalidOutputAudioDeviceIdError, MediaDevicesDiff, MediaManager,
+        MediaManagerHandle, MicVolumeError, NoDevicesError,
+    },
+    track::{
+        remote::MediaDirection, CodecInfo, ContentHint, DeviceMismatch,
+        DisplaySurface, JitterBufferTargetError, MediaSourceKind,
+        MediaStreamTrackState, RequestKeyFrameError, SilenceDetectionError,
+        TrackTransformError, VideoRotation, VideoTransform,
     },
-    track::{remote::MediaDirection, MediaSourceKind, MediaStreamTrackState},
 };
 
 /// [MediaStreamTrack.kind][1] representation.
 ///
 /// [1]: https://w3.org/TR/mediacapture-streams#dom-mediastreamtrack-kind
-#[derive(Clone, Copy, Debug, Display, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Display, Eq, Hash, PartialEq)]
 #[repr(u8)]
 pub enum MediaKind {
     /// Audio track.
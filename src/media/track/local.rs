@@ -4,23 +4,41 @@
 //! [1]: https://w3.org/TR/mediacapture-streams#dom-mediadevices-getusermedia
 //! [2]: https://w3.org/TR/screen-capture/#dom-mediadevices-getdisplaymedia
 
-use std::rc::Rc;
+use std::{
+    cell::{Cell, RefCell},
+    ptr,
+    rc::{Rc, Weak},
+    time::Duration,
+};
 
 use derive_more::AsRef;
+use futures::{future, FutureExt as _};
 use medea_client_api_proto as proto;
+use tracerr::Traced;
 
 use crate::{
-    media::{MediaKind, MediaSourceKind},
+    media::{
+        ContentHint, DisplayCaptureOptions, DisplaySurface, MediaKind,
+        MediaSourceKind, TrackConstraints, VideoRotation, VideoSource,
+        VideoTransform,
+    },
     platform,
+    utils::TaskHandle,
+};
+
+use super::{
+    CodecInfo, DeviceMismatch, MediaStreamTrackState, RequestKeyFrameError,
+    SilenceDetectionError, TrackTransformError,
 };
 
-use super::MediaStreamTrackState;
+/// Interval between audio level checks of the silence detection.
+const SILENCE_CHECK_INTERVAL: Duration = Duration::from_millis(100);
 
 /// Wrapper around a [`platform::MediaStreamTrack`] received from a
 /// [getUserMedia()][1]/[getDisplayMedia()][2] request.
 ///
 /// Underlying [`platform::MediaStreamTrack`] is stopped on this [`Track`]'s
-/// [`Drop`].
+/// [`Drop`], unless it's provided by an application (see [`Track::custom()`]).
 ///
 /// [1]: https://w3.org/TR/mediacapture-streams#dom-mediadevices-getusermedia
 /// [2]: https://w3.org/TR/screen-capture/#dom-mediadevices-getdisplaymedia
@@ -38,8 +56,49 @@ pub struct Track {
     /// Parent will be [`None`] if this [`Track`] wasn't forked from another
     /// [`Track`].
     ///
-    /// This field is used only for holding strong reference to the parent.
-    _parent: Option<Rc<Self>>,
+    /// Holds a strong reference to the parent, and propagates media exchange
+    /// state changes to it.
+    parent: Option<Rc<Self>>,
+
+    /// [`Track`]s forked from this [`Track`].
+    forks: RefCell<Vec<Weak<Self>>>,
+
+    /// [`platform::Transceiver`] sending this [`Track`], if any.
+    transceiver: RefCell<Option<platform::Transceiver>>,
+
+    /// [`TrackConstraints`] this [`Track`] was captured with, if known.
+    capture_constraints: RefCell<Option<TrackConstraints>>,
+
+    /// Indicator whether media exchange of this [`Track`] is enabled in a
+    /// `Room`.
+    media_exchange_enabled: Cell<bool>,
+
+    /// Callback to be invoked when media exchange of this [`Track`] is
+    /// enabled in a `Room`.
+    on_enabled: platform::Callback<()>,
+
+    /// Callback to be invoked when media exchange of this [`Track`] is
+    /// disabled in a `Room`.
+    on_disabled: platform::Callback<()>,
+
+    /// Indicator whether the underlying [`platform::MediaStreamTrack`] is
+    /// provided by an application, so is never stopped by this [`Track`].
+    is_custom: bool,
+
+    /// [`VideoTransform`] applied to this [`Track`] and its forks.
+    transform: Cell<VideoTransform>,
+
+    /// [`platform::VideoTransformer`] producing the
+    /// [`platform::MediaStreamTrack`] sent instead of this [`Track`], if any.
+    transformer: RefCell<Option<platform::VideoTransformer>>,
+
+    /// [`DeviceMismatch`] of this [`Track`] being captured from another
+    /// device than the requested one, if any.
+    device_mismatch: RefCell<Option<DeviceMismatch>>,
+
+    /// [`CodecInfo`] of the encoder last reported for this [`Track`], if
+    /// any.
+    encoder_info: RefCell<Option<CodecInfo>>,
 }
 
 impl Track {
@@ -49,14 +108,63 @@ impl Track {
     pub const fn new(
         track: platform::MediaStreamTrack,
         source_kind: proto::MediaSourceKind,
+    ) -> Self {
+        Self::new_inner(track, source_kind, false)
+    }
+
+    /// Builds a new [`Track`] from the [`platform::MediaStreamTrack`] provided
+    /// by an application instead of capturing one.
+    ///
+    /// The provided [`platform::MediaStreamTrack`] stays owned by the
+    /// application, so it's never stopped by this [`Track`], while the
+    /// [`Track::fork()`]s of it are.
+    #[must_use]
+    pub const fn custom(
+        track: platform::MediaStreamTrack,
+        source_kind: proto::MediaSourceKind,
+    ) -> Self {
+        Self::new_inner(track, source_kind, true)
+    }
+
+    /// Builds a new root [`Track`] from the provided
+    /// [`platform::MediaStreamTrack`].
+    const fn new_inner(
+        track: platform::MediaStreamTrack,
+        source_kind: proto::MediaSourceKind,
+        is_custom: bool,
     ) -> Self {
         Self {
             track,
             source_kind,
-            _parent: None,
+            parent: None,
+            forks: RefCell::new(Vec::new()),
+            transceiver: RefCell::new(None),
+            capture_constraints: RefCell::new(None),
+            media_exchange_enabled: Cell::new(false),
+            on_enabled: platform::Callback::default(),
+            on_disabled: platform::Callback::default(),
+            is_custom,
+            transform: Cell::new(VideoTransform {
+                rotation: VideoRotation::Deg0,
+                mirror: false,
+            }),
+            transformer: RefCell::new(None),
+            device_mismatch: RefCell::new(None),
+            encoder_info: RefCell::new(None),
         }
     }
 
+    /// Indicates whether this [`Track`] is the provided one, or is forked from
+    /// it.
+    #[must_use]
+    pub fn is_derived_from(&self, other: &Self) -> bool {
+        ptr::eq(self, other)
+            || self
+                .parent
+                .as_ref()
+                .map_or(false, |p| p.is_derived_from(other))
+    }
+
     /// Returns the underlying [`platform::MediaStreamTrack`] of this [`Track`].
     #[must_use]
     pub const fn platform_track(&self) -> &platform::MediaStreamTrack {
@@ -87,6 +195,70 @@ impl Track {
         self.source_kind
     }
 
+    /// Returns ID of the device this [`Track`] is actually captured from.
+    #[must_use]
+    pub fn device_id(&self) -> String {
+        self.track.device_id()
+    }
+
+    /// Returns the [`DeviceMismatch`] of this [`Track`] being captured from
+    /// another device than the requested one, if any.
+    #[must_use]
+    pub fn device_mismatch(&self) -> Option<DeviceMismatch> {
+        self.device_mismatch.borrow().clone()
+    }
+
+    /// Marks this [`Track`] as captured from another device than the
+    /// requested one.
+    pub fn set_device_mismatch(&self, mismatch: DeviceMismatch) {
+        drop(self.device_mismatch.replace(Some(mismatch)));
+    }
+
+    /// Returns [`CodecInfo`] of the encoder last reported for this [`Track`],
+    /// if any.
+    #[must_use]
+    pub fn encoder_info(&self) -> Option<CodecInfo> {
+        self.encoder_info.borrow().clone()
+    }
+
+    /// Sets [`CodecInfo`] of the encoder reported for this [`Track`] and all
+    /// its parents.
+    pub fn set_encoder_info(&self, info: CodecInfo) {
+        if let Some(parent) = &self.parent {
+            parent.set_encoder_info(info.clone());
+        }
+        drop(self.encoder_info.replace(Some(info)));
+    }
+
+    /// Returns a [`DisplaySurface`] captured by this [`Track`], or [`None`] if
+    /// it isn't a [`proto::MediaSourceKind::Display`] one.
+    #[must_use]
+    pub fn display_surface(&self) -> Option<DisplaySurface> {
+        (self.source_kind == proto::MediaSourceKind::Display).then(|| {
+            self.track
+                .display_surface()
+                .unwrap_or(DisplaySurface::Unknown)
+        })
+    }
+
+    /// Returns a [`ContentHint`] of this [`Track`] accepted by the platform,
+    /// if any.
+    #[must_use]
+    pub fn content_hint(&self) -> Option<ContentHint> {
+        self.track.content_hint()
+    }
+
+    /// Sets the provided [`ContentHint`] to this [`Track`] and all its forks.
+    ///
+    /// The platform ignores the [`ContentHint`]s not applicable to the
+    /// [`MediaKind`] of this [`Track`].
+    pub fn set_content_hint(&self, hint: ContentHint) {
+        self.track.set_content_hint(hint);
+        for fork in self.forks.borrow().iter().filter_map(Weak::upgrade) {
+            fork.set_content_hint(hint);
+        }
+    }
+
     /// Returns this [`Track`]'s kind (audio/video).
     #[allow(clippy::missing_const_for_fn)] // not all platforms allow this
     #[must_use]
@@ -99,6 +271,17 @@ impl Track {
         self.track.on_ended(Some(move || callback.call0()));
     }
 
+    /// Sets a Rust callback to invoke when this [`Track`] is ended by its
+    /// source, replacing the one set by [`Track::on_ended()`].
+    ///
+    /// Is not invoked when this [`Track`] is stopped by the library itself.
+    pub fn on_source_ended<F>(&self, f: F)
+    where
+        F: 'static + FnOnce(),
+    {
+        self.track.on_ended(Some(f));
+    }
+
     /// Returns a [`MediaStreamTrackState::Live`] if this [`Track`] is active,
     /// or a [`MediaStreamTrackState::Ended`] if it has ended.
     pub async fn state(&self) -> MediaStreamTrackState {
@@ -113,27 +296,277 @@ impl Track {
     /// Forked [`Track`] will hold a strong reference to this [`Track`].
     ///
     /// [1]: https://w3.org/TR/mediacapture-streams#dom-mediastreamtrack-clone
-    pub async fn fork(self: &Rc<Self>) -> Self {
+    ///
+    /// Forked [`Track`] inherits the [`VideoTransform`] of this [`Track`].
+    pub async fn fork(self: &Rc<Self>) -> Rc<Self> {
         let parent = Rc::clone(self);
         let track = self.track.fork().await;
-        Self {
+        let transform = self.transform.get();
+        let fork = Rc::new(Self {
             track,
             source_kind: self.source_kind,
-            _parent: Some(parent),
+            parent: Some(parent),
+            forks: RefCell::default(),
+            transceiver: RefCell::default(),
+            capture_constraints: RefCell::new(self.capture_constraints()),
+            media_exchange_enabled: Cell::new(false),
+            on_enabled: platform::Callback::default(),
+            on_disabled: platform::Callback::default(),
+            is_custom: false,
+            transform: Cell::new(transform),
+            transformer: RefCell::new(None),
+            device_mismatch: RefCell::new(self.device_mismatch()),
+            encoder_info: RefCell::new(None),
+        });
+        match fork.build_transformer(transform) {
+            Ok(transformer) => drop(fork.transformer.replace(transformer)),
+            Err(e) => log::error!("Failed to transform forked track: {e}"),
+        }
+        let mut forks = self.forks.borrow_mut();
+        forks.retain(|f| f.strong_count() > 0);
+        forks.push(Rc::downgrade(&fork));
+        fork
+    }
+
+    /// Calls the provided function with the [`platform::MediaStreamTrack`] to
+    /// be sent instead of this [`Track`], being the transformed one, if any
+    /// [`VideoTransform`] is applied.
+    pub fn with_sent_track<R>(
+        &self,
+        f: impl FnOnce(&platform::MediaStreamTrack) -> R,
+    ) -> R {
+        match self.transformer.borrow().as_ref() {
+            Some(transformer) => f(transformer.output()),
+            None => f(&self.track),
+        }
+    }
+
+    /// Applies the provided [`VideoTransform`] to this [`Track`] and all its
+    /// forks, replacing the [`platform::MediaStreamTrack`]s sent by their
+    /// [`platform::Transceiver`]s with the transformed ones.
+    ///
+    /// An identity [`VideoTransform`] restores the original
+    /// [`platform::MediaStreamTrack`]s being sent.
+    ///
+    /// # Errors
+    ///
+    /// With a [`TrackTransformError::NotSupported`] if this [`Track`] isn't a
+    /// video one, or the platform doesn't allow processing tracks.
+    ///
+    /// With a [`TrackTransformError::Failed`] if failed to build a processing
+    /// pipeline, or to replace a sent [`platform::MediaStreamTrack`].
+    pub async fn set_transform(
+        self: &Rc<Self>,
+        transform: VideoTransform,
+    ) -> Result<(), Traced<TrackTransformError>> {
+        if self.kind() != MediaKind::Video {
+            return Err(tracerr::new!(TrackTransformError::NotSupported(
+                "only video tracks can be transformed",
+            )));
+        }
+        if !platform::supports_track_processing() {
+            return Err(tracerr::new!(TrackTransformError::NotSupported(
+                "platform doesn't allow processing tracks",
+            )));
+        }
+
+        let mut tracks = vec![Rc::clone(self)];
+        let mut i = 0;
+        while let Some(track) = tracks.get(i).cloned() {
+            tracks
+                .extend(track.forks.borrow().iter().filter_map(Weak::upgrade));
+            i += 1;
+        }
+        for track in tracks {
+            track.transform.set(transform);
+            let transceiver = track.transceiver.borrow().clone();
+            let Some(transceiver) = transceiver else {
+                continue;
+            };
+            let prev = track
+                .transformer
+                .replace(track.build_transformer(transform)?);
+            transceiver
+                .set_send_track(Some(&track))
+                .await
+                .map_err(TrackTransformError::Failed)
+                .map_err(tracerr::wrap!())?;
+            // Stops the previously sent transformed track, if any.
+            drop(prev);
+        }
+        Ok(())
+    }
+
+    /// Builds a [`platform::VideoTransformer`] of this [`Track`] applying the
+    /// provided [`VideoTransform`], or returns [`None`] if it's an identity
+    /// one.
+    ///
+    /// # Errors
+    ///
+    /// With a [`TrackTransformError::NotSupported`] if the platform doesn't
+    /// allow processing tracks.
+    ///
+    /// With a [`TrackTransformError::Failed`] if failed to build a processing
+    /// pipeline.
+    fn build_transformer(
+        &self,
+        transform: VideoTransform,
+    ) -> Result<Option<platform::VideoTransformer>, Traced<TrackTransformError>>
+    {
+        if transform.is_identity() {
+            return Ok(None);
+        }
+        platform::VideoTransformer::new(
+            &self.track,
+            self.source_kind.into(),
+            transform,
+        )
+        .ok_or_else(|| {
+            tracerr::new!(TrackTransformError::NotSupported(
+                "platform doesn't allow processing tracks",
+            ))
+        })?
+        .map(Some)
+        .map_err(TrackTransformError::Failed)
+        .map_err(tracerr::wrap!())
+    }
+
+    /// Sets the [`platform::Transceiver`] sending this [`Track`].
+    pub fn set_transceiver(&self, transceiver: Option<platform::Transceiver>) {
+        drop(self.transceiver.replace(transceiver));
+    }
+
+    /// Indicates whether this [`Track`] or any of its forks is being sent by
+    /// some [`platform::Transceiver`].
+    #[must_use]
+    pub fn is_published(&self) -> bool {
+        !self.transceivers().is_empty()
+    }
+
+    /// Marks media exchange of this [`Track`] and all its parents as enabled
+    /// or disabled in a `Room`, invoking the `on_enabled` or `on_disabled`
+    /// callback of the ones it has changed for.
+    pub fn set_media_exchange_enabled(&self, enabled: bool) {
+        if self.media_exchange_enabled.replace(enabled) != enabled {
+            if enabled {
+                self.on_enabled.call0();
+            } else {
+                self.on_disabled.call0();
+            }
+        }
+        if let Some(parent) = &self.parent {
+            parent.set_media_exchange_enabled(enabled);
         }
     }
 
+    /// Returns [`TrackConstraints`] this [`Track`] was captured with, if known.
+    #[must_use]
+    pub fn capture_constraints(&self) -> Option<TrackConstraints> {
+        self.capture_constraints.borrow().clone()
+    }
+
+    /// Returns [`DisplayCaptureOptions`] of the [getDisplayMedia()][1]
+    /// request this [`Track`] was captured with, or [`None`] if it isn't a
+    /// [`proto::MediaSourceKind::Display`] one.
+    ///
+    /// [1]: https://w3.org/TR/screen-capture/#dom-mediadevices-getdisplaymedia
+    #[must_use]
+    pub fn display_capture_options(&self) -> Option<DisplayCaptureOptions> {
+        if self.source_kind != proto::MediaSourceKind::Display {
+            return None;
+        }
+        Some(match self.capture_constraints.borrow().as_ref() {
+            Some(TrackConstraints::Video(VideoSource::Display(caps))) => {
+                caps.capture_options
+            }
+            _ => DisplayCaptureOptions::default(),
+        })
+    }
+
+    /// Sets [`TrackConstraints`] this [`Track`] was captured with.
+    pub fn set_capture_constraints(&self, caps: TrackConstraints) {
+        drop(self.capture_constraints.replace(Some(caps)));
+    }
+
+    /// Forces all the [`platform::Transceiver`]s sending this [`Track`] or its
+    /// forks to encode a new keyframe.
+    ///
+    /// # Errors
+    ///
+    /// With a [`RequestKeyFrameError::NotSupported`] if this [`Track`] isn't a
+    /// video one, or the platform doesn't allow requesting keyframes.
+    ///
+    /// With a [`RequestKeyFrameError::Failed`] if a keyframe request to some
+    /// [`platform::Transceiver`] fails.
+    pub async fn request_keyframe(
+        &self,
+    ) -> Result<(), Traced<RequestKeyFrameError>> {
+        if self.kind() != MediaKind::Video {
+            return Err(tracerr::new!(RequestKeyFrameError::NotSupported(
+                "keyframes are requested for video tracks only",
+            )));
+        }
+
+        let transceivers = self.transceivers();
+        let requests = future::join_all(
+            transceivers
+                .iter()
+                .map(platform::Transceiver::request_keyframe),
+        )
+        .await;
+        for res in requests {
+            match res {
+                Some(Ok(())) => (),
+                Some(Err(e)) => {
+                    return Err(tracerr::new!(RequestKeyFrameError::Failed(e)));
+                }
+                None => {
+                    return Err(tracerr::new!(
+                        RequestKeyFrameError::NotSupported(
+                            "platform doesn't allow requesting keyframes",
+                        )
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns all the [`platform::Transceiver`]s sending this [`Track`] or its
+    /// forks.
+    fn transceivers(&self) -> Vec<platform::Transceiver> {
+        let forks: Vec<_> = self
+            .forks
+            .borrow()
+            .iter()
+            .filter_map(Weak::upgrade)
+            .collect();
+        self.transceiver
+            .borrow()
+            .iter()
+            .cloned()
+            .chain(forks.iter().flat_map(|f| f.transceivers()))
+            .collect()
+    }
+
     /// [Stops][1] this [`Track`].
     ///
+    /// Does nothing if this [`Track`] is provided by an application via
+    /// [`Track::custom()`].
+    ///
     /// [1]: https://w3.org/TR/mediacapture-streams#dom-mediastreamtrack-stop
     pub async fn stop(&self) {
-        self.track.stop().await;
+        if !self.is_custom {
+            self.track.stop().await;
+        }
     }
 }
 
 impl Drop for Track {
     fn drop(&mut self) {
-        platform::spawn(Box::pin(self.track.stop()));
+        if !self.is_custom {
+            platform::spawn(Box::pin(self.track.stop()));
+        }
     }
 }
 
@@ -143,38 +576,51 @@ impl Drop for Track {
 /// [1]: https://w3.org/TR/mediacapture-streams#dom-mediadevices-getusermedia
 /// [2]: https://w3.org/TR/screen-capture/#dom-mediadevices-getdisplaymedia
 #[derive(Debug, Clone)]
-pub struct LocalMediaTrack(Rc<Track>);
+pub struct LocalMediaTrack {
+    /// Underlying [`Track`].
+    track: Rc<Track>,
+
+    /// [`TaskHandle`] of the silence detection of this [`LocalMediaTrack`],
+    /// if it's enabled.
+    ///
+    /// Shared between clones, so the silence detection is stopped once all
+    /// of them are dropped.
+    silence_detection: Rc<RefCell<Option<TaskHandle>>>,
+}
 
 impl LocalMediaTrack {
     /// Creates a new [`LocalMediaTrack`] from the provided [`Track`].
     #[must_use]
     pub fn new(track: Rc<Track>) -> Self {
-        Self(track)
+        Self {
+            track,
+            silence_detection: Rc::default(),
+        }
     }
 
     /// Returns the underlying [`platform::MediaStreamTrack`] of this
     /// [`LocalMediaTrack`].
     #[must_use]
     pub fn get_track(&self) -> &platform::MediaStreamTrack {
-        &self.0.track
+        &self.track.track
     }
 
     /// Returns a [`MediaKind::Audio`] if this [`LocalMediaTrack`] represents an
     /// audio track, or a [`MediaKind::Video`] if it represents a video track.
     #[must_use]
     pub fn kind(&self) -> MediaKind {
-        self.0.kind()
+        self.track.kind()
     }
 
     /// Sets a callback to invoke when this [`LocalMediaTrack`] is ended.
     pub fn on_ended(&self, callback: platform::Function<()>) {
-        self.0.on_ended(callback);
+        self.track.on_ended(callback);
     }
 
     /// Returns a [`MediaStreamTrackState::Live`] if this [`LocalMediaTrack`] is
     /// active, or a [`MediaStreamTrackState::Ended`] if it has ended.
     pub async fn state(&self) -> MediaStreamTrackState {
-        self.0.state().await
+        self.track.state().await
     }
 
     /// Returns a [`MediaSourceKind::Device`] if this [`LocalMediaTrack`] is
@@ -185,7 +631,201 @@ impl LocalMediaTrack {
     /// [1]: https://w3.org/TR/screen-capture/#dom-mediadevices-getdisplaymedia
     #[must_use]
     pub fn media_source_kind(&self) -> MediaSourceKind {
-        self.0.media_source_kind().into()
+        self.track.media_source_kind().into()
+    }
+
+    /// Returns a [`DisplaySurface`] captured by this [`LocalMediaTrack`], or
+    /// [`None`] if it isn't a [`MediaSourceKind::Display`] one.
+    #[must_use]
+    pub fn display_surface(&self) -> Option<DisplaySurface> {
+        self.track.display_surface()
+    }
+
+    /// Returns a [`ContentHint`] of this [`LocalMediaTrack`] accepted by the
+    /// platform, if any.
+    #[must_use]
+    pub fn content_hint(&self) -> Option<ContentHint> {
+        self.track.content_hint()
+    }
+
+    /// Sets the provided [`ContentHint`] to this [`LocalMediaTrack`].
+    ///
+    /// The platform ignores the [`ContentHint`]s not applicable to the
+    /// [`MediaKind`] of this [`LocalMediaTrack`], so
+    /// [`LocalMediaTrack::content_hint()`] should be checked afterwards.
+    pub fn set_content_hint(&self, hint: ContentHint) {
+        self.track.set_content_hint(hint);
+    }
+
+    /// Returns [`DisplayCaptureOptions`] this [`LocalMediaTrack`] was
+    /// requested with, or [`None`] if it isn't a [`MediaSourceKind::Display`]
+    /// one.
+    ///
+    /// The options are passed to the platform as is, and ignored by the ones
+    /// not supporting them.
+    #[must_use]
+    pub fn display_capture_options(&self) -> Option<DisplayCaptureOptions> {
+        self.track.display_capture_options()
+    }
+
+    /// Sets a callback to invoke when media exchange of this
+    /// [`LocalMediaTrack`] is enabled in a `Room`.
+    ///
+    /// The callback is invoked before the corresponding `RoomHandle` method
+    /// call resolves.
+    pub fn on_enabled(&self, callback: platform::Function<()>) {
+        self.track.on_enabled.set_func(callback);
+    }
+
+    /// Sets a callback to invoke when media exchange of this
+    /// [`LocalMediaTrack`] is disabled in a `Room`, either via a `RoomHandle`
+    /// or by a media server.
+    ///
+    /// The callback is invoked before the corresponding `RoomHandle` method
+    /// call resolves.
+    pub fn on_disabled(&self, callback: platform::Function<()>) {
+        self.track.on_disabled.set_func(callback);
+    }
+
+    /// Indicates whether this [`LocalMediaTrack`] is being published to some
+    /// `Room`.
+    #[must_use]
+    pub fn is_published(&self) -> bool {
+        self.track.is_published()
+    }
+
+    /// Returns a [`DeviceMismatch`] warning if this [`LocalMediaTrack`] is
+    /// captured from another device than the requested one.
+    #[must_use]
+    pub fn device_mismatch(&self) -> Option<DeviceMismatch> {
+        self.track.device_mismatch()
+    }
+
+    /// Returns [`CodecInfo`] of the encoder last reported for this
+    /// [`LocalMediaTrack`] being published, if any.
+    ///
+    /// Is updated with the [`RtcStats`] whenever the implementation changes.
+    ///
+    /// [`RtcStats`]: platform::RtcStats
+    #[must_use]
+    pub fn encoder_info(&self) -> Option<CodecInfo> {
+        self.track.encoder_info()
+    }
+
+    /// Forces this [`LocalMediaTrack`] to be encoded as a new keyframe by all
+    /// the senders it's being published with.
+    ///
+    /// # Errors
+    ///
+    /// With a [`RequestKeyFrameError::NotSupported`] if this
+    /// [`LocalMediaTrack`] isn't a video one, or the platform doesn't allow
+    /// requesting keyframes.
+    ///
+    /// With a [`RequestKeyFrameError::Failed`] if a keyframe request fails.
+    pub async fn request_keyframe(
+        &self,
+    ) -> Result<(), Traced<RequestKeyFrameError>> {
+        self.track.request_keyframe().await
+    }
+
+    /// Rotates and/or mirrors this [`LocalMediaTrack`] as it's being sent by
+    /// all the senders it's being published with, replacing the previously
+    /// applied [`VideoTransform`].
+    ///
+    /// Local preview of this [`LocalMediaTrack`] stays untransformed.
+    ///
+    /// # Errors
+    ///
+    /// With a [`TrackTransformError::NotSupported`] if this
+    /// [`LocalMediaTrack`] isn't a video one, or the platform doesn't allow
+    /// processing tracks.
+    ///
+    /// With a [`TrackTransformError::Failed`] if failed to apply the provided
+    /// [`VideoTransform`].
+    pub async fn set_transform(
+        &self,
+        transform: VideoTransform,
+    ) -> Result<(), Traced<TrackTransformError>> {
+        self.track.set_transform(transform).await
+    }
+
+    /// Enables silence detection of this [`LocalMediaTrack`], replacing the
+    /// previously enabled one, if any.
+    ///
+    /// The provided `callback` is invoked with `true` once the captured audio
+    /// level stays below the provided `threshold_db` (in [dBFS][1]) for the
+    /// provided `duration`, and with `false` once it rises above the
+    /// `threshold_db` again.
+    ///
+    /// Silence detection is stopped by
+    /// [`LocalMediaTrack::disable_silence_detection()`] or once this
+    /// [`LocalMediaTrack`] is dropped.
+    ///
+    /// # Errors
+    ///
+    /// With a [`SilenceDetectionError::NotSupported`] if this
+    /// [`LocalMediaTrack`] isn't an audio one, or the platform doesn't allow
+    /// metering audio level.
+    ///
+    /// With a [`SilenceDetectionError::Failed`] if failed to start metering
+    /// audio level.
+    ///
+    /// [1]: https://en.wikipedia.org/wiki/DBFS
+    pub fn enable_silence_detection(
+        &self,
+        threshold_db: f64,
+        duration: Duration,
+        callback: platform::Function<bool>,
+    ) -> Result<(), Traced<SilenceDetectionError>> {
+        use SilenceDetectionError as E;
+
+        if self.kind() != MediaKind::Audio {
+            return Err(tracerr::new!(E::NotSupported(
+                "silence is detected for audio tracks only",
+            )));
+        }
+        let meter = platform::AudioLevelMeter::new(self.get_track())
+            .ok_or_else(|| {
+                tracerr::new!(E::NotSupported(
+                    "platform doesn't allow metering audio level",
+                ))
+            })?
+            .map_err(E::Failed)
+            .map_err(tracerr::wrap!())?;
+
+        let (fut, abort) = future::abortable(async move {
+            let mut silent_since = None;
+            let mut is_silent = false;
+            loop {
+                platform::delay_for(SILENCE_CHECK_INTERVAL).await;
+                if meter.level_db() < threshold_db {
+                    let since = *silent_since.get_or_insert_with(platform::now);
+                    let elapsed = platform::now().saturating_sub(since);
+                    if !is_silent && elapsed >= duration {
+                        is_silent = true;
+                        callback.call1(true);
+                    }
+                } else {
+                    silent_since = None;
+                    if is_silent {
+                        is_silent = false;
+                        callback.call1(false);
+                    }
+                }
+            }
+        });
+        platform::spawn(fut.map(drop));
+        drop(
+            self.silence_detection
+                .replace(Some(TaskHandle::from(abort))),
+        );
+
+        Ok(())
+    }
+
+    /// Disables silence detection of this [`LocalMediaTrack`], if any.
+    pub fn disable_silence_detection(&self) {
+        drop(self.silence_detection.take());
     }
 
     /// [Stops][1] this [`LocalMediaTrack`] if this is the last wrapper for the
@@ -193,7 +833,7 @@ impl LocalMediaTrack {
     ///
     /// [1]: https://w3.org/TR/mediacapture-streams#dom-mediastreamtrack-stop
     pub async fn maybe_stop(mut self) {
-        if let Some(track) = Rc::get_mut(&mut self.0) {
+        if let Some(track) = Rc::get_mut(&mut self.track) {
             track.stop().await;
         }
     }
@@ -5,8 +5,11 @@
 pub mod local;
 pub mod remote;
 
+use derive_more::Display;
 use medea_client_api_proto as proto;
 
+use crate::{platform, utils::Caused};
+
 /// Liveness state of a [MediaStreamTrack][1] .
 ///
 /// [1]: crate::platform::MediaStreamTrack
@@ -24,7 +27,7 @@ pub enum MediaStreamTrackState {
 }
 
 /// Media source type.
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 #[repr(u8)]
 pub enum MediaSourceKind {
     /// Media is sourced from some media device (webcam or microphone).
@@ -51,3 +54,324 @@ impl From<proto::MediaSourceKind> for MediaSourceKind {
         }
     }
 }
+
+/// Type of a display surface captured by a [`MediaSourceKind::Display`]
+/// track.
+///
+/// Representation of a [DisplayCaptureSurfaceType][1].
+///
+/// [1]: https://w3.org/TR/screen-capture#dom-displaycapturesurfacetype
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u8)]
+pub enum DisplaySurface {
+    /// Whole monitor is captured.
+    Monitor = 0,
+
+    /// Single application window is captured.
+    Window = 1,
+
+    /// Single browser tab is captured.
+    Browser = 2,
+
+    /// Captured surface type is unknown.
+    Unknown = 3,
+}
+
+/// Hint of a kind of the content carried by a track, letting a browser
+/// optimize its processing and encoding.
+///
+/// Representation of a [contentHint][1] value.
+///
+/// [1]: https://w3.org/TR/mst-content-hint
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u8)]
+pub enum ContentHint {
+    /// Audio track carries speech.
+    Speech = 0,
+
+    /// Audio track carries speech to be processed by a machine.
+    SpeechRecognition = 1,
+
+    /// Audio track carries music.
+    Music = 2,
+
+    /// Video track carries motion, so its smoothness is preferred over its
+    /// resolution.
+    Motion = 3,
+
+    /// Video track carries details, so its resolution is preferred over its
+    /// smoothness.
+    Detail = 4,
+
+    /// Video track carries text, so its resolution is critical.
+    Text = 5,
+}
+
+impl ContentHint {
+    /// Returns a [contentHint][1] value of this [`ContentHint`].
+    ///
+    /// [1]: https://w3.org/TR/mst-content-hint
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Speech => "speech",
+            Self::SpeechRecognition => "speech-recognition",
+            Self::Music => "music",
+            Self::Motion => "motion",
+            Self::Detail => "detail",
+            Self::Text => "text",
+        }
+    }
+
+    /// Parses a [`ContentHint`] out of the provided [contentHint][1] value.
+    ///
+    /// [1]: https://w3.org/TR/mst-content-hint
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        Some(match value {
+            "speech" => Self::Speech,
+            "speech-recognition" => Self::SpeechRecognition,
+            "music" => Self::Music,
+            "motion" => Self::Motion,
+            "detail" => Self::Detail,
+            "text" => Self::Text,
+            _ => return None,
+        })
+    }
+}
+
+impl From<DisplaySurface> for proto::DisplaySurface {
+    fn from(val: DisplaySurface) -> Self {
+        match val {
+            DisplaySurface::Monitor => Self::Monitor,
+            DisplaySurface::Window => Self::Window,
+            DisplaySurface::Browser => Self::Browser,
+            DisplaySurface::Unknown => Self::Unknown,
+        }
+    }
+}
+
+impl From<proto::DisplaySurface> for DisplaySurface {
+    fn from(val: proto::DisplaySurface) -> Self {
+        match val {
+            proto::DisplaySurface::Monitor => Self::Monitor,
+            proto::DisplaySurface::Window => Self::Window,
+            proto::DisplaySurface::Browser => Self::Browser,
+            proto::DisplaySurface::Unknown => Self::Unknown,
+        }
+    }
+}
+
+/// Errors occurring when requesting a keyframe of a [`local::Track`] or a
+/// [`remote::Track`].
+#[derive(Caused, Clone, Debug, Display)]
+#[cause(error = platform::Error)]
+pub enum RequestKeyFrameError {
+    /// Requesting a keyframe isn't supported for the track.
+    #[display(fmt = "Requesting a keyframe is not supported: {}", _0)]
+    NotSupported(&'static str),
+
+    /// [`platform::Error`] occurred while requesting a keyframe.
+    #[display(fmt = "Failed to request a keyframe: {}", _0)]
+    Failed(platform::Error),
+}
+
+/// Errors occurring when setting a jitter buffer target of a
+/// [`remote::Track`].
+#[derive(Caused, Clone, Debug, Display)]
+#[cause(error = platform::Error)]
+pub enum JitterBufferTargetError {
+    /// Setting a jitter buffer target isn't supported for the track.
+    #[display(fmt = "Setting a jitter buffer target is not supported: {}", _0)]
+    NotSupported(&'static str),
+
+    /// [`platform::Error`] occurred while setting a jitter buffer target.
+    #[display(fmt = "Failed to set a jitter buffer target: {}", _0)]
+    Failed(platform::Error),
+}
+
+/// Errors occurring when enabling silence detection of a
+/// [`local::LocalMediaTrack`].
+#[derive(Caused, Clone, Debug, Display)]
+#[cause(error = platform::Error)]
+pub enum SilenceDetectionError {
+    /// Silence detection isn't supported for the track.
+    #[display(fmt = "Silence detection is not supported: {}", _0)]
+    NotSupported(&'static str),
+
+    /// [`platform::Error`] occurred while building an audio level meter.
+    #[display(fmt = "Failed to start silence detection: {}", _0)]
+    Failed(platform::Error),
+}
+
+/// Errors occurring when transforming a video of a [`local::Track`].
+#[derive(Caused, Clone, Debug, Display)]
+#[cause(error = platform::Error)]
+pub enum TrackTransformError {
+    /// Transforming isn't supported for the track.
+    #[display(fmt = "Transforming a track is not supported: {}", _0)]
+    NotSupported(&'static str),
+
+    /// [`platform::Error`] occurred while building a processing pipeline or
+    /// replacing a sent track.
+    #[display(fmt = "Failed to transform a track: {}", _0)]
+    Failed(platform::Error),
+}
+
+/// Non-fatal warning about a [`local::Track`] being captured from another
+/// device than the requested one.
+///
+/// Some platforms capture the default device instead of the one requested via
+/// an ideal [deviceId][1] constraint.
+///
+/// [1]: https://w3.org/TR/mediacapture-streams#def-constraint-deviceId
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DeviceMismatch {
+    /// ID of the requested device.
+    pub requested: String,
+
+    /// ID of the device the [`local::Track`] is actually captured from.
+    pub actual: String,
+}
+
+/// Implementation of an encoder or a decoder used for a track, as reported by
+/// a browser in the [`RtcStats`].
+///
+/// [`RtcStats`]: crate::platform::RtcStats
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CodecInfo {
+    /// Name of the implementation (e.g. `libvpx` or `ExternalEncoder`).
+    pub implementation: String,
+
+    /// Indicator whether the implementation is considered power efficient by
+    /// a browser, if reported.
+    pub power_efficient: Option<bool>,
+}
+
+impl CodecInfo {
+    /// Indicates whether this [`CodecInfo`] describes a hardware accelerated
+    /// implementation, if known.
+    ///
+    /// Browsers consider only the hardware implementations power efficient.
+    #[must_use]
+    pub const fn is_hardware(&self) -> Option<bool> {
+        self.power_efficient
+    }
+}
+
+/// Clockwise rotation of a video.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum VideoRotation {
+    /// Video is not rotated.
+    #[default]
+    Deg0,
+
+    /// Video is rotated by 90 degrees.
+    Deg90,
+
+    /// Video is rotated by 180 degrees.
+    Deg180,
+
+    /// Video is rotated by 270 degrees.
+    Deg270,
+}
+
+impl VideoRotation {
+    /// Parses a [`VideoRotation`] out of the provided clockwise angle in
+    /// degrees.
+    ///
+    /// Returns [`None`] if the angle isn't a multiple of `90`.
+    #[must_use]
+    pub const fn from_degrees(degrees: u16) -> Option<Self> {
+        Some(match degrees % 360 {
+            0 => Self::Deg0,
+            90 => Self::Deg90,
+            180 => Self::Deg180,
+            270 => Self::Deg270,
+            _ => return None,
+        })
+    }
+
+    /// Returns a clockwise angle of this [`VideoRotation`] in degrees.
+    #[must_use]
+    pub const fn degrees(self) -> u16 {
+        match self {
+            Self::Deg0 => 0,
+            Self::Deg90 => 90,
+            Self::Deg180 => 180,
+            Self::Deg270 => 270,
+        }
+    }
+}
+
+/// Transformation applied to a video before sending it.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct VideoTransform {
+    /// [`VideoRotation`] applied to the video.
+    pub rotation: VideoRotation,
+
+    /// Indicator whether the video is mirrored horizontally before being
+    /// rotated.
+    pub mirror: bool,
+}
+
+impl VideoTransform {
+    /// Indicates whether this [`VideoTransform`] leaves a video intact.
+    #[must_use]
+    pub fn is_identity(self) -> bool {
+        self == Self::default()
+    }
+
+    /// Returns `(width, height)` of a video of the provided size once
+    /// transformed with this [`VideoTransform`].
+    #[must_use]
+    pub const fn output_size(self, width: u32, height: u32) -> (u32, u32) {
+        match self.rotation {
+            VideoRotation::Deg0 | VideoRotation::Deg180 => (width, height),
+            VideoRotation::Deg90 | VideoRotation::Deg270 => (height, width),
+        }
+    }
+}
+
+#[cfg(test)]
+mod spec {
+    use super::{VideoRotation, VideoTransform};
+
+    #[test]
+    fn parses_rotation_degrees() {
+        assert_eq!(VideoRotation::from_degrees(0), Some(VideoRotation::Deg0));
+        assert_eq!(
+            VideoRotation::from_degrees(270),
+            Some(VideoRotation::Deg270),
+        );
+        assert_eq!(
+            VideoRotation::from_degrees(450),
+            Some(VideoRotation::Deg90),
+        );
+        assert_eq!(VideoRotation::from_degrees(45), None);
+    }
+
+    #[test]
+    fn swaps_size_of_quarter_rotations_only() {
+        let transform = |rotation, mirror| VideoTransform { rotation, mirror };
+
+        assert_eq!(
+            transform(VideoRotation::Deg0, true).output_size(640, 480),
+            (640, 480),
+        );
+        assert_eq!(
+            transform(VideoRotation::Deg90, false).output_size(640, 480),
+            (480, 640),
+        );
+        assert_eq!(
+            transform(VideoRotation::Deg180, false).output_size(640, 480),
+            (640, 480),
+        );
+        assert_eq!(
+            transform(VideoRotation::Deg270, true).output_size(640, 480),
+            (480, 640),
+        );
+        assert!(transform(VideoRotation::Deg0, false).is_identity());
+        assert!(!transform(VideoRotation::Deg0, true).is_identity());
+    }
+}
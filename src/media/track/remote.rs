@@ -1,14 +1,25 @@
 //! Wrapper around a received remote [`platform::MediaStreamTrack`].
 
-use std::{cell::Cell, rc::Rc};
+use std::{
+    cell::{Cell, RefCell},
+    rc::{Rc, Weak},
+    time::Duration,
+};
 
-use futures::StreamExt as _;
+use futures::{future::LocalBoxFuture, FutureExt as _, StreamExt as _};
 use medea_client_api_proto as proto;
 use medea_reactive::ObservableCell;
+use tracerr::Traced;
 
 use crate::{
     api,
-    media::{track::MediaStreamTrackState, MediaKind, MediaSourceKind},
+    media::{
+        track::{
+            CodecInfo, JitterBufferTargetError, MediaStreamTrackState,
+            RequestKeyFrameError,
+        },
+        DisplaySurface, MediaKind, MediaSourceKind,
+    },
     platform,
 };
 
@@ -18,9 +29,26 @@ struct Inner {
     /// Underlying platform-specific [`platform::MediaStreamTrack`].
     track: platform::MediaStreamTrack,
 
+    /// Clone of the underlying [`platform::MediaStreamTrack`] handed out to
+    /// users, if any.
+    ///
+    /// It's stopped along with the underlying [`platform::MediaStreamTrack`].
+    track_clone: RefCell<Option<Rc<platform::MediaStreamTrack>>>,
+
     /// Underlying [`platform::MediaStreamTrack`] source kind.
     media_source_kind: proto::MediaSourceKind,
 
+    /// [`platform::Transceiver`] this [`Track`] is received via, if known.
+    transceiver: RefCell<Option<platform::Transceiver>>,
+
+    /// Jitter buffer target set via the [`Track::set_jitter_buffer_target()`],
+    /// if any.
+    jitter_buffer_target: Cell<Option<Duration>>,
+
+    /// [`DisplaySurface`] captured by the sender of this [`Track`], if it
+    /// has been reported already.
+    display_surface: Cell<Option<DisplaySurface>>,
+
     /// Callback to be invoked when this [`Track`] is muted.
     on_muted: platform::Callback<()>,
 
@@ -30,6 +58,25 @@ struct Inner {
     /// Callback to be invoked when this [`Track`] is stopped.
     on_stopped: platform::Callback<()>,
 
+    /// Callback to be invoked when this [`Track`] stops decoding new frames.
+    on_freeze: platform::Callback<()>,
+
+    /// Callback to be invoked when this [`Track`] resumes decoding new frames
+    /// after being frozen.
+    on_unfreeze: platform::Callback<()>,
+
+    /// Indicator whether this [`Track`] is frozen (stopped decoding new
+    /// frames while being enabled and unmuted).
+    frozen: Cell<bool>,
+
+    /// Period of not decoding new frames this [`Track`] is considered frozen
+    /// after.
+    freeze_timeout: Cell<Duration>,
+
+    /// [`CodecInfo`] of the decoder last reported for this [`Track`], if
+    /// any.
+    decoder_info: RefCell<Option<CodecInfo>>,
+
     /// Callback to be invoked whenever this [`Track`]'s general
     /// [`MediaDirection`] is changed.
     #[allow(unused_qualifications)]
@@ -47,6 +94,18 @@ struct Inner {
     /// [1]: https://w3.org/TR/mediacapture-streams#dom-mediastreamtrack-muted
     /// [2]: https://w3.org/TR/mediacapture-streams#dom-mediastreamtrack
     muted: ObservableCell<bool>,
+
+    /// Indicator whether this [`Track`] has been stopped permanently.
+    stopped: ObservableCell<bool>,
+}
+
+impl Inner {
+    /// Stops the [`Inner::track_clone`], if it has been handed out.
+    fn stop_track_clone(&self) {
+        if let Some(track) = self.track_clone.borrow().as_ref() {
+            platform::spawn(track.stop());
+        }
+    }
 }
 
 /// Wrapper around a received remote [MediaStreamTrack][1].
@@ -55,7 +114,25 @@ struct Inner {
 #[derive(Clone, Debug)]
 pub struct Track(Rc<Inner>);
 
+/// Weak reference to a [`Track`].
+#[derive(Clone, Debug)]
+pub struct WeakTrack(Weak<Inner>);
+
+impl WeakTrack {
+    /// Upgrades this [`WeakTrack`] into a [`Track`], if it's still alive.
+    #[must_use]
+    pub fn upgrade(&self) -> Option<Track> {
+        self.0.upgrade().map(Track)
+    }
+}
+
 impl Track {
+    /// Default period of not decoding new frames a [`Track`] is considered
+    /// frozen after.
+    pub const DEFAULT_FREEZE_TIMEOUT: Duration = Duration::from_secs(2);
+    /// Maximum jitter buffer target allowed by the platforms.
+    pub const MAX_JITTER_BUFFER_TARGET: Duration = Duration::from_secs(4);
+
     /// Creates a new [`Track`] spawning a listener for its [`enabled`][1] and
     /// [`muted`][2] properties changes.
     ///
@@ -74,19 +151,31 @@ impl Track {
         let track = platform::MediaStreamTrack::from(track);
         let track = Self(Rc::new(Inner {
             track,
+            track_clone: RefCell::new(None),
             media_source_kind,
+            transceiver: RefCell::new(None),
+            jitter_buffer_target: Cell::new(None),
+            display_surface: Cell::new(None),
             muted: ObservableCell::new(muted),
             on_media_direction_changed: platform::Callback::default(),
             media_direction: Cell::new(media_direction),
             on_stopped: platform::Callback::default(),
             on_muted: platform::Callback::default(),
             on_unmuted: platform::Callback::default(),
+            on_freeze: platform::Callback::default(),
+            on_unfreeze: platform::Callback::default(),
+            frozen: Cell::new(false),
+            freeze_timeout: Cell::new(Self::DEFAULT_FREEZE_TIMEOUT),
+            decoder_info: RefCell::new(None),
+            stopped: ObservableCell::new(false),
         }));
 
         track.0.track.on_ended({
             let weak_inner = Rc::downgrade(&track.0);
             Some(move || {
                 if let Some(inner) = weak_inner.upgrade() {
+                    inner.stop_track_clone();
+                    inner.stopped.set(true);
                     inner.on_stopped.call0();
                 }
             })
@@ -150,21 +239,190 @@ impl Track {
         self.0.media_source_kind.into()
     }
 
+    /// Returns a [`DisplaySurface`] captured by the sender of this [`Track`],
+    /// or [`None`] if it isn't a [`MediaSourceKind::Display`] one.
+    ///
+    /// [`DisplaySurface::Unknown`] is returned until the sender reports the
+    /// actual [`DisplaySurface`].
+    #[must_use]
+    pub fn display_surface(&self) -> Option<DisplaySurface> {
+        (self.0.media_source_kind == proto::MediaSourceKind::Display).then(
+            || {
+                self.0
+                    .display_surface
+                    .get()
+                    .unwrap_or(DisplaySurface::Unknown)
+            },
+        )
+    }
+
+    /// Sets the [`DisplaySurface`] captured by the sender of this [`Track`].
+    pub fn set_display_surface(&self, surface: Option<DisplaySurface>) {
+        self.0.display_surface.set(surface);
+    }
+
+    /// Requests the sender of this [`Track`] to encode a new keyframe.
+    ///
+    /// # Errors
+    ///
+    /// With a [`RequestKeyFrameError::NotSupported`] always, since neither
+    /// the platforms allow sending a [PLI][1] from a receiver, nor a media
+    /// server provides a command for requesting a keyframe.
+    ///
+    /// [1]: https://tools.ietf.org/html/rfc4585#section-6.3.1
+    pub fn request_keyframe(&self) -> Result<(), Traced<RequestKeyFrameError>> {
+        let reason = if self.kind() == MediaKind::Video {
+            "keyframes cannot be requested from a remote sender"
+        } else {
+            "keyframes are requested for video tracks only"
+        };
+        Err(tracerr::new!(RequestKeyFrameError::NotSupported(reason)))
+    }
+
+    /// Sets the [`platform::Transceiver`] this [`Track`] is received via.
+    pub fn set_transceiver(&self, transceiver: platform::Transceiver) {
+        drop(self.0.transceiver.replace(Some(transceiver)));
+    }
+
+    /// Indicates whether the platform allows setting a jitter buffer target
+    /// of this [`Track`].
+    #[must_use]
+    pub fn is_jitter_buffer_target_supported(&self) -> bool {
+        self.0
+            .transceiver
+            .borrow()
+            .as_ref()
+            .map_or(false, |t| t.is_jitter_buffer_target_supported())
+    }
+
+    /// Sets the target duration of media to be held in the jitter buffer of
+    /// this [`Track`], clamping it to the
+    /// [`Track::MAX_JITTER_BUFFER_TARGET`].
+    ///
+    /// Returns the clamped target being set.
+    ///
+    /// # Errors
+    ///
+    /// With a [`JitterBufferTargetError::NotSupported`] if the platform
+    /// doesn't allow setting a jitter buffer target.
+    ///
+    /// With a [`JitterBufferTargetError::Failed`] if the platform rejects the
+    /// target.
+    pub fn set_jitter_buffer_target(
+        &self,
+        target: Duration,
+    ) -> Result<Duration, Traced<JitterBufferTargetError>> {
+        use JitterBufferTargetError as E;
+
+        let target = target.min(Self::MAX_JITTER_BUFFER_TARGET);
+        let transceiver =
+            self.0.transceiver.borrow().clone().ok_or_else(|| {
+                tracerr::new!(E::NotSupported("track has no transceiver"))
+            })?;
+        match transceiver.set_jitter_buffer_target(target) {
+            Some(Ok(())) => {
+                self.0.jitter_buffer_target.set(Some(target));
+                Ok(target)
+            }
+            Some(Err(e)) => Err(tracerr::new!(E::Failed(e))),
+            None => Err(tracerr::new!(E::NotSupported(
+                "platform supports neither `jitterBufferTarget` nor \
+                 `playoutDelayHint`",
+            ))),
+        }
+    }
+
+    /// Returns the effective jitter buffer target of this [`Track`], as it's
+    /// reported by the platform.
+    ///
+    /// [`None`] if the platform doesn't allow setting a jitter buffer target,
+    /// or no target is set.
+    #[must_use]
+    pub fn jitter_buffer_target(&self) -> Option<Duration> {
+        self.0
+            .transceiver
+            .borrow()
+            .as_ref()
+            .and_then(platform::Transceiver::jitter_buffer_target)
+    }
+
+    /// Returns [`CodecInfo`] of the decoder last reported for this [`Track`],
+    /// if any.
+    #[must_use]
+    pub fn decoder_info(&self) -> Option<CodecInfo> {
+        self.0.decoder_info.borrow().clone()
+    }
+
+    /// Sets [`CodecInfo`] of the decoder reported for this [`Track`].
+    pub fn set_decoder_info(&self, info: CodecInfo) {
+        drop(self.0.decoder_info.replace(Some(info)));
+    }
+
+    /// Returns the jitter buffer target set via the
+    /// [`Track::set_jitter_buffer_target()`], if any.
+    #[must_use]
+    pub fn configured_jitter_buffer_target(&self) -> Option<Duration> {
+        self.0.jitter_buffer_target.get()
+    }
+
     /// Stops this [`Track`] invoking an `on_stopped` callback if it's in a
     /// [`MediaStreamTrackState::Live`] state.
     pub async fn stop(self) {
         if self.0.track.ready_state().await == MediaStreamTrackState::Live {
             self.0.track.stop().await;
+            self.0.stop_track_clone();
+            self.0.stopped.set(true);
             self.0.on_stopped.call0();
+        } else {
+            self.0.stopped.set(true);
         }
     }
 
+    /// Returns a [`Future`] resolving once this [`Track`] is stopped
+    /// permanently, either via the [`Track::stop()`] or by its source.
+    ///
+    /// [`Future`]: std::future::Future
+    pub fn when_stopped(&self) -> LocalBoxFuture<'static, ()> {
+        self.0.stopped.when_eq(true).map(drop).boxed_local()
+    }
+
+    /// Creates a new [`WeakTrack`] reference to this [`Track`].
+    #[must_use]
+    pub fn downgrade(&self) -> WeakTrack {
+        WeakTrack(Rc::downgrade(&self.0))
+    }
+
+    /// Indicates whether the provided [`WeakTrack`] references this
+    /// [`Track`].
+    #[must_use]
+    pub fn is_referenced_by(&self, weak: &WeakTrack) -> bool {
+        Weak::ptr_eq(&weak.0, &Rc::downgrade(&self.0))
+    }
+
     /// Returns the underlying [`platform::MediaStreamTrack`] of this [`Track`].
     #[must_use]
     pub fn get_track(&self) -> &platform::MediaStreamTrack {
         &self.0.track
     }
 
+    /// Returns a clone of the underlying [`platform::MediaStreamTrack`] of
+    /// this [`Track`], which lifetime is independent of this [`Track`].
+    ///
+    /// The same clone is returned on subsequent calls. It's stopped only along
+    /// with the underlying [`platform::MediaStreamTrack`] (being stopped or
+    /// ended), so the `on_stopped` callback indicates its end as well.
+    pub async fn get_track_clone(&self) -> Rc<platform::MediaStreamTrack> {
+        if let Some(track) = self.0.track_clone.borrow().as_ref() {
+            return Rc::clone(track);
+        }
+
+        let track = Rc::new(self.0.track.fork().await);
+        if self.0.track.ready_state().await == MediaStreamTrackState::Ended {
+            track.stop().await;
+        }
+        Rc::clone(self.0.track_clone.borrow_mut().get_or_insert(track))
+    }
+
     /// Indicate whether this [`Track`] is muted.
     #[must_use]
     pub fn muted(&self) -> bool {
@@ -186,6 +444,67 @@ impl Track {
         self.0.on_stopped.set_func(callback);
     }
 
+    /// Sets callback to invoke when this [`Track`] stops decoding new frames
+    /// while being enabled and unmuted.
+    pub fn on_freeze(&self, callback: platform::Function<()>) {
+        self.0.on_freeze.set_func(callback);
+    }
+
+    /// Sets callback to invoke when this [`Track`] resumes decoding new frames
+    /// after being frozen.
+    pub fn on_unfreeze(&self, callback: platform::Function<()>) {
+        self.0.on_unfreeze.set_func(callback);
+    }
+
+    /// Indicates whether this [`Track`] is frozen (stopped decoding new frames
+    /// while being enabled and unmuted).
+    ///
+    /// Always `false` unless `on_freeze` or `on_unfreeze` callback is set.
+    #[must_use]
+    pub fn is_frozen(&self) -> bool {
+        self.0.frozen.get()
+    }
+
+    /// Sets the frozen state of this [`Track`], invoking `on_freeze` or
+    /// `on_unfreeze` callback if it has changed.
+    pub fn set_frozen(&self, frozen: bool) {
+        if self.0.frozen.replace(frozen) == frozen {
+            return;
+        }
+        if frozen {
+            self.0.on_freeze.call0();
+        } else {
+            self.0.on_unfreeze.call0();
+        }
+    }
+
+    /// Returns the period of not decoding new frames this [`Track`] is
+    /// considered frozen after.
+    #[must_use]
+    pub fn freeze_timeout(&self) -> Duration {
+        self.0.freeze_timeout.get()
+    }
+
+    /// Sets the period of not decoding new frames this [`Track`] is considered
+    /// frozen after.
+    pub fn set_freeze_timeout(&self, timeout: Duration) {
+        self.0.freeze_timeout.set(timeout);
+    }
+
+    /// Indicates whether freezes of this [`Track`] should be detected, which
+    /// is so once `on_freeze` or `on_unfreeze` callback is set.
+    #[must_use]
+    pub fn is_freeze_detection_enabled(&self) -> bool {
+        self.0.on_freeze.is_set() || self.0.on_unfreeze.is_set()
+    }
+
+    /// Indicates whether this [`Track`] is expected to receive media, being
+    /// enabled on both sides and unmuted.
+    #[must_use]
+    pub fn is_active(&self) -> bool {
+        self.media_direction() == MediaDirection::SendRecv && !self.muted()
+    }
+
     /// Sets callback to be invoked whenever this [`Track`]'s general
     /// [`MediaDirection`] is changed.
     #[allow(unused_qualifications)]
@@ -0,0 +1,341 @@
+//! Declarative policy of the media states of a [`Room`].
+//!
+//! [`Room`]: crate::room::Room
+
+use crate::{
+    media::{MediaKind, MediaSourceKind},
+    peer::{media_exchange_state, mute_state, MediaState, TrackDirection},
+    room::{LocalMediaSnapshot, RoomSnapshot},
+};
+
+/// Intended state of an outbound media in a [`RoomMediaPolicy`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SendMediaPolicy {
+    /// Media is published and unmuted.
+    Enabled,
+
+    /// Media is published, but muted.
+    Muted,
+
+    /// Media is not published at all.
+    Disabled,
+}
+
+/// Declarative policy of the media states of a [`Room`], applied via the
+/// [`RoomHandle::apply_policy()`].
+///
+/// Media states not specified in the policy are left untouched.
+///
+/// [`Room`]: crate::room::Room
+/// [`RoomHandle::apply_policy()`]: crate::room::RoomHandle::apply_policy
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct RoomMediaPolicy {
+    /// Intended state of the outbound audio.
+    pub send_audio: Option<SendMediaPolicy>,
+
+    /// Intended state of the outbound device video.
+    pub send_device_video: Option<SendMediaPolicy>,
+
+    /// Intended state of the outbound display video.
+    pub send_display_video: Option<SendMediaPolicy>,
+
+    /// Indicator whether receiving of the remote audio is intended.
+    pub recv_audio: Option<bool>,
+
+    /// Indicator whether receiving of the remote video (both device and
+    /// display) is intended.
+    pub recv_video: Option<bool>,
+}
+
+/// Kind of a [`MediaIntent`] change.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MediaChange {
+    /// Media should be muted.
+    Mute,
+
+    /// Media should be unmuted.
+    Unmute,
+
+    /// Media exchange should be enabled.
+    Enable,
+
+    /// Media exchange should be disabled.
+    Disable,
+}
+
+impl From<MediaChange> for MediaState {
+    fn from(change: MediaChange) -> Self {
+        match change {
+            MediaChange::Mute => mute_state::Stable::Muted.into(),
+            MediaChange::Unmute => mute_state::Stable::Unmuted.into(),
+            MediaChange::Enable => media_exchange_state::Stable::Enabled.into(),
+            MediaChange::Disable => {
+                media_exchange_state::Stable::Disabled.into()
+            }
+        }
+    }
+}
+
+/// Single media state change required to apply a [`RoomMediaPolicy`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct MediaIntent {
+    /// [`MediaChange`] to be performed.
+    pub change: MediaChange,
+
+    /// [`MediaKind`] of the changed media.
+    pub kind: MediaKind,
+
+    /// [`TrackDirection`] of the changed media.
+    pub direction: TrackDirection,
+
+    /// [`MediaSourceKind`] of the changed media, if it's not the whole
+    /// [`MediaKind`].
+    pub source_kind: Option<MediaSourceKind>,
+}
+
+impl RoomMediaPolicy {
+    /// Returns the minimal sequence of [`MediaIntent`]s turning the media
+    /// states of the provided [`RoomSnapshot`] into the ones intended by this
+    /// [`RoomMediaPolicy`].
+    ///
+    /// Mutes and disables go first, while enables and unmutes go last, so no
+    /// media is ever published unmuted in between.
+    #[must_use]
+    pub fn intents(&self, current: &RoomSnapshot) -> Vec<MediaIntent> {
+        use MediaChange::{Disable, Enable, Mute, Unmute};
+
+        let mut mutes = Vec::new();
+        let mut disables = Vec::new();
+        let mut enables = Vec::new();
+        let mut unmutes = Vec::new();
+
+        for (policy, kind, source_kind, local) in [
+            (self.send_audio, MediaKind::Audio, None, &current.audio),
+            (
+                self.send_device_video,
+                MediaKind::Video,
+                Some(MediaSourceKind::Device),
+                &current.device_video,
+            ),
+            (
+                self.send_display_video,
+                MediaKind::Video,
+                Some(MediaSourceKind::Display),
+                &current.display_video,
+            ),
+        ] {
+            let Some(policy) = policy else {
+                continue;
+            };
+            let intent = |change| MediaIntent {
+                change,
+                kind,
+                direction: TrackDirection::Send,
+                source_kind,
+            };
+            let LocalMediaSnapshot { enabled, muted, .. } = *local;
+
+            match policy {
+                SendMediaPolicy::Enabled | SendMediaPolicy::Muted
+                    if !enabled =>
+                {
+                    enables.push(intent(Enable));
+                }
+                SendMediaPolicy::Disabled if enabled => {
+                    disables.push(intent(Disable));
+                }
+                _ => {}
+            }
+            match policy {
+                SendMediaPolicy::Enabled if muted => {
+                    unmutes.push(intent(Unmute));
+                }
+                SendMediaPolicy::Muted if !muted => mutes.push(intent(Mute)),
+                _ => {}
+            }
+        }
+
+        let recv = |change, kind| MediaIntent {
+            change,
+            kind,
+            direction: TrackDirection::Recv,
+            source_kind: None,
+        };
+        for (intended, kind, current) in [
+            (self.recv_audio, MediaKind::Audio, [current.recv_audio; 2]),
+            (
+                self.recv_video,
+                MediaKind::Video,
+                [current.recv_device_video, current.recv_display_video],
+            ),
+        ] {
+            match intended {
+                Some(true) if current.contains(&false) => {
+                    enables.push(recv(Enable, kind));
+                }
+                Some(false) if current.contains(&true) => {
+                    disables.push(recv(Disable, kind));
+                }
+                _ => {}
+            }
+        }
+
+        mutes
+            .into_iter()
+            .chain(disables)
+            .chain(enables)
+            .chain(unmutes)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod spec {
+    use crate::{
+        media::{
+            MediaKind,
+            MediaSourceKind::{self, Device, Display},
+        },
+        peer::TrackDirection,
+        room::{LocalMediaSnapshot, RoomSnapshot},
+    };
+
+    use super::{
+        MediaChange::{self, Disable, Enable, Mute, Unmute},
+        MediaIntent, RoomMediaPolicy, SendMediaPolicy,
+    };
+
+    /// Returns a [`LocalMediaSnapshot`] of the provided states.
+    fn local(enabled: bool, muted: bool) -> LocalMediaSnapshot {
+        LocalMediaSnapshot {
+            enabled,
+            muted,
+            device_id: None,
+        }
+    }
+
+    /// Returns a [`RoomSnapshot`] of a fresh `Room` having all the media
+    /// enabled and unmuted.
+    fn fresh() -> RoomSnapshot {
+        RoomSnapshot {
+            room_url: None,
+            member_id: None,
+            audio: local(true, false),
+            device_video: local(true, false),
+            display_video: local(true, false),
+            recv_audio: true,
+            recv_device_video: true,
+            recv_display_video: true,
+        }
+    }
+
+    /// Returns a [`MediaIntent`] of the outbound media.
+    fn send(
+        change: MediaChange,
+        kind: MediaKind,
+        source_kind: Option<MediaSourceKind>,
+    ) -> MediaIntent {
+        MediaIntent {
+            change,
+            kind,
+            direction: TrackDirection::Send,
+            source_kind,
+        }
+    }
+
+    #[test]
+    fn empty_policy_changes_nothing() {
+        assert!(RoomMediaPolicy::default().intents(&fresh()).is_empty());
+    }
+
+    #[test]
+    fn mutes_audio_and_disables_video() {
+        let policy = RoomMediaPolicy {
+            send_audio: Some(SendMediaPolicy::Muted),
+            send_device_video: Some(SendMediaPolicy::Disabled),
+            send_display_video: Some(SendMediaPolicy::Disabled),
+            ..RoomMediaPolicy::default()
+        };
+
+        assert_eq!(
+            policy.intents(&fresh()),
+            [
+                send(Mute, MediaKind::Audio, None),
+                send(Disable, MediaKind::Video, Some(Device)),
+                send(Disable, MediaKind::Video, Some(Display)),
+            ],
+        );
+    }
+
+    #[test]
+    fn skips_already_applied_states() {
+        let mut current = fresh();
+        current.audio = local(true, true);
+        current.device_video = local(false, true);
+        let policy = RoomMediaPolicy {
+            send_audio: Some(SendMediaPolicy::Muted),
+            send_device_video: Some(SendMediaPolicy::Disabled),
+            send_display_video: Some(SendMediaPolicy::Enabled),
+            recv_audio: Some(true),
+            recv_video: Some(true),
+        };
+
+        assert!(policy.intents(&current).is_empty());
+    }
+
+    #[test]
+    fn unmutes_after_enabling_and_mutes_before() {
+        let mut current = fresh();
+        current.audio = local(false, true);
+        current.device_video = local(false, false);
+        let policy = RoomMediaPolicy {
+            send_audio: Some(SendMediaPolicy::Enabled),
+            send_device_video: Some(SendMediaPolicy::Muted),
+            ..RoomMediaPolicy::default()
+        };
+
+        assert_eq!(
+            policy.intents(&current),
+            [
+                send(Mute, MediaKind::Video, Some(Device)),
+                send(Enable, MediaKind::Audio, None),
+                send(Enable, MediaKind::Video, Some(Device)),
+                send(Unmute, MediaKind::Audio, None),
+            ],
+        );
+    }
+
+    #[test]
+    fn toggles_receiving_when_any_source_differs() {
+        let mut current = fresh();
+        current.recv_display_video = false;
+        let recv = |change, kind| MediaIntent {
+            change,
+            kind,
+            direction: TrackDirection::Recv,
+            source_kind: None,
+        };
+
+        let enable = RoomMediaPolicy {
+            recv_audio: Some(false),
+            recv_video: Some(true),
+            ..RoomMediaPolicy::default()
+        };
+        assert_eq!(
+            enable.intents(&current),
+            [
+                recv(Disable, MediaKind::Audio),
+                recv(Enable, MediaKind::Video),
+            ],
+        );
+
+        let disable = RoomMediaPolicy {
+            recv_video: Some(false),
+            ..RoomMediaPolicy::default()
+        };
+        assert_eq!(
+            disable.intents(&current),
+            [recv(Disable, MediaKind::Video)],
+        );
+    }
+}
@@ -0,0 +1,370 @@
+//! Adaptation of outbound video to the quality limitations reported by a
+//! [`PeerConnection`].
+//!
+//! [`PeerConnection`]: super::PeerConnection
+
+use std::{cell::Cell, time::Duration};
+
+use medea_client_api_proto::stats::{
+    QualityLimitationReason, RtcOutboundRtpStreamMediaType, RtcStat,
+    RtcStatsType,
+};
+
+use crate::platform;
+
+/// Maximum bitrates (in bits per second) the outbound video is stepwise
+/// lowered through by the [`AdaptivePolicy::AudioFirst`], before pausing it.
+pub const AUDIO_FIRST_BITRATES: [u32; 3] = [1_000_000, 500_000, 250_000];
+
+/// Policy of adapting outbound video to the quality limitations.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum AdaptivePolicy {
+    /// Outbound video is never adapted.
+    #[default]
+    Disabled,
+
+    /// Outbound video is degraded in favor of audio.
+    ///
+    /// Once the outbound video stays [limited][`LimitationReason`] for the
+    /// `patience`, its maximum bitrate is lowered through the
+    /// [`AUDIO_FIRST_BITRATES`] one step at a time, and finally its encoding
+    /// is paused. Once it stays unlimited for the `patience`, it's restored
+    /// one step at a time in the reverse order.
+    AudioFirst {
+        /// Duration a condition should hold for to make a single step.
+        patience: Duration,
+    },
+}
+
+/// Reason of the outbound video being limited.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LimitationReason {
+    /// Video is limited due to CPU load.
+    Cpu,
+
+    /// Video is limited due to congestion cues during bandwidth estimation.
+    Bandwidth,
+}
+
+/// Quality level of the outbound video set by an [`AdaptivePolicy`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum VideoLevel {
+    /// Video is encoded without restrictions.
+    Full,
+
+    /// Video is encoded with the provided maximum bitrate (in bits per
+    /// second).
+    Limited(u32),
+
+    /// Video encoding is paused.
+    Paused,
+}
+
+impl VideoLevel {
+    /// Returns maximum bitrate (in bits per second) of the outbound video at
+    /// this [`VideoLevel`], if any.
+    #[must_use]
+    pub const fn max_bitrate(self) -> Option<u32> {
+        if let Self::Limited(bitrate) = self {
+            Some(bitrate)
+        } else {
+            None
+        }
+    }
+
+    /// Indicates whether the outbound video is encoded at this
+    /// [`VideoLevel`].
+    #[must_use]
+    pub const fn is_active(self) -> bool {
+        !matches!(self, Self::Paused)
+    }
+
+    /// Returns [`VideoLevel`] of the provided step of the
+    /// [`AdaptivePolicy::AudioFirst`].
+    fn audio_first(step: usize) -> Self {
+        match step {
+            0 => Self::Full,
+            s => AUDIO_FIRST_BITRATES
+                .get(s - 1)
+                .copied()
+                .map_or(Self::Paused, Self::Limited),
+        }
+    }
+}
+
+/// Transition of the outbound video to another [`VideoLevel`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Adaptation {
+    /// New [`VideoLevel`] of the outbound video.
+    pub level: VideoLevel,
+
+    /// [`LimitationReason`] caused a downgrade, or [`None`] if the outbound
+    /// video is being restored.
+    pub reason: Option<LimitationReason>,
+}
+
+/// Engine applying an [`AdaptivePolicy`] to the periodically scraped
+/// [`RtcStat`]s of a [`PeerConnection`].
+///
+/// [`PeerConnection`]: super::PeerConnection
+#[derive(Debug, Default)]
+pub struct AdaptationEngine {
+    /// [`AdaptivePolicy`] being applied.
+    policy: Cell<AdaptivePolicy>,
+
+    /// Current step of the [`AdaptivePolicy::AudioFirst`], where `0` means
+    /// [`VideoLevel::Full`].
+    step: Cell<usize>,
+
+    /// Current [`LimitationReason`] (if any) and the time it has started at.
+    condition: Cell<Option<(Option<LimitationReason>, Duration)>>,
+}
+
+impl AdaptationEngine {
+    /// Sets the [`AdaptivePolicy`] to be applied.
+    ///
+    /// Returns an [`Adaptation`] restoring the outbound video, if it has been
+    /// degraded by the previous [`AdaptivePolicy`].
+    pub fn set_policy(&self, policy: AdaptivePolicy) -> Option<Adaptation> {
+        self.policy.set(policy);
+        self.condition.set(None);
+        (self.step.replace(0) > 0).then_some(Adaptation {
+            level: VideoLevel::Full,
+            reason: None,
+        })
+    }
+
+    /// Returns the current [`VideoLevel`] of the outbound video.
+    #[must_use]
+    pub fn level(&self) -> VideoLevel {
+        VideoLevel::audio_first(self.step.get())
+    }
+
+    /// Accounts the provided [`RtcStat`]s, returning the [`Adaptation`] to be
+    /// applied, if any.
+    pub fn update(&self, stats: &[RtcStat]) -> Option<Adaptation> {
+        if self.policy.get() == AdaptivePolicy::Disabled {
+            return None;
+        }
+        self.update_at(platform::now(), limitation(stats))
+    }
+
+    /// Accounts the provided [`LimitationReason`] of the outbound video
+    /// observed at the provided time, returning the [`Adaptation`] to be
+    /// applied, if any.
+    fn update_at(
+        &self,
+        now: Duration,
+        reason: Option<LimitationReason>,
+    ) -> Option<Adaptation> {
+        let AdaptivePolicy::AudioFirst { patience } = self.policy.get() else {
+            return None;
+        };
+
+        let since = match self.condition.get() {
+            Some((current, since)) if current == reason => since,
+            _ => {
+                self.condition.set(Some((reason, now)));
+                return None;
+            }
+        };
+        if now.saturating_sub(since) < patience {
+            return None;
+        }
+
+        let step = self.step.get();
+        let next = if reason.is_some() {
+            (step <= AUDIO_FIRST_BITRATES.len()).then_some(step + 1)
+        } else {
+            step.checked_sub(1)
+        }?;
+        self.step.set(next);
+        self.condition.set(Some((reason, now)));
+
+        Some(Adaptation {
+            level: VideoLevel::audio_first(next),
+            reason,
+        })
+    }
+}
+
+/// Returns [`LimitationReason`] of the outbound video described by the
+/// provided [`RtcStat`]s, if it's limited.
+///
+/// [`LimitationReason::Bandwidth`] takes precedence if several outbound video
+/// RTP streams are limited for different reasons.
+fn limitation(stats: &[RtcStat]) -> Option<LimitationReason> {
+    stats
+        .iter()
+        .filter_map(|stat| {
+            let RtcStatsType::OutboundRtp(outbound) = &stat.stats else {
+                return None;
+            };
+            let RtcOutboundRtpStreamMediaType::Video {
+                quality_limitation_reason,
+                ..
+            } = outbound.media_type
+            else {
+                return None;
+            };
+            match quality_limitation_reason? {
+                QualityLimitationReason::Cpu => Some(LimitationReason::Cpu),
+                QualityLimitationReason::Bandwidth => {
+                    Some(LimitationReason::Bandwidth)
+                }
+                QualityLimitationReason::None
+                | QualityLimitationReason::Other => None,
+            }
+        })
+        .max_by_key(|reason| *reason == LimitationReason::Bandwidth)
+}
+
+#[cfg(test)]
+mod spec {
+    use std::time::Duration;
+
+    use crate::peer::stats_fixture::stats;
+
+    use super::{
+        limitation, Adaptation, AdaptationEngine, AdaptivePolicy,
+        LimitationReason, VideoLevel,
+    };
+
+    /// Returns an [`AdaptationEngine`] with the [`AdaptivePolicy::AudioFirst`]
+    /// of 2 seconds patience.
+    fn audio_first() -> AdaptationEngine {
+        let engine = AdaptationEngine::default();
+        _ = engine.set_policy(AdaptivePolicy::AudioFirst {
+            patience: Duration::from_secs(2),
+        });
+        engine
+    }
+
+    /// Feeds the provided [`AdaptationEngine`] with one sample per second
+    /// starting at the provided second, returning all the [`Adaptation`]s.
+    fn feed(
+        engine: &AdaptationEngine,
+        start: u64,
+        samples: &[Option<LimitationReason>],
+    ) -> Vec<Adaptation> {
+        (start..)
+            .zip(samples)
+            .filter_map(|(sec, reason)| {
+                engine.update_at(Duration::from_secs(sec), *reason)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn degrades_stepwise_until_paused() {
+        let engine = audio_first();
+        let bw = Some(LimitationReason::Bandwidth);
+
+        let adaptations = feed(&engine, 0, &[bw; 9]);
+
+        assert_eq!(
+            adaptations,
+            [
+                VideoLevel::Limited(1_000_000),
+                VideoLevel::Limited(500_000),
+                VideoLevel::Limited(250_000),
+                VideoLevel::Paused,
+            ]
+            .map(|level| Adaptation { level, reason: bw }),
+        );
+        assert!(feed(&engine, 9, &[bw; 5]).is_empty());
+        assert_eq!(engine.level(), VideoLevel::Paused);
+    }
+
+    #[test]
+    fn ignores_short_limitations() {
+        let engine = audio_first();
+        let cpu = Some(LimitationReason::Cpu);
+
+        let adaptations = feed(&engine, 0, &[cpu, cpu, None, cpu, cpu, None]);
+
+        assert!(adaptations.is_empty());
+        assert_eq!(engine.level(), VideoLevel::Full);
+    }
+
+    #[test]
+    fn restores_on_recovery() {
+        let engine = audio_first();
+        let cpu = Some(LimitationReason::Cpu);
+        assert_eq!(feed(&engine, 0, &[cpu; 5]).len(), 2);
+
+        let adaptations = feed(&engine, 5, &[None; 7]);
+
+        assert_eq!(
+            adaptations,
+            [VideoLevel::Limited(1_000_000), VideoLevel::Full].map(|level| {
+                Adaptation {
+                    level,
+                    reason: None,
+                }
+            }),
+        );
+        assert_eq!(engine.level(), VideoLevel::Full);
+    }
+
+    #[test]
+    fn restarts_patience_on_reason_change() {
+        let engine = audio_first();
+        let cpu = Some(LimitationReason::Cpu);
+        let bw = Some(LimitationReason::Bandwidth);
+
+        let adaptations = feed(&engine, 0, &[cpu, cpu, bw, bw, bw]);
+
+        assert_eq!(
+            adaptations,
+            [Adaptation {
+                level: VideoLevel::Limited(1_000_000),
+                reason: bw,
+            }],
+        );
+    }
+
+    #[test]
+    fn disabling_restores_full_level() {
+        let engine = audio_first();
+        let bw = Some(LimitationReason::Bandwidth);
+        assert_eq!(feed(&engine, 0, &[bw; 3]).len(), 1);
+
+        assert_eq!(
+            engine.set_policy(AdaptivePolicy::Disabled),
+            Some(Adaptation {
+                level: VideoLevel::Full,
+                reason: None,
+            }),
+        );
+        assert!(feed(&engine, 3, &[bw; 5]).is_empty());
+        assert_eq!(engine.set_policy(AdaptivePolicy::Disabled), None);
+    }
+
+    #[test]
+    fn reads_limitation_from_outbound_video_stats() {
+        let outbound = |reasons: &[&str]| {
+            let json = reasons.iter().enumerate().map(|(i, reason)| {
+                serde_json::json!({
+                    "id": format!("out{i}"),
+                    "timestamp": 1.0,
+                    "type": "outbound-rtp",
+                    "mediaType": "video",
+                    "qualityLimitationReason": reason
+                })
+            });
+            stats(json.collect())
+        };
+
+        assert_eq!(limitation(&outbound(&[])), None);
+        assert_eq!(limitation(&outbound(&["none", "other"])), None);
+        assert_eq!(
+            limitation(&outbound(&["none", "cpu"])),
+            Some(LimitationReason::Cpu),
+        );
+        assert_eq!(
+            limitation(&outbound(&["cpu", "bandwidth", "cpu"])),
+            Some(LimitationReason::Bandwidth),
+        );
+    }
+}
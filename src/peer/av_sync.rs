@@ -0,0 +1,471 @@
+//! Diagnostics of the audio-video synchronization of the media received by a
+//! [`PeerConnection`].
+//!
+//! [`PeerConnection`]: super::PeerConnection
+
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    time::Duration,
+};
+
+use medea_client_api_proto::{
+    self as proto,
+    stats::{
+        RtcInboundRtpStreamMediaType, RtcInboundRtpStreamStats, RtcStat,
+        RtcStatsType, StatId,
+    },
+};
+
+use crate::media::MediaKind;
+
+/// Audio-video synchronization estimates of the media received by a
+/// [`PeerConnection`].
+///
+/// [`PeerConnection`]: super::PeerConnection
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct AvSyncInfo {
+    /// Estimated drift of the received video relative to the received audio
+    /// in milliseconds, positive when the video is played out ahead of the
+    /// audio.
+    ///
+    /// [`None`] unless exactly one audio and one video inbound RTP streams
+    /// are played out and report their `estimatedPlayoutTimestamp`.
+    pub drift_ms: Option<i32>,
+
+    /// Average jitter buffer delay of the received audio since the previous
+    /// stats scrape.
+    ///
+    /// [`None`] if no inbound audio RTP stream reports its
+    /// `jitterBufferDelay` and `jitterBufferEmittedCount`.
+    pub audio_jitter_buffer_delay: Option<Duration>,
+
+    /// Average jitter buffer delay of the received video since the previous
+    /// stats scrape.
+    ///
+    /// [`None`] if no inbound video RTP stream reports its
+    /// `jitterBufferDelay` and `jitterBufferEmittedCount`.
+    pub video_jitter_buffer_delay: Option<Duration>,
+
+    /// Jitter buffer target of the received audio, if set for any of its
+    /// tracks.
+    pub audio_jitter_buffer_target: Option<Duration>,
+
+    /// Jitter buffer target of the received video, if set for any of its
+    /// tracks.
+    pub video_jitter_buffer_target: Option<Duration>,
+}
+
+impl From<AvSyncInfo> for proto::AvSyncStats {
+    fn from(info: AvSyncInfo) -> Self {
+        let to_ms =
+            |d: Duration| u64::try_from(d.as_millis()).unwrap_or(u64::MAX);
+        Self {
+            drift_ms: info.drift_ms,
+            audio_jitter_buffer_delay_ms: info
+                .audio_jitter_buffer_delay
+                .map(to_ms),
+            video_jitter_buffer_delay_ms: info
+                .video_jitter_buffer_delay
+                .map(to_ms),
+            audio_jitter_buffer_target_ms: info
+                .audio_jitter_buffer_target
+                .map(to_ms),
+            video_jitter_buffer_target_ms: info
+                .video_jitter_buffer_target
+                .map(to_ms),
+        }
+    }
+}
+
+/// Cumulative jitter buffer counters of an inbound RTP stream.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct JitterBufferSample {
+    /// `jitterBufferDelay` in seconds.
+    delay: f64,
+
+    /// `jitterBufferEmittedCount`.
+    emitted: u64,
+}
+
+impl JitterBufferSample {
+    /// Returns counters accumulated since the provided previous
+    /// [`JitterBufferSample`].
+    ///
+    /// Counters less than the previous ones mean they have been reset (like
+    /// it happens when RTP streams are recreated on ICE restart), so all of
+    /// them are new ones.
+    fn since(self, prev: Option<Self>) -> Self {
+        match prev {
+            Some(prev)
+                if self.delay >= prev.delay && self.emitted >= prev.emitted =>
+            {
+                Self {
+                    delay: self.delay - prev.delay,
+                    emitted: self.emitted - prev.emitted,
+                }
+            }
+            _ => self,
+        }
+    }
+}
+
+/// Estimator of an [`AvSyncInfo`] from the periodically scraped [`RtcStat`]s
+/// of a [`PeerConnection`].
+///
+/// The drift is estimated by comparing `estimatedPlayoutTimestamp`s of the
+/// inbound audio and video RTP streams, each normalized by the time it's
+/// sampled at. Only the streams being played out (having received audio
+/// samples or decoded video frames) are considered.
+///
+/// Only the jitter buffer counters of the streams present in the last
+/// [`RtcStat`]s are retained.
+///
+/// [`PeerConnection`]: super::PeerConnection
+#[derive(Debug, Default)]
+pub struct AvSyncCounter {
+    /// Last seen jitter buffer counters of inbound RTP streams by their
+    /// [`StatId`]s.
+    last: RefCell<HashMap<StatId, JitterBufferSample>>,
+
+    /// Last estimated [`AvSyncInfo`].
+    info: Cell<AvSyncInfo>,
+
+    /// Indicator whether the [`AvSyncInfo`] has changed since the last
+    /// [`AvSyncCounter::take_update()`] call.
+    is_updated: Cell<bool>,
+}
+
+impl AvSyncCounter {
+    /// Returns the last estimated [`AvSyncInfo`].
+    #[must_use]
+    pub fn info(&self) -> AvSyncInfo {
+        self.info.get()
+    }
+
+    /// Returns the last estimated [`AvSyncInfo`] if it has changed since the
+    /// previous call of this method.
+    pub fn take_update(&self) -> Option<AvSyncInfo> {
+        self.is_updated.take().then(|| self.info())
+    }
+
+    /// Estimates a new [`AvSyncInfo`] from the inbound RTP streams of the
+    /// provided [`RtcStat`]s.
+    pub fn update(&self, stats: &[RtcStat]) {
+        let mut last = self.last.borrow_mut();
+        last.retain(|id, _| stats.iter().any(|s| s.id == *id));
+
+        let mut jitter_buffers: HashMap<MediaKind, JitterBufferSample> =
+            HashMap::new();
+        let mut playout_offsets: HashMap<MediaKind, Vec<f64>> = HashMap::new();
+        for stat in stats {
+            let RtcStatsType::InboundRtp(inbound) = &stat.stats else {
+                continue;
+            };
+            let kind = match inbound.media_specific_stats {
+                RtcInboundRtpStreamMediaType::Audio { .. } => MediaKind::Audio,
+                RtcInboundRtpStreamMediaType::Video { .. } => MediaKind::Video,
+            };
+
+            if let Some(current) = jitter_buffer(inbound) {
+                let prev = last.insert(stat.id.clone(), current);
+                let delta = current.since(prev);
+                let total = jitter_buffers.entry(kind).or_default();
+                total.delay += delta.delay;
+                total.emitted += delta.emitted;
+            }
+            if let Some(playout) = inbound.estimated_playout_timestamp {
+                if is_played_out(inbound) {
+                    playout_offsets
+                        .entry(kind)
+                        .or_default()
+                        .push(playout.0 - stat.timestamp.0);
+                }
+            }
+        }
+        drop(last);
+
+        #[allow(clippy::as_conversions, clippy::cast_precision_loss)]
+        let average_delay = |kind: MediaKind| {
+            jitter_buffers
+                .get(&kind)
+                .filter(|s| s.emitted > 0)
+                .map(|s| s.delay / s.emitted as f64)
+                .filter(|secs| secs.is_finite() && *secs >= 0.0)
+                .map(Duration::from_secs_f64)
+        };
+        let prev = self.info.get();
+        let info = AvSyncInfo {
+            drift_ms: match (
+                playout_offsets.get(&MediaKind::Audio).map(Vec::as_slice),
+                playout_offsets.get(&MediaKind::Video).map(Vec::as_slice),
+            ) {
+                (Some([audio]), Some([video])) => {
+                    Some(drift_ms(*audio, *video))
+                }
+                _ => None,
+            },
+            audio_jitter_buffer_delay: average_delay(MediaKind::Audio),
+            video_jitter_buffer_delay: average_delay(MediaKind::Video),
+            ..prev
+        };
+        self.set_info(info);
+    }
+
+    /// Sets jitter buffer targets of the received audio and video to be
+    /// reported along with the estimated [`AvSyncInfo`].
+    pub fn set_jitter_buffer_targets(
+        &self,
+        audio: Option<Duration>,
+        video: Option<Duration>,
+    ) {
+        self.set_info(AvSyncInfo {
+            audio_jitter_buffer_target: audio,
+            video_jitter_buffer_target: video,
+            ..self.info.get()
+        });
+    }
+
+    /// Replaces the last [`AvSyncInfo`] with the provided one, marking it as
+    /// updated if it differs.
+    fn set_info(&self, info: AvSyncInfo) {
+        if self.info.replace(info) != info {
+            self.is_updated.set(true);
+        }
+    }
+}
+
+/// Returns the current [`JitterBufferSample`] of the provided inbound RTP
+/// stream, if it reports one.
+fn jitter_buffer(
+    inbound: &RtcInboundRtpStreamStats,
+) -> Option<JitterBufferSample> {
+    Some(JitterBufferSample {
+        delay: inbound.jitter_buffer_delay?.0,
+        emitted: inbound.jitter_buffer_emitted_count?,
+    })
+}
+
+/// Returns drift of the video relative to the audio in milliseconds, provided
+/// their playout offsets.
+// Saturating conversion is intended, as no meaningful drift exceeds `i32`
+// milliseconds.
+#[allow(clippy::as_conversions, clippy::cast_possible_truncation)]
+fn drift_ms(audio: f64, video: f64) -> i32 {
+    (video - audio).round() as i32
+}
+
+/// Indicates whether the provided inbound RTP stream is being played out,
+/// i.e. has received audio samples or decoded video frames.
+fn is_played_out(inbound: &RtcInboundRtpStreamStats) -> bool {
+    match inbound.media_specific_stats {
+        RtcInboundRtpStreamMediaType::Audio {
+            total_samples_received,
+            ..
+        } => total_samples_received.unwrap_or_default() > 0,
+        RtcInboundRtpStreamMediaType::Video { frames_decoded, .. } => {
+            frames_decoded.unwrap_or_default() > 0
+        }
+    }
+}
+
+#[cfg(test)]
+mod spec {
+    use std::time::Duration;
+
+    use medea_client_api_proto::stats::RtcStat;
+
+    use crate::peer::stats_fixture::stats;
+
+    use super::{AvSyncCounter, AvSyncInfo};
+
+    /// Returns `inbound-rtp` audio and video [`RtcStat`]s captured in Chrome,
+    /// with the provided jitter buffer counters and playout timestamps.
+    fn captured(
+        audio: (f64, u64, f64),
+        video: (f64, u64, f64),
+    ) -> Vec<RtcStat> {
+        stats(serde_json::json!([
+            {
+                "id": "IT01A2964373843",
+                "timestamp": 1_700_000_000_000.0,
+                "type": "inbound-rtp",
+                "mediaType": "audio",
+                "trackIdentifier": "8e5b4c59-a0c3-4b0f-9d0c-8a8c1b8e4c8d",
+                "bytesReceived": 412_331,
+                "packetsReceived": 5_021,
+                "packetsLost": 3,
+                "jitter": 0.004,
+                "totalSamplesReceived": 4_815_360,
+                "concealedSamples": 1_920,
+                "silentConcealedSamples": 960,
+                "audioLevel": 0.0123,
+                "totalAudioEnergy": 0.4567,
+                "totalSamplesDuration": 100.32,
+                "jitterBufferDelay": audio.0,
+                "jitterBufferEmittedCount": audio.1,
+                "estimatedPlayoutTimestamp": audio.2,
+            },
+            {
+                "id": "IT01V1237163452",
+                "timestamp": 1_700_000_000_000.0,
+                "type": "inbound-rtp",
+                "mediaType": "video",
+                "trackIdentifier": "2c2b1b80-7f3c-45e4-a3e2-1f6d4b0d1e55",
+                "bytesReceived": 10_123_456,
+                "packetsReceived": 9_876,
+                "packetsLost": 0,
+                "jitter": 0.012,
+                "framesDecoded": 2_990,
+                "keyFramesDecoded": 4,
+                "frameWidth": 640,
+                "frameHeight": 480,
+                "framesPerSecond": 30,
+                "framesReceived": 3_000,
+                "totalDecodeTime": 4.35,
+                "jitterBufferDelay": video.0,
+                "jitterBufferEmittedCount": video.1,
+                "estimatedPlayoutTimestamp": video.2,
+            },
+        ]))
+    }
+
+    #[test]
+    fn estimates_from_captured_reports() {
+        let counter = AvSyncCounter::default();
+
+        counter.update(&captured(
+            (3_000.0, 48_000, 3_909_000_000_000.0),
+            (93.75, 3_000, 3_909_000_000_080.0),
+        ));
+        assert_eq!(
+            counter.info(),
+            AvSyncInfo {
+                drift_ms: Some(80),
+                audio_jitter_buffer_delay: Some(Duration::from_micros(62_500)),
+                video_jitter_buffer_delay: Some(Duration::from_micros(31_250)),
+                ..AvSyncInfo::default()
+            },
+        );
+
+        // Averages are taken over the counters since the previous scrape.
+        counter.update(&captured(
+            (4_500.0, 96_000, 3_909_000_001_000.0),
+            (101.25, 3_030, 3_909_000_000_960.0),
+        ));
+        assert_eq!(
+            counter.info(),
+            AvSyncInfo {
+                drift_ms: Some(-40),
+                audio_jitter_buffer_delay: Some(Duration::from_micros(31_250)),
+                video_jitter_buffer_delay: Some(Duration::from_millis(250)),
+                ..AvSyncInfo::default()
+            },
+        );
+    }
+
+    #[test]
+    fn normalizes_playout_by_sampling_time() {
+        let mut stats = captured(
+            (1.0, 100, 3_909_000_000_000.0),
+            (1.0, 100, 3_909_000_000_050.0),
+        );
+        // Video is sampled 30ms later, so it has been played out further.
+        stats[1].timestamp.0 += 30.0;
+        let counter = AvSyncCounter::default();
+
+        counter.update(&stats);
+        assert_eq!(counter.info().drift_ms, Some(20));
+    }
+
+    #[test]
+    fn absent_fields_give_none() {
+        let counter = AvSyncCounter::default();
+
+        counter.update(&stats(serde_json::json!([
+            {
+                "id": "audio",
+                "timestamp": 1.0,
+                "type": "inbound-rtp",
+                "mediaType": "audio",
+                "bytesReceived": 100,
+                "packetsReceived": 10,
+                "totalSamplesReceived": 480,
+                "estimatedPlayoutTimestamp": 5_000.0,
+            },
+            {
+                "id": "video",
+                "timestamp": 1.0,
+                "type": "inbound-rtp",
+                "mediaType": "video",
+                "bytesReceived": 100,
+                "packetsReceived": 10,
+                "jitterBufferDelay": 0.5,
+                "jitterBufferEmittedCount": 8,
+                "estimatedPlayoutTimestamp": 5_100.0,
+            },
+        ])));
+        assert_eq!(
+            counter.info(),
+            AvSyncInfo {
+                // Video has no decoded frames, so isn't played out.
+                drift_ms: None,
+                audio_jitter_buffer_delay: None,
+                video_jitter_buffer_delay: Some(Duration::from_micros(62_500)),
+                ..AvSyncInfo::default()
+            },
+        );
+
+        counter.update(&[]);
+        assert_eq!(counter.info(), AvSyncInfo::default());
+        assert!(counter.last.borrow().is_empty());
+    }
+
+    #[test]
+    fn reports_updates_only_on_changes() {
+        let counter = AvSyncCounter::default();
+        let report = captured((1.0, 100, 1_000.0), (1.0, 100, 1_000.0));
+
+        counter.update(&report);
+        assert_eq!(counter.take_update().unwrap().drift_ms, Some(0));
+        assert_eq!(counter.take_update(), None);
+
+        // Counters haven't changed, so averages are unavailable.
+        counter.update(&report);
+        assert_eq!(
+            counter.take_update(),
+            Some(AvSyncInfo {
+                drift_ms: Some(0),
+                ..AvSyncInfo::default()
+            }),
+        );
+        counter.update(&report);
+        assert_eq!(counter.take_update(), None);
+    }
+
+    #[test]
+    fn keeps_jitter_buffer_targets_across_updates() {
+        let counter = AvSyncCounter::default();
+        let report = captured((1.0, 100, 1_000.0), (1.0, 100, 1_000.0));
+        counter.update(&report);
+        _ = counter.take_update();
+
+        let target = Some(Duration::from_millis(40));
+        counter.set_jitter_buffer_targets(target, None);
+        let info = counter.take_update().unwrap();
+        assert_eq!(info.audio_jitter_buffer_target, target);
+        assert_eq!(info.video_jitter_buffer_target, None);
+
+        counter.set_jitter_buffer_targets(target, None);
+        assert_eq!(counter.take_update(), None);
+
+        // Jitter buffer delay is still measured after the target is set.
+        counter.update(&captured((1.5, 200, 1_000.0), (1.0, 100, 1_000.0)));
+        let info = counter.take_update().unwrap();
+        assert_eq!(info.audio_jitter_buffer_target, target);
+        assert_eq!(
+            info.audio_jitter_buffer_delay,
+            Some(Duration::from_millis(5)),
+        );
+    }
+}
@@ -0,0 +1,284 @@
+//! Tracking of encoder and decoder implementations used for the media of a
+//! [`PeerConnection`].
+//!
+//! [`PeerConnection`]: super::PeerConnection
+
+use std::{collections::HashMap, rc::Rc};
+
+use medea_client_api_proto::stats::{RtcStat, RtcStatsType};
+
+use crate::{
+    media::{
+        track::{local, remote},
+        CodecInfo, MediaKind,
+    },
+    platform,
+};
+
+use super::TrackDirection;
+
+/// Fallback of an encoder or a decoder of a [`PeerConnection`]'s media from a
+/// hardware implementation to a software one.
+///
+/// [`PeerConnection`]: super::PeerConnection
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CodecFallback {
+    /// [`MediaKind`] of the media the implementation has changed for.
+    pub kind: MediaKind,
+
+    /// [`TrackDirection::Send`] for an encoder, or [`TrackDirection::Recv`]
+    /// for a decoder.
+    pub direction: TrackDirection,
+
+    /// Hardware [`CodecInfo`] used before.
+    pub from: CodecInfo,
+
+    /// Software [`CodecInfo`] used now.
+    pub to: CodecInfo,
+}
+
+/// Updates [`CodecInfo`]s of the provided [`local::Track`]s being sent and
+/// [`remote::Track`]s from the provided [`RtcStat`]s, returning the detected
+/// [`CodecFallback`]s.
+pub fn update(
+    stats: &[RtcStat],
+    local_tracks: &[Rc<local::Track>],
+    remote_tracks: &[remote::Track],
+) -> Vec<CodecFallback> {
+    let mut fallbacks = Vec::new();
+
+    let mut encoders = encoder_infos(stats);
+    for track in local_tracks {
+        let id = track.with_sent_track(platform::MediaStreamTrack::id);
+        let Some(next) = encoders.remove(&id) else {
+            continue;
+        };
+        let prev = track.encoder_info();
+        if let Some(fallback) = detect(prev.as_ref(), &next) {
+            fallbacks.push(CodecFallback {
+                kind: track.kind(),
+                direction: TrackDirection::Send,
+                from: fallback,
+                to: next.clone(),
+            });
+        }
+        if prev.as_ref() != Some(&next) {
+            track.set_encoder_info(next);
+        }
+    }
+
+    let mut decoders = decoder_infos(stats);
+    for track in remote_tracks {
+        let Some(next) = decoders.remove(&track.id()) else {
+            continue;
+        };
+        let prev = track.decoder_info();
+        if let Some(fallback) = detect(prev.as_ref(), &next) {
+            fallbacks.push(CodecFallback {
+                kind: track.kind(),
+                direction: TrackDirection::Recv,
+                from: fallback,
+                to: next.clone(),
+            });
+        }
+        if prev.as_ref() != Some(&next) {
+            track.set_decoder_info(next);
+        }
+    }
+
+    fallbacks
+}
+
+/// Returns the previous [`CodecInfo`] if switching from it to the next one is
+/// a fallback from a hardware implementation to a software one.
+///
+/// Implementations not reporting whether they're power efficient are never
+/// considered as falling back.
+fn detect(prev: Option<&CodecInfo>, next: &CodecInfo) -> Option<CodecInfo> {
+    let prev = prev?;
+    (prev.implementation != next.implementation
+        && prev.is_hardware() == Some(true)
+        && next.is_hardware() == Some(false))
+    .then(|| prev.clone())
+}
+
+/// Returns [`CodecInfo`]s of the encoders described by the provided
+/// [`RtcStat`]s, keyed by [`id`][1]s of the sent tracks.
+///
+/// [1]: https://w3.org/TR/mediacapture-streams#dom-mediastreamtrack-id
+fn encoder_infos(stats: &[RtcStat]) -> HashMap<String, CodecInfo> {
+    let identifiers: HashMap<_, _> = stats
+        .iter()
+        .filter_map(|stat| {
+            let RtcStatsType::MediaSource(source) = &stat.stats else {
+                return None;
+            };
+            Some((&stat.id.0, source.track_identifier.as_ref()?))
+        })
+        .collect();
+    stats
+        .iter()
+        .filter_map(|stat| {
+            let RtcStatsType::OutboundRtp(outbound) = &stat.stats else {
+                return None;
+            };
+            let id = identifiers.get(outbound.media_source_id.as_ref()?)?;
+            let info = CodecInfo {
+                implementation: outbound.encoder_implementation.clone()?,
+                power_efficient: outbound.power_efficient_encoder,
+            };
+            Some(((*id).clone(), info))
+        })
+        .collect()
+}
+
+/// Returns [`CodecInfo`]s of the decoders described by the provided
+/// [`RtcStat`]s, keyed by [`id`][1]s of the received tracks.
+///
+/// [1]: https://w3.org/TR/mediacapture-streams#dom-mediastreamtrack-id
+fn decoder_infos(stats: &[RtcStat]) -> HashMap<String, CodecInfo> {
+    let identifiers: HashMap<_, _> = stats
+        .iter()
+        .filter_map(|stat| {
+            let RtcStatsType::Track(track) = &stat.stats else {
+                return None;
+            };
+            Some((&stat.id.0, &track.track_identifier))
+        })
+        .collect();
+    stats
+        .iter()
+        .filter_map(|stat| {
+            let RtcStatsType::InboundRtp(inbound) = &stat.stats else {
+                return None;
+            };
+            let id = inbound.track_identifier.as_ref().or_else(|| {
+                inbound
+                    .track_id
+                    .as_ref()
+                    .and_then(|id| identifiers.get(id).copied())
+            })?;
+            let info = CodecInfo {
+                implementation: inbound.decoder_implementation.clone()?,
+                power_efficient: inbound.power_efficient_decoder,
+            };
+            Some((id.clone(), info))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod spec {
+    use crate::{media::CodecInfo, peer::stats_fixture::stats};
+
+    use super::{decoder_infos, detect, encoder_infos};
+
+    /// Returns a [`CodecInfo`] of the provided implementation.
+    fn info(implementation: &str, power_efficient: Option<bool>) -> CodecInfo {
+        CodecInfo {
+            implementation: implementation.into(),
+            power_efficient,
+        }
+    }
+
+    #[test]
+    fn detects_hardware_to_software_fallback() {
+        let hardware = info("ExternalEncoder", Some(true));
+        let software = info("libvpx", Some(false));
+
+        assert_eq!(detect(None, &software), None);
+        assert_eq!(detect(Some(&hardware), &hardware), None);
+        assert_eq!(detect(Some(&hardware), &software), Some(hardware.clone()));
+        assert_eq!(detect(Some(&software), &hardware), None);
+    }
+
+    #[test]
+    fn ignores_unknown_power_efficiency() {
+        let unknown = info("libvpx", None);
+        let hardware = info("ExternalEncoder", Some(true));
+        let software = info("OpenH264", Some(false));
+
+        assert_eq!(detect(Some(&hardware), &unknown), None);
+        assert_eq!(detect(Some(&unknown), &software), None);
+    }
+
+    #[test]
+    fn resolves_sent_track_identifiers() {
+        let stats = stats(serde_json::json!([
+            {
+                "id": "out1",
+                "timestamp": 1.0,
+                "type": "outbound-rtp",
+                "mediaType": "video",
+                "mediaSourceId": "src1",
+                "encoderImplementation": "ExternalEncoder",
+                "powerEfficientEncoder": true
+            },
+            {
+                "id": "src1",
+                "timestamp": 1.0,
+                "type": "media-source",
+                "kind": "video",
+                "trackIdentifier": "track1"
+            },
+            {
+                "id": "out2",
+                "timestamp": 1.0,
+                "type": "outbound-rtp",
+                "mediaType": "audio",
+                "mediaSourceId": "src2"
+            },
+            {
+                "id": "src2",
+                "timestamp": 1.0,
+                "type": "media-source",
+                "kind": "audio",
+                "trackIdentifier": "track2"
+            }
+        ]));
+
+        let encoders = encoder_infos(&stats);
+
+        assert_eq!(encoders.len(), 1);
+        assert_eq!(encoders["track1"], info("ExternalEncoder", Some(true)));
+    }
+
+    #[test]
+    fn resolves_received_track_identifiers() {
+        let stats = stats(serde_json::json!([
+            {
+                "id": "in1",
+                "timestamp": 1.0,
+                "type": "inbound-rtp",
+                "mediaType": "video",
+                "trackIdentifier": "track1",
+                "decoderImplementation": "libvpx",
+                "powerEfficientDecoder": false,
+                "bytesReceived": 300,
+                "packetsReceived": 3
+            },
+            {
+                "id": "in2",
+                "timestamp": 1.0,
+                "type": "inbound-rtp",
+                "mediaType": "video",
+                "trackId": "T02",
+                "decoderImplementation": "ExternalDecoder",
+                "bytesReceived": 300,
+                "packetsReceived": 3
+            },
+            {
+                "id": "T02",
+                "timestamp": 1.0,
+                "type": "track",
+                "trackIdentifier": "track2"
+            }
+        ]));
+
+        let decoders = decoder_infos(&stats);
+
+        assert_eq!(decoders.len(), 2);
+        assert_eq!(decoders["track1"], info("libvpx", Some(false)));
+        assert_eq!(decoders["track2"], info("ExternalDecoder", None));
+    }
+}
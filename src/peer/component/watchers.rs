@@ -12,11 +12,15 @@ use medea_reactive::Guarded;
 use tracerr::Traced;
 
 use crate::{
+    connection::Connection,
+    error_report::NonFatalErrorKind,
     peer::{
         component::{NegotiationState, SyncState},
         media::{receiver, sender},
-        GetMidsError, PeerEvent, RtcPeerConnectionError,
+        report_error, Description, GetMidsError, PeerEvent,
+        RtcPeerConnectionError, SetupPhase,
     },
+    platform,
     utils::{transpose_guarded, Updatable as _},
 };
 
@@ -46,19 +50,29 @@ impl Component {
     ///
     /// Calls [`PeerConnection::add_ice_candidate()`] with the pushed
     /// [`IceCandidate`].
+    ///
+    /// An [`IceCandidate`] failed to be added is ignored, so the failure is
+    /// reported via a [`PeerEvent::NonFatalError`].
     #[watch(self.ice_candidates.on_add())]
     async fn ice_candidate_added(
         peer: Rc<PeerConnection>,
         _: Rc<State>,
         candidate: IceCandidate,
-    ) -> Result<(), Traced<RtcPeerConnectionError>> {
-        peer.add_ice_candidate(
-            candidate.candidate,
-            candidate.sdp_m_line_index,
-            candidate.sdp_mid,
-        )
-        .await
-        .map_err(tracerr::map_from_and_wrap!())
+    ) {
+        let res = peer
+            .add_ice_candidate(
+                candidate.candidate,
+                candidate.sdp_m_line_index,
+                candidate.sdp_mid,
+            )
+            .await;
+        if let Err(e) = res {
+            report_error(
+                &peer.peer_events_sender,
+                NonFatalErrorKind::IceCandidate,
+                e,
+            );
+        }
     }
 
     /// Watcher for the [`State::remote_sdp`] update.
@@ -81,17 +95,30 @@ impl Component {
         if let Some(role) = state.negotiation_role.get() {
             match role {
                 NegotiationRole::Offerer => {
-                    peer.set_remote_answer(description)
+                    peer.setup_timings.finish(SetupPhase::ServerRtt);
+                    peer.setup_timings
+                        .measure(
+                            SetupPhase::SetRemoteDescription,
+                            peer.set_remote_answer(description),
+                        )
                         .await
                         .map_err(tracerr::map_from_and_wrap!())?;
+                    peer.negotiations.finish();
+                    peer.setup_timings.start(SetupPhase::IceConnected);
                     peer.media_connections.sync_receivers().await;
                     state.negotiation_state.set(NegotiationState::Stable);
                     state.negotiation_role.set(None);
                 }
                 NegotiationRole::Answerer(_) => {
-                    peer.set_remote_offer(description)
+                    peer.negotiations.start();
+                    peer.setup_timings
+                        .measure(
+                            SetupPhase::SetRemoteDescription,
+                            peer.set_remote_offer(description),
+                        )
                         .await
                         .map_err(tracerr::map_from_and_wrap!())?;
+                    peer.setup_timings.start(SetupPhase::IceConnected);
                     peer.media_connections.sync_receivers().await;
                 }
             }
@@ -139,7 +166,6 @@ impl Component {
     /// [`Connections::create_connection()`][1].
     ///
     /// [`Answerer`]: NegotiationRole::Answerer
-    /// [`Connection`]: crate::connection::Connection
     /// [1]: crate::connection::Connections::create_connection
     #[watch(self.senders.on_insert())]
     async fn sender_added(
@@ -160,10 +186,21 @@ impl Component {
         medea_reactive::when_all_processed(wait_futs).await;
 
         let ((track_id, new_sender), _guard) = val.into_parts();
-        drop(peer.connections.update_connections(
+        for conn in peer.connections.update_connections(
             &track_id,
             new_sender.receivers().into_iter().collect(),
-        ));
+        ) {
+            conn.set_setup_timings(Rc::clone(&peer.setup_timings));
+            conn.set_traffic_counter(Rc::clone(&peer.traffic));
+            conn.set_transport_monitor(Rc::clone(&peer.transport));
+            conn.set_negotiation_counter(Rc::clone(&peer.negotiations));
+            conn.set_quality_limitation_counter(Rc::clone(
+                &peer.quality_limitation,
+            ));
+            conn.set_av_sync_counter(Rc::clone(&peer.av_sync));
+            conn.set_ice_gathering_counter(peer.ice_gathering());
+            conn.add_sender(&new_sender);
+        }
         let sender = sender::Sender::new(
             &new_sender,
             &peer.media_connections,
@@ -204,11 +241,16 @@ impl Component {
             &track_id,
             HashSet::from([rcvr_state.sender_id().clone()]),
         );
+        // Individual constraints of the `Connection` are respected, so the
+        // media disabled in it before being negotiated is never received.
+        let recv_constraints = conns
+            .first()
+            .map_or(&*peer.recv_constraints, Connection::recv_constraints);
         let receiver = receiver::Receiver::new(
             &rcvr_state,
             &peer.media_connections,
             peer.track_events_sender.clone(),
-            &peer.recv_constraints,
+            recv_constraints,
             state.connection_mode,
         )
         .await;
@@ -218,6 +260,15 @@ impl Component {
                 Rc::clone(&rcvr_state),
             ));
         for conn in conns {
+            conn.set_setup_timings(Rc::clone(&peer.setup_timings));
+            conn.set_traffic_counter(Rc::clone(&peer.traffic));
+            conn.set_transport_monitor(Rc::clone(&peer.transport));
+            conn.set_negotiation_counter(Rc::clone(&peer.negotiations));
+            conn.set_quality_limitation_counter(Rc::clone(
+                &peer.quality_limitation,
+            ));
+            conn.set_av_sync_counter(Rc::clone(&peer.av_sync));
+            conn.set_ice_gathering_counter(peer.ice_gathering());
             conn.add_receiver(Rc::clone(&rcvr_state));
         }
     }
@@ -234,7 +285,21 @@ impl Component {
         state: &State,
         val: (TrackId, HashSet<MemberId>),
     ) {
-        drop(peer.connections.update_connections(&val.0, val.1));
+        let sender = state.get_sender(val.0);
+        for conn in peer.connections.update_connections(&val.0, val.1) {
+            conn.set_setup_timings(Rc::clone(&peer.setup_timings));
+            conn.set_traffic_counter(Rc::clone(&peer.traffic));
+            conn.set_transport_monitor(Rc::clone(&peer.transport));
+            conn.set_negotiation_counter(Rc::clone(&peer.negotiations));
+            conn.set_quality_limitation_counter(Rc::clone(
+                &peer.quality_limitation,
+            ));
+            conn.set_av_sync_counter(Rc::clone(&peer.av_sync));
+            conn.set_ice_gathering_counter(peer.ice_gathering());
+            if let Some(sender) = &sender {
+                conn.add_sender(sender);
+            }
+        }
 
         state.maybe_update_connections.set(None);
     }
@@ -266,8 +331,7 @@ impl Component {
                 // TODO: Temporary fix that allows us to ignore rollback
                 //       since it won't work anyway.
                 if state.negotiation_state.get() != NegotiationState::Stable {
-                    peer.peer
-                        .rollback()
+                    peer.rollback()
                         .await
                         .map_err(tracerr::map_from_and_wrap!())?;
                 }
@@ -280,10 +344,16 @@ impl Component {
             } else {
                 match role {
                     NegotiationRole::Offerer => {
-                        peer.peer
-                            .set_offer(&sdp)
+                        peer.setup_timings
+                            .measure(
+                                SetupPhase::SetLocalDescription,
+                                peer.peer.set_offer(&sdp),
+                            )
                             .await
                             .map_err(tracerr::map_from_and_wrap!())?;
+                        peer.description_applied(Description::Local(
+                            platform::SdpType::Offer(sdp.clone()),
+                        ));
                         peer.media_connections.sync_receivers().await;
                         let mids = peer
                             .get_mids()
@@ -299,15 +369,23 @@ impl Component {
                                 mids,
                             })
                             .ok();
+                        peer.setup_timings.start(SetupPhase::ServerRtt);
                         state
                             .negotiation_state
                             .set(NegotiationState::WaitLocalSdpApprove);
                     }
                     NegotiationRole::Answerer(_) => {
-                        peer.peer
-                            .set_answer(&sdp)
+                        peer.setup_timings
+                            .measure(
+                                SetupPhase::SetLocalDescription,
+                                peer.peer.set_answer(&sdp),
+                            )
                             .await
                             .map_err(tracerr::map_from_and_wrap!())?;
+                        peer.description_applied(Description::Local(
+                            platform::SdpType::Answer(sdp.clone()),
+                        ));
+                        peer.negotiations.finish();
                         peer.media_connections.sync_receivers().await;
                         _ = peer
                             .peer_events_sender
@@ -364,6 +442,9 @@ impl Component {
     ///
     /// Creates and sets local SDP offer on a
     /// [`NegotiationState::WaitLocalSdp`].
+    ///
+    /// Before creating a local SDP answer, corrects directions of the
+    /// transceivers to match the intended media exchange.
     #[watch(self.negotiation_state.subscribe().skip(1))]
     async fn negotiation_state_changed(
         peer: Rc<PeerConnection>,
@@ -383,17 +464,29 @@ impl Component {
                 if let Some(negotiation_role) = state.negotiation_role.get() {
                     match negotiation_role {
                         NegotiationRole::Offerer => {
+                            peer.negotiations.start();
                             if state.restart_ice.take() {
                                 peer.restart_ice();
                             }
+                            let receive = peer
+                                .offer_receive_options(state.connection_mode);
+                            peer.media_connections
+                                .add_deferred_recv_transceivers(receive)
+                                .await;
                             let sdp_offer = peer
-                                .peer
-                                .create_offer()
+                                .setup_timings
+                                .measure(
+                                    SetupPhase::CreateOffer,
+                                    peer.peer.create_offer(receive),
+                                )
                                 .await
                                 .map_err(tracerr::map_from_and_wrap!())?;
                             state.local_sdp.unapproved_set(sdp_offer);
                         }
                         NegotiationRole::Answerer(_) => {
+                            peer.media_connections
+                                .correct_transceivers_directions()
+                                .await;
                             let sdp_answer = peer
                                 .peer
                                 .create_answer()
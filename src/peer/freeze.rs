@@ -0,0 +1,244 @@
+//! Detection of frozen remote video of a [`PeerConnection`].
+//!
+//! [`PeerConnection`]: super::PeerConnection
+
+use std::{cell::RefCell, collections::HashMap, time::Duration};
+
+use medea_client_api_proto::stats::{
+    RtcInboundRtpStreamMediaType, RtcStat, RtcStatsType,
+};
+
+use crate::{media::track::remote, platform};
+
+/// Decoding progress of a remote video track.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct Progress {
+    /// Last reported number of the decoded frames.
+    frames_decoded: u64,
+
+    /// Time the [`Progress::frames_decoded`] has changed at last.
+    changed_at: Duration,
+}
+
+/// Detector of remote video tracks of a [`PeerConnection`] that stopped
+/// decoding new frames while being enabled and unmuted.
+///
+/// Only [`remote::Track`]s having `on_freeze` or `on_unfreeze` callbacks set
+/// are watched.
+///
+/// [`PeerConnection`]: super::PeerConnection
+#[derive(Debug, Default)]
+pub struct FreezeDetector(RefCell<HashMap<String, Progress>>);
+
+impl FreezeDetector {
+    /// Updates the frozen state of the provided [`remote::Track`]s from the
+    /// provided [`RtcStat`]s.
+    pub fn update(&self, stats: &[RtcStat], tracks: &[remote::Track]) {
+        let mut progress = self.0.borrow_mut();
+        let watched: Vec<_> = tracks
+            .iter()
+            .filter(|t| t.is_freeze_detection_enabled())
+            .collect();
+        progress.retain(|id, _| watched.iter().any(|t| t.id() == *id));
+        if watched.is_empty() {
+            return;
+        }
+
+        let decoded = frames_decoded(stats);
+        let now = platform::now();
+        for track in watched {
+            let id = track.id();
+            if !track.is_active() {
+                drop(progress.remove(&id));
+                track.set_frozen(false);
+                continue;
+            }
+            let Some(&frames_decoded) = decoded.get(&id) else {
+                continue;
+            };
+            let (next, frozen) = detect(
+                progress.get(&id).copied(),
+                frames_decoded,
+                now,
+                track.freeze_timeout(),
+                track.is_frozen(),
+            );
+            drop(progress.insert(id, next));
+            if let Some(frozen) = frozen {
+                track.set_frozen(frozen);
+            }
+        }
+    }
+}
+
+/// Returns the next [`Progress`] of a remote video track, and its new frozen
+/// state if it has changed.
+///
+/// A track is frozen once its `frames_decoded` hasn't changed for the
+/// provided `timeout`, and is unfrozen as soon as it changes again.
+fn detect(
+    prev: Option<Progress>,
+    frames_decoded: u64,
+    now: Duration,
+    timeout: Duration,
+    is_frozen: bool,
+) -> (Progress, Option<bool>) {
+    match prev {
+        Some(prev) if prev.frames_decoded == frames_decoded => {
+            let frozen = now.saturating_sub(prev.changed_at) >= timeout;
+            (prev, (frozen != is_frozen).then_some(frozen))
+        }
+        Some(_) => (
+            Progress {
+                frames_decoded,
+                changed_at: now,
+            },
+            is_frozen.then_some(false),
+        ),
+        None => (
+            Progress {
+                frames_decoded,
+                changed_at: now,
+            },
+            None,
+        ),
+    }
+}
+
+/// Returns numbers of the decoded frames of the remote video tracks described
+/// by the provided [`RtcStat`]s, keyed by [`id`][1]s of the tracks.
+///
+/// [1]: https://w3.org/TR/mediacapture-streams#dom-mediastreamtrack-id
+fn frames_decoded(stats: &[RtcStat]) -> HashMap<String, u64> {
+    let identifiers: HashMap<_, _> = stats
+        .iter()
+        .filter_map(|stat| {
+            let RtcStatsType::Track(track) = &stat.stats else {
+                return None;
+            };
+            Some((&stat.id.0, &track.track_identifier))
+        })
+        .collect();
+    stats
+        .iter()
+        .filter_map(|stat| {
+            let RtcStatsType::InboundRtp(inbound) = &stat.stats else {
+                return None;
+            };
+            let RtcInboundRtpStreamMediaType::Video {
+                frames_decoded: Some(frames_decoded),
+                ..
+            } = inbound.media_specific_stats
+            else {
+                return None;
+            };
+            let id = inbound.track_identifier.as_ref().or_else(|| {
+                inbound
+                    .track_id
+                    .as_ref()
+                    .and_then(|id| identifiers.get(id).copied())
+            })?;
+            Some((id.clone(), frames_decoded))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod spec {
+    use std::time::Duration;
+
+    use crate::peer::stats_fixture::stats;
+
+    use super::{detect, frames_decoded, Progress};
+
+    /// Freeze timeout used in the tests.
+    const TIMEOUT: Duration = Duration::from_secs(2);
+
+    #[test]
+    fn freezes_once_frames_stall_for_timeout() {
+        let (progress, change) =
+            detect(None, 10, Duration::from_secs(0), TIMEOUT, false);
+        assert_eq!(change, None);
+
+        let (progress, change) =
+            detect(Some(progress), 20, Duration::from_secs(1), TIMEOUT, false);
+        assert_eq!(change, None);
+        assert_eq!(
+            progress,
+            Progress {
+                frames_decoded: 20,
+                changed_at: Duration::from_secs(1),
+            },
+        );
+
+        let (progress, change) =
+            detect(Some(progress), 20, Duration::from_secs(2), TIMEOUT, false);
+        assert_eq!(change, None);
+
+        let (progress, change) =
+            detect(Some(progress), 20, Duration::from_secs(3), TIMEOUT, false);
+        assert_eq!(change, Some(true));
+
+        let (progress, change) =
+            detect(Some(progress), 20, Duration::from_secs(4), TIMEOUT, true);
+        assert_eq!(change, None);
+
+        let (_, change) =
+            detect(Some(progress), 21, Duration::from_secs(5), TIMEOUT, true);
+        assert_eq!(change, Some(false));
+    }
+
+    #[test]
+    fn doesnt_unfreeze_without_baseline() {
+        let (_, change) =
+            detect(None, 10, Duration::from_secs(0), TIMEOUT, true);
+        assert_eq!(change, None);
+    }
+
+    #[test]
+    fn resolves_track_identifiers() {
+        let stats = stats(serde_json::json!([
+            {
+                "id": "in1",
+                "timestamp": 1.0,
+                "type": "inbound-rtp",
+                "mediaType": "video",
+                "trackIdentifier": "track1",
+                "framesDecoded": 10,
+                "bytesReceived": 300,
+                "packetsReceived": 3
+            },
+            {
+                "id": "in2",
+                "timestamp": 1.0,
+                "type": "inbound-rtp",
+                "mediaType": "video",
+                "trackId": "T02",
+                "framesDecoded": 20,
+                "bytesReceived": 300,
+                "packetsReceived": 3
+            },
+            {
+                "id": "T02",
+                "timestamp": 1.0,
+                "type": "track",
+                "trackIdentifier": "track2"
+            },
+            {
+                "id": "in3",
+                "timestamp": 1.0,
+                "type": "inbound-rtp",
+                "mediaType": "audio",
+                "trackIdentifier": "track3",
+                "bytesReceived": 300,
+                "packetsReceived": 3
+            }
+        ]));
+
+        let decoded = frames_decoded(&stats);
+
+        assert_eq!(decoded.len(), 2);
+        assert_eq!(decoded["track1"], 10);
+        assert_eq!(decoded["track2"], 20);
+    }
+}
@@ -0,0 +1,292 @@
+//! Mapping of the [`mid`]s to the stable IDs of the transceiver-associated
+//! state.
+//!
+//! [`mid`]: https://w3.org/TR/webrtc#dom-rtptransceiver-mid
+
+use std::collections::{BTreeMap, HashMap};
+
+use derive_more::Display;
+use medea_client_api_proto::TrackId;
+
+/// Stable internal ID of a [`platform::Transceiver`]-associated state.
+///
+/// Unlike [`mid`]s, is never reused within a [`MediaConnections`].
+///
+/// [`MediaConnections`]: super::MediaConnections
+/// [`platform::Transceiver`]: crate::platform::Transceiver
+/// [`mid`]: https://w3.org/TR/webrtc#dom-rtptransceiver-mid
+#[derive(Clone, Copy, Debug, Display, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct TransceiverId(u32);
+
+/// Remap of a [`mid`] to a new [`TransceiverId`].
+///
+/// Happens once a browser reuses the [`mid`] of a stopped
+/// [`platform::Transceiver`] for a new one.
+///
+/// [`platform::Transceiver`]: crate::platform::Transceiver
+/// [`mid`]: https://w3.org/TR/webrtc#dom-rtptransceiver-mid
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MidRemap {
+    /// Reused [`mid`].
+    ///
+    /// [`mid`]: https://w3.org/TR/webrtc#dom-rtptransceiver-mid
+    pub mid: String,
+
+    /// [`TransceiverId`] the [`mid`] was mapped to before.
+    ///
+    /// [`mid`]: https://w3.org/TR/webrtc#dom-rtptransceiver-mid
+    pub from: TransceiverId,
+
+    /// [`TransceiverId`] the [`mid`] is mapped to now.
+    ///
+    /// [`mid`]: https://w3.org/TR/webrtc#dom-rtptransceiver-mid
+    pub to: TransceiverId,
+}
+
+/// Mapping of the [`mid`]s of the [`platform::Transceiver`]s to their
+/// [`TransceiverId`]s, refreshed after every negotiation.
+///
+/// [`platform::Transceiver`]: crate::platform::Transceiver
+/// [`mid`]: https://w3.org/TR/webrtc#dom-rtptransceiver-mid
+#[derive(Debug, Default)]
+pub struct MidMap {
+    /// Last [`TransceiverId`]s of all the ever negotiated [`mid`]s.
+    ///
+    /// [`mid`]: https://w3.org/TR/webrtc#dom-rtptransceiver-mid
+    mids: HashMap<String, TransceiverId>,
+
+    /// [`TransceiverId`]s of the live [`Sender`]s and [`Receiver`]s.
+    ///
+    /// [`Receiver`]: super::Receiver
+    /// [`Sender`]: super::Sender
+    tracks: HashMap<TrackId, TransceiverId>,
+
+    /// Last allocated [`TransceiverId`].
+    last_id: u32,
+}
+
+impl MidMap {
+    /// Returns [`TransceiverId`] the provided [`TrackId`] is mapped to, if
+    /// any.
+    ///
+    /// [`TrackId`]s of the [`Sender`]s and [`Receiver`]s whose
+    /// [`platform::Transceiver`]s are stopped or aren't negotiated yet aren't
+    /// mapped.
+    ///
+    /// [`platform::Transceiver`]: crate::platform::Transceiver
+    /// [`Receiver`]: super::Receiver
+    /// [`Sender`]: super::Sender
+    #[must_use]
+    pub fn id(&self, track_id: TrackId) -> Option<TransceiverId> {
+        self.tracks.get(&track_id).copied()
+    }
+
+    /// Returns the last [`TransceiverId`] the provided [`mid`] has been mapped
+    /// to, if any.
+    ///
+    /// [`mid`]: https://w3.org/TR/webrtc#dom-rtptransceiver-mid
+    #[must_use]
+    pub fn id_by_mid(&self, mid: &str) -> Option<TransceiverId> {
+        self.mids.get(mid).copied()
+    }
+
+    /// Refreshes this [`MidMap`] with the provided [`TrackId`]s of the
+    /// [`Sender`]s and [`Receiver`]s, along with the [`mid`]s of their live
+    /// [`platform::Transceiver`]s, unmapping all the other [`TrackId`]s.
+    ///
+    /// A [`mid`] keeps its [`TransceiverId`] while any of its [`TrackId`]s
+    /// keeps it too. Otherwise, it's a [`mid`] reused for a new
+    /// [`platform::Transceiver`], so it's remapped to a new [`TransceiverId`]
+    /// and returned in a [`MidRemap`].
+    ///
+    /// [`platform::Transceiver`]: crate::platform::Transceiver
+    /// [`Receiver`]: super::Receiver
+    /// [`Sender`]: super::Sender
+    /// [`mid`]: https://w3.org/TR/webrtc#dom-rtptransceiver-mid
+    pub fn refresh(
+        &mut self,
+        live: impl IntoIterator<Item = (TrackId, String)>,
+    ) -> Vec<MidRemap> {
+        let mut by_mid: BTreeMap<String, Vec<TrackId>> = BTreeMap::new();
+        for (track_id, mid) in live {
+            by_mid.entry(mid).or_default().push(track_id);
+        }
+
+        let mut tracks = HashMap::new();
+        let mut remaps = Vec::new();
+        for (mid, track_ids) in by_mid {
+            let id = match self.mids.get(&mid).copied() {
+                Some(id)
+                    if track_ids.iter().any(|t| self.id(*t) == Some(id)) =>
+                {
+                    id
+                }
+                Some(from) => {
+                    let to = self.next_id();
+                    remaps.push(MidRemap {
+                        mid: mid.clone(),
+                        from,
+                        to,
+                    });
+                    to
+                }
+                None => self.next_id(),
+            };
+            tracks.extend(track_ids.into_iter().map(|t| (t, id)));
+            drop(self.mids.insert(mid, id));
+        }
+        self.tracks = tracks;
+
+        remaps
+    }
+
+    /// Allocates a new [`TransceiverId`].
+    fn next_id(&mut self) -> TransceiverId {
+        self.last_id += 1;
+        TransceiverId(self.last_id)
+    }
+}
+
+#[cfg(test)]
+mod spec {
+    use medea_client_api_proto::TrackId;
+
+    use super::{MidMap, MidRemap};
+
+    /// Local SDP offer publishing audio, device video and display video.
+    const INITIAL_OFFER: &str = "v=0\r\n\
+                                 m=audio 9 UDP/TLS/RTP/SAVPF 111\r\n\
+                                 a=mid:0\r\n\
+                                 a=sendonly\r\n\
+                                 m=video 9 UDP/TLS/RTP/SAVPF 96\r\n\
+                                 a=mid:1\r\n\
+                                 a=sendonly\r\n\
+                                 m=video 9 UDP/TLS/RTP/SAVPF 96\r\n\
+                                 a=mid:2\r\n\
+                                 a=sendonly\r\n";
+
+    /// Local SDP offer after the display video [`platform::Transceiver`] is
+    /// stopped.
+    ///
+    /// [`platform::Transceiver`]: crate::platform::Transceiver
+    const DISPLAY_STOPPED_OFFER: &str = "v=0\r\n\
+                                         m=audio 9 UDP/TLS/RTP/SAVPF 111\r\n\
+                                         a=mid:0\r\n\
+                                         a=sendonly\r\n\
+                                         m=video 9 UDP/TLS/RTP/SAVPF 96\r\n\
+                                         a=mid:1\r\n\
+                                         a=sendonly\r\n\
+                                         m=video 0 UDP/TLS/RTP/SAVPF 96\r\n\
+                                         a=mid:2\r\n\
+                                         a=inactive\r\n";
+
+    /// Local SDP offer after the display video is re-enabled, with its new
+    /// [`platform::Transceiver`] recycling the stopped [m-line][1] and
+    /// reusing its `mid`.
+    ///
+    /// [`platform::Transceiver`]: crate::platform::Transceiver
+    /// [1]: https://tools.ietf.org/html/rfc4566#section-5.14
+    const DISPLAY_READDED_OFFER: &str = INITIAL_OFFER;
+
+    /// Returns `mid`s of the [m-line][1]s of the provided SDP, omitting the
+    /// rejected ones (having zero port).
+    ///
+    /// [1]: https://tools.ietf.org/html/rfc4566#section-5.14
+    fn live_mids(sdp: &str) -> Vec<String> {
+        let mut mids = Vec::new();
+        let mut is_live = false;
+        for line in sdp.lines() {
+            if let Some(media) = line.strip_prefix("m=") {
+                is_live = media.split(' ').nth(1) != Some("0");
+            } else if let Some(mid) = line.strip_prefix("a=mid:") {
+                if is_live {
+                    mids.push(mid.to_owned());
+                }
+            }
+        }
+        mids
+    }
+
+    /// Refreshes the provided [`MidMap`] with the live `mid`s of the provided
+    /// SDP, assigned to the provided [`TrackId`]s in order.
+    fn negotiate(map: &mut MidMap, sdp: &str, tracks: &[u32]) -> Vec<MidRemap> {
+        let mids = live_mids(sdp);
+        assert_eq!(mids.len(), tracks.len(), "{mids:?}");
+        map.refresh(tracks.iter().map(|t| TrackId(*t)).zip(mids))
+    }
+
+    #[test]
+    fn keeps_ids_across_renegotiations() {
+        let mut map = MidMap::default();
+        assert!(negotiate(&mut map, INITIAL_OFFER, &[1, 2, 3]).is_empty());
+        let ids: Vec<_> = (1..=3).map(|t| map.id(TrackId(t))).collect();
+
+        assert!(negotiate(&mut map, INITIAL_OFFER, &[1, 2, 3]).is_empty());
+        assert_eq!(
+            (1..=3).map(|t| map.id(TrackId(t))).collect::<Vec<_>>(),
+            ids,
+        );
+        assert!(ids.iter().all(Option::is_some));
+        assert_ne!(ids[0], ids[1]);
+        assert_ne!(ids[1], ids[2]);
+    }
+
+    #[test]
+    fn remaps_reused_mid() {
+        let mut map = MidMap::default();
+        assert!(negotiate(&mut map, INITIAL_OFFER, &[1, 2, 3]).is_empty());
+        let display = map.id(TrackId(3)).unwrap();
+        assert_eq!(map.id_by_mid("2"), Some(display));
+
+        assert!(negotiate(&mut map, DISPLAY_STOPPED_OFFER, &[1, 2]).is_empty());
+        assert_eq!(map.id(TrackId(3)), None);
+
+        let remaps = negotiate(&mut map, DISPLAY_READDED_OFFER, &[1, 2, 4]);
+        let readded = map.id(TrackId(4)).unwrap();
+        assert_eq!(
+            remaps,
+            [MidRemap {
+                mid: "2".into(),
+                from: display,
+                to: readded,
+            }],
+        );
+        assert_eq!(map.id(TrackId(3)), None);
+        assert_eq!(map.id_by_mid("2"), Some(readded));
+    }
+
+    #[test]
+    fn reports_mid_reused_without_stopping() {
+        let mut map = MidMap::default();
+        assert!(negotiate(&mut map, INITIAL_OFFER, &[1, 2, 3]).is_empty());
+        let display = map.id(TrackId(3)).unwrap();
+
+        let remaps = negotiate(&mut map, DISPLAY_READDED_OFFER, &[1, 2, 4]);
+        let readded = map.id(TrackId(4)).unwrap();
+        assert_eq!(
+            remaps,
+            [MidRemap {
+                mid: "2".into(),
+                from: display,
+                to: readded,
+            }],
+        );
+        assert_eq!(map.id(TrackId(3)), None);
+        assert_eq!(map.id(TrackId(1)), map.id_by_mid("0"));
+    }
+
+    #[test]
+    fn keeps_id_for_receiver_joining_mid() {
+        let mut map = MidMap::default();
+        assert!(negotiate(&mut map, INITIAL_OFFER, &[1, 2, 3]).is_empty());
+        let audio = map.id(TrackId(1));
+
+        let remaps = map.refresh([
+            (TrackId(1), "0".into()),
+            (TrackId(5), "0".into()),
+            (TrackId(2), "1".into()),
+        ]);
+        assert!(remaps.is_empty());
+        assert_eq!(map.id(TrackId(5)), audio);
+    }
+}
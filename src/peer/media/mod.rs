@@ -2,6 +2,7 @@
 //!
 //! [`PeerConnection`]: crate::peer::PeerConnection
 
+mod mid_map;
 pub mod receiver;
 pub mod sender;
 mod transitable_state;
@@ -16,20 +17,26 @@ use futures::{
 use medea_client_api_proto as proto;
 #[cfg(feature = "mockable")]
 use medea_client_api_proto::{ConnectionMode, MediaType, MemberId};
-use proto::{MediaSourceKind, TrackId};
+use proto::{MediaSourceKind, PeerId, TrackId};
 use tracerr::Traced;
 
 #[cfg(feature = "mockable")]
 use crate::media::{LocalTracksConstraints, RecvConstraints};
 use crate::{
-    media::{track::local, MediaKind},
-    peer::{LocalStreamUpdateCriteria, PeerEvent},
+    error_report::NonFatalErrorKind,
+    media::{
+        track::{local, remote},
+        MediaKind,
+    },
+    peer::{report_error, LocalStreamUpdateCriteria, PeerEvent},
     platform,
     utils::{Caused, Component},
 };
 
 use super::tracks_request::TracksRequest;
 
+use self::mid_map::{MidMap, MidRemap, TransceiverId};
+
 #[doc(inline)]
 pub use self::{
     receiver::Receiver,
@@ -84,6 +91,19 @@ pub trait MediaStateControllable {
         self.mute_state_controller().state()
     }
 
+    /// Indicates whether the current [`MediaState`] of the same kind as the
+    /// provided one has been forced by a media server, rather than intended
+    /// locally.
+    #[must_use]
+    fn is_media_state_forced(&self, state: MediaState) -> bool {
+        match state {
+            MediaState::Mute(_) => self.mute_state_controller().is_forced(),
+            MediaState::MediaExchange(_) => {
+                self.media_exchange_state_controller().is_forced()
+            }
+        }
+    }
+
     /// Sets current [`MediaState`] to [`TransitableState::Transition`].
     ///
     /// # Errors
@@ -195,7 +215,7 @@ pub trait MediaStateControllable {
 }
 
 /// Direction of the `MediaTrack`.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub enum TrackDirection {
     /// Sends media data.
     Send,
@@ -241,6 +261,39 @@ pub enum GetMidsError {
     ReceiversWithoutMid,
 }
 
+/// Snapshot of a single [`platform::Transceiver`] state, used for debugging
+/// purposes.
+#[derive(Clone, Debug)]
+pub struct TransceiverInfo {
+    /// [`PeerId`] of the [`PeerConnection`] owning the
+    /// [`platform::Transceiver`].
+    ///
+    /// [`PeerConnection`]: crate::peer::PeerConnection
+    pub peer_id: PeerId,
+
+    /// [`mid`] of the [`platform::Transceiver`].
+    ///
+    /// [`mid`]: https://w3.org/TR/webrtc#dom-rtptransceiver-mid
+    pub mid: String,
+
+    /// [`MediaKind`] of the [`platform::Transceiver`].
+    pub kind: MediaKind,
+
+    /// Negotiated direction of the [`platform::Transceiver`], if any.
+    pub direction: Option<platform::TransceiverDirection>,
+
+    /// [`TrackId`] of the [`Sender`] using the [`platform::Transceiver`], if
+    /// any.
+    pub sender_track_id: Option<TrackId>,
+
+    /// [`TrackId`] of the [`Receiver`] using the [`platform::Transceiver`],
+    /// if any.
+    pub receiver_track_id: Option<TrackId>,
+
+    /// Indicator whether the [`platform::Transceiver`] is stopped.
+    pub is_stopped: bool,
+}
+
 /// Actual data of [`MediaConnections`] storage.
 #[derive(Debug)]
 struct InnerMediaConnections {
@@ -257,6 +310,12 @@ struct InnerMediaConnections {
 
     /// [`TrackId`] to its [`receiver::Component`].
     receivers: HashMap<TrackId, receiver::Component>,
+
+    /// [`TransceiverId`]s of the [`Sender`]s and [`Receiver`]s, being the
+    /// stable keys of their transceiver-associated state, unlike [`mid`]s.
+    ///
+    /// [`mid`]: https://w3.org/TR/webrtc#dom-rtptransceiver-mid
+    mids: MidMap,
 }
 
 impl InnerMediaConnections {
@@ -313,6 +372,51 @@ impl InnerMediaConnections {
         }
     }
 
+    /// Returns [`TrackId`]s of the [`Sender`]s and [`Receiver`]s respectively,
+    /// along with the [`mid`]s of their [`platform::Transceiver`]s.
+    ///
+    /// Stopped [`platform::Transceiver`]s are omitted, since their [`mid`]s
+    /// may be reused by the new ones already.
+    ///
+    /// [`mid`]: https://w3.org/TR/webrtc#dom-rtptransceiver-mid
+    fn live_mids(&self) -> [Vec<(TrackId, String)>; 2] {
+        let senders = self
+            .senders
+            .iter()
+            .filter(|(_, s)| !s.transceiver().is_stopped())
+            .filter_map(|(&id, s)| s.mid().map(|mid| (id, mid)))
+            .collect();
+        let receivers = self
+            .receivers
+            .iter()
+            .filter(|(_, r)| !r.transceiver().map_or(false, |t| t.is_stopped()))
+            .filter_map(|(&id, r)| r.mid().map(|mid| (id, mid)))
+            .collect();
+        [senders, receivers]
+    }
+
+    /// Indicates whether the [`Receiver`] with the provided [`TrackId`] may
+    /// own the provided [`mid`] now.
+    ///
+    /// A [`Receiver`] with a stopped [`platform::Transceiver`], or mapped to a
+    /// [`TransceiverId`] the [`mid`] isn't mapped to anymore, is a stale one,
+    /// whose [`mid`] has been reused for a new [`platform::Transceiver`].
+    ///
+    /// [`mid`]: https://w3.org/TR/webrtc#dom-rtptransceiver-mid
+    fn is_receiver_of_mid(
+        &self,
+        track_id: TrackId,
+        receiver: &receiver::Component,
+        mid: &str,
+    ) -> bool {
+        receiver.mid().as_deref() == Some(mid)
+            && !receiver.transceiver().map_or(false, |t| t.is_stopped())
+            && self
+                .mids
+                .id(track_id)
+                .map_or(true, |id| self.mids.id_by_mid(mid) == Some(id))
+    }
+
     /// Creates a [`platform::Transceiver`] and adds it to the
     /// [`platform::RtcPeerConnection`].
     fn add_transceiver(
@@ -352,6 +456,7 @@ impl MediaConnections {
             peer_events_sender,
             senders: HashMap::new(),
             receivers: HashMap::new(),
+            mids: MidMap::default(),
         }))
     }
 
@@ -433,6 +538,10 @@ impl MediaConnections {
             );
         }
         for (track_id, receiver) in &inner.receivers {
+            if receiver.mid().is_none() && receiver.transceiver().is_none() {
+                // Deferred `Receiver` isn't offered, so has no `mid`.
+                continue;
+            }
             drop(
                 mids.insert(
                     *track_id,
@@ -446,6 +555,85 @@ impl MediaConnections {
         Ok(mids)
     }
 
+    /// Creates [`platform::Transceiver`]s for the [`Receiver`]s deferred by
+    /// the [`Receiver::new()`], if their [`MediaKind`] is received according
+    /// to the provided [`platform::OfferReceiveOptions`].
+    ///
+    /// Should be called before creating a local SDP offer, so it contains
+    /// [m-line][1]s for the enabled [`MediaKind`]s.
+    ///
+    /// [1]: https://tools.ietf.org/html/rfc4566#section-5.14
+    pub async fn add_deferred_recv_transceivers(
+        &self,
+        receive: platform::OfferReceiveOptions,
+    ) {
+        let deferred: Vec<_> = self
+            .0
+            .borrow()
+            .receivers
+            .values()
+            .filter(|r| {
+                r.mid().is_none()
+                    && r.transceiver().is_none()
+                    && receive.is_receiving(r.caps().media_kind())
+            })
+            .map(|r| (r.obj(), r.state().enabled_individual()))
+            .collect();
+
+        for (receiver, enabled) in deferred {
+            let transceiver = self.0.borrow().add_transceiver(
+                receiver.caps().media_kind(),
+                platform::TransceiverDirection::INACTIVE,
+            );
+            let transceiver = transceiver.await;
+            transceiver.set_recv(enabled).await;
+            receiver.set_transceiver(transceiver);
+        }
+    }
+
+    /// Sets directions of the live [`platform::Transceiver`]s having [`mid`]s
+    /// to match the media exchange intended by the [`Sender`]s and
+    /// [`Receiver`]s from these [`MediaConnections`].
+    ///
+    /// Should be called before creating a local SDP answer, since
+    /// [`platform::Transceiver`]s reused for a remote SDP offer might be left
+    /// in a direction sending (or receiving) media not intended to.
+    ///
+    /// [`mid`]: https://w3.org/TR/webrtc#dom-rtptransceiver-mid
+    pub fn correct_transceivers_directions(
+        &self,
+    ) -> impl Future<Output = ()> + 'static {
+        let inner = self.0.borrow();
+        let [senders, receivers] = inner.live_mids();
+        let mut intents: HashMap<
+            (String, Option<TransceiverId>),
+            (bool, bool),
+        > = HashMap::new();
+        for (track_id, mid) in senders {
+            let key = (mid, inner.mids.id(track_id));
+            intents.entry(key).or_default().0 =
+                inner.senders[&track_id].state().is_sending_intended();
+        }
+        for (track_id, mid) in receivers {
+            let key = (mid, inner.mids.id(track_id));
+            intents.entry(key).or_default().1 =
+                inner.receivers[&track_id].state().is_receiving_intended();
+        }
+
+        future::join_all(intents.into_iter().map(|((mid, _), (send, recv))| {
+            let transceiver = inner.peer.get_transceiver_by_mid(mid);
+            async move {
+                if let Some(t) = transceiver.await {
+                    if !t.is_stopped() {
+                        t.set_send(send).await;
+                        t.set_recv(recv).await;
+                    }
+                }
+            }
+        }))
+        .map(drop)
+    }
+
     /// Returns activity statuses of the all the [`Sender`]s and [`Receiver`]s
     /// from these [`MediaConnections`].
     pub fn get_transceivers_statuses(
@@ -470,6 +658,73 @@ impl MediaConnections {
         future::join_all(transceivers).map(|r| r.into_iter().collect())
     }
 
+    /// Returns [`TransceiverInfo`]s of all the [`platform::Transceiver`]s used
+    /// by the [`Sender`]s and [`Receiver`]s from these [`MediaConnections`],
+    /// sorted by their [`mid`]s.
+    ///
+    /// [`platform::Transceiver`]s without a [`mid`] aren't negotiated yet, so
+    /// are omitted.
+    ///
+    /// [`mid`]: https://w3.org/TR/webrtc#dom-rtptransceiver-mid
+    pub fn get_transceivers_info(
+        &self,
+        peer_id: PeerId,
+    ) -> impl Future<Output = Vec<TransceiverInfo>> + 'static {
+        let inner = self.0.borrow();
+        let mut by_id: HashMap<
+            (String, Option<TransceiverId>),
+            (platform::Transceiver, TransceiverInfo),
+        > = HashMap::new();
+        let sides = inner
+            .senders
+            .iter()
+            .map(|(&id, s)| {
+                (id, s.state().kind(), Some(s.obj().transceiver()), true)
+            })
+            .chain(inner.receivers.iter().map(|(&id, r)| {
+                (id, r.state().kind(), r.obj().transceiver(), false)
+            }));
+        for (track_id, kind, transceiver, is_sender) in sides {
+            let Some(transceiver) = transceiver else {
+                continue;
+            };
+            let Some(mid) = transceiver.mid() else {
+                continue;
+            };
+            // Stale sides of the stopped `platform::Transceiver`s aren't
+            // mapped, so aren't merged with the ones reusing their `mid`.
+            let key = (mid.clone(), inner.mids.id(track_id));
+            let (_, info) = by_id.entry(key).or_insert_with(|| {
+                let info = TransceiverInfo {
+                    peer_id,
+                    mid,
+                    kind,
+                    direction: None,
+                    sender_track_id: None,
+                    receiver_track_id: None,
+                    is_stopped: transceiver.is_stopped(),
+                };
+                (transceiver, info)
+            });
+            if is_sender {
+                info.sender_track_id = Some(track_id);
+            } else {
+                info.receiver_track_id = Some(track_id);
+            }
+        }
+
+        future::join_all(by_id.into_values().map(
+            |(transceiver, info)| async move {
+                let direction = transceiver.current_direction().await;
+                TransceiverInfo { direction, ..info }
+            },
+        ))
+        .map(|mut infos| {
+            infos.sort_by(|a, b| a.mid.cmp(&b.mid));
+            infos
+        })
+    }
+
     /// Returns [`Rc`] to [`TransceiverSide`] with a provided [`TrackId`].
     ///
     /// Returns `None` if [`TransceiverSide`] with a provided [`TrackId`]
@@ -598,6 +853,43 @@ impl MediaConnections {
         Ok(media_exchange_state_updates)
     }
 
+    /// Refreshes [`TransceiverId`]s of the [`Sender`]s and [`Receiver`]s from
+    /// these [`MediaConnections`] with the [`mid`]s of their live
+    /// [`platform::Transceiver`]s.
+    ///
+    /// Should be called after every negotiation. Logs the [`mid`]s reused for
+    /// the new [`platform::Transceiver`]s, and reports the ones shared by
+    /// multiple [`Sender`]s or [`Receiver`]s, which is not supposed to happen,
+    /// via a [`PeerEvent::NonFatalError`].
+    ///
+    /// [`mid`]: https://w3.org/TR/webrtc#dom-rtptransceiver-mid
+    pub fn refresh_mids(&self) {
+        let mut inner = self.0.borrow_mut();
+        let sides = inner.live_mids();
+        for side in &sides {
+            let mut owners = HashMap::with_capacity(side.len());
+            for (track_id, mid) in side {
+                if let Some(other) = owners.insert(mid, track_id) {
+                    report_error(
+                        &inner.peer_events_sender,
+                        NonFatalErrorKind::Negotiation,
+                        tracerr::new!(format!(
+                            "Tracks {other} and {track_id} share `mid` {mid}",
+                        )),
+                    );
+                }
+            }
+        }
+
+        let remaps = inner.mids.refresh(sides.into_iter().flatten());
+        for MidRemap { mid, from, to } in remaps {
+            log::warn!(
+                "`mid` {mid} of a stopped transceiver is reused by a new one: \
+                 remapped from transceiver {from} to {to}",
+            );
+        }
+    }
+
     /// Adds a new track to the corresponding [`Receiver`].
     ///
     /// # Errors
@@ -620,13 +912,14 @@ impl MediaConnections {
         // Cannot fail, since transceiver is guaranteed to be negotiated at this
         // point.
         let mid = transceiver.mid().ok_or("No Transceiver::mid found")?;
-        let receiver = self
-            .0
-            .borrow()
-            .receivers
-            .values()
-            .find(|rcvr| rcvr.mid().as_ref() == Some(&mid))
-            .map(Component::obj);
+        let receiver = {
+            let inner = self.0.borrow();
+            inner
+                .receivers
+                .iter()
+                .find(|(id, r)| inner.is_receiver_of_mid(**id, r, &mid))
+                .map(|(_, r)| Component::obj(r))
+        };
 
         if let Some(rcvr) = receiver {
             rcvr.set_remote_track(transceiver, track).await;
@@ -938,6 +1231,17 @@ impl MediaConnections {
         Ok(())
     }
 
+    /// Returns [`remote::Track`]s of all the underlying [`Receiver`]s having
+    /// them.
+    pub fn get_remote_tracks(&self) -> Vec<remote::Track> {
+        self.0
+            .borrow()
+            .receivers
+            .values()
+            .filter_map(|r| r.obj().track())
+            .collect()
+    }
+
     /// Returns all underlying [`Sender`]'s.
     pub fn get_senders(&self) -> Vec<Rc<Sender>> {
         self.0
@@ -2,7 +2,7 @@
 
 use std::{iter, rc::Rc};
 
-use futures::StreamExt as _;
+use futures::{future, stream, stream::LocalBoxStream, StreamExt as _};
 use medea_client_api_proto as proto;
 use medea_client_api_proto::{
     MediaSourceKind, MediaType, MemberId, TrackId, TrackPatchEvent,
@@ -15,7 +15,10 @@ use medea_reactive::{
 use proto::ConnectionMode;
 
 use crate::{
-    media::{LocalTracksConstraints, MediaDirection, MediaKind},
+    connection::MemberMediaState,
+    media::{
+        DisplaySurface, LocalTracksConstraints, MediaDirection, MediaKind,
+    },
     peer::{
         component::SyncState,
         media::{transitable_state::media_exchange_state, InTransition},
@@ -75,6 +78,12 @@ pub struct State {
     /// [`remote::Track`]: crate::media::track::remote::Track
     muted: ObservableCell<bool>,
 
+    /// [`DisplaySurface`] captured by the sender of the [`Receiver`]'s
+    /// [`remote::Track`], if it has been reported already.
+    ///
+    /// [`remote::Track`]: crate::media::track::remote::Track
+    display_surface: ObservableCell<Option<DisplaySurface>>,
+
     /// Indicator whether this [`Receiver`] is working in a [P2P mesh] or [SFU]
     /// mode.
     ///
@@ -96,7 +105,7 @@ impl AsProtoState for State {
             mid: self.mid.clone(),
             media_type: self.media_type,
             sender_id: self.sender_id.clone(),
-            muted: false,
+            muted: self.muted.get(),
             media_direction: self.media_direction().into(),
         }
     }
@@ -122,6 +131,7 @@ impl SynchronizableState for State {
                 ),
             ),
             muted: ObservableCell::new(input.muted),
+            display_surface: ObservableCell::new(None),
             media_direction: ObservableCell::new(input.media_direction.into()),
             connection_mode: input.connection_mode,
             sync_state: ObservableCell::new(SyncState::Synced),
@@ -147,6 +157,7 @@ impl SynchronizableState for State {
             input.media_direction.is_enabled_general(),
         ));
         self.media_direction.set(input.media_direction.into());
+        self.muted.set(input.muted);
 
         self.sync_state.set(SyncState::Synced);
     }
@@ -230,6 +241,7 @@ impl State {
                 media_direction.is_enabled_general().into(),
             ),
             muted: ObservableCell::new(muted),
+            display_surface: ObservableCell::new(None),
             sync_state: ObservableCell::new(SyncState::Synced),
             connection_mode,
             media_direction: ObservableCell::new(media_direction.into()),
@@ -279,12 +291,74 @@ impl State {
         self.muted.get()
     }
 
+    /// Indicates whether the [`Receiver`] of this [`State`] is intended to
+    /// receive media, so its [`Transceiver`] should have a [`RECV`]
+    /// direction.
+    ///
+    /// [`RECV`]: crate::platform::TransceiverDirection::RECV
+    /// [`Transceiver`]: crate::platform::Transceiver
+    #[must_use]
+    pub fn is_receiving_intended(&self) -> bool {
+        // All receivers are always on in SFU mode.
+        self.connection_mode == ConnectionMode::Sfu || self.enabled_individual()
+    }
+
+    /// Returns the current [`DisplaySurface`] of this [`State`], if it has
+    /// been reported already.
+    #[must_use]
+    pub fn display_surface(&self) -> Option<DisplaySurface> {
+        self.display_surface.get()
+    }
+
     /// Returns the current general [`MediaDirection`] of this [`State`].
     #[must_use]
     pub fn media_direction(&self) -> MediaDirection {
         self.media_direction.get()
     }
 
+    /// Returns the current [`MemberMediaState`]s of the media exchange and
+    /// mute states controlled by the sender of this [`State`].
+    #[must_use]
+    pub fn member_media_state(&self) -> (MemberMediaState, MemberMediaState) {
+        (
+            MemberMediaState::from_enabled(is_send_enabled(
+                self.media_direction.get(),
+            )),
+            MemberMediaState::from_muted(self.muted.get()),
+        )
+    }
+
+    /// Returns a [`LocalBoxStream`] of the [`MemberMediaState`] changes made
+    /// by the sender of this [`State`].
+    ///
+    /// Driven by the same changes the [`remote::Track`]'s callbacks are
+    /// invoked on.
+    ///
+    /// [`remote::Track`]: crate::media::track::remote::Track
+    pub fn on_member_media_state_change(
+        &self,
+    ) -> LocalBoxStream<'static, MemberMediaState> {
+        let enabled_changes = self
+            .media_direction
+            .subscribe()
+            .map(is_send_enabled)
+            .scan(None, |prev, enabled| {
+                // The first value is the current one, so isn't a change.
+                let is_changed =
+                    prev.replace(enabled).map_or(false, |p| p != enabled);
+                future::ready(Some(is_changed.then_some(enabled)))
+            })
+            .filter_map(future::ready)
+            .map(MemberMediaState::from_enabled);
+        let muted_changes = self
+            .muted
+            .subscribe()
+            .skip(1)
+            .map(MemberMediaState::from_muted);
+
+        stream::select(enabled_changes, muted_changes).boxed_local()
+    }
+
     /// Updates this [`State`] with the provided [`TrackPatchEvent`].
     pub fn update(&self, track_patch: &TrackPatchEvent) {
         if self.id != track_patch.id {
@@ -303,6 +377,9 @@ impl State {
         if let Some(direction) = track_patch.media_direction {
             self.media_direction.set(direction.into());
         }
+        if let Some(surface) = track_patch.display_surface {
+            self.display_surface.set(Some(surface.into()));
+        }
     }
 }
 
@@ -370,10 +447,11 @@ impl Component {
     /// updates.
     ///
     /// Sends [`TrackEvent::MediaExchangeIntention`][1] with the provided
-    /// [`media_exchange_state`].
+    /// [`media_exchange_state`]. Rapid updates are coalesced, so only one
+    /// intention awaits a media server acknowledgement at a time.
     ///
     /// [1]: crate::peer::TrackEvent::MediaExchangeIntention
-    #[watch(self.enabled_individual.subscribe_transition())]
+    #[watch(self.enabled_individual.subscribe_intentions())]
     fn enabled_individual_transition_started(
         receiver: &Receiver,
         _: &State,
@@ -394,6 +472,19 @@ impl Component {
         }
     }
 
+    /// Watcher for the [`DisplaySurface`] updates.
+    ///
+    /// Propagates it to the associated [`Receiver`] and its media track (if
+    /// any).
+    #[watch(self.display_surface.subscribe())]
+    fn display_surface_changed(
+        receiver: &Receiver,
+        _: &State,
+        surface: Option<DisplaySurface>,
+    ) {
+        receiver.set_display_surface(surface);
+    }
+
     /// Stops transition timeouts on [`SyncState::Desynced`].
     ///
     /// Sends media state intentions and resets transition timeouts on
@@ -406,8 +497,8 @@ impl Component {
     ) {
         match sync_state {
             SyncState::Synced => {
-                if let MediaExchangeState::Transition(transition) =
-                    state.enabled_individual.state()
+                if let Some(transition) =
+                    state.enabled_individual.resend_intention()
                 {
                     receiver.send_media_exchange_state_intention(transition);
                 }
@@ -496,3 +587,9 @@ impl State {
         self.sync_state.set(SyncState::Synced);
     }
 }
+
+/// Indicates whether the provided [`MediaDirection`] has media enabled on its
+/// sender side.
+fn is_send_enabled(direction: MediaDirection) -> bool {
+    proto::MediaDirection::from(direction).is_send_enabled()
+}
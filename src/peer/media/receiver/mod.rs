@@ -10,8 +10,8 @@ use proto::{ConnectionMode, TrackId};
 
 use crate::{
     media::{
-        track::remote, MediaDirection, MediaKind, RecvConstraints,
-        TrackConstraints,
+        track::remote, DisplaySurface, MediaDirection, MediaKind,
+        RecvConstraints, TrackConstraints,
     },
     peer::{
         media::media_exchange_state, MediaConnections, MediaStateControllable,
@@ -78,6 +78,10 @@ pub struct Receiver {
     /// Indicator whether this [`remote::Track`] is muted.
     muted: Cell<bool>,
 
+    /// [`DisplaySurface`] captured by the sender of this [`remote::Track`], if
+    /// it has been reported already.
+    display_surface: Cell<Option<DisplaySurface>>,
+
     /// Channel for sending [`PeerEvent`]s to the remote peer.
     peer_events_sender: mpsc::UnboundedSender<PeerEvent>,
 
@@ -94,11 +98,19 @@ impl Receiver {
     /// [`TransceiverDirection::INACTIVE`][1] if `enabled_individual` is
     /// `false`.
     ///
+    /// In a [`ConnectionMode::Mesh`] no [`platform::Transceiver`] is created
+    /// if there is no send one to reuse and media of this [`Receiver`] is
+    /// disabled in the provided [`RecvConstraints`], so local SDP offers
+    /// contain no [m-line][2] for it. Such [`platform::Transceiver`] is created
+    /// later via [`MediaConnections::add_deferred_recv_transceivers()`] once
+    /// its [`MediaKind`] is enabled.
+    ///
     /// `track` field in the created [`Receiver`] will be `None`, since
     /// [`Receiver`] must be created before the actual [`remote::Track`] data
     /// arrives.
     ///
     /// [1]: platform::TransceiverDirection::INACTIVE
+    /// [2]: https://tools.ietf.org/html/rfc4566#section-5.14
     pub async fn new(
         state: &State,
         media_connections: &MediaConnections,
@@ -108,6 +120,8 @@ impl Receiver {
     ) -> Self {
         let caps = TrackConstraints::from(state.media_type());
         let kind = MediaKind::from(&caps);
+        let enabled_in_cons =
+            recv_constraints.is_enabled_for(&state.media_type());
 
         #[allow(clippy::if_then_some_else_none)]
         let transceiver = if state.mid().is_none() {
@@ -125,22 +139,27 @@ impl Receiver {
                 .map(utils::component::Component::obj);
 
             let trnsvr = if let Some(s) = sender {
-                s.transceiver()
+                Some(s.transceiver())
+            } else if connection_mode == ConnectionMode::Mesh
+                && !enabled_in_cons
+            {
+                None
             } else {
                 let new_transceiver =
                     media_connections.0.borrow().add_transceiver(
                         kind,
                         platform::TransceiverDirection::INACTIVE,
                     );
-                new_transceiver.await
+                Some(new_transceiver.await)
             };
-            trnsvr
-                .set_recv(match connection_mode {
+            if let Some(t) = &trnsvr {
+                t.set_recv(match connection_mode {
                     ConnectionMode::Mesh => state.enabled_individual(),
                     ConnectionMode::Sfu => true,
                 })
                 .await;
-            Some(trnsvr)
+            }
+            trnsvr
         } else {
             None
         };
@@ -157,20 +176,14 @@ impl Receiver {
             track: RefCell::new(None),
             is_track_notified: Cell::new(false),
             peer_events_sender,
-            enabled_general: Cell::new(state.enabled_individual()),
-            enabled_individual: Cell::new(state.enabled_general()),
+            enabled_general: Cell::new(state.enabled_general()),
+            enabled_individual: Cell::new(state.enabled_individual()),
             muted: Cell::new(state.muted()),
+            display_surface: Cell::new(state.display_surface()),
             media_direction: Cell::new(state.media_direction()),
             track_events_sender,
         };
 
-        let enabled_in_cons = match &state.media_type() {
-            proto::MediaType::Audio(_) => recv_constraints.is_audio_enabled(),
-            proto::MediaType::Video(_) => {
-                recv_constraints.is_video_device_enabled()
-                    || recv_constraints.is_video_display_enabled()
-            }
-        };
         if !enabled_in_cons {
             state
                 .media_exchange_state_controller()
@@ -201,6 +214,12 @@ impl Receiver {
         self.mid.borrow().clone()
     }
 
+    /// Returns the [`remote::Track`] of this [`Receiver`], if any.
+    #[must_use]
+    pub fn track(&self) -> Option<remote::Track> {
+        self.track.borrow().clone()
+    }
+
     /// Indicates whether this [`Receiver`] receives media data.
     pub async fn is_receiving(&self) -> bool {
         let transceiver = self.transceiver.borrow().clone();
@@ -247,7 +266,7 @@ impl Receiver {
             }
         }
 
-        self.set_transceiver(transceiver);
+        self.set_transceiver(transceiver.clone());
 
         let new_track = remote::Track::new(
             new_track,
@@ -255,6 +274,8 @@ impl Receiver {
             self.muted.get(),
             self.media_direction.get(),
         );
+        new_track.set_transceiver(transceiver);
+        new_track.set_display_surface(self.display_surface.get());
 
         // It's OK to `.clone()` here, as the `Transceiver` represents a pointer
         // to a garbage-collectable memory on each platform.
@@ -281,6 +302,14 @@ impl Receiver {
         }
     }
 
+    /// Updates [`DisplaySurface`] captured by the sender of this [`Receiver`].
+    pub fn set_display_surface(&self, surface: Option<DisplaySurface>) {
+        self.display_surface.set(surface);
+        if let Some(track) = self.track.borrow().as_ref() {
+            track.set_display_surface(surface);
+        }
+    }
+
     /// Replaces [`Receiver`]'s [`platform::Transceiver`] with the provided
     /// [`platform::Transceiver`].
     ///
@@ -11,7 +11,9 @@ use medea_client_api_proto::{
     TrackId, TrackPatchEvent,
 };
 use medea_macro::watchers;
-use medea_reactive::{AllProcessed, Guarded, ObservableCell, ProgressableCell};
+use medea_reactive::{
+    AllProcessed, Guard, Guarded, ObservableCell, ProgressableCell,
+};
 use proto::ConnectionMode;
 use tracerr::Traced;
 
@@ -213,11 +215,7 @@ impl SynchronizableState for State {
             self.mute_state.update(new_mute_state);
         }
 
-        let new_general_media_exchange_state =
-            media_exchange_state::Stable::from(
-                input.media_direction.is_enabled_general(),
-            );
-        self.enabled_general.set(new_general_media_exchange_state);
+        self.set_media_direction(input.media_direction);
 
         self.sync_state.set(SyncState::Synced);
     }
@@ -356,12 +354,43 @@ impl State {
         self.enabled_general.get() == media_exchange_state::Stable::Enabled
     }
 
+    /// Indicates whether the individual media exchange state of this [`State`]
+    /// is enabled or is transiting into the enabled one.
+    #[must_use]
+    pub fn is_enabled_intended(&self) -> bool {
+        self.enabled_individual.state().intended()
+            == media_exchange_state::Stable::Enabled
+    }
+
+    /// Returns the current general [`MediaDirection`] of this [`State`]
+    /// approved by a media server.
+    #[must_use]
+    pub fn media_direction(&self) -> MediaDirection {
+        self.media_direction.get()
+    }
+
     /// Returns current mute state of this [`State`].
     #[must_use]
     pub fn is_muted(&self) -> bool {
         self.mute_state.muted()
     }
 
+    /// Indicates whether the [`Sender`] of this [`State`] is intended to send
+    /// media, so its [`Transceiver`] should have a [`SEND`] direction.
+    ///
+    /// [`SEND`]: crate::platform::TransceiverDirection::SEND
+    /// [`Transceiver`]: crate::platform::Transceiver
+    #[must_use]
+    pub fn is_sending_intended(&self) -> bool {
+        // All senders are always on in SFU mode.
+        self.connection_mode == ConnectionMode::Sfu
+            || (self.is_enabled_general()
+                && self.send_constraints.is_track_enabled_and_constrained(
+                    self.media_kind(),
+                    Some(self.media_source()),
+                ))
+    }
+
     /// Returns [`Future`] which will be resolved once
     /// [getUserMedia()][1]/[getDisplayMedia()][2] request for this [`State`] is
     /// resolved.
@@ -395,12 +424,9 @@ impl State {
             return;
         }
         if let Some(direction) = track_patch.media_direction {
-            self.media_direction.set(direction);
-            self.enabled_general
-                .set((direction.is_enabled_general()).into());
-
             self.enabled_individual
                 .update(direction.is_send_enabled().into());
+            self.set_media_direction(direction);
         }
         if let Some(muted) = track_patch.muted {
             self.mute_state.update(mute_state::Stable::from(muted));
@@ -410,6 +436,23 @@ impl State {
         }
     }
 
+    /// Sets the provided [`MediaDirection`] approved by a media server,
+    /// updating the [`State::enabled_general`] accordingly.
+    ///
+    /// The general media exchange state is [reconciled][1] with the pending
+    /// local intention (if any), so a stale server state can't resume the
+    /// media transiently.
+    ///
+    /// [1]: MediaExchangeState::reconcile_general
+    fn set_media_direction(&self, direction: MediaDirection) {
+        self.media_direction.set(direction);
+        self.enabled_general.set(
+            self.enabled_individual
+                .state()
+                .reconcile_general(direction.is_enabled_general().into()),
+        );
+    }
+
     /// Indicates whether local `MediaStream` update needed for this [`State`].
     #[must_use]
     pub fn is_local_stream_update_needed(&self) -> bool {
@@ -455,10 +498,11 @@ impl Component {
     /// updates.
     ///
     /// Sends [`TrackEvent::MediaExchangeIntention`][1] with the provided
-    /// [`media_exchange_state`].
+    /// [`media_exchange_state`]. Rapid updates are coalesced, so only one
+    /// intention awaits a media server acknowledgement at a time.
     ///
     /// [1]: crate::peer::TrackEvent::MediaExchangeIntention
-    #[watch(self.enabled_individual.subscribe_transition())]
+    #[watch(self.enabled_individual.subscribe_intentions())]
     fn enabled_individual_transition_started(
         sender: &Sender,
         _: &State,
@@ -470,10 +514,11 @@ impl Component {
     /// Watcher for mute state [`mute_state::Transition`] updates.
     ///
     /// Sends [`TrackEvent::MuteUpdateIntention`][1] with the provided
-    /// [`mute_state`].
+    /// [`mute_state`]. Rapid updates are coalesced, so only one intention
+    /// awaits a media server acknowledgement at a time.
     ///
     /// [1]: crate::peer::TrackEvent::MuteUpdateIntention
-    #[watch(self.mute_state.subscribe_transition())]
+    #[watch(self.mute_state.subscribe_intentions())]
     fn mute_state_transition_watcher(
         sender: &Sender,
         _: &State,
@@ -523,24 +568,39 @@ impl Component {
     /// Updates [`Sender::enabled_individual`] to the `new_state`.
     ///
     /// Removes `MediaTrack` from [`platform::Transceiver`] if `new_state` is
-    /// [`media_exchange_state::Stable::Disabled`].
+    /// [`media_exchange_state::Stable::Disabled`], invoking its `on_disabled`
+    /// callback beforehand.
     ///
     /// Marks [`State::local_track_state`] as [`LocalTrackState::NeedUpdate`] if
     /// `new_state` is [`media_exchange_state::Stable::Enabled`].
-    #[watch(self.enabled_individual.subscribe_stable())]
+    ///
+    /// Emits [`PeerEvent::MediaStateForced`][1] if `new_state` is forced by a
+    /// media server.
+    ///
+    /// [1]: crate::peer::PeerEvent::MediaStateForced
+    #[watch(self.enabled_individual.subscribe_stable_guarded())]
     async fn enabled_individual_stable_state_changed(
         sender: Rc<Sender>,
         state: Rc<State>,
-        new_state: media_exchange_state::Stable,
+        (new_state, _guard): (media_exchange_state::Stable, Guard),
     ) {
-        sender
-            .enabled_individual
-            .set(new_state == media_exchange_state::Stable::Enabled);
+        let enabled = new_state == media_exchange_state::Stable::Enabled;
+        if sender.enabled_individual.replace(enabled) != enabled
+            && state.enabled_individual.is_forced()
+        {
+            sender.send_forced_media_state(new_state.into());
+        }
         match new_state {
             media_exchange_state::Stable::Enabled => {
+                // Re-derive the general state, as it may have been held
+                // disabled by an intention which is canceled now.
+                state.set_media_direction(state.media_direction.get());
                 state.local_track_state.set(LocalTrackState::NeedUpdate);
             }
             media_exchange_state::Stable::Disabled => {
+                if let Some(track) = sender.get_send_track() {
+                    track.set_media_exchange_enabled(false);
+                }
                 sender.remove_track().await;
             }
         }
@@ -552,13 +612,22 @@ impl Component {
     ///
     /// Updates [`Sender`]'s [`platform::Transceiver`] `MediaTrack.enabled`
     /// property.
+    ///
+    /// Emits [`PeerEvent::MediaStateForced`][1] if `new_state` is forced by a
+    /// media server.
+    ///
+    /// [1]: crate::peer::PeerEvent::MediaStateForced
     #[watch(self.mute_state.subscribe_stable())]
     fn mute_state_stable_watcher(
         sender: &Sender,
-        _: &State,
+        state: &State,
         new_state: mute_state::Stable,
     ) {
-        sender.muted.set(new_state == mute_state::Stable::Muted);
+        let muted = new_state == mute_state::Stable::Muted;
+        if sender.muted.replace(muted) != muted && state.mute_state.is_forced()
+        {
+            sender.send_forced_media_state(new_state.into());
+        }
         if let Some(track) = sender.track.borrow().as_ref() {
             match new_state {
                 mute_state::Stable::Muted => {
@@ -583,14 +652,12 @@ impl Component {
     ) {
         match sync_state {
             SyncState::Synced => {
-                if let MediaExchangeState::Transition(transition) =
-                    state.enabled_individual.state()
+                if let Some(transition) =
+                    state.enabled_individual.resend_intention()
                 {
                     sender.send_media_exchange_state_intention(transition);
                 }
-                if let MuteState::Transition(transition) =
-                    state.mute_state.state()
-                {
+                if let Some(transition) = state.mute_state.resend_intention() {
                     sender.send_mute_state_intention(transition);
                 }
                 state.enabled_individual.reset_transition_timeout();
@@ -9,20 +9,21 @@ use std::{
 
 use derive_more::{Display, From};
 use futures::channel::mpsc;
-use medea_client_api_proto::TrackId;
+use medea_client_api_proto::{MediaSourceKind, TrackId};
 use tracerr::Traced;
 
 use crate::{
     media::{
         track::local, LocalTracksConstraints, MediaKind, TrackConstraints,
     },
-    peer::TrackEvent,
+    peer::{PeerEvent, TrackEvent},
     platform,
     utils::Caused,
 };
 
 use super::{
-    media_exchange_state, mute_state, MediaConnections, MediaStateControllable,
+    media_exchange_state, mute_state, MediaConnections, MediaState,
+    MediaStateControllable,
 };
 
 #[doc(inline)]
@@ -83,6 +84,11 @@ pub struct Sender {
 
     /// Channel for sending [`TrackEvent`]s to the actual [`local::Track`].
     track_events_sender: mpsc::UnboundedSender<TrackEvent>,
+
+    /// Channel for sending [`PeerEvent`]s to the [`PeerConnection`].
+    ///
+    /// [`PeerConnection`]: crate::peer::PeerConnection
+    peer_events_sender: mpsc::UnboundedSender<PeerEvent>,
 }
 
 impl Sender {
@@ -157,6 +163,8 @@ impl Sender {
             }
         };
 
+        let peer_events_sender =
+            media_connections.0.borrow().peer_events_sender.clone();
         let this = Rc::new(Self {
             track_id: state.id(),
             caps,
@@ -165,6 +173,7 @@ impl Sender {
             enabled_individual: Cell::new(state.is_enabled_individual()),
             muted: Cell::new(state.is_muted()),
             track_events_sender,
+            peer_events_sender,
             send_constraints,
             track: RefCell::new(None),
         });
@@ -207,7 +216,9 @@ impl Sender {
     /// [1]: https://w3c.github.io/webrtc-pc/#dom-rtcrtpsender
     /// [2]: https://w3.org/TR/webrtc#dom-rtcrtpsender-replacetrack
     pub async fn remove_track(&self) {
-        drop(self.track.take());
+        if let Some(track) = self.track.take() {
+            track.set_transceiver(None);
+        }
         drop(self.transceiver.set_send_track(None).await);
     }
 
@@ -220,6 +231,16 @@ impl Sender {
     /// Inserts provided [`local::Track`] into provided [`Sender`]s
     /// transceiver. No-op if provided track already being used by this
     /// [`Sender`].
+    ///
+    /// Sends [`TrackEvent::DisplaySurfaceUpdate`] if the provided
+    /// [`local::Track`] is captured from a display.
+    ///
+    /// Invokes `on_enabled` callback of the provided [`local::Track`], if media
+    /// exchange of this [`Sender`] is enabled.
+    ///
+    /// Emits [`PeerEvent::LocalTrackEnded`] once the inserted display
+    /// [`local::Track`] is ended by its source (for example, when a user stops
+    /// a screen sharing via browser's UI).
     pub(super) async fn insert_track(
         self: Rc<Self>,
         new_track: Rc<local::Track>,
@@ -231,7 +252,7 @@ impl Sender {
             }
         }
 
-        let new_track = Rc::new(new_track.fork().await);
+        let new_track = new_track.fork().await;
         new_track.set_enabled(!self.muted.get());
         self.transceiver
             .set_send_track(Some(&new_track))
@@ -241,7 +262,33 @@ impl Sender {
 
         // Set enabled once again since `muted` might have changed.
         new_track.set_enabled(!self.muted.get());
-        drop(self.track.replace(Some(new_track)));
+        if let Some(surface) = new_track.display_surface() {
+            _ = self.track_events_sender.unbounded_send(
+                TrackEvent::DisplaySurfaceUpdate {
+                    id: self.track_id,
+                    surface,
+                },
+            );
+        }
+        if new_track.media_source_kind() == MediaSourceKind::Display {
+            let peer_events_sender = self.peer_events_sender.clone();
+            let kind = new_track.kind();
+            new_track.on_source_ended(move || {
+                _ = peer_events_sender.unbounded_send(
+                    PeerEvent::LocalTrackEnded {
+                        kind,
+                        source_kind: MediaSourceKind::Display,
+                    },
+                );
+            });
+        }
+        new_track.set_transceiver(Some(self.transceiver.clone()));
+        if self.enabled_individual.get() {
+            new_track.set_media_exchange_enabled(true);
+        }
+        if let Some(old_track) = self.track.replace(Some(new_track)) {
+            old_track.set_transceiver(None);
+        }
 
         Ok(())
     }
@@ -302,6 +349,18 @@ impl Sender {
             },
         );
     }
+
+    /// Emits [`PeerEvent::MediaStateForced`] with the provided [`MediaState`]
+    /// forced by a media server.
+    fn send_forced_media_state(&self, state: MediaState) {
+        _ = self.peer_events_sender.unbounded_send(
+            PeerEvent::MediaStateForced {
+                kind: self.caps.media_kind(),
+                source_kind: self.caps.media_source_kind(),
+                state,
+            },
+        );
+    }
 }
 
 #[cfg(feature = "mockable")]
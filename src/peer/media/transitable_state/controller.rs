@@ -10,7 +10,7 @@ use futures::{
     future, future::Either, stream::LocalBoxStream, FutureExt as _,
     StreamExt as _,
 };
-use medea_reactive::{Processed, ProgressableCell};
+use medea_reactive::{Guard, Processed, ProgressableCell};
 
 use crate::{
     peer::media::transitable_state::{
@@ -44,6 +44,16 @@ pub struct TransitableStateController<S, T> {
     /// Indicator whether [`TransitableStateController::timeout_handle`]'s
     /// timeout is stopped.
     is_transition_timeout_stopped: Cell<bool>,
+
+    /// Intention sent to a media server and not acknowledged by it yet.
+    ///
+    /// Only one intention is allowed to be in flight, so newer ones are
+    /// coalesced until this one is acknowledged.
+    in_flight: Rc<Cell<Option<S>>>,
+
+    /// Indicator whether the current stable state has been forced by a media
+    /// server, rather than intended locally.
+    forced: Cell<bool>,
 }
 
 impl<S, T> TransitableStateController<S, T>
@@ -71,6 +81,8 @@ where
             state: ProgressableCell::new(state.into()),
             timeout_handle: RefCell::new(None),
             is_transition_timeout_stopped: Cell::new(false),
+            in_flight: Rc::new(Cell::new(None)),
+            forced: Cell::new(false),
         });
         Rc::clone(&this).spawn();
         this
@@ -117,6 +129,7 @@ where
                                             .state
                                             .get()
                                             .cancel_transition();
+                                        this.in_flight.set(None);
                                         this.state.set(stable);
                                     }
                                 }
@@ -148,6 +161,29 @@ where
             .boxed_local()
     }
 
+    /// Returns [`Stream`] into which the [`TransitableState::Stable`] updates
+    /// will be emitted along with their [`Guard`]s.
+    ///
+    /// [`TransitableStateController::when_media_state_stable()`] isn't
+    /// resolved until the emitted [`Guard`]s are dropped.
+    ///
+    /// [`Stream`]: futures::Stream
+    pub fn subscribe_stable_guarded(
+        &self,
+    ) -> LocalBoxStream<'static, (S, Guard)> {
+        self.state
+            .subscribe()
+            .filter_map(|s| async move {
+                let (s, guard) = s.into_parts();
+                if let TransitableState::Stable(stable) = s {
+                    Some((stable, guard))
+                } else {
+                    None
+                }
+            })
+            .boxed_local()
+    }
+
     /// Returns [`Stream`] into which the [`TransitableState::Transition`]
     /// updates will be emitted.
     ///
@@ -166,6 +202,41 @@ where
             .boxed_local()
     }
 
+    /// Returns [`Stream`] into which the [`TransitableState::Transition`]
+    /// intentions to be sent to a media server will be emitted.
+    ///
+    /// Unlike [`TransitableStateController::subscribe_transition()`], doesn't
+    /// emit anything while a previously emitted intention is not acknowledged
+    /// by a media server, so rapid toggles are coalesced into at most one
+    /// intention in flight.
+    ///
+    /// [`Stream`]: futures::Stream
+    pub fn subscribe_intentions(&self) -> LocalBoxStream<'static, T> {
+        let in_flight = Rc::clone(&self.in_flight);
+        self.state
+            .subscribe()
+            .filter_map(move |s| {
+                let (s, _guard) = s.into_parts();
+                let intention = s.intention_to_send(in_flight.get());
+                if let Some(transition) = intention {
+                    in_flight.set(Some(transition.intended()));
+                }
+                future::ready(intention)
+            })
+            .boxed_local()
+    }
+
+    /// Forgets about the intention in flight (if any) and returns the one that
+    /// should be (re)sent to a media server instead.
+    ///
+    /// Intended to be used once a connection with a media server is restored,
+    /// as the intention in flight could be lost.
+    pub fn resend_intention(&self) -> Option<T> {
+        let intention = self.state.get().intention_to_send(None);
+        self.in_flight.set(intention.map(InTransition::intended));
+        intention
+    }
+
     /// Stops disable/enable timeout of this [`TransitableStateController`].
     pub fn stop_transition_timeout(&self) {
         self.is_transition_timeout_stopped.set(true);
@@ -202,6 +273,11 @@ where
     /// Succeeds if [`TransitableStateController`]'s state transits into the
     /// `desired_state` or the [`TransitableStateController`] is dropped.
     ///
+    /// Once the `desired_state` is reached, waits for it to be processed by
+    /// all the subscribers, so the watchers of the
+    /// [`TransitableStateController::subscribe_stable_guarded()`] (like the
+    /// ones invoking `LocalMediaTrack` callbacks) always complete first.
+    ///
     /// # Errors
     ///
     /// With an approved stable [`MediaState`] if transition to the
@@ -214,14 +290,17 @@ where
         desired_state: S,
     ) -> future::LocalBoxFuture<'static, Result<(), S>> {
         let mut states = self.state.subscribe();
+        let processed = self.state.when_all_processed();
         async move {
             while let Some(state) = states.next().await {
-                let (state, _guard) = state.into_parts();
-                match state {
+                match state.into_inner() {
                     TransitableState::Transition(_) => continue,
-                    TransitableState::Stable(s) => {
-                        return if s == desired_state { Ok(()) } else { Err(s) }
+                    TransitableState::Stable(s) if s == desired_state => {
+                        drop(states);
+                        processed.await;
+                        return Ok(());
                     }
+                    TransitableState::Stable(s) => return Err(s),
                 }
             }
             Ok(())
@@ -248,22 +327,32 @@ where
         }))
     }
 
-    /// Updates [`TransitableStateController::state`].
+    /// Updates [`TransitableStateController::state`] with the one approved by
+    /// a media server.
+    ///
+    /// Acknowledges the intention in flight (if any), so the coalesced one
+    /// (if any) is emitted by the
+    /// [`TransitableStateController::subscribe_intentions()`].
+    ///
+    /// Remembers whether the `new_state` is [forced][1] by the media server.
+    ///
+    /// [1]: TransitableState::is_forced
     pub(in super::super) fn update(&self, new_state: S) {
-        let current_state = self.state.get();
-
-        let state_update = match current_state {
-            TransitableState::Stable(_) => new_state.into(),
-            TransitableState::Transition(t) => {
-                if t.intended() == new_state {
-                    new_state.into()
-                } else {
-                    t.set_inner(new_state).into()
-                }
-            }
-        };
+        let state = self.state.get();
+        if state.current() != new_state {
+            self.forced
+                .set(state.is_forced(new_state, self.in_flight.get()));
+        }
+        self.in_flight.set(None);
+        self.state.set(state.acknowledge(new_state));
+    }
 
-        self.state.set(state_update);
+    /// Indicates whether the current stable state has been forced by a media
+    /// server (by a moderator, for example), so local transitions out of it
+    /// are likely to be rejected.
+    #[must_use]
+    pub fn is_forced(&self) -> bool {
+        self.forced.get()
     }
 }
 
@@ -29,7 +29,7 @@ pub type MuteState =
 /// All media states which can be toggled in the [`MediaStateControllable`].
 ///
 /// [`MediaStateControllable`]: crate::peer::MediaStateControllable
-#[derive(Clone, Copy, Debug, Display, From)]
+#[derive(Clone, Copy, Debug, Display, Eq, From, PartialEq)]
 pub enum MediaState {
     /// Responsible for changing [`enabled`][1] property of
     /// [MediaStreamTrack][2].
@@ -63,6 +63,7 @@ impl MediaState {
                 id: track_id,
                 muted: Some(mute == mute_state::Stable::Muted),
                 enabled: None,
+                display_surface: None,
             },
             Self::MediaExchange(media_exchange) => TrackPatchCommand {
                 id: track_id,
@@ -70,6 +71,7 @@ impl MediaState {
                     media_exchange == media_exchange_state::Stable::Enabled,
                 ),
                 muted: None,
+                display_surface: None,
             },
         }
     }
@@ -156,6 +158,36 @@ where
         }
     }
 
+    /// Applies the provided `acked` state approved by a media server.
+    ///
+    /// Stabilizes an ongoing transition if it's the intended one, or keeps
+    /// the transition going from the `acked` state otherwise.
+    #[must_use]
+    pub fn acknowledge(self, acked: S) -> Self {
+        match self {
+            Self::Stable(_) => acked.into(),
+            Self::Transition(t) => {
+                if t.intended() == acked {
+                    acked.into()
+                } else {
+                    t.set_inner(acked).into()
+                }
+            }
+        }
+    }
+
+    /// Returns an intention to be sent to a media server, if any.
+    ///
+    /// Returns [`None`] while the `in_flight` intention is not acknowledged,
+    /// so at most one intention is awaiting its acknowledgement at a time.
+    #[must_use]
+    pub fn intention_to_send(self, in_flight: Option<S>) -> Option<T> {
+        match self {
+            Self::Stable(_) => None,
+            Self::Transition(t) => in_flight.is_none().then_some(t),
+        }
+    }
+
     /// Cancels an ongoing transition, if any.
     #[must_use]
     pub fn cancel_transition(self) -> Self {
@@ -164,6 +196,61 @@ where
             Self::Transition(t) => t.into_inner().into(),
         }
     }
+
+    /// Returns the stable state this [`TransitableState`] ends up in once
+    /// an ongoing transition (if any) is acknowledged.
+    #[must_use]
+    pub fn intended(self) -> S {
+        match self {
+            Self::Stable(stable) => stable,
+            Self::Transition(t) => t.intended(),
+        }
+    }
+
+    /// Returns the stable state approved by a media server, ignoring an
+    /// ongoing transition (if any).
+    #[must_use]
+    pub fn current(self) -> S {
+        match self {
+            Self::Stable(stable) => stable,
+            Self::Transition(t) => t.into_inner(),
+        }
+    }
+
+    /// Indicates whether the provided `acked` state approved by a media server
+    /// changes this [`TransitableState`] without being intended locally, so
+    /// it's forced by the media server (by a moderator, for example).
+    ///
+    /// The `in_flight` intention is considered intended locally, even if it's
+    /// superseded already.
+    #[must_use]
+    pub fn is_forced(self, acked: S, in_flight: Option<S>) -> bool {
+        acked != self.current()
+            && acked != self.intended()
+            && in_flight != Some(acked)
+    }
+}
+
+impl MediaExchangeState {
+    /// Reconciles the provided `general` [`media_exchange_state::Stable`]
+    /// approved by a media server with this individual [`MediaExchangeState`].
+    ///
+    /// Media exchange intended to be disabled locally stays disabled, even if
+    /// a media server hasn't acknowledged it yet (like after a reconnection,
+    /// when a stale server state is applied before the pending intention is
+    /// resent), so media is never resumed transiently.
+    #[must_use]
+    pub fn reconcile_general(
+        self,
+        general: media_exchange_state::Stable,
+    ) -> media_exchange_state::Stable {
+        match self.intended() {
+            media_exchange_state::Stable::Enabled => general,
+            media_exchange_state::Stable::Disabled => {
+                media_exchange_state::Stable::Disabled
+            }
+        }
+    }
 }
 
 impl From<media_exchange_state::Stable> for MediaExchangeState {
@@ -280,6 +367,232 @@ mod test {
         );
     }
 
+    #[test]
+    fn acknowledge() {
+        use media_exchange_state::Stable::{Disabled, Enabled};
+
+        assert_eq!(ENABLED.acknowledge(Disabled), DISABLED);
+        assert_eq!(DISABLED.acknowledge(Disabled), DISABLED);
+        assert_eq!(DISABLING_ENABLED.acknowledge(Disabled), DISABLED);
+        assert_eq!(ENABLING_DISABLED.acknowledge(Enabled), ENABLED);
+        assert_eq!(ENABLING_ENABLED.acknowledge(Disabled), ENABLING_DISABLED);
+        assert_eq!(DISABLING_DISABLED.acknowledge(Enabled), DISABLING_ENABLED);
+    }
+
+    #[test]
+    fn intention_to_send() {
+        use media_exchange_state::{
+            Stable::{Disabled, Enabled},
+            Transition::Disabling,
+        };
+
+        assert_eq!(ENABLED.intention_to_send(None), None);
+        assert_eq!(ENABLED.intention_to_send(Some(Disabled)), None);
+        assert_eq!(
+            DISABLING_ENABLED.intention_to_send(None),
+            Some(Disabling(Enabled)),
+        );
+        assert_eq!(DISABLING_ENABLED.intention_to_send(Some(Enabled)), None);
+    }
+
+    #[test]
+    fn is_forced() {
+        use media_exchange_state::Stable::{Disabled, Enabled};
+
+        assert!(ENABLED.is_forced(Disabled, None));
+        assert!(!ENABLED.is_forced(Enabled, None));
+        assert!(!ENABLED.is_forced(Disabled, Some(Disabled)));
+        assert!(!DISABLING_ENABLED.is_forced(Disabled, None));
+        assert!(!ENABLING_DISABLED.is_forced(Disabled, Some(Enabled)));
+        assert!(!ENABLING_ENABLED.is_forced(Disabled, Some(Disabled)));
+        assert!(ENABLING_ENABLED.is_forced(Disabled, None));
+    }
+
+    /// Deterministic pseudo-random numbers generator for the property-style
+    /// tests.
+    struct XorShift(u64);
+
+    impl XorShift {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+    }
+
+    /// Emulates sending of the intention (if any) to a media server.
+    fn send_intention(
+        client: MediaExchangeState,
+        in_flight: &mut Option<media_exchange_state::Stable>,
+    ) {
+        if let Some(t) = client.intention_to_send(*in_flight) {
+            *in_flight = Some(t.intended());
+        }
+    }
+
+    /// Drives random sequences of toggles and media server acknowledgements,
+    /// checking that the final client state always equals both the final
+    /// acknowledged state and the last requested one.
+    #[test]
+    fn random_toggles_converge_to_acked_state() {
+        use media_exchange_state::Stable::{Disabled, Enabled};
+
+        for seed in 1..=1000 {
+            let mut rng = XorShift(seed);
+            let mut client = ENABLED;
+            let mut server = Enabled;
+            let mut last_requested = Enabled;
+            let mut in_flight = None;
+
+            for _ in 0..rng.next() % 64 {
+                match rng.next() % 3 {
+                    0 | 1 => {
+                        last_requested = if rng.next() % 2 == 0 {
+                            Enabled
+                        } else {
+                            Disabled
+                        };
+                        client = client.transition_to(last_requested);
+                    }
+                    _ => {
+                        if let Some(acked) = in_flight.take() {
+                            server = acked;
+                            client = client.acknowledge(acked);
+                        }
+                    }
+                }
+                send_intention(client, &mut in_flight);
+            }
+            while let Some(acked) = in_flight.take() {
+                server = acked;
+                client = client.acknowledge(acked);
+                send_intention(client, &mut in_flight);
+            }
+
+            assert_eq!(client, server.into(), "seed: {seed}");
+            assert_eq!(client, last_requested.into(), "seed: {seed}");
+        }
+    }
+
+    /// Event delivered to a client while restoring a session after a
+    /// reconnection.
+    #[derive(Clone, Copy, Debug)]
+    enum ReconnectEvent {
+        /// Local intention made by a user.
+        Intend(media_exchange_state::Stable),
+
+        /// Server state (either a snapshot or a stale [`TrackPatchEvent`])
+        /// with the individual and general media exchange states.
+        ///
+        /// [`TrackPatchEvent`]: medea_client_api_proto::TrackPatchEvent
+        Server(media_exchange_state::Stable, media_exchange_state::Stable),
+
+        /// Pending intention is resent and acknowledged by a server.
+        Ack,
+    }
+
+    /// Replays the provided recorded reconnection `events` starting from the
+    /// `initial` state, checking that the applied general media exchange
+    /// state never contradicts the pending local intention.
+    ///
+    /// Returns the final individual and general states.
+    fn replay_reconnect(
+        initial: MediaExchangeState,
+        events: &[ReconnectEvent],
+    ) -> (MediaExchangeState, media_exchange_state::Stable) {
+        use media_exchange_state::Stable::Disabled;
+
+        let mut client = initial;
+        let mut general = initial.intended();
+        for (i, ev) in events.iter().copied().enumerate() {
+            match ev {
+                ReconnectEvent::Intend(desired) => {
+                    client = client.transition_to(desired);
+                }
+                ReconnectEvent::Server(individual, server_general) => {
+                    client = client.acknowledge(individual);
+                    general = client.reconcile_general(server_general);
+                }
+                ReconnectEvent::Ack => {
+                    let intended = client.intended();
+                    client = client.acknowledge(intended);
+                    general = client.reconcile_general(intended);
+                }
+            }
+            if client.intended() == Disabled {
+                assert_eq!(general, Disabled, "leaked at event {i}: {ev:?}");
+            }
+        }
+        (client, general)
+    }
+
+    #[test]
+    fn reconnect_keeps_pending_disable() {
+        use media_exchange_state::Stable::{Disabled, Enabled};
+        use ReconnectEvent::{Ack, Intend, Server};
+
+        // Disabled during an outage, then a snapshot and a stale event still
+        // having the media enabled are applied before the resent intention is
+        // acknowledged.
+        assert_eq!(
+            replay_reconnect(
+                ENABLED,
+                &[
+                    Intend(Disabled),
+                    Server(Enabled, Enabled),
+                    Server(Enabled, Enabled),
+                    Ack,
+                ],
+            ),
+            (DISABLED, Disabled),
+        );
+
+        // Disabling was in-flight when the connection was lost, and a server
+        // has processed it, but the acknowledgement was lost.
+        assert_eq!(
+            replay_reconnect(DISABLING_ENABLED, &[Server(Disabled, Disabled)]),
+            (DISABLED, Disabled),
+        );
+
+        // Disabled and re-enabled during an outage.
+        assert_eq!(
+            replay_reconnect(
+                ENABLED,
+                &[Intend(Disabled), Intend(Enabled), Server(Enabled, Enabled),],
+            ),
+            (ENABLED, Enabled),
+        );
+
+        // Enabled during an outage, while a partner still has its media
+        // disabled.
+        assert_eq!(
+            replay_reconnect(
+                DISABLED,
+                &[Intend(Enabled), Server(Disabled, Disabled), Ack],
+            ),
+            (ENABLED, Enabled),
+        );
+    }
+
+    #[test]
+    fn reconnect_keeps_pending_mute() {
+        use mute_state::Stable::{Muted, Unmuted};
+
+        // Muted during an outage, then a snapshot and a stale event still
+        // having the media unmuted are applied.
+        let mut client = MuteState::Stable(Unmuted).transition_to(Muted);
+        for server in [Unmuted, Unmuted] {
+            client = client.acknowledge(server);
+            assert_eq!(
+                client,
+                MuteState::Transition(mute_state::Transition::Muting(Unmuted)),
+            );
+        }
+        assert_eq!(client.intended(), Muted);
+        assert_eq!(client.acknowledge(Muted), MuteState::Stable(Muted));
+    }
+
     #[test]
     fn cancel_transition() {
         assert_eq!(DISABLED.cancel_transition(), DISABLED);
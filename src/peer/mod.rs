@@ -2,21 +2,37 @@
 //!
 //! [1]: https://w3.org/TR/webrtc#rtcpeerconnection-interface
 
+mod adaptation;
+mod av_sync;
+mod codec_info;
 mod component;
+mod freeze;
 pub mod media;
+mod negotiation;
+mod quality_limitation;
 pub mod repo;
+mod rollback;
+mod sdp_event;
+mod setup_timings;
+#[cfg(test)]
+mod stats_fixture;
 mod stream_update_criteria;
 mod tracks_request;
+mod traffic;
+mod transport;
 
 use std::{
     cell::{Cell, RefCell},
     collections::{hash_map::DefaultHasher, HashMap},
+    fmt,
+    future::Future,
     hash::{Hash, Hasher},
     rc::Rc,
+    time::Duration,
 };
 
 use derive_more::{Display, From};
-use futures::{channel::mpsc, future, StreamExt as _};
+use futures::{channel::mpsc, future, FutureExt as _, StreamExt as _};
 use medea_client_api_proto::{
     stats::StatId, Command, ConnectionMode, IceConnectionState,
     MediaSourceKind, MemberId, PeerConnectionState, PeerId as Id, PeerId,
@@ -27,10 +43,11 @@ use tracerr::Traced;
 
 use crate::{
     connection::Connections,
+    error_report::{NonFatalError, NonFatalErrorKind},
     media::{
         track::{local, remote},
-        InitLocalTracksError, LocalTracksConstraints, MediaKind, MediaManager,
-        MediaStreamSettings, RecvConstraints,
+        DisplaySurface, InitLocalTracksError, LocalTracksConstraints,
+        MediaKind, MediaManager, MediaStreamSettings, RecvConstraints,
     },
     platform,
     utils::Caused,
@@ -38,17 +55,38 @@ use crate::{
 
 #[doc(inline)]
 pub use self::{
+    adaptation::{
+        Adaptation, AdaptationEngine, AdaptivePolicy, LimitationReason,
+        VideoLevel, AUDIO_FIRST_BITRATES,
+    },
+    av_sync::{AvSyncCounter, AvSyncInfo},
+    codec_info::CodecFallback,
     component::{Component, State},
+    freeze::FreezeDetector,
     media::{
         media_exchange_state, mute_state, receiver, sender, GetMidsError,
         InsertLocalTracksError, MediaConnections, MediaExchangeState,
         MediaExchangeStateController, MediaState, MediaStateControllable,
         MuteState, MuteStateController, ProhibitedStateError, TrackDirection,
-        TransceiverSide, TransitableState, TransitableStateController,
+        TransceiverInfo, TransceiverSide, TransitableState,
+        TransitableStateController,
     },
+    negotiation::{NegotiationCounter, NegotiationStats},
     platform::RtcPeerConnectionError,
+    quality_limitation::{QualityLimitationCounter, QualitySummary},
+    rollback::{
+        Description, RollbackStrategy, StableDescriptions,
+        StableDescriptionsTracker,
+    },
+    sdp_event::{redact_sdp, SdpDirection, SdpEvent, SdpKind},
+    setup_timings::{
+        JoinPhase, JoinProgress, JoinProgressUpdate, SetupPhase, SetupTimings,
+        SetupTimingsReport,
+    },
     stream_update_criteria::LocalStreamUpdateCriteria,
     tracks_request::{SimpleTracksRequest, TracksRequest, TracksRequestError},
+    traffic::{TrafficCounter, TrafficTotals},
+    transport::{TransportInfo, TransportMonitor},
 };
 
 /// Errors occurring in [`PeerConnection::update_local_stream()`] method.
@@ -88,6 +126,41 @@ pub enum TrackEvent {
         /// The enabling/disabling intention itself.
         enabled: bool,
     },
+
+    /// [`DisplaySurface`] of the `MediaTrack` is known, so should be reported
+    /// to its receivers.
+    DisplaySurfaceUpdate {
+        /// ID of the `MediaTrack` which [`DisplaySurface`] is known.
+        id: TrackId,
+
+        /// [`DisplaySurface`] captured by the `MediaTrack`.
+        surface: DisplaySurface,
+    },
+}
+
+impl From<TrackEvent> for TrackPatchCommand {
+    fn from(event: TrackEvent) -> Self {
+        match event {
+            TrackEvent::MediaExchangeIntention { id, enabled } => Self {
+                id,
+                muted: None,
+                enabled: Some(enabled),
+                display_surface: None,
+            },
+            TrackEvent::MuteUpdateIntention { id, muted } => Self {
+                id,
+                muted: Some(muted),
+                enabled: None,
+                display_surface: None,
+            },
+            TrackEvent::DisplaySurfaceUpdate { id, surface } => Self {
+                id,
+                muted: None,
+                enabled: None,
+                display_surface: Some(surface.into()),
+            },
+        }
+    }
 }
 
 /// Local media update errors that [`PeerConnection`] reports in
@@ -152,6 +225,31 @@ pub enum PeerEvent {
         local_track: Rc<local::Track>,
     },
 
+    /// [`local::Track`] being sent to remote members has been ended by its
+    /// source (for example, by a user stopping a screen sharing via browser's
+    /// UI), so its sending should be disabled.
+    LocalTrackEnded {
+        /// [`MediaKind`] of the ended [`local::Track`].
+        kind: MediaKind,
+
+        /// [`MediaSourceKind`] of the ended [`local::Track`].
+        source_kind: MediaSourceKind,
+    },
+
+    /// [`MediaState`] of a [`local::Track`] being sent to remote members has
+    /// been forced by a media server (by a moderator, for example), rather
+    /// than changed locally.
+    MediaStateForced {
+        /// [`MediaKind`] of the [`local::Track`].
+        kind: MediaKind,
+
+        /// [`MediaSourceKind`] of the [`local::Track`].
+        source_kind: MediaSourceKind,
+
+        /// [`MediaState`] forced by the media server.
+        state: MediaState,
+    },
+
     /// [`platform::RtcPeerConnection`]'s [ICE connection][1] state changed.
     ///
     /// [1]: https://w3.org/TR/webrtc#dfn-ice-connection-state
@@ -190,6 +288,57 @@ pub enum PeerEvent {
         stats: platform::RtcStats,
     },
 
+    /// [`NegotiationStats`] of a [`PeerConnection`] have been changed.
+    NegotiationStatsUpdate {
+        /// ID of the [`PeerConnection`] which [`NegotiationStats`] have been
+        /// changed.
+        peer_id: Id,
+
+        /// New [`NegotiationStats`] of the [`PeerConnection`].
+        stats: NegotiationStats,
+    },
+
+    /// [`AvSyncInfo`] of a [`PeerConnection`] has been changed.
+    AvSyncUpdate {
+        /// ID of the [`PeerConnection`] which [`AvSyncInfo`] has been
+        /// changed.
+        peer_id: Id,
+
+        /// New [`AvSyncInfo`] of the [`PeerConnection`].
+        info: AvSyncInfo,
+    },
+
+    /// Outbound video of a [`PeerConnection`] has been adapted according to
+    /// its [`AdaptivePolicy`].
+    VideoAdapted {
+        /// ID of the [`PeerConnection`] which outbound video has been adapted.
+        peer_id: Id,
+
+        /// [`Adaptation`] applied to the outbound video.
+        adaptation: Adaptation,
+    },
+
+    /// Encoder or decoder of a [`PeerConnection`]'s media has fallen back
+    /// from a hardware implementation to a software one.
+    SoftwareCodecFallback {
+        /// ID of the [`PeerConnection`] which media codec has fallen back.
+        peer_id: Id,
+
+        /// [`CodecFallback`] that has happened.
+        fallback: CodecFallback,
+    },
+
+    /// SDP description has been successfully applied to a
+    /// [`PeerConnection`].
+    SdpApplied {
+        /// ID of the [`PeerConnection`] the SDP description has been applied
+        /// to.
+        peer_id: Id,
+
+        /// [`SdpEvent`] describing the applied SDP.
+        event: SdpEvent,
+    },
+
     /// [`PeerConnection::update_local_stream`] was failed, so
     /// `on_failed_local_stream` callback should be called.
     FailedLocalMedia {
@@ -197,6 +346,13 @@ pub enum PeerEvent {
         error: Traced<LocalMediaError>,
     },
 
+    /// Non-fatal error has happened in a [`PeerConnection`], so it should be
+    /// reported via `on_error` callback.
+    NonFatalError {
+        /// Happened [`NonFatalError`].
+        error: NonFatalError,
+    },
+
     /// [`Component`] generated a new SDP answer.
     NewSdpAnswer {
         /// ID of the [`PeerConnection`] for which SDP answer was generated.
@@ -236,6 +392,20 @@ pub enum PeerEvent {
     },
 }
 
+/// [`MediaState`] of a [`local::Track`] forced by a media server (by a
+/// moderator, for example).
+#[derive(Clone, Copy, Debug)]
+pub struct ForcedMediaState {
+    /// [`MediaKind`] of the [`local::Track`].
+    pub kind: MediaKind,
+
+    /// [`MediaSourceKind`] of the [`local::Track`].
+    pub source_kind: MediaSourceKind,
+
+    /// [`MediaState`] forced by the media server.
+    pub state: MediaState,
+}
+
 /// High-level wrapper around a [`platform::RtcPeerConnection`].
 #[derive(Debug)]
 pub struct PeerConnection {
@@ -257,7 +427,7 @@ pub struct PeerConnection {
 
     /// Indicator whether the underlying [`platform::RtcPeerConnection`] has a
     /// remote description.
-    has_remote_description: Cell<bool>,
+    has_remote_description: Rc<Cell<bool>>,
 
     /// Buffer of [`platform::IceCandidate`]s received before a remote
     /// description for the underlying [`platform::RtcPeerConnection`].
@@ -267,7 +437,8 @@ pub struct PeerConnection {
     /// to a server, so we won't duplicate stats that were already sent.
     ///
     /// Stores precomputed hashes, since we don't need access to actual stats
-    /// values.
+    /// values. Only the hashes of the stats present in the last
+    /// [`platform::RtcStats`] are retained.
     sent_stats_cache: RefCell<HashMap<StatId, u64>>,
 
     /// Local media stream constraints used in this [`PeerConnection`].
@@ -282,18 +453,61 @@ pub struct PeerConnection {
     /// [`PeerConnection`].
     track_events_sender: mpsc::UnboundedSender<TrackEvent>,
 
+    /// Window during which [`TrackEvent`]s are collected into a single
+    /// [`Command::UpdateTracks`], so simultaneous track changes cause a single
+    /// renegotiation.
+    negotiation_debounce: Rc<Cell<Duration>>,
+
+    /// Sender for forcing the currently collected [`TrackEvent`]s to be sent
+    /// without waiting for the [`PeerConnection::negotiation_debounce`] window
+    /// to pass.
+    flush_negotiation_tx: mpsc::UnboundedSender<()>,
+
     /// Constraints to the [`remote::Track`] from this [`PeerConnection`]. Used
     /// to disable or enable media receiving.
     recv_constraints: Rc<RecvConstraints>,
+
+    /// [`SetupTimings`] of this [`PeerConnection`].
+    setup_timings: Rc<SetupTimings>,
+
+    /// [`TrafficCounter`] of this [`PeerConnection`].
+    traffic: Rc<TrafficCounter>,
+
+    /// [`TransportMonitor`] of this [`PeerConnection`].
+    transport: Rc<TransportMonitor>,
+
+    /// [`NegotiationCounter`] of this [`PeerConnection`].
+    negotiations: Rc<NegotiationCounter>,
+
+    /// [`QualityLimitationCounter`] of the outbound media of this
+    /// [`PeerConnection`].
+    quality_limitation: Rc<QualityLimitationCounter>,
+
+    /// [`AvSyncCounter`] of the inbound media of this [`PeerConnection`].
+    av_sync: Rc<AvSyncCounter>,
+
+    /// [`AdaptationEngine`] of the outbound video of this [`PeerConnection`].
+    adaptation: AdaptationEngine,
+
+    /// [`FreezeDetector`] of the remote video of this [`PeerConnection`].
+    freeze: FreezeDetector,
+
+    /// [`StableDescriptionsTracker`] of this [`PeerConnection`], used for
+    /// rolling it back when a platform rejects an explicit rollback.
+    stable_descriptions: StableDescriptionsTracker,
 }
 
 impl PeerConnection {
+    /// Default [`PeerConnection::negotiation_debounce`] window.
+    pub const DEFAULT_NEGOTIATION_DEBOUNCE: Duration =
+        Duration::from_millis(50);
+
     /// Creates new [`PeerConnection`].
     ///
     /// Provided `peer_events_sender` will be used to emit [`PeerEvent`]s from
     /// this peer.
     ///
-    /// Provided `ice_servers` will be used by the created
+    /// Provided `ice_servers` and `rtc_config` will be used by the created
     /// [`platform::RtcPeerConnection`].
     ///
     /// # Errors
@@ -307,11 +521,13 @@ impl PeerConnection {
         send_constraints: LocalTracksConstraints,
         connections: Rc<Connections>,
         recv_constraints: Rc<RecvConstraints>,
+        rtc_config: &platform::RtcConfiguration,
     ) -> Result<Rc<Self>, Traced<RtcPeerConnectionError>> {
         let peer = Rc::new(
             platform::RtcPeerConnection::new(
                 state.ice_servers().clone(),
                 state.force_relay(),
+                rtc_config,
             )
             .await
             .map_err(tracerr::map_from_and_wrap!())?,
@@ -322,16 +538,19 @@ impl PeerConnection {
             peer_events_sender.clone(),
         ));
 
-        platform::spawn({
-            let peer_events_sender = peer_events_sender.clone();
-            let peer_id = state.id();
+        let (flush_negotiation_tx, flush_negotiation_rx) = mpsc::unbounded();
+        let negotiation_debounce =
+            Rc::new(Cell::new(Self::DEFAULT_NEGOTIATION_DEBOUNCE));
+        let has_remote_description = Rc::new(Cell::new(false));
 
-            async move {
-                while let Some(e) = track_events_rx.next().await {
-                    Self::handle_track_event(peer_id, &peer_events_sender, e);
-                }
-            }
-        });
+        platform::spawn(Self::batch_track_events(
+            state.id(),
+            peer_events_sender.clone(),
+            track_events_rx,
+            flush_negotiation_rx,
+            Rc::clone(&negotiation_debounce),
+            Rc::clone(&has_remote_description),
+        ));
 
         let peer = Self {
             id: state.id(),
@@ -340,12 +559,23 @@ impl PeerConnection {
             media_manager,
             peer_events_sender: Rc::new(peer_events_sender),
             sent_stats_cache: RefCell::new(HashMap::new()),
-            has_remote_description: Cell::new(false),
+            has_remote_description,
             ice_candidates_buffer: RefCell::new(Vec::new()),
             send_constraints,
             connections,
             track_events_sender,
+            negotiation_debounce,
+            flush_negotiation_tx,
             recv_constraints,
+            setup_timings: Rc::default(),
+            traffic: Rc::default(),
+            transport: Rc::default(),
+            negotiations: Rc::default(),
+            quality_limitation: Rc::default(),
+            av_sync: Rc::default(),
+            adaptation: AdaptationEngine::default(),
+            freeze: FreezeDetector::default(),
+            stable_descriptions: StableDescriptionsTracker::default(),
         };
 
         // Bind to `icecandidate` event.
@@ -380,8 +610,14 @@ impl PeerConnection {
         {
             let id = peer.id;
             let weak_sender = Rc::downgrade(&peer.peer_events_sender);
+            let weak_timings = Rc::downgrade(&peer.setup_timings);
             peer.peer.on_connection_state_change(Some(
                 move |peer_connection_state| {
+                    if peer_connection_state == PeerConnectionState::Connected {
+                        if let Some(timings) = weak_timings.upgrade() {
+                            timings.finish(SetupPhase::IceConnected);
+                        }
+                    }
                     if let Some(sender) = weak_sender.upgrade() {
                         Self::on_connection_state_changed(
                             id,
@@ -397,15 +633,22 @@ impl PeerConnection {
         {
             let media_conns = Rc::downgrade(&peer.media_connections);
             let connection_mode = state.connection_mode();
+            let sender = Rc::clone(&peer.peer_events_sender);
             peer.peer.on_track(Some(move |track, transceiver| {
                 if let Some(c) = media_conns.upgrade() {
+                    let sender = Rc::clone(&sender);
                     platform::spawn(async move {
                         if let (Err(mid), ConnectionMode::Mesh) = (
                             c.add_remote_track(track, transceiver).await,
                             connection_mode,
                         ) {
-                            log::error!(
-                                "Cannot add new remote track with mid={mid}",
+                            report_error(
+                                &sender,
+                                NonFatalErrorKind::RemoteTrack,
+                                tracerr::new!(format!(
+                                    "Cannot add new remote track with \
+                                     mid={mid}",
+                                )),
                             );
                         };
                     });
@@ -416,44 +659,95 @@ impl PeerConnection {
         Ok(Rc::new(peer))
     }
 
-    /// Handles [`TrackEvent`]s emitted from a [`Sender`] or a [`Receiver`].
+    /// Collects [`TrackEvent`]s emitted from [`Sender`]s and [`Receiver`]s
+    /// into batches, sending a single [`PeerEvent::MediaUpdateCommand`] with a
+    /// [`Command::UpdateTracks`] per batch, so simultaneous track changes are
+    /// negotiated at once.
     ///
-    /// Sends a [`PeerEvent::MediaUpdateCommand`] with a
-    /// [`Command::UpdateTracks`] on [`TrackEvent::MediaExchangeIntention`] and
-    /// [`TrackEvent::MuteUpdateIntention`].
+    /// A batch is started by the first received [`TrackEvent`] and is sent
+    /// once the `debounce` window passes, or once a flush is requested via the
+    /// `flush_rx`. Until the [`platform::RtcPeerConnection`] has a remote
+    /// description, the window is a single tick only, so the initial
+    /// negotiation isn't delayed.
     ///
     /// [`Sender`]: sender::Sender
     /// [`Receiver`]: receiver::Receiver
-    fn handle_track_event(
+    async fn batch_track_events(
         peer_id: PeerId,
-        peer_events_sender: &mpsc::UnboundedSender<PeerEvent>,
-        event: TrackEvent,
+        peer_events_sender: mpsc::UnboundedSender<PeerEvent>,
+        mut track_events_rx: mpsc::UnboundedReceiver<TrackEvent>,
+        mut flush_rx: mpsc::UnboundedReceiver<()>,
+        debounce: Rc<Cell<Duration>>,
+        has_remote_description: Rc<Cell<bool>>,
     ) {
-        let patch = match event {
-            TrackEvent::MediaExchangeIntention { id, enabled } => {
-                TrackPatchCommand {
-                    id,
-                    muted: None,
-                    enabled: Some(enabled),
-                }
-            }
-            TrackEvent::MuteUpdateIntention { id, muted } => {
-                TrackPatchCommand {
-                    id,
-                    muted: Some(muted),
-                    enabled: None,
+        while let Some(first) = track_events_rx.next().await {
+            // Flushes requested before this batch has started are stale.
+            while let Ok(Some(())) = flush_rx.try_next() {}
+
+            let window = if has_remote_description.get() {
+                debounce.get()
+            } else {
+                Duration::ZERO
+            };
+            let deadline = platform::delay_for(window).fuse();
+            futures::pin_mut!(deadline);
+
+            let mut patches = Vec::new();
+            Self::merge_track_patch(&mut patches, first.into());
+            loop {
+                futures::select! {
+                    e = track_events_rx.next() => {
+                        if let Some(e) = e {
+                            Self::merge_track_patch(&mut patches, e.into());
+                        } else {
+                            break;
+                        }
+                    }
+                    _ = flush_rx.next() => break,
+                    () = deadline => break,
                 }
             }
-        };
 
-        _ = peer_events_sender
-            .unbounded_send(PeerEvent::MediaUpdateCommand {
-                command: Command::UpdateTracks {
-                    peer_id,
-                    tracks_patches: vec![patch],
-                },
-            })
-            .ok();
+            _ = peer_events_sender
+                .unbounded_send(PeerEvent::MediaUpdateCommand {
+                    command: Command::UpdateTracks {
+                        peer_id,
+                        tracks_patches: patches,
+                    },
+                })
+                .ok();
+        }
+    }
+
+    /// Merges the provided [`TrackPatchCommand`] into the provided batch of
+    /// [`TrackPatchCommand`]s, so the batch contains a single
+    /// [`TrackPatchCommand`] per [`TrackId`], with the latest values winning.
+    fn merge_track_patch(
+        patches: &mut Vec<TrackPatchCommand>,
+        patch: TrackPatchCommand,
+    ) {
+        if let Some(batched) = patches.iter_mut().find(|p| p.id == patch.id) {
+            batched.enabled = patch.enabled.or(batched.enabled);
+            batched.muted = patch.muted.or(batched.muted);
+            batched.display_surface =
+                patch.display_surface.or(batched.display_surface);
+        } else {
+            patches.push(patch);
+        }
+    }
+
+    /// Sets the window during which simultaneous track changes of this
+    /// [`PeerConnection`] are collected into a single renegotiation.
+    ///
+    /// [`Duration::ZERO`] makes track changes to be sent on the next tick.
+    pub fn set_negotiation_debounce(&self, debounce: Duration) {
+        self.negotiation_debounce.set(debounce);
+    }
+
+    /// Sets [`JoinProgress`] of the `Room` this [`PeerConnection`] belongs to,
+    /// advanced by its [`SetupTimings`].
+    pub fn set_join_progress(&self, progress: Rc<JoinProgress>) {
+        self.setup_timings.set_join_progress(progress);
     }
 
     /// Returns all [`TrackId`]s of [`Sender`]s that match the provided
@@ -476,10 +770,128 @@ impl PeerConnection {
         self.media_connections.drop_send_tracks(kinds).await;
     }
 
+    /// Sets [`AdaptivePolicy`] of the outbound video of this
+    /// [`PeerConnection`].
+    ///
+    /// Restores the outbound video if it has been degraded by the previous
+    /// [`AdaptivePolicy`].
+    pub fn set_adaptive_policy(&self, policy: AdaptivePolicy) {
+        if let Some(adaptation) = self.adaptation.set_policy(policy) {
+            self.adapt_video(adaptation);
+        }
+    }
+
+    /// Applies the provided [`Adaptation`] to the encodings of all the video
+    /// [`Sender`]s of this [`PeerConnection`], and emits a
+    /// [`PeerEvent::VideoAdapted`].
+    ///
+    /// [`Sender`]: sender::Sender
+    fn adapt_video(&self, adaptation: Adaptation) {
+        let transceivers: Vec<_> = self
+            .media_connections
+            .get_senders()
+            .into_iter()
+            .filter(|s| s.caps().media_kind() == MediaKind::Video)
+            .map(|s| s.transceiver())
+            .collect();
+        let level = adaptation.level;
+        let sender = Rc::clone(&self.peer_events_sender);
+        platform::spawn(async move {
+            for transceiver in transceivers {
+                let res = transceiver
+                    .set_send_encodings(level.max_bitrate(), level.is_active())
+                    .await;
+                if let Some(Err(e)) = res {
+                    report_error(
+                        &sender,
+                        NonFatalErrorKind::Encoding,
+                        tracerr::new!(e),
+                    );
+                }
+            }
+        });
+
+        drop(
+            self.peer_events_sender
+                .unbounded_send(PeerEvent::VideoAdapted {
+                    peer_id: self.id,
+                    adaptation,
+                }),
+        );
+    }
+
     /// Filters out already sent stats, and send new stats from the provided
     /// [`platform::RtcStats`].
+    ///
+    /// Also accounts the provided [`platform::RtcStats`] in the
+    /// [`TrafficCounter`], the [`QualityLimitationCounter`], the
+    /// [`AvSyncCounter`], the [`TransportMonitor`], the [`AdaptationEngine`]
+    /// and the [`FreezeDetector`] of this [`PeerConnection`], and sends its
+    /// [`NegotiationStats`] and [`AvSyncInfo`] (along with the jitter buffer
+    /// targets of its [`remote::Track`]s) if they have changed since the last
+    /// time.
+    ///
+    /// Updates encoder and decoder [`CodecInfo`]s of the sent and received
+    /// tracks, sending a [`PeerEvent::SoftwareCodecFallback`] for each
+    /// detected [`CodecFallback`].
+    ///
+    /// [`CodecInfo`]: crate::media::CodecInfo
     pub fn send_peer_stats(&self, stats: platform::RtcStats) {
+        self.traffic.update(&stats.0);
+        self.quality_limitation.update(&stats.0);
+        let remote_tracks = self.media_connections.get_remote_tracks();
+        let jitter_buffer_target = |kind| {
+            remote_tracks
+                .iter()
+                .filter(|t| t.kind() == kind)
+                .filter_map(remote::Track::configured_jitter_buffer_target)
+                .max()
+        };
+        self.av_sync.update(&stats.0);
+        self.av_sync.set_jitter_buffer_targets(
+            jitter_buffer_target(MediaKind::Audio),
+            jitter_buffer_target(MediaKind::Video),
+        );
+        self.transport.update(&stats.0);
+        self.freeze.update(&stats.0, &remote_tracks);
+        let local_tracks: Vec<_> = self
+            .media_connections
+            .get_senders()
+            .iter()
+            .filter_map(|s| s.get_send_track())
+            .collect();
+        for fallback in
+            codec_info::update(&stats.0, &local_tracks, &remote_tracks)
+        {
+            drop(self.peer_events_sender.unbounded_send(
+                PeerEvent::SoftwareCodecFallback {
+                    peer_id: self.id,
+                    fallback,
+                },
+            ));
+        }
+        if let Some(adaptation) = self.adaptation.update(&stats.0) {
+            self.adapt_video(adaptation);
+        }
+        if let Some(stats) = self.negotiations.take_update() {
+            drop(self.peer_events_sender.unbounded_send(
+                PeerEvent::NegotiationStatsUpdate {
+                    peer_id: self.id,
+                    stats,
+                },
+            ));
+        }
+        if let Some(info) = self.av_sync.take_update() {
+            drop(self.peer_events_sender.unbounded_send(
+                PeerEvent::AvSyncUpdate {
+                    peer_id: self.id,
+                    info,
+                },
+            ));
+        }
+
         let mut stats_cache = self.sent_stats_cache.borrow_mut();
+        stats_cache.retain(|id, _| stats.0.iter().any(|s| s.id == *id));
         let stats = platform::RtcStats(
             stats
                 .0
@@ -515,12 +927,23 @@ impl PeerConnection {
         }
     }
 
+    /// Marks that the [`platform::RtcStats`] collection of this
+    /// [`PeerConnection`] is paused, so its [`TrafficCounter`] flags the gap
+    /// on resume.
+    pub fn mark_stats_gap(&self) {
+        self.traffic.mark_gap();
+    }
+
     /// Sends [`platform::RtcStats`] update of this [`PeerConnection`] to a
     /// server.
     pub async fn scrape_and_send_peer_stats(&self) {
         match self.peer.get_stats().await {
             Ok(stats) => self.send_peer_stats(stats),
-            Err(e) => log::error!("{e}"),
+            Err(e) => report_error(
+                &self.peer_events_sender,
+                NonFatalErrorKind::Stats,
+                e,
+            ),
         };
     }
 
@@ -548,6 +971,14 @@ impl PeerConnection {
         self.id
     }
 
+    /// Returns [`TransceiverInfo`]s of all the negotiated transceivers of this
+    /// [`PeerConnection`].
+    pub fn get_transceivers_info(
+        &self,
+    ) -> impl Future<Output = Vec<TransceiverInfo>> + 'static {
+        self.media_connections.get_transceivers_info(self.id)
+    }
+
     /// Handle `icecandidate` event from underlying peer emitting
     /// [`PeerEvent::IceCandidateDiscovered`] event into this peers
     /// `peer_events_sender`.
@@ -618,6 +1049,25 @@ impl PeerConnection {
         self.peer.restart_ice();
     }
 
+    /// Returns [`platform::OfferReceiveOptions`] to create a local SDP offer
+    /// of this [`PeerConnection`] with.
+    ///
+    /// In a [`ConnectionMode::Sfu`] everything is always received, since media
+    /// receiving is controlled by a media server there.
+    fn offer_receive_options(
+        &self,
+        mode: ConnectionMode,
+    ) -> platform::OfferReceiveOptions {
+        match mode {
+            ConnectionMode::Mesh => platform::OfferReceiveOptions {
+                audio: self.recv_constraints.is_audio_enabled(),
+                video: self.recv_constraints.is_video_device_enabled()
+                    || self.recv_constraints.is_video_display_enabled(),
+            },
+            ConnectionMode::Sfu => platform::OfferReceiveOptions::default(),
+        }
+    }
+
     /// Returns all [`TransceiverSide`]s from this [`PeerConnection`] with
     /// provided [`MediaKind`], [`TrackDirection`] and [`MediaSourceKind`].
     pub fn get_transceivers_sides(
@@ -764,7 +1214,7 @@ impl PeerConnection {
         let mut required_caps = SimpleTracksRequest::try_from(request)
             .map_err(tracerr::from_and_wrap!())?;
         required_caps
-            .merge(self.send_constraints.inner())
+            .merge(self.send_constraints.effective())
             .map_err(tracerr::map_from_and_wrap!())?;
 
         Ok(Some(required_caps))
@@ -785,8 +1235,11 @@ impl PeerConnection {
             let used_caps = MediaStreamSettings::from(&required_caps);
 
             let media_tracks = self
-                .media_manager
-                .get_tracks(used_caps)
+                .setup_timings
+                .measure(
+                    SetupPhase::GetUserMedia,
+                    self.media_manager.get_tracks(used_caps),
+                )
                 .await
                 .map_err(tracerr::map_from_and_wrap!())?;
             let peer_tracks = required_caps
@@ -867,11 +1320,19 @@ impl PeerConnection {
     /// Updates underlying [RTCPeerConnection][1]'s remote SDP with given
     /// description.
     ///
+    /// If applying a remote [SDP offer][`platform::SdpType::Offer`] fails
+    /// with a [transient][4] error (e.g. because of a glare with a local SDP
+    /// offer), then the [RTCPeerConnection][1] is rolled back to its stable
+    /// state and the SDP offer is applied once again.
+    ///
     /// # Errors
     ///
     /// With [`platform::RtcPeerConnectionError::SetRemoteDescriptionFailed`] if
     /// [RTCPeerConnection.setRemoteDescription()][2] fails.
     ///
+    /// With [`platform::RtcPeerConnectionError::RollbackFailed`] if rolling
+    /// back after a [transient][4] error fails.
+    ///
     /// With [`platform::RtcPeerConnectionError::AddIceCandidateFailed`] if
     /// [RtcPeerConnection.addIceCandidate()][3] fails when adding buffered ICE
     /// candidates.
@@ -879,14 +1340,26 @@ impl PeerConnection {
     /// [1]: https://w3.org/TR/webrtc/#rtcpeerconnection-interface
     /// [2]: https://w3.org/TR/webrtc#dom-peerconnection-setremotedescription
     /// [3]: https://w3.org/TR/webrtc#dom-peerconnection-addicecandidate
+    /// [4]: RtcPeerConnectionError::is_transient
     async fn set_remote_description(
         &self,
         desc: platform::SdpType,
     ) -> Result<(), Traced<RtcPeerConnectionError>> {
-        self.peer
-            .set_remote_description(desc)
-            .await
-            .map_err(tracerr::map_from_and_wrap!())?;
+        let is_offer = matches!(desc, platform::SdpType::Offer(_));
+        if let Err(e) = self.peer.set_remote_description(desc.clone()).await {
+            let (err, trace) = e.split();
+            if !(is_offer && err.is_transient()) {
+                return Err(Traced::compose(err, trace))
+                    .map_err(tracerr::wrap!());
+            }
+            log::warn!("Retrying remote SDP offer after rollback: {err}");
+            self.rollback().await.map_err(tracerr::wrap!())?;
+            self.peer
+                .set_remote_description(desc.clone())
+                .await
+                .map_err(tracerr::map_from_and_wrap!())?;
+        }
+        self.description_applied(Description::Remote(desc));
         self.has_remote_description.set(true);
         self.media_connections.sync_receivers().await;
 
@@ -913,6 +1386,99 @@ impl PeerConnection {
         Ok(())
     }
 
+    /// Accounts the provided [`Description`] being successfully applied in the
+    /// [`StableDescriptionsTracker`] and the [`MediaConnections`] `mid`s, and
+    /// emits a [`PeerEvent::SdpApplied`] for it.
+    fn description_applied(&self, desc: Description) {
+        self.stable_descriptions.applied(desc.clone());
+        self.media_connections.refresh_mids();
+        _ = self
+            .peer_events_sender
+            .unbounded_send(PeerEvent::SdpApplied {
+                peer_id: self.id,
+                event: desc.into(),
+            })
+            .ok();
+    }
+
+    /// Rollbacks the underlying [RTCPeerConnection][1] to its stable
+    /// signaling state.
+    ///
+    /// If a platform rejects an explicit rollback (e.g. Safari does so in some
+    /// signaling states), then the last [`StableDescriptions`] are re-applied
+    /// instead. The used [`RollbackStrategy`] is accounted in the
+    /// [`NegotiationStats`].
+    ///
+    /// # Errors
+    ///
+    /// With [`platform::RtcPeerConnectionError::RollbackFailed`] if an
+    /// explicit rollback fails and the [`StableDescriptions`] cannot be
+    /// re-applied.
+    ///
+    /// [1]: https://w3.org/TR/webrtc/#rtcpeerconnection-interface
+    async fn rollback(&self) -> Result<(), Traced<RtcPeerConnectionError>> {
+        let strategy = match self.peer.rollback().await {
+            Ok(()) => RollbackStrategy::Explicit,
+            Err(e) => {
+                let (err, trace) = e.split();
+                let stable = self
+                    .stable_descriptions
+                    .stable()
+                    .filter(|_| rollback::is_rollback_rejected(&err));
+                let Some(stable) = stable else {
+                    return Err(Traced::compose(err, trace))
+                        .map_err(tracerr::wrap!());
+                };
+                log::warn!("Re-applying the last stable SDP: {err}");
+                if let Err(e) = self.reapply_descriptions(stable).await {
+                    report_error(
+                        &self.peer_events_sender,
+                        NonFatalErrorKind::Negotiation,
+                        e,
+                    );
+                    return Err(Traced::compose(err, trace))
+                        .map_err(tracerr::wrap!());
+                }
+                RollbackStrategy::ReapplyStable
+            }
+        };
+        log::debug!("Rolled back with `{strategy}` strategy");
+        self.stable_descriptions.rolled_back();
+        self.negotiations.rolled_back(strategy);
+        Ok(())
+    }
+
+    /// Re-applies the provided [`StableDescriptions`] to the underlying
+    /// [RTCPeerConnection][1].
+    ///
+    /// # Errors
+    ///
+    /// With [`platform::RtcPeerConnectionError::SetLocalDescriptionFailed`] or
+    /// [`platform::RtcPeerConnectionError::SetRemoteDescriptionFailed`] if
+    /// any of the [`StableDescriptions`] cannot be applied.
+    ///
+    /// [1]: https://w3.org/TR/webrtc/#rtcpeerconnection-interface
+    async fn reapply_descriptions(
+        &self,
+        stable: StableDescriptions,
+    ) -> Result<(), Traced<RtcPeerConnectionError>> {
+        for step in stable.into_steps() {
+            let applied = match step {
+                Description::Local(platform::SdpType::Offer(sdp)) => {
+                    self.peer.set_offer(&sdp).await
+                }
+                Description::Local(platform::SdpType::Answer(sdp)) => {
+                    self.peer.set_answer(&sdp).await
+                }
+                Description::Remote(desc) => {
+                    self.peer.set_remote_description(desc).await
+                }
+            };
+            applied.map_err(tracerr::wrap!())?;
+        }
+        Ok(())
+    }
+
     /// Adds remote peers [ICE Candidate][1] to this peer.
     ///
     /// # Errors
@@ -956,6 +1522,13 @@ impl PeerConnection {
 #[cfg(feature = "mockable")]
 #[allow(clippy::multiple_inherent_impl)]
 impl PeerConnection {
+    /// Sends the currently collected track changes of this [`PeerConnection`]
+    /// right away, without waiting for its negotiation debounce window to
+    /// pass.
+    pub fn flush_negotiation(&self) {
+        _ = self.flush_negotiation_tx.unbounded_send(()).ok();
+    }
+
     /// Returns [`RtcStats`] of this [`PeerConnection`].
     ///
     /// # Errors
@@ -968,6 +1541,27 @@ impl PeerConnection {
         self.peer.get_stats().await
     }
 
+    /// Returns the [`QualitySummary`] of the outbound media of this
+    /// [`PeerConnection`] accumulated so far.
+    #[must_use]
+    pub fn quality_summary(&self) -> QualitySummary {
+        self.quality_limitation.summary()
+    }
+
+    /// Returns the last estimated [`AvSyncInfo`] of the inbound media of this
+    /// [`PeerConnection`].
+    #[must_use]
+    pub fn av_sync_info(&self) -> AvSyncInfo {
+        self.av_sync.info()
+    }
+
+    /// Returns [`platform::IceGatheringCounter`] of the ICE candidates
+    /// gathered by this [`PeerConnection`].
+    #[must_use]
+    pub fn ice_gathering(&self) -> Rc<platform::IceGatheringCounter> {
+        self.peer.ice_gathering()
+    }
+
     /// Indicates whether all [`Receiver`]s audio tracks are enabled.
     #[must_use]
     pub fn is_recv_audio_enabled(&self) -> bool {
@@ -1066,3 +1660,15 @@ impl Drop for PeerConnection {
             .on_ice_candidate::<Box<dyn FnMut(platform::IceCandidate)>>(None);
     }
 }
+
+/// Emits a [`PeerEvent::NonFatalError`] of the provided [`NonFatalErrorKind`]
+/// out of the provided error via the provided [`PeerEvent`]s sender.
+fn report_error<E: fmt::Display>(
+    sender: &mpsc::UnboundedSender<PeerEvent>,
+    kind: NonFatalErrorKind,
+    err: Traced<E>,
+) {
+    drop(sender.unbounded_send(PeerEvent::NonFatalError {
+        error: NonFatalError::new(kind, err),
+    }));
+}
@@ -0,0 +1,203 @@
+//! Accounting of SDP negotiations performed by a [`PeerConnection`].
+//!
+//! [`PeerConnection`]: super::PeerConnection
+
+use std::{cell::Cell, time::Duration};
+
+use medea_client_api_proto as proto;
+
+use crate::platform;
+
+use super::RollbackStrategy;
+
+/// Statistics of SDP offer/answer exchanges performed by a
+/// [`PeerConnection`].
+///
+/// [`PeerConnection`]: super::PeerConnection
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct NegotiationStats {
+    /// Number of the completed SDP offer/answer exchanges.
+    pub count: u32,
+
+    /// Wall-clock duration of the last completed SDP offer/answer exchange.
+    pub last_duration: Option<Duration>,
+
+    /// Number of the performed rollbacks to a stable signaling state.
+    pub rollbacks: u32,
+
+    /// Number of the rollbacks performed with the
+    /// [`RollbackStrategy::ReapplyStable`], since a platform has rejected an
+    /// explicit rollback.
+    pub fallback_rollbacks: u32,
+}
+
+impl From<NegotiationStats> for proto::NegotiationStats {
+    fn from(stats: NegotiationStats) -> Self {
+        Self {
+            count: stats.count,
+            last_duration_ms: stats
+                .last_duration
+                .map(|d| u64::try_from(d.as_millis()).unwrap_or(u64::MAX)),
+            rollbacks: stats.rollbacks,
+            fallback_rollbacks: stats.fallback_rollbacks,
+        }
+    }
+}
+
+/// Accumulator of [`NegotiationStats`] of a [`PeerConnection`].
+///
+/// An SDP offer/answer exchange is measured from creating a local SDP offer
+/// till applying the remote SDP answer on the offering side, and from applying
+/// the remote SDP offer till applying the local SDP answer on the answering
+/// side. Exchanges never completed (e.g. rolled back) are not accounted.
+///
+/// Lives as long as its [`PeerConnection`] does, so ICE restarts (being
+/// ordinary renegotiations) don't reset it.
+///
+/// [`PeerConnection`]: super::PeerConnection
+#[derive(Debug, Default)]
+pub struct NegotiationCounter {
+    /// Start time of the current SDP offer/answer exchange, if any.
+    started_at: Cell<Option<Duration>>,
+
+    /// Accumulated [`NegotiationStats`].
+    stats: Cell<NegotiationStats>,
+
+    /// Indicator whether the [`NegotiationStats`] have changed since the last
+    /// [`NegotiationCounter::take_update()`] call.
+    is_updated: Cell<bool>,
+}
+
+impl NegotiationCounter {
+    /// Starts measuring a new SDP offer/answer exchange, discarding the
+    /// uncompleted one, if any.
+    pub fn start(&self) {
+        self.start_at(platform::now());
+    }
+
+    /// Completes the SDP offer/answer exchange started with a
+    /// [`NegotiationCounter::start()`].
+    pub fn finish(&self) {
+        self.finish_at(platform::now());
+    }
+
+    /// Accounts a rollback to a stable signaling state performed with the
+    /// provided [`RollbackStrategy`].
+    pub fn rolled_back(&self, strategy: RollbackStrategy) {
+        let mut stats = self.stats.get();
+        stats.rollbacks = stats.rollbacks.saturating_add(1);
+        if strategy == RollbackStrategy::ReapplyStable {
+            stats.fallback_rollbacks =
+                stats.fallback_rollbacks.saturating_add(1);
+        }
+        self.stats.set(stats);
+        self.is_updated.set(true);
+    }
+
+    /// Returns the accumulated [`NegotiationStats`].
+    #[must_use]
+    pub fn stats(&self) -> NegotiationStats {
+        self.stats.get()
+    }
+
+    /// Returns the accumulated [`NegotiationStats`] if they have changed since
+    /// the previous call of this method.
+    pub fn take_update(&self) -> Option<NegotiationStats> {
+        self.is_updated.take().then(|| self.stats())
+    }
+
+    /// Starts measuring a new SDP offer/answer exchange at the provided time.
+    fn start_at(&self, now: Duration) {
+        self.started_at.set(Some(now));
+    }
+
+    /// Completes the current SDP offer/answer exchange at the provided time.
+    fn finish_at(&self, now: Duration) {
+        if let Some(started_at) = self.started_at.take() {
+            let stats = self.stats.get();
+            self.stats.set(NegotiationStats {
+                count: stats.count.saturating_add(1),
+                last_duration: Some(now.saturating_sub(started_at)),
+                ..stats
+            });
+            self.is_updated.set(true);
+        }
+    }
+}
+
+#[cfg(test)]
+mod spec {
+    use std::time::Duration;
+
+    use super::{NegotiationCounter, NegotiationStats, RollbackStrategy};
+
+    #[test]
+    fn counts_completed_negotiations() {
+        let counter = NegotiationCounter::default();
+        assert_eq!(counter.stats(), NegotiationStats::default());
+        assert_eq!(counter.take_update(), None);
+
+        counter.start_at(Duration::from_millis(100));
+        counter.finish_at(Duration::from_millis(350));
+        assert_eq!(
+            counter.take_update(),
+            Some(NegotiationStats {
+                count: 1,
+                last_duration: Some(Duration::from_millis(250)),
+                ..NegotiationStats::default()
+            }),
+        );
+        assert_eq!(counter.take_update(), None);
+
+        counter.start_at(Duration::from_millis(1000));
+        counter.finish_at(Duration::from_millis(1040));
+        assert_eq!(
+            counter.stats(),
+            NegotiationStats {
+                count: 2,
+                last_duration: Some(Duration::from_millis(40)),
+                ..NegotiationStats::default()
+            },
+        );
+    }
+
+    #[test]
+    fn ignores_uncompleted_negotiations() {
+        let counter = NegotiationCounter::default();
+
+        counter.finish_at(Duration::from_millis(100));
+        assert_eq!(counter.take_update(), None);
+
+        // The first exchange is rolled back, so it's restarted.
+        counter.start_at(Duration::from_millis(200));
+        counter.start_at(Duration::from_millis(300));
+        counter.finish_at(Duration::from_millis(400));
+        counter.finish_at(Duration::from_millis(500));
+        assert_eq!(
+            counter.stats(),
+            NegotiationStats {
+                count: 1,
+                last_duration: Some(Duration::from_millis(100)),
+                ..NegotiationStats::default()
+            },
+        );
+    }
+
+    #[test]
+    fn counts_rollbacks_by_strategy() {
+        let counter = NegotiationCounter::default();
+
+        counter.rolled_back(RollbackStrategy::Explicit);
+        counter.rolled_back(RollbackStrategy::ReapplyStable);
+        counter.rolled_back(RollbackStrategy::Explicit);
+
+        assert_eq!(
+            counter.take_update(),
+            Some(NegotiationStats {
+                rollbacks: 3,
+                fallback_rollbacks: 1,
+                ..NegotiationStats::default()
+            }),
+        );
+    }
+}
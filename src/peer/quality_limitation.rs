@@ -0,0 +1,242 @@
+//! Accounting of time the outbound video of a [`PeerConnection`] spends
+//! limited in quality.
+//!
+//! [`PeerConnection`]: super::PeerConnection
+
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    time::Duration,
+};
+
+use medea_client_api_proto::stats::{RtcStat, RtcStatsType, StatId};
+
+/// Total time the outbound RTP streams of a [`PeerConnection`] have spent in
+/// each quality limitation state.
+///
+/// [`PeerConnection`]: super::PeerConnection
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct QualitySummary {
+    /// Time spent without any quality limitation.
+    pub none: Duration,
+
+    /// Time spent limited due to CPU load.
+    pub cpu: Duration,
+
+    /// Time spent limited due to congestion cues during bandwidth estimation.
+    pub bandwidth: Duration,
+
+    /// Time spent limited for a reason other than the above.
+    pub other: Duration,
+}
+
+impl QualitySummary {
+    /// Returns a mutable reference to the duration of the provided
+    /// `qualityLimitationDurations` key, if it's a known one.
+    fn get_mut(&mut self, reason: &str) -> Option<&mut Duration> {
+        match reason {
+            "none" => Some(&mut self.none),
+            "cpu" => Some(&mut self.cpu),
+            "bandwidth" => Some(&mut self.bandwidth),
+            "other" => Some(&mut self.other),
+            _ => None,
+        }
+    }
+}
+
+/// Accumulator of a [`QualitySummary`] from the periodically scraped
+/// [`RtcStat`]s of a [`PeerConnection`].
+///
+/// Cumulative `qualityLimitationDurations` of every outbound RTP stream are
+/// diffed against their previous values, so a counter reset (like it happens
+/// when RTP streams are recreated on ICE restart) never decreases the
+/// [`QualitySummary`]. Streams without `qualityLimitationDurations` (as
+/// reported by some browsers) are ignored.
+///
+/// Only the durations of the streams present in the last [`RtcStat`]s are
+/// retained, so the memory held doesn't grow with the recreated RTP streams
+/// during a long call.
+///
+/// [`PeerConnection`]: super::PeerConnection
+#[derive(Debug, Default)]
+pub struct QualityLimitationCounter {
+    /// Last seen durations of outbound RTP streams by their [`StatId`]s.
+    last: RefCell<HashMap<StatId, QualitySummary>>,
+
+    /// Accumulated [`QualitySummary`].
+    summary: Cell<QualitySummary>,
+}
+
+impl QualityLimitationCounter {
+    /// Returns the accumulated [`QualitySummary`].
+    #[must_use]
+    pub fn summary(&self) -> QualitySummary {
+        self.summary.get()
+    }
+
+    /// Accumulates `qualityLimitationDurations` of the outbound RTP streams
+    /// from the provided [`RtcStat`]s.
+    pub fn update(&self, stats: &[RtcStat]) {
+        self.last
+            .borrow_mut()
+            .retain(|id, _| stats.iter().any(|s| s.id == *id));
+        for stat in stats {
+            let RtcStatsType::OutboundRtp(outbound) = &stat.stats else {
+                continue;
+            };
+            let Some(durations) = &outbound.quality_limitation_durations else {
+                continue;
+            };
+            let mut current = QualitySummary::default();
+            for (reason, secs) in durations {
+                // Malformed durations are ignored, as `Duration` cannot
+                // represent them.
+                if !secs.0.is_finite() || secs.0 < 0.0 {
+                    continue;
+                }
+                if let Some(d) = current.get_mut(reason) {
+                    *d = Duration::from_secs_f64(secs.0);
+                }
+            }
+            self.add_sample(&stat.id, current);
+        }
+    }
+
+    /// Accounts the provided current durations of the outbound RTP stream
+    /// with the provided [`StatId`].
+    fn add_sample(&self, id: &StatId, current: QualitySummary) {
+        let prev = self
+            .last
+            .borrow_mut()
+            .insert(id.clone(), current)
+            .unwrap_or_default();
+
+        let mut summary = self.summary.get();
+        for (total, cur, prev) in [
+            (&mut summary.none, current.none, prev.none),
+            (&mut summary.cpu, current.cpu, prev.cpu),
+            (&mut summary.bandwidth, current.bandwidth, prev.bandwidth),
+            (&mut summary.other, current.other, prev.other),
+        ] {
+            // A duration less than the previous one means it has been reset,
+            // so all of it is a new one.
+            *total += cur.checked_sub(prev).unwrap_or(cur);
+        }
+        self.summary.set(summary);
+    }
+}
+
+#[cfg(test)]
+mod spec {
+    use std::time::Duration;
+
+    use medea_client_api_proto::stats::RtcStat;
+
+    use crate::peer::stats_fixture::stats;
+
+    use super::{QualityLimitationCounter, QualitySummary};
+
+    /// Parses the provided `outbound-rtp` [`RtcStat`]s fixture.
+    fn outbound(durations: &[serde_json::Value]) -> Vec<RtcStat> {
+        stats(serde_json::Value::Array(
+            durations
+                .iter()
+                .enumerate()
+                .map(|(i, d)| {
+                    let mut stat = serde_json::json!({
+                        "id": format!("out{i}"),
+                        "timestamp": 1.0,
+                        "type": "outbound-rtp",
+                        "mediaType": "video",
+                        "bytesSent": 100,
+                    });
+                    if !d.is_null() {
+                        stat["qualityLimitationDurations"] = d.clone();
+                    }
+                    stat
+                })
+                .collect(),
+        ))
+    }
+
+    #[test]
+    fn parses_quality_limitation_durations() {
+        let stats = outbound(&[
+            serde_json::json!({
+                "none": 10.5,
+                "cpu": 2.0,
+                "bandwidth": 0.25,
+                "other": 0
+            }),
+            serde_json::Value::Null,
+        ]);
+        let counter = QualityLimitationCounter::default();
+
+        counter.update(&stats);
+        assert_eq!(
+            counter.summary(),
+            QualitySummary {
+                none: Duration::from_millis(10500),
+                cpu: Duration::from_secs(2),
+                bandwidth: Duration::from_millis(250),
+                other: Duration::ZERO,
+            },
+        );
+    }
+
+    #[test]
+    fn accumulates_deltas_across_counter_reset() {
+        let counter = QualityLimitationCounter::default();
+
+        counter.update(&outbound(&[serde_json::json!({
+            "none": 5.0,
+            "cpu": 1.0,
+        })]));
+        counter.update(&outbound(&[serde_json::json!({
+            "none": 7.0,
+            "cpu": 4.0,
+        })]));
+        assert_eq!(
+            counter.summary(),
+            QualitySummary {
+                none: Duration::from_secs(7),
+                cpu: Duration::from_secs(4),
+                ..QualitySummary::default()
+            },
+        );
+
+        // Durations are reset, like on ICE restart.
+        counter.update(&outbound(&[serde_json::json!({
+            "none": 1.0,
+            "bandwidth": 3.0,
+            "unknown": 100.0,
+        })]));
+        assert_eq!(
+            counter.summary(),
+            QualitySummary {
+                none: Duration::from_secs(8),
+                cpu: Duration::from_secs(4),
+                bandwidth: Duration::from_secs(3),
+                other: Duration::ZERO,
+            },
+        );
+    }
+
+    #[test]
+    fn retains_only_last_snapshot_in_long_call() {
+        let counter = QualityLimitationCounter::default();
+
+        for i in 0..10_000_u32 {
+            // RTP streams are recreated every 10 snapshots, like on ICE
+            // restarts or renegotiations.
+            let mut stats = outbound(&[serde_json::json!({
+                "none": f64::from(i % 10 + 1),
+            })]);
+            stats[0].id = format!("out{}", i / 10).into();
+            counter.update(&stats);
+
+            assert!(counter.last.borrow().len() <= stats.len());
+        }
+        assert_eq!(counter.summary().none, Duration::from_secs(10_000));
+    }
+}
@@ -1,8 +1,13 @@
 //! Component responsible for the [`peer::Component`] creating and removing.
 
-use std::{cell::RefCell, collections::HashMap, rc::Rc, time::Duration};
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    rc::{Rc, Weak},
+    time::Duration,
+};
 
-use futures::{channel::mpsc, future};
+use futures::{channel::mpsc, future, StreamExt as _};
 use medea_client_api_proto::{self as proto, PeerId};
 use medea_macro::watchers;
 use medea_reactive::ObservableHashMap;
@@ -19,7 +24,7 @@ use crate::{
     },
 };
 
-use super::{PeerConnection, PeerEvent};
+use super::{AdaptivePolicy, JoinProgress, PeerConnection, PeerEvent};
 
 /// Component responsible for the [`peer::Component`] creating and removing.
 pub type Component = component::Component<State, Repository>;
@@ -41,6 +46,91 @@ impl Component {
             .collect()
     }
 
+    /// Sets the window during which simultaneous track changes are collected
+    /// into a single renegotiation for all the existing and future
+    /// [`PeerConnection`]s.
+    pub fn set_negotiation_debounce(&self, debounce: Duration) {
+        self.negotiation_debounce.set(debounce);
+        for peer in self.peers.borrow().values() {
+            peer.set_negotiation_debounce(debounce);
+        }
+    }
+
+    /// Returns [`JoinProgress`] advanced by [`PeerConnection`]s from this
+    /// [`Repository`].
+    #[must_use]
+    pub const fn join_progress(&self) -> &Rc<JoinProgress> {
+        &self.join_progress
+    }
+
+    /// Sets [`AdaptivePolicy`] of the outbound video for all the existing and
+    /// future [`PeerConnection`]s.
+    pub fn set_adaptive_policy(&self, policy: AdaptivePolicy) {
+        self.adaptive_policy.set(policy);
+        for peer in self.peers.borrow().values() {
+            peer.set_adaptive_policy(policy);
+        }
+    }
+
+    /// Enables or disables the periodic [`platform::RtcStats`] collection of
+    /// all the [`PeerConnection`]s, setting its interval (1 second if
+    /// [`None`]).
+    ///
+    /// While disabled, no stats are scraped at all, so all the stats-driven
+    /// accounting is frozen. Cumulative counters continue from their last
+    /// values once re-enabled.
+    pub fn set_stats_collection(
+        &self,
+        enabled: bool,
+        interval: Option<Duration>,
+    ) {
+        self.stats_collector.is_enabled.set(enabled);
+        self.stats_collector
+            .interval
+            .set(interval.unwrap_or(StatsCollector::DEFAULT_INTERVAL));
+        self.stats_collector.restart();
+    }
+
+    /// Makes the periodic [`platform::RtcStats`] collection be paused while
+    /// the page is hidden (switched to a background tab, for example).
+    ///
+    /// Takes effect since the next page visibility change.
+    pub fn set_pause_stats_when_hidden(&self, enabled: bool) {
+        let task = enabled.then(|| {
+            StatsCollector::spawn_visibility_listener(Rc::downgrade(
+                &self.stats_collector,
+            ))
+        });
+        drop(self.stats_collector.visibility_task.replace(task));
+        if !enabled {
+            self.stats_collector.is_hidden.set(false);
+            self.stats_collector.restart();
+        }
+    }
+
+    /// Sets [`platform::RtcConfiguration`] of all the future
+    /// [`PeerConnection`]s.
+    pub fn set_rtc_configuration(&self, config: platform::RtcConfiguration) {
+        self.rtc_configuration.set(config);
+    }
+
+    /// Indicates whether this [`Component`] has no [`peer::State`]s, meaning
+    /// that no [`PeerConnection`]s are created or being created.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.state().0.borrow().iter().next().is_none()
+    }
+
+    /// Removes all the [`peer::State`]s, so all the [`PeerConnection`]s are
+    /// dropped and their pending negotiations are abandoned.
+    pub fn remove_all(&self) {
+        let state = self.state();
+        let ids: Vec<_> = state.0.borrow().iter().map(|(id, _)| *id).collect();
+        for id in ids {
+            state.remove(id);
+        }
+    }
+
     /// Notifies all [`peer::Component`]s about a RPC connection loss.
     pub fn connection_lost(&self) {
         for peer in self.peers.borrow().values() {
@@ -89,13 +179,8 @@ pub struct Repository {
     /// Peer id to [`PeerConnection`],
     peers: Rc<RefCell<HashMap<PeerId, peer::Component>>>,
 
-    /// [`TaskHandle`] for a task which will call
-    /// [`PeerConnection::send_peer_stats`] of all [`PeerConnection`]s
-    /// every second and send updated [`PeerMetrics::RtcStats`] to the server.
-    ///
-    /// [`PeerMetrics::RtcStats`]:
-    /// medea_client_api_proto::PeerMetrics::RtcStats
-    _stats_scrape_task: TaskHandle,
+    /// [`StatsCollector`] of all the [`PeerConnection`]s.
+    stats_collector: Rc<StatsCollector>,
 
     /// Channel for sending events produced by [`PeerConnection`] to [`Room`].
     ///
@@ -121,6 +206,22 @@ pub struct Repository {
     ///
     /// [`remote::Track`]: crate::media::track::remote::Track
     recv_constraints: Rc<RecvConstraints>,
+
+    /// Window during which simultaneous track changes of [`PeerConnection`]s
+    /// from this [`Repository`] are collected into a single renegotiation.
+    negotiation_debounce: Cell<Duration>,
+
+    /// [`platform::RtcConfiguration`] of [`PeerConnection`]s created by this
+    /// [`Repository`].
+    rtc_configuration: Cell<platform::RtcConfiguration>,
+
+    /// [`AdaptivePolicy`] of the outbound video of [`PeerConnection`]s from
+    /// this [`Repository`].
+    adaptive_policy: Cell<AdaptivePolicy>,
+
+    /// [`JoinProgress`] advanced by [`PeerConnection`]s from this
+    /// [`Repository`].
+    join_progress: Rc<JoinProgress>,
 }
 
 impl Repository {
@@ -136,30 +237,137 @@ impl Repository {
         connections: Rc<Connections>,
     ) -> Self {
         let peers = Rc::default();
+        let stats_collector = Rc::new(StatsCollector::new(Rc::clone(&peers)));
+        stats_collector.restart();
         Self {
             media_manager,
-            _stats_scrape_task: Self::spawn_peers_stats_scrape_task(Rc::clone(
-                &peers,
-            )),
+            stats_collector,
             peers,
             peer_event_sender,
             send_constraints,
             recv_constraints,
             connections,
+            negotiation_debounce: Cell::new(
+                PeerConnection::DEFAULT_NEGOTIATION_DEBOUNCE,
+            ),
+            rtc_configuration: Cell::default(),
+            adaptive_policy: Cell::default(),
+            join_progress: Rc::default(),
         }
     }
+}
+
+/// Periodic collection of the [`platform::RtcStats`] of [`PeerConnection`]s.
+#[derive(Debug)]
+struct StatsCollector {
+    /// [`PeerConnection`]s to collect the [`platform::RtcStats`] of.
+    peers: Rc<RefCell<HashMap<PeerId, peer::Component>>>,
+
+    /// Indicator whether the [`platform::RtcStats`] collection is enabled.
+    is_enabled: Cell<bool>,
+
+    /// Interval of the [`platform::RtcStats`] collection.
+    interval: Cell<Duration>,
+
+    /// Indicator whether the page is hidden, while the
+    /// [`platform::RtcStats`] collection is paused.
+    is_hidden: Cell<bool>,
+
+    /// [`TaskHandle`] for a task which will call
+    /// [`PeerConnection::send_peer_stats`] of all [`PeerConnection`]s
+    /// every `interval` and send updated [`PeerMetrics::RtcStats`] to the
+    /// server, if the collection is active.
+    ///
+    /// [`PeerMetrics::RtcStats`]:
+    /// medea_client_api_proto::PeerMetrics::RtcStats
+    scrape_task: RefCell<Option<TaskHandle>>,
+
+    /// [`TaskHandle`] for a task pausing the [`platform::RtcStats`]
+    /// collection while the page is hidden, if enabled.
+    visibility_task: RefCell<Option<TaskHandle>>,
+}
+
+impl StatsCollector {
+    /// Default interval of the [`platform::RtcStats`] collection.
+    const DEFAULT_INTERVAL: Duration = Duration::from_secs(1);
+
+    /// Creates a new inactive [`StatsCollector`] of the provided
+    /// [`PeerConnection`]s.
+    fn new(peers: Rc<RefCell<HashMap<PeerId, peer::Component>>>) -> Self {
+        Self {
+            peers,
+            is_enabled: Cell::new(true),
+            interval: Cell::new(Self::DEFAULT_INTERVAL),
+            is_hidden: Cell::new(false),
+            scrape_task: RefCell::default(),
+            visibility_task: RefCell::default(),
+        }
+    }
+
+    /// Indicates whether the [`platform::RtcStats`] should be collected.
+    fn is_active(&self) -> bool {
+        self.is_enabled.get() && !self.is_hidden.get()
+    }
+
+    /// (Re)spawns the [`platform::RtcStats`] collection task according to
+    /// the current settings, or stops it if the collection is not active.
+    ///
+    /// Marks a gap in the accounting of all the [`PeerConnection`]s once the
+    /// collection is paused.
+    fn restart(&self) {
+        let task = self.is_active().then(|| {
+            Self::spawn_scrape_task(Rc::clone(&self.peers), self.interval.get())
+        });
+        let was_active = self.scrape_task.replace(task).is_some();
+        if was_active && !self.is_active() {
+            for peer in self.peers.borrow().values() {
+                peer.obj().mark_stats_gap();
+            }
+        }
+    }
+
+    /// Spawns a task pausing the [`platform::RtcStats`] collection of the
+    /// provided [`StatsCollector`] while the page is hidden.
+    ///
+    /// Returns [`TaskHandle`] which will stop this task on its [`Drop`].
+    fn spawn_visibility_listener(this: Weak<Self>) -> TaskHandle {
+        let mut events = platform::on_network_events();
+        let (fut, abort) = future::abortable(async move {
+            while let Some(event) = events.next().await {
+                let is_hidden = match event {
+                    platform::NetworkEvent::Hidden => true,
+                    platform::NetworkEvent::Visible => false,
+                    platform::NetworkEvent::Online
+                    | platform::NetworkEvent::Offline => continue,
+                };
+                let Some(this) = this.upgrade() else {
+                    break;
+                };
+                if this.is_hidden.replace(is_hidden) != is_hidden {
+                    this.restart();
+                }
+            }
+        });
+
+        platform::spawn(async move {
+            _ = fut.await.ok();
+        });
+
+        abort.into()
+    }
 
     /// Spawns a task which will call [`PeerConnection::send_peer_stats()`] of
-    /// all [`PeerConnection`]s every second and send updated
+    /// all [`PeerConnection`]s every provided `interval` and send updated
     /// [`platform::RtcStats`] to a server.
     ///
     /// Returns [`TaskHandle`] which will stop this task on its [`Drop`].
-    fn spawn_peers_stats_scrape_task(
+    fn spawn_scrape_task(
         peers: Rc<RefCell<HashMap<PeerId, peer::Component>>>,
+        interval: Duration,
     ) -> TaskHandle {
         let (fut, abort) = future::abortable(async move {
             loop {
-                platform::delay_for(Duration::from_secs(1)).await;
+                platform::delay_for(interval).await;
 
                 let peers = peers
                     .borrow()
@@ -227,19 +435,21 @@ impl Component {
         _: Rc<State>,
         (peer_id, new_peer): (PeerId, Rc<peer::State>),
     ) -> Result<(), Traced<RtcPeerConnectionError>> {
-        let peer = peer::Component::new(
-            PeerConnection::new(
-                &new_peer,
-                peers.peer_event_sender.clone(),
-                Rc::clone(&peers.media_manager),
-                peers.send_constraints.clone(),
-                Rc::clone(&peers.connections),
-                Rc::clone(&peers.recv_constraints),
-            )
-            .await
-            .map_err(tracerr::map_from_and_wrap!())?,
-            new_peer,
-        );
+        let peer_conn = PeerConnection::new(
+            &new_peer,
+            peers.peer_event_sender.clone(),
+            Rc::clone(&peers.media_manager),
+            peers.send_constraints.clone(),
+            Rc::clone(&peers.connections),
+            Rc::clone(&peers.recv_constraints),
+            &peers.rtc_configuration.get(),
+        )
+        .await
+        .map_err(tracerr::map_from_and_wrap!())?;
+        peer_conn.set_negotiation_debounce(peers.negotiation_debounce.get());
+        peer_conn.set_adaptive_policy(peers.adaptive_policy.get());
+        peer_conn.set_join_progress(Rc::clone(&peers.join_progress));
+        let peer = peer::Component::new(peer_conn, new_peer);
 
         drop(peers.peers.borrow_mut().insert(peer_id, peer));
 
@@ -0,0 +1,334 @@
+//! Rolling back SDP negotiations of a [`PeerConnection`].
+//!
+//! Some platforms (e.g. Safari) reject an explicit [rollback][1] in some
+//! signaling states, so the last stable SDP offer/answer exchange is
+//! re-applied instead.
+//!
+//! [`PeerConnection`]: super::PeerConnection
+//! [1]: https://w3.org/TR/webrtc#dom-rtcsdptype-rollback
+
+use std::cell::RefCell;
+
+use derive_more::Display;
+
+use crate::platform::{RtcPeerConnectionError, SdpType};
+
+/// Names of the exceptions thrown by the platforms rejecting an explicit
+/// rollback.
+const ROLLBACK_REJECTIONS: [&str; 2] = ["InvalidStateError", "OperationError"];
+
+/// Strategy a [`PeerConnection`] has been rolled back to its stable signaling
+/// state with.
+///
+/// [`PeerConnection`]: super::PeerConnection
+#[derive(Clone, Copy, Debug, Display, Eq, PartialEq)]
+pub enum RollbackStrategy {
+    /// Setting a local description of a [rollback][1] type.
+    ///
+    /// [1]: https://w3.org/TR/webrtc#dom-rtcsdptype-rollback
+    #[display(fmt = "explicit")]
+    Explicit,
+
+    /// Re-applying the last [`StableDescriptions`].
+    #[display(fmt = "reapply_stable")]
+    ReapplyStable,
+}
+
+/// SDP applied to a [`platform::RtcPeerConnection`] as its local or remote
+/// description.
+///
+/// [`platform::RtcPeerConnection`]: crate::platform::RtcPeerConnection
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Description {
+    /// Local description.
+    Local(SdpType),
+
+    /// Remote description.
+    Remote(SdpType),
+}
+
+impl Description {
+    /// Indicates whether this [`Description`] is an SDP offer.
+    #[must_use]
+    pub const fn is_offer(&self) -> bool {
+        matches!(
+            self,
+            Self::Local(SdpType::Offer(_)) | Self::Remote(SdpType::Offer(_))
+        )
+    }
+}
+
+/// SDP offer/answer exchange applied the last time a [`PeerConnection`] has
+/// reached its stable signaling state.
+///
+/// [`PeerConnection`]: super::PeerConnection
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StableDescriptions {
+    /// Applied SDP offer.
+    pub offer: Description,
+
+    /// Applied SDP answer.
+    pub answer: Description,
+}
+
+impl StableDescriptions {
+    /// Returns [`Description`]s to be applied (in the returned order) for
+    /// restoring these [`StableDescriptions`].
+    #[must_use]
+    pub fn into_steps(self) -> [Description; 2] {
+        [self.offer, self.answer]
+    }
+}
+
+/// Tracker of the [`StableDescriptions`] of a [`PeerConnection`].
+///
+/// [`PeerConnection`]: super::PeerConnection
+#[derive(Debug, Default)]
+pub struct StableDescriptionsTracker {
+    /// SDP offer applied, but not answered yet.
+    pending_offer: RefCell<Option<Description>>,
+
+    /// Last [`StableDescriptions`], if any.
+    stable: RefCell<Option<StableDescriptions>>,
+}
+
+impl StableDescriptionsTracker {
+    /// Accounts the provided [`Description`] being successfully applied.
+    ///
+    /// An SDP answer completes the pending SDP offer, making them the new
+    /// [`StableDescriptions`].
+    pub fn applied(&self, desc: Description) {
+        if desc.is_offer() {
+            drop(self.pending_offer.replace(Some(desc)));
+            return;
+        }
+        if let Some(offer) = self.pending_offer.take() {
+            drop(self.stable.replace(Some(StableDescriptions {
+                offer,
+                answer: desc,
+            })));
+        }
+    }
+
+    /// Discards the pending SDP offer, since the signaling has been rolled
+    /// back to the [`StableDescriptions`].
+    pub fn rolled_back(&self) {
+        drop(self.pending_offer.take());
+    }
+
+    /// Returns the last [`StableDescriptions`], if any.
+    #[must_use]
+    pub fn stable(&self) -> Option<StableDescriptions> {
+        self.stable.borrow().clone()
+    }
+}
+
+/// Indicates whether the provided [`RtcPeerConnectionError`] means that a
+/// platform has rejected an explicit rollback, so the
+/// [`RollbackStrategy::ReapplyStable`] should be used.
+#[must_use]
+pub fn is_rollback_rejected(err: &RtcPeerConnectionError) -> bool {
+    matches!(err, RtcPeerConnectionError::RollbackFailed(_))
+        && err
+            .exception_name()
+            .map_or(false, |name| is_rejection(&name))
+}
+
+/// Indicates whether the provided exception name is the one thrown by the
+/// platforms rejecting an explicit rollback.
+fn is_rejection(name: &str) -> bool {
+    ROLLBACK_REJECTIONS.contains(&name)
+}
+
+#[cfg(test)]
+mod spec {
+    use crate::platform::SdpType;
+
+    use super::{
+        is_rejection, Description, StableDescriptions,
+        StableDescriptionsTracker,
+    };
+
+    /// Simplified model of a [signaling state][1] machine of a platform
+    /// rejecting an explicit rollback.
+    ///
+    /// [1]: https://w3.org/TR/webrtc#rtcsignalingstate-enum
+    #[derive(Debug, Default)]
+    struct Signaling {
+        /// Current local description.
+        local: Option<SdpType>,
+
+        /// Current remote description.
+        remote: Option<SdpType>,
+
+        /// Pending SDP offer, if the signaling isn't stable.
+        pending: Option<Description>,
+    }
+
+    impl Signaling {
+        /// Applies the provided [`Description`], returning the exception
+        /// name if it's not allowed in the current signaling state.
+        fn apply(&mut self, desc: Description) -> Result<(), &'static str> {
+            match (&self.pending, desc.is_offer()) {
+                (Some(Description::Local(_)), true)
+                    if matches!(desc, Description::Remote(_)) =>
+                {
+                    return Err("InvalidStateError");
+                }
+                (Some(Description::Remote(_)), true)
+                    if matches!(desc, Description::Local(_)) =>
+                {
+                    return Err("InvalidStateError");
+                }
+                (None, false) => return Err("InvalidStateError"),
+                _ => {}
+            }
+            self.pending = desc.is_offer().then(|| desc.clone());
+            match desc {
+                Description::Local(sdp) => self.local = Some(sdp),
+                Description::Remote(sdp) => self.remote = Some(sdp),
+            }
+            Ok(())
+        }
+
+        /// Emulates an explicit rollback being rejected in a non-stable
+        /// signaling state.
+        const fn rollback(&self) -> Result<(), &'static str> {
+            if self.is_stable() {
+                Ok(())
+            } else {
+                Err("InvalidStateError")
+            }
+        }
+
+        /// Indicates whether the signaling is stable.
+        const fn is_stable(&self) -> bool {
+            self.pending.is_none()
+        }
+    }
+
+    /// Applies the provided [`Description`] to both the provided [`Signaling`]
+    /// and [`StableDescriptionsTracker`].
+    fn apply(
+        signaling: &mut Signaling,
+        tracker: &StableDescriptionsTracker,
+        desc: Description,
+    ) {
+        signaling.apply(desc.clone()).unwrap();
+        tracker.applied(desc);
+    }
+
+    /// Rolls back the provided [`Signaling`] re-applying the
+    /// [`StableDescriptions`] once an explicit rollback is rejected.
+    fn rollback(
+        signaling: &mut Signaling,
+        tracker: &StableDescriptionsTracker,
+    ) {
+        let err = signaling.rollback().unwrap_err();
+        assert!(is_rejection(err));
+
+        for step in tracker.stable().unwrap().into_steps() {
+            signaling.apply(step).unwrap();
+        }
+        tracker.rolled_back();
+    }
+
+    #[test]
+    fn tracks_stable_descriptions() {
+        let tracker = StableDescriptionsTracker::default();
+        tracker.applied(Description::Local(SdpType::Offer("o1".into())));
+        assert_eq!(tracker.stable(), None);
+
+        tracker.applied(Description::Remote(SdpType::Answer("a1".into())));
+        tracker.applied(Description::Remote(SdpType::Offer("o2".into())));
+
+        assert_eq!(
+            tracker.stable(),
+            Some(StableDescriptions {
+                offer: Description::Local(SdpType::Offer("o1".into())),
+                answer: Description::Remote(SdpType::Answer("a1".into())),
+            }),
+        );
+    }
+
+    #[test]
+    fn restores_stable_state_after_local_offer() {
+        let mut signaling = Signaling::default();
+        let tracker = StableDescriptionsTracker::default();
+        apply(
+            &mut signaling,
+            &tracker,
+            Description::Local(SdpType::Offer("o1".into())),
+        );
+        apply(
+            &mut signaling,
+            &tracker,
+            Description::Remote(SdpType::Answer("a1".into())),
+        );
+        apply(
+            &mut signaling,
+            &tracker,
+            Description::Local(SdpType::Offer("o2".into())),
+        );
+        assert!(!signaling.is_stable());
+
+        rollback(&mut signaling, &tracker);
+
+        assert!(signaling.is_stable());
+        assert_eq!(signaling.local, Some(SdpType::Offer("o1".into())));
+        assert_eq!(signaling.remote, Some(SdpType::Answer("a1".into())));
+
+        apply(
+            &mut signaling,
+            &tracker,
+            Description::Local(SdpType::Offer("o3".into())),
+        );
+        apply(
+            &mut signaling,
+            &tracker,
+            Description::Remote(SdpType::Answer("a3".into())),
+        );
+        assert_eq!(
+            tracker.stable().unwrap().into_steps(),
+            [
+                Description::Local(SdpType::Offer("o3".into())),
+                Description::Remote(SdpType::Answer("a3".into())),
+            ],
+        );
+    }
+
+    #[test]
+    fn restores_stable_state_after_remote_offer() {
+        let mut signaling = Signaling::default();
+        let tracker = StableDescriptionsTracker::default();
+        apply(
+            &mut signaling,
+            &tracker,
+            Description::Remote(SdpType::Offer("o1".into())),
+        );
+        apply(
+            &mut signaling,
+            &tracker,
+            Description::Local(SdpType::Answer("a1".into())),
+        );
+        apply(
+            &mut signaling,
+            &tracker,
+            Description::Remote(SdpType::Offer("o2".into())),
+        );
+
+        rollback(&mut signaling, &tracker);
+
+        assert!(signaling.is_stable());
+        assert_eq!(signaling.remote, Some(SdpType::Offer("o1".into())));
+        assert_eq!(signaling.local, Some(SdpType::Answer("a1".into())));
+    }
+
+    #[test]
+    fn detects_rejected_rollback() {
+        assert!(is_rejection("InvalidStateError"));
+        assert!(is_rejection("OperationError"));
+        assert!(!is_rejection("InvalidModificationError"));
+        assert!(!is_rejection("TypeError"));
+    }
+}
@@ -0,0 +1,188 @@
+//! SDP descriptions applied to a [`PeerConnection`], reported for debugging.
+//!
+//! [`PeerConnection`]: super::PeerConnection
+
+use derive_more::Display;
+
+use crate::platform::SdpType;
+
+use super::Description;
+
+/// Replacement of the redacted values.
+const REDACTED: &str = "<redacted>";
+
+/// Side of a [`PeerConnection`] an SDP description is applied as.
+///
+/// [`PeerConnection`]: super::PeerConnection
+#[derive(Clone, Copy, Debug, Display, Eq, PartialEq)]
+pub enum SdpDirection {
+    /// SDP is applied as a local description.
+    Local,
+
+    /// SDP is applied as a remote description.
+    Remote,
+}
+
+/// Type of an SDP description.
+#[derive(Clone, Copy, Debug, Display, Eq, PartialEq)]
+pub enum SdpKind {
+    /// [`offer` type][1] of SDP.
+    ///
+    /// [1]: https://w3.org/TR/webrtc#dom-rtcsdptype-offer
+    Offer,
+
+    /// [`answer` type][1] of SDP.
+    ///
+    /// [1]: https://w3.org/TR/webrtc#dom-rtcsdptype-answer
+    Answer,
+}
+
+/// SDP description successfully applied to a [`PeerConnection`].
+///
+/// [`PeerConnection`]: super::PeerConnection
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SdpEvent {
+    /// [`SdpDirection`] the SDP is applied as.
+    pub direction: SdpDirection,
+
+    /// [`SdpKind`] of the SDP.
+    pub kind: SdpKind,
+
+    /// Applied SDP itself.
+    pub sdp: String,
+}
+
+impl SdpEvent {
+    /// Returns this [`SdpEvent`] with its SDP redacted by the
+    /// [`redact_sdp()`].
+    #[must_use]
+    pub fn redacted(self) -> Self {
+        Self {
+            sdp: redact_sdp(&self.sdp),
+            ..self
+        }
+    }
+}
+
+impl From<Description> for SdpEvent {
+    fn from(desc: Description) -> Self {
+        let (direction, sdp) = match desc {
+            Description::Local(sdp) => (SdpDirection::Local, sdp),
+            Description::Remote(sdp) => (SdpDirection::Remote, sdp),
+        };
+        let (kind, sdp) = match sdp {
+            SdpType::Offer(sdp) => (SdpKind::Offer, sdp),
+            SdpType::Answer(sdp) => (SdpKind::Answer, sdp),
+        };
+        Self {
+            direction,
+            kind,
+            sdp,
+        }
+    }
+}
+
+/// Masks ICE credentials (`ice-ufrag` and `ice-pwd` attributes) and IP
+/// addresses (of `candidate` attributes and connection data lines) in the
+/// provided SDP, preserving its line endings.
+#[must_use]
+pub fn redact_sdp(sdp: &str) -> String {
+    let mut redacted = String::with_capacity(sdp.len());
+    for line in sdp.split_inclusive('\n') {
+        let content = line.trim_end_matches(['\r', '\n']);
+        let ending = &line[content.len()..];
+        redacted.push_str(&redact_line(content));
+        redacted.push_str(ending);
+    }
+    redacted
+}
+
+/// Redacts the provided single SDP line (without its ending).
+fn redact_line(line: &str) -> String {
+    for prefix in ["a=ice-ufrag:", "a=ice-pwd:"] {
+        if line.starts_with(prefix) {
+            return format!("{prefix}{REDACTED}");
+        }
+    }
+    if line.starts_with("a=candidate:") {
+        // `a=candidate:<foundation> <component> <transport> <priority>
+        // <address> <port> typ <type> [raddr <address> rport <port>] ...`
+        let mut fields: Vec<_> = line.split(' ').collect();
+        if let Some(address) = fields.get_mut(4) {
+            *address = REDACTED;
+        }
+        if let Some(i) = fields.iter().position(|f| *f == "raddr") {
+            if let Some(address) = fields.get_mut(i + 1) {
+                *address = REDACTED;
+            }
+        }
+        return fields.join(" ");
+    }
+    if let Some(data) = line.strip_prefix("c=") {
+        // `c=<nettype> <addrtype> <connection-address>`
+        let mut fields: Vec<_> = data.split(' ').collect();
+        if let Some(address) = fields.get_mut(2) {
+            *address = REDACTED;
+        }
+        return format!("c={}", fields.join(" "));
+    }
+    line.to_owned()
+}
+
+#[cfg(test)]
+mod spec {
+    use crate::{peer::Description, platform::SdpType};
+
+    use super::{redact_sdp, SdpDirection, SdpEvent, SdpKind};
+
+    #[test]
+    fn redacts_credentials_and_addresses() {
+        let sdp = "v=0\r\n\
+                   o=- 4611731400430051336 2 IN IP4 127.0.0.1\r\n\
+                   m=audio 9 UDP/TLS/RTP/SAVPF 111\r\n\
+                   c=IN IP4 192.168.1.5\r\n\
+                   a=ice-ufrag:EsAw\r\n\
+                   a=ice-pwd:P2uYro0UCOQ4zxjKXaWCBui1\r\n\
+                   a=candidate:1 1 udp 2122260223 192.168.1.5 54321 typ host \
+                   generation 0\r\n\
+                   a=candidate:2 1 udp 1686052607 203.0.113.7 54321 typ srflx \
+                   raddr 192.168.1.5 rport 54321\r\n\
+                   a=candidate:3 1 udp 2122260223 4a1b.local 5000 typ host\r\n\
+                   a=mid:0\r\n";
+
+        assert_eq!(
+            redact_sdp(sdp),
+            "v=0\r\n\
+             o=- 4611731400430051336 2 IN IP4 127.0.0.1\r\n\
+             m=audio 9 UDP/TLS/RTP/SAVPF 111\r\n\
+             c=IN IP4 <redacted>\r\n\
+             a=ice-ufrag:<redacted>\r\n\
+             a=ice-pwd:<redacted>\r\n\
+             a=candidate:1 1 udp 2122260223 <redacted> 54321 typ host \
+             generation 0\r\n\
+             a=candidate:2 1 udp 1686052607 <redacted> 54321 typ srflx \
+             raddr <redacted> rport 54321\r\n\
+             a=candidate:3 1 udp 2122260223 <redacted> 5000 typ host\r\n\
+             a=mid:0\r\n",
+        );
+    }
+
+    #[test]
+    fn keeps_malformed_lines_and_endings() {
+        assert_eq!(redact_sdp(""), "");
+        assert_eq!(redact_sdp("a=candidate:1 1\n"), "a=candidate:1 1\n");
+        assert_eq!(redact_sdp("c=IN\na=mid:0"), "c=IN\na=mid:0");
+    }
+
+    #[test]
+    fn converts_description() {
+        let event = SdpEvent::from(Description::Remote(SdpType::Answer(
+            "a=ice-pwd:secret\r\n".into(),
+        )));
+
+        assert_eq!(event.direction, SdpDirection::Remote);
+        assert_eq!(event.kind, SdpKind::Answer);
+        assert_eq!(event.sdp, "a=ice-pwd:secret\r\n");
+        assert_eq!(event.redacted().sdp, "a=ice-pwd:<redacted>\r\n");
+    }
+}
@@ -0,0 +1,446 @@
+//! Timings of a [`PeerConnection`] setup phases, and progress of joining a
+//! `Room` built upon them.
+//!
+//! [`PeerConnection`]: super::PeerConnection
+
+use std::{
+    cell::RefCell, collections::HashMap, fmt, future::Future, rc::Rc,
+    time::Duration,
+};
+
+use derive_more::Display;
+use tracing::Instrument as _;
+
+use crate::platform;
+
+/// Phase of a [`PeerConnection`] setup.
+///
+/// [`PeerConnection`]: super::PeerConnection
+#[derive(Clone, Copy, Debug, Display, Eq, Hash, PartialEq)]
+pub enum SetupPhase {
+    /// Acquiring local media via [getUserMedia()][1] or
+    /// [getDisplayMedia()][2].
+    ///
+    /// [1]: https://tinyurl.com/w3-streams#dom-mediadevices-getusermedia
+    /// [2]: https://w3.org/TR/screen-capture#dom-mediadevices-getdisplaymedia
+    #[display(fmt = "gum")]
+    GetUserMedia,
+
+    /// Creating a local SDP offer.
+    ///
+    /// Happens only on the offering side of a negotiation.
+    #[display(fmt = "create_offer")]
+    CreateOffer,
+
+    /// Applying a local SDP offer or answer.
+    #[display(fmt = "set_local_description")]
+    SetLocalDescription,
+
+    /// Waiting for a media server to respond with a remote SDP answer on the
+    /// sent local SDP offer.
+    ///
+    /// Happens only on the offering side of a negotiation.
+    #[display(fmt = "server_rtt")]
+    ServerRtt,
+
+    /// Applying a remote SDP offer or answer.
+    #[display(fmt = "set_remote_description")]
+    SetRemoteDescription,
+
+    /// Waiting for the [RTCPeerConnection][1] to become connected after a
+    /// remote SDP is applied.
+    ///
+    /// [1]: https://w3.org/TR/webrtc#dom-rtcpeerconnection
+    #[display(fmt = "ice_connected")]
+    IceConnected,
+}
+
+impl SetupPhase {
+    /// All the [`SetupPhase`]s in the order they happen.
+    pub const ALL: [Self; 6] = [
+        Self::GetUserMedia,
+        Self::CreateOffer,
+        Self::SetLocalDescription,
+        Self::ServerRtt,
+        Self::SetRemoteDescription,
+        Self::IceConnected,
+    ];
+}
+
+/// Phase of joining a `Room`, in the order they happen.
+///
+/// The phases after the [`JoinPhase::Authorized`] one are reported by the
+/// first [`PeerConnection`] reaching them.
+///
+/// [`PeerConnection`]: super::PeerConnection
+#[derive(Clone, Copy, Debug, Display, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum JoinPhase {
+    /// Connecting to a media server and authorizing on it.
+    #[display(fmt = "connecting_signaling")]
+    ConnectingSignaling,
+
+    /// Authorized on a media server and waiting for it to create
+    /// [`PeerConnection`]s.
+    ///
+    /// [`PeerConnection`]: super::PeerConnection
+    #[display(fmt = "authorized")]
+    Authorized,
+
+    /// Acquiring local media (see [`SetupPhase::GetUserMedia`]).
+    #[display(fmt = "capturing_media")]
+    CapturingMedia,
+
+    /// Negotiating SDP (see [`SetupPhase::CreateOffer`],
+    /// [`SetupPhase::SetLocalDescription`], [`SetupPhase::ServerRtt`] and
+    /// [`SetupPhase::SetRemoteDescription`]).
+    #[display(fmt = "negotiating")]
+    Negotiating,
+
+    /// Waiting for ICE to connect (see [`SetupPhase::IceConnected`]).
+    #[display(fmt = "connecting_ice")]
+    ConnectingIce,
+
+    /// Media is flowing via a connected [`PeerConnection`].
+    ///
+    /// [`PeerConnection`]: super::PeerConnection
+    #[display(fmt = "joined")]
+    Joined,
+}
+
+impl From<SetupPhase> for JoinPhase {
+    fn from(phase: SetupPhase) -> Self {
+        match phase {
+            SetupPhase::GetUserMedia => Self::CapturingMedia,
+            SetupPhase::CreateOffer
+            | SetupPhase::SetLocalDescription
+            | SetupPhase::ServerRtt
+            | SetupPhase::SetRemoteDescription => Self::Negotiating,
+            SetupPhase::IceConnected => Self::ConnectingIce,
+        }
+    }
+}
+
+/// Reached [`JoinPhase`] along with the time it has been reached at.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct JoinProgressUpdate {
+    /// Reached [`JoinPhase`].
+    pub phase: JoinPhase,
+
+    /// Time the [`JoinPhase`] has been reached at, since the Unix epoch.
+    pub at: Duration,
+}
+
+/// Tracker of [`JoinPhase`]s reached by a `Room`.
+///
+/// Only ever advances, so the phases reached out of order (like the ones of
+/// a second [`PeerConnection`]) are ignored. Every reached [`JoinPhase`] is
+/// logged inside the `setup` [`tracing::Span`] of the [`SetupPhase`] it comes
+/// from.
+///
+/// [`PeerConnection`]: super::PeerConnection
+#[derive(Default)]
+pub struct JoinProgress {
+    /// [`JoinProgressUpdate`]s of all the reached [`JoinPhase`]s.
+    reached: RefCell<Vec<JoinProgressUpdate>>,
+
+    /// Callback to be invoked with every [`JoinProgressUpdate`].
+    on_progress: RefCell<Option<Box<dyn FnMut(JoinProgressUpdate)>>>,
+}
+
+impl fmt::Debug for JoinProgress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JoinProgress")
+            .field("reached", &self.reached)
+            .finish_non_exhaustive()
+    }
+}
+
+impl JoinProgress {
+    /// Returns the last reached [`JoinPhase`], if any.
+    #[must_use]
+    pub fn current(&self) -> Option<JoinPhase> {
+        self.reached.borrow().last().map(|u| u.phase)
+    }
+
+    /// Forgets all the reached [`JoinPhase`]s, so joining is tracked from
+    /// scratch.
+    pub fn reset(&self) {
+        self.reached.borrow_mut().clear();
+    }
+
+    /// Reaches the provided [`JoinPhase`], if it goes after the
+    /// [current][`JoinProgress::current()`] one, invoking the `on_progress`
+    /// callback.
+    pub fn advance(&self, phase: JoinPhase) {
+        if self.current().map_or(false, |current| current >= phase) {
+            return;
+        }
+        tracing::info!(%phase, "join phase reached");
+        let update = JoinProgressUpdate {
+            phase,
+            at: platform::now(),
+        };
+        self.reached.borrow_mut().push(update);
+
+        // Taken out to allow resubscribing from inside the callback.
+        let cb = self.on_progress.borrow_mut().take();
+        if let Some(mut cb) = cb {
+            cb(update);
+            if self.on_progress.borrow().is_none() {
+                drop(self.on_progress.replace(Some(cb)));
+            }
+        }
+    }
+
+    /// Sets the callback to be invoked with every [`JoinProgressUpdate`],
+    /// immediately invoking it with the already reached [`JoinPhase`]s.
+    pub fn on_progress<F>(&self, mut cb: F)
+    where
+        F: FnMut(JoinProgressUpdate) + 'static,
+    {
+        let reached = self.reached.borrow().clone();
+        for update in reached {
+            cb(update);
+        }
+        drop(self.on_progress.replace(Some(Box::new(cb))));
+    }
+}
+
+/// Durations of [`SetupPhase`]s of a connected [`PeerConnection`].
+///
+/// [`PeerConnection`]: super::PeerConnection
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SetupTimingsReport(HashMap<SetupPhase, Duration>);
+
+impl SetupTimingsReport {
+    /// Returns [`Duration`] of the provided [`SetupPhase`], if it happened.
+    #[must_use]
+    pub fn get(&self, phase: SetupPhase) -> Option<Duration> {
+        self.0.get(&phase).copied()
+    }
+
+    /// Serializes this [`SetupTimingsReport`] into a JSON object with
+    /// milliseconds per [`SetupPhase`] (`null` for the ones didn't happen).
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        let report: serde_json::Map<_, _> = SetupPhase::ALL
+            .into_iter()
+            .map(|phase| {
+                let ms = self.get(phase).map(|d| d.as_secs_f64() * 1000.0);
+                (phase.to_string(), ms.into())
+            })
+            .collect();
+        serde_json::Value::Object(report).to_string()
+    }
+}
+
+/// Recorder of [`SetupPhase`]s durations of a [`PeerConnection`].
+///
+/// Records only the first occurrence of every [`SetupPhase`], so
+/// renegotiations don't affect the initial setup timings. Every measured
+/// [`SetupPhase`] is wrapped into a `setup` [`tracing::Span`], and advances
+/// the [`JoinProgress`] (if any) once started.
+///
+/// [`PeerConnection`]: super::PeerConnection
+#[derive(Debug, Default)]
+pub struct SetupTimings {
+    /// Durations of the finished [`SetupPhase`]s.
+    finished: RefCell<HashMap<SetupPhase, Duration>>,
+
+    /// Start times and [`tracing::Span`]s of the started, but not finished
+    /// yet, [`SetupPhase`]s.
+    started: RefCell<HashMap<SetupPhase, (Duration, tracing::Span)>>,
+
+    /// [`JoinProgress`] of the `Room` the measured [`PeerConnection`] belongs
+    /// to.
+    ///
+    /// [`PeerConnection`]: super::PeerConnection
+    join_progress: RefCell<Option<Rc<JoinProgress>>>,
+}
+
+impl SetupTimings {
+    /// Sets the [`JoinProgress`] to be advanced by the measured
+    /// [`SetupPhase`]s.
+    pub fn set_join_progress(&self, progress: Rc<JoinProgress>) {
+        drop(self.join_progress.replace(Some(progress)));
+    }
+
+    /// Advances the [`JoinProgress`] (if any) to the provided [`JoinPhase`].
+    fn advance(&self, phase: JoinPhase) {
+        let progress = self.join_progress.borrow().clone();
+        if let Some(progress) = progress {
+            progress.advance(phase);
+        }
+    }
+
+    /// Indicates whether the provided [`SetupPhase`] should be measured.
+    fn is_measurable(&self, phase: SetupPhase) -> bool {
+        let finished = self.finished.borrow();
+        !finished.contains_key(&phase)
+            && !finished.contains_key(&SetupPhase::IceConnected)
+    }
+
+    /// Starts measuring the provided [`SetupPhase`], finished later with a
+    /// [`SetupTimings::finish()`].
+    pub fn start(&self, phase: SetupPhase) {
+        if !self.is_measurable(phase)
+            || self.started.borrow().contains_key(&phase)
+        {
+            return;
+        }
+        let span = tracing::info_span!("setup", %phase);
+        span.in_scope(|| self.advance(phase.into()));
+        drop(
+            self.started
+                .borrow_mut()
+                .insert(phase, (platform::now(), span)),
+        );
+    }
+
+    /// Finishes measuring the provided [`SetupPhase`] started with a
+    /// [`SetupTimings::start()`].
+    pub fn finish(&self, phase: SetupPhase) {
+        let started = self.started.borrow_mut().remove(&phase);
+        if let Some((started_at, span)) = started {
+            span.in_scope(|| {
+                self.record(phase, platform::now().saturating_sub(started_at));
+            });
+        }
+    }
+
+    /// Measures the provided [`SetupPhase`] as a duration of the provided
+    /// [`Future`] resolving.
+    pub async fn measure<F: Future>(
+        &self,
+        phase: SetupPhase,
+        fut: F,
+    ) -> F::Output {
+        if !self.is_measurable(phase) {
+            return fut.await;
+        }
+
+        let span = tracing::info_span!("setup", %phase);
+        span.in_scope(|| self.advance(phase.into()));
+        let started_at = platform::now();
+        let output = fut.instrument(span.clone()).await;
+        span.in_scope(|| {
+            self.record(phase, platform::now().saturating_sub(started_at));
+        });
+        output
+    }
+
+    /// Returns a [`SetupTimingsReport`] if the measured [`PeerConnection`]
+    /// has been connected already.
+    ///
+    /// [`PeerConnection`]: super::PeerConnection
+    #[must_use]
+    pub fn report(&self) -> Option<SetupTimingsReport> {
+        let finished = self.finished.borrow();
+        finished
+            .contains_key(&SetupPhase::IceConnected)
+            .then(|| SetupTimingsReport(finished.clone()))
+    }
+
+    /// Records the provided `duration` of the provided [`SetupPhase`].
+    ///
+    /// Logs the whole [`SetupTimingsReport`] as JSON once the
+    /// [`SetupPhase::IceConnected`] is recorded.
+    fn record(&self, phase: SetupPhase, duration: Duration) {
+        if !self.is_measurable(phase) {
+            return;
+        }
+        tracing::debug!(
+            duration_ms = duration.as_secs_f64() * 1000.0,
+            "setup phase finished",
+        );
+        _ = self.finished.borrow_mut().insert(phase, duration);
+
+        if phase == SetupPhase::IceConnected {
+            self.advance(JoinPhase::Joined);
+            self.started.borrow_mut().clear();
+            if let Some(report) = self.report() {
+                tracing::info!(
+                    target: "medea_jason::setup_timings",
+                    "{}",
+                    report.to_json(),
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod spec {
+    use std::{cell::RefCell, rc::Rc};
+
+    use super::{JoinPhase, JoinProgress, SetupPhase, SetupTimings};
+
+    /// Returns a [`JoinProgress`] along with all the [`JoinPhase`]s reported
+    /// to its callback.
+    fn recorded() -> (Rc<JoinProgress>, Rc<RefCell<Vec<JoinPhase>>>) {
+        let progress = Rc::new(JoinProgress::default());
+        let phases = Rc::new(RefCell::new(Vec::new()));
+        let recorded = Rc::clone(&phases);
+        progress.on_progress(move |u| recorded.borrow_mut().push(u.phase));
+        (progress, phases)
+    }
+
+    #[test]
+    fn reports_phases_of_peer_setup() {
+        let (progress, phases) = recorded();
+        progress.advance(JoinPhase::ConnectingSignaling);
+        progress.advance(JoinPhase::Authorized);
+
+        let timings = SetupTimings::default();
+        timings.set_join_progress(Rc::clone(&progress));
+        for phase in SetupPhase::ALL {
+            timings.start(phase);
+            timings.finish(phase);
+        }
+
+        assert_eq!(
+            *phases.borrow(),
+            [
+                JoinPhase::ConnectingSignaling,
+                JoinPhase::Authorized,
+                JoinPhase::CapturingMedia,
+                JoinPhase::Negotiating,
+                JoinPhase::ConnectingIce,
+                JoinPhase::Joined,
+            ],
+        );
+    }
+
+    #[test]
+    fn ignores_phases_reached_out_of_order() {
+        let (progress, phases) = recorded();
+        progress.advance(JoinPhase::Authorized);
+        progress.advance(JoinPhase::Negotiating);
+        progress.advance(JoinPhase::CapturingMedia);
+        progress.advance(JoinPhase::Negotiating);
+
+        assert_eq!(
+            *phases.borrow(),
+            [JoinPhase::Authorized, JoinPhase::Negotiating],
+        );
+        assert_eq!(progress.current(), Some(JoinPhase::Negotiating));
+    }
+
+    #[test]
+    fn replays_reached_phases() {
+        let progress = JoinProgress::default();
+        progress.advance(JoinPhase::ConnectingSignaling);
+        progress.reset();
+        progress.advance(JoinPhase::ConnectingSignaling);
+        progress.advance(JoinPhase::Authorized);
+
+        let phases = Rc::new(RefCell::new(Vec::new()));
+        let recorded = Rc::clone(&phases);
+        progress.on_progress(move |u| recorded.borrow_mut().push(u.phase));
+
+        assert_eq!(
+            *phases.borrow(),
+            [JoinPhase::ConnectingSignaling, JoinPhase::Authorized],
+        );
+    }
+}
@@ -0,0 +1,12 @@
+//! [`RtcStat`]s fixtures for unit tests of the stats based counters.
+
+use medea_client_api_proto::stats::RtcStat;
+
+/// Parses the provided JSON array of stats into [`RtcStat`]s.
+///
+/// # Panics
+///
+/// If the provided JSON doesn't represent [`RtcStat`]s.
+pub fn stats(json: serde_json::Value) -> Vec<RtcStat> {
+    serde_json::from_value(json).unwrap()
+}
@@ -0,0 +1,337 @@
+//! Accounting of bytes transferred by a [`PeerConnection`].
+//!
+//! [`PeerConnection`]: super::PeerConnection
+
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+};
+
+use medea_client_api_proto::stats::{
+    RtcInboundRtpStreamMediaType, RtcOutboundRtpStreamMediaType, RtcStat,
+    RtcStatsType, StatId,
+};
+
+use crate::media::MediaKind;
+
+use super::TrackDirection;
+
+/// Numbers of bytes transferred in RTP streams of a [`PeerConnection`].
+///
+/// [`PeerConnection`]: super::PeerConnection
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct TrafficTotals {
+    /// Number of bytes sent in all the outbound RTP streams.
+    pub bytes_sent: u64,
+
+    /// Number of bytes received in all the inbound RTP streams.
+    pub bytes_received: u64,
+
+    /// Number of bytes sent and received in all the audio RTP streams.
+    pub audio_bytes: u64,
+
+    /// Number of bytes sent and received in all the video RTP streams.
+    pub video_bytes: u64,
+
+    /// Number of the stats collection pauses the accounted traffic spans.
+    ///
+    /// The traffic transferred during a pause is accounted at once on the
+    /// resume, so a change of this number between two [`TrafficTotals`]
+    /// flags that their difference covers a pause and is unfit for bitrate
+    /// calculations.
+    pub collection_gaps: u32,
+}
+
+/// Accumulator of [`TrafficTotals`] from the periodically scraped
+/// [`RtcStat`]s of a [`PeerConnection`].
+///
+/// Processes nothing until [armed][`TrafficCounter::arm()`], so no cost is
+/// paid while there are no consumers of the [`TrafficTotals`]. Once armed,
+/// the whole traffic of the RTP streams is accounted, including the one
+/// transferred before arming.
+///
+/// Counters of every RTP stream are diffed against their previous values, so
+/// a counter reset (like it happens when RTP streams are recreated on ICE
+/// restart) never decreases the [`TrafficTotals`].
+///
+/// Only the counters of the RTP streams present in the last [`RtcStat`]s are
+/// retained, so the memory held doesn't grow with the recreated RTP streams
+/// during a long call.
+///
+/// Pausing the stats collection doesn't lose any traffic: the counters
+/// continue from their last seen values on resume, and the first accounting
+/// after a [gap][`TrafficCounter::mark_gap()`] is flagged in the
+/// [`TrafficTotals::collection_gaps`].
+///
+/// [`PeerConnection`]: super::PeerConnection
+#[derive(Debug, Default)]
+pub struct TrafficCounter {
+    /// Indicator whether this [`TrafficCounter`] processes [`RtcStat`]s.
+    is_armed: Cell<bool>,
+
+    /// Last seen byte counters of RTP streams by their [`StatId`]s.
+    last_bytes: RefCell<HashMap<StatId, u64>>,
+
+    /// Accumulated [`TrafficTotals`].
+    totals: Cell<TrafficTotals>,
+
+    /// Indicator whether the stats collection has been paused since the last
+    /// accounting.
+    has_gap: Cell<bool>,
+}
+
+impl TrafficCounter {
+    /// Makes this [`TrafficCounter`] process all the subsequent [`RtcStat`]s.
+    pub fn arm(&self) {
+        self.is_armed.set(true);
+    }
+
+    /// Indicates whether this [`TrafficCounter`] processes [`RtcStat`]s.
+    #[must_use]
+    pub fn is_armed(&self) -> bool {
+        self.is_armed.get()
+    }
+
+    /// Returns the accumulated [`TrafficTotals`].
+    #[must_use]
+    pub fn totals(&self) -> TrafficTotals {
+        self.totals.get()
+    }
+
+    /// Marks that the stats collection is paused, so the next accounting
+    /// covers a gap.
+    ///
+    /// No-op if nothing has been accounted yet.
+    pub fn mark_gap(&self) {
+        if !self.last_bytes.borrow().is_empty() {
+            self.has_gap.set(true);
+        }
+    }
+
+    /// Accumulates byte counters of the RTP streams from the provided
+    /// [`RtcStat`]s, if this [`TrafficCounter`] is armed.
+    pub fn update(&self, stats: &[RtcStat]) {
+        if !self.is_armed() {
+            return;
+        }
+        self.last_bytes
+            .borrow_mut()
+            .retain(|id, _| stats.iter().any(|s| s.id == *id));
+        for stat in stats {
+            if let Some((direction, kind, bytes)) = rtp_bytes(&stat.stats) {
+                self.add_sample(&stat.id, direction, kind, bytes);
+            }
+        }
+        if self.has_gap.take() {
+            let mut totals = self.totals.get();
+            totals.collection_gaps = totals.collection_gaps.saturating_add(1);
+            self.totals.set(totals);
+        }
+    }
+
+    /// Accounts the provided current byte counter of the RTP stream with the
+    /// provided [`StatId`].
+    fn add_sample(
+        &self,
+        id: &StatId,
+        direction: TrackDirection,
+        kind: MediaKind,
+        bytes: u64,
+    ) {
+        let prev = self
+            .last_bytes
+            .borrow_mut()
+            .insert(id.clone(), bytes)
+            .unwrap_or_default();
+        // A counter less than the previous one means it has been reset, so
+        // all its bytes are new ones.
+        let delta = if bytes < prev { bytes } else { bytes - prev };
+
+        let mut totals = self.totals.get();
+        match direction {
+            TrackDirection::Send => totals.bytes_sent += delta,
+            TrackDirection::Recv => totals.bytes_received += delta,
+        }
+        match kind {
+            MediaKind::Audio => totals.audio_bytes += delta,
+            MediaKind::Video => totals.video_bytes += delta,
+        }
+        self.totals.set(totals);
+    }
+}
+
+/// Returns [`TrackDirection`], [`MediaKind`] and the current byte counter of
+/// the RTP stream described by the provided [`RtcStatsType`], if it's the
+/// one.
+fn rtp_bytes(stats: &RtcStatsType) -> Option<(TrackDirection, MediaKind, u64)> {
+    if let RtcStatsType::InboundRtp(inbound) = stats {
+        let kind = match inbound.media_specific_stats {
+            RtcInboundRtpStreamMediaType::Audio { .. } => MediaKind::Audio,
+            RtcInboundRtpStreamMediaType::Video { .. } => MediaKind::Video,
+        };
+        Some((TrackDirection::Recv, kind, inbound.bytes_received))
+    } else if let RtcStatsType::OutboundRtp(outbound) = stats {
+        let kind = match outbound.media_type {
+            RtcOutboundRtpStreamMediaType::Audio { .. } => MediaKind::Audio,
+            RtcOutboundRtpStreamMediaType::Video { .. } => MediaKind::Video,
+        };
+        outbound
+            .bytes_sent
+            .map(|bytes| (TrackDirection::Send, kind, bytes))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod spec {
+    use medea_client_api_proto::stats::{RtcStat, StatId};
+
+    use crate::{
+        media::MediaKind as K,
+        peer::{stats_fixture::stats, TrackDirection as D},
+    };
+
+    use super::{TrafficCounter, TrafficTotals};
+
+    #[test]
+    fn accumulates_across_counter_reset() {
+        let counter = TrafficCounter::default();
+        let audio = StatId::from("audio");
+        let video = StatId::from("video");
+
+        counter.add_sample(&audio, D::Send, K::Audio, 100);
+        counter.add_sample(&video, D::Recv, K::Video, 500);
+        counter.add_sample(&audio, D::Send, K::Audio, 150);
+        counter.add_sample(&video, D::Recv, K::Video, 700);
+        assert_eq!(
+            counter.totals(),
+            TrafficTotals {
+                bytes_sent: 150,
+                bytes_received: 700,
+                audio_bytes: 150,
+                video_bytes: 700,
+                collection_gaps: 0,
+            },
+        );
+
+        // Counters are reset, like on ICE restart.
+        counter.add_sample(&audio, D::Send, K::Audio, 20);
+        counter.add_sample(&video, D::Recv, K::Video, 0);
+        counter.add_sample(&video, D::Recv, K::Video, 30);
+        assert_eq!(
+            counter.totals(),
+            TrafficTotals {
+                bytes_sent: 170,
+                bytes_received: 730,
+                audio_bytes: 170,
+                video_bytes: 730,
+                collection_gaps: 0,
+            },
+        );
+    }
+
+    #[test]
+    fn processes_rtp_stats_once_armed() {
+        let stats = stats(serde_json::json!([
+            {
+                "id": "out",
+                "timestamp": 1.0,
+                "type": "outbound-rtp",
+                "mediaType": "audio",
+                "bytesSent": 100
+            },
+            {
+                "id": "in",
+                "timestamp": 1.0,
+                "type": "inbound-rtp",
+                "mediaType": "video",
+                "bytesReceived": 300,
+                "packetsReceived": 3
+            }
+        ]));
+        let counter = TrafficCounter::default();
+
+        counter.update(&stats);
+        assert_eq!(counter.totals(), TrafficTotals::default());
+
+        counter.arm();
+        counter.update(&stats);
+        assert_eq!(
+            counter.totals(),
+            TrafficTotals {
+                bytes_sent: 100,
+                bytes_received: 300,
+                audio_bytes: 100,
+                video_bytes: 300,
+                collection_gaps: 0,
+            },
+        );
+    }
+
+    #[test]
+    fn retains_only_last_snapshot_in_long_call() {
+        let counter = TrafficCounter::default();
+        counter.arm();
+
+        for i in 0..10_000_u64 {
+            // RTP streams are recreated every 10 snapshots, like on ICE
+            // restarts or renegotiations.
+            let generation = i / 10;
+            let stats = stats(serde_json::json!([{
+                "id": format!("out{generation}"),
+                "timestamp": 1.0,
+                "type": "outbound-rtp",
+                "mediaType": "audio",
+                "bytesSent": (i % 10 + 1) * 10
+            }]));
+            counter.update(&stats);
+
+            assert!(counter.last_bytes.borrow().len() <= stats.len());
+        }
+        assert_eq!(counter.totals().bytes_sent, 100_000);
+    }
+
+    #[test]
+    fn continues_totals_after_pause() {
+        /// Returns `outbound-rtp` [`RtcStat`]s with the provided counter.
+        fn outbound(bytes: u64) -> Vec<RtcStat> {
+            stats(serde_json::json!([{
+                "id": "out",
+                "timestamp": 1.0,
+                "type": "outbound-rtp",
+                "mediaType": "video",
+                "bytesSent": bytes
+            }]))
+        }
+        let counter = TrafficCounter::default();
+        counter.arm();
+
+        // Nothing to flag before the first accounting.
+        counter.mark_gap();
+        counter.update(&outbound(100));
+        counter.update(&outbound(200));
+        assert_eq!(counter.totals().bytes_sent, 200);
+        assert_eq!(counter.totals().collection_gaps, 0);
+
+        // Stats collection is paused, so the totals are frozen.
+        counter.mark_gap();
+        assert_eq!(counter.totals().bytes_sent, 200);
+
+        // The traffic transferred during the pause is accounted on resume.
+        counter.update(&outbound(1_200));
+        assert_eq!(
+            counter.totals(),
+            TrafficTotals {
+                bytes_sent: 1_200,
+                video_bytes: 1_200,
+                collection_gaps: 1,
+                ..TrafficTotals::default()
+            },
+        );
+
+        counter.update(&outbound(1_300));
+        assert_eq!(counter.totals().bytes_sent, 1_300);
+        assert_eq!(counter.totals().collection_gaps, 1);
+    }
+}
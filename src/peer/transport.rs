@@ -0,0 +1,210 @@
+//! Diagnostic information about a transport of a [`PeerConnection`].
+//!
+//! [`PeerConnection`]: super::PeerConnection
+
+use std::cell::RefCell;
+
+use medea_client_api_proto::stats::{
+    DtlsTransportState, IceRole, KnownDtlsTransportState, NonExhaustive,
+    RtcStat, RtcStatsType,
+};
+use serde::Serialize;
+
+/// Diagnostic information about the ICE and DTLS transport of a
+/// [`PeerConnection`], taken from its [RTCTransportStats][1].
+///
+/// Every field is optional, since not all the browsers report them (for
+/// example, Firefox reports only a partial set).
+///
+/// [`PeerConnection`]: super::PeerConnection
+/// [1]: https://w3.org/TR/webrtc-stats#transportstats-dict%2A
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransportInfo {
+    /// Role of this side in the ICE negotiation (whether it's the controlling
+    /// agent or the controlled one).
+    pub ice_role: Option<IceRole>,
+
+    /// State of the DTLS transport.
+    pub dtls_state: Option<DtlsTransportState>,
+
+    /// Number of times the selected ICE candidate pair has changed.
+    pub selected_candidate_pair_changes: Option<u64>,
+
+    /// Name of the protection profile used for the SRTP transport.
+    pub srtp_cipher: Option<String>,
+
+    /// Name of the cipher suite used for the DTLS transport.
+    pub dtls_cipher: Option<String>,
+}
+
+impl TransportInfo {
+    /// Extracts a [`TransportInfo`] from the provided [`RtcStat`]s, if they
+    /// describe any transport.
+    ///
+    /// If there are several transports (when bundling isn't used), the one
+    /// with the connected DTLS is preferred.
+    #[must_use]
+    pub fn from_stats(stats: &[RtcStat]) -> Option<Self> {
+        stats
+            .iter()
+            .filter_map(|stat| {
+                let RtcStatsType::Transport(transport) = &stat.stats else {
+                    return None;
+                };
+                Some(Self {
+                    ice_role: transport.ice_role,
+                    dtls_state: transport.dtls_state.clone(),
+                    selected_candidate_pair_changes: transport
+                        .selected_candidate_pair_changes,
+                    srtp_cipher: transport.srtp_cipher.clone(),
+                    dtls_cipher: transport.dtls_cipher.clone(),
+                })
+            })
+            .max_by_key(Self::is_dtls_connected)
+    }
+
+    /// Indicates whether the DTLS transport has completed negotiation.
+    #[must_use]
+    pub fn is_dtls_connected(&self) -> bool {
+        self.dtls_state
+            == Some(NonExhaustive::Known(KnownDtlsTransportState::Connected))
+    }
+
+    /// Serializes this [`TransportInfo`] into a JSON object (with `null` for
+    /// the fields not reported by a browser).
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+}
+
+/// Tracker of the latest [`TransportInfo`] from the periodically scraped
+/// [`RtcStat`]s of a [`PeerConnection`].
+///
+/// Logs every changed [`TransportInfo`] as JSON.
+///
+/// [`PeerConnection`]: super::PeerConnection
+#[derive(Debug, Default)]
+pub struct TransportMonitor(RefCell<Option<TransportInfo>>);
+
+impl TransportMonitor {
+    /// Returns the latest [`TransportInfo`], if any has been reported.
+    #[must_use]
+    pub fn info(&self) -> Option<TransportInfo> {
+        self.0.borrow().clone()
+    }
+
+    /// Updates the latest [`TransportInfo`] from the provided [`RtcStat`]s, if
+    /// they describe any transport.
+    pub fn update(&self, stats: &[RtcStat]) {
+        let Some(info) = TransportInfo::from_stats(stats) else {
+            return;
+        };
+        let mut current = self.0.borrow_mut();
+        if current.as_ref() != Some(&info) {
+            tracing::info!(
+                target: "medea_jason::transport_info",
+                "{}",
+                info.to_json(),
+            );
+            *current = Some(info);
+        }
+    }
+}
+
+#[cfg(test)]
+mod spec {
+    use medea_client_api_proto::stats::{
+        IceRole, KnownDtlsTransportState, NonExhaustive,
+    };
+
+    use crate::peer::stats_fixture::stats;
+
+    use super::{TransportInfo, TransportMonitor};
+
+    #[test]
+    fn parses_full_transport_stats() {
+        let stats = stats(serde_json::json!([{
+            "id": "T01",
+            "timestamp": 1.0,
+            "type": "transport",
+            "bytesSent": 100,
+            "bytesReceived": 200,
+            "iceRole": "controlling",
+            "dtlsState": "connected",
+            "selectedCandidatePairChanges": 2,
+            "srtpCipher": "AES_CM_128_HMAC_SHA1_80",
+            "dtlsCipher": "TLS_ECDHE_ECDSA_WITH_AES_128_GCM_SHA256"
+        }]));
+
+        assert_eq!(
+            TransportInfo::from_stats(&stats),
+            Some(TransportInfo {
+                ice_role: Some(IceRole::Controlling),
+                dtls_state: Some(NonExhaustive::Known(
+                    KnownDtlsTransportState::Connected
+                )),
+                selected_candidate_pair_changes: Some(2),
+                srtp_cipher: Some("AES_CM_128_HMAC_SHA1_80".into()),
+                dtls_cipher: Some(
+                    "TLS_ECDHE_ECDSA_WITH_AES_128_GCM_SHA256".into()
+                ),
+            }),
+        );
+    }
+
+    #[test]
+    fn parses_partial_transport_stats() {
+        let stats = stats(serde_json::json!([
+            {
+                "id": "T01",
+                "timestamp": 1.0,
+                "type": "transport",
+                "dtlsState": "connecting"
+            },
+            {
+                "id": "T02",
+                "timestamp": 1.0,
+                "type": "transport",
+                "dtlsState": "connected",
+                "bytesSent": 100
+            }
+        ]));
+
+        let info = TransportInfo::from_stats(&stats).unwrap();
+
+        assert!(info.is_dtls_connected());
+        assert_eq!(info.ice_role, None);
+        assert_eq!(info.srtp_cipher, None);
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&info.to_json()).unwrap(),
+            serde_json::json!({
+                "iceRole": null,
+                "dtlsState": "connected",
+                "selectedCandidatePairChanges": null,
+                "srtpCipher": null,
+                "dtlsCipher": null
+            }),
+        );
+    }
+
+    #[test]
+    fn keeps_latest_info() {
+        let monitor = TransportMonitor::default();
+        assert_eq!(monitor.info(), None);
+
+        monitor.update(&stats(serde_json::json!([{
+            "id": "T01",
+            "timestamp": 1.0,
+            "type": "transport",
+            "iceRole": "controlled"
+        }])));
+        monitor.update(&stats(serde_json::json!([])));
+
+        assert_eq!(
+            monitor.info().and_then(|info| info.ice_role),
+            Some(IceRole::Controlled),
+        );
+    }
+}
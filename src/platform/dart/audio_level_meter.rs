@@ -0,0 +1,28 @@
+//! Meter of an audio level of a [`MediaStreamTrack`].
+
+use crate::platform::{Error, MediaStreamTrack};
+
+/// Meter of an audio level of a [`MediaStreamTrack`].
+///
+/// Cannot be built on Flutter platform.
+#[derive(Debug)]
+pub enum AudioLevelMeter {}
+
+impl AudioLevelMeter {
+    /// Returns [`None`], since Flutter platform doesn't allow metering audio
+    /// level of a [`MediaStreamTrack`].
+    #[allow(clippy::unnecessary_wraps)] // for platform code uniformity
+    #[must_use]
+    pub const fn new(_: &MediaStreamTrack) -> Option<Result<Self, Error>> {
+        None
+    }
+
+    /// Returns the current level of the metered [`MediaStreamTrack`] in
+    /// [dBFS].
+    ///
+    /// [dBFS]: https://en.wikipedia.org/wiki/DBFS
+    #[must_use]
+    pub const fn level_db(&self) -> f64 {
+        match *self {}
+    }
+}
@@ -350,6 +350,16 @@ impl From<DeviceVideoTrackConstraints> for MediaTrackConstraints {
                 );
             }
         }
+        if let Some(frame_rate) = from.frame_rate {
+            unsafe {
+                set_video_constrain_u32(
+                    frame_rate,
+                    VideoConstraintKind::FrameRate,
+                    &optional,
+                    &mandatory,
+                );
+            }
+        }
 
         Self {
             optional,
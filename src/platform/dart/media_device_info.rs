@@ -82,6 +82,14 @@ impl MediaDeviceInfo {
         unsafe { dart_string_into_rust(label) }
     }
 
+    /// Indicates whether the label of the device represented by this
+    /// [`MediaDeviceInfo`] is redacted, which happens until a media access
+    /// permission is granted.
+    #[must_use]
+    pub fn is_label_redacted(&self) -> bool {
+        self.label().is_empty()
+    }
+
     /// Returns a group identifier of the device represented by this
     /// [`MediaDeviceInfo`]
     ///
@@ -6,7 +6,10 @@ use medea_macro::dart_bridge;
 use tracerr::Traced;
 
 use crate::{
-    media::MediaSourceKind,
+    media::{
+        AudioCapabilities, DeviceCapabilities, MediaKind, MediaSourceKind,
+        VideoCapabilities,
+    },
     platform::{
         dart::utils::{
             dart_future::FutureFromDart, handle::DartHandle, list::DartList,
@@ -213,6 +216,29 @@ impl MediaDevices {
         Ok(tracks)
     }
 
+    /// Returns empty [`DeviceCapabilities`] of the provided [`MediaKind`],
+    /// since capabilities of media input devices are not exposed on this
+    /// platform.
+    ///
+    /// # Errors
+    ///
+    /// Never.
+    #[allow(clippy::unused_async, clippy::unused_self)]
+    pub async fn device_capabilities(
+        &self,
+        _: &MediaDeviceInfo,
+        kind: MediaKind,
+    ) -> Result<DeviceCapabilities, Traced<GetUserMediaError>> {
+        Ok(match kind {
+            MediaKind::Audio => {
+                DeviceCapabilities::Audio(AudioCapabilities::default())
+            }
+            MediaKind::Video => {
+                DeviceCapabilities::Video(VideoCapabilities::default())
+            }
+        })
+    }
+
     /// Switches the current output audio device to the device with the provided
     /// `device_id`.
     ///
@@ -286,4 +312,13 @@ impl MediaDevices {
             };
         }
     }
+
+    /// Does nothing, since media permissions are managed by an OS, and their
+    /// changes are not observable on this platform.
+    #[allow(clippy::needless_pass_by_value, clippy::unused_self)]
+    pub fn on_permission_granted<F>(&self, _: Option<F>)
+    where
+        F: 'static + FnMut(MediaKind),
+    {
+    }
 }
@@ -9,7 +9,8 @@ use medea_macro::dart_bridge;
 
 use crate::{
     media::{
-        track::MediaStreamTrackState, FacingMode, MediaKind, MediaSourceKind,
+        track::MediaStreamTrackState, ContentHint, DisplaySurface, FacingMode,
+        MediaKind, MediaSourceKind,
     },
     platform::{
         self,
@@ -216,6 +217,36 @@ impl MediaStreamTrack {
         Option::try_from(unsafe { width.unbox() }).unwrap()
     }
 
+    /// Returns [displaySurface][1] of this [`MediaStreamTrack`].
+    ///
+    /// Always [`None`], since Flutter doesn't report it at the moment.
+    ///
+    /// [1]: https://w3.org/TR/screen-capture#extensions-to-mediatracksettings
+    #[allow(clippy::unused_self)] // for platform code uniformity
+    #[must_use]
+    pub const fn display_surface(&self) -> Option<DisplaySurface> {
+        None
+    }
+
+    /// Returns [contentHint][1] of this [`MediaStreamTrack`].
+    ///
+    /// Always [`None`], since Flutter doesn't support it at the moment.
+    ///
+    /// [1]: https://w3.org/TR/mst-content-hint#dom-mediastreamtrack-contenthint
+    #[allow(clippy::unused_self)] // for platform code uniformity
+    #[must_use]
+    pub const fn content_hint(&self) -> Option<ContentHint> {
+        None
+    }
+
+    /// Sets [contentHint][1] of this [`MediaStreamTrack`].
+    ///
+    /// No-op, since Flutter doesn't support it at the moment.
+    ///
+    /// [1]: https://w3.org/TR/mst-content-hint#dom-mediastreamtrack-contenthint
+    #[allow(clippy::unused_self)] // for platform code uniformity
+    pub const fn set_content_hint(&self, _: ContentHint) {}
+
     /// Returns [enabled][1] field of this [`MediaStreamTrack`].
     ///
     /// [1]: https://w3.org/TR/mediacapture-streams#dom-mediastreamtrack-enabled
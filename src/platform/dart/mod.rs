@@ -12,6 +12,7 @@
     unused_variables
 )]
 
+pub mod audio_level_meter;
 pub mod constraints;
 pub mod error;
 pub mod executor;
@@ -26,14 +27,20 @@ pub mod rtc_stats;
 pub mod transceiver;
 pub mod transport;
 pub mod utils;
+pub mod video_transformer;
 
-use std::panic;
+use std::{
+    panic,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
+use futures::stream::{self, LocalBoxStream, StreamExt as _};
 use libc::c_void;
 
-use crate::platform::utils::dart_api;
+use crate::platform::{utils::dart_api, NetworkEvent, WebRtcSupport};
 
 pub use self::{
+    audio_level_meter::AudioLevelMeter,
     constraints::{DisplayMediaStreamConstraints, MediaStreamConstraints},
     error::Error,
     executor::spawn,
@@ -46,6 +53,7 @@ pub use self::{
     transceiver::Transceiver,
     transport::WebSocketRpcTransport,
     utils::{completer::delay_for, Function},
+    video_transformer::VideoTransformer,
 };
 
 /// Function to initialize `dart_api_dl` functions.
@@ -106,3 +114,55 @@ pub fn init_logger() {
         .with_level(log::LevelFilter::Debug)
         .init();
 }
+
+/// Returns the current time as a [`Duration`] since the UNIX epoch.
+#[must_use]
+pub fn now() -> Duration {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+}
+
+/// Returns [`WebRtcSupport`] of Flutter platform.
+///
+/// [WebRTC] is always available on Flutter platform, except
+/// [insertable streams][1].
+///
+/// [WebRTC]: https://w3.org/TR/webrtc
+/// [1]: https://w3.org/TR/webrtc-encoded-transform
+#[must_use]
+pub const fn webrtc_available() -> WebRtcSupport {
+    WebRtcSupport {
+        peer_connection: true,
+        get_user_media: true,
+        get_display_media: true,
+        insertable_streams: false,
+    }
+}
+
+/// Indicates whether a [`MediaStreamTrack`] can be processed by a
+/// [`VideoTransformer`].
+///
+/// Always `false` on Flutter platform.
+#[must_use]
+pub const fn supports_track_processing() -> bool {
+    false
+}
+
+/// Indicates whether the network is available.
+///
+/// Always `true` on Flutter platform, since its network availability is not
+/// observed.
+#[must_use]
+pub const fn is_online() -> bool {
+    true
+}
+
+/// Returns a [`LocalBoxStream`] of [`NetworkEvent`]s.
+///
+/// Never emits anything on Flutter platform, since its network availability
+/// and application visibility are not observed.
+#[must_use]
+pub fn on_network_events() -> LocalBoxStream<'static, NetworkEvent> {
+    stream::pending().boxed_local()
+}
@@ -2,7 +2,7 @@
 //!
 //! [1]: https://w3.org/TR/webrtc#dom-rtcpeerconnection
 
-use std::future::Future;
+use std::{future::Future, rc::Rc};
 
 use derive_more::Display;
 use medea_client_api_proto::{
@@ -23,8 +23,9 @@ use crate::{
                 peer_connection_state_from_int,
             },
         },
-        IceCandidate, RtcPeerConnectionError, RtcStats, SdpType,
-        TransceiverDirection,
+        IceCandidate, IceGatheringCounter, IceTransportPolicy,
+        OfferReceiveOptions, RtcConfiguration, RtcPeerConnectionError,
+        RtcStats, SdpType, TransceiverDirection,
     },
 };
 
@@ -139,10 +140,20 @@ mod peer_connection {
 #[derive(Clone, Debug)]
 pub struct RtcPeerConnection {
     handle: DartHandle,
+
+    /// [`IceGatheringCounter`] of the [ICE] candidates gathered by this
+    /// [`RtcPeerConnection`].
+    ///
+    /// [ICE]: https://webrtcglossary.com/ice
+    ice_gathering: Rc<IceGatheringCounter>,
 }
 
 impl RtcPeerConnection {
-    /// Instantiates a new [`RtcPeerConnection`].
+    /// Instantiates a new [`RtcPeerConnection`] with the provided
+    /// [`RtcConfiguration`].
+    ///
+    /// Only [`RtcConfiguration::ice_transport_policy`] is considered, the
+    /// other options are left up to the platform.
     ///
     /// # Errors
     ///
@@ -151,25 +162,38 @@ impl RtcPeerConnection {
     pub async fn new<I>(
         ice_servers: I,
         is_force_relayed: bool,
+        config: &RtcConfiguration,
     ) -> RtcPeerConnectionResult<Self>
     where
         I: IntoIterator<Item = IceServer>,
     {
         let ice_servers = RtcIceServers::from(ice_servers);
+        let is_relayed = config.ice_transport_policy(is_force_relayed)
+            == IceTransportPolicy::Relay;
         let fut = unsafe {
-            peer_connection::new_peer(
-                ice_servers.get_handle(),
-                is_force_relayed,
-            )
+            peer_connection::new_peer(ice_servers.get_handle(), is_relayed)
         };
         Ok(Self {
             handle: unsafe { FutureFromDart::execute(fut) }
                 .await
                 .map_err(RtcPeerConnectionError::PeerCreationError)
                 .map_err(tracerr::wrap!())?,
+            ice_gathering: Rc::default(),
         })
     }
 
+    /// Returns [`IceGatheringCounter`] of the [ICE] candidates gathered by
+    /// this [`RtcPeerConnection`].
+    ///
+    /// Only the candidates are accounted on Flutter platform, since changes of
+    /// its gathering state are not observed.
+    ///
+    /// [ICE]: https://webrtcglossary.com/ice
+    #[must_use]
+    pub fn ice_gathering(&self) -> Rc<IceGatheringCounter> {
+        Rc::clone(&self.ice_gathering)
+    }
+
     /// Returns [`RtcStats`] of this [`RtcPeerConnection`].
     #[allow(clippy::missing_errors_doc, clippy::unused_async)]
     pub async fn get_stats(&self) -> RtcPeerConnectionResult<RtcStats> {
@@ -213,11 +237,13 @@ impl RtcPeerConnection {
         F: 'static + FnMut(IceCandidate),
     {
         if let Some(mut h) = handler {
+            let ice_gathering = Rc::clone(&self.ice_gathering);
             unsafe {
                 peer_connection::on_ice_candidate(
                     self.handle.get(),
                     Callback::from_fn_mut(move |handle: DartHandle| {
                         let candidate = PlatformIceCandidate::from(handle);
+                        ice_gathering.add_candidate(&candidate.candidate());
                         h(IceCandidate {
                             candidate: candidate.candidate(),
                             sdp_m_line_index: candidate.sdp_m_line_index(),
@@ -388,7 +414,7 @@ impl RtcPeerConnection {
     ///
     /// # Errors
     ///
-    /// With [`RtcPeerConnectionError::SetLocalDescriptionFailed`] if
+    /// With [`RtcPeerConnectionError::RollbackFailed`] if
     /// [RtcPeerConnection.setLocalDescription()][1] fails.
     ///
     /// [1]: https://w3.org/TR/webrtc#dom-peerconnection-setlocaldescription
@@ -396,7 +422,7 @@ impl RtcPeerConnection {
         let fut = unsafe { peer_connection::rollback(self.handle.get()) };
         unsafe { FutureFromDart::execute(fut) }
             .await
-            .map_err(RtcPeerConnectionError::SetLocalDescriptionFailed)
+            .map_err(RtcPeerConnectionError::RollbackFailed)
             .map_err(tracerr::wrap!())
     }
 
@@ -405,13 +431,20 @@ impl RtcPeerConnection {
     /// Should be called after local tracks changes, which require
     /// (re)negotiation.
     ///
+    /// The provided [`OfferReceiveOptions`] are realized by not creating
+    /// receiving transceivers for the media kinds not being received, so
+    /// they're not passed to the [RtcPeerConnection.createOffer()][1].
+    ///
     /// # Errors
     ///
     /// With [`RtcPeerConnectionError::CreateOfferFailed`] if
     /// [RtcPeerConnection.createOffer()][1] fails.
     ///
     /// [1]: https://w3.org/TR/webrtc#dom-rtcpeerconnection-createoffer
-    pub async fn create_offer(&self) -> RtcPeerConnectionResult<String> {
+    pub async fn create_offer(
+        &self,
+        _: OfferReceiveOptions,
+    ) -> RtcPeerConnectionResult<String> {
         let fut = unsafe { peer_connection::create_offer(self.handle.get()) };
         unsafe { FutureFromDart::execute(fut) }
             .await
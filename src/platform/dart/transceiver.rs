@@ -2,7 +2,7 @@
 //!
 //! [RTCRtpTransceiver]: https://w3.org/TR/webrtc#dom-rtcrtptransceiver
 
-use std::{future::Future, rc::Rc};
+use std::{future::Future, rc::Rc, time::Duration};
 
 use futures::future::LocalBoxFuture;
 use medea_macro::dart_bridge;
@@ -128,6 +128,62 @@ impl Transceiver {
         unsafe { FutureFromDart::execute::<()>(fut) }.await
     }
 
+    /// Forces the sender of this [`Transceiver`] to encode a new keyframe.
+    ///
+    /// Returns [`None`], since Flutter platform doesn't allow this.
+    #[allow(clippy::unused_async)] // for platform code uniformity
+    #[allow(clippy::unused_self)] // for platform code uniformity
+    pub async fn request_keyframe(
+        &self,
+    ) -> Option<Result<(), platform::Error>> {
+        None
+    }
+
+    /// Sets maximum bitrate and activity of the encodings of the sender of
+    /// this [`Transceiver`].
+    ///
+    /// Returns [`None`], since Flutter platform doesn't allow this.
+    #[allow(clippy::unused_async)] // for platform code uniformity
+    #[allow(clippy::unused_self)] // for platform code uniformity
+    pub async fn set_send_encodings(
+        &self,
+        _: Option<u32>,
+        _: bool,
+    ) -> Option<Result<(), platform::Error>> {
+        None
+    }
+
+    /// Sets jitter buffer target of the receiver of this [`Transceiver`].
+    ///
+    /// Returns [`None`], since Flutter platform doesn't allow this.
+    #[allow(clippy::unused_self)] // for platform code uniformity
+    pub fn set_jitter_buffer_target(
+        &self,
+        _: Duration,
+    ) -> Option<Result<(), platform::Error>> {
+        None
+    }
+
+    /// Returns the effective jitter buffer target of the receiver of this
+    /// [`Transceiver`].
+    ///
+    /// Returns [`None`], since Flutter platform doesn't allow setting it.
+    #[allow(clippy::unused_self)] // for platform code uniformity
+    #[must_use]
+    pub fn jitter_buffer_target(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Indicates whether the platform allows setting a jitter buffer target
+    /// of the receiver of this [`Transceiver`].
+    ///
+    /// Always returns `false`, since Flutter platform doesn't allow this.
+    #[allow(clippy::unused_self)] // for platform code uniformity
+    #[must_use]
+    pub fn is_jitter_buffer_target_supported(&self) -> bool {
+        false
+    }
+
     /// Returns [`mid`] of this [`Transceiver`].
     ///
     /// [`mid`]: https://w3.org/TR/webrtc#dom-rtptransceiver-mid
@@ -146,6 +202,20 @@ impl Transceiver {
         unsafe { transceiver::is_stopped(self.0.get()) }
     }
 
+    /// Returns the [`currentDirection`][1] negotiated for this [`Transceiver`].
+    ///
+    /// Flutter WebRTC doesn't expose the negotiated direction, so the
+    /// preferred [`TransceiverDirection`] is returned instead, unless this
+    /// [`Transceiver`] is stopped.
+    ///
+    /// [1]: https://w3.org/TR/webrtc#dom-rtptransceiver-currentdirection
+    pub async fn current_direction(&self) -> Option<TransceiverDirection> {
+        if self.is_stopped() {
+            return Some(TransceiverDirection::STOPPED);
+        }
+        Some(self.direction().await)
+    }
+
     /// Returns current [`TransceiverDirection`] of this [`Transceiver`].
     fn direction(&self) -> impl Future<Output = TransceiverDirection> {
         let handle = self.0.get();
@@ -0,0 +1,32 @@
+//! Transformer of a video [`MediaStreamTrack`].
+
+use crate::{
+    media::{MediaSourceKind, VideoTransform},
+    platform::{Error, MediaStreamTrack},
+};
+
+/// Transformer of a video [`MediaStreamTrack`].
+///
+/// Cannot be built on Flutter platform.
+#[derive(Debug)]
+pub enum VideoTransformer {}
+
+impl VideoTransformer {
+    /// Returns [`None`], since Flutter platform doesn't allow processing a
+    /// [`MediaStreamTrack`].
+    #[allow(clippy::unnecessary_wraps)] // for platform code uniformity
+    #[must_use]
+    pub const fn new(
+        _: &MediaStreamTrack,
+        _: MediaSourceKind,
+        _: VideoTransform,
+    ) -> Option<Result<Self, Error>> {
+        None
+    }
+
+    /// Returns the transformed [`MediaStreamTrack`].
+    #[must_use]
+    pub const fn output(&self) -> &MediaStreamTrack {
+        match *self {}
+    }
+}
@@ -0,0 +1,411 @@
+//! Parsing and filtering of [ICE candidates][1].
+//!
+//! [1]: https://w3.org/TR/webrtc#rtcicecandidate-interface
+
+use std::{fmt, net::IpAddr, rc::Rc};
+
+/// Type of an [ICE candidate][1].
+///
+/// [1]: https://w3.org/TR/webrtc#dom-rtcicecandidatetype
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum IceCandidateType {
+    /// Candidate with an actual local address of a host.
+    Host,
+
+    /// Candidate with an address assigned by a NAT, discovered via a STUN
+    /// server.
+    ServerReflexive,
+
+    /// Candidate with an address assigned by a NAT, discovered via a remote
+    /// peer.
+    PeerReflexive,
+
+    /// Candidate with an address allocated on a TURN server.
+    Relay,
+}
+
+impl IceCandidateType {
+    /// Parses an [`IceCandidateType`] from the provided `cand-type` of a
+    /// [candidate-attribute][1].
+    ///
+    /// [1]: https://tools.ietf.org/html/rfc5245#section-15.1
+    fn parse(kind: &str) -> Option<Self> {
+        Some(match kind {
+            "host" => Self::Host,
+            "srflx" => Self::ServerReflexive,
+            "prflx" => Self::PeerReflexive,
+            "relay" => Self::Relay,
+            _ => return None,
+        })
+    }
+}
+
+/// Attributes of an [ICE candidate][1] parsed from its
+/// [candidate-attribute][2].
+///
+/// [1]: https://w3.org/TR/webrtc#rtcicecandidate-interface
+/// [2]: https://tools.ietf.org/html/rfc5245#section-15.1
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IceCandidateAttributes {
+    /// Foundation of the candidate.
+    pub foundation: String,
+
+    /// ID of the component (RTP or RTCP) the candidate is gathered for.
+    pub component: u16,
+
+    /// Lowercased transport protocol of the candidate.
+    pub protocol: String,
+
+    /// Priority of the candidate.
+    pub priority: u32,
+
+    /// IP address or [mDNS] hostname of the candidate.
+    ///
+    /// [mDNS]: https://tools.ietf.org/html/rfc6762
+    pub address: String,
+
+    /// Port of the candidate.
+    pub port: u16,
+
+    /// [`IceCandidateType`] of the candidate.
+    pub kind: IceCandidateType,
+
+    /// Address the candidate is derived from, if any.
+    pub related_address: Option<String>,
+
+    /// Port the candidate is derived from, if any.
+    pub related_port: Option<u16>,
+}
+
+impl IceCandidateAttributes {
+    /// Parses [`IceCandidateAttributes`] from the provided
+    /// [candidate-attribute][1], with or without its `a=` and `candidate:`
+    /// prefixes.
+    ///
+    /// Returns [`None`] if the provided `candidate` is malformed.
+    ///
+    /// [1]: https://tools.ietf.org/html/rfc5245#section-15.1
+    #[must_use]
+    pub fn parse(candidate: &str) -> Option<Self> {
+        let candidate = candidate.trim();
+        let candidate = candidate.strip_prefix("a=").unwrap_or(candidate);
+        let candidate =
+            candidate.strip_prefix("candidate:").unwrap_or(candidate);
+
+        let mut parts = candidate.split_whitespace();
+        let foundation = parts.next()?.to_owned();
+        let component = parts.next()?.parse().ok()?;
+        let protocol = parts.next()?.to_lowercase();
+        let priority = parts.next()?.parse().ok()?;
+        let address = parts.next()?.to_owned();
+        let port = parts.next()?.parse().ok()?;
+        if parts.next()? != "typ" {
+            return None;
+        }
+        let kind = IceCandidateType::parse(parts.next()?)?;
+
+        let mut related_address = None;
+        let mut related_port = None;
+        while let (Some(name), Some(value)) = (parts.next(), parts.next()) {
+            match name {
+                "raddr" => related_address = Some(value.to_owned()),
+                "rport" => related_port = Some(value.parse().ok()?),
+                _ => {}
+            }
+        }
+
+        Some(Self {
+            foundation,
+            component,
+            protocol,
+            priority,
+            address,
+            port,
+            kind,
+            related_address,
+            related_port,
+        })
+    }
+
+    /// Indicates whether the address of this candidate is an [mDNS] hostname,
+    /// concealing the actual local IP address.
+    ///
+    /// [mDNS]: https://tools.ietf.org/html/rfc6762
+    #[must_use]
+    pub fn is_mdns(&self) -> bool {
+        self.address.to_lowercase().ends_with(".local")
+    }
+
+    /// Indicates whether the address of this candidate is an IPv4
+    /// (`169.254.0.0/16`) or an IPv6 (`fe80::/10`) link-local one.
+    #[must_use]
+    pub fn is_link_local(&self) -> bool {
+        match self.ip() {
+            Some(IpAddr::V4(ip)) => ip.is_link_local(),
+            Some(IpAddr::V6(ip)) => ip.segments()[0] & 0xffc0 == 0xfe80,
+            None => false,
+        }
+    }
+
+    /// Indicates whether the address of this candidate is an IPv6 one.
+    #[must_use]
+    pub fn is_ipv6(&self) -> bool {
+        matches!(self.ip(), Some(IpAddr::V6(_)))
+    }
+
+    /// Returns the [`IpAddr`] of this candidate, if its address is not an
+    /// [mDNS] hostname.
+    ///
+    /// [mDNS]: https://tools.ietf.org/html/rfc6762
+    fn ip(&self) -> Option<IpAddr> {
+        self.address
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .parse()
+            .ok()
+    }
+}
+
+/// Filter of [ICE candidates][1] exchanged with a remote peer.
+///
+/// Malformed candidates (including the empty end-of-candidates indication)
+/// are never filtered out, since their attributes are unknown.
+///
+/// [1]: https://w3.org/TR/webrtc#rtcicecandidate-interface
+#[derive(Clone, Default)]
+pub enum IceCandidateFilter {
+    /// All candidates are allowed.
+    #[default]
+    All,
+
+    /// Candidates with [mDNS] hostnames are filtered out.
+    ///
+    /// [mDNS]: https://tools.ietf.org/html/rfc6762
+    NoMdns,
+
+    /// Candidates with link-local addresses are filtered out.
+    NoLinkLocal,
+
+    /// Candidates with IPv6 addresses are filtered out.
+    NoIpv6,
+
+    /// Only [`IceCandidateType::Relay`] candidates are allowed.
+    RelayOnly,
+
+    /// Only candidates satisfying the provided predicate are allowed.
+    Custom(Rc<dyn Fn(&IceCandidateAttributes) -> bool>),
+}
+
+impl fmt::Debug for IceCandidateFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::All => write!(f, "All"),
+            Self::NoMdns => write!(f, "NoMdns"),
+            Self::NoLinkLocal => write!(f, "NoLinkLocal"),
+            Self::NoIpv6 => write!(f, "NoIpv6"),
+            Self::RelayOnly => write!(f, "RelayOnly"),
+            Self::Custom(_) => write!(f, "Custom"),
+        }
+    }
+}
+
+impl IceCandidateFilter {
+    /// Indicates whether the provided [candidate-attribute][1] passes this
+    /// [`IceCandidateFilter`].
+    ///
+    /// [1]: https://tools.ietf.org/html/rfc5245#section-15.1
+    #[must_use]
+    pub fn allows(&self, candidate: &str) -> bool {
+        if matches!(self, Self::All) {
+            return true;
+        }
+        IceCandidateAttributes::parse(candidate)
+            .map_or(true, |c| self.allows_parsed(&c))
+    }
+
+    /// Indicates whether a candidate with the provided
+    /// [`IceCandidateAttributes`] passes this [`IceCandidateFilter`].
+    #[must_use]
+    pub fn allows_parsed(&self, candidate: &IceCandidateAttributes) -> bool {
+        match self {
+            Self::All => true,
+            Self::NoMdns => !candidate.is_mdns(),
+            Self::NoLinkLocal => !candidate.is_link_local(),
+            Self::NoIpv6 => !candidate.is_ipv6(),
+            Self::RelayOnly => candidate.kind == IceCandidateType::Relay,
+            Self::Custom(predicate) => predicate(candidate),
+        }
+    }
+}
+
+#[cfg(test)]
+mod spec {
+    use std::rc::Rc;
+
+    use super::{IceCandidateAttributes, IceCandidateFilter, IceCandidateType};
+
+    /// Host candidate with an IPv4 address.
+    const HOST_V4: &str =
+        "candidate:1 1 UDP 2122260223 192.168.1.2 54321 typ host";
+
+    /// Host candidate with an mDNS hostname.
+    const HOST_MDNS: &str = "candidate:2 1 udp 2122260223 \
+                             5f0c3c1e-7a1b-4c2d-9e3f-0a1b2c3d4e5f.local 54321 \
+                             typ host generation 0";
+
+    /// Host candidate with a link-local IPv4 address.
+    const HOST_LINK_LOCAL_V4: &str =
+        "candidate:3 1 udp 2122260223 169.254.10.20 54321 typ host";
+
+    /// Host candidate with a link-local IPv6 address.
+    const HOST_LINK_LOCAL_V6: &str =
+        "candidate:4 1 udp 2122262783 fe80::1ff:fe23:4567:890a 54321 typ host";
+
+    /// Server reflexive candidate with a global IPv6 address.
+    const SRFLX_V6: &str = "candidate:5 1 udp 1686052607 2001:db8::1 54321 \
+                            typ srflx raddr :: rport 0";
+
+    /// Relay candidate.
+    const RELAY: &str = "a=candidate:6 2 tcp 41885439 10.0.0.1 3478 typ \
+                         relay raddr 1.2.3.4 rport 54321 tcptype passive";
+
+    #[test]
+    fn parses_candidates() {
+        assert_eq!(
+            IceCandidateAttributes::parse(HOST_V4).unwrap(),
+            IceCandidateAttributes {
+                foundation: "1".into(),
+                component: 1,
+                protocol: "udp".into(),
+                priority: 2_122_260_223,
+                address: "192.168.1.2".into(),
+                port: 54321,
+                kind: IceCandidateType::Host,
+                related_address: None,
+                related_port: None,
+            },
+        );
+        assert_eq!(
+            IceCandidateAttributes::parse(RELAY).unwrap(),
+            IceCandidateAttributes {
+                foundation: "6".into(),
+                component: 2,
+                protocol: "tcp".into(),
+                priority: 41_885_439,
+                address: "10.0.0.1".into(),
+                port: 3478,
+                kind: IceCandidateType::Relay,
+                related_address: Some("1.2.3.4".into()),
+                related_port: Some(54321),
+            },
+        );
+
+        let srflx = IceCandidateAttributes::parse(SRFLX_V6).unwrap();
+        assert_eq!(srflx.kind, IceCandidateType::ServerReflexive);
+        assert_eq!(srflx.address, "2001:db8::1");
+        assert_eq!(srflx.related_address.as_deref(), Some("::"));
+        assert_eq!(srflx.related_port, Some(0));
+
+        let without_prefix = "1 1 udp 1 10.0.0.1 3478 typ prflx";
+        assert_eq!(
+            IceCandidateAttributes::parse(without_prefix).unwrap().kind,
+            IceCandidateType::PeerReflexive,
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_candidates() {
+        for candidate in [
+            "",
+            "candidate:1 1 udp",
+            "candidate:1 one udp 1 10.0.0.1 3478 typ host",
+            "candidate:1 1 udp 1 10.0.0.1 70000 typ host",
+            "candidate:1 1 udp 1 10.0.0.1 3478 type host",
+            "candidate:1 1 udp 1 10.0.0.1 3478 typ unknown",
+            "candidate:1 1 udp 1 10.0.0.1 3478 typ srflx rport x",
+        ] {
+            assert!(
+                IceCandidateAttributes::parse(candidate).is_none(),
+                "`{candidate}` should be rejected",
+            );
+        }
+    }
+
+    #[test]
+    fn classifies_addresses() {
+        let parse = |c| IceCandidateAttributes::parse(c).unwrap();
+
+        assert!(parse(HOST_MDNS).is_mdns());
+        assert!(!parse(HOST_V4).is_mdns());
+
+        assert!(parse(HOST_LINK_LOCAL_V4).is_link_local());
+        assert!(parse(HOST_LINK_LOCAL_V6).is_link_local());
+        assert!(!parse(HOST_V4).is_link_local());
+        assert!(!parse(SRFLX_V6).is_link_local());
+        assert!(!parse(HOST_MDNS).is_link_local());
+
+        assert!(parse(SRFLX_V6).is_ipv6());
+        assert!(parse(HOST_LINK_LOCAL_V6).is_ipv6());
+        assert!(!parse(HOST_V4).is_ipv6());
+        assert!(!parse(HOST_MDNS).is_ipv6());
+    }
+
+    #[test]
+    fn filters_candidates() {
+        let all = [
+            HOST_V4,
+            HOST_MDNS,
+            HOST_LINK_LOCAL_V4,
+            HOST_LINK_LOCAL_V6,
+            SRFLX_V6,
+            RELAY,
+        ];
+        let allowed = |filter: &IceCandidateFilter| -> Vec<_> {
+            all.into_iter().filter(|c| filter.allows(c)).collect()
+        };
+
+        assert_eq!(allowed(&IceCandidateFilter::All), all);
+        assert_eq!(
+            allowed(&IceCandidateFilter::NoMdns),
+            [
+                HOST_V4,
+                HOST_LINK_LOCAL_V4,
+                HOST_LINK_LOCAL_V6,
+                SRFLX_V6,
+                RELAY
+            ],
+        );
+        assert_eq!(
+            allowed(&IceCandidateFilter::NoLinkLocal),
+            [HOST_V4, HOST_MDNS, SRFLX_V6, RELAY],
+        );
+        assert_eq!(
+            allowed(&IceCandidateFilter::NoIpv6),
+            [HOST_V4, HOST_MDNS, HOST_LINK_LOCAL_V4, RELAY],
+        );
+        assert_eq!(allowed(&IceCandidateFilter::RelayOnly), [RELAY]);
+        assert_eq!(
+            allowed(&IceCandidateFilter::Custom(Rc::new(|c| {
+                c.protocol == "udp" && c.port == 54321 && c.component == 1
+            }))),
+            [
+                HOST_V4,
+                HOST_MDNS,
+                HOST_LINK_LOCAL_V4,
+                HOST_LINK_LOCAL_V6,
+                SRFLX_V6,
+            ],
+        );
+    }
+
+    #[test]
+    fn passes_malformed_candidates() {
+        for filter in [
+            IceCandidateFilter::RelayOnly,
+            IceCandidateFilter::Custom(Rc::new(|_| false)),
+        ] {
+            assert!(filter.allows(""));
+            assert!(filter.allows("candidate:1 1 udp"));
+        }
+    }
+}
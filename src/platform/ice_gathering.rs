@@ -0,0 +1,222 @@
+//! Accounting of [ICE] candidates gathered by an [`RtcPeerConnection`].
+//!
+//! [`RtcPeerConnection`]: crate::platform::RtcPeerConnection
+//! [ICE]: https://webrtcglossary.com/ice
+
+use std::{cell::Cell, time::Duration};
+
+use crate::platform::{self, IceCandidateAttributes, IceCandidateType};
+
+/// Info about a single generation of [ICE] candidates gathering.
+///
+/// [ICE]: https://webrtcglossary.com/ice
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct IceGatheringInfo {
+    /// Number of the gathered [`IceCandidateType::Host`] candidates.
+    pub host_candidates: u32,
+
+    /// Number of the gathered [`IceCandidateType::ServerReflexive`]
+    /// candidates.
+    pub srflx_candidates: u32,
+
+    /// Number of the gathered [`IceCandidateType::PeerReflexive`] candidates.
+    pub prflx_candidates: u32,
+
+    /// Number of the gathered [`IceCandidateType::Relay`] candidates.
+    pub relay_candidates: u32,
+
+    /// Time (since the UNIX epoch) the gathering has started at, if it has.
+    pub started_at: Option<Duration>,
+
+    /// Time (since the UNIX epoch) the gathering has completed at, if it has.
+    pub completed_at: Option<Duration>,
+}
+
+impl IceGatheringInfo {
+    /// Returns duration of the gathering, if it has started and completed.
+    #[must_use]
+    pub fn duration(&self) -> Option<Duration> {
+        Some(self.completed_at?.saturating_sub(self.started_at?))
+    }
+
+    /// Indicates whether nothing has been gathered yet.
+    const fn is_empty(&self) -> bool {
+        self.started_at.is_none()
+            && self.host_candidates == 0
+            && self.srflx_candidates == 0
+            && self.prflx_candidates == 0
+            && self.relay_candidates == 0
+    }
+}
+
+/// Accumulator of [`IceGatheringInfo`]s of an [`RtcPeerConnection`].
+///
+/// Every started gathering (an ICE restart, in particular) begins a new
+/// [`IceGatheringInfo`] generation, keeping the previous one accessible.
+///
+/// [`RtcPeerConnection`]: crate::platform::RtcPeerConnection
+#[derive(Debug, Default)]
+pub struct IceGatheringCounter {
+    /// [`IceGatheringInfo`] of the current gathering generation.
+    current: Cell<IceGatheringInfo>,
+
+    /// [`IceGatheringInfo`] of the previous gathering generation, if any.
+    previous: Cell<Option<IceGatheringInfo>>,
+}
+
+impl IceGatheringCounter {
+    /// Starts a new gathering generation.
+    pub fn start(&self) {
+        self.start_at(platform::now());
+    }
+
+    /// Completes the current gathering generation.
+    pub fn complete(&self) {
+        self.complete_at(platform::now());
+    }
+
+    /// Accounts the provided gathered [candidate-attribute][1].
+    ///
+    /// [1]: https://tools.ietf.org/html/rfc5245#section-15.1
+    pub fn add_candidate(&self, candidate: &str) {
+        let Some(attrs) = IceCandidateAttributes::parse(candidate) else {
+            return;
+        };
+        let mut info = self.current.get();
+        let count = match attrs.kind {
+            IceCandidateType::Host => &mut info.host_candidates,
+            IceCandidateType::ServerReflexive => &mut info.srflx_candidates,
+            IceCandidateType::PeerReflexive => &mut info.prflx_candidates,
+            IceCandidateType::Relay => &mut info.relay_candidates,
+        };
+        *count = count.saturating_add(1);
+        self.current.set(info);
+    }
+
+    /// Returns [`IceGatheringInfo`] of the current gathering generation.
+    #[must_use]
+    pub fn info(&self) -> IceGatheringInfo {
+        self.current.get()
+    }
+
+    /// Returns [`IceGatheringInfo`] of the previous gathering generation, if
+    /// any.
+    #[must_use]
+    pub fn previous_info(&self) -> Option<IceGatheringInfo> {
+        self.previous.get()
+    }
+
+    /// Starts a new gathering generation at the provided time.
+    fn start_at(&self, now: Duration) {
+        let current = self.current.take();
+        if !current.is_empty() {
+            self.previous.set(Some(current));
+        }
+        self.current.set(IceGatheringInfo {
+            started_at: Some(now),
+            ..IceGatheringInfo::default()
+        });
+    }
+
+    /// Completes the current gathering generation at the provided time, unless
+    /// it's completed already.
+    fn complete_at(&self, now: Duration) {
+        let mut info = self.current.get();
+        if info.completed_at.is_none() {
+            info.completed_at = Some(now);
+            self.current.set(info);
+        }
+    }
+}
+
+#[cfg(test)]
+mod spec {
+    use std::time::Duration;
+
+    use super::{IceGatheringCounter, IceGatheringInfo};
+
+    /// Returns a [`Duration`] of the provided number of milliseconds.
+    const fn ms(ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+
+    #[test]
+    fn counts_candidates_by_type() {
+        let counter = IceGatheringCounter::default();
+        counter.start_at(ms(100));
+        counter.add_candidate(
+            "candidate:1 1 udp 2122260223 192.168.1.2 54321 typ host",
+        );
+        counter.add_candidate(
+            "candidate:2 1 udp 1686052607 1.2.3.4 54321 typ srflx \
+             raddr 192.168.1.2 rport 54321",
+        );
+        counter.add_candidate(
+            "candidate:3 1 udp 41885439 5.6.7.8 3478 typ relay \
+             raddr 1.2.3.4 rport 54321",
+        );
+        counter.add_candidate(
+            "candidate:4 1 udp 41885439 5.6.7.9 3478 typ relay \
+             raddr 1.2.3.4 rport 54322",
+        );
+        counter.add_candidate("malformed");
+        counter.complete_at(ms(350));
+
+        let info = counter.info();
+
+        assert_eq!(
+            info,
+            IceGatheringInfo {
+                host_candidates: 1,
+                srflx_candidates: 1,
+                prflx_candidates: 0,
+                relay_candidates: 2,
+                started_at: Some(ms(100)),
+                completed_at: Some(ms(350)),
+            },
+        );
+        assert_eq!(info.duration(), Some(ms(250)));
+        assert_eq!(counter.previous_info(), None);
+    }
+
+    #[test]
+    fn keeps_first_completion() {
+        let counter = IceGatheringCounter::default();
+        counter.start_at(ms(100));
+        counter.complete_at(ms(200));
+        counter.complete_at(ms(300));
+
+        assert_eq!(counter.info().duration(), Some(ms(100)));
+    }
+
+    #[test]
+    fn restart_starts_new_generation() {
+        let counter = IceGatheringCounter::default();
+        counter.start_at(ms(100));
+        counter.add_candidate(
+            "candidate:1 1 udp 2122260223 192.168.1.2 54321 typ host",
+        );
+        counter.complete_at(ms(200));
+        let first = counter.info();
+
+        counter.start_at(ms(1000));
+
+        assert_eq!(counter.previous_info(), Some(first));
+        assert_eq!(
+            counter.info(),
+            IceGatheringInfo {
+                started_at: Some(ms(1000)),
+                ..IceGatheringInfo::default()
+            },
+        );
+        assert_eq!(counter.info().duration(), None);
+    }
+
+    #[test]
+    fn doesnt_keep_empty_generation() {
+        let counter = IceGatheringCounter::default();
+        counter.start_at(ms(100));
+
+        assert_eq!(counter.previous_info(), None);
+    }
+}
@@ -0,0 +1,322 @@
+//! Reachability diagnostics of [ICE] servers.
+//!
+//! [ICE]: https://webrtcglossary.com/ice
+
+use std::{cell::RefCell, collections::HashSet, iter, rc::Rc, time::Duration};
+
+use futures::{channel::mpsc, future, StreamExt as _};
+use medea_client_api_proto::IceServer;
+
+use crate::{
+    media::MediaKind,
+    platform::{
+        self, IceCandidateAttributes, IceCandidateFilter, IceCandidateType,
+        TransceiverDirection,
+    },
+};
+
+/// [ICE candidate][1] gathered while probing [ICE] servers.
+///
+/// [ICE]: https://webrtcglossary.com/ice
+/// [1]: https://w3.org/TR/webrtc#dom-rtcicecandidate
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+struct GatheredCandidate {
+    /// [`IceCandidateType`] of this [`GatheredCandidate`].
+    kind: IceCandidateType,
+
+    /// Transport protocol of this [`GatheredCandidate`].
+    protocol: String,
+
+    /// IP address (or mDNS hostname) of this [`GatheredCandidate`].
+    address: String,
+
+    /// Indicator whether this [`GatheredCandidate`] is filtered out by an
+    /// [`IceCandidateFilter`].
+    is_filtered_out: bool,
+}
+
+impl GatheredCandidate {
+    /// Parses a [`GatheredCandidate`] from the provided
+    /// [candidate-attribute][1], checking it against the provided
+    /// [`IceCandidateFilter`].
+    ///
+    /// [1]: https://tools.ietf.org/html/rfc5245#section-15.1
+    fn parse(candidate: &str, filter: &IceCandidateFilter) -> Option<Self> {
+        IceCandidateAttributes::parse(candidate).map(|c| Self {
+            is_filtered_out: !filter.allows_parsed(&c),
+            kind: c.kind,
+            protocol: c.protocol,
+            address: c.address,
+        })
+    }
+}
+
+/// Result of probing a single [ICE] server URL.
+///
+/// [ICE]: https://webrtcglossary.com/ice
+#[derive(Clone, Debug)]
+pub struct IceServerProbe {
+    /// Probed URL of the [ICE] server.
+    ///
+    /// [ICE]: https://webrtcglossary.com/ice
+    pub url: String,
+
+    /// Indicator whether the [ICE] server is reachable, meaning that a
+    /// [`IceCandidateType::ServerReflexive`] (for a STUN server) or a
+    /// [`IceCandidateType::Relay`] (for a TURN server) candidate has been
+    /// gathered from it in time.
+    ///
+    /// [ICE]: https://webrtcglossary.com/ice
+    pub is_reachable: bool,
+
+    /// Error occurred while probing the [ICE] server, if any.
+    ///
+    /// [ICE]: https://webrtcglossary.com/ice
+    pub error: Option<String>,
+}
+
+/// Summary of probing [ICE] servers with the [`probe_ice_servers()`].
+///
+/// [ICE]: https://webrtcglossary.com/ice
+#[derive(Clone, Debug)]
+pub struct IceProbeReport {
+    /// Number of distinct [`IceCandidateType::Host`] candidates gathered.
+    pub host_candidates: usize,
+
+    /// Number of distinct [`IceCandidateType::ServerReflexive`] candidates
+    /// gathered.
+    pub srflx_candidates: usize,
+
+    /// Number of distinct [`IceCandidateType::Relay`] candidates gathered.
+    pub relay_candidates: usize,
+
+    /// Number of distinct gathered candidates filtered out by an
+    /// [`IceCandidateFilter`], not counted as candidates of any
+    /// [`IceCandidateType`].
+    pub filtered_candidates: usize,
+
+    /// [`IceServerProbe`]s of every probed [ICE] server URL.
+    ///
+    /// [ICE]: https://webrtcglossary.com/ice
+    pub servers: Vec<IceServerProbe>,
+
+    /// Duration of the whole candidates gathering.
+    pub gathering_duration: Duration,
+}
+
+impl IceProbeReport {
+    /// Returns number of distinct candidates of the provided
+    /// [`IceCandidateType`] in the provided [`GatheredCandidate`]s, which
+    /// aren't filtered out.
+    fn count(
+        candidates: &HashSet<GatheredCandidate>,
+        kind: IceCandidateType,
+    ) -> usize {
+        candidates
+            .iter()
+            .filter(|c| !c.is_filtered_out && c.kind == kind)
+            .count()
+    }
+}
+
+/// Probes reachability of the provided [ICE] servers, gathering candidates
+/// from each of their URLs for no longer than the provided `timeout`.
+///
+/// Every URL is probed concurrently with its own throwaway
+/// [`platform::RtcPeerConnection`], which is closed once the probe finishes.
+/// The probe of a URL finishes as soon as the URL is proven reachable, so
+/// only unreachable ones take the whole `timeout`.
+///
+/// Candidates filtered out by the provided [`IceCandidateFilter`] are only
+/// counted as [`IceProbeReport::filtered_candidates`], while still proving
+/// reachability of the [ICE] servers.
+///
+/// [ICE]: https://webrtcglossary.com/ice
+pub async fn probe_ice_servers<I>(
+    servers: I,
+    timeout: Duration,
+    filter: &IceCandidateFilter,
+) -> IceProbeReport
+where
+    I: IntoIterator<Item = IceServer>,
+{
+    let started_at = platform::now();
+
+    let probes = servers.into_iter().flat_map(|server| {
+        let IceServer {
+            urls,
+            username,
+            credential,
+        } = server;
+        urls.into_iter().map(move |url| IceServer {
+            urls: vec![url],
+            username: username.clone(),
+            credential: credential.clone(),
+        })
+    });
+    let probes = probes.map(|s| probe_ice_server(s, timeout, filter));
+    let results = future::join_all(probes).await;
+
+    let mut candidates = HashSet::new();
+    let mut servers = Vec::with_capacity(results.len());
+    for (probe, gathered) in results {
+        candidates.extend(gathered);
+        servers.push(probe);
+    }
+
+    IceProbeReport {
+        host_candidates: IceProbeReport::count(
+            &candidates,
+            IceCandidateType::Host,
+        ),
+        srflx_candidates: IceProbeReport::count(
+            &candidates,
+            IceCandidateType::ServerReflexive,
+        ),
+        relay_candidates: IceProbeReport::count(
+            &candidates,
+            IceCandidateType::Relay,
+        ),
+        filtered_candidates: candidates
+            .iter()
+            .filter(|c| c.is_filtered_out)
+            .count(),
+        servers,
+        gathering_duration: platform::now().saturating_sub(started_at),
+    }
+}
+
+/// Probes reachability of the provided [ICE] server having a single URL.
+///
+/// [ICE]: https://webrtcglossary.com/ice
+async fn probe_ice_server(
+    server: IceServer,
+    timeout: Duration,
+    filter: &IceCandidateFilter,
+) -> (IceServerProbe, Vec<GatheredCandidate>) {
+    let url = server.urls.first().cloned().unwrap_or_default();
+    let expected = if url.starts_with("turn") {
+        IceCandidateType::Relay
+    } else {
+        IceCandidateType::ServerReflexive
+    };
+    let mut probe = IceServerProbe {
+        url,
+        is_reachable: false,
+        error: None,
+    };
+
+    let peer = match platform::RtcPeerConnection::new(
+        iter::once(server),
+        false,
+        &platform::RtcConfiguration::default(),
+    )
+    .await
+    {
+        Ok(peer) => peer,
+        Err(e) => {
+            probe.error = Some(e.into_inner().to_string());
+            return (probe, Vec::new());
+        }
+    };
+
+    let (tx, mut rx) = mpsc::unbounded();
+    peer.on_ice_candidate(Some(move |c: platform::IceCandidate| {
+        _ = tx.unbounded_send(c.candidate);
+    }));
+    // Candidates are gathered only for negotiated media, so some should be
+    // offered.
+    drop(
+        peer.add_transceiver(MediaKind::Audio, TransceiverDirection::RECV)
+            .await,
+    );
+    let receive = platform::OfferReceiveOptions::default();
+    let offered = match peer.create_offer(receive).await {
+        Ok(offer) => peer.set_offer(&offer).await,
+        Err(e) => Err(e),
+    };
+    if let Err(e) = offered {
+        probe.error = Some(e.into_inner().to_string());
+        return (probe, Vec::new());
+    }
+
+    let gathered = Rc::new(RefCell::new(Vec::new()));
+    let gathering = {
+        let gathered = Rc::clone(&gathered);
+        async move {
+            while let Some(candidate) = rx.next().await {
+                if let Some(c) = GatheredCandidate::parse(&candidate, filter) {
+                    let is_expected = c.kind == expected;
+                    gathered.borrow_mut().push(c);
+                    if is_expected {
+                        return true;
+                    }
+                }
+            }
+            false
+        }
+    };
+    probe.is_reachable = matches!(
+        future::select(
+            Box::pin(gathering),
+            Box::pin(platform::delay_for(timeout)),
+        )
+        .await,
+        future::Either::Left((true, _)),
+    );
+
+    peer.on_ice_candidate::<fn(platform::IceCandidate)>(None);
+    drop(peer);
+
+    let gathered = gathered.take();
+    (probe, gathered)
+}
+
+#[cfg(test)]
+mod spec {
+    use crate::platform::IceCandidateFilter;
+
+    use super::{GatheredCandidate, IceCandidateType};
+
+    /// Host candidate gathered while probing.
+    const HOST: &str =
+        "candidate:1 1 UDP 2122260223 192.168.1.2 54321 typ host";
+
+    /// Relay candidate gathered while probing.
+    const RELAY: &str = "candidate:2 1 udp 41885439 10.0.0.1 3478 typ relay \
+                         raddr 1.2.3.4 rport 54321";
+
+    #[test]
+    fn parses_candidates() {
+        let all = IceCandidateFilter::All;
+
+        let host = GatheredCandidate::parse(HOST, &all).unwrap();
+        assert_eq!(host.kind, IceCandidateType::Host);
+        assert_eq!(host.protocol, "udp");
+        assert_eq!(host.address, "192.168.1.2");
+        assert!(!host.is_filtered_out);
+
+        let relay = GatheredCandidate::parse(RELAY, &all).unwrap();
+        assert_eq!(relay.kind, IceCandidateType::Relay);
+        assert_eq!(relay.address, "10.0.0.1");
+        assert!(!relay.is_filtered_out);
+
+        assert!(GatheredCandidate::parse("candidate:3 1 udp", &all).is_none());
+        assert!(GatheredCandidate::parse(
+            "candidate:4 1 udp 1 10.0.0.1 3478 typ unknown",
+            &all,
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn marks_filtered_out_candidates() {
+        let relay_only = IceCandidateFilter::RelayOnly;
+
+        let host = GatheredCandidate::parse(HOST, &relay_only).unwrap();
+        assert!(host.is_filtered_out);
+
+        let relay = GatheredCandidate::parse(RELAY, &relay_only).unwrap();
+        assert!(!relay.is_filtered_out);
+    }
+}
@@ -1,10 +1,15 @@
 //! Platform-specific functionality.
 
 pub mod callback;
+pub mod ice_candidate;
+pub mod ice_gathering;
+pub mod ice_probe;
+pub mod network;
 pub mod peer_connection;
 pub mod rtc_stats;
 pub mod transceiver;
 pub mod transport;
+pub mod webrtc_support;
 
 #[cfg(not(target_family = "wasm"))]
 mod dart;
@@ -22,10 +27,21 @@ use crate::utils::Caused;
 
 pub use self::{
     callback::Callback,
-    peer_connection::{IceCandidate, RtcPeerConnectionError, SdpType},
+    ice_candidate::{
+        IceCandidateAttributes, IceCandidateFilter, IceCandidateType,
+    },
+    ice_gathering::{IceGatheringCounter, IceGatheringInfo},
+    ice_probe::{probe_ice_servers, IceProbeReport, IceServerProbe},
+    network::NetworkEvent,
+    peer_connection::{
+        BundlePolicy, IceCandidate, IceTransportPolicy, OfferReceiveOptions,
+        RtcConfiguration, RtcPeerConnectionError, RtcPeerConnectionOperation,
+        SdpType,
+    },
     rtc_stats::RtcStatsError,
     transceiver::Direction as TransceiverDirection,
     transport::{RpcTransport, TransportError, TransportState},
+    webrtc_support::WebRtcSupport,
 };
 
 #[cfg(feature = "mockable")]
@@ -47,3 +63,17 @@ pub enum GetUserMediaError {
     /// Cause cannot be identified.
     Unknown(Error),
 }
+
+impl GetUserMediaError {
+    /// Indicates whether this [`GetUserMediaError`] is likely to be transient
+    /// (like a device being briefly held by another tab or application), so
+    /// the request is worth retrying.
+    ///
+    /// These are `NotReadableError` and `AbortError` only, which are never
+    /// thrown before a permission is granted.
+    #[must_use]
+    pub fn is_transient(&self) -> bool {
+        let (Self::Audio(err) | Self::Video(err) | Self::Unknown(err)) = self;
+        matches!(err.name().as_str(), "NotReadableError" | "AbortError")
+    }
+}
@@ -0,0 +1,129 @@
+//! Network availability and page visibility changes of the current platform.
+
+use std::time::Duration;
+
+use futures::{
+    future::{self, Either},
+    StreamExt as _,
+};
+
+use crate::platform;
+
+/// Change of the network availability or the page visibility, which a
+/// reconnection to a media server should react to.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum NetworkEvent {
+    /// Network has become available.
+    Online,
+
+    /// Network has become unavailable.
+    Offline,
+
+    /// Page has become visible (after being in a background tab, for
+    /// example), so its timers are not throttled anymore.
+    Visible,
+
+    /// Page has become hidden (switched to a background tab, for example).
+    Hidden,
+}
+
+impl NetworkEvent {
+    /// Updates the provided `online` state with this [`NetworkEvent`] and
+    /// indicates whether a pending reconnection attempt should be performed
+    /// right away.
+    fn wakes_up(self, online: &mut bool) -> bool {
+        match self {
+            Self::Online => {
+                *online = true;
+                true
+            }
+            Self::Offline => {
+                *online = false;
+                false
+            }
+            Self::Visible => *online,
+            Self::Hidden => false,
+        }
+    }
+}
+
+/// [`Future`] which resolves after the provided [`Duration`], or earlier once
+/// the network becomes available or the page becomes visible.
+///
+/// Doesn't resolve while the network is unavailable, regardless of the
+/// provided [`Duration`].
+///
+/// [`Future`]: std::future::Future
+pub async fn delay_for(delay: Duration) {
+    let mut events = platform::on_network_events();
+    let mut online = platform::is_online();
+    let mut timeout = Box::pin(platform::delay_for(delay));
+
+    loop {
+        let event = if online {
+            match future::select(timeout, events.next()).await {
+                Either::Left(((), _)) => return,
+                Either::Right((event, pending)) => {
+                    timeout = pending;
+                    event
+                }
+            }
+        } else {
+            events.next().await
+        };
+
+        match event {
+            Some(event) => {
+                if event.wakes_up(&mut online) {
+                    return;
+                }
+            }
+            None => {
+                if online {
+                    timeout.await;
+                }
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod spec {
+    use super::NetworkEvent;
+
+    #[test]
+    fn online_wakes_up() {
+        let mut online = false;
+
+        assert!(NetworkEvent::Online.wakes_up(&mut online));
+        assert!(online);
+    }
+
+    #[test]
+    fn offline_pauses() {
+        let mut online = true;
+
+        assert!(!NetworkEvent::Offline.wakes_up(&mut online));
+        assert!(!online);
+    }
+
+    #[test]
+    fn hidden_doesnt_wake_up() {
+        let mut online = true;
+
+        assert!(!NetworkEvent::Hidden.wakes_up(&mut online));
+        assert!(online);
+    }
+
+    #[test]
+    fn visible_wakes_up_only_when_online() {
+        let mut online = true;
+        assert!(NetworkEvent::Visible.wakes_up(&mut online));
+        assert!(online);
+
+        let mut online = false;
+        assert!(!NetworkEvent::Visible.wakes_up(&mut online));
+        assert!(!online);
+    }
+}
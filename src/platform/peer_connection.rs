@@ -3,6 +3,7 @@
 use derive_more::{Display, From};
 
 use crate::{
+    media::MediaKind,
     platform::{self, RtcStatsError},
     utils::Caused,
 };
@@ -10,7 +11,7 @@ use crate::{
 /// Representation of [RTCSdpType].
 ///
 /// [RTCSdpType]: https://w3.org/TR/webrtc#dom-rtcsdptype
-#[derive(Debug)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub enum SdpType {
     /// [`offer` type][1] of SDP.
     ///
@@ -23,6 +24,101 @@ pub enum SdpType {
     Answer(String),
 }
 
+/// Per-[`MediaKind`] indicators whether media should be received, used for
+/// creating an SDP offer.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct OfferReceiveOptions {
+    /// Indicator whether audio should be received.
+    pub audio: bool,
+
+    /// Indicator whether video should be received.
+    pub video: bool,
+}
+
+impl Default for OfferReceiveOptions {
+    fn default() -> Self {
+        Self {
+            audio: true,
+            video: true,
+        }
+    }
+}
+
+impl OfferReceiveOptions {
+    /// Indicates whether media of the provided [`MediaKind`] should be
+    /// received.
+    #[must_use]
+    pub const fn is_receiving(self, kind: MediaKind) -> bool {
+        match kind {
+            MediaKind::Audio => self.audio,
+            MediaKind::Video => self.video,
+        }
+    }
+}
+
+/// Representation of [RTCBundlePolicy][1].
+///
+/// [1]: https://w3.org/TR/webrtc#dom-rtcbundlepolicy
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum BundlePolicy {
+    /// Gather ICE candidates for each media type in use (audio, video and
+    /// data), negotiating a separate transport for each of them.
+    Balanced,
+
+    /// Gather ICE candidates for each track, negotiating a separate
+    /// transport for each of them.
+    MaxCompat,
+
+    /// Gather ICE candidates for only one track, bundling all the media onto
+    /// a single transport.
+    #[default]
+    MaxBundle,
+}
+
+/// Representation of [RTCIceTransportPolicy][1].
+///
+/// [1]: https://w3.org/TR/webrtc#dom-rtcicetransportpolicy
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum IceTransportPolicy {
+    /// All ICE candidates are considered.
+    All,
+
+    /// Only ICE candidates whose IP addresses are being relayed, such as
+    /// those being passed through a TURN server, are considered.
+    Relay,
+}
+
+/// Options of a [`platform::RtcPeerConnection`] configurable by a client.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct RtcConfiguration {
+    /// [`BundlePolicy`] to negotiate media with.
+    pub bundle_policy: BundlePolicy,
+
+    /// Size of the prefetched ICE candidate pool.
+    ///
+    /// Browser's default is used if [`None`].
+    pub ice_candidate_pool_size: Option<u8>,
+
+    /// [`IceTransportPolicy`] overriding the one requested by a media server.
+    pub ice_transport_policy: Option<IceTransportPolicy>,
+}
+
+impl RtcConfiguration {
+    /// Resolves the [`IceTransportPolicy`] to be used, considering whether
+    /// a media server requires the media to be relayed.
+    #[must_use]
+    pub fn ice_transport_policy(
+        self,
+        is_force_relayed: bool,
+    ) -> IceTransportPolicy {
+        self.ice_transport_policy.unwrap_or(if is_force_relayed {
+            IceTransportPolicy::Relay
+        } else {
+            IceTransportPolicy::All
+        })
+    }
+}
+
 /// [RTCIceCandidate][1] representation.
 ///
 /// [1]: https://w3.org/TR/webrtc/#rtcicecandidate-interface
@@ -47,10 +143,71 @@ pub struct IceCandidate {
     pub sdp_mid: Option<String>,
 }
 
+/// Operation of a [`platform::RtcPeerConnection`] an
+/// [`RtcPeerConnectionError`] originates from.
+#[derive(Clone, Copy, Debug, Display, Eq, PartialEq)]
+pub enum RtcPeerConnectionOperation {
+    /// [RTCPeerConnection.addIceCandidate()][1] call.
+    ///
+    /// [1]: https://w3.org/TR/webrtc#dom-peerconnection-addicecandidate
+    #[display(fmt = "addIceCandidate")]
+    AddIceCandidate,
+
+    /// [RTCPeerConnection.createAnswer()][1] call.
+    ///
+    /// [1]: https://w3.org/TR/webrtc#dom-rtcpeerconnection-createanswer
+    #[display(fmt = "createAnswer")]
+    CreateAnswer,
+
+    /// [RTCPeerConnection.createOffer()][1] call.
+    ///
+    /// [1]: https://w3.org/TR/webrtc#dom-rtcpeerconnection-createoffer
+    #[display(fmt = "createOffer")]
+    CreateOffer,
+
+    /// Construction of a new [RTCPeerConnection][1].
+    ///
+    /// [1]: https://w3.org/TR/webrtc#dom-rtcpeerconnection
+    #[display(fmt = "new RTCPeerConnection")]
+    Create,
+
+    /// [RTCPeerConnection.getStats()][1] call.
+    ///
+    /// [1]: https://w3.org/TR/webrtc#dom-rtcpeerconnection-getstats
+    #[display(fmt = "getStats")]
+    GetStats,
+
+    /// [RTCPeerConnection.setLocalDescription()][1] call with an
+    /// [SDP offer][`SdpType::Offer`] or an [SDP answer][`SdpType::Answer`].
+    ///
+    /// [1]: https://w3.org/TR/webrtc#dom-peerconnection-setlocaldescription
+    #[display(fmt = "setLocalDescription")]
+    SetLocalDescription,
+
+    /// [RTCPeerConnection.setRemoteDescription()][1] call.
+    ///
+    /// [1]: https://w3.org/TR/webrtc#dom-peerconnection-setremotedescription
+    #[display(fmt = "setRemoteDescription")]
+    SetRemoteDescription,
+
+    /// [RTCPeerConnection.setLocalDescription()][1] call with a [rollback][2].
+    ///
+    /// [1]: https://w3.org/TR/webrtc#dom-peerconnection-setlocaldescription
+    /// [2]: https://w3.org/TR/webrtc#dom-rtcsdptype-rollback
+    #[display(fmt = "rollback")]
+    Rollback,
+}
+
 /// Errors that may occur during signaling between this and remote
 /// [RTCPeerConnection][1] and event handlers setting errors.
 ///
+/// Each error carries the [`RtcPeerConnectionOperation`] it originates from
+/// and, if thrown by a platform, the name of the thrown exception (usually, a
+/// [DOMException][2] name). See [`RtcPeerConnectionError::is_transient()`] for
+/// telling apart the errors which may be retried.
+///
 /// [1]: https://w3.org/TR/webrtc#dom-rtcpeerconnection
+/// [2]: https://webidl.spec.whatwg.org#idl-DOMException
 #[derive(Caused, Clone, Debug, Display, From)]
 #[cause(error = platform::Error)]
 pub enum RtcPeerConnectionError {
@@ -102,4 +259,68 @@ pub enum RtcPeerConnectionError {
     #[display(fmt = "Failed to set remote SDP description: {}", _0)]
     #[from(ignore)]
     SetRemoteDescriptionFailed(platform::Error),
+
+    /// Occurs if the [`platform::RtcPeerConnection`] cannot be rolled back to
+    /// its previous stable state.
+    #[display(fmt = "Failed to rollback SDP description: {}", _0)]
+    #[from(ignore)]
+    RollbackFailed(platform::Error),
+}
+
+impl RtcPeerConnectionError {
+    /// Returns the [`RtcPeerConnectionOperation`] this
+    /// [`RtcPeerConnectionError`] originates from.
+    #[must_use]
+    pub const fn operation(&self) -> RtcPeerConnectionOperation {
+        use RtcPeerConnectionOperation as Op;
+
+        match self {
+            Self::AddIceCandidateFailed(_) => Op::AddIceCandidate,
+            Self::CreateAnswerFailed(_) => Op::CreateAnswer,
+            Self::PeerCreationError(_) => Op::Create,
+            Self::CreateOfferFailed(_) => Op::CreateOffer,
+            Self::RtcStatsError(_) | Self::GetStatsException(_) => Op::GetStats,
+            Self::SetLocalDescriptionFailed(_) => Op::SetLocalDescription,
+            Self::SetRemoteDescriptionFailed(_) => Op::SetRemoteDescription,
+            Self::RollbackFailed(_) => Op::Rollback,
+        }
+    }
+
+    /// Returns name of the exception thrown by a platform (usually, a
+    /// [DOMException][1] name, like `InvalidStateError`), if any.
+    ///
+    /// [1]: https://webidl.spec.whatwg.org#idl-DOMException
+    #[must_use]
+    pub fn exception_name(&self) -> Option<String> {
+        match self {
+            Self::AddIceCandidateFailed(e)
+            | Self::CreateAnswerFailed(e)
+            | Self::PeerCreationError(e)
+            | Self::CreateOfferFailed(e)
+            | Self::GetStatsException(e)
+            | Self::SetLocalDescriptionFailed(e)
+            | Self::SetRemoteDescriptionFailed(e)
+            | Self::RollbackFailed(e) => Some(e.name()),
+            Self::RtcStatsError(_) => None,
+        }
+    }
+
+    /// Indicates whether this [`RtcPeerConnectionError`] is transient, so the
+    /// failed operation may succeed being retried once the
+    /// [`platform::RtcPeerConnection`] is rolled back to its stable state.
+    ///
+    /// This is the case for an [`InvalidStateError`][1] thrown by a remote
+    /// or local description setting, which happens when the
+    /// [`platform::RtcPeerConnection`]'s signaling state has been changed by
+    /// a concurrent negotiation (glare).
+    ///
+    /// [1]: https://webidl.spec.whatwg.org#invalidstateerror
+    #[must_use]
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self.operation(),
+            RtcPeerConnectionOperation::SetLocalDescription
+                | RtcPeerConnectionOperation::SetRemoteDescription
+        ) && self.exception_name().as_deref() == Some("InvalidStateError")
+    }
 }
@@ -0,0 +1,105 @@
+//! Meter of an audio level of a [MediaStreamTrack][1].
+//!
+//! [1]: https://w3.org/TR/mediacapture-streams#mediastreamtrack
+
+use std::cell::RefCell;
+
+use js_sys::Array;
+use web_sys::{
+    AnalyserNode, AudioContext, MediaStream, MediaStreamAudioSourceNode,
+};
+
+use crate::platform::{Error, MediaStreamTrack};
+
+/// Number of samples analysed on every [`AudioLevelMeter::level_db()`] call.
+const FFT_SIZE: u16 = 2048;
+
+/// Meter of an audio level of a [`MediaStreamTrack`] backed by a WebAudio
+/// [AnalyserNode][1].
+///
+/// The underlying audio graph is torn down on [`Drop`].
+///
+/// [1]: https://w3.org/TR/webaudio#AnalyserNode
+#[derive(Debug)]
+pub struct AudioLevelMeter {
+    /// [AudioContext][1] hosting the audio graph.
+    ///
+    /// [1]: https://w3.org/TR/webaudio#AudioContext
+    ctx: AudioContext,
+
+    /// [MediaStreamAudioSourceNode][1] feeding the metered
+    /// [`MediaStreamTrack`] into the audio graph.
+    ///
+    /// [1]: https://w3.org/TR/webaudio#MediaStreamAudioSourceNode
+    source: MediaStreamAudioSourceNode,
+
+    /// [AnalyserNode][1] providing samples of the metered
+    /// [`MediaStreamTrack`].
+    ///
+    /// [1]: https://w3.org/TR/webaudio#AnalyserNode
+    analyser: AnalyserNode,
+
+    /// Buffer the samples are read into.
+    samples: RefCell<Vec<f32>>,
+}
+
+impl AudioLevelMeter {
+    /// Builds a new [`AudioLevelMeter`] of the provided audio
+    /// [`MediaStreamTrack`].
+    ///
+    /// Never returns [`None`] on web platform.
+    ///
+    /// # Errors
+    ///
+    /// With an [`Error`] if failed to build the underlying audio graph.
+    #[allow(clippy::unnecessary_wraps)] // for platform code uniformity
+    pub fn new(track: &MediaStreamTrack) -> Option<Result<Self, Error>> {
+        Some(Self::build(track))
+    }
+
+    /// Builds the audio graph of a new [`AudioLevelMeter`].
+    ///
+    /// # Errors
+    ///
+    /// With an [`Error`] if any of the underlying WebAudio calls fails.
+    fn build(track: &MediaStreamTrack) -> Result<Self, Error> {
+        let ctx = AudioContext::new()?;
+        // `AudioContext` may be created suspended due to an autoplay policy,
+        // producing silence only.
+        _ = ctx.resume()?;
+        let sys_track: &web_sys::MediaStreamTrack = track.as_ref();
+        let stream = MediaStream::new_with_tracks(&Array::of1(sys_track))?;
+        let source = ctx.create_media_stream_source(&stream)?;
+        let analyser = ctx.create_analyser()?;
+        analyser.set_fft_size(u32::from(FFT_SIZE));
+        _ = source.connect_with_audio_node(&analyser)?;
+
+        Ok(Self {
+            ctx,
+            source,
+            analyser,
+            samples: RefCell::new(vec![0.0; usize::from(FFT_SIZE)]),
+        })
+    }
+
+    /// Returns the current [RMS] level of the metered [`MediaStreamTrack`] in
+    /// [dBFS].
+    ///
+    /// [dBFS]: https://en.wikipedia.org/wiki/DBFS
+    /// [RMS]: https://en.wikipedia.org/wiki/Root_mean_square
+    #[must_use]
+    pub fn level_db(&self) -> f64 {
+        let mut samples = self.samples.borrow_mut();
+        self.analyser.get_float_time_domain_data(&mut samples);
+        let sum: f64 = samples.iter().map(|s| f64::from(*s).powi(2)).sum();
+        let rms = (sum / f64::from(FFT_SIZE)).sqrt();
+        20.0 * rms.log10()
+    }
+}
+
+impl Drop for AudioLevelMeter {
+    fn drop(&mut self) {
+        _ = self.source.disconnect();
+        _ = self.ctx.close();
+    }
+}
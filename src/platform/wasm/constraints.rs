@@ -2,10 +2,12 @@
 
 use crate::media::{
     constraints::{ConstrainString, ConstrainU32},
-    AudioTrackConstraints, DeviceVideoTrackConstraints,
+    AudioTrackConstraints, DeviceVideoTrackConstraints, DisplayCaptureOptions,
     DisplayVideoTrackConstraints,
 };
 use derive_more::{AsRef, Into};
+use js_sys::Reflect;
+use wasm_bindgen::JsValue;
 use web_sys::{
     ConstrainDomStringParameters, ConstrainDoubleRange, MediaTrackConstraints,
 };
@@ -13,7 +15,7 @@ use web_sys::{
 /// [MediaStreamConstraints][1] wrapper.
 ///
 /// [1]: https://w3.org/TR/mediacapture-streams/#dom-mediastreamconstraints
-#[derive(AsRef, Debug, Into)]
+#[derive(AsRef, Clone, Debug, Into)]
 pub struct MediaStreamConstraints(web_sys::MediaStreamConstraints);
 
 impl MediaStreamConstraints {
@@ -52,6 +54,15 @@ impl From<AudioTrackConstraints> for MediaTrackConstraints {
             _ = constraints
                 .device_id(&ConstrainDomStringParameters::from(&device_id));
         }
+        if let Some(enabled) = track_constraints.auto_gain_control {
+            _ = constraints.auto_gain_control(&JsValue::from_bool(enabled));
+        }
+        if let Some(enabled) = track_constraints.echo_cancellation {
+            _ = constraints.echo_cancellation(&JsValue::from_bool(enabled));
+        }
+        if let Some(enabled) = track_constraints.noise_suppression {
+            _ = constraints.noise_suppression(&JsValue::from_bool(enabled));
+        }
 
         constraints
     }
@@ -75,6 +86,9 @@ impl From<DeviceVideoTrackConstraints> for MediaTrackConstraints {
         if let Some(height) = track_constraints.height {
             _ = constraints.height(&ConstrainDoubleRange::from(height));
         }
+        if let Some(frame_rate) = track_constraints.frame_rate {
+            _ = constraints.frame_rate(&ConstrainDoubleRange::from(frame_rate));
+        }
 
         constraints
     }
@@ -130,11 +144,42 @@ impl DisplayMediaStreamConstraints {
         Self(web_sys::DisplayMediaStreamConstraints::new())
     }
 
-    /// Specifies the nature and settings of the `video` [MediaStreamTrack][1].
+    /// Specifies the nature and settings of the `video` [MediaStreamTrack][1],
+    /// along with its [`DisplayCaptureOptions`].
     ///
     /// [1]: https://w3.org/TR/mediacapture-streams/#mediastreamtrack
     pub fn video(&mut self, video: DisplayVideoTrackConstraints) {
+        let options = video.capture_options;
         _ = self.0.video(&MediaTrackConstraints::from(video).into());
+        self.capture_options(options);
+    }
+
+    /// Specifies the provided [`DisplayCaptureOptions`] as the top-level
+    /// members of these [`DisplayMediaStreamConstraints`].
+    ///
+    /// Browsers ignore the members they don't support.
+    fn capture_options(&self, options: DisplayCaptureOptions) {
+        let target: &JsValue = self.0.as_ref();
+        if let Some(prefer) = options.prefer_current_tab {
+            _ = Reflect::set(
+                target,
+                &JsValue::from_str("preferCurrentTab"),
+                &JsValue::from_bool(prefer),
+            );
+        }
+        for (key, inclusion) in [
+            ("selfBrowserSurface", options.self_browser_surface),
+            ("surfaceSwitching", options.surface_switching),
+            ("systemAudio", options.system_audio),
+        ] {
+            if let Some(inclusion) = inclusion {
+                _ = Reflect::set(
+                    target,
+                    &JsValue::from_str(key),
+                    &JsValue::from_str(inclusion.as_str()),
+                );
+            }
+        }
     }
 }
 
@@ -13,6 +13,16 @@ use wasm_bindgen::{
 pub struct Error(js_sys::Error);
 
 impl Error {
+    /// Returns name of the error (for a [DOMException][1] it's one of the
+    /// [error names][2]).
+    ///
+    /// [1]: https://webidl.spec.whatwg.org#idl-DOMException
+    /// [2]: https://webidl.spec.whatwg.org#dfn-error-names-table
+    #[must_use]
+    pub fn name(&self) -> String {
+        self.0.name().into()
+    }
+
     /// Returns a brief description of the error if it's available or has been
     /// set.
     #[must_use]
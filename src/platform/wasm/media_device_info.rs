@@ -2,14 +2,14 @@
 //!
 //! [1]: https://w3.org/TR/mediacapture-streams#device-info
 
-use derive_more::From;
+use derive_more::{AsRef, From};
 
 use crate::media::MediaDeviceKind;
 
 /// Representation of a [MediaDeviceInfo][1].
 ///
 /// [1]: https://w3.org/TR/mediacapture-streams#device-info
-#[derive(Debug, From)]
+#[derive(AsRef, Clone, Debug, From)]
 pub struct MediaDeviceInfo(web_sys::MediaDeviceInfo);
 
 impl From<web_sys::MediaDeviceKind> for MediaDeviceKind {
@@ -51,6 +51,13 @@ impl MediaDeviceInfo {
         self.0.label()
     }
 
+    /// Indicates whether the label of the represented device is redacted,
+    /// which happens until a media access permission is granted.
+    #[must_use]
+    pub fn is_label_redacted(&self) -> bool {
+        self.0.label().is_empty()
+    }
+
     /// Returns group identifier of the represented device.
     ///
     /// Two devices have the same group identifier if they belong to the same
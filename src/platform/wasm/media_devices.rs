@@ -7,13 +7,21 @@
 #![allow(clippy::let_underscore_untyped, let_underscore_drop)]
 
 use std::{cell::RefCell, rc::Rc};
+use wasm_bindgen::{JsCast as _, JsValue};
 use wasm_bindgen_futures::JsFuture;
 
 use tracerr::Traced;
-use web_sys::{Event, MediaDevices as SysMediaDevices};
+use web_sys::{
+    Event, MediaDevices as SysMediaDevices, PermissionState, PermissionStatus,
+};
 
 use crate::{
-    media::{InvalidOutputAudioDeviceIdError, MediaSourceKind, MicVolumeError},
+    media::{
+        AudioCapabilities, AudioTrackConstraints, CapabilityRange,
+        DeviceCapabilities, DeviceVideoTrackConstraints,
+        InvalidOutputAudioDeviceIdError, MediaKind, MediaSourceKind,
+        MicVolumeError, VideoCapabilities,
+    },
     platform::{
         utils::EventListener, DisplayMediaStreamConstraints, Error,
         GetUserMediaError, MediaDeviceInfo, MediaDisplayInfo,
@@ -21,7 +29,9 @@ use crate::{
     },
 };
 
-use super::window;
+use super::{
+    get_property_by_name, media_track::parse_facing_mode, spawn, window,
+};
 
 impl From<Error> for GetUserMediaError {
     fn from(err: Error) -> Self {
@@ -37,6 +47,58 @@ impl From<Error> for GetUserMediaError {
     }
 }
 
+/// Pending [getUserMedia()][1]/[getDisplayMedia()][2] request.
+///
+/// If dropped before being resolved (for example, when a `Room` requesting
+/// media is closed), stops all the captured [`web_sys::MediaStreamTrack`]s
+/// once the request completes, so no camera or microphone is left on.
+///
+/// [1]: https://tinyurl.com/w3-streams#dom-mediadevices-getusermedia
+/// [2]: https://w3.org/TR/screen-capture#dom-mediadevices-getdisplaymedia
+#[derive(Debug)]
+struct PendingCapture {
+    /// [`js_sys::Promise`] of the requested [`web_sys::MediaStream`].
+    promise: js_sys::Promise,
+
+    /// Indicator whether the requested [`web_sys::MediaStream`] has been
+    /// taken by the requester.
+    is_taken: bool,
+}
+
+impl PendingCapture {
+    /// Wraps the provided [`js_sys::Promise`] of a media capture request.
+    const fn new(promise: js_sys::Promise) -> Self {
+        Self {
+            promise,
+            is_taken: false,
+        }
+    }
+
+    /// Waits for the requested [`web_sys::MediaStream`].
+    async fn resolve(mut self) -> Result<web_sys::MediaStream, JsValue> {
+        let stream = JsFuture::from(self.promise.clone()).await;
+        self.is_taken = true;
+        stream.map(web_sys::MediaStream::from)
+    }
+}
+
+impl Drop for PendingCapture {
+    fn drop(&mut self) {
+        if self.is_taken {
+            return;
+        }
+        let promise = JsFuture::from(self.promise.clone());
+        spawn(async move {
+            if let Ok(stream) = promise.await {
+                let stream = web_sys::MediaStream::from(stream);
+                for track in stream.get_tracks().iter() {
+                    web_sys::MediaStreamTrack::from(track).stop();
+                }
+            }
+        });
+    }
+}
+
 /// Media devices controller.
 #[derive(Debug)]
 pub struct MediaDevices {
@@ -47,6 +109,11 @@ pub struct MediaDevices {
     /// [`SysMediaDevices`].
     on_device_change_listener:
         RefCell<Option<EventListener<SysMediaDevices, Event>>>,
+
+    /// [`EventListener`]s for the `change` events of the camera and
+    /// microphone [`PermissionStatus`]es.
+    on_permission_change_listeners:
+        Rc<RefCell<Vec<EventListener<PermissionStatus, Event>>>>,
 }
 
 impl Default for MediaDevices {
@@ -72,6 +139,7 @@ impl MediaDevices {
         Self {
             devices: Rc::new(devices),
             on_device_change_listener: RefCell::new(None),
+            on_permission_change_listeners: Rc::default(),
         }
     }
 
@@ -145,14 +213,14 @@ impl MediaDevices {
         &self,
         caps: MediaStreamConstraints,
     ) -> Result<Vec<MediaStreamTrack>, Traced<GetUserMediaError>> {
-        let stream = JsFuture::from(
+        let stream = PendingCapture::new(
             self.devices
                 .get_user_media_with_constraints(&caps.into())
                 .map_err(Error::from)
                 .map_err(tracerr::from_and_wrap!())?,
         )
+        .resolve()
         .await
-        .map(web_sys::MediaStream::from)
         .map_err(Error::from)
         .map_err(tracerr::from_and_wrap!())?;
 
@@ -196,14 +264,14 @@ impl MediaDevices {
             .map_err(Error::from)
             .map_err(tracerr::wrap!())?;
 
-        let stream = JsFuture::from(
+        let stream = PendingCapture::new(
             media_devices
                 .get_display_media_with_constraints(&caps.into())
                 .map_err(Error::from)
                 .map_err(tracerr::wrap!())?,
         )
+        .resolve()
         .await
-        .map(web_sys::MediaStream::from)
         .map_err(Error::from)
         .map_err(tracerr::wrap!())?;
 
@@ -219,6 +287,62 @@ impl MediaDevices {
             .collect())
     }
 
+    /// Returns [`DeviceCapabilities`] of the provided media input device of
+    /// the provided [`MediaKind`].
+    ///
+    /// Uses [InputDeviceInfo.getCapabilities()][1] if it's available.
+    /// Otherwise, captures the device via [getUserMedia()][2], queries
+    /// [MediaStreamTrack.getCapabilities()][3] of the captured track and stops
+    /// it right away.
+    ///
+    /// # Errors
+    ///
+    /// With [`GetUserMediaError`] if [getUserMedia()][2] request fails.
+    ///
+    /// [1]: https://tinyurl.com/w3-streams#dom-inputdeviceinfo-getcapabilities
+    /// [2]: https://tinyurl.com/w3-streams#dom-mediadevices-getusermedia
+    /// [3]: https://tinyurl.com/w3-streams#dom-mediastreamtrack-getcapabilities
+    pub async fn device_capabilities(
+        &self,
+        device: &MediaDeviceInfo,
+        kind: MediaKind,
+    ) -> Result<DeviceCapabilities, Traced<GetUserMediaError>> {
+        let info: &web_sys::MediaDeviceInfo = device.as_ref();
+        if let Some(caps) = get_capabilities(info) {
+            return Ok(parse_capabilities(kind, &caps));
+        }
+
+        let mut constraints = MediaStreamConstraints::new();
+        match kind {
+            MediaKind::Audio => {
+                let mut audio = AudioTrackConstraints::new();
+                audio.device_id(device.device_id());
+                constraints.audio(audio);
+            }
+            MediaKind::Video => {
+                let mut video = DeviceVideoTrackConstraints::new();
+                video.device_id(device.device_id());
+                constraints.video(video);
+            }
+        }
+        let tracks = self
+            .get_user_media(constraints)
+            .await
+            .map_err(tracerr::wrap!())?;
+
+        let caps = tracks.iter().find(|t| t.kind() == kind).and_then(|t| {
+            let track: &web_sys::MediaStreamTrack = t.as_ref();
+            get_capabilities(track)
+        });
+        for track in tracks {
+            track.stop().await;
+        }
+        Ok(parse_capabilities(
+            kind,
+            &caps.unwrap_or(JsValue::UNDEFINED),
+        ))
+    }
+
     /// This method should be unreachable, because this functional is
     /// implemented on the Dart side of Jason only.
     ///
@@ -263,6 +387,68 @@ impl MediaDevices {
         }
     }
 
+    /// Subscribes onto the `change` events of the camera and microphone
+    /// permissions, invoking the provided callback with a [`MediaKind`] of the
+    /// media, the access to which has been granted.
+    ///
+    /// Permissions are observed via the [Permissions API][1], so the ones not
+    /// supported by a browser are not observed at all.
+    ///
+    /// [1]: https://w3.org/TR/permissions
+    pub fn on_permission_granted<F>(&self, f: Option<F>)
+    where
+        F: 'static + FnMut(MediaKind),
+    {
+        self.on_permission_change_listeners.borrow_mut().clear();
+        let Some(f) = f else {
+            return;
+        };
+        let Ok(permissions) = window().navigator().permissions() else {
+            return;
+        };
+
+        let f = Rc::new(RefCell::new(f));
+        for (name, kind) in [
+            ("microphone", MediaKind::Audio),
+            ("camera", MediaKind::Video),
+        ] {
+            let descriptor = js_sys::Object::new();
+            _ = js_sys::Reflect::set(
+                &descriptor,
+                &JsValue::from_str("name"),
+                &JsValue::from_str(name),
+            );
+            let Ok(query) = permissions.query(&descriptor) else {
+                continue;
+            };
+            let f = Rc::clone(&f);
+            let listeners = Rc::clone(&self.on_permission_change_listeners);
+            spawn(async move {
+                let status = match JsFuture::from(query).await {
+                    Ok(status) => Rc::new(PermissionStatus::from(status)),
+                    Err(e) => {
+                        log::debug!(
+                            "`{name}` permission is not observable: {}",
+                            Error::from(e),
+                        );
+                        return;
+                    }
+                };
+                let listener = EventListener::new_mut(
+                    Rc::clone(&status),
+                    "change",
+                    move |_| {
+                        if status.state() == PermissionState::Granted {
+                            (f.borrow_mut())(kind);
+                        }
+                    },
+                )
+                .unwrap();
+                listeners.borrow_mut().push(listener);
+            });
+        }
+    }
+
     /// Always returns `false` since accessing microphone cannot be implemented
     /// on web platform.
     #[allow(clippy::unused_async)]
@@ -309,3 +495,60 @@ impl MediaDevices {
         )
     }
 }
+
+/// Calls a `getCapabilities()` method of the provided JS object, if it has
+/// one, returning its result unless it's empty.
+///
+/// Some platforms return an empty object instead of not implementing the
+/// method at all.
+fn get_capabilities<T: AsRef<JsValue>>(obj: &T) -> Option<JsValue> {
+    let get = get_property_by_name(obj, "getCapabilities", |f| {
+        f.dyn_into::<js_sys::Function>().ok()
+    })?;
+    let caps = get.call0(obj.as_ref()).ok()?;
+    let is_empty = !caps.is_object()
+        || js_sys::Object::keys(caps.unchecked_ref()).length() == 0;
+    (!is_empty).then_some(caps)
+}
+
+/// Parses [`DeviceCapabilities`] of the provided [`MediaKind`] out of the
+/// provided [MediaTrackCapabilities][1].
+///
+/// [1]: https://w3.org/TR/mediacapture-streams#media-track-capabilities
+fn parse_capabilities(kind: MediaKind, caps: &JsValue) -> DeviceCapabilities {
+    let range = |name: &str| {
+        get_property_by_name(caps, name, |r| {
+            Some(CapabilityRange {
+                min: get_property_by_name(&r, "min", |v| v.as_f64())?,
+                max: get_property_by_name(&r, "max", |v| v.as_f64())?,
+            })
+        })
+    };
+    let list = |name: &str| {
+        get_property_by_name(caps, name, |v| {
+            v.is_array().then(|| js_sys::Array::from(&v).to_vec())
+        })
+        .unwrap_or_default()
+    };
+
+    match kind {
+        MediaKind::Audio => DeviceCapabilities::Audio(AudioCapabilities {
+            sample_rates: range("sampleRate"),
+            channel_counts: range("channelCount"),
+            echo_cancellation_modes: list("echoCancellation")
+                .iter()
+                .filter_map(JsValue::as_bool)
+                .collect(),
+        }),
+        MediaKind::Video => DeviceCapabilities::Video(VideoCapabilities {
+            width_range: range("width"),
+            height_range: range("height"),
+            frame_rate_range: range("frameRate"),
+            facing_modes: list("facingMode")
+                .iter()
+                .filter_map(JsValue::as_string)
+                .filter_map(|m| parse_facing_mode(&m))
+                .collect(),
+        }),
+    }
+}
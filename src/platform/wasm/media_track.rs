@@ -9,7 +9,8 @@ use futures::future;
 
 use crate::{
     media::{
-        track::MediaStreamTrackState, FacingMode, MediaKind, MediaSourceKind,
+        track::MediaStreamTrackState, ContentHint, DisplaySurface, FacingMode,
+        MediaKind, MediaSourceKind,
     },
     platform::wasm::{get_property_by_name, utils::EventListener},
 };
@@ -135,16 +136,7 @@ impl MediaStreamTrack {
             "facingMode",
             |v| v.as_string(),
         );
-        facing_mode.and_then(|fm| match fm.as_ref() {
-            "user" => Some(FacingMode::User),
-            "environment" => Some(FacingMode::Environment),
-            "left" => Some(FacingMode::Left),
-            "right" => Some(FacingMode::Right),
-            _ => {
-                log::error!("Unknown FacingMode: {fm}");
-                None
-            }
-        })
+        facing_mode.as_deref().and_then(parse_facing_mode)
     }
 
     /// Returns a [`height`][1] of the underlying [MediaStreamTrack][2].
@@ -179,6 +171,51 @@ impl MediaStreamTrack {
         })
     }
 
+    /// Returns a [`displaySurface`][1] of the underlying [MediaStreamTrack][2].
+    ///
+    /// [1]: https://w3.org/TR/screen-capture#extensions-to-mediatracksettings
+    /// [2]: https://w3.org/TR/mediacapture-streams#mediastreamtrack
+    #[must_use]
+    pub fn display_surface(&self) -> Option<DisplaySurface> {
+        let surface = get_property_by_name(
+            &self.sys_track.get_settings(),
+            "displaySurface",
+            |v| v.as_string(),
+        );
+        surface.map(|s| match s.as_ref() {
+            "monitor" => DisplaySurface::Monitor,
+            "window" => DisplaySurface::Window,
+            "browser" => DisplaySurface::Browser,
+            _ => DisplaySurface::Unknown,
+        })
+    }
+
+    /// Returns a [`contentHint`][1] of the underlying [MediaStreamTrack][2],
+    /// or [`None`] if it has no (or an unknown) one.
+    ///
+    /// [1]: https://w3.org/TR/mst-content-hint#dom-mediastreamtrack-contenthint
+    /// [2]: https://w3.org/TR/mediacapture-streams#mediastreamtrack
+    #[must_use]
+    pub fn content_hint(&self) -> Option<ContentHint> {
+        get_property_by_name(&*self.sys_track, "contentHint", |v| v.as_string())
+            .and_then(|hint| ContentHint::parse(&hint))
+    }
+
+    /// Changes a [`contentHint`][1] of the underlying [MediaStreamTrack][2].
+    ///
+    /// A browser ignores the [`ContentHint`]s not applicable to the kind of
+    /// the underlying [MediaStreamTrack][2].
+    ///
+    /// [1]: https://w3.org/TR/mst-content-hint#dom-mediastreamtrack-contenthint
+    /// [2]: https://w3.org/TR/mediacapture-streams#mediastreamtrack
+    pub fn set_content_hint(&self, hint: ContentHint) {
+        _ = js_sys::Reflect::set(
+            &self.sys_track,
+            &"contentHint".into(),
+            &hint.as_str().into(),
+        );
+    }
+
     /// Changes an [`enabled`][1] attribute in the underlying
     /// [MediaStreamTrack][2].
     ///
@@ -272,3 +309,19 @@ impl MediaStreamTrack {
         });
     }
 }
+
+/// Parses the provided [VideoFacingModeEnum][1] value into a [`FacingMode`].
+///
+/// [1]: https://w3.org/TR/mediacapture-streams#dom-videofacingmodeenum
+pub(super) fn parse_facing_mode(facing_mode: &str) -> Option<FacingMode> {
+    match facing_mode {
+        "user" => Some(FacingMode::User),
+        "environment" => Some(FacingMode::Environment),
+        "left" => Some(FacingMode::Left),
+        "right" => Some(FacingMode::Right),
+        _ => {
+            log::error!("Unknown FacingMode: {facing_mode}");
+            None
+        }
+    }
+}
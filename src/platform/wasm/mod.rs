@@ -1,18 +1,21 @@
 //! `wasm32`-platform-specific functionality.
 
+pub mod audio_level_meter;
 pub mod constraints;
 pub mod error;
 pub mod ice_server;
 pub mod media_device_info;
 pub mod media_devices;
 pub mod media_track;
+pub mod network;
 pub mod peer_connection;
 pub mod rtc_stats;
 pub mod transceiver;
 pub mod transport;
 pub mod utils;
+pub mod video_transformer;
 
-use std::time::Duration;
+use std::{cell::Cell, time::Duration};
 
 use futures::Future;
 use js_sys::{Promise, Reflect};
@@ -20,17 +23,22 @@ use wasm_bindgen::JsValue;
 use wasm_bindgen_futures::JsFuture;
 use web_sys::Window;
 
+use crate::platform::WebRtcSupport;
+
 pub use self::{
+    audio_level_meter::AudioLevelMeter,
     constraints::{DisplayMediaStreamConstraints, MediaStreamConstraints},
     error::Error,
     media_device_info::MediaDeviceInfo,
     media_devices::MediaDevices,
     media_track::MediaStreamTrack,
+    network::{is_online, on_network_events},
     peer_connection::RtcPeerConnection,
     rtc_stats::RtcStats,
     transceiver::Transceiver,
     transport::WebSocketRpcTransport,
     utils::Function,
+    video_transformer::VideoTransformer,
 };
 
 /// Unimplemented on WASM targets.
@@ -88,6 +96,12 @@ pub async fn delay_for(delay: Duration) {
     .unwrap();
 }
 
+/// Returns the current time as a [`Duration`] since the UNIX epoch.
+#[must_use]
+pub fn now() -> Duration {
+    Duration::from_secs_f64(js_sys::Date::now() / 1000.0)
+}
+
 /// Returns property of JS object by name if its defined.
 /// Converts the value with a given predicate.
 pub fn get_property_by_name<T, F, U>(
@@ -117,6 +131,77 @@ pub fn window() -> Window {
     web_sys::window().unwrap()
 }
 
+thread_local! {
+    /// [`WebRtcSupport`] of the current browser, detected lazily.
+    static WEBRTC_SUPPORT: Cell<Option<WebRtcSupport>> = Cell::new(None);
+}
+
+/// Returns [`WebRtcSupport`] of the current browser.
+///
+/// Detected on the first call and cached afterwards.
+#[must_use]
+pub fn webrtc_available() -> WebRtcSupport {
+    WEBRTC_SUPPORT.with(|cached| {
+        cached.get().unwrap_or_else(|| {
+            let support = detect_webrtc_support();
+            cached.set(Some(support));
+            support
+        })
+    })
+}
+
+/// Detects [`WebRtcSupport`] of the current browser.
+///
+/// Availability of [RTCPeerConnection][1] is checked by creating one, since
+/// browsers with [WebRTC] disabled by a policy may still expose the
+/// constructor throwing on every call.
+///
+/// [WebRTC]: https://w3.org/TR/webrtc
+/// [1]: https://w3.org/TR/webrtc#dom-rtcpeerconnection
+fn detect_webrtc_support() -> WebRtcSupport {
+    let has_function = |obj: &JsValue, name: &str| {
+        get_property_by_name(obj, name, |f| f.is_function().then_some(()))
+            .is_some()
+    };
+    let window = window();
+
+    let peer_connection =
+        web_sys::RtcPeerConnection::new().map_or(false, |peer| {
+            peer.close();
+            true
+        });
+    let media_devices =
+        get_property_by_name(&window.navigator(), "mediaDevices", Some)
+            .unwrap_or_default();
+    let encoded_streams = get_property_by_name(&window, "RTCRtpSender", Some)
+        .and_then(|sender| get_property_by_name(&sender, "prototype", Some))
+        .map_or(false, |proto| has_function(&proto, "createEncodedStreams"));
+
+    WebRtcSupport {
+        peer_connection,
+        get_user_media: has_function(&media_devices, "getUserMedia"),
+        get_display_media: has_function(&media_devices, "getDisplayMedia"),
+        insertable_streams: encoded_streams
+            || has_function(&window, "RTCRtpScriptTransform"),
+    }
+}
+
+/// Indicates whether a [`MediaStreamTrack`] can be processed by a
+/// [`VideoTransformer`], which requires a `<canvas>` to be [capturable][1].
+///
+/// [1]: https://w3.org/TR/mediacapture-fromelement
+#[must_use]
+pub fn supports_track_processing() -> bool {
+    get_property_by_name(&window(), "HTMLCanvasElement", Some)
+        .and_then(|canvas| get_property_by_name(&canvas, "prototype", Some))
+        .and_then(|proto| {
+            get_property_by_name(&proto, "captureStream", |f| {
+                f.is_function().then_some(())
+            })
+        })
+        .is_some()
+}
+
 /// Wrapper around interval timer ID.
 #[derive(Debug)]
 pub struct IntervalHandle(pub i32);
@@ -0,0 +1,103 @@
+//! Network availability and page visibility changes of a browser.
+
+use std::{
+    pin::Pin,
+    rc::Rc,
+    task::{Context, Poll},
+};
+
+use futures::{
+    channel::mpsc,
+    stream::{LocalBoxStream, Stream, StreamExt as _},
+};
+use web_sys::{Event, Node, VisibilityState, Window};
+
+use crate::platform::{
+    wasm::{utils::EventListener, window},
+    NetworkEvent,
+};
+
+/// [`Stream`] of [`NetworkEvent`]s, unsubscribing from the browser events on
+/// [`Drop`].
+struct NetworkEvents {
+    /// Receiver of the [`NetworkEvent`]s.
+    events: mpsc::UnboundedReceiver<NetworkEvent>,
+
+    /// Listeners of the [`Window`]'s `online` and `offline` events.
+    _window_listeners: Vec<EventListener<Window, Event>>,
+
+    /// Listener of the [`Document`]'s `visibilitychange` event.
+    ///
+    /// [`Document`]: web_sys::Document
+    _visibility_listener: Option<EventListener<Node, Event>>,
+}
+
+impl Stream for NetworkEvents {
+    type Item = NetworkEvent;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        self.events.poll_next_unpin(cx)
+    }
+}
+
+/// Indicates whether the browser reports the network as available.
+#[must_use]
+pub fn is_online() -> bool {
+    window().navigator().on_line()
+}
+
+/// Returns a [`LocalBoxStream`] of [`NetworkEvent`]s, emitted on the
+/// [`Window`]'s `online` and `offline` events, and on the [`Document`]'s
+/// `visibilitychange` event.
+///
+/// The browser events are listened to only while the returned
+/// [`LocalBoxStream`] is alive.
+///
+/// [`Document`]: web_sys::Document
+#[must_use]
+pub fn on_network_events() -> LocalBoxStream<'static, NetworkEvent> {
+    let (tx, events) = mpsc::unbounded();
+    let window = Rc::new(window());
+
+    let window_listeners = [
+        ("online", NetworkEvent::Online),
+        ("offline", NetworkEvent::Offline),
+    ]
+    .into_iter()
+    .filter_map(|(name, event)| {
+        let tx = tx.clone();
+        EventListener::new_mut(Rc::clone(&window), name, move |_: Event| {
+            _ = tx.unbounded_send(event);
+        })
+        .map_err(|e| log::error!("Failed to listen `{name}` event: {e}"))
+        .ok()
+    })
+    .collect();
+
+    let visibility_listener = window.document().and_then(|document| {
+        let target = Rc::new(Node::from(document.clone()));
+        EventListener::new_mut(target, "visibilitychange", move |_: Event| {
+            _ = tx.unbounded_send(
+                if document.visibility_state() == VisibilityState::Visible {
+                    NetworkEvent::Visible
+                } else {
+                    NetworkEvent::Hidden
+                },
+            );
+        })
+        .map_err(|e| {
+            log::error!("Failed to listen `visibilitychange` event: {e}");
+        })
+        .ok()
+    });
+
+    NetworkEvents {
+        events,
+        _window_listeners: window_listeners,
+        _visibility_listener: visibility_listener,
+    }
+    .boxed_local()
+}
@@ -16,8 +16,9 @@ use medea_client_api_proto::{
 use tracerr::Traced;
 use wasm_bindgen_futures::JsFuture;
 use web_sys::{
-    Event, RtcBundlePolicy, RtcConfiguration, RtcIceCandidateInit,
-    RtcIceConnectionState, RtcIceTransportPolicy, RtcOfferOptions,
+    Event, RtcBundlePolicy, RtcConfiguration as SysRtcConfiguration,
+    RtcIceCandidateInit, RtcIceConnectionState, RtcIceGatheringState,
+    RtcIceTransportPolicy, RtcOfferOptions,
     RtcPeerConnection as SysRtcPeerConnection, RtcPeerConnectionIceEvent,
     RtcRtpTransceiver, RtcRtpTransceiverInit, RtcSdpType,
     RtcSessionDescription, RtcSessionDescriptionInit, RtcTrackEvent,
@@ -28,8 +29,10 @@ use crate::{
     platform::{
         self,
         wasm::{get_property_by_name, utils::EventListener},
-        IceCandidate, MediaStreamTrack, RtcPeerConnectionError, RtcStats,
-        SdpType, Transceiver, TransceiverDirection,
+        BundlePolicy, IceCandidate, IceGatheringCounter, IceTransportPolicy,
+        MediaStreamTrack, OfferReceiveOptions, RtcConfiguration,
+        RtcPeerConnectionError, RtcStats, SdpType, Transceiver,
+        TransceiverDirection,
     },
 };
 
@@ -97,47 +100,97 @@ pub struct RtcPeerConnection {
     /// [4]: https://developer.mozilla.org/en-US/docs/Web/API/MediaStreamTrack
     on_track:
         RefCell<Option<EventListener<SysRtcPeerConnection, RtcTrackEvent>>>,
+
+    /// [`IceGatheringCounter`] of the [ICE] candidates gathered by this
+    /// [`RtcPeerConnection`].
+    ///
+    /// [ICE]: https://webrtcglossary.com/ice
+    ice_gathering: Rc<IceGatheringCounter>,
+
+    /// [`icegatheringstatechange`][2] callback of [RTCPeerConnection][1],
+    /// updating the [`RtcPeerConnection::ice_gathering`].
+    ///
+    /// [1]: https://w3.org/TR/webrtc/#rtcpeerconnection-interface
+    /// [2]: https://w3.org/TR/webrtc#event-icegatheringstatechange
+    on_ice_gathering_state_changed:
+        RefCell<Option<EventListener<SysRtcPeerConnection, Event>>>,
 }
 
 impl RtcPeerConnection {
-    /// Instantiates new [`RtcPeerConnection`].
+    /// Instantiates new [`RtcPeerConnection`] with the provided
+    /// [`RtcConfiguration`].
     ///
     /// # Errors
     ///
     /// Errors with [`RtcPeerConnectionError::PeerCreationError`] if
     /// [`SysRtcPeerConnection`] creation fails.
+    ///
+    /// # Panics
+    ///
+    /// If binding to the [`icegatheringstatechange`][1] event fails. Not
+    /// supposed to ever happen.
+    ///
+    /// [1]: https://w3.org/TR/webrtc#event-icegatheringstatechange
     #[allow(clippy::unused_async)] // for platform code uniformity
     pub async fn new<I>(
         ice_servers: I,
         is_force_relayed: bool,
+        config: &RtcConfiguration,
     ) -> RtcPeerConnectionResult<Self>
     where
         I: IntoIterator<Item = IceServer>,
     {
-        let mut peer_conf = RtcConfiguration::new();
-        let policy = if is_force_relayed {
-            RtcIceTransportPolicy::Relay
-        } else {
-            RtcIceTransportPolicy::All
-        };
-        _ = peer_conf.bundle_policy(RtcBundlePolicy::MaxBundle);
-        _ = peer_conf.ice_transport_policy(policy);
-        _ = peer_conf.ice_servers(&RtcIceServers::from(ice_servers));
+        let peer_conf =
+            sys_configuration(ice_servers, is_force_relayed, *config);
         let peer = SysRtcPeerConnection::new_with_configuration(&peer_conf)
             .map_err(Into::into)
             .map_err(RtcPeerConnectionError::PeerCreationError)
             .map_err(tracerr::wrap!())?;
 
+        let peer = Rc::new(peer);
+        let ice_gathering = Rc::new(IceGatheringCounter::default());
+        // Unwrapping is OK here, because this function shouldn't error ever.
+        let on_ice_gathering_state_changed = EventListener::new_mut(
+            Rc::clone(&peer),
+            "icegatheringstatechange",
+            {
+                let peer = Rc::clone(&peer);
+                let ice_gathering = Rc::clone(&ice_gathering);
+                move |_| match peer.ice_gathering_state() {
+                    RtcIceGatheringState::Gathering => ice_gathering.start(),
+                    RtcIceGatheringState::Complete => ice_gathering.complete(),
+                    _ => {}
+                }
+            },
+        )
+        .unwrap();
+
         Ok(Self {
-            peer: Rc::new(peer),
+            peer,
             ice_restart: Cell::new(false),
             on_ice_candidate: RefCell::new(None),
             on_ice_connection_state_changed: RefCell::new(None),
             on_connection_state_changed: RefCell::new(None),
             on_track: RefCell::new(None),
+            ice_gathering,
+            on_ice_gathering_state_changed: RefCell::new(Some(
+                on_ice_gathering_state_changed,
+            )),
         })
     }
 
+    /// Returns [`IceGatheringCounter`] of the [ICE] candidates gathered by
+    /// this [`RtcPeerConnection`].
+    ///
+    /// Candidates are accounted only while an
+    /// [`RtcPeerConnection::on_ice_candidate()`] handler is set.
+    ///
+    /// [ICE]: https://webrtcglossary.com/ice
+    #[must_use]
+    pub fn ice_gathering(&self) -> Rc<IceGatheringCounter> {
+        Rc::clone(&self.ice_gathering)
+    }
+
     /// Returns [`RtcStats`] of this [`RtcPeerConnection`].
     ///
     /// # Errors
@@ -216,6 +269,7 @@ impl RtcPeerConnection {
         drop(match f {
             None => on_ice_candidate.take(),
             Some(mut f) => {
+                let ice_gathering = Rc::clone(&self.ice_gathering);
                 on_ice_candidate.replace(
                     // Unwrapping is OK here, because this function shouldn't
                     // error ever.
@@ -228,11 +282,14 @@ impl RtcPeerConnection {
                             // Doesn't need to be delivered onward to the remote
                             // peer.
                             if let Some(c) = msg.candidate() {
+                                ice_gathering.add_candidate(&c.candidate());
                                 f(IceCandidate {
                                     candidate: c.candidate(),
                                     sdp_m_line_index: c.sdp_m_line_index(),
                                     sdp_mid: c.sdp_mid(),
                                 });
+                            } else {
+                                ice_gathering.complete();
                             }
                         },
                     )
@@ -475,7 +532,7 @@ impl RtcPeerConnection {
     ///
     /// # Errors
     ///
-    /// With [`RtcPeerConnectionError::SetLocalDescriptionFailed`] if
+    /// With [`RtcPeerConnectionError::RollbackFailed`] if
     /// [RtcPeerConnection.setLocalDescription()][1] fails.
     ///
     /// [1]: https://w3.org/TR/webrtc#dom-peerconnection-setlocaldescription
@@ -488,7 +545,7 @@ impl RtcPeerConnection {
         .await
         .map(drop)
         .map_err(Into::into)
-        .map_err(RtcPeerConnectionError::SetLocalDescriptionFailed)
+        .map_err(RtcPeerConnectionError::RollbackFailed)
         .map_err(tracerr::wrap!())?;
 
         Ok(())
@@ -500,19 +557,35 @@ impl RtcPeerConnection {
     /// Should be called after local tracks changes, which require
     /// (re)negotiation.
     ///
+    /// Media kinds not received accordingly to the provided
+    /// [`OfferReceiveOptions`] are offered with
+    /// [`offerToReceiveAudio`/`offerToReceiveVideo`][2] set to `false`.
+    ///
     /// # Errors
     ///
     /// With [`RtcPeerConnectionError::CreateOfferFailed`] if
     /// [RtcPeerConnection.createOffer()][1] fails.
     ///
     /// [1]: https://w3.org/TR/webrtc#dom-rtcpeerconnection-createoffer
-    pub async fn create_offer(&self) -> RtcPeerConnectionResult<String> {
+    /// [2]: https://w3.org/TR/webrtc#legacy-configuration-extensions
+    pub async fn create_offer(
+        &self,
+        receive: OfferReceiveOptions,
+    ) -> RtcPeerConnectionResult<String> {
         let peer: Rc<SysRtcPeerConnection> = Rc::clone(&self.peer);
 
         let mut offer_options = RtcOfferOptions::new();
         if self.ice_restart.take() {
             _ = offer_options.ice_restart(true);
         }
+        // Setting `true` would add new `recvonly` transceivers, so only
+        // opting out is passed.
+        if !receive.audio {
+            _ = offer_options.offer_to_receive_audio(false);
+        }
+        if !receive.video {
+            _ = offer_options.offer_to_receive_video(false);
+        }
         let create_offer = JsFuture::from(
             peer.create_offer_with_rtc_offer_options(&offer_options),
         )
@@ -633,6 +706,7 @@ impl Drop for RtcPeerConnection {
         drop(self.on_ice_candidate.borrow_mut().take());
         drop(self.on_ice_connection_state_changed.borrow_mut().take());
         drop(self.on_connection_state_changed.borrow_mut().take());
+        drop(self.on_ice_gathering_state_changed.borrow_mut().take());
         self.peer.close();
     }
 }
@@ -678,3 +752,132 @@ fn parse_ice_connection_state(
         }
     }
 }
+
+/// Builds a [`SysRtcConfiguration`] from the provided [`RtcConfiguration`]
+/// for a [`SysRtcPeerConnection`] using the provided [`IceServer`]s.
+///
+/// # Panics
+///
+/// If setting of the `iceCandidatePoolSize` property fails. Not supposed to
+/// ever happen.
+fn sys_configuration<I>(
+    ice_servers: I,
+    is_force_relayed: bool,
+    config: RtcConfiguration,
+) -> SysRtcConfiguration
+where
+    I: IntoIterator<Item = IceServer>,
+{
+    let mut peer_conf = SysRtcConfiguration::new();
+    _ = peer_conf.bundle_policy(config.bundle_policy.into());
+    _ = peer_conf.ice_transport_policy(
+        config.ice_transport_policy(is_force_relayed).into(),
+    );
+    _ = peer_conf.ice_servers(&RtcIceServers::from(ice_servers));
+    if let Some(size) = config.ice_candidate_pool_size {
+        _ = js_sys::Reflect::set(
+            &peer_conf,
+            &"iceCandidatePoolSize".into(),
+            &size.into(),
+        )
+        .unwrap();
+    }
+    peer_conf
+}
+
+impl From<BundlePolicy> for RtcBundlePolicy {
+    fn from(policy: BundlePolicy) -> Self {
+        match policy {
+            BundlePolicy::Balanced => Self::Balanced,
+            BundlePolicy::MaxCompat => Self::MaxCompat,
+            BundlePolicy::MaxBundle => Self::MaxBundle,
+        }
+    }
+}
+
+impl From<IceTransportPolicy> for RtcIceTransportPolicy {
+    fn from(policy: IceTransportPolicy) -> Self {
+        match policy {
+            IceTransportPolicy::All => Self::All,
+            IceTransportPolicy::Relay => Self::Relay,
+        }
+    }
+}
+
+#[cfg(test)]
+mod spec {
+    use js_sys::Reflect;
+    use wasm_bindgen::JsValue;
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use crate::platform::{BundlePolicy, IceTransportPolicy, RtcConfiguration};
+
+    use super::sys_configuration;
+
+    /// Returns the value of the provided property of the provided
+    /// [`JsValue`].
+    fn prop(obj: &JsValue, name: &str) -> JsValue {
+        Reflect::get(obj, &name.into()).unwrap()
+    }
+
+    #[wasm_bindgen_test]
+    fn defaults_to_max_bundle() {
+        let conf =
+            sys_configuration(Vec::new(), false, RtcConfiguration::default());
+
+        assert_eq!(prop(&conf, "bundlePolicy"), "max-bundle");
+        assert_eq!(prop(&conf, "iceTransportPolicy"), "all");
+        assert!(prop(&conf, "iceCandidatePoolSize").is_undefined());
+    }
+
+    #[wasm_bindgen_test]
+    fn reflects_configured_options() {
+        for (policy, expected) in [
+            (BundlePolicy::Balanced, "balanced"),
+            (BundlePolicy::MaxCompat, "max-compat"),
+            (BundlePolicy::MaxBundle, "max-bundle"),
+        ] {
+            let conf = sys_configuration(
+                Vec::new(),
+                false,
+                RtcConfiguration {
+                    bundle_policy: policy,
+                    ..RtcConfiguration::default()
+                },
+            );
+
+            assert_eq!(prop(&conf, "bundlePolicy"), expected);
+        }
+
+        let conf = sys_configuration(
+            Vec::new(),
+            false,
+            RtcConfiguration {
+                ice_candidate_pool_size: Some(4),
+                ..RtcConfiguration::default()
+            },
+        );
+        assert_eq!(prop(&conf, "iceCandidatePoolSize"), 4);
+    }
+
+    #[wasm_bindgen_test]
+    fn overrides_ice_transport_policy() {
+        for (is_force_relayed, policy, expected) in [
+            (true, None, "relay"),
+            (false, None, "all"),
+            (false, Some(IceTransportPolicy::Relay), "relay"),
+            (true, Some(IceTransportPolicy::All), "all"),
+        ] {
+            let conf = sys_configuration(
+                Vec::new(),
+                is_force_relayed,
+                RtcConfiguration {
+                    ice_transport_policy: policy,
+                    ..RtcConfiguration::default()
+                },
+            );
+
+            assert_eq!(prop(&conf, "iceTransportPolicy"), expected);
+        }
+    }
+}
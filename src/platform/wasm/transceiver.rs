@@ -1,10 +1,12 @@
 //! [`RtcRtpTransceiver`] wrapper.
 
-use std::{future::Future, rc::Rc};
+use std::{future::Future, rc::Rc, time::Duration};
 
 use derive_more::From;
+use js_sys::{Array, Object, Reflect};
+use wasm_bindgen::JsValue;
 use wasm_bindgen_futures::JsFuture;
-use web_sys::RtcRtpTransceiver;
+use web_sys::{RtcRtpReceiver, RtcRtpSender, RtcRtpTransceiver};
 
 use crate::{
     media::track::local,
@@ -71,15 +73,179 @@ impl Transceiver {
         &self,
         new_track: Option<&Rc<local::Track>>,
     ) -> Result<(), Error> {
+        let fut = new_track.map_or_else(
+            || self.0.sender().replace_track(None),
+            |track| {
+                track.with_sent_track(|t| {
+                    self.0.sender().replace_track(Some(t.as_ref()))
+                })
+            },
+        );
+        drop(JsFuture::from(fut).await?);
+        Ok(())
+    }
+
+    /// Forces the sender of this [`Transceiver`] to encode a new keyframe, by
+    /// deactivating and reactivating all of its [encodings][1].
+    ///
+    /// Returns [`None`] if the platform doesn't allow this.
+    ///
+    /// # Errors
+    ///
+    /// Errors with JS error if the underlying [setParameters()][2] call fails.
+    ///
+    /// [1]: https://w3.org/TR/webrtc#dom-rtcrtpsendparameters-encodings
+    /// [2]: https://w3.org/TR/webrtc#dom-rtcrtpsender-setparameters
+    pub async fn request_keyframe(&self) -> Option<Result<(), Error>> {
+        let sender = self.0.sender();
+        let toggle = async {
+            Self::set_encodings_active(&sender, false).await?;
+            Self::set_encodings_active(&sender, true).await
+        };
+        Some(toggle.await)
+    }
+
+    /// Sets [`maxBitrate`][1] (removing it if [`None`]) and [`active`][2]
+    /// attributes of all the [encodings][3] of the sender of this
+    /// [`Transceiver`].
+    ///
+    /// Returns [`None`] if the platform doesn't allow this.
+    ///
+    /// # Errors
+    ///
+    /// Errors with JS error if the underlying [setParameters()][4] call fails.
+    ///
+    /// [1]: https://w3.org/TR/webrtc#dom-rtcrtpencodingparameters-maxbitrate
+    /// [2]: https://w3.org/TR/webrtc#dom-rtcrtpencodingparameters-active
+    /// [3]: https://w3.org/TR/webrtc#dom-rtcrtpsendparameters-encodings
+    /// [4]: https://w3.org/TR/webrtc#dom-rtcrtpsender-setparameters
+    pub async fn set_send_encodings(
+        &self,
+        max_bitrate: Option<u32>,
+        active: bool,
+    ) -> Option<Result<(), Error>> {
+        let sender = self.0.sender();
+        let update = async {
+            let params = sender.get_parameters();
+            let encodings =
+                Reflect::get(&params, &JsValue::from_str("encodings"))?;
+            if Array::is_array(&encodings) {
+                let key = JsValue::from_str("maxBitrate");
+                for encoding in Array::from(&encodings).iter() {
+                    _ = if let Some(bitrate) = max_bitrate {
+                        Reflect::set(&encoding, &key, &bitrate.into())?
+                    } else {
+                        Reflect::delete_property(
+                            &Object::from(encoding.clone()),
+                            &key,
+                        )?
+                    };
+                    _ = Reflect::set(
+                        &encoding,
+                        &JsValue::from_str("active"),
+                        &JsValue::from_bool(active),
+                    )?;
+                }
+            }
+            drop(
+                JsFuture::from(sender.set_parameters_with_parameters(&params))
+                    .await?,
+            );
+            Ok(())
+        };
+        Some(update.await)
+    }
+
+    /// Sets [`active`][1] attribute of all the [encodings][2] of the provided
+    /// [`RtcRtpSender`].
+    ///
+    /// # Errors
+    ///
+    /// Errors with JS error if the underlying [setParameters()][3] call fails.
+    ///
+    /// [1]: https://w3.org/TR/webrtc#dom-rtcrtpencodingparameters-active
+    /// [2]: https://w3.org/TR/webrtc#dom-rtcrtpsendparameters-encodings
+    /// [3]: https://w3.org/TR/webrtc#dom-rtcrtpsender-setparameters
+    async fn set_encodings_active(
+        sender: &RtcRtpSender,
+        active: bool,
+    ) -> Result<(), Error> {
+        let params = sender.get_parameters();
+        let encodings = Reflect::get(&params, &JsValue::from_str("encodings"))?;
+        if Array::is_array(&encodings) {
+            for encoding in Array::from(&encodings).iter() {
+                _ = Reflect::set(
+                    &encoding,
+                    &JsValue::from_str("active"),
+                    &JsValue::from_bool(active),
+                )?;
+            }
+        }
         drop(
-            JsFuture::from(self.0.sender().replace_track(
-                new_track.map(|track| (**track).as_ref().as_ref()),
-            ))
-            .await?,
+            JsFuture::from(sender.set_parameters_with_parameters(&params))
+                .await?,
         );
         Ok(())
     }
 
+    /// Sets [jitterBufferTarget][1] of the receiver of this [`Transceiver`],
+    /// falling back to the legacy `playoutDelayHint` if the former isn't
+    /// supported.
+    ///
+    /// Returns [`None`] if the platform supports neither of them.
+    ///
+    /// # Errors
+    ///
+    /// Errors with JS error if the browser rejects the provided `target`.
+    ///
+    /// [1]: https://w3.org/TR/webrtc-extensions
+    pub fn set_jitter_buffer_target(
+        &self,
+        target: Duration,
+    ) -> Option<Result<(), Error>> {
+        let receiver = self.0.receiver();
+        let attr = JitterBufferAttr::detect(&receiver)?;
+        let value = match attr {
+            JitterBufferAttr::Target => target.as_secs_f64() * 1000.0,
+            JitterBufferAttr::PlayoutDelayHint => target.as_secs_f64(),
+        };
+        Some(
+            Reflect::set(&receiver, &attr.name().into(), &value.into())
+                .map(drop)
+                .map_err(Error::from),
+        )
+    }
+
+    /// Returns the effective [jitterBufferTarget][1] (or the legacy
+    /// `playoutDelayHint`) of the receiver of this [`Transceiver`].
+    ///
+    /// Returns [`None`] if the platform supports neither of them, or no
+    /// target is set.
+    ///
+    /// [1]: https://w3.org/TR/webrtc-extensions
+    #[must_use]
+    pub fn jitter_buffer_target(&self) -> Option<Duration> {
+        let receiver = self.0.receiver();
+        let attr = JitterBufferAttr::detect(&receiver)?;
+        let value = Reflect::get(&receiver, &attr.name().into())
+            .ok()?
+            .as_f64()
+            .filter(|v| v.is_finite() && *v >= 0.0)?;
+        Some(match attr {
+            JitterBufferAttr::Target => Duration::from_secs_f64(value / 1000.0),
+            JitterBufferAttr::PlayoutDelayHint => {
+                Duration::from_secs_f64(value)
+            }
+        })
+    }
+
+    /// Indicates whether the platform allows setting a jitter buffer target
+    /// of the receiver of this [`Transceiver`].
+    #[must_use]
+    pub fn is_jitter_buffer_target_supported(&self) -> bool {
+        JitterBufferAttr::detect(&self.0.receiver()).is_some()
+    }
+
     /// Returns [`mid`] of this [`Transceiver`].
     ///
     /// [`mid`]: https://w3.org/TR/webrtc#dom-rtptransceiver-mid
@@ -93,6 +259,51 @@ impl Transceiver {
     pub fn is_stopped(&self) -> bool {
         self.0.stopped()
     }
+
+    /// Returns the [`currentDirection`][1] negotiated for this [`Transceiver`].
+    ///
+    /// Returns [`None`] if this [`Transceiver`] hasn't been negotiated yet.
+    ///
+    /// [1]: https://w3.org/TR/webrtc#dom-rtptransceiver-currentdirection
+    #[allow(clippy::unused_async)] // for platform code uniformity
+    pub async fn current_direction(&self) -> Option<TransceiverDirection> {
+        if self.is_stopped() {
+            return Some(TransceiverDirection::STOPPED);
+        }
+        self.0.current_direction().map(TransceiverDirection::from)
+    }
+}
+
+/// Attribute of a [`RtcRtpReceiver`] controlling its jitter buffer target.
+#[derive(Clone, Copy, Debug)]
+enum JitterBufferAttr {
+    /// [jitterBufferTarget][1] in milliseconds.
+    ///
+    /// [1]: https://w3.org/TR/webrtc-extensions
+    Target,
+
+    /// Legacy non-standard `playoutDelayHint` in seconds.
+    PlayoutDelayHint,
+}
+
+impl JitterBufferAttr {
+    /// Detects the [`JitterBufferAttr`] supported by the provided
+    /// [`RtcRtpReceiver`], preferring the standard one.
+    fn detect(receiver: &RtcRtpReceiver) -> Option<Self> {
+        [Self::Target, Self::PlayoutDelayHint]
+            .into_iter()
+            .find(|attr| {
+                Reflect::has(receiver, &attr.name().into()).unwrap_or_default()
+            })
+    }
+
+    /// Returns name of this [`JitterBufferAttr`].
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Target => "jitterBufferTarget",
+            Self::PlayoutDelayHint => "playoutDelayHint",
+        }
+    }
 }
 
 #[cfg(test)]
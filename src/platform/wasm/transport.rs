@@ -9,9 +9,11 @@ use std::{cell::RefCell, rc::Rc};
 use async_trait::async_trait;
 use derive_more::{From, Into};
 use futures::{channel::mpsc, stream::LocalBoxStream, StreamExt};
+use js_sys::{Array, Function, Reflect};
 use medea_client_api_proto::{ClientMsg, ServerMsg};
 use medea_reactive::ObservableCell;
 use tracerr::Traced;
+use wasm_bindgen::{JsCast as _, JsValue};
 use web_sys::{CloseEvent, Event, MessageEvent, WebSocket as SysWebSocket};
 
 use crate::{
@@ -85,12 +87,19 @@ struct InnerSocket {
     ///
     /// [1]: https://tools.ietf.org/html/rfc6455#section-5.5.1
     close_reason: ClientDisconnect,
+
+    /// JS class (or constructor function) used instead of the
+    /// [WebSocket] to create the socket.
+    ///
+    /// [WebSocket]: https://developer.mozilla.org/docs/Web/API/WebSocket
+    constructor: Option<Function>,
 }
 
 impl InnerSocket {
     /// Creates a new [`InnerSocket`] which can be connected to the server with
-    /// the [`RpcTransport::connect()`] method call.
-    const fn new() -> Self {
+    /// the [`RpcTransport::connect()`] method call, creating its socket with
+    /// the provided JS `constructor`, if any.
+    const fn new(constructor: Option<Function>) -> Self {
         Self {
             socket_state: ObservableCell::new(TransportState::Connecting),
             socket: RefCell::new(None),
@@ -99,6 +108,7 @@ impl InnerSocket {
             on_close_listener: None,
             on_message_subs: Vec::new(),
             close_reason: ClientDisconnect::RpcTransportUnexpectedlyDropped,
+            constructor,
         }
     }
 }
@@ -136,7 +146,30 @@ impl WebSocketRpcTransport {
     /// server with the [`RpcTransport::connect()`] method call.
     #[must_use]
     pub fn new() -> Self {
-        Self(Rc::new(RefCell::new(InnerSocket::new())))
+        Self(Rc::new(RefCell::new(InnerSocket::new(None))))
+    }
+
+    /// Returns a new [`WebSocketRpcTransport`] creating its socket with the
+    /// provided JS class (or constructor function) instead of the
+    /// [WebSocket], which should implement the same interface.
+    ///
+    /// [WebSocket]: https://developer.mozilla.org/docs/Web/API/WebSocket
+    #[must_use]
+    pub fn with_constructor(constructor: Function) -> Self {
+        Self(Rc::new(RefCell::new(InnerSocket::new(Some(constructor)))))
+    }
+
+    /// Creates a new socket connecting to the provided `url`, using the
+    /// [`InnerSocket::constructor`], if any.
+    fn create_socket(&self, url: &str) -> Result<SysWebSocket, JsValue> {
+        let inner = self.0.borrow();
+        inner.constructor.as_ref().map_or_else(
+            || SysWebSocket::new(url),
+            |constructor| {
+                Reflect::construct(constructor, &Array::of1(&url.into()))
+                    .map(JsCast::unchecked_into)
+            },
+        )
     }
 
     /// Sets [`InnerSocket::on_close_listener`] which will update
@@ -193,7 +226,8 @@ impl Default for WebSocketRpcTransport {
 #[async_trait(?Send)]
 impl RpcTransport for WebSocketRpcTransport {
     async fn connect(&self, url: ApiUrl) -> TransportResult<()> {
-        let socket = SysWebSocket::new(url.as_ref())
+        let socket = self
+            .create_socket(url.as_ref())
             .map_err(Into::into)
             .map_err(TransportError::CreateSocket)
             .map_err(tracerr::wrap!())?;
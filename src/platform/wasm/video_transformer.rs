@@ -0,0 +1,188 @@
+//! Transformer of a video [MediaStreamTrack][1] drawing its frames on a
+//! `<canvas>`.
+//!
+//! [1]: https://w3.org/TR/mediacapture-streams#mediastreamtrack
+
+use js_sys::Array;
+use wasm_bindgen::{closure::Closure, JsCast as _, JsValue};
+use web_sys::{
+    CanvasRenderingContext2d, HtmlCanvasElement, HtmlVideoElement, MediaStream,
+};
+
+use crate::{
+    media::{MediaSourceKind, VideoTransform},
+    platform::{
+        wasm::{get_property_by_name, window, IntervalHandle},
+        Error, MediaStreamTrack,
+    },
+};
+
+/// Frame rate of a transformed [`MediaStreamTrack`], if the input one doesn't
+/// report its own.
+const DEFAULT_FRAME_RATE: f64 = 30.0;
+
+/// Transformer of a video [`MediaStreamTrack`] playing it in a `<video>`
+/// element and drawing its frames transformed on a `<canvas>`, which is
+/// [captured][1] into a new [`MediaStreamTrack`].
+///
+/// The pipeline is torn down and the transformed [`MediaStreamTrack`] is
+/// stopped on [`Drop`].
+///
+/// [1]: https://w3.org/TR/mediacapture-fromelement
+#[derive(Debug)]
+pub struct VideoTransformer {
+    /// `<video>` element playing the input [`MediaStreamTrack`].
+    video: HtmlVideoElement,
+
+    /// Transformed [`MediaStreamTrack`] captured from a `<canvas>`.
+    output: MediaStreamTrack,
+
+    /// [`IntervalHandle`] of drawing the frames.
+    _interval: IntervalHandle,
+
+    /// [`Closure`] drawing a single frame, invoked by the interval.
+    _draw: Closure<dyn FnMut()>,
+}
+
+impl VideoTransformer {
+    /// Builds a new [`VideoTransformer`] of the provided video
+    /// [`MediaStreamTrack`], applying the provided [`VideoTransform`] to it.
+    ///
+    /// Returns [`None`] if the platform doesn't allow processing a
+    /// [`MediaStreamTrack`].
+    ///
+    /// # Errors
+    ///
+    /// With an [`Error`] if failed to build the processing pipeline.
+    #[must_use]
+    pub fn new(
+        track: &MediaStreamTrack,
+        source_kind: MediaSourceKind,
+        transform: VideoTransform,
+    ) -> Option<Result<Self, Error>> {
+        super::supports_track_processing()
+            .then(|| Self::build(track, source_kind, transform))
+    }
+
+    /// Returns the transformed [`MediaStreamTrack`].
+    #[must_use]
+    pub const fn output(&self) -> &MediaStreamTrack {
+        &self.output
+    }
+
+    /// Builds the processing pipeline of a new [`VideoTransformer`].
+    ///
+    /// # Errors
+    ///
+    /// With an [`Error`] if any of the underlying DOM calls fails.
+    fn build(
+        track: &MediaStreamTrack,
+        source_kind: MediaSourceKind,
+        transform: VideoTransform,
+    ) -> Result<Self, Error> {
+        let document = window().document().ok_or_else(|| {
+            Error::from(JsValue::from_str("`document` is not available"))
+        })?;
+        let sys_track: &web_sys::MediaStreamTrack = track.as_ref();
+
+        let video: HtmlVideoElement =
+            document.create_element("video")?.unchecked_into();
+        video.set_muted(true);
+        video.set_src_object(Some(&MediaStream::new_with_tracks(
+            &Array::of1(sys_track),
+        )?));
+        // Muted video is allowed to autoplay, so the returned `Promise` is
+        // not awaited.
+        _ = video.play()?;
+
+        let canvas: HtmlCanvasElement =
+            document.create_element("canvas")?.unchecked_into();
+        let ctx: CanvasRenderingContext2d = canvas
+            .get_context("2d")?
+            .ok_or_else(|| {
+                Error::from(JsValue::from_str("2D context is not available"))
+            })?
+            .unchecked_into();
+
+        let frame_rate =
+            get_property_by_name(&sys_track.get_settings(), "frameRate", |v| {
+                v.as_f64()
+            })
+            .filter(|rate| *rate > 0.0)
+            .unwrap_or(DEFAULT_FRAME_RATE);
+        #[allow(clippy::as_conversions, clippy::cast_possible_truncation)]
+        let interval_ms = (1000.0 / frame_rate).round() as i32;
+
+        let draw = {
+            let video = video.clone();
+            let canvas = canvas.clone();
+            Closure::<dyn FnMut()>::new(move || {
+                draw_frame(&video, &canvas, &ctx, transform);
+            })
+        };
+        let interval = window()
+            .set_interval_with_callback_and_timeout_and_arguments_0(
+                draw.as_ref().unchecked_ref(),
+                interval_ms,
+            )?;
+        let interval = IntervalHandle(interval);
+
+        let output = canvas
+            .capture_stream_with_frame_request_rate(frame_rate)?
+            .get_video_tracks()
+            .get(0)
+            .dyn_into::<web_sys::MediaStreamTrack>()?;
+
+        Ok(Self {
+            video,
+            output: MediaStreamTrack::new(output, Some(source_kind)),
+            _interval: interval,
+            _draw: draw,
+        })
+    }
+}
+
+impl Drop for VideoTransformer {
+    fn drop(&mut self) {
+        self.video.set_src_object(None);
+        let output: &web_sys::MediaStreamTrack = self.output.as_ref();
+        output.stop();
+    }
+}
+
+/// Draws the current frame of the provided `<video>` element on the provided
+/// `<canvas>`, transformed with the provided [`VideoTransform`].
+///
+/// Resizes the `<canvas>` to fit the transformed frame, if required.
+fn draw_frame(
+    video: &HtmlVideoElement,
+    canvas: &HtmlCanvasElement,
+    ctx: &CanvasRenderingContext2d,
+    transform: VideoTransform,
+) {
+    let (width, height) = (video.video_width(), video.video_height());
+    if width == 0 || height == 0 {
+        // No frames have been decoded yet.
+        return;
+    }
+    let (out_width, out_height) = transform.output_size(width, height);
+    if canvas.width() != out_width || canvas.height() != out_height {
+        canvas.set_width(out_width);
+        canvas.set_height(out_height);
+    }
+
+    let (width, height) = (f64::from(width), f64::from(height));
+    _ = ctx.set_transform(1.0, 0.0, 0.0, 1.0, 0.0, 0.0);
+    _ = ctx.translate(f64::from(out_width) / 2.0, f64::from(out_height) / 2.0);
+    _ = ctx.rotate(f64::from(transform.rotation.degrees()).to_radians());
+    if transform.mirror {
+        _ = ctx.scale(-1.0, 1.0);
+    }
+    _ = ctx.draw_image_with_html_video_element_and_dw_and_dh(
+        video,
+        -width / 2.0,
+        -height / 2.0,
+        width,
+        height,
+    );
+}
@@ -0,0 +1,31 @@
+//! Availability of [WebRTC] functionality on the current platform.
+//!
+//! [WebRTC]: https://w3.org/TR/webrtc
+
+/// Report about availability of [WebRTC] functionality on the current
+/// platform.
+///
+/// [WebRTC]: https://w3.org/TR/webrtc
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct WebRtcSupport {
+    /// Indicator whether a [RTCPeerConnection][1] can be created.
+    ///
+    /// [1]: https://w3.org/TR/webrtc#dom-rtcpeerconnection
+    pub peer_connection: bool,
+
+    /// Indicator whether a [getUserMedia()][1] request can be made.
+    ///
+    /// [1]: https://tinyurl.com/w3-streams#dom-mediadevices-getusermedia
+    pub get_user_media: bool,
+
+    /// Indicator whether a [getDisplayMedia()][1] request can be made.
+    ///
+    /// [1]: https://w3.org/TR/screen-capture#dom-mediadevices-getdisplaymedia
+    pub get_display_media: bool,
+
+    /// Indicator whether [insertable streams][1] of encoded media are
+    /// available.
+    ///
+    /// [1]: https://w3.org/TR/webrtc-encoded-transform
+    pub insertable_streams: bool,
+}
@@ -1,19 +1,22 @@
 //! Medea [`Room`].
 
 use std::{
-    cell::RefCell,
+    cell::{Cell, RefCell},
     collections::{HashMap, HashSet},
     fmt,
     future::Future,
     rc::{Rc, Weak},
+    str::FromStr,
+    time::Duration,
 };
 
 use async_recursion::async_recursion;
 use async_trait::async_trait;
 use derive_more::{Display, From};
 use futures::{
-    channel::mpsc, future, future::LocalBoxFuture, FutureExt as _,
-    StreamExt as _, TryFutureExt as _,
+    channel::mpsc,
+    future::{self, AbortRegistration, LocalBoxFuture},
+    FutureExt as _, StreamExt as _, TryFutureExt as _,
 };
 use medea_client_api_proto::{
     self as proto, Command, ConnectionQualityScore, Event as RpcEvent,
@@ -21,33 +24,72 @@ use medea_client_api_proto::{
     NegotiationRole, PeerConnectionState, PeerId, PeerMetrics, PeerUpdate,
     Track, TrackId,
 };
+use medea_reactive::{DroppedError, ObservableCell};
 use proto::ConnectionMode;
+use serde::{Deserialize, Serialize};
 use tracerr::Traced;
 
 use crate::{
     api,
-    connection::Connections,
+    connection::{
+        Connection, ConnectionHandle, Connections, MemberMediaStateChange,
+    },
+    error_report::{ErrorRateLimiter, NonFatalError, NonFatalErrorKind},
+    event_log::{EventKind, EventLog},
     media::{
         track::{local, remote},
-        InitLocalTracksError, LocalTracksConstraints, MediaKind, MediaManager,
-        MediaSourceKind, MediaStreamSettings, RecvConstraints,
+        AudioTrackConstraints, DeviceVideoTrackConstraints,
+        DisplayVideoTrackConstraints, InitLocalTracksError,
+        LocalTracksConstraints, MediaKind, MediaManager, MediaSourceKind,
+        MediaStreamSettings, RecvConstraints,
     },
+    media_policy::RoomMediaPolicy,
     peer::{
         self, media::ProhibitedStateError, media_exchange_state, mute_state,
-        InsertLocalTracksError, LocalMediaError, LocalStreamUpdateCriteria,
-        MediaState, PeerConnection, PeerEvent, PeerEventHandler,
-        TrackDirection, TracksRequestError, UpdateLocalStreamError,
+        InsertLocalTracksError, JoinPhase, JoinProgressUpdate, LocalMediaError,
+        LocalStreamUpdateCriteria, MediaState, PeerConnection, PeerEvent,
+        PeerEventHandler, TrackDirection, TracksRequestError, TransceiverInfo,
+        UpdateLocalStreamError,
     },
     platform,
     rpc::{
         ClientDisconnect, CloseReason, ConnectionInfo,
         ConnectionInfoParseError, ReconnectHandle, RpcSession, SessionError,
     },
-    utils::{AsProtoState, Caused},
+    session_metrics::{SessionMetrics, SessionMetricsCounter},
+    utils::{self, AbortableError, AsProtoState, Caused},
 };
 
 /// Alias of [`Result`]s related to [`MediaState`] update functions.
-type ChangeMediaStateResult = Result<(), Traced<ChangeMediaStateError>>;
+type ChangeMediaStateResult =
+    Result<MediaStateOutcome, Traced<ChangeMediaStateError>>;
+
+/// Outcome of a [`MediaState`] change requested via a [`RoomHandle`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MediaStateOutcome {
+    /// Requested [`MediaState`] has been applied.
+    Applied,
+
+    /// Requested [`MediaState`] change has been superseded by a newer
+    /// opposite one before being approved by a media server.
+    Superseded,
+}
+
+/// Identifier of a [`MediaState`] which can be changed via a [`RoomHandle`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+struct MediaStateKey {
+    /// Indicator whether this key identifies a [`MediaState::Mute`].
+    is_mute: bool,
+
+    /// [`MediaKind`] of the changed [`MediaState`].
+    kind: MediaKind,
+
+    /// [`TrackDirection`] of the changed [`MediaState`].
+    direction: TrackDirection,
+
+    /// [`MediaSourceKind`] of the changed [`MediaState`].
+    source_kind: Option<MediaSourceKind>,
+}
 
 /// Reason of why [`Room`] has been closed.
 ///
@@ -66,21 +108,26 @@ pub struct RoomCloseReason {
     ///
     /// This field may be `true` only on closing by client.
     pub(crate) is_err: bool,
+
+    /// [`SessionMetrics`] of the [`Room`] accumulated by its closing.
+    pub(crate) session_metrics: SessionMetrics,
 }
 
 impl RoomCloseReason {
-    /// Creates a new [`RoomCloseReason`] with the provided [`CloseReason`].
+    /// Creates a new [`RoomCloseReason`] with the provided [`CloseReason`]
+    /// and the final [`SessionMetrics`] of the [`Room`].
     ///
     /// `is_err` may be `true` only on closing by client.
     ///
     /// `is_closed_by_server` is `true` on [`CloseReason::ByServer`].
     #[must_use]
-    pub fn new(reason: CloseReason) -> Self {
+    pub fn new(reason: CloseReason, session_metrics: SessionMetrics) -> Self {
         match reason {
             CloseReason::ByServer(rsn) => Self {
                 reason: rsn.to_string(),
                 is_closed_by_server: true,
                 is_err: false,
+                session_metrics,
             },
             CloseReason::ByClient {
                 reason: rsn,
@@ -89,6 +136,7 @@ impl RoomCloseReason {
                 reason: rsn.to_string(),
                 is_closed_by_server: false,
                 is_err,
+                session_metrics,
             },
         }
     }
@@ -110,15 +158,189 @@ impl RoomCloseReason {
     pub const fn is_err(&self) -> bool {
         self.is_err
     }
+
+    /// Returns [`SessionMetrics`] of the [`Room`] accumulated by its closing.
+    #[must_use]
+    pub const fn session_metrics(&self) -> SessionMetrics {
+        self.session_metrics
+    }
+}
+
+/// Intended state of the local media of a single [`MediaKind`] and
+/// [`MediaSourceKind`], saved in a [`RoomSnapshot`].
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct LocalMediaSnapshot {
+    /// Indicator whether publishing of the media is enabled.
+    pub enabled: bool,
+
+    /// Indicator whether the media is muted.
+    pub muted: bool,
+
+    /// ID of the device the media is captured from, if chosen.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub device_id: Option<String>,
+}
+
+/// Snapshot of the media states intended in a [`Room`], allowing to rejoin it
+/// after a page refresh without the partners observing any media state the
+/// user hasn't intended.
+///
+/// Contains no credentials, so the [`Room`] is rejoined with the ones an
+/// application has.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct RoomSnapshot {
+    /// URL of the joined [`Room`] without credentials, in the
+    /// `{{ Host URL }}/{{ Room ID }}` format.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub room_url: Option<String>,
+
+    /// ID of the `Member` the [`Room`] is joined by.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub member_id: Option<MemberId>,
+
+    /// Intended state of the local audio.
+    pub audio: LocalMediaSnapshot,
+
+    /// Intended state of the local device video.
+    pub device_video: LocalMediaSnapshot,
+
+    /// Intended state of the local display video.
+    pub display_video: LocalMediaSnapshot,
+
+    /// Indicator whether receiving of the remote audio is enabled.
+    pub recv_audio: bool,
+
+    /// Indicator whether receiving of the remote device video is enabled.
+    pub recv_device_video: bool,
+
+    /// Indicator whether receiving of the remote display video is enabled.
+    pub recv_display_video: bool,
+}
+
+impl RoomSnapshot {
+    /// Serializes this [`RoomSnapshot`] into a compact JSON string.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        // PANIC: Serializing plain strings and booleans never fails.
+        #[allow(clippy::unwrap_used)]
+        serde_json::to_string(self).unwrap()
+    }
+}
+
+impl FromStr for RoomSnapshot {
+    type Err = Traced<RoomSnapshotParseError>;
+
+    fn from_str(json: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(json)
+            .map_err(|e| tracerr::new!(RoomSnapshotParseError(e.to_string())))
+    }
+}
+
+/// Error of parsing a [`RoomSnapshot`] from a JSON string.
+#[derive(Caused, Clone, Debug, Display, Eq, PartialEq)]
+#[cause(error = platform::Error)]
+#[display(fmt = "Failed to parse RoomSnapshot: {}", _0)]
+pub struct RoomSnapshotParseError(String);
+
+/// Current state of the local media of a single [`MediaKind`] and
+/// [`MediaSourceKind`] in a [`RoomMediaState`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize)]
+pub struct SendMediaState {
+    /// Indicator whether publishing of the media is enabled.
+    pub enabled: bool,
+
+    /// Indicator whether the media is muted.
+    pub muted: bool,
+}
+
+/// Current media states of a [`Room`], consistent with the ones reported by
+/// its callbacks.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+pub struct RoomMediaState {
+    /// [`SendMediaState`] of the local device audio.
+    pub send_device_audio: SendMediaState,
+
+    /// [`SendMediaState`] of the local display audio.
+    ///
+    /// Always equals to the [`RoomMediaState::send_device_audio`], since the
+    /// audio is published regardless of its [`MediaSourceKind`].
+    pub send_display_audio: SendMediaState,
+
+    /// [`SendMediaState`] of the local device video.
+    pub send_device_video: SendMediaState,
+
+    /// [`SendMediaState`] of the local display video.
+    pub send_display_video: SendMediaState,
+
+    /// Indicator whether receiving of the remote audio is enabled.
+    pub recv_audio: bool,
+
+    /// Indicator whether receiving of the remote device video is enabled.
+    pub recv_device_video: bool,
+
+    /// Indicator whether receiving of the remote display video is enabled.
+    pub recv_display_video: bool,
+}
+
+impl RoomMediaState {
+    /// Returns [`SendMediaState`] of the local media of the provided
+    /// [`MediaKind`] and [`MediaSourceKind`].
+    #[must_use]
+    pub const fn send(
+        &self,
+        kind: MediaKind,
+        source: MediaSourceKind,
+    ) -> SendMediaState {
+        match (kind, source) {
+            (MediaKind::Audio, MediaSourceKind::Device) => {
+                self.send_device_audio
+            }
+            (MediaKind::Audio, MediaSourceKind::Display) => {
+                self.send_display_audio
+            }
+            (MediaKind::Video, MediaSourceKind::Device) => {
+                self.send_device_video
+            }
+            (MediaKind::Video, MediaSourceKind::Display) => {
+                self.send_display_video
+            }
+        }
+    }
+
+    /// Indicates whether receiving of the remote media of the provided
+    /// [`MediaKind`] and [`MediaSourceKind`] is enabled.
+    #[must_use]
+    pub const fn recv(&self, kind: MediaKind, source: MediaSourceKind) -> bool {
+        match (kind, source) {
+            (MediaKind::Audio, _) => self.recv_audio,
+            (MediaKind::Video, MediaSourceKind::Device) => {
+                self.recv_device_video
+            }
+            (MediaKind::Video, MediaSourceKind::Display) => {
+                self.recv_display_video
+            }
+        }
+    }
+
+    /// Serializes this [`RoomMediaState`] into a compact JSON string.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        // PANIC: Serializing plain booleans never fails.
+        #[allow(clippy::unwrap_used)]
+        serde_json::to_string(self).unwrap()
+    }
 }
 
 /// Errors occurring in [`RoomHandle::join()`] method.
 #[derive(Caused, Clone, Debug, Display, From)]
 #[cause(error = platform::Error)]
 pub enum RoomJoinError {
-    /// [`RoomHandle`]'s [`Weak`] pointer is detached.
-    #[display(fmt = "RoomHandle is in detached state")]
-    Detached,
+    /// [`Room`] is closed, so cannot be joined anymore.
+    #[display(
+        fmt = "Room is closed, so cannot be joined anymore: a new one should \
+               be created via `Jason.init_room()`"
+    )]
+    RoomClosed,
 
     /// Returned if the mandatory callback wasn't set.
     #[display(fmt = "`{}` callback isn't set.", _0)]
@@ -129,9 +351,67 @@ pub enum RoomJoinError {
     #[display(fmt = "Failed to parse ConnectionInfo: {}", _0)]
     ConnectionInfoParse(ConnectionInfoParseError),
 
-    /// [`RpcSession`] returned [`SessionError`].
-    #[display(fmt = "WebSocketSession error occurred: {}", _0)]
-    SessionError(#[cause] SessionError),
+    /// [`RpcSession`] returned [`SessionError`] in the provided
+    /// [`JoinPhase`].
+    #[display(
+        fmt = "WebSocketSession error occurred in `{}` join phase: {}. \
+               Recent events: {}",
+        phase,
+        err,
+        events
+    )]
+    #[from(ignore)]
+    SessionError {
+        /// [`SessionError`] returned by [`RpcSession`].
+        #[cause]
+        err: SessionError,
+
+        /// [`JoinPhase`] the [`SessionError`] has occurred in.
+        phase: JoinPhase,
+
+        /// JSON dump of the last [`EventLog`] entries.
+        events: String,
+    },
+
+    /// [RTCPeerConnection][1] cannot be created on the current platform.
+    ///
+    /// [1]: https://w3.org/TR/webrtc#dom-rtcpeerconnection
+    #[display(fmt = "WebRTC is unavailable on the current platform")]
+    WebRtcUnavailable,
+}
+
+/// [`Shared`] [`Future`] of joining a [`Room`], resolving to the same outcome
+/// for all the concurrent [`RoomHandle::join()`] calls.
+///
+/// [`Shared`]: future::Shared
+type JoiningFuture =
+    future::Shared<LocalBoxFuture<'static, Result<(), Traced<RoomJoinError>>>>;
+
+/// State of joining a [`Room`].
+#[derive(Clone)]
+enum JoinState {
+    /// [`Room`] hasn't been joined yet, or its last joining has failed.
+    Idle,
+
+    /// [`Room`] is being joined.
+    Joining(JoiningFuture),
+
+    /// [`Room`] has been joined.
+    Joined,
+
+    /// [`Room`] is closed, so cannot be joined anymore.
+    Closed,
+}
+
+impl fmt::Debug for JoinState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Idle => write!(f, "Idle"),
+            Self::Joining(_) => write!(f, "Joining"),
+            Self::Joined => write!(f, "Joined"),
+            Self::Closed => write!(f, "Closed"),
+        }
+    }
 }
 
 /// Error of [`RoomHandle`]'s [`Weak`] pointer being detached.
@@ -139,6 +419,23 @@ pub enum RoomJoinError {
 #[cause(error = platform::Error)]
 pub struct HandleDetachedError;
 
+/// Errors occurring in [`RoomHandle::set_rtc_configuration()`] method.
+#[derive(Caused, Clone, Copy, Debug, Display, Eq, PartialEq)]
+#[cause(error = platform::Error)]
+pub enum SetRtcConfigurationError {
+    /// [`RoomHandle`]'s [`Weak`] pointer is detached.
+    #[display(fmt = "RoomHandle is in detached state")]
+    Detached,
+
+    /// [`PeerConnection`]s of the [`Room`] are created already, so their
+    /// configuration cannot be changed anymore.
+    #[display(
+        fmt = "RTCConfiguration cannot be changed after PeerConnections are \
+               created"
+    )]
+    PeersExist,
+}
+
 /// Errors occurring when changing media state of [`Sender`]s and [`Receiver`]s.
 ///
 /// [`Sender`]: peer::media::Sender
@@ -179,6 +476,35 @@ pub enum ChangeMediaStateError {
         _0
     )]
     TransitionIntoOppositeState(MediaState),
+
+    /// [`MediaState`] of a [`Sender`] has been forced by a media server (by a
+    /// moderator, for example), which rejects its change.
+    ///
+    /// [`Sender`]: peer::media::Sender
+    #[display(
+        fmt = "MediaState of Sender is forced ({}) by a media server, so it \
+               cannot be changed",
+        _0
+    )]
+    #[from(ignore)]
+    ForbiddenByServer(MediaState),
+}
+
+impl ChangeMediaStateError {
+    /// Returns a [`ChangeMediaStateError`] for the provided [`TransceiverSide`]
+    /// stabilized in the provided `state`, opposite to the requested one.
+    ///
+    /// [`TransceiverSide`]: peer::TransceiverSide
+    fn opposite_state(
+        transceiver: &dyn peer::TransceiverSide,
+        state: MediaState,
+    ) -> Self {
+        if transceiver.is_media_state_forced(state) {
+            Self::ForbiddenByServer(state)
+        } else {
+            Self::TransitionIntoOppositeState(state)
+        }
+    }
 }
 
 impl From<GetLocalTracksError> for ChangeMediaStateError {
@@ -224,6 +550,26 @@ macro_rules! upgrade_inner {
     };
 }
 
+/// Resolves the provided [`MediaState`] change, or fails with a
+/// [`ChangeMediaStateError::Detached`] once the provided `closed` [`Future`]
+/// resolves before it.
+fn abandon_on_close<F>(
+    closed: LocalBoxFuture<'static, Result<(), DroppedError>>,
+    change: F,
+) -> LocalBoxFuture<'static, ChangeMediaStateResult>
+where
+    F: Future<Output = ChangeMediaStateResult> + 'static,
+{
+    Box::pin(
+        future::select(Box::pin(change), closed).map(|res| match res {
+            future::Either::Left((res, _)) => res,
+            future::Either::Right(_) => {
+                Err(tracerr::new!(ChangeMediaStateError::Detached))
+            }
+        }),
+    )
+}
+
 /// External handle to a [`Room`].
 #[derive(Clone, Debug)]
 pub struct RoomHandle(Weak<InnerRoom>);
@@ -239,14 +585,76 @@ impl RoomHandle {
     /// Establishes connection with a media server (if it doesn't exist
     /// already ).
     ///
+    /// Calling it again while this [`Room`] is being joined resolves to the
+    /// same outcome as the pending call (ignoring the provided `url`), and
+    /// resolves immediately once this [`Room`] is joined already.
+    ///
     /// # Errors
     ///
-    /// See [`RoomJoinError`] for details.
+    /// With [`RoomJoinError::RoomClosed`] if this [`Room`] is closed.
+    ///
+    /// See [`RoomJoinError`] for other details.
     pub async fn join(&self, url: String) -> Result<(), Traced<RoomJoinError>> {
+        let joining = {
+            let inner = self
+                .0
+                .upgrade()
+                .ok_or_else(|| tracerr::new!(RoomJoinError::RoomClosed))?;
+            let state = inner.join_state.borrow().clone();
+            match state {
+                JoinState::Closed => {
+                    return Err(tracerr::new!(RoomJoinError::RoomClosed));
+                }
+                JoinState::Joined if inner.rpc.is_opened() => return Ok(()),
+                JoinState::Joining(joining) => joining,
+                JoinState::Idle | JoinState::Joined => {
+                    let joining =
+                        self.clone().join_once(url).boxed_local().shared();
+                    drop(
+                        inner
+                            .join_state
+                            .replace(JoinState::Joining(joining.clone())),
+                    );
+                    joining
+                }
+            }
+        };
+
+        joining.await
+    }
+
+    /// Joins the [`Room`] as described in the [`RoomHandle::join()`], and
+    /// updates its [`JoinState`] with the outcome.
+    async fn join_once(self, url: String) -> Result<(), Traced<RoomJoinError>> {
+        let res = self.connect(url).await;
+        if let Some(inner) = self.0.upgrade() {
+            let mut state = inner.join_state.borrow_mut();
+            if !matches!(*state, JoinState::Closed) {
+                *state = if res.is_ok() {
+                    JoinState::Joined
+                } else {
+                    JoinState::Idle
+                };
+            }
+        }
+        res
+    }
+
+    /// Connects to a media server and authorizes in the [`Room`] with the
+    /// provided authorization `url`.
+    ///
+    /// # Errors
+    ///
+    /// See [`RoomJoinError`] for details.
+    async fn connect(&self, url: String) -> Result<(), Traced<RoomJoinError>> {
         let inner = self
             .0
             .upgrade()
-            .ok_or_else(|| tracerr::new!(RoomJoinError::Detached))?;
+            .ok_or_else(|| tracerr::new!(RoomJoinError::RoomClosed))?;
+
+        if !platform::webrtc_available().peer_connection {
+            return Err(tracerr::new!(RoomJoinError::WebRtcUnavailable));
+        }
 
         let connection_info: ConnectionInfo =
             url.parse().map_err(tracerr::map_from_and_wrap!())?;
@@ -263,17 +671,147 @@ impl RoomHandle {
             )));
         }
 
+        let progress = Rc::clone(inner.peers.join_progress());
+        if progress.current() < Some(JoinPhase::Authorized) {
+            progress.reset();
+        }
+        progress.advance(JoinPhase::ConnectingSignaling);
+        inner
+            .events
+            .record(EventKind::Join, &JoinPhase::ConnectingSignaling);
+
+        drop(inner.connection_info.replace(Some(connection_info.clone())));
         Rc::clone(&inner.rpc)
             .connect(connection_info)
             .await
-            .map_err(tracerr::map_from_and_wrap!( => RoomJoinError))?;
+            .map_err(|e| {
+                let phase = progress
+                    .current()
+                    .unwrap_or(JoinPhase::ConnectingSignaling);
+                let (err, trace) = e.split();
+                inner
+                    .events
+                    .record(EventKind::Error, &format_args!("{err}"));
+                let events =
+                    inner.events.dump(Some(EventLog::ERROR_DUMP_LIMIT));
+                let err = RoomJoinError::SessionError { err, phase, events };
+                Traced::compose(err, trace)
+            })
+            .map_err(tracerr::wrap!())?;
+        progress.advance(JoinPhase::Authorized);
+        inner.events.record(EventKind::Join, &JoinPhase::Authorized);
+        inner.session_metrics.session_started();
+        inner.record_session_metrics();
 
         Ok(())
     }
 
+    /// Same as [`RoomHandle::join()`], but can be aborted via an
+    /// [`AbortHandle`] of the provided [`AbortRegistration`].
+    ///
+    /// Aborting closes this [`Room`] with a [`ClientDisconnect::RoomClosed`]
+    /// reason, so its RPC session is finished and its transport connection is
+    /// released.
+    ///
+    /// # Errors
+    ///
+    /// With [`AbortableError::Aborted`] if joining is aborted.
+    ///
+    /// With [`AbortableError::Failed`] if [`RoomHandle::join()`] fails.
+    ///
+    /// [`AbortHandle`]: future::AbortHandle
+    pub async fn join_abortable(
+        &self,
+        url: String,
+        abort: AbortRegistration,
+    ) -> Result<(), AbortableError<Traced<RoomJoinError>>> {
+        let res = utils::abortable(self.join(url), abort).await;
+        if matches!(res, Err(AbortableError::Aborted)) {
+            if let Some(inner) = self.0.upgrade() {
+                inner.rpc.close_with_reason(ClientDisconnect::RoomClosed);
+            }
+        }
+        res
+    }
+
+    /// Sets callback, invoked with a [`JoinProgressUpdate`] whenever this
+    /// [`Room`] reaches the next [`JoinPhase`] while joining.
+    ///
+    /// The callback is immediately invoked with all the [`JoinPhase`]s
+    /// reached already.
+    ///
+    /// # Errors
+    ///
+    /// See [`HandleDetachedError`] for details.
+    pub fn on_join_progress<F>(
+        &self,
+        f: F,
+    ) -> Result<(), Traced<HandleDetachedError>>
+    where
+        F: FnMut(JoinProgressUpdate) + 'static,
+    {
+        upgrade_inner!(self.0)
+            .map(|inner| inner.peers.join_progress().on_progress(f))
+    }
+
+    /// Returns JSON array of the last `limit` (or all if [`None`]) lifecycle
+    /// events happened in this [`Room`], with SDP bodies, credentials and
+    /// tokens redacted.
+    ///
+    /// # Errors
+    ///
+    /// See [`HandleDetachedError`] for details.
+    pub fn dump_events(
+        &self,
+        limit: Option<usize>,
+    ) -> Result<String, Traced<HandleDetachedError>> {
+        upgrade_inner!(self.0).map(|inner| inner.events.dump(limit))
+    }
+
+    /// Returns [`SessionMetrics`] of this [`Room`] accumulated across all its
+    /// reconnects so far.
+    ///
+    /// # Errors
+    ///
+    /// See [`HandleDetachedError`] for details.
+    pub fn session_metrics(
+        &self,
+    ) -> Result<SessionMetrics, Traced<HandleDetachedError>> {
+        upgrade_inner!(self.0).map(|inner| inner.session_metrics.metrics())
+    }
+
+    /// Sets the maximum number of the lifecycle events retained by this
+    /// [`Room`] for the [`RoomHandle::dump_events()`] (the
+    /// [`EventLog::DEFAULT_CAPACITY`] by default).
+    ///
+    /// # Errors
+    ///
+    /// See [`HandleDetachedError`] for details.
+    pub fn set_event_log_capacity(
+        &self,
+        capacity: usize,
+    ) -> Result<(), Traced<HandleDetachedError>> {
+        upgrade_inner!(self.0).map(|inner| inner.events.set_capacity(capacity))
+    }
+
+    /// Returns an estimate of the memory (in bytes) held by the lifecycle
+    /// events retained by this [`Room`] for the [`RoomHandle::dump_events()`].
+    ///
+    /// # Errors
+    ///
+    /// See [`HandleDetachedError`] for details.
+    pub fn event_log_memory_usage(
+        &self,
+    ) -> Result<usize, Traced<HandleDetachedError>> {
+        upgrade_inner!(self.0).map(|inner| inner.events.memory_usage_estimate())
+    }
+
     /// Sets callback, invoked when a new [`Connection`] with some remote `Peer`
     /// is established.
     ///
+    /// The callback is immediately invoked for all the already established
+    /// [`Connection`]s in the order of their creation.
+    ///
     /// # Errors
     ///
     /// See [`HandleDetachedError`] for details.
@@ -287,6 +825,65 @@ impl RoomHandle {
             .map(|inner| inner.connections.on_new_connection(f))
     }
 
+    /// Returns new [`ConnectionHandle`]s to all the currently established
+    /// [`Connection`]s in the order of their creation.
+    ///
+    /// # Errors
+    ///
+    /// See [`HandleDetachedError`] for details.
+    pub fn connections(
+        &self,
+    ) -> Result<Vec<ConnectionHandle>, Traced<HandleDetachedError>> {
+        upgrade_inner!(self.0).map(|inner| {
+            inner
+                .connections
+                .all()
+                .iter()
+                .map(Connection::new_handle)
+                .collect()
+        })
+    }
+
+    /// Returns a new [`ConnectionHandle`] to the currently established
+    /// [`Connection`] with the provided remote `Member`, if any.
+    ///
+    /// # Errors
+    ///
+    /// See [`HandleDetachedError`] for details.
+    pub fn connection(
+        &self,
+        remote_member_id: &MemberId,
+    ) -> Result<Option<ConnectionHandle>, Traced<HandleDetachedError>> {
+        upgrade_inner!(self.0).map(|inner| {
+            inner
+                .connections
+                .get(remote_member_id)
+                .map(|conn| conn.new_handle())
+        })
+    }
+
+    /// Sets callback, invoked whenever media of any remote `Member` is
+    /// enabled, disabled, muted or unmuted by this `Member`.
+    ///
+    /// The callback is immediately invoked with the current
+    /// [`MemberMediaState`]s of all the remote `Member`s' media.
+    ///
+    /// # Errors
+    ///
+    /// See [`HandleDetachedError`] for details.
+    ///
+    /// [`MemberMediaState`]: crate::connection::MemberMediaState
+    pub fn on_member_media_state_changed<F>(
+        &self,
+        f: F,
+    ) -> Result<(), Traced<HandleDetachedError>>
+    where
+        F: FnMut(MemberMediaStateChange) + 'static,
+    {
+        upgrade_inner!(self.0)
+            .map(|inner| inner.connections.on_member_media_state_changed(f))
+    }
+
     /// Sets `on_close` callback, invoked on this [`Room`] close, providing a
     /// [`RoomCloseReason`].
     ///
@@ -309,6 +906,9 @@ impl RoomHandle {
     /// 2. `enable_audio`/`enable_video` is called.
     /// 3. [`MediaStreamSettings`] updated via `set_local_media_settings`.
     ///
+    /// The callback is immediately invoked for all the alive [`local::Track`]s
+    /// added to this [`Room`] already, in the order of their addition.
+    ///
     /// # Errors
     ///
     /// See [`HandleDetachedError`] for details.
@@ -316,7 +916,7 @@ impl RoomHandle {
         &self,
         f: platform::Function<api::LocalMediaTrack>,
     ) -> Result<(), Traced<HandleDetachedError>> {
-        upgrade_inner!(self.0).map(|inner| inner.on_local_track.set_func(f))
+        upgrade_inner!(self.0).map(|inner| inner.set_on_local_track(f))
     }
 
     /// Sets `on_failed_local_media` callback, invoked on a local media
@@ -347,6 +947,117 @@ impl RoomHandle {
         upgrade_inner!(self.0).map(|inner| inner.on_connection_loss.set_func(f))
     }
 
+    /// Sets `on_adaptation` callback, invoked whenever outbound video is
+    /// adapted according to the [`peer::AdaptivePolicy`] set via
+    /// [`RoomHandle::set_adaptive_policy()`].
+    ///
+    /// # Errors
+    ///
+    /// See [`HandleDetachedError`] for details.
+    #[cfg_attr(not(target_family = "wasm"), allow(unused_qualifications))]
+    pub fn on_adaptation(
+        &self,
+        f: platform::Function<api::Adaptation>,
+    ) -> Result<(), Traced<HandleDetachedError>> {
+        upgrade_inner!(self.0).map(|inner| inner.on_adaptation.set_func(f))
+    }
+
+    /// Sets `on_codec_fallback` callback, invoked whenever an encoder or a
+    /// decoder of media in this [`Room`] falls back from a hardware
+    /// implementation to a software one.
+    ///
+    /// # Errors
+    ///
+    /// See [`HandleDetachedError`] for details.
+    #[cfg_attr(not(target_family = "wasm"), allow(unused_qualifications))]
+    pub fn on_codec_fallback(
+        &self,
+        f: platform::Function<api::CodecFallback>,
+    ) -> Result<(), Traced<HandleDetachedError>> {
+        upgrade_inner!(self.0).map(|inner| inner.on_codec_fallback.set_func(f))
+    }
+
+    /// Sets `on_error` callback, invoked with a [`NonFatalError`] whenever an
+    /// error happens in this [`Room`] without breaking it.
+    ///
+    /// Identical errors are reported at most once per
+    /// [`ErrorRateLimiter::INTERVAL`].
+    ///
+    /// # Errors
+    ///
+    /// See [`HandleDetachedError`] for details.
+    #[cfg_attr(not(target_family = "wasm"), allow(unused_qualifications))]
+    pub fn on_error(
+        &self,
+        f: platform::Function<api::NonFatalError>,
+    ) -> Result<(), Traced<HandleDetachedError>> {
+        upgrade_inner!(self.0).map(|inner| inner.on_error.set_func(f))
+    }
+
+    /// Sets `on_forced_media_state` callback, invoked whenever a media server
+    /// forces a [`MediaState`] of the local media (a moderator mutes it, for
+    /// example), rather than it's changed via this [`RoomHandle`].
+    ///
+    /// # Errors
+    ///
+    /// See [`HandleDetachedError`] for details.
+    #[cfg_attr(not(target_family = "wasm"), allow(unused_qualifications))]
+    pub fn on_forced_media_state(
+        &self,
+        f: platform::Function<api::ForcedMediaState>,
+    ) -> Result<(), Traced<HandleDetachedError>> {
+        upgrade_inner!(self.0)
+            .map(|inner| inner.on_forced_media_state.set_func(f))
+    }
+
+    /// Sets `on_sdp` callback, invoked with a [`peer::SdpEvent`] for every
+    /// SDP description successfully applied to a [`PeerConnection`] of this
+    /// [`Room`], for debugging purposes.
+    ///
+    /// If `redact` is `true`, then ICE credentials and candidate addresses are
+    /// masked in the provided SDPs (see [`peer::redact_sdp()`]).
+    ///
+    /// # Errors
+    ///
+    /// See [`HandleDetachedError`] for details.
+    #[cfg_attr(not(target_family = "wasm"), allow(unused_qualifications))]
+    pub fn on_sdp(
+        &self,
+        f: platform::Function<api::SdpEvent>,
+        redact: bool,
+    ) -> Result<(), Traced<HandleDetachedError>> {
+        upgrade_inner!(self.0).map(|inner| {
+            inner.redact_sdp.set(redact);
+            inner.on_sdp.set_func(f);
+        })
+    }
+
+    /// Returns [`TransceiverInfo`]s of all the negotiated transceivers in this
+    /// [`Room`], for debugging purposes.
+    ///
+    /// Resolves to an empty [`Vec`] if there are no negotiated transceivers
+    /// yet.
+    ///
+    /// # Errors
+    ///
+    /// See [`HandleDetachedError`] for details.
+    pub fn transceivers_info(
+        &self,
+    ) -> Result<
+        impl Future<Output = Vec<TransceiverInfo>> + 'static,
+        Traced<HandleDetachedError>,
+    > {
+        let inner = upgrade_inner!(self.0)?;
+        let infos = future::join_all(
+            inner
+                .peers
+                .get_all()
+                .into_iter()
+                .map(|peer| peer.get_transceivers_info()),
+        );
+        Ok(infos.map(|infos| infos.into_iter().flatten().collect()))
+    }
+
     /// Updates this [`Room`]s [`MediaStreamSettings`]. This affects all
     /// [`PeerConnection`]s in this [`Room`]. If [`MediaStreamSettings`] is
     /// configured for some [`Room`], then this [`Room`] can only send media
@@ -370,33 +1081,267 @@ impl RoomHandle {
     /// If recovering from fail state isn't possible then affected media types
     /// will be disabled.
     ///
+    /// If `seamless` is set to `true` along with the `stop_first`, then only
+    /// the affected [`local::Track`]s recaptured from the same device are
+    /// dropped first (see [`MediaStreamSettings::stop_first_kinds()`]). The
+    /// other ones are replaced in the senders by the newly captured ones and
+    /// dropped only then, so no gap in the sent media occurs.
+    ///
     /// # Errors
     ///
     /// With [`ConstraintsUpdateError::Errored`] if and error has occurred while
     /// applying the provided [`MediaStreamSettings`].
     ///
+    /// With [`ConstraintsUpdateError::Errored`] wrapping an
+    /// [`InitLocalTracksError::InvalidConstraints`] if the provided
+    /// [`MediaStreamSettings`] don't pass [validation][2], in which case
+    /// nothing is applied.
+    ///
     /// With [`ConstraintsUpdateError::Recovered`] if [`MediaStreamSettings`]
     /// are rolled-back because an error had occurred.
     ///
     /// With [`ConstraintsUpdateError::RecoverFailed`] if
     /// [`MediaStreamSettings`] rollback failed.
     ///
+    /// With [`ConstraintsUpdateError::Errored`] wrapping a
+    /// [`ChangeMediaStateError::Detached`] if the [`Room`] is closed before
+    /// the provided [`MediaStreamSettings`] are applied.
+    ///
     /// [1]: https://tinyurl.com/w3-streams#dom-mediadevices-getusermedia
+    /// [2]: MediaStreamSettings::validate
     pub async fn set_local_media_settings(
         &self,
         settings: MediaStreamSettings,
         stop_first: bool,
         rollback_on_fail: bool,
+        seamless: bool,
     ) -> Result<(), ConstraintsUpdateError> {
         let inner = (self.0).upgrade().ok_or_else(|| {
             ConstraintsUpdateError::errored(tracerr::new!(
                 ChangeMediaStateError::Detached
             ))
         })?;
+        settings.validate().map_err(|e| {
+            ConstraintsUpdateError::errored(tracerr::new!(
+                ChangeMediaStateError::from(InitLocalTracksError::from(e))
+            ))
+        })?;
+
+        inner
+            .unless_closed(inner.set_local_media_settings(
+                settings,
+                stop_first,
+                rollback_on_fail,
+                seamless,
+            ))
+            .await
+            .unwrap_or_else(|| {
+                Err(ConstraintsUpdateError::errored(tracerr::new!(
+                    ChangeMediaStateError::Detached
+                )))
+            })
+    }
+
+    /// Same as [`RoomHandle::set_local_media_settings()`], but can be aborted
+    /// via an [`AbortHandle`] of the provided [`AbortRegistration`].
+    ///
+    /// Aborting stops the [`local::Track`]s being acquired at the moment, while
+    /// the ones already inserted into [`PeerConnection`]s are kept.
+    ///
+    /// # Errors
+    ///
+    /// With [`AbortableError::Aborted`] if the update is aborted.
+    ///
+    /// With [`AbortableError::Failed`] if
+    /// [`RoomHandle::set_local_media_settings()`] fails.
+    ///
+    /// [`AbortHandle`]: future::AbortHandle
+    pub async fn set_local_media_settings_abortable(
+        &self,
+        settings: MediaStreamSettings,
+        stop_first: bool,
+        rollback_on_fail: bool,
+        seamless: bool,
+        abort: AbortRegistration,
+    ) -> Result<(), AbortableError<ConstraintsUpdateError>> {
+        utils::abortable(
+            self.set_local_media_settings(
+                settings,
+                stop_first,
+                rollback_on_fail,
+                seamless,
+            ),
+            abort,
+        )
+        .await
+    }
+
+    /// Sets the default [`MediaStreamSettings`] of this [`Room`], applied to
+    /// all the subsequent local media acquisitions.
+    ///
+    /// The constraints set via [`RoomHandle::set_local_media_settings()`] win
+    /// over the default ones field-by-field. Device IDs of the default
+    /// [`MediaStreamSettings`] are applied as ideal constraints, so a
+    /// disappeared default device doesn't break media acquisition.
+    ///
+    /// Doesn't affect already acquired [`local::Track`]s.
+    ///
+    /// # Errors
+    ///
+    /// See [`HandleDetachedError`] for details.
+    pub fn set_default_media_settings(
+        &self,
+        settings: MediaStreamSettings,
+    ) -> Result<(), Traced<HandleDetachedError>> {
+        upgrade_inner!(self.0)
+            .map(|inner| inner.send_constraints.set_defaults(settings))
+    }
+
+    /// Returns the effective [`MediaStreamSettings`] of this [`Room`], being
+    /// the ones set via [`RoomHandle::set_local_media_settings()`] merged with
+    /// the ones set via [`RoomHandle::set_default_media_settings()`].
+    ///
+    /// # Errors
+    ///
+    /// See [`HandleDetachedError`] for details.
+    pub fn effective_media_settings(
+        &self,
+    ) -> Result<MediaStreamSettings, Traced<HandleDetachedError>> {
+        upgrade_inner!(self.0).map(|inner| inner.send_constraints.effective())
+    }
+
+    /// Exports a [`RoomSnapshot`] of the media states intended in this
+    /// [`Room`], to be restored via [`Jason::restore_room()`] after a page
+    /// refresh.
+    ///
+    /// # Errors
+    ///
+    /// See [`HandleDetachedError`] for details.
+    ///
+    /// [`Jason::restore_room()`]: crate::jason::Jason::restore_room
+    pub fn export_state(
+        &self,
+    ) -> Result<RoomSnapshot, Traced<HandleDetachedError>> {
+        upgrade_inner!(self.0).map(|inner| inner.snapshot())
+    }
+
+    /// Returns the current [`RoomMediaState`] of this [`Room`].
+    ///
+    /// It's updated synchronously before any callback reporting the change is
+    /// invoked, so is always consistent with the callbacks.
+    ///
+    /// # Errors
+    ///
+    /// See [`HandleDetachedError`] for details.
+    pub fn media_state(
+        &self,
+    ) -> Result<RoomMediaState, Traced<HandleDetachedError>> {
+        upgrade_inner!(self.0).map(|inner| inner.media_state())
+    }
+
+    /// Sets the window during which simultaneous track changes (enabling,
+    /// disabling, muting or unmuting) are collected into a single
+    /// renegotiation.
+    ///
+    /// Defaults to [`PeerConnection::DEFAULT_NEGOTIATION_DEBOUNCE`]. Doesn't
+    /// delay the initial negotiation of a [`PeerConnection`].
+    ///
+    /// # Errors
+    ///
+    /// See [`HandleDetachedError`] for details.
+    pub fn set_negotiation_debounce(
+        &self,
+        debounce: Duration,
+    ) -> Result<(), Traced<HandleDetachedError>> {
+        upgrade_inner!(self.0)
+            .map(|inner| inner.peers.set_negotiation_debounce(debounce))
+    }
+
+    /// Enables or disables the periodic stats collection of all the
+    /// [`PeerConnection`]s of this [`Room`], setting its interval (1 second
+    /// if [`None`]).
+    ///
+    /// While disabled, no stats are scraped at all: stats-driven callbacks
+    /// stop firing and all the stats-driven accounting is frozen. Cumulative
+    /// counters (like [`peer::TrafficTotals`]) continue from their last values
+    /// once re-enabled, with the gap flagged in the
+    /// [`peer::TrafficTotals::collection_gaps`].
+    ///
+    /// # Errors
+    ///
+    /// See [`HandleDetachedError`] for details.
+    pub fn set_stats_collection(
+        &self,
+        enabled: bool,
+        interval: Option<Duration>,
+    ) -> Result<(), Traced<HandleDetachedError>> {
+        upgrade_inner!(self.0)
+            .map(|inner| inner.peers.set_stats_collection(enabled, interval))
+    }
+
+    /// Sets [`peer::AdaptivePolicy`] of the outbound video of all the
+    /// [`PeerConnection`]s of this [`Room`].
+    ///
+    /// Setting [`peer::AdaptivePolicy::Disabled`] restores the outbound video
+    /// degraded by the previous [`peer::AdaptivePolicy`].
+    ///
+    /// # Errors
+    ///
+    /// See [`HandleDetachedError`] for details.
+    pub fn set_adaptive_policy(
+        &self,
+        policy: peer::AdaptivePolicy,
+    ) -> Result<(), Traced<HandleDetachedError>> {
+        upgrade_inner!(self.0)
+            .map(|inner| inner.peers.set_adaptive_policy(policy))
+    }
+
+    /// Sets [`platform::IceCandidateFilter`] applied to the locally gathered
+    /// ICE candidates before sending them to a media server, and to the
+    /// remote ones before adding them to [`PeerConnection`]s of this
+    /// [`Room`].
+    ///
+    /// Should be called before [`RoomHandle::join()`], since the candidates
+    /// exchanged already aren't affected.
+    ///
+    /// # Errors
+    ///
+    /// See [`HandleDetachedError`] for details.
+    pub fn set_ice_candidate_filter(
+        &self,
+        filter: platform::IceCandidateFilter,
+    ) -> Result<(), Traced<HandleDetachedError>> {
+        upgrade_inner!(self.0).map(|inner| {
+            *inner.ice_candidate_filter.borrow_mut() = filter;
+        })
+    }
 
-        inner
-            .set_local_media_settings(settings, stop_first, rollback_on_fail)
-            .await
+    /// Sets [`platform::RtcConfiguration`] of the [`PeerConnection`]s of this
+    /// [`Room`].
+    ///
+    /// Should be called before [`RoomHandle::join()`], since the
+    /// [`PeerConnection`]s once created cannot be reconfigured.
+    ///
+    /// # Errors
+    ///
+    /// With [`SetRtcConfigurationError::Detached`] if an inner [`Weak`]
+    /// pointer upgrade fails.
+    ///
+    /// With [`SetRtcConfigurationError::PeersExist`] if any
+    /// [`PeerConnection`] of this [`Room`] has been created already.
+    pub fn set_rtc_configuration(
+        &self,
+        config: platform::RtcConfiguration,
+    ) -> Result<(), Traced<SetRtcConfigurationError>> {
+        let inner = self
+            .0
+            .upgrade()
+            .ok_or_else(|| tracerr::new!(SetRtcConfigurationError::Detached))?;
+        if !inner.peers.is_empty() {
+            return Err(tracerr::new!(SetRtcConfigurationError::PeersExist));
+        }
+        inner.peers.set_rtc_configuration(config);
+        Ok(())
     }
 
     /// Changes [`MediaState`] of the provided [`MediaKind`], [`TrackDirection`]
@@ -404,6 +1349,13 @@ impl RoomHandle {
     ///
     /// Helper function for all the exported mute/unmute/enable/disable
     /// audio/video send/receive methods.
+    ///
+    /// Resolves with a [`MediaStateOutcome::Superseded`] instead of failing
+    /// if a newer opposite change has been requested before this one is
+    /// approved by a media server.
+    ///
+    /// Fails with a [`ChangeMediaStateError::Detached`] once the [`Room`] is
+    /// closed, abandoning the change.
     fn change_media_state<S>(
         &self,
         new_state: S,
@@ -423,6 +1375,13 @@ impl RoomHandle {
         };
 
         let new_state = new_state.into();
+        let key = MediaStateKey {
+            is_mute: matches!(new_state, MediaState::Mute(_)),
+            kind,
+            direction,
+            source_kind,
+        };
+        let generation = inner.start_media_state_change(key, new_state);
         let source_kind = source_kind.map(Into::into);
 
         inner.set_constraints_media_state(
@@ -432,7 +1391,8 @@ impl RoomHandle {
             source_kind,
         );
 
-        Box::pin(async move {
+        let closed = inner.closed.when_eq(true);
+        abandon_on_close(closed, async move {
             let direction_send = matches!(direction, TrackDirection::Send);
             let enabling = matches!(
                 new_state,
@@ -448,20 +1408,25 @@ impl RoomHandle {
             // reused without additional requests.
             let tracks_handles;
             if direction_send && enabling {
-                tracks_handles = inner
-                    .get_local_tracks(kind, source_kind)
-                    .await
-                    .map_err(|e| {
-                        inner.set_constraints_media_state(
-                            new_state.opposite(),
-                            kind,
-                            direction,
-                            source_kind,
-                        );
-                        // false positive: output expression is not input one
-                        #[allow(clippy::redundant_closure_call)]
-                        tracerr::map_from_and_wrap!()(e)
-                    })?;
+                let tracks = inner.get_local_tracks(kind, source_kind).await;
+                // Newer opposite request owns the constraints now, so nothing
+                // should be rolled back.
+                if inner.is_media_state_change_superseded(
+                    key, generation, new_state,
+                ) {
+                    return Ok(MediaStateOutcome::Superseded);
+                }
+                tracks_handles = tracks.map_err(|e| {
+                    inner.set_constraints_media_state(
+                        new_state.opposite(),
+                        kind,
+                        direction,
+                        source_kind,
+                    );
+                    // false positive: output expression is not input one
+                    #[allow(clippy::redundant_closure_call)]
+                    tracerr::map_from_and_wrap!()(e)
+                })?;
                 if !inner.send_constraints.is_track_enabled(kind, source_kind) {
                     return Err(tracerr::new!(
                         ChangeMediaStateError::TransitionIntoOppositeState(
@@ -484,6 +1449,11 @@ impl RoomHandle {
                     .await
                     .map_err(tracerr::map_from_and_wrap!())
                 {
+                    if inner.is_media_state_change_superseded(
+                        key, generation, new_state,
+                    ) {
+                        return Ok(MediaStateOutcome::Superseded);
+                    }
                     if direction_send && enabling {
                         inner.set_constraints_media_state(
                             new_state.opposite(),
@@ -506,7 +1476,7 @@ impl RoomHandle {
             }
 
             drop(tracks_handles);
-            Ok(())
+            Ok(MediaStateOutcome::Applied)
         })
     }
 
@@ -517,9 +1487,8 @@ impl RoomHandle {
     /// With [`ChangeMediaStateError::Detached`] if an inner [`Weak`] pointer
     /// upgrade fails.
     ///
-    /// With [`ChangeMediaStateError::TransitionIntoOppositeState`] if
-    /// [`RoomHandle::unmute_audio()`] was called while muting or a media server
-    /// didn't approve this state transition.
+    /// With [`ChangeMediaStateError::TransitionIntoOppositeState`] if a media
+    /// server didn't approve this state transition.
     pub fn mute_audio(
         &self,
     ) -> impl Future<Output = ChangeMediaStateResult> + 'static {
@@ -539,9 +1508,11 @@ impl RoomHandle {
     /// With [`ChangeMediaStateError::Detached`] if an inner [`Weak`] pointer
     /// upgrade fails.
     ///
-    /// With [`ChangeMediaStateError::TransitionIntoOppositeState`] if
-    /// [`RoomHandle::mute_audio()`] was called while muting or a media server
-    /// didn't approve this state transition.
+    /// With [`ChangeMediaStateError::TransitionIntoOppositeState`] if a media
+    /// server didn't approve this state transition.
+    ///
+    /// With [`ChangeMediaStateError::ForbiddenByServer`] if a media server
+    /// rejected this state transition, since it has forced the current state.
     pub fn unmute_audio(
         &self,
     ) -> impl Future<Output = ChangeMediaStateResult> + 'static {
@@ -561,9 +1532,8 @@ impl RoomHandle {
     /// With [`ChangeMediaStateError::Detached`] if an inner [`Weak`] pointer
     /// upgrade fails.
     ///
-    /// With [`ChangeMediaStateError::TransitionIntoOppositeState`] if
-    /// [`RoomHandle::unmute_video()`] was called while muting or a media server
-    /// didn't approve this state transition.
+    /// With [`ChangeMediaStateError::TransitionIntoOppositeState`] if a media
+    /// server didn't approve this state transition.
     pub fn mute_video(
         &self,
         source_kind: Option<MediaSourceKind>,
@@ -584,9 +1554,11 @@ impl RoomHandle {
     /// With [`ChangeMediaStateError::Detached`] if an inner [`Weak`] pointer
     /// upgrade fails.
     ///
-    /// With [`ChangeMediaStateError::TransitionIntoOppositeState`] if
-    /// [`RoomHandle::mute_video()`] was called while muting or a media server
-    /// didn't approve this state transition.
+    /// With [`ChangeMediaStateError::TransitionIntoOppositeState`] if a media
+    /// server didn't approve this state transition.
+    ///
+    /// With [`ChangeMediaStateError::ForbiddenByServer`] if a media server
+    /// rejected this state transition, since it has forced the current state.
     pub fn unmute_video(
         &self,
         source_kind: Option<MediaSourceKind>,
@@ -610,8 +1582,7 @@ impl RoomHandle {
     /// With [`ChangeMediaStateError::ProhibitedState`] if audio track's sender
     /// is configured as `required`.
     ///
-    /// With [`ChangeMediaStateError::TransitionIntoOppositeState`] if
-    /// [`RoomHandle::enable_audio()`] was called while disabling or a media
+    /// With [`ChangeMediaStateError::TransitionIntoOppositeState`] if a media
     /// server didn't approve this state transition.
     pub fn disable_audio(
         &self,
@@ -632,10 +1603,12 @@ impl RoomHandle {
     /// With [`ChangeMediaStateError::Detached`] if an inner [`Weak`] pointer
     /// upgrade fails.
     ///
-    /// With [`ChangeMediaStateError::TransitionIntoOppositeState`] if
-    /// [`RoomHandle::disable_audio()`] was called while enabling or a media
+    /// With [`ChangeMediaStateError::TransitionIntoOppositeState`] if a media
     /// server didn't approve this state transition.
     ///
+    /// With [`ChangeMediaStateError::ForbiddenByServer`] if a media server
+    /// rejected this state transition, since it has forced the current state.
+    ///
     /// With [`ChangeMediaStateError::CouldNotGetLocalMedia`] if media
     /// acquisition request failed.
     pub fn enable_audio(
@@ -662,8 +1635,7 @@ impl RoomHandle {
     /// With [`ChangeMediaStateError::ProhibitedState`] video track's sender is
     /// configured as `required`.
     ///
-    /// With [`ChangeMediaStateError::TransitionIntoOppositeState`] if
-    /// [`RoomHandle::enable_video()`] was called while disabling or a media
+    /// With [`ChangeMediaStateError::TransitionIntoOppositeState`] if a media
     /// server didn't approve this state transition.
     pub fn disable_video(
         &self,
@@ -687,10 +1659,12 @@ impl RoomHandle {
     /// With [`ChangeMediaStateError::Detached`] if an inner [`Weak`] pointer
     /// upgrade fails.
     ///
-    /// With [`ChangeMediaStateError::TransitionIntoOppositeState`] if
-    /// [`RoomHandle::disable_video()`] was called while enabling or a media
+    /// With [`ChangeMediaStateError::TransitionIntoOppositeState`] if a media
     /// server didn't approve this state transition.
     ///
+    /// With [`ChangeMediaStateError::ForbiddenByServer`] if a media server
+    /// rejected this state transition, since it has forced the current state.
+    ///
     /// With [`ChangeMediaStateError::CouldNotGetLocalMedia`] if media
     /// acquisition request failed.
     pub fn enable_video(
@@ -708,14 +1682,16 @@ impl RoomHandle {
 
     /// Disables inbound audio in this [`Room`].
     ///
+    /// The preference is remembered and applied to all the connections
+    /// created afterwards, until toggled back.
+    ///
     /// # Errors
     ///
     /// With [`ChangeMediaStateError::Detached`] if an inner [`Weak`] pointer
     /// upgrade fails.
     ///
-    /// With [`ChangeMediaStateError::TransitionIntoOppositeState`] if
-    /// [`RoomHandle::enable_remote_audio()`] was called while disabling or a
-    /// media server didn't approve this state transition.
+    /// With [`ChangeMediaStateError::TransitionIntoOppositeState`] if a media
+    /// server didn't approve this state transition.
     pub fn disable_remote_audio(
         &self,
     ) -> impl Future<Output = ChangeMediaStateResult> + 'static {
@@ -730,6 +1706,9 @@ impl RoomHandle {
 
     /// Disables inbound video in this [`Room`].
     ///
+    /// The preference is remembered and applied to all the connections
+    /// created afterwards, until toggled back.
+    ///
     /// Affects only video with the specific [`MediaSourceKind`], if specified.
     ///
     /// # Errors
@@ -737,9 +1716,8 @@ impl RoomHandle {
     /// With [`ChangeMediaStateError::Detached`] if an inner [`Weak`] pointer
     /// upgrade fails.
     ///
-    /// With [`ChangeMediaStateError::TransitionIntoOppositeState`] if
-    /// [`RoomHandle::enable_remote_video()`] was called while disabling or a
-    /// media server didn't approve this state transition.
+    /// With [`ChangeMediaStateError::TransitionIntoOppositeState`] if a media
+    /// server didn't approve this state transition.
     pub fn disable_remote_video(
         &self,
         source_kind: Option<MediaSourceKind>,
@@ -755,14 +1733,16 @@ impl RoomHandle {
 
     /// Enables inbound audio in this [`Room`].
     ///
+    /// The preference is remembered and applied to all the connections
+    /// created afterwards, until toggled back.
+    ///
     /// # Errors
     ///
     /// With [`ChangeMediaStateError::Detached`] if an inner [`Weak`] pointer
     /// upgrade fails.
     ///
-    /// With [`ChangeMediaStateError::TransitionIntoOppositeState`] if
-    /// [`RoomHandle::disable_remote_audio()`] was called while enabling or a
-    /// media server didn't approve this state transition.
+    /// With [`ChangeMediaStateError::TransitionIntoOppositeState`] if a media
+    /// server didn't approve this state transition.
     pub fn enable_remote_audio(
         &self,
     ) -> impl Future<Output = ChangeMediaStateResult> + 'static {
@@ -777,6 +1757,9 @@ impl RoomHandle {
 
     /// Enables inbound video in this [`Room`].
     ///
+    /// The preference is remembered and applied to all the connections
+    /// created afterwards, until toggled back.
+    ///
     /// Affects only video with the specific [`MediaSourceKind`], if specified.
     ///
     /// # Errors
@@ -784,9 +1767,8 @@ impl RoomHandle {
     /// With [`ChangeMediaStateError::Detached`] if an inner [`Weak`] pointer
     /// upgrade fails.
     ///
-    /// With [`ChangeMediaStateError::TransitionIntoOppositeState`] if
-    /// [`RoomHandle::disable_remote_video()`] was called while enabling or a
-    /// media server didn't approve this state transition.
+    /// With [`ChangeMediaStateError::TransitionIntoOppositeState`] if a media
+    /// server didn't approve this state transition.
     pub fn enable_remote_video(
         &self,
         source_kind: Option<MediaSourceKind>,
@@ -799,6 +1781,53 @@ impl RoomHandle {
         )
         .map_err(tracerr::map_from_and_wrap!())
     }
+
+    /// Applies the provided [`RoomMediaPolicy`] to this [`Room`], performing
+    /// only the media state changes it requires.
+    ///
+    /// Can be called both before and after [`RoomHandle::join()`]. Media
+    /// state changes requested afterwards via the other [`RoomHandle`]
+    /// methods override the corresponding [`RoomMediaPolicy`] fields.
+    ///
+    /// # Errors
+    ///
+    /// With [`ChangeMediaStateError::Detached`] if an inner [`Weak`] pointer
+    /// upgrade fails.
+    ///
+    /// With the first [`ChangeMediaStateError`] the required media state
+    /// changes fail with.
+    pub fn apply_policy(
+        &self,
+        policy: RoomMediaPolicy,
+    ) -> impl Future<Output = Result<(), Traced<ChangeMediaStateError>>> {
+        let changes = self
+            .0
+            .upgrade()
+            .ok_or_else(|| tracerr::new!(ChangeMediaStateError::Detached))
+            .map(|inner| {
+                policy
+                    .intents(&inner.snapshot())
+                    .into_iter()
+                    .map(|intent| {
+                        self.change_media_state(
+                            MediaState::from(intent.change),
+                            intent.kind,
+                            intent.direction,
+                            intent.source_kind,
+                        )
+                    })
+                    .collect::<Vec<_>>()
+            });
+
+        async move {
+            // Changes superseded by the newer ones are fine, since the latter
+            // override the policy.
+            for change in changes? {
+                _ = change.await.map_err(tracerr::wrap!())?;
+            }
+            Ok(())
+        }
+    }
 }
 
 /// [`Weak`] reference upgradeable to the [`Room`].
@@ -873,29 +1902,68 @@ impl Room {
                 };
 
                 if let Some(this_room) = weak_room.upgrade() {
+                    if this_room.closed.get() {
+                        break;
+                    }
+                    let events = &this_room.events;
                     match event {
                         RoomEvent::RpcEvent(event) => {
+                            events.record(EventKind::Rpc, &event);
                             if let Err(e) = event
                                 .dispatch_with(&*this_room)
                                 .await
                                 .map_err(tracerr::wrap!(=> UnknownPeerIdError))
                             {
-                                log::error!("{e}");
+                                events.record(
+                                    EventKind::Error,
+                                    &format_args!("{e}"),
+                                );
+                                this_room.report_error(NonFatalError::new(
+                                    NonFatalErrorKind::EventHandling,
+                                    e,
+                                ));
                             };
                         }
                         RoomEvent::PeerEvent(event) => {
+                            // Periodic stats updates would flood the log.
+                            if !matches!(
+                                event,
+                                PeerEvent::StatsUpdate { .. }
+                                    | PeerEvent::AvSyncUpdate { .. }
+                            ) {
+                                events.record(EventKind::Peer, &event);
+                            }
                             if let Err(e) =
                                 event.dispatch_with(&*this_room).await.map_err(
                                     tracerr::wrap!(=> UnknownRemoteMemberError),
                                 )
                             {
-                                log::error!("{e}");
+                                events.record(
+                                    EventKind::Error,
+                                    &format_args!("{e}"),
+                                );
+                                this_room.report_error(NonFatalError::new(
+                                    NonFatalErrorKind::EventHandling,
+                                    e,
+                                ));
                             };
                         }
                         RoomEvent::RpcClientLostConnection => {
+                            events.record(
+                                EventKind::ConnectionLost,
+                                &format_args!("Connection lost"),
+                            );
+                            this_room.session_metrics.connection_lost();
+                            this_room.record_session_metrics();
                             this_room.handle_rpc_connection_lost();
                         }
                         RoomEvent::RpcClientReconnected => {
+                            events.record(
+                                EventKind::Reconnected,
+                                &format_args!("Connection restored"),
+                            );
+                            this_room.session_metrics.session_restored();
+                            this_room.record_session_metrics();
                             this_room.handle_rpc_connection_recovered();
                         }
                     }
@@ -911,10 +1979,16 @@ impl Room {
 
     /// Sets `close_reason` and consumes this [`Room`].
     ///
+    /// All the pending operations of this [`Room`] are abandoned: its
+    /// [`PeerConnection`]s are closed, the [`local::Track`]s being acquired
+    /// are stopped once acquired, and the pending [`RoomHandle`] calls fail
+    /// as detached ones.
+    ///
     /// [`Room`] [`Drop`] triggers `on_close` callback with provided
     /// [`CloseReason`].
     pub fn close(self, reason: CloseReason) {
         self.0.set_close_reason(reason);
+        self.0.close();
     }
 
     /// Sets [`Room`]'s [`CloseReason`] to the provided value.
@@ -950,6 +2024,43 @@ impl Room {
     pub fn downgrade(&self) -> WeakRoom {
         WeakRoom(Rc::downgrade(&self.0))
     }
+
+    /// Pre-applies the media states intended in the provided [`RoomSnapshot`]
+    /// to this [`Room`].
+    ///
+    /// Should be called before [`RoomHandle::join()`], so the tracks are
+    /// created in the intended states right away.
+    ///
+    /// Device IDs are applied as the default [`MediaStreamSettings`], so a
+    /// disappeared device doesn't break media acquisition, and the
+    /// [`MediaStreamSettings`] set afterwards win.
+    pub fn restore_state(&self, snapshot: &RoomSnapshot) {
+        self.0.restore_state(snapshot);
+    }
+
+    /// Sets whether [`ReconnectHandle`]s of this [`Room`] should reconnect
+    /// immediately once the network becomes available or the page becomes
+    /// visible, and pause reconnection attempts while the network is
+    /// unavailable.
+    ///
+    /// Enabled by default.
+    pub fn set_reconnect_on_network_events(&self, enabled: bool) {
+        self.0.reconnect_on_network_events.set(enabled);
+    }
+
+    /// Sets the maximum number of the lifecycle events retained by this
+    /// [`Room`] in its [`EventLog`].
+    pub fn set_event_log_capacity(&self, capacity: usize) {
+        self.0.events.set_capacity(capacity);
+    }
+
+    /// Sets whether the periodic stats collection of this [`Room`] should be
+    /// paused while the page is hidden.
+    ///
+    /// Disabled by default.
+    pub fn set_pause_stats_when_hidden(&self, enabled: bool) {
+        self.0.peers.set_pause_stats_when_hidden(enabled);
+    }
 }
 
 /// Actual data of a [`Room`].
@@ -982,6 +2093,10 @@ struct InnerRoom {
     /// added to this [`Room`].
     on_local_track: platform::Callback<api::LocalMediaTrack>,
 
+    /// All the [`local::Track`]s added to this [`Room`], in the order of their
+    /// addition.
+    local_tracks: RefCell<Vec<Weak<local::Track>>>,
+
     /// Callback invoked when failed obtain [`local::Track`]s from
     /// [`MediaManager`] or failed inject stream into [`PeerConnection`].
     on_failed_local_media: Rc<platform::Callback<api::Error>>,
@@ -994,6 +2109,37 @@ struct InnerRoom {
     #[cfg_attr(not(target_family = "wasm"), allow(unused_qualifications))]
     on_close: Rc<platform::Callback<api::RoomCloseReason>>,
 
+    /// Callback invoked when outbound video is adapted according to a
+    /// [`peer::AdaptivePolicy`].
+    #[cfg_attr(not(target_family = "wasm"), allow(unused_qualifications))]
+    on_adaptation: platform::Callback<api::Adaptation>,
+
+    /// Callback invoked when an encoder or a decoder of media falls back from
+    /// a hardware implementation to a software one.
+    #[cfg_attr(not(target_family = "wasm"), allow(unused_qualifications))]
+    on_codec_fallback: platform::Callback<api::CodecFallback>,
+
+    /// Callback invoked when a [`NonFatalError`] happens in this [`Room`].
+    #[cfg_attr(not(target_family = "wasm"), allow(unused_qualifications))]
+    on_error: platform::Callback<api::NonFatalError>,
+
+    /// [`ErrorRateLimiter`] of the [`NonFatalError`]s passed to the `on_error`
+    /// callback.
+    error_limiter: ErrorRateLimiter,
+
+    /// Callback invoked when a [`MediaState`] of the local media is forced by
+    /// a media server.
+    #[cfg_attr(not(target_family = "wasm"), allow(unused_qualifications))]
+    on_forced_media_state: platform::Callback<api::ForcedMediaState>,
+
+    /// Callback invoked when an SDP description is applied to a
+    /// [`PeerConnection`].
+    #[cfg_attr(not(target_family = "wasm"), allow(unused_qualifications))]
+    on_sdp: platform::Callback<api::SdpEvent>,
+
+    /// Indicator whether SDPs provided to the `on_sdp` callback are redacted.
+    redact_sdp: Cell<bool>,
+
     /// Reason of [`Room`] closing.
     ///
     /// This [`CloseReason`] will be provided into [`RoomHandle::on_close`]
@@ -1002,6 +2148,40 @@ struct InnerRoom {
     /// Note that `None` will be considered as error and `is_err` will be
     /// `true` in [`CloseReason`] provided to callback.
     close_reason: RefCell<CloseReason>,
+
+    /// Generations of the [`MediaState`] changes requested via
+    /// [`RoomHandle`]s along with their target [`MediaState`]s, used to detect
+    /// the superseded ones.
+    media_state_generations: RefCell<HashMap<MediaStateKey, (u64, MediaState)>>,
+
+    /// [`platform::IceCandidateFilter`] applied to both local and remote ICE
+    /// candidates of [`PeerConnection`]s in this [`Room`].
+    ice_candidate_filter: RefCell<platform::IceCandidateFilter>,
+
+    /// [`ConnectionInfo`] this [`Room`] has been joined with the last time.
+    connection_info: RefCell<Option<ConnectionInfo>>,
+
+    /// [`ReconnectHandle`] passed to the `on_connection_loss` callback on
+    /// every connection loss of this [`Room`].
+    reconnect_handle: ReconnectHandle,
+
+    /// Indicator whether [`ReconnectHandle`]s of this [`Room`] should react
+    /// to the network availability and page visibility changes.
+    reconnect_on_network_events: Cell<bool>,
+
+    /// Indicator whether this [`Room`] is closed, so all its pending
+    /// operations are abandoned.
+    closed: ObservableCell<bool>,
+
+    /// [`JoinState`] of this [`Room`].
+    join_state: RefCell<JoinState>,
+
+    /// [`EventLog`] of the lifecycle events happened in this [`Room`].
+    events: EventLog,
+
+    /// [`SessionMetricsCounter`] of the connection losses and recoveries
+    /// happened in this [`Room`].
+    session_metrics: SessionMetricsCounter,
 }
 
 impl fmt::Debug for InnerRoom {
@@ -1013,10 +2193,30 @@ impl fmt::Debug for InnerRoom {
             .field("media_manager", &self.media_manager)
             .field("connections", &self.connections)
             .field("on_local_track", &self.on_local_track)
+            .field("local_tracks", &self.local_tracks)
             .field("on_failed_local_media", &self.on_failed_local_media)
             .field("on_connection_loss", &self.on_connection_loss)
             .field("on_close", &self.on_close)
+            .field("on_adaptation", &self.on_adaptation)
+            .field("on_codec_fallback", &self.on_codec_fallback)
+            .field("on_error", &self.on_error)
+            .field("error_limiter", &self.error_limiter)
+            .field("on_forced_media_state", &self.on_forced_media_state)
+            .field("on_sdp", &self.on_sdp)
+            .field("redact_sdp", &self.redact_sdp)
             .field("close_reason", &self.close_reason)
+            .field("media_state_generations", &self.media_state_generations)
+            .field("ice_candidate_filter", &self.ice_candidate_filter)
+            .field("connection_info", &self.connection_info)
+            .field("reconnect_handle", &self.reconnect_handle)
+            .field(
+                "reconnect_on_network_events",
+                &self.reconnect_on_network_events,
+            )
+            .field("closed", &self.closed)
+            .field("join_state", &self.join_state)
+            .field("events", &self.events)
+            .field("session_metrics", &self.session_metrics)
             .finish_non_exhaustive()
     }
 }
@@ -1151,6 +2351,7 @@ impl InnerRoom {
                 Rc::new(peer::repo::State::default()),
             ),
             media_manager,
+            reconnect_handle: ReconnectHandle::new(Rc::downgrade(&rpc)),
             rpc,
             send_constraints,
             recv_constraints,
@@ -1158,14 +2359,90 @@ impl InnerRoom {
             on_connection_loss: platform::Callback::default(),
             on_failed_local_media: Rc::new(platform::Callback::default()),
             on_local_track: platform::Callback::default(),
+            local_tracks: RefCell::default(),
             on_close: Rc::new(platform::Callback::default()),
+            on_adaptation: platform::Callback::default(),
+            on_codec_fallback: platform::Callback::default(),
+            on_error: platform::Callback::default(),
+            error_limiter: ErrorRateLimiter::default(),
+            on_forced_media_state: platform::Callback::default(),
+            on_sdp: platform::Callback::default(),
+            redact_sdp: Cell::new(false),
             close_reason: RefCell::new(CloseReason::ByClient {
                 reason: ClientDisconnect::RoomUnexpectedlyDropped,
                 is_err: true,
             }),
+            media_state_generations: RefCell::default(),
+            ice_candidate_filter: RefCell::default(),
+            connection_info: RefCell::default(),
+            reconnect_on_network_events: Cell::new(true),
+            closed: ObservableCell::new(false),
+            join_state: RefCell::new(JoinState::Idle),
+            events: EventLog::default(),
+            session_metrics: SessionMetricsCounter::default(),
+        }
+    }
+
+    /// Remembers the provided [`local::Track`] as added to this [`Room`] and
+    /// invokes the `on_local_track` callback with it.
+    fn add_local_track(&self, track: Rc<local::Track>) {
+        self.local_tracks.borrow_mut().push(Rc::downgrade(&track));
+        self.on_local_track
+            .call1(local::LocalMediaTrack::new(track));
+    }
+
+    /// Sets the `on_local_track` callback and immediately invokes it for all
+    /// the alive [`local::Track`]s added to this [`Room`] already, in the
+    /// order of their addition.
+    fn set_on_local_track(&self, f: platform::Function<api::LocalMediaTrack>) {
+        self.on_local_track.set_func(f);
+
+        let existing: Vec<_> = {
+            let mut tracks = self.local_tracks.borrow_mut();
+            tracks.retain(|t| t.strong_count() > 0);
+            tracks.iter().filter_map(Weak::upgrade).collect()
+        };
+        for track in existing {
+            self.on_local_track
+                .call1(local::LocalMediaTrack::new(track));
         }
     }
 
+    /// Registers a new request of the [`MediaState`] change identified by the
+    /// provided [`MediaStateKey`] into the provided [`MediaState`],
+    /// superseding the previous opposite ones.
+    ///
+    /// Returns generation of the registered request.
+    fn start_media_state_change(
+        &self,
+        key: MediaStateKey,
+        state: MediaState,
+    ) -> u64 {
+        let mut generations = self.media_state_generations.borrow_mut();
+        let (generation, target) = generations.entry(key).or_insert((0, state));
+        *generation = generation.wrapping_add(1);
+        *target = state;
+        *generation
+    }
+
+    /// Indicates whether the [`MediaState`] change request of the provided
+    /// `generation` has been superseded by a newer one into the opposite
+    /// [`MediaState`].
+    ///
+    /// Newer requests into the same [`MediaState`] are coalesced with this
+    /// one, so don't supersede it.
+    fn is_media_state_change_superseded(
+        &self,
+        key: MediaStateKey,
+        generation: u64,
+        state: MediaState,
+    ) -> bool {
+        self.media_state_generations
+            .borrow()
+            .get(&key)
+            .map_or(false, |(g, target)| *g != generation && *target != state)
+    }
+
     /// Toggles [`InnerRoom::recv_constraints`] or
     /// [`InnerRoom::send_constraints`] media exchange status based on the
     /// provided [`TrackDirection`], [`MediaKind`] and
@@ -1199,6 +2476,135 @@ impl InnerRoom {
         }
     }
 
+    /// Returns a [`RoomSnapshot`] of the media states intended in this
+    /// [`InnerRoom`].
+    fn snapshot(&self) -> RoomSnapshot {
+        let settings = self.send_constraints.effective();
+        let local = |kind: MediaKind, source: MediaSourceKind| {
+            let source = proto::MediaSourceKind::from(source);
+            LocalMediaSnapshot {
+                enabled: settings.is_track_enabled(kind, Some(source)),
+                muted: settings.is_track_muted(kind, source),
+                device_id: settings.device_id(kind, source),
+            }
+        };
+        let info = self.connection_info.borrow();
+
+        RoomSnapshot {
+            room_url: info.as_ref().map(|info| {
+                let url = info.url().as_ref().as_str().trim_end_matches('/');
+                format!("{url}/{}", info.room_id())
+            }),
+            member_id: info.as_ref().map(|info| info.member_id().clone()),
+            audio: local(MediaKind::Audio, MediaSourceKind::Device),
+            device_video: local(MediaKind::Video, MediaSourceKind::Device),
+            display_video: local(MediaKind::Video, MediaSourceKind::Display),
+            recv_audio: self.recv_constraints.is_audio_enabled(),
+            recv_device_video: self.recv_constraints.is_video_device_enabled(),
+            recv_display_video: self
+                .recv_constraints
+                .is_video_display_enabled(),
+        }
+    }
+
+    /// Returns the current [`RoomMediaState`] of this [`InnerRoom`].
+    fn media_state(&self) -> RoomMediaState {
+        let settings = self.send_constraints.effective();
+        let send = |kind: MediaKind, source: MediaSourceKind| {
+            let source = proto::MediaSourceKind::from(source);
+            SendMediaState {
+                enabled: settings.is_track_enabled(kind, Some(source)),
+                muted: settings.is_track_muted(kind, source),
+            }
+        };
+
+        RoomMediaState {
+            send_device_audio: send(MediaKind::Audio, MediaSourceKind::Device),
+            send_display_audio: send(
+                MediaKind::Audio,
+                MediaSourceKind::Display,
+            ),
+            send_device_video: send(MediaKind::Video, MediaSourceKind::Device),
+            send_display_video: send(
+                MediaKind::Video,
+                MediaSourceKind::Display,
+            ),
+            recv_audio: self.recv_constraints.is_audio_enabled(),
+            recv_device_video: self.recv_constraints.is_video_device_enabled(),
+            recv_display_video: self
+                .recv_constraints
+                .is_video_display_enabled(),
+        }
+    }
+
+    /// Pre-applies the media states intended in the provided [`RoomSnapshot`]
+    /// to this [`InnerRoom`].
+    fn restore_state(&self, snapshot: &RoomSnapshot) {
+        use media_exchange_state::Stable::{Disabled, Enabled};
+        use mute_state::Stable::{Muted, Unmuted};
+
+        let mut defaults = MediaStreamSettings::new();
+        if let Some(id) = &snapshot.audio.device_id {
+            let mut audio = AudioTrackConstraints::new();
+            audio.device_id(id.clone());
+            defaults.audio(audio);
+        }
+        if let Some(id) = &snapshot.device_video.device_id {
+            let mut video = DeviceVideoTrackConstraints::new();
+            video.device_id(id.clone());
+            defaults.device_video(video);
+        }
+        if let Some(id) = &snapshot.display_video.device_id {
+            let mut video = DisplayVideoTrackConstraints::new();
+            video.device_id(id.clone());
+            defaults.display_video(video);
+        }
+        self.send_constraints.set_defaults(defaults);
+
+        for (kind, source, local, recv) in [
+            (
+                MediaKind::Audio,
+                MediaSourceKind::Device,
+                &snapshot.audio,
+                snapshot.recv_audio,
+            ),
+            (
+                MediaKind::Video,
+                MediaSourceKind::Device,
+                &snapshot.device_video,
+                snapshot.recv_device_video,
+            ),
+            (
+                MediaKind::Video,
+                MediaSourceKind::Display,
+                &snapshot.display_video,
+                snapshot.recv_display_video,
+            ),
+        ] {
+            let source = Some(source.into());
+            let exchange = |enabled| if enabled { Enabled } else { Disabled };
+            let mute = if local.muted { Muted } else { Unmuted };
+            self.set_constraints_media_state(
+                exchange(local.enabled).into(),
+                kind,
+                TrackDirection::Send,
+                source,
+            );
+            self.set_constraints_media_state(
+                mute.into(),
+                kind,
+                TrackDirection::Send,
+                source,
+            );
+            self.set_constraints_media_state(
+                exchange(recv).into(),
+                kind,
+                TrackDirection::Recv,
+                source,
+            );
+        }
+    }
+
     /// Sets `close_reason` of this [`InnerRoom`].
     ///
     /// [`Drop`] implementation of [`InnerRoom`] is supposed to be triggered
@@ -1207,6 +2613,29 @@ impl InnerRoom {
         _ = self.close_reason.replace(reason);
     }
 
+    /// Closes this [`InnerRoom`] abandoning all its pending operations.
+    ///
+    /// Removes all the [`PeerConnection`]s, so their pending negotiations are
+    /// abandoned.
+    fn close(&self) {
+        self.closed.set(true);
+        drop(self.join_state.replace(JoinState::Closed));
+        self.peers.remove_all();
+    }
+
+    /// Resolves the provided [`Future`], or returns [`None`] if this
+    /// [`InnerRoom`] is closed before it resolves.
+    async fn unless_closed<T>(
+        &self,
+        fut: impl Future<Output = T>,
+    ) -> Option<T> {
+        let closed = self.closed.when_eq(true);
+        match future::select(Box::pin(fut), closed).await {
+            future::Either::Left((out, _)) => Some(out),
+            future::Either::Right(_) => None,
+        }
+    }
+
     /// Toggles [`TransceiverSide`]s [`MediaState`] by the provided
     /// [`MediaKind`] in all [`PeerConnection`]s of this [`Room`].
     ///
@@ -1285,7 +2714,13 @@ impl InnerRoom {
                         .map(|(trnscvr, desired_state)| {
                             trnscvr.media_state_transition_to(desired_state)?;
 
-                            Ok(trnscvr.when_media_state_stable(desired_state))
+                            Ok(trnscvr
+                                .when_media_state_stable(desired_state)
+                                .map_err(move |state| {
+                                    ChangeMediaStateError::opposite_state(
+                                        &*trnscvr, state,
+                                    )
+                                }))
                         })
                         .collect::<Result<_, Traced<ProhibitedStateError>>>()
                         .map_err(tracerr::wrap!())?;
@@ -1297,7 +2732,7 @@ impl InnerRoom {
         )
         .await
         .map(drop)
-        .map_err(tracerr::from_and_wrap!())?;
+        .map_err(tracerr::wrap!())?;
 
         future::try_join_all(stream_upd_sub.into_iter().filter_map(
             |(id, tracks_ids)| {
@@ -1355,8 +2790,7 @@ impl InnerRoom {
                 .map_err(tracerr::map_from_and_wrap!())?;
             for (track, is_new) in tracks {
                 if is_new {
-                    self.on_local_track
-                        .call1(local::LocalMediaTrack::new(Rc::clone(&track)));
+                    self.add_local_track(Rc::clone(&track));
                 }
                 result.push(track);
             }
@@ -1450,6 +2884,7 @@ impl InnerRoom {
         new_settings: MediaStreamSettings,
         stop_first: bool,
         rollback_on_fail: bool,
+        seamless: bool,
     ) -> Result<(), ConstraintsUpdateError> {
         use ConstraintsUpdateError as E;
 
@@ -1461,8 +2896,20 @@ impl InnerRoom {
         let peers = self.peers.get_all();
 
         if stop_first {
+            let kinds = if seamless {
+                let captured: Vec<_> = peers
+                    .iter()
+                    .flat_map(|p| p.get_send_tracks())
+                    .map(|t| (t.kind(), t.media_source_kind(), t.device_id()))
+                    .collect();
+                self.send_constraints
+                    .effective()
+                    .stop_first_kinds(criteria_kinds_diff, &captured)
+            } else {
+                criteria_kinds_diff
+            };
             for peer in &peers {
-                peer.drop_send_tracks(criteria_kinds_diff).await;
+                peer.drop_send_tracks(kinds).await;
             }
         }
 
@@ -1494,6 +2941,7 @@ impl InnerRoom {
                             current_settings,
                             stop_first,
                             false,
+                            seamless,
                         )
                         .await
                         .map_err(|err| {
@@ -1549,8 +2997,27 @@ impl InnerRoom {
     /// [`Room`].
     fn handle_rpc_connection_lost(&self) {
         self.peers.connection_lost();
-        self.on_connection_loss
-            .call1(ReconnectHandle::new(Rc::downgrade(&self.rpc)));
+        self.on_connection_loss.call1(
+            self.reconnect_handle
+                .clone()
+                .network_aware(self.reconnect_on_network_events.get()),
+        );
+    }
+
+    /// Records the current [`SessionMetrics`] of this [`Room`] into its
+    /// [`EventLog`].
+    fn record_session_metrics(&self) {
+        self.events
+            .record(EventKind::SessionMetrics, &self.session_metrics.metrics());
+    }
+
+    /// Logs the provided [`NonFatalError`] and passes it to the `on_error`
+    /// callback, unless an identical one has been passed recently.
+    fn report_error(&self, error: NonFatalError) {
+        log::error!("{error}");
+        if self.error_limiter.pass(&error) {
+            self.on_error.call1(api::NonFatalError::from(error));
+        }
     }
 
     /// Sends [`Command::SynchronizeMe`] with a current Client state to the
@@ -1641,7 +3108,8 @@ impl EventHandler for InnerRoom {
         Ok(())
     }
 
-    /// Applies specified [`IceCandidate`] to a specified [`PeerConnection`].
+    /// Applies specified [`IceCandidate`] to a specified [`PeerConnection`],
+    /// unless it's filtered out by the [`platform::IceCandidateFilter`].
     async fn on_ice_candidate_discovered(
         &self,
         peer_id: PeerId,
@@ -1652,14 +3120,38 @@ impl EventHandler for InnerRoom {
             .state()
             .get(peer_id)
             .ok_or_else(|| tracerr::new!(UnknownPeerIdError(peer_id)))?;
-        peer.add_ice_candidate(candidate);
+        if self
+            .ice_candidate_filter
+            .borrow()
+            .allows(&candidate.candidate)
+        {
+            peer.add_ice_candidate(candidate);
+        }
 
         Ok(())
     }
 
-    /// Disposes specified [`PeerConnection`]s.
+    /// Disposes specified [`PeerConnection`]s, recording their final
+    /// [`peer::QualitySummary`]s and [`peer::AvSyncInfo`]s into the
+    /// [`EventLog`].
     async fn on_peers_removed(&self, peer_ids: Vec<PeerId>) -> Self::Output {
         for id in peer_ids {
+            if let Some(peer) = self.peers.get(id) {
+                self.events.record(
+                    EventKind::Peer,
+                    &format_args!(
+                        "QualitySummary {{ peer_id: {id}, summary: {:?} }}",
+                        peer.quality_summary(),
+                    ),
+                );
+                self.events.record(
+                    EventKind::Peer,
+                    &format_args!(
+                        "AvSyncInfo {{ peer_id: {id}, info: {:?} }}",
+                        peer.av_sync_info(),
+                    ),
+                );
+            }
             self.peers.state().remove(id);
         }
         Ok(())
@@ -1694,6 +3186,8 @@ impl EventHandler for InnerRoom {
                 }
                 PeerUpdate::IceRestart => {
                     peer_state.restart_ice();
+                    self.session_metrics.ice_restarted();
+                    self.record_session_metrics();
                 }
                 PeerUpdate::Removed(id) => {
                     peer_state.remove_track(id);
@@ -1756,7 +3250,8 @@ impl PeerEventHandler for InnerRoom {
     type Output = Result<(), Traced<UnknownRemoteMemberError>>;
 
     /// Handles [`PeerEvent::IceCandidateDiscovered`] event and sends received
-    /// candidate to RPC server.
+    /// candidate to RPC server, unless it's filtered out by the
+    /// [`platform::IceCandidateFilter`].
     async fn on_ice_candidate_discovered(
         &self,
         peer_id: PeerId,
@@ -1764,6 +3259,9 @@ impl PeerEventHandler for InnerRoom {
         sdp_m_line_index: Option<u16>,
         sdp_mid: Option<String>,
     ) -> Self::Output {
+        if !self.ice_candidate_filter.borrow().allows(&candidate) {
+            return Ok(());
+        }
         self.rpc.send_command(Command::SetIceCandidate {
             peer_id,
             candidate: IceCandidate {
@@ -1798,8 +3296,99 @@ impl PeerEventHandler for InnerRoom {
         &self,
         track: Rc<local::Track>,
     ) -> Self::Output {
-        self.on_local_track
-            .call1(local::LocalMediaTrack::new(track));
+        self.add_local_track(track);
+        Ok(())
+    }
+
+    /// Handles [`PeerEvent::LocalTrackEnded`] event by disabling sending of
+    /// the media with the provided [`MediaKind`] and
+    /// [`proto::MediaSourceKind`], the same way as if it has been disabled via
+    /// a [`RoomHandle`].
+    ///
+    /// Doesn't wait for the disabling to be approved by a media server, since
+    /// its intentions are sent via [`PeerEvent`]s too.
+    async fn on_local_track_ended(
+        &self,
+        kind: MediaKind,
+        source_kind: proto::MediaSourceKind,
+    ) -> Self::Output {
+        let state = MediaState::from(media_exchange_state::Stable::Disabled);
+        let direction = TrackDirection::Send;
+        // Supersedes any pending enabling request of the ended media.
+        _ = self.start_media_state_change(
+            MediaStateKey {
+                is_mute: false,
+                kind,
+                direction,
+                source_kind: Some(source_kind.into()),
+            },
+            state,
+        );
+        self.set_constraints_media_state(
+            state,
+            kind,
+            direction,
+            Some(source_kind),
+        );
+
+        for peer in self.peers.get_all() {
+            let transceivers = peer
+                .get_transceivers_sides(kind, direction, Some(source_kind))
+                .into_iter()
+                .filter(|t| {
+                    t.is_transitable() && t.is_subscription_needed(state)
+                });
+            for transceiver in transceivers {
+                if let Err(e) = transceiver.media_state_transition_to(state) {
+                    self.report_error(NonFatalError::new(
+                        NonFatalErrorKind::LocalTrack,
+                        e,
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Handles [`PeerEvent::SdpApplied`] event by invoking `on_sdp` [`Room`]'s
+    /// callback, redacting the SDP if required.
+    async fn on_sdp_applied(
+        &self,
+        _: PeerId,
+        event: peer::SdpEvent,
+    ) -> Self::Output {
+        if self.on_sdp.is_set() {
+            let event = if self.redact_sdp.get() {
+                event.redacted()
+            } else {
+                event
+            };
+            self.on_sdp.call1(api::SdpEvent::from(event));
+        }
+        Ok(())
+    }
+
+    /// Handles [`PeerEvent::MediaStateForced`] event by applying the forced
+    /// [`MediaState`] to the [`RoomMediaState`] and invoking
+    /// `on_forced_media_state` [`Room`]'s callback.
+    async fn on_media_state_forced(
+        &self,
+        kind: MediaKind,
+        source_kind: proto::MediaSourceKind,
+        state: MediaState,
+    ) -> Self::Output {
+        self.set_constraints_media_state(
+            state,
+            kind,
+            TrackDirection::Send,
+            Some(source_kind),
+        );
+        self.on_forced_media_state
+            .call1(api::ForcedMediaState::from(peer::ForcedMediaState {
+                kind,
+                source_kind,
+                state,
+            }));
         Ok(())
     }
 
@@ -1851,6 +3440,64 @@ impl PeerEventHandler for InnerRoom {
         Ok(())
     }
 
+    /// Handles [`PeerEvent::NegotiationStatsUpdate`] event and sends new
+    /// [`peer::NegotiationStats`] to the RPC server.
+    async fn on_negotiation_stats_update(
+        &self,
+        peer_id: PeerId,
+        stats: peer::NegotiationStats,
+    ) -> Self::Output {
+        self.rpc.send_command(Command::AddPeerConnectionMetrics {
+            peer_id,
+            metrics: PeerMetrics::NegotiationStats(stats.into()),
+        });
+        Ok(())
+    }
+
+    /// Handles [`PeerEvent::AvSyncUpdate`] event and sends new
+    /// [`peer::AvSyncInfo`] to the RPC server.
+    async fn on_av_sync_update(
+        &self,
+        peer_id: PeerId,
+        info: peer::AvSyncInfo,
+    ) -> Self::Output {
+        self.rpc.send_command(Command::AddPeerConnectionMetrics {
+            peer_id,
+            metrics: PeerMetrics::AvSyncStats(info.into()),
+        });
+        Ok(())
+    }
+
+    /// Handles [`PeerEvent::VideoAdapted`] event by invoking `on_adaptation`
+    /// [`Room`]'s callback.
+    async fn on_video_adapted(
+        &self,
+        _: PeerId,
+        adaptation: peer::Adaptation,
+    ) -> Self::Output {
+        self.on_adaptation.call1(api::Adaptation::from(adaptation));
+        Ok(())
+    }
+
+    /// Handles [`PeerEvent::SoftwareCodecFallback`] event by invoking
+    /// `on_codec_fallback` [`Room`]'s callback.
+    async fn on_software_codec_fallback(
+        &self,
+        _: PeerId,
+        fallback: peer::CodecFallback,
+    ) -> Self::Output {
+        self.on_codec_fallback
+            .call1(api::CodecFallback::from(fallback));
+        Ok(())
+    }
+
+    /// Handles [`PeerEvent::NonFatalError`] event by reporting it via
+    /// `on_error` [`Room`]'s callback.
+    async fn on_non_fatal_error(&self, error: NonFatalError) -> Self::Output {
+        self.report_error(error);
+        Ok(())
+    }
+
     /// Handles [`PeerEvent::FailedLocalMedia`] event by invoking
     /// `on_failed_local_media` [`Room`]'s callback.
     async fn on_failed_local_media(
@@ -1915,8 +3562,10 @@ impl Drop for InnerRoom {
             self.rpc.close_with_reason(reason);
         };
 
-        self.on_close
-            .call1(RoomCloseReason::new(*self.close_reason.borrow()));
+        self.on_close.call1(RoomCloseReason::new(
+            *self.close_reason.borrow(),
+            self.session_metrics.metrics(),
+        ));
     }
 }
 
@@ -10,7 +10,16 @@ use crate::platform;
 
 /// [`ExponentialBackoff`] adapted for the used async runtime.
 #[derive(Debug)]
-pub struct BackoffDelayer(ExponentialBackoff);
+pub struct BackoffDelayer {
+    /// Policy of the delays between the retries.
+    backoff: ExponentialBackoff,
+
+    /// Indicator whether the delays between the retries should react to
+    /// [`NetworkEvent`]s.
+    ///
+    /// [`NetworkEvent`]: platform::NetworkEvent
+    network_aware: bool,
+}
 
 impl BackoffDelayer {
     /// Creates a new [`BackoffDelayer`] out of the provided options.
@@ -27,15 +36,29 @@ impl BackoffDelayer {
         // initial_interval = max_interval if initial_interval > max_delay
         let initial_interval = initial_interval.min(max_interval);
 
-        Self(ExponentialBackoff {
-            current_interval: initial_interval,
-            initial_interval,
-            randomization_factor: 0.0,
-            multiplier,
-            max_interval,
-            max_elapsed_time,
-            ..ExponentialBackoff::default()
-        })
+        Self {
+            backoff: ExponentialBackoff {
+                current_interval: initial_interval,
+                initial_interval,
+                randomization_factor: 0.0,
+                multiplier,
+                max_interval,
+                max_elapsed_time,
+                ..ExponentialBackoff::default()
+            },
+            network_aware: false,
+        }
+    }
+
+    /// Makes delays of this [`BackoffDelayer`] being cut short once the
+    /// network becomes available or the page becomes visible, and paused
+    /// while the network is unavailable.
+    ///
+    /// See [`platform::network::delay_for()`] for details.
+    #[must_use]
+    pub const fn network_aware(mut self, enabled: bool) -> Self {
+        self.network_aware = enabled;
+        self
     }
 
     /// Retries the given `operation` according to this [`BackoffDelayer`]'s
@@ -49,20 +72,32 @@ impl BackoffDelayer {
         Fn: FnMut() -> Fut,
         Fut: Future<Output = Result<I, backoff::Error<E>>>,
     {
-        Retry::new(Sleeper, self.0, |_, _| {}, operation).await
+        let sleeper = Sleeper {
+            network_aware: self.network_aware,
+        };
+        Retry::new(sleeper, self.backoff, |_, _| {}, operation).await
     }
 }
 
-/// [`backoff::future::Sleeper`] implementation using [`platform::delay_for()`].
-struct Sleeper;
+/// [`backoff::future::Sleeper`] implementation using [`platform::delay_for()`]
+/// or [`platform::network::delay_for()`].
+struct Sleeper {
+    /// Indicator whether [`platform::network::delay_for()`] should be used.
+    network_aware: bool,
+}
 
 impl backoff::future::Sleeper for Sleeper {
     type Sleep = BoxFuture<'static, ()>;
 
     fn sleep(&self, delay: Duration) -> Self::Sleep {
         let (tx, rx) = oneshot::channel();
+        let network_aware = self.network_aware;
         platform::spawn(async move {
-            platform::delay_for(delay).await;
+            if network_aware {
+                platform::network::delay_for(delay).await;
+            } else {
+                platform::delay_for(delay).await;
+            }
             _ = tx.send(());
         });
         Box::pin(rx.map(drop))
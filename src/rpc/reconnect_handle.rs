@@ -1,13 +1,23 @@
 //! Reconnection for [`RpcSession`].
 
-use std::{fmt, rc::Weak, time::Duration};
+use std::{
+    cell::{Cell, RefCell},
+    fmt,
+    future::Future,
+    rc::{Rc, Weak},
+    time::Duration,
+};
 
 use derive_more::{Display, From};
+use futures::{
+    future::{LocalBoxFuture, Shared, WeakShared},
+    FutureExt as _,
+};
 use tracerr::Traced;
 
 use crate::{
     platform,
-    rpc::{BackoffDelayer, RpcSession, SessionError},
+    rpc::{BackoffDelayer, ClientDisconnect, RpcSession, SessionError},
     utils::Caused,
 };
 
@@ -23,11 +33,53 @@ pub enum ReconnectError {
     Detached,
 }
 
+/// Outcome of a reconnection requested via a [`ReconnectHandle`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ReconnectOutcome {
+    /// [`RpcSession`] has been reconnected.
+    Reconnected,
+
+    /// [`RpcSession`] has been connected already, so no reconnection attempt
+    /// has been performed.
+    AlreadyConnected,
+}
+
+/// Result of a reconnection requested via a [`ReconnectHandle`].
+type ReconnectResult = Result<ReconnectOutcome, Traced<ReconnectError>>;
+
+/// [`Future`] of a reconnection requested via a [`ReconnectHandle`].
+type ReconnectFuture = LocalBoxFuture<'static, ReconnectResult>;
+
+/// State shared between all the clones of a [`ReconnectHandle`].
+#[derive(Default)]
+struct State {
+    /// Reconnection being performed currently, if any.
+    reconnection: RefCell<Option<WeakShared<ReconnectFuture>>>,
+
+    /// Number of the attempts performed during the current (or the last)
+    /// reconnection.
+    attempt_number: Cell<u32>,
+}
+
 /// External handle used to reconnect to a media server when connection is lost.
 ///
-/// This handle will be passed to a `Room.on_connection_loss` callback.
+/// This handle will be passed to a `Room.on_connection_loss` callback. The same
+/// handle (sharing its state with all its clones) is passed on every connection
+/// loss of a `Room`, so it may be stored and used across repeated outages.
 #[derive(Clone)]
-pub struct ReconnectHandle(Weak<dyn RpcSession>);
+pub struct ReconnectHandle {
+    /// [`RpcSession`] to reconnect.
+    rpc: Weak<dyn RpcSession>,
+
+    /// [`State`] shared between all the clones of this [`ReconnectHandle`].
+    state: Rc<State>,
+
+    /// Indicator whether reconnection delays should react to
+    /// [`NetworkEvent`]s.
+    ///
+    /// [`NetworkEvent`]: platform::NetworkEvent
+    network_aware: bool,
+}
 
 impl fmt::Debug for ReconnectHandle {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -40,30 +92,84 @@ impl ReconnectHandle {
     /// reference.
     #[must_use]
     pub fn new(rpc: Weak<dyn RpcSession>) -> Self {
-        Self(rpc)
+        Self {
+            rpc,
+            state: Rc::default(),
+            network_aware: false,
+        }
+    }
+
+    /// Makes this [`ReconnectHandle`] to reconnect immediately once the
+    /// network becomes available or the page becomes visible, and to pause
+    /// reconnection attempts while the network is unavailable.
+    #[must_use]
+    pub const fn network_aware(mut self, enabled: bool) -> Self {
+        self.network_aware = enabled;
+        self
+    }
+
+    /// Waits until the network becomes available, if this [`ReconnectHandle`]
+    /// is [network aware][1] and the network is unavailable now.
+    ///
+    /// [1]: ReconnectHandle::network_aware
+    async fn wait_online(&self) {
+        if self.network_aware && !platform::is_online() {
+            platform::network::delay_for(Duration::ZERO).await;
+        }
+    }
+
+    /// Indicates whether a reconnection is being performed via this
+    /// [`ReconnectHandle`] (or any of its clones) currently.
+    #[must_use]
+    pub fn is_reconnecting(&self) -> bool {
+        self.reconnection().is_some()
+    }
+
+    /// Returns number of the attempts performed during the current (or the
+    /// last) reconnection via this [`ReconnectHandle`] (or any of its clones).
+    #[must_use]
+    pub fn attempt_number(&self) -> u32 {
+        self.state.attempt_number.get()
     }
 
     /// Tries to reconnect after the provided delay in milliseconds.
     ///
-    /// If [`RpcSession`] is already reconnecting then new reconnection attempt
-    /// won't be performed. Instead, it will wait for the first reconnection
-    /// attempt result and use it here.
+    /// Resolves with [`ReconnectOutcome::AlreadyConnected`] right away if the
+    /// [`RpcSession`] is connected already.
+    ///
+    /// If a reconnection is being performed via this [`ReconnectHandle`]
+    /// already, then a new one won't be started. Instead, the result of the
+    /// ongoing one will be used here.
+    ///
+    /// If this [`ReconnectHandle`] is [network aware][1], then the delay is
+    /// cut short once the network becomes available or the page becomes
+    /// visible, and is prolonged while the network is unavailable.
     ///
     /// # Errors
     ///
     /// See [`ReconnectError`] for details.
+    ///
+    /// [1]: ReconnectHandle::network_aware
     pub async fn reconnect_with_delay(
         &self,
         delay_ms: u32,
-    ) -> Result<(), Traced<ReconnectError>> {
-        platform::delay_for(Duration::from_millis(u64::from(delay_ms))).await;
-
-        let rpc = self
-            .0
-            .upgrade()
-            .ok_or_else(|| tracerr::new!(ReconnectError::Detached))?;
+    ) -> Result<ReconnectOutcome, Traced<ReconnectError>> {
+        let this = self.clone();
+        self.reconnect(async move {
+            let delay = Duration::from_millis(u64::from(delay_ms));
+            if this.network_aware {
+                platform::network::delay_for(delay).await;
+            } else {
+                platform::delay_for(delay).await;
+            }
 
-        rpc.reconnect().await.map_err(tracerr::map_from_and_wrap!())
+            let rpc = this
+                .rpc
+                .upgrade()
+                .ok_or_else(|| tracerr::new!(ReconnectError::Detached))?;
+            this.attempt(rpc).await
+        })
+        .await
     }
 
     /// Tries to reconnect [`RpcSession`] in a loop with a growing backoff
@@ -82,41 +188,138 @@ impl ReconnectHandle {
     /// `0.0`. This might cause a busy loop, so it's not recommended.
     ///
     /// Max elapsed time can be limited with an optional `max_elapsed_time_ms`
-    /// argument.
+    /// argument. Once it's reached, reconnecting is [given up][2], so the
+    /// `Room` is closed with [`ClientDisconnect::ConnectionLost`] reason.
     ///
-    /// If [`RpcSession`] is already reconnecting then new reconnection attempt
-    /// won't be performed. Instead, it will wait for the first reconnection
-    /// attempt result and use it here.
+    /// Resolves with [`ReconnectOutcome::AlreadyConnected`] right away if the
+    /// [`RpcSession`] is connected already.
+    ///
+    /// If a reconnection is being performed via this [`ReconnectHandle`]
+    /// already, then a new one won't be started. Instead, the result of the
+    /// ongoing one will be used here.
+    ///
+    /// If this [`ReconnectHandle`] is [network aware][1], then no attempts are
+    /// performed while the network is unavailable, and the next attempt is
+    /// performed right away once the network becomes available or the page
+    /// becomes visible.
     ///
     /// # Errors
     ///
     /// See [`ReconnectError`] for details.
+    ///
+    /// [1]: ReconnectHandle::network_aware
+    /// [2]: ReconnectHandle::cancel
     pub async fn reconnect_with_backoff(
         &self,
         starting_delay_ms: u32,
         multiplier: f64,
         max_delay: u32,
         max_elapsed_time_ms: Option<u32>,
-    ) -> Result<(), Traced<ReconnectError>> {
-        BackoffDelayer::new(
-            Duration::from_millis(starting_delay_ms.into()),
-            multiplier,
-            Duration::from_millis(max_delay.into()),
-            max_elapsed_time_ms.map(|val| Duration::from_millis(val.into())),
-        )
-        .retry(|| async {
-            self.0
-                .upgrade()
-                .ok_or_else(|| {
+    ) -> Result<ReconnectOutcome, Traced<ReconnectError>> {
+        let this = self.clone();
+        self.reconnect(async move {
+            this.wait_online().await;
+
+            BackoffDelayer::new(
+                Duration::from_millis(starting_delay_ms.into()),
+                multiplier,
+                Duration::from_millis(max_delay.into()),
+                max_elapsed_time_ms
+                    .map(|val| Duration::from_millis(val.into())),
+            )
+            .network_aware(this.network_aware)
+            .retry(|| async {
+                let rpc = this.rpc.upgrade().ok_or_else(|| {
                     backoff::Error::Permanent(tracerr::new!(
                         ReconnectError::Detached
                     ))
-                })?
-                .reconnect()
-                .await
-                .map_err(tracerr::map_from_and_wrap!())
-                .map_err(backoff::Error::transient)
+                })?;
+                this.attempt(rpc).await.map_err(|e| {
+                    if is_finished(&e) {
+                        backoff::Error::Permanent(e)
+                    } else {
+                        backoff::Error::transient(e)
+                    }
+                })
+            })
+            .await
+            .map_err(|e| {
+                if !is_finished(&e) {
+                    this.cancel();
+                }
+                e
+            })
         })
         .await
     }
+
+    /// Gives up reconnecting to a media server, closing the `Room` with
+    /// [`ClientDisconnect::ConnectionLost`] reason, so its resources are
+    /// released like on a normal close.
+    ///
+    /// The ongoing reconnection (if any) fails once its current attempt is
+    /// over.
+    ///
+    /// Does nothing if the [`RpcSession`] is connected.
+    pub fn cancel(&self) {
+        if let Some(rpc) = self.rpc.upgrade() {
+            if !rpc.is_opened() {
+                rpc.close_with_reason(ClientDisconnect::ConnectionLost);
+            }
+        }
+    }
+
+    /// Performs the provided `reconnection`, unless the [`RpcSession`] is
+    /// connected already, or another reconnection is being performed, which
+    /// result is used instead.
+    async fn reconnect<F>(&self, reconnection: F) -> ReconnectResult
+    where
+        F: Future<Output = ReconnectResult> + 'static,
+    {
+        if self.rpc.upgrade().map_or(false, |rpc| rpc.is_opened()) {
+            return Ok(ReconnectOutcome::AlreadyConnected);
+        }
+
+        let reconnection = self.reconnection().unwrap_or_else(|| {
+            self.state.attempt_number.set(0);
+            let reconnection = reconnection.boxed_local().shared();
+            drop(self.state.reconnection.replace(reconnection.downgrade()));
+            reconnection
+        });
+        reconnection.await
+    }
+
+    /// Returns the reconnection being performed currently, if any.
+    fn reconnection(&self) -> Option<Shared<ReconnectFuture>> {
+        self.state
+            .reconnection
+            .borrow()
+            .as_ref()
+            .and_then(WeakShared::upgrade)
+            .filter(|r| r.peek().is_none())
+    }
+
+    /// Performs a single reconnection attempt of the provided [`RpcSession`].
+    async fn attempt(&self, rpc: Rc<dyn RpcSession>) -> ReconnectResult {
+        if rpc.is_opened() {
+            return Ok(ReconnectOutcome::AlreadyConnected);
+        }
+        self.state
+            .attempt_number
+            .set(self.state.attempt_number.get().saturating_add(1));
+
+        rpc.reconnect()
+            .await
+            .map(|()| ReconnectOutcome::Reconnected)
+            .map_err(tracerr::map_from_and_wrap!())
+    }
+}
+
+/// Indicates whether the provided [`ReconnectError`] is caused by the
+/// [`RpcSession`] being closed, so reconnecting to it is impossible.
+fn is_finished(err: &Traced<ReconnectError>) -> bool {
+    matches!(
+        err.as_ref(),
+        ReconnectError::Session(SessionError::SessionFinished(_))
+    )
 }
@@ -118,6 +118,9 @@ pub trait RpcSession {
     /// Tries to reconnect (or connect) this [`RpcSession`] to the Media Server.
     async fn reconnect(self: Rc<Self>) -> Result<(), Traced<SessionError>>;
 
+    /// Indicates whether this [`RpcSession`] is in [`SessionState::Opened`].
+    fn is_opened(&self) -> bool;
+
     /// Returns [`Stream`] of all [`Event`]s received by this [`RpcSession`].
     ///
     /// [`Stream`]: futures::Stream
@@ -276,20 +279,27 @@ impl WebSocketRpcSession {
             while let Some(state) = state_updates.next().await {
                 let this = upgrade_or_break!(weak_this);
                 match state {
-                    S::Connecting(info) => match Rc::clone(&this.client)
-                        .connect(info.url.clone())
-                        .await
-                    {
-                        Ok(()) => {
-                            this.state.set(S::Authorizing(info));
+                    S::Connecting(info) => {
+                        let res = Rc::clone(&this.client)
+                            .connect(info.url.clone())
+                            .await;
+                        // Session may be finished (or re-initialized) while
+                        // connecting, so its state shouldn't be overridden.
+                        if this.state.get() != S::Connecting(Rc::clone(&info)) {
+                            continue;
                         }
-                        Err(e) => {
-                            this.state.set(S::Lost(
-                                ConnectionLostReason::ConnectError(e),
-                                info,
-                            ));
+                        match res {
+                            Ok(()) => {
+                                this.state.set(S::Authorizing(info));
+                            }
+                            Err(e) => {
+                                this.state.set(S::Lost(
+                                    ConnectionLostReason::ConnectError(e),
+                                    info,
+                                ));
+                            }
                         }
-                    },
+                    }
                     S::Authorizing(info) => {
                         this.client.authorize(
                             info.room_id.clone(),
@@ -426,6 +436,10 @@ impl RpcSession for WebSocketRpcSession {
         Ok(())
     }
 
+    fn is_opened(&self) -> bool {
+        matches!(*self.state.borrow(), SessionState::Opened(_))
+    }
+
     fn subscribe(&self) -> LocalBoxStream<'static, Event> {
         let (tx, rx) = mpsc::unbounded();
         self.event_txs.borrow_mut().push(tx);
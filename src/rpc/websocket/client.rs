@@ -50,6 +50,10 @@ pub enum ClientDisconnect {
     ///
     /// [`WebSocketRpcSession`]: crate::rpc::WebSocketRpcSession
     SessionUnexpectedlyDropped,
+
+    /// Connection with a media server was lost and reconnecting to it has
+    /// been given up.
+    ConnectionLost,
 }
 
 impl ClientDisconnect {
@@ -60,7 +64,8 @@ impl ClientDisconnect {
             Self::RoomUnexpectedlyDropped
             | Self::RpcClientUnexpectedlyDropped
             | Self::RpcTransportUnexpectedlyDropped
-            | Self::SessionUnexpectedlyDropped => true,
+            | Self::SessionUnexpectedlyDropped
+            | Self::ConnectionLost => true,
             Self::RoomClosed => false,
         }
     }
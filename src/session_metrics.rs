@@ -0,0 +1,194 @@
+//! Accounting of the connection losses and recoveries happened during a
+//! [`Room`] lifetime.
+//!
+//! [`Room`]: crate::room::Room
+
+use std::{cell::Cell, time::Duration};
+
+use crate::platform;
+
+/// Counters of the connection losses and recoveries happened during a
+/// [`Room`] lifetime, accumulated across all its reconnects.
+///
+/// [`Room`]: crate::room::Room
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct SessionMetrics {
+    /// Number of the losses of a connection to a media server.
+    pub connection_losses: u32,
+
+    /// Total time spent without a connection to a media server after its
+    /// losses, including the ongoing one, if any.
+    pub disconnected: Duration,
+
+    /// Number of the ICE restarts requested by a media server.
+    pub ice_restarts: u32,
+
+    /// Number of the lost sessions restored by reconnecting to a media server.
+    pub session_restores: u32,
+
+    /// Number of the new sessions started by joining the [`Room`].
+    ///
+    /// Rejoining the [`Room`] after a connection loss restores its lost
+    /// session too, so it's counted in the `session_restores` as well.
+    ///
+    /// [`Room`]: crate::room::Room
+    pub new_sessions: u32,
+}
+
+/// Accumulator of [`SessionMetrics`] driven by the connection state changes
+/// of a [`Room`].
+///
+/// Subsequent connection losses without a recovery between them (like failed
+/// reconnection attempts) are counted as a single one.
+///
+/// [`Room`]: crate::room::Room
+#[derive(Debug, Default)]
+pub struct SessionMetricsCounter {
+    /// [`SessionMetrics`] accumulated so far, excluding the ongoing
+    /// disconnection.
+    metrics: Cell<SessionMetrics>,
+
+    /// Time the ongoing disconnection has started at, if any.
+    lost_at: Cell<Option<Duration>>,
+}
+
+impl SessionMetricsCounter {
+    /// Returns the accumulated [`SessionMetrics`].
+    #[must_use]
+    pub fn metrics(&self) -> SessionMetrics {
+        self.metrics_at(platform::now())
+    }
+
+    /// Accounts a loss of the connection to a media server.
+    pub fn connection_lost(&self) {
+        self.connection_lost_at(platform::now());
+    }
+
+    /// Accounts a lost session restored by reconnecting to a media server.
+    pub fn session_restored(&self) {
+        self.session_restored_at(platform::now());
+    }
+
+    /// Accounts a new session started by joining a [`Room`].
+    ///
+    /// [`Room`]: crate::room::Room
+    pub fn session_started(&self) {
+        self.update(|m| m.new_sessions += 1);
+    }
+
+    /// Accounts an ICE restart requested by a media server.
+    pub fn ice_restarted(&self) {
+        self.update(|m| m.ice_restarts += 1);
+    }
+
+    /// Returns [`SessionMetrics`] accumulated by the provided time.
+    fn metrics_at(&self, now: Duration) -> SessionMetrics {
+        let mut metrics = self.metrics.get();
+        if let Some(lost_at) = self.lost_at.get() {
+            metrics.disconnected += now.saturating_sub(lost_at);
+        }
+        metrics
+    }
+
+    /// Accounts a loss of the connection happened at the provided time.
+    fn connection_lost_at(&self, at: Duration) {
+        if self.lost_at.get().is_none() {
+            self.lost_at.set(Some(at));
+            self.update(|m| m.connection_losses += 1);
+        }
+    }
+
+    /// Accounts a lost session restored at the provided time.
+    fn session_restored_at(&self, at: Duration) {
+        let lost_at = self.lost_at.take();
+        self.update(|m| {
+            if let Some(lost_at) = lost_at {
+                m.disconnected += at.saturating_sub(lost_at);
+            }
+            m.session_restores += 1;
+        });
+    }
+
+    /// Applies the provided function to the accumulated [`SessionMetrics`].
+    fn update(&self, f: impl FnOnce(&mut SessionMetrics)) {
+        let mut metrics = self.metrics.get();
+        f(&mut metrics);
+        self.metrics.set(metrics);
+    }
+}
+
+#[cfg(test)]
+mod spec {
+    use std::time::Duration;
+
+    use super::{SessionMetrics, SessionMetricsCounter};
+
+    #[test]
+    fn accumulates_across_reconnects() {
+        let counter = SessionMetricsCounter::default();
+        counter.session_started();
+
+        counter.connection_lost_at(Duration::from_secs(10));
+        counter.session_restored_at(Duration::from_secs(13));
+        counter.ice_restarted();
+        counter.connection_lost_at(Duration::from_secs(20));
+        counter.session_restored_at(Duration::from_secs(25));
+        counter.ice_restarted();
+
+        assert_eq!(
+            counter.metrics_at(Duration::from_secs(100)),
+            SessionMetrics {
+                connection_losses: 2,
+                disconnected: Duration::from_secs(8),
+                ice_restarts: 2,
+                session_restores: 2,
+                new_sessions: 1,
+            },
+        );
+    }
+
+    #[test]
+    fn counts_failed_reconnection_attempts_as_single_loss() {
+        let counter = SessionMetricsCounter::default();
+        counter.session_started();
+
+        counter.connection_lost_at(Duration::from_secs(10));
+        counter.connection_lost_at(Duration::from_secs(12));
+        counter.connection_lost_at(Duration::from_secs(15));
+        assert_eq!(
+            counter.metrics_at(Duration::from_secs(16)),
+            SessionMetrics {
+                connection_losses: 1,
+                disconnected: Duration::from_secs(6),
+                new_sessions: 1,
+                ..SessionMetrics::default()
+            },
+        );
+
+        counter.session_restored_at(Duration::from_secs(18));
+        assert_eq!(
+            counter.metrics_at(Duration::from_secs(50)),
+            SessionMetrics {
+                connection_losses: 1,
+                disconnected: Duration::from_secs(8),
+                session_restores: 1,
+                new_sessions: 1,
+                ..SessionMetrics::default()
+            },
+        );
+    }
+
+    #[test]
+    fn counts_new_sessions_separately_from_restores() {
+        let counter = SessionMetricsCounter::default();
+        counter.session_started();
+        counter.connection_lost_at(Duration::from_secs(1));
+        counter.session_restored_at(Duration::from_secs(2));
+        counter.session_started();
+
+        let metrics = counter.metrics_at(Duration::from_secs(3));
+        assert_eq!(metrics.new_sessions, 2);
+        assert_eq!(metrics.session_restores, 1);
+        assert_eq!(metrics.disconnected, Duration::from_secs(1));
+    }
+}
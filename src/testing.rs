@@ -0,0 +1,299 @@
+//! In-memory media server and [`platform::RpcTransport`] for exercising the
+//! RPC layer in tests without a real media server.
+
+use std::{
+    cell::{Cell, RefCell},
+    fmt,
+    rc::{Rc, Weak},
+};
+
+use async_trait::async_trait;
+use futures::{channel::mpsc, stream::LocalBoxStream};
+use medea_client_api_proto::{
+    ClientMsg, Command, Event, RpcSettings, ServerMsg,
+};
+use medea_reactive::ObservableCell;
+use tracerr::Traced;
+
+use crate::{
+    platform::{self, TransportError, TransportState},
+    rpc::{ApiUrl, ClientDisconnect, CloseMsg},
+};
+
+/// Close code of a [WebSocket] connection closed abnormally.
+///
+/// [WebSocket]: https://developer.mozilla.org/docs/Web/API/WebSocket
+const ABNORMAL_CLOSE_CODE: u16 = 1006;
+
+/// In-memory media server accepting connections of the [`InMemoryTransport`]s
+/// created by its [`InMemoryServer::transport_factory()`].
+///
+/// Performs the handshake on its own: sends [`RpcSettings`] once a
+/// connection is opened, and confirms every [`Command::JoinRoom`] with an
+/// [`Event::RoomJoined`]. Sends no [`ServerMsg::Ping`]s, so its
+/// [`RpcSettings::idle_timeout_ms`] should outlast a test.
+#[derive(Clone, Debug)]
+pub struct InMemoryServer(Rc<RefCell<ServerState>>);
+
+/// Inner state of an [`InMemoryServer`].
+#[derive(Debug)]
+struct ServerState {
+    /// [`RpcSettings`] sent to every opened connection.
+    rpc_settings: RpcSettings,
+
+    /// Indicator whether new connections are accepted.
+    is_available: bool,
+
+    /// Number of the connections opened so far.
+    connections: usize,
+
+    /// Currently opened connection, if any.
+    current: Option<Weak<Connection>>,
+
+    /// All the [`ClientMsg`]s received so far, in the order of their
+    /// receiving.
+    received: Vec<ClientMsg>,
+
+    /// [`ClientDisconnect`] reasons of all the connections closed by a
+    /// client so far.
+    close_reasons: Vec<ClientDisconnect>,
+}
+
+impl Default for InMemoryServer {
+    fn default() -> Self {
+        Self::new(RpcSettings {
+            idle_timeout_ms: 60_000,
+            ping_interval_ms: 10_000,
+        })
+    }
+}
+
+impl InMemoryServer {
+    /// Creates a new [`InMemoryServer`] sending the provided [`RpcSettings`]
+    /// to its connections.
+    #[must_use]
+    pub fn new(rpc_settings: RpcSettings) -> Self {
+        Self(Rc::new(RefCell::new(ServerState {
+            rpc_settings,
+            is_available: true,
+            connections: 0,
+            current: None,
+            received: Vec::new(),
+            close_reasons: Vec::new(),
+        })))
+    }
+
+    /// Returns a factory of [`InMemoryTransport`]s connecting to this
+    /// [`InMemoryServer`], to be provided to a [`Jason`].
+    ///
+    /// [`Jason`]: crate::jason::Jason
+    #[must_use]
+    pub fn transport_factory(
+        &self,
+    ) -> impl Fn() -> Rc<dyn platform::RpcTransport> + 'static {
+        let server = self.clone();
+        move || Rc::new(InMemoryTransport::new(server.clone()))
+    }
+
+    /// Makes this [`InMemoryServer`] accept or refuse new connections.
+    ///
+    /// Doesn't affect the currently opened connection.
+    pub fn set_available(&self, is_available: bool) {
+        self.0.borrow_mut().is_available = is_available;
+    }
+
+    /// Returns number of the connections opened to this [`InMemoryServer`]
+    /// so far.
+    #[must_use]
+    pub fn connections_count(&self) -> usize {
+        self.0.borrow().connections
+    }
+
+    /// Returns all the [`ClientMsg`]s received by this [`InMemoryServer`] so
+    /// far, in the order of their receiving.
+    #[must_use]
+    pub fn received(&self) -> Vec<ClientMsg> {
+        self.0.borrow().received.clone()
+    }
+
+    /// Returns [`ClientDisconnect`] reasons of all the connections closed by
+    /// a client so far, in the order of their closing.
+    #[must_use]
+    pub fn close_reasons(&self) -> Vec<ClientDisconnect> {
+        self.0.borrow().close_reasons.clone()
+    }
+
+    /// Sends the provided [`ServerMsg`] via the currently opened connection.
+    ///
+    /// Returns `false` if there is no opened connection.
+    pub fn send(&self, msg: ServerMsg) -> bool {
+        let Some(conn) = self.current() else {
+            return false;
+        };
+        conn.deliver(&msg);
+        true
+    }
+
+    /// Closes the currently opened connection with the provided
+    /// [`CloseMsg`], if any.
+    ///
+    /// [`CloseMsg::Abnormal`] imitates a connection loss, so a client is
+    /// expected to reconnect.
+    pub fn close_connection(&self, msg: CloseMsg) {
+        if let Some(conn) = self.0.borrow_mut().current.take() {
+            if let Some(conn) = conn.upgrade() {
+                conn.state.set(TransportState::Closed(msg));
+            }
+        }
+    }
+
+    /// Closes the currently opened connection abnormally, imitating a
+    /// connection loss.
+    pub fn drop_connection(&self) {
+        self.close_connection(CloseMsg::Abnormal(ABNORMAL_CLOSE_CODE));
+    }
+
+    /// Returns the currently opened connection, if any.
+    fn current(&self) -> Option<Rc<Connection>> {
+        self.0.borrow().current.as_ref().and_then(Weak::upgrade)
+    }
+
+    /// Handles the provided [`ClientMsg`] received via the provided
+    /// connection.
+    fn receive(&self, conn: &Connection, msg: &ClientMsg) {
+        self.0.borrow_mut().received.push(msg.clone());
+        if let ClientMsg::Command {
+            room_id,
+            command: Command::JoinRoom { member_id, .. },
+        } = msg
+        {
+            conn.deliver(&ServerMsg::Event {
+                room_id: room_id.clone(),
+                event: Event::RoomJoined {
+                    member_id: member_id.clone(),
+                },
+            });
+        }
+    }
+}
+
+/// [`platform::RpcTransport`] connecting to an [`InMemoryServer`].
+#[derive(Debug)]
+pub struct InMemoryTransport {
+    /// [`InMemoryServer`] this [`InMemoryTransport`] connects to.
+    server: InMemoryServer,
+
+    /// [`Connection`] of this [`InMemoryTransport`] shared with its
+    /// [`InMemoryServer`].
+    inner: Rc<Connection>,
+}
+
+/// Connection of an [`InMemoryTransport`] to an [`InMemoryServer`].
+struct Connection {
+    /// Current [`TransportState`].
+    state: ObservableCell<TransportState>,
+
+    /// Subscribers for the [`ServerMsg`]s received from the
+    /// [`InMemoryServer`].
+    on_message_subs: RefCell<Vec<mpsc::UnboundedSender<ServerMsg>>>,
+
+    /// Reason of closing provided by a client, recorded by the
+    /// [`InMemoryServer`] once the [`InMemoryTransport`] is dropped.
+    close_reason: Cell<Option<ClientDisconnect>>,
+}
+
+impl fmt::Debug for Connection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Connection")
+            .field("state", &self.state)
+            .field("close_reason", &self.close_reason)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Connection {
+    /// Delivers the provided [`ServerMsg`] to all the subscribers.
+    fn deliver(&self, msg: &ServerMsg) {
+        self.on_message_subs
+            .borrow_mut()
+            .retain(|sub| sub.unbounded_send(msg.clone()).is_ok());
+    }
+}
+
+impl InMemoryTransport {
+    /// Creates a new [`InMemoryTransport`] connecting to the provided
+    /// [`InMemoryServer`].
+    #[must_use]
+    pub fn new(server: InMemoryServer) -> Self {
+        Self {
+            server,
+            inner: Rc::new(Connection {
+                state: ObservableCell::new(TransportState::Connecting),
+                on_message_subs: RefCell::default(),
+                close_reason: Cell::default(),
+            }),
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl platform::RpcTransport for InMemoryTransport {
+    async fn connect(&self, _: ApiUrl) -> Result<(), Traced<TransportError>> {
+        let rpc_settings = {
+            let mut server = self.server.0.borrow_mut();
+            if !server.is_available {
+                drop(server);
+                self.inner.state.set(TransportState::Closed(
+                    CloseMsg::Abnormal(ABNORMAL_CLOSE_CODE),
+                ));
+                return Err(tracerr::new!(TransportError::InitSocket));
+            }
+            server.connections += 1;
+            server.current = Some(Rc::downgrade(&self.inner));
+            server.rpc_settings
+        };
+        self.inner.state.set(TransportState::Open);
+        self.inner.deliver(&ServerMsg::RpcSettings(rpc_settings));
+        Ok(())
+    }
+
+    fn on_message(&self) -> LocalBoxStream<'static, ServerMsg> {
+        let (tx, rx) = mpsc::unbounded();
+        self.inner.on_message_subs.borrow_mut().push(tx);
+        Box::pin(rx)
+    }
+
+    fn set_close_reason(&self, reason: ClientDisconnect) {
+        self.inner.close_reason.set(Some(reason));
+    }
+
+    fn send(&self, msg: &ClientMsg) -> Result<(), Traced<TransportError>> {
+        if *self.inner.state.borrow() != TransportState::Open {
+            return Err(tracerr::new!(TransportError::ClosedSocket));
+        }
+        self.server.receive(&self.inner, msg);
+        Ok(())
+    }
+
+    fn on_state_change(&self) -> LocalBoxStream<'static, TransportState> {
+        self.inner.state.subscribe()
+    }
+}
+
+impl Drop for InMemoryTransport {
+    /// Detaches this [`InMemoryTransport`] from its [`InMemoryServer`], like a
+    /// closed socket does.
+    fn drop(&mut self) {
+        let mut server = self.server.0.borrow_mut();
+        if let Some(reason) = self.inner.close_reason.get() {
+            server.close_reasons.push(reason);
+        }
+        let is_current = server
+            .current
+            .as_ref()
+            .map_or(false, |c| c.ptr_eq(&Rc::downgrade(&self.inner)));
+        if is_current {
+            server.current = None;
+        }
+    }
+}
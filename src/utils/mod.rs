@@ -8,8 +8,10 @@ mod resettable_delay;
 
 use std::future::Future;
 
-use derive_more::From;
-use futures::future::{self, AbortHandle};
+use derive_more::{Display, From};
+use futures::future::{
+    self, AbortHandle, AbortRegistration, Abortable, Aborted,
+};
 use medea_reactive::Guarded;
 
 #[doc(inline)]
@@ -31,6 +33,40 @@ impl Drop for TaskHandle {
     }
 }
 
+/// Error of an operation, which can be aborted via an [`AbortHandle`].
+#[derive(Clone, Debug, Display)]
+pub enum AbortableError<E> {
+    /// Operation has been aborted via its [`AbortHandle`] before completion.
+    #[display(fmt = "Operation has been aborted")]
+    Aborted,
+
+    /// Operation has failed on its own.
+    #[display(fmt = "{}", _0)]
+    Failed(E),
+}
+
+/// Resolves the provided [`Future`], unless it's aborted via an [`AbortHandle`]
+/// of the provided [`AbortRegistration`] earlier, in which case the provided
+/// [`Future`] is dropped.
+///
+/// # Errors
+///
+/// With [`AbortableError::Aborted`] if the provided [`Future`] is aborted.
+///
+/// With [`AbortableError::Failed`] if the provided [`Future`] fails.
+pub async fn abortable<F, T, E>(
+    fut: F,
+    abort: AbortRegistration,
+) -> Result<T, AbortableError<E>>
+where
+    F: Future<Output = Result<T, E>>,
+{
+    Abortable::new(fut, abort)
+        .await
+        .map_err(|Aborted| AbortableError::Aborted)?
+        .map_err(AbortableError::Failed)
+}
+
 /// Tries to upgrade [`Weak`] reference breaks cycle if upgrade fails.
 macro_rules! upgrade_or_break {
     ($weak:tt) => {
@@ -2,10 +2,13 @@
 
 use medea_client_api_proto::{MediaSourceKind, VideoSettings};
 use medea_jason::{
+    api,
     media::{
+        constraints::{ConstrainString, ConstrainU32},
         AudioTrackConstraints, DeviceVideoTrackConstraints,
-        DisplayVideoTrackConstraints, MediaKind, MediaManager,
-        MediaStreamSettings, MultiSourceTracksConstraints, VideoSource,
+        DisplayVideoTrackConstraints, LocalTracksConstraints, MediaKind,
+        MediaManager, MediaStreamSettings, MultiSourceTracksConstraints,
+        VideoSource,
     },
     platform::get_property_by_name,
 };
@@ -14,7 +17,7 @@ use wasm_bindgen_futures::JsFuture;
 use wasm_bindgen_test::*;
 use web_sys::{MediaDeviceInfo, MediaDeviceKind};
 
-use crate::{is_firefox, window};
+use crate::{is_firefox, jsval_cast, window};
 
 wasm_bindgen_test_configure!(run_in_browser);
 
@@ -465,6 +468,121 @@ async fn merge_audio() {}
 #[wasm_bindgen_test]
 async fn merge_video() {}
 
+// 1. Set default device video constraints with device ID, width and height;
+// 2. Set user device video constraints with device ID and width;
+// 3. Merge defaults into user constraints;
+// 4. Assert that user constraints win and missing ones are taken from defaults.
+#[wasm_bindgen_test]
+async fn merge_defaults_user_constraints_win() {
+    let mut defaults = MediaStreamSettings::new();
+    let mut default_video = DeviceVideoTrackConstraints::new();
+    default_video.device_id("default-camera".into());
+    default_video.exact_width(1280);
+    default_video.exact_height(720);
+    defaults.device_video(default_video);
+
+    let mut settings = MediaStreamSettings::new();
+    let mut video = DeviceVideoTrackConstraints::new();
+    video.device_id("user-camera".into());
+    video.ideal_width(640);
+    settings.device_video(video);
+
+    settings.merge_defaults(&defaults);
+
+    let merged = settings.get_device_video().unwrap();
+    assert_eq!(
+        merged.device_id,
+        Some(ConstrainString::Exact("user-camera".into())),
+    );
+    assert_eq!(merged.width, Some(ConstrainU32::Ideal(640)));
+    assert_eq!(merged.height, Some(ConstrainU32::Exact(720)));
+}
+
+// 1. Set default audio and device video constraints;
+// 2. Set user audio constraints only;
+// 3. Merge defaults into user constraints;
+// 4. Assert that default device ID is applied as ideal constraint;
+// 5. Assert that device video is not constrained by defaults.
+#[wasm_bindgen_test]
+async fn merge_defaults_doesnt_constrain_disabled_sources() {
+    let mut defaults = MediaStreamSettings::new();
+    let mut default_audio = AudioTrackConstraints::new();
+    default_audio.device_id("default-mic".into());
+    defaults.audio(default_audio);
+    defaults.device_video(DeviceVideoTrackConstraints::new());
+
+    let mut settings = MediaStreamSettings::new();
+    settings.audio(AudioTrackConstraints::new());
+
+    settings.merge_defaults(&defaults);
+
+    assert_eq!(
+        settings.get_audio().device_id,
+        Some(ConstrainString::Ideal("default-mic".into())),
+    );
+    assert!(settings.get_device_video().is_none());
+    assert!(!settings.is_device_video_enabled());
+}
+
+// 1. Set default audio constraints with non-existent device ID;
+// 2. Set user audio constraints without device ID;
+// 3. Get effective constraints;
+// 4. Assert that media is obtained from some other device.
+#[wasm_bindgen_test]
+async fn merge_defaults_with_disappeared_device() {
+    let mut settings = MediaStreamSettings::new();
+    settings.audio(AudioTrackConstraints::new());
+    let constraints = LocalTracksConstraints::from(settings);
+
+    let mut defaults = MediaStreamSettings::new();
+    let mut default_audio = AudioTrackConstraints::new();
+    default_audio.device_id("disappeared-mic".into());
+    defaults.audio(default_audio);
+    constraints.set_defaults(defaults);
+
+    let media_manager = MediaManager::default();
+    let mut tracks = media_manager
+        .get_tracks(constraints.effective())
+        .await
+        .unwrap();
+
+    assert_eq!(tracks.len(), 1);
+
+    let track = tracks.pop().unwrap().0;
+
+    assert_eq!(track.kind(), MediaKind::Audio);
+    assert_ne!(track.platform_track().device_id(), "disappeared-mic");
+}
+
+// 1. Set captured device video constraints with device ID and exact width;
+// 2. Assert that equal and looser constraints are covered by them;
+// 3. Assert that stricter and different constraints are not covered by them.
+#[wasm_bindgen_test]
+async fn looser_constraints_are_covered() {
+    let mut captured = DeviceVideoTrackConstraints::new();
+    captured.device_id("camera".into());
+    captured.exact_width(640);
+
+    assert!(captured.is_covered_by(&captured));
+
+    let mut looser = DeviceVideoTrackConstraints::new();
+    looser.width_in_range(320, 1280);
+    looser.ideal_height(480);
+    assert!(looser.is_covered_by(&captured));
+
+    let mut stricter = captured.clone();
+    stricter.exact_height(480);
+    assert!(!stricter.is_covered_by(&captured));
+
+    let mut other_device = DeviceVideoTrackConstraints::new();
+    other_device.device_id("other-camera".into());
+    assert!(!other_device.is_covered_by(&captured));
+
+    let mut other_width = DeviceVideoTrackConstraints::new();
+    other_width.width_in_range(800, 1280);
+    assert!(!other_width.is_covered_by(&captured));
+}
+
 // Maps undefined to None.
 fn js_val_to_option(val: JsValue) -> Option<JsValue> {
     if val.is_undefined() {
@@ -603,3 +721,95 @@ async fn simultaneous_device_and_display() {
         MediaSourceKind::Device,
     );
 }
+
+/// Calls the `name`d method of the provided JS `object` with the provided
+/// `args`, the same way JS code does.
+fn call_js_method(
+    object: &JsValue,
+    name: &str,
+    args: &[JsValue],
+) -> Result<JsValue, JsValue> {
+    let method = js_sys::Function::from(js_sys::Reflect::get(
+        object,
+        &JsValue::from_str(name),
+    )?);
+    method.apply(object, &args.iter().collect())
+}
+
+// 1. Chain device video constraints setters from JS;
+// 2. Assert that constraints are equal to the ones set one by one.
+#[wasm_bindgen_test]
+async fn device_video_constraints_are_chained_from_js() {
+    let constraints = JsValue::from(api::DeviceVideoTrackConstraints::new());
+    let constraints =
+        call_js_method(&constraints, "with_ideal_width", &[1280.into()])
+            .unwrap();
+    let constraints =
+        call_js_method(&constraints, "with_ideal_frame_rate", &[30.into()])
+            .unwrap();
+    let constraints: api::DeviceVideoTrackConstraints =
+        jsval_cast(constraints, "DeviceVideoTrackConstraints").unwrap();
+
+    let mut expected = DeviceVideoTrackConstraints::new();
+    expected.ideal_width(1280);
+    expected.ideal_frame_rate(30);
+    assert_eq!(DeviceVideoTrackConstraints::from(constraints), expected);
+}
+
+// 1. Chain audio, device video and display video constraints into
+//    `MediaStreamSettings` from JS;
+// 2. Assert that settings are equal to the ones set one by one.
+#[wasm_bindgen_test]
+async fn media_stream_settings_are_chained_from_js() {
+    let audio = JsValue::from(
+        api::AudioTrackConstraints::new()
+            .with_device_id("mic".into())
+            .with_echo_cancellation(true),
+    );
+    let device_video = JsValue::from(
+        api::DeviceVideoTrackConstraints::new().with_height_in_range(360, 720),
+    );
+    let display_video = JsValue::from(
+        api::DisplayVideoTrackConstraints::new().with_exact_frame_rate(15),
+    );
+
+    let settings = JsValue::from(api::MediaStreamSettings::new());
+    let settings = call_js_method(&settings, "with_audio", &[audio]).unwrap();
+    let settings =
+        call_js_method(&settings, "with_device_video", &[device_video])
+            .unwrap();
+    let settings =
+        call_js_method(&settings, "with_display_video", &[display_video])
+            .unwrap();
+    let settings: api::MediaStreamSettings =
+        jsval_cast(settings, "MediaStreamSettings").unwrap();
+
+    let mut expected = MediaStreamSettings::new();
+    let mut audio = AudioTrackConstraints::new();
+    audio.device_id("mic".into());
+    audio.echo_cancellation(true);
+    expected.audio(audio);
+    let mut device_video = DeviceVideoTrackConstraints::new();
+    device_video.height_in_range(360, 720);
+    expected.device_video(device_video);
+    let mut display_video = DisplayVideoTrackConstraints::new();
+    display_video.exact_frame_rate(15);
+    expected.display_video(display_video);
+    assert_eq!(MediaStreamSettings::from(settings), expected);
+}
+
+// 1. Call a chaining setter of audio constraints from JS;
+// 2. Assert that the consumed JS object cannot be used anymore.
+#[wasm_bindgen_test]
+async fn chained_constraints_are_consumed_in_js() {
+    let constraints = JsValue::from(api::AudioTrackConstraints::new());
+    call_js_method(&constraints, "with_noise_suppression", &[JsValue::TRUE])
+        .unwrap();
+
+    assert!(call_js_method(
+        &constraints,
+        "with_auto_gain_control",
+        &[JsValue::TRUE],
+    )
+    .is_err());
+}
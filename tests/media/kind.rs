@@ -0,0 +1,54 @@
+#![cfg(target_arch = "wasm32")]
+
+use medea_jason::api::{
+    self, err::FormatException, MediaKind, MediaSourceKind,
+};
+use wasm_bindgen_test::*;
+
+use crate::jsval_cast;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn converts_media_kinds() {
+    for (kind, name) in
+        [(MediaKind::Audio, "audio"), (MediaKind::Video, "video")]
+    {
+        assert_eq!(api::media_kind_to_string(kind), name);
+        assert_eq!(api::media_kind_from_string(name).unwrap(), kind);
+    }
+}
+
+#[wasm_bindgen_test]
+fn converts_media_source_kinds() {
+    for (kind, name) in [
+        (MediaSourceKind::Device, "device"),
+        (MediaSourceKind::Display, "display"),
+    ] {
+        assert_eq!(api::media_source_kind_to_string(kind), name);
+        assert_eq!(api::media_source_kind_from_string(name).unwrap(), kind);
+    }
+}
+
+#[wasm_bindgen_test]
+fn rejects_unknown_media_kind() {
+    let err = api::media_kind_from_string("Audio").unwrap_err();
+    let err: FormatException = jsval_cast(err, "FormatException").unwrap();
+
+    assert_eq!(
+        err.message(),
+        "Unknown `MediaKind` name: `Audio`, expected one of: `audio`, `video`",
+    );
+}
+
+#[wasm_bindgen_test]
+fn rejects_unknown_media_source_kind() {
+    let err = api::media_source_kind_from_string("screen").unwrap_err();
+    let err: FormatException = jsval_cast(err, "FormatException").unwrap();
+
+    assert_eq!(
+        err.message(),
+        "Unknown `MediaSourceKind` name: `screen`, expected one of: \
+         `device`, `display`",
+    );
+}
@@ -1,6 +1,7 @@
 #![cfg(target_arch = "wasm32")]
 
 use js_sys::Array as JsArray;
+use wasm_bindgen::JsValue;
 use wasm_bindgen_futures::JsFuture;
 use wasm_bindgen_test::*;
 use web_sys as sys;
@@ -12,9 +13,9 @@ use medea_jason::{
         LocalMediaInitExceptionKind,
     },
     media::{
-        AudioTrackConstraints, DeviceVideoTrackConstraints,
+        AudioTrackConstraints, ContentHint, DeviceVideoTrackConstraints,
         DisplayVideoTrackConstraints, GetUserMediaError, InitLocalTracksError,
-        MediaKind, MediaManager, MediaStreamSettings,
+        MediaDeviceKind, MediaKind, MediaManager, MediaStreamSettings,
     },
 };
 
@@ -36,6 +37,70 @@ async fn get_media_devices_info() {
     assert!(devices.length() >= 2);
 }
 
+/// Enumerates devices via the [`api::MediaManagerHandle`] of the provided
+/// [`MediaManager`], guaranteeing no [getUserMedia()][1] request is made.
+///
+/// [1]: https://tinyurl.com/w3-streams#dom-mediadevices-getusermedia
+async fn enumerate_devices_quiet(
+    media_manager: &MediaManager,
+) -> Vec<api::MediaDeviceDetails> {
+    let devices = JsFuture::from(
+        api::MediaManagerHandle::from(media_manager.new_handle())
+            .enumerate_devices_quiet(),
+    )
+    .await
+    .unwrap();
+    JsArray::from(&devices)
+        .iter()
+        .map(|d| jsval_cast(d, "MediaDeviceDetails").unwrap())
+        .collect()
+}
+
+#[wasm_bindgen_test]
+async fn quiet_enumeration_reports_redacted_labels() {
+    let mock_navigator = MockNavigator::new();
+    mock_navigator.set_enumerate_devices_returns(
+        js_sys::JSON::parse(
+            r#"[
+                {"deviceId": "", "kind": "audioinput", "label": "",
+                 "groupId": ""},
+                {"deviceId": "", "kind": "videoinput", "label": "",
+                 "groupId": ""}
+            ]"#,
+        )
+        .unwrap(),
+    );
+    let media_manager = MediaManager::default();
+
+    let devices = enumerate_devices_quiet(&media_manager).await;
+    assert_eq!(devices.len(), 2);
+    assert!(devices.iter().all(|d| d.is_label_redacted()));
+    assert_eq!(mock_navigator.get_user_media_requests_count(), 0);
+
+    mock_navigator.stop();
+}
+
+#[wasm_bindgen_test]
+async fn quiet_enumeration_never_requests_user_media() {
+    let mock_navigator = MockNavigator::new();
+    let media_manager = MediaManager::default();
+    let handle = api::MediaManagerHandle::from(media_manager.new_handle());
+    let constraints = {
+        let mut constraints = api::MediaStreamSettings::new();
+        constraints.audio(api::AudioTrackConstraints::new());
+        constraints
+    };
+    drop(JsFuture::from(handle.init_local_tracks(&constraints)).await);
+    assert_eq!(mock_navigator.get_user_media_requests_count(), 1);
+
+    let devices = enumerate_devices_quiet(&media_manager).await;
+    assert!(!devices.is_empty());
+    assert!(devices.iter().all(|d| !d.is_label_redacted()));
+    assert_eq!(mock_navigator.get_user_media_requests_count(), 1);
+
+    mock_navigator.stop();
+}
+
 #[wasm_bindgen_test]
 async fn failed_get_media_devices_info() {
     let mock_navigator = MockNavigator::new();
@@ -145,6 +210,83 @@ async fn failed_get_user_media2() {
     }
 }
 
+/// Returns `NotReadableError` thrown by a busy camera.
+fn not_readable_error() -> JsValue {
+    let error = js_sys::Error::new("Could not start video source");
+    error.set_name("NotReadableError");
+    error.into()
+}
+
+#[wasm_bindgen_test]
+async fn retries_transient_get_user_media_failures() {
+    let mock_navigator = MockNavigator::new();
+    mock_navigator.fail_get_user_media(not_readable_error(), 2);
+    let media_manager = MediaManager::default();
+    let handle = api::MediaManagerHandle::from(media_manager.new_handle());
+    handle.set_get_user_media_retries(3, 10).unwrap();
+    let constraints = {
+        let mut constraints = api::MediaStreamSettings::new();
+        constraints.device_video(api::DeviceVideoTrackConstraints::new());
+        constraints
+    };
+
+    let tracks = JsFuture::from(handle.init_local_tracks(&constraints)).await;
+    let requests = mock_navigator.get_user_media_requests_count();
+    mock_navigator.stop();
+
+    assert_eq!(JsArray::from(&tracks.unwrap()).length(), 1);
+    assert_eq!(requests, 3);
+}
+
+#[wasm_bindgen_test]
+async fn surfaces_transient_get_user_media_failure_if_retries_disabled() {
+    let mock_navigator = MockNavigator::new();
+    mock_navigator.fail_get_user_media(not_readable_error(), 1);
+    let media_manager = MediaManager::default();
+    let handle = api::MediaManagerHandle::from(media_manager.new_handle());
+    handle.set_get_user_media_retries(0, 10).unwrap();
+    let constraints = {
+        let mut constraints = api::MediaStreamSettings::new();
+        constraints.device_video(api::DeviceVideoTrackConstraints::new());
+        constraints
+    };
+
+    let err = JsFuture::from(handle.init_local_tracks(&constraints))
+        .await
+        .unwrap_err();
+    let requests = mock_navigator.get_user_media_requests_count();
+    mock_navigator.stop();
+
+    let err: LocalMediaInitException =
+        jsval_cast(err, "LocalMediaInitException").unwrap();
+    assert_eq!(err.kind(), LocalMediaInitExceptionKind::GetUserMediaFailed);
+    assert_eq!(err.cause().unwrap().name(), "NotReadableError");
+    assert_eq!(requests, 1);
+}
+
+#[wasm_bindgen_test]
+async fn does_not_retry_permanent_get_user_media_failures() {
+    let mock_navigator = MockNavigator::new();
+    let error = js_sys::Error::new("Permission denied");
+    error.set_name("NotAllowedError");
+    mock_navigator.fail_get_user_media(error.into(), 1);
+    let media_manager = MediaManager::default();
+    let handle = api::MediaManagerHandle::from(media_manager.new_handle());
+    handle.set_get_user_media_retries(3, 10).unwrap();
+    let constraints = {
+        let mut constraints = api::MediaStreamSettings::new();
+        constraints.device_video(api::DeviceVideoTrackConstraints::new());
+        constraints
+    };
+
+    let result = JsFuture::from(handle.init_local_tracks(&constraints)).await;
+    let requests = mock_navigator.get_user_media_requests_count();
+    mock_navigator.stop();
+
+    assert!(result.is_err());
+    assert_eq!(requests, 1);
+}
+
 /// 1. Do `media_manager.get_stream(caps)`
 /// 2. Only one `getUserMedia` request
 /// 3. Do `media_manager.get_stream(caps)`
@@ -227,6 +369,49 @@ async fn new_track_if_previous_dropped() {
     mock_navigator.stop();
 }
 
+/// 1. Do `media_manager.get_stream(caps)` with exact device video width;
+/// 2. Do `media_manager.get_stream(caps)` with looser width constraint;
+/// 3. Assert that same track was returned and no additional `getUserMedia`
+///    request was made;
+/// 4. Assert that track isn't published.
+#[wasm_bindgen_test]
+async fn same_track_for_looser_constraints() {
+    let mock_navigator = MockNavigator::new();
+
+    let media_manager = MediaManager::default();
+    let mut video = DeviceVideoTrackConstraints::new();
+    video.width_in_range(320, 1280);
+    let mut constraints = MediaStreamSettings::new();
+    constraints.device_video(video);
+
+    let (track1, track1_is_new) = media_manager
+        .get_tracks(constraints)
+        .await
+        .unwrap()
+        .pop()
+        .unwrap();
+    assert!(track1_is_new);
+    assert_eq!(mock_navigator.get_user_media_requests_count(), 1);
+
+    let mut video = DeviceVideoTrackConstraints::new();
+    video.width_in_range(160, 1920);
+    let mut constraints = MediaStreamSettings::new();
+    constraints.device_video(video);
+
+    let (track2, track2_is_new) = media_manager
+        .get_tracks(constraints)
+        .await
+        .unwrap()
+        .pop()
+        .unwrap();
+    assert!(!track2_is_new);
+    assert_eq!(track1.id(), track2.id());
+    assert_eq!(mock_navigator.get_user_media_requests_count(), 1);
+    assert!(!track2.is_published());
+
+    mock_navigator.stop();
+}
+
 /// 1. Do `media_manager.get_stream({audio:true, video:true}})`;
 /// 2. Do `media_manager.get_stream({audio:true}})`;
 /// 3. Do `media_manager.get_stream({video:true}})`;
@@ -334,6 +519,94 @@ async fn display_track_is_cached() {
     mock_navigator.stop();
 }
 
+/// Checks that [`ContentHint::Detail`] is applied to display tracks by default,
+/// while device tracks are left without any [`ContentHint`].
+#[wasm_bindgen_test]
+async fn default_content_hints_applied() {
+    if is_firefox() {
+        // contentHint and getDisplayMedia are not supported in ff atm
+        return;
+    }
+    let mock_navigator = MockNavigator::new();
+
+    let media_manager = MediaManager::default();
+    let mut constraints = MediaStreamSettings::new();
+    constraints.audio(AudioTrackConstraints::new());
+    constraints.display_video(DisplayVideoTrackConstraints::new());
+
+    let tracks = media_manager.get_tracks(constraints).await.unwrap();
+    assert_eq!(tracks.len(), 2);
+    for (track, _) in tracks {
+        match track.kind() {
+            MediaKind::Audio => assert_eq!(track.content_hint(), None),
+            MediaKind::Video => {
+                assert_eq!(track.content_hint(), Some(ContentHint::Detail));
+            }
+        }
+    }
+
+    mock_navigator.stop();
+}
+
+/// Checks that [`ContentHint`]s of constraints override the default ones.
+#[wasm_bindgen_test]
+async fn content_hints_from_constraints_applied() {
+    if is_firefox() {
+        // contentHint and getDisplayMedia are not supported in ff atm
+        return;
+    }
+    let mock_navigator = MockNavigator::new();
+
+    let media_manager = MediaManager::default();
+    let mut constraints = MediaStreamSettings::new();
+    let mut audio = AudioTrackConstraints::new();
+    audio.content_hint(ContentHint::Music);
+    constraints.audio(audio);
+    let mut display = DisplayVideoTrackConstraints::new();
+    display.content_hint(ContentHint::Motion);
+    constraints.display_video(display);
+
+    let tracks = media_manager.get_tracks(constraints).await.unwrap();
+    assert_eq!(tracks.len(), 2);
+    for (track, _) in tracks {
+        let expected = match track.kind() {
+            MediaKind::Audio => ContentHint::Music,
+            MediaKind::Video => ContentHint::Motion,
+        };
+        assert_eq!(track.content_hint(), Some(expected));
+    }
+
+    mock_navigator.stop();
+}
+
+/// Checks that a [`ContentHint`] not applicable to a track's kind is ignored.
+#[wasm_bindgen_test]
+async fn inapplicable_content_hint_ignored() {
+    if is_firefox() {
+        // contentHint is not supported in ff atm
+        return;
+    }
+    let mock_navigator = MockNavigator::new();
+
+    let media_manager = MediaManager::default();
+    let mut constraints = MediaStreamSettings::new();
+    constraints.audio(AudioTrackConstraints::new());
+
+    let (track, _) = media_manager
+        .get_tracks(constraints)
+        .await
+        .unwrap()
+        .pop()
+        .unwrap();
+    track.set_content_hint(ContentHint::Speech);
+    assert_eq!(track.content_hint(), Some(ContentHint::Speech));
+
+    track.set_content_hint(ContentHint::Text);
+    assert_eq!(track.content_hint(), Some(ContentHint::Speech));
+
+    mock_navigator.stop();
+}
+
 /// Check that error is thrown if stream obtained via gUM request contains ended
 /// track.
 #[wasm_bindgen_test]
@@ -382,3 +655,68 @@ async fn new_tracks_should_be_live() {
 
     mock_navigator.stop();
 }
+
+/// Checks that invalid constraints are rejected without any
+/// [getUserMedia()][1] request, listing all the invalid fields.
+///
+/// [1]: https://tinyurl.com/w3-streams#dom-mediadevices-getusermedia
+#[wasm_bindgen_test]
+async fn rejects_invalid_constraints() {
+    let mock_navigator = MockNavigator::new();
+    let media_manager = MediaManager::default();
+    let constraints = {
+        let mut audio = api::AudioTrackConstraints::new();
+        audio.device_id(String::new());
+        let mut video = api::DeviceVideoTrackConstraints::new();
+        video.exact_width(0);
+        video.height_in_range(1080, 720);
+        let mut constraints = api::MediaStreamSettings::new();
+        constraints.audio(audio);
+        constraints.device_video(video);
+        constraints
+    };
+    let result = JsFuture::from(
+        api::MediaManagerHandle::from(media_manager.new_handle())
+            .init_local_tracks(&constraints),
+    )
+    .await;
+
+    let err: LocalMediaInitException =
+        jsval_cast(result.unwrap_err(), "LocalMediaInitException").unwrap();
+    assert_eq!(err.kind(), LocalMediaInitExceptionKind::InvalidConstraints);
+    assert_eq!(
+        err.message(),
+        "Failed to get local tracks: Invalid media constraints: \
+         `audio.device_id` cannot be empty, `device_video.width` cannot be \
+         0, `device_video.height` has minimum 1080 greater than maximum 720",
+    );
+    assert!(err.cause().is_none());
+    assert_eq!(mock_navigator.get_user_media_requests_count(), 0);
+
+    mock_navigator.stop();
+}
+
+#[wasm_bindgen_test]
+async fn reset_capture_state_refreshes_stale_devices() {
+    let mock_navigator = MockNavigator::new();
+    mock_navigator.set_enumerate_devices_returns(JsArray::new().into());
+    let media_manager = MediaManager::default();
+    let handle = media_manager.new_handle();
+    assert!(!handle
+        .has_device(MediaDeviceKind::VideoInput)
+        .await
+        .unwrap());
+
+    // Devices enumerated without the access are still cached.
+    mock_navigator.stop();
+    assert!(!handle
+        .has_device(MediaDeviceKind::VideoInput)
+        .await
+        .unwrap());
+
+    handle.reset_capture_state(MediaKind::Video).await.unwrap();
+    assert!(handle
+        .has_device(MediaDeviceKind::VideoInput)
+        .await
+        .unwrap());
+}
@@ -1,4 +1,5 @@
 mod constraints;
+mod kind;
 mod manager;
 mod track;
 
@@ -12,7 +13,7 @@ use medea_client_api_proto::{
 use medea_jason::{
     media::{MediaDirection, MediaManager, RecvConstraints},
     peer::{LocalStreamUpdateCriteria, MediaConnections, SimpleTracksRequest},
-    platform::{RtcPeerConnection, TransceiverDirection},
+    platform::{RtcConfiguration, RtcPeerConnection, TransceiverDirection},
 };
 use wasm_bindgen_test::*;
 
@@ -24,7 +25,15 @@ wasm_bindgen_test_configure!(run_in_browser);
 async fn sendrecv_works() {
     let (tx, _rx) = mpsc::unbounded();
     let media_connections = MediaConnections::new(
-        Rc::new(RtcPeerConnection::new(Vec::new(), false).await.unwrap()),
+        Rc::new(
+            RtcPeerConnection::new(
+                Vec::new(),
+                false,
+                &RtcConfiguration::default(),
+            )
+            .await
+            .unwrap(),
+        ),
         tx,
     );
     let send_audio_track = Track {
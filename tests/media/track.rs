@@ -9,14 +9,15 @@ use futures::{
 use medea_jason::{
     api::MediaDirection,
     media::{
-        track::remote, DeviceVideoTrackConstraints, MediaManager,
-        MediaStreamSettings,
+        track::remote, DeviceVideoTrackConstraints, MediaKind, MediaManager,
+        MediaSourceKind, MediaStreamSettings,
     },
+    platform,
 };
 use wasm_bindgen::closure::Closure;
 use wasm_bindgen_test::*;
 
-use crate::{get_audio_track, timeout};
+use crate::{get_audio_track, get_video_track, timeout, MockNavigator};
 
 /// Assert that track is stopped when all strong refs are dropped.
 #[wasm_bindgen_test]
@@ -39,6 +40,39 @@ async fn track_autostop() {
     assert_eq!(Weak::strong_count(&weak_track), 0);
 }
 
+/// Assert that custom track is used without any `getUserMedia` request, and is
+/// not stopped when all strong refs are dropped.
+#[wasm_bindgen_test]
+async fn custom_track_is_not_autostopped() {
+    let media_manager = MediaManager::default();
+    let mut caps = MediaStreamSettings::new();
+    caps.device_video(DeviceVideoTrackConstraints::new());
+    let (source, _) =
+        media_manager.get_tracks(caps).await.unwrap().pop().unwrap();
+    // Independent `MediaStreamTrack`, so `source` doesn't affect it.
+    let sys_track =
+        web_sys::MediaStreamTrack::clone(source.as_ref().as_ref().as_ref());
+
+    let mock_navigator = MockNavigator::new();
+    let mut caps = MediaStreamSettings::new();
+    caps.set_custom_video_track(platform::MediaStreamTrack::new(
+        Clone::clone(&sys_track),
+        Some(MediaSourceKind::Device),
+    ));
+    let mut tracks = media_manager.get_tracks(caps).await.unwrap();
+    assert_eq!(mock_navigator.get_user_media_requests_count(), 0);
+    mock_navigator.stop();
+
+    assert_eq!(1, tracks.len());
+    let (custom_track, custom_track_is_new) = tracks.pop().unwrap();
+    assert!(!custom_track_is_new);
+    assert_eq!(custom_track.id(), sys_track.id());
+
+    custom_track.stop().await;
+    drop(custom_track);
+    assert!(sys_track.ready_state() == web_sys::MediaStreamTrackState::Live);
+}
+
 #[wasm_bindgen_test]
 async fn on_track_unmuted_works() {
     let api_track = get_audio_track().await;
@@ -111,3 +145,70 @@ async fn on_track_muted_works() {
     timeout(100, test_rx).await.unwrap().unwrap();
     timeout(100, dont_fire_rx.next()).await.unwrap_err();
 }
+
+/// Checks that `RemoteMediaTrack.get_track()` returns a clone outliving the
+/// freed `RemoteMediaTrack`, which is stopped along with the original track.
+#[wasm_bindgen_test]
+async fn get_track_returns_independent_clone() {
+    let api_track = get_video_track().await;
+    let core_track: remote::Track = api_track.clone().into();
+
+    let track = api_track.get_track();
+    assert_ne!(track.id(), core_track.id());
+    assert_eq!(api_track.get_track().id(), track.id());
+
+    let (test_tx, test_rx) = oneshot::channel();
+    api_track.on_stopped(
+        Closure::once_into_js(move || {
+            test_tx.send(()).unwrap();
+        })
+        .into(),
+    );
+    drop(api_track);
+    assert!(track.ready_state() == web_sys::MediaStreamTrackState::Live);
+
+    core_track.stop().await;
+
+    timeout(100, test_rx).await.unwrap().unwrap();
+    assert!(track.ready_state() == web_sys::MediaStreamTrackState::Ended);
+}
+
+/// Checks that `RemoteMediaTrack.set_jitter_buffer_target()` sets the
+/// `jitterBufferTarget` of the underlying `RTCRtpReceiver`, clamping the
+/// provided values.
+#[wasm_bindgen_test]
+async fn sets_jitter_buffer_target() {
+    let peer = platform::RtcPeerConnection::new(
+        Vec::new(),
+        false,
+        &platform::RtcConfiguration::default(),
+    )
+    .await
+    .unwrap();
+    let transceiver = peer
+        .add_transceiver(MediaKind::Audio, platform::TransceiverDirection::RECV)
+        .await;
+
+    let api_track = get_audio_track().await;
+    assert!(!api_track.is_jitter_buffer_target_supported());
+    let core_track: remote::Track = api_track.clone().into();
+    core_track.set_transceiver(transceiver);
+    assert!(api_track.is_jitter_buffer_target_supported());
+    assert_eq!(api_track.jitter_buffer_target(), None);
+
+    assert_eq!(api_track.set_jitter_buffer_target(125.0).unwrap(), 125.0);
+    assert_eq!(api_track.jitter_buffer_target(), Some(125.0));
+
+    assert_eq!(
+        api_track.set_jitter_buffer_target(10_000.0).unwrap(),
+        4000.0
+    );
+    assert_eq!(api_track.jitter_buffer_target(), Some(4000.0));
+    assert_eq!(
+        core_track.configured_jitter_buffer_target(),
+        Some(remote::Track::MAX_JITTER_BUFFER_TARGET),
+    );
+
+    assert_eq!(api_track.set_jitter_buffer_target(-5.0).unwrap(), 0.0);
+    assert_eq!(api_track.jitter_buffer_target(), Some(0.0));
+}
@@ -31,10 +31,30 @@ export class MockNavigator {
     window.navigator.mediaDevices.enumerateDevices = async function() {throw err}
   }
 
+  set setEnumerateDevicesReturns(devices) {
+    window.navigator.mediaDevices.enumerateDevices = async function() {return devices};
+  }
+
   set errorGetUserMedia(err) {
     window.navigator.mediaDevices.getUserMedia = async function() {throw err}
   }
 
+  failGetUserMedia(err, times) {
+    let self = this;
+    let failures = times;
+    window.navigator.mediaDevices.getUserMedia = async function(arg) {
+      self.getUserMediaInvocations++;
+      if (failures > 0) {
+        failures--;
+        throw err;
+      }
+      return await self._getUserMedia.call(
+        window.navigator.mediaDevices,
+        arg
+      );
+    }
+  }
+
   set errorGetDisplayMedia(err) {
     window.navigator.mediaDevices.getDisplayMedia = async function() {throw err}
   }
@@ -55,6 +75,25 @@ export class MockNavigator {
     window.navigator.mediaDevices.getDisplayMedia = async function() {return stream};
   }
 
+  set getUserMediaLatency(ms) {
+    let self = this;
+    this._capturedTracks = [];
+    window.navigator.mediaDevices.getUserMedia = async function(arg) {
+      self.getUserMediaInvocations++;
+      await new Promise((resolve) => setTimeout(resolve, ms));
+      let stream = await self._getUserMedia.call(
+        window.navigator.mediaDevices,
+        arg
+      );
+      self._capturedTracks.push(...stream.getTracks());
+      return stream;
+    }
+  }
+
+  get capturedTracks() {
+    return this._capturedTracks;
+  }
+
   stop() {
     window.navigator.mediaDevices.getUserMedia = this._getUserMedia;
     window.navigator.mediaDevices.getDisplayMedia = this._getDisplayMedia;
@@ -12,7 +12,7 @@ use medea_jason::{
         media_exchange_state, LocalStreamUpdateCriteria, MediaConnections,
         MediaStateControllable, SimpleTracksRequest,
     },
-    platform::RtcPeerConnection,
+    platform::{RtcConfiguration, RtcPeerConnection},
     utils::Updatable as _,
 };
 use wasm_bindgen_test::*;
@@ -30,7 +30,15 @@ async fn get_test_media_connections(
     let (tx, rx) = mpsc::unbounded();
     mem::forget(rx);
     let media_connections = MediaConnections::new(
-        Rc::new(RtcPeerConnection::new(Vec::new(), false).await.unwrap()),
+        Rc::new(
+            RtcPeerConnection::new(
+                Vec::new(),
+                false,
+                &RtcConfiguration::default(),
+            )
+            .await
+            .unwrap(),
+        ),
         tx,
     );
     let (audio_track, video_track) = get_test_unrequired_tracks();
@@ -85,7 +93,15 @@ async fn get_tracks_request1() {
     let (tx, rx) = mpsc::unbounded();
     mem::forget(rx);
     let media_connections = MediaConnections::new(
-        Rc::new(RtcPeerConnection::new(Vec::new(), false).await.unwrap()),
+        Rc::new(
+            RtcPeerConnection::new(
+                Vec::new(),
+                false,
+                &RtcConfiguration::default(),
+            )
+            .await
+            .unwrap(),
+        ),
         tx,
     );
     let (audio_track, video_track) = get_test_unrequired_tracks();
@@ -108,7 +124,15 @@ async fn get_tracks_request2() {
     let (tx, rx) = mpsc::unbounded();
     mem::forget(rx);
     let media_connections = MediaConnections::new(
-        Rc::new(RtcPeerConnection::new(Vec::new(), false).await.unwrap()),
+        Rc::new(
+            RtcPeerConnection::new(
+                Vec::new(),
+                false,
+                &RtcConfiguration::default(),
+            )
+            .await
+            .unwrap(),
+        ),
         tx,
     );
     media_connections
@@ -175,7 +199,15 @@ mod sender_patch {
         let (tx, rx) = mpsc::unbounded();
         mem::forget(rx);
         let media_connections = MediaConnections::new(
-            Rc::new(RtcPeerConnection::new(Vec::new(), false).await.unwrap()),
+            Rc::new(
+                RtcPeerConnection::new(
+                    Vec::new(),
+                    false,
+                    &RtcConfiguration::default(),
+                )
+                .await
+                .unwrap(),
+            ),
             tx,
         );
         let sender = media_connections
@@ -203,6 +235,7 @@ mod sender_patch {
             receivers: None,
             media_direction: Some(MediaDirection::RecvOnly),
             muted: None,
+            display_surface: None,
         });
         sender.state().when_updated().await;
 
@@ -217,6 +250,7 @@ mod sender_patch {
             receivers: None,
             media_direction: Some(MediaDirection::RecvOnly),
             muted: None,
+            display_surface: None,
         });
         sender.state().when_updated().await;
 
@@ -231,6 +265,7 @@ mod sender_patch {
             receivers: None,
             media_direction: Some(MediaDirection::SendRecv),
             muted: None,
+            display_surface: None,
         });
         sender.state().when_updated().await;
 
@@ -245,6 +280,7 @@ mod sender_patch {
             receivers: None,
             media_direction: Some(MediaDirection::RecvOnly),
             muted: None,
+            display_surface: None,
         });
         sender.state().when_updated().await;
         assert!(sender.general_disabled());
@@ -254,6 +290,7 @@ mod sender_patch {
             receivers: None,
             media_direction: Some(MediaDirection::RecvOnly),
             muted: None,
+            display_surface: None,
         });
         sender.state().when_updated().await;
 
@@ -268,6 +305,7 @@ mod sender_patch {
             receivers: None,
             media_direction: None,
             muted: None,
+            display_surface: None,
         });
         sender.state().when_updated().await;
 
@@ -317,7 +355,15 @@ mod receiver_patch {
     ) -> (receiver::Component, mpsc::UnboundedReceiver<PeerEvent>) {
         let (tx, rx) = mpsc::unbounded();
         let media_connections = MediaConnections::new(
-            Rc::new(RtcPeerConnection::new(Vec::new(), false).await.unwrap()),
+            Rc::new(
+                RtcPeerConnection::new(
+                    Vec::new(),
+                    false,
+                    &RtcConfiguration::default(),
+                )
+                .await
+                .unwrap(),
+            ),
             tx,
         );
         let recv = media_connections
@@ -344,6 +390,7 @@ mod receiver_patch {
             receivers: None,
             media_direction: Some(MediaDirection::RecvOnly),
             muted: None,
+            display_surface: None,
         });
         receiver.state().when_updated().await;
 
@@ -358,6 +405,7 @@ mod receiver_patch {
             receivers: None,
             media_direction: Some(MediaDirection::RecvOnly),
             muted: None,
+            display_surface: None,
         });
         receiver.state().when_updated().await;
 
@@ -372,6 +420,7 @@ mod receiver_patch {
             receivers: None,
             media_direction: Some(MediaDirection::SendRecv),
             muted: None,
+            display_surface: None,
         });
         receiver.state().when_updated().await;
 
@@ -386,6 +435,7 @@ mod receiver_patch {
             receivers: None,
             media_direction: Some(MediaDirection::RecvOnly),
             muted: None,
+            display_surface: None,
         });
         receiver.state().when_updated().await;
         assert!(!receiver.enabled_general());
@@ -395,6 +445,7 @@ mod receiver_patch {
             receivers: None,
             media_direction: Some(MediaDirection::RecvOnly),
             muted: None,
+            display_surface: None,
         });
         receiver.state().when_updated().await;
 
@@ -409,12 +460,67 @@ mod receiver_patch {
             receivers: None,
             media_direction: None,
             muted: None,
+            display_surface: None,
         });
         receiver.state().when_updated().await;
 
         assert!(receiver.enabled_general());
     }
 
+    /// Checks that [`Receiver`] created for an already muted and disabled
+    /// remote track reflects it right away.
+    #[wasm_bindgen_test]
+    async fn created_with_initial_state() {
+        let (tx, _rx) = mpsc::unbounded();
+        let media_connections = MediaConnections::new(
+            Rc::new(
+                RtcPeerConnection::new(
+                    Vec::new(),
+                    false,
+                    &RtcConfiguration::default(),
+                )
+                .await
+                .unwrap(),
+            ),
+            tx,
+        );
+        let receiver = media_connections
+            .create_receiver(
+                TRACK_ID,
+                MediaType::Audio(AudioSettings { required: true }).into(),
+                MediaDirection::RecvOnly,
+                true,
+                Some(MID.to_string()),
+                MemberId(SENDER_ID.to_string()),
+                &RecvConstraints::default(),
+                ConnectionMode::Mesh,
+            )
+            .await;
+
+        assert!(!receiver.enabled_general());
+        assert!(receiver.state().enabled_individual());
+        assert!(receiver.state().muted());
+        assert!(receiver.state().as_proto().muted);
+    }
+
+    /// Checks that [`Receiver`]'s mute state is synchronized by
+    /// [`ReceiverState`] update.
+    #[wasm_bindgen_test]
+    async fn mute_state_synchronized_by_state() {
+        let (receiver, _tx) = get_receiver().await;
+
+        let mut proto_state = receiver.state().as_proto();
+        assert!(!proto_state.muted);
+        proto_state.muted = true;
+
+        receiver
+            .state()
+            .apply(proto_state, &LocalTracksConstraints::default());
+
+        receiver.state().when_updated().await;
+        assert!(receiver.state().muted());
+    }
+
     /// Checks that [`Receiver`]'s media exchange state can be changed by
     /// [`ReceiverState`] update.
     #[wasm_bindgen_test]
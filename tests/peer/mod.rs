@@ -15,20 +15,22 @@ use medea_client_api_proto::{
         RtcInboundRtpStreamMediaType, RtcOutboundRtpStreamMediaType, RtcStat,
         RtcStatsType, StatId, TrackStats, TrackStatsKind,
     },
-    AudioSettings, ConnectionMode, Direction, IceConnectionState,
+    AudioSettings, Command, ConnectionMode, Direction, IceConnectionState,
     MediaDirection, MediaSourceKind, MediaType, MemberId, NegotiationRole,
     PeerId, Track, TrackId, TrackPatchEvent, VideoSettings,
 };
 use medea_jason::{
     connection::Connections,
+    error_report::{ErrorRateLimiter, NonFatalErrorKind},
     media::{LocalTracksConstraints, MediaKind, MediaManager, RecvConstraints},
     peer::{
         self, media_exchange_state, MediaStateControllable, PeerEvent,
         TrackDirection,
     },
-    platform::RtcStats,
+    platform::{RtcConfiguration, RtcStats, TransceiverDirection},
     utils::Updatable,
 };
+use wasm_bindgen::prelude::*;
 use wasm_bindgen_test::*;
 
 use crate::{
@@ -49,6 +51,7 @@ fn toggle_disable_track_update(id: TrackId, enabled: bool) -> TrackPatchEvent {
         receivers: None,
         media_direction: Some(media_direction),
         muted: None,
+        display_surface: None,
     }
 }
 
@@ -77,6 +80,7 @@ async fn disable_enable_audio() {
             send_constraints.clone(),
             Rc::new(Connections::new(Rc::clone(&recv_constraints))),
             recv_constraints,
+            &RtcConfiguration::default(),
         )
         .await
         .unwrap(),
@@ -129,6 +133,7 @@ async fn disable_enable_video() {
             send_constraints.clone(),
             Rc::new(Connections::new(Rc::clone(&recv_constraints))),
             recv_constraints,
+            &RtcConfiguration::default(),
         )
         .await
         .unwrap(),
@@ -182,6 +187,7 @@ async fn new_with_disable_audio() {
             send_constraints.clone(),
             Rc::new(Connections::new(Rc::clone(&recv_constraints))),
             recv_constraints,
+            &RtcConfiguration::default(),
         )
         .await
         .unwrap(),
@@ -215,6 +221,7 @@ async fn new_with_disable_video() {
             send_constraints.clone(),
             Rc::new(Connections::new(Rc::clone(&recv_constraints))),
             recv_constraints,
+            &RtcConfiguration::default(),
         )
         .await
         .unwrap(),
@@ -230,6 +237,61 @@ async fn new_with_disable_video() {
     assert!(!peer.is_send_video_enabled(None));
 }
 
+/// Tests that [`peer::PeerConnection::get_transceivers_info()`] returns
+/// nothing before negotiation, and describes all the negotiated
+/// transceivers after it.
+#[wasm_bindgen_test]
+async fn get_transceivers_info() {
+    let (tx, _rx) = mpsc::unbounded();
+    let manager = Rc::new(MediaManager::default());
+    let (audio_track, video_track) = get_test_unrequired_tracks();
+    let peer_state = peer::State::new(
+        PeerId(1),
+        Vec::new(),
+        false,
+        None,
+        ConnectionMode::Mesh,
+    );
+    let send_constraints = local_constraints(true, true);
+    peer_state.insert_track(&audio_track, send_constraints.clone());
+    peer_state.insert_track(&video_track, send_constraints.clone());
+    let recv_constraints = Rc::new(RecvConstraints::default());
+    let peer = peer::Component::new(
+        peer::PeerConnection::new(
+            &peer_state,
+            tx,
+            manager,
+            send_constraints.clone(),
+            Rc::new(Connections::new(Rc::clone(&recv_constraints))),
+            recv_constraints,
+            &RtcConfiguration::default(),
+        )
+        .await
+        .unwrap(),
+        Rc::new(peer_state),
+    );
+    peer.state().when_all_senders_processed().await;
+
+    assert!(peer.get_transceivers_info().await.is_empty());
+
+    let peers = InterconnectedPeers::new().await;
+    let sent = peers.first_peer.get_transceivers_info().await;
+    let received = peers.second_peer.get_transceivers_info().await;
+
+    assert_eq!(sent.len(), 2);
+    assert_eq!(received.len(), 2);
+    for (sent, received) in sent.iter().zip(&received) {
+        assert_eq!(sent.mid, received.mid);
+        assert_eq!(sent.kind, received.kind);
+        assert_eq!(sent.direction, Some(TransceiverDirection::SEND));
+        assert_eq!(received.direction, Some(TransceiverDirection::RECV));
+        assert!(sent.sender_track_id.is_some());
+        assert!(received.receiver_track_id.is_some());
+    }
+    assert!(sent.iter().any(|i| i.kind == MediaKind::Audio));
+    assert!(sent.iter().any(|i| i.kind == MediaKind::Video));
+}
+
 #[wasm_bindgen_test]
 async fn add_candidates_to_answerer_before_offer() {
     let (tx1, rx1) = mpsc::unbounded();
@@ -253,6 +315,7 @@ async fn add_candidates_to_answerer_before_offer() {
             LocalTracksConstraints::default(),
             Rc::new(Connections::new(Rc::clone(&recv_constraints))),
             recv_constraints,
+            &RtcConfiguration::default(),
         )
         .await
         .unwrap(),
@@ -280,6 +343,7 @@ async fn add_candidates_to_answerer_before_offer() {
             LocalTracksConstraints::default(),
             Rc::new(Connections::new(Rc::clone(&recv_constraints))),
             recv_constraints,
+            &RtcConfiguration::default(),
         )
         .await
         .unwrap(),
@@ -319,6 +383,7 @@ async fn add_candidates_to_offerer_before_answer() {
             LocalTracksConstraints::default(),
             Rc::new(Connections::new(Rc::clone(&recv_constraints))),
             recv_constraints,
+            &RtcConfiguration::default(),
         )
         .await
         .unwrap(),
@@ -345,6 +410,7 @@ async fn add_candidates_to_offerer_before_answer() {
             LocalTracksConstraints::default(),
             Rc::new(Connections::new(Rc::clone(&recv_constraints))),
             recv_constraints,
+            &RtcConfiguration::default(),
         )
         .await
         .unwrap(),
@@ -391,6 +457,7 @@ async fn normal_exchange_of_candidates() {
             LocalTracksConstraints::default(),
             Rc::new(Connections::new(Rc::clone(&recv_constraints))),
             recv_constraints,
+            &RtcConfiguration::default(),
         )
         .await
         .unwrap(),
@@ -417,6 +484,7 @@ async fn normal_exchange_of_candidates() {
             LocalTracksConstraints::default(),
             Rc::new(Connections::new(Rc::clone(&recv_constraints))),
             recv_constraints,
+            &RtcConfiguration::default(),
         )
         .await
         .unwrap(),
@@ -490,6 +558,7 @@ async fn send_event_on_new_local_stream() {
             send_constraints.clone(),
             Rc::new(Connections::new(Rc::clone(&recv_constraints))),
             recv_constraints,
+            &RtcConfiguration::default(),
         )
         .await
         .unwrap(),
@@ -533,6 +602,7 @@ async fn ice_connection_state_changed_is_emitted() {
             LocalTracksConstraints::default(),
             Rc::new(Connections::new(Rc::clone(&recv_constraints))),
             recv_constraints,
+            &RtcConfiguration::default(),
         )
         .await
         .unwrap(),
@@ -560,6 +630,7 @@ async fn ice_connection_state_changed_is_emitted() {
             LocalTracksConstraints::default(),
             Rc::new(Connections::new(Rc::clone(&recv_constraints))),
             recv_constraints,
+            &RtcConfiguration::default(),
         )
         .await
         .unwrap(),
@@ -666,6 +737,7 @@ impl InterconnectedPeers {
                 pc1_send_cons,
                 Rc::new(Connections::new(Rc::clone(&recv_constraints))),
                 recv_constraints,
+                &RtcConfiguration::default(),
             )
             .await
             .unwrap(),
@@ -693,6 +765,7 @@ impl InterconnectedPeers {
                 pc2_send_cons,
                 Rc::new(Connections::new(Rc::clone(&recv_constraints))),
                 recv_constraints,
+                &RtcConfiguration::default(),
             )
             .await
             .unwrap(),
@@ -964,6 +1037,7 @@ mod peer_stats_caching {
                 LocalTracksConstraints::default(),
                 Rc::new(Connections::new(Rc::clone(&recv_constraints))),
                 recv_constraints,
+                &RtcConfiguration::default(),
             )
             .await
             .unwrap(),
@@ -1022,6 +1096,7 @@ mod peer_stats_caching {
                 LocalTracksConstraints::default(),
                 Rc::new(Connections::new(Rc::clone(&recv_constraints))),
                 recv_constraints,
+                &RtcConfiguration::default(),
             )
             .await
             .unwrap(),
@@ -1082,6 +1157,7 @@ mod peer_stats_caching {
                 LocalTracksConstraints::default(),
                 Rc::new(Connections::new(Rc::clone(&recv_constraints))),
                 recv_constraints,
+                &RtcConfiguration::default(),
             )
             .await
             .unwrap(),
@@ -1150,6 +1226,7 @@ async fn reset_transition_timers() {
             send_constraints,
             Rc::new(Connections::new(Rc::clone(&recv_constraints))),
             recv_constraints,
+            &RtcConfiguration::default(),
         )
         .await
         .unwrap(),
@@ -1243,6 +1320,7 @@ async fn new_remote_track() {
                 tx_caps.clone(),
                 Rc::new(Connections::new(Rc::clone(&recv_constraints))),
                 recv_constraints,
+                &RtcConfiguration::default(),
             )
             .await
             .unwrap(),
@@ -1283,6 +1361,7 @@ async fn new_remote_track() {
                 LocalTracksConstraints::default(),
                 Rc::new(Connections::new(Rc::clone(&rcv_caps))),
                 rcv_caps,
+                &RtcConfiguration::default(),
             )
             .await
             .unwrap(),
@@ -1531,6 +1610,91 @@ mod ice_restart {
     }
 }
 
+mod ice_gathering {
+    use medea_jason::platform::IceGatheringCounter;
+
+    use super::*;
+
+    /// Waits for the current ICE candidates gathering of the provided
+    /// [`IceGatheringCounter`] to complete.
+    async fn wait_gathering_completed(gathering: &IceGatheringCounter) {
+        for _ in 0..50 {
+            if gathering.info().completed_at.is_some() {
+                return;
+            }
+            delay_for(100).await;
+        }
+        panic!("ICE candidates gathering hasn't completed in 5 seconds");
+    }
+
+    /// Tests that [`PeerConnection::ice_gathering`] accounts the gathered
+    /// candidates and the gathering duration.
+    #[wasm_bindgen_test]
+    async fn accounts_gathered_candidates() {
+        let peers = InterconnectedPeers::new().await;
+
+        for peer in [&peers.first_peer, &peers.second_peer] {
+            let gathering = peer.ice_gathering();
+            wait_gathering_completed(&gathering).await;
+
+            let info = gathering.info();
+            assert!(info.host_candidates > 0);
+            assert_eq!(info.relay_candidates, 0);
+            assert!(info.started_at.is_some());
+            assert!(info.duration().is_some());
+            assert_eq!(gathering.previous_info(), None);
+        }
+    }
+
+    /// Tests that ICE restart starts a new generation of
+    /// [`PeerConnection::ice_gathering`], keeping the previous one.
+    #[wasm_bindgen_test]
+    async fn ice_restart_starts_new_generation() {
+        let peers = InterconnectedPeers::new().await;
+        let gathering = peers.first_peer.ice_gathering();
+        wait_gathering_completed(&gathering).await;
+        let first = gathering.info();
+
+        peers
+            .first_peer
+            .state()
+            .set_negotiation_role(NegotiationRole::Offerer)
+            .await;
+        peers
+            .first_peer
+            .state()
+            .when_local_sdp_updated()
+            .await
+            .unwrap();
+        peers.first_peer.state().reset_negotiation_role();
+        delay_for(100).await;
+        peers.first_peer.state().restart_ice();
+        peers
+            .first_peer
+            .state()
+            .set_negotiation_role(NegotiationRole::Offerer)
+            .await;
+        peers
+            .first_peer
+            .state()
+            .when_local_sdp_updated()
+            .await
+            .unwrap();
+        for _ in 0..50 {
+            if gathering.previous_info().is_some() {
+                break;
+            }
+            delay_for(100).await;
+        }
+        wait_gathering_completed(&gathering).await;
+
+        assert_eq!(gathering.previous_info(), Some(first));
+        let current = gathering.info();
+        assert!(current.started_at > first.started_at);
+        assert!(current.host_candidates > 0);
+    }
+}
+
 /// Tests [`peer::State::patch_track`] method.
 #[wasm_bindgen_test]
 async fn disable_and_enable_all_tracks() {
@@ -1559,6 +1723,7 @@ async fn disable_and_enable_all_tracks() {
             LocalTracksConstraints::default(),
             Rc::new(Connections::new(Rc::clone(&recv_constraints))),
             recv_constraints,
+            &RtcConfiguration::default(),
         )
         .await
         .unwrap(),
@@ -1586,6 +1751,7 @@ async fn disable_and_enable_all_tracks() {
             receivers: None,
             media_direction: Some(MediaDirection::RecvOnly),
             muted: None,
+            display_surface: None,
         })
         .await;
     pc.state().when_updated().await;
@@ -1601,6 +1767,7 @@ async fn disable_and_enable_all_tracks() {
             receivers: None,
             media_direction: Some(MediaDirection::RecvOnly),
             muted: None,
+            display_surface: None,
         })
         .await;
     pc.state().when_updated().await;
@@ -1616,6 +1783,7 @@ async fn disable_and_enable_all_tracks() {
             receivers: None,
             media_direction: Some(MediaDirection::SendRecv),
             muted: None,
+            display_surface: None,
         })
         .await;
     pc.state().when_updated().await;
@@ -1631,9 +1799,368 @@ async fn disable_and_enable_all_tracks() {
             receivers: None,
             media_direction: Some(MediaDirection::SendRecv),
             muted: None,
+            display_surface: None,
         })
         .await;
     pc.state().when_updated().await;
     assert!(!audio_track.general_disabled());
     assert!(!video_track.general_disabled());
 }
+
+#[wasm_bindgen_test]
+async fn batches_simultaneous_track_changes() {
+    use media_exchange_state::Stable::Disabled;
+
+    let (audio_track, video_track) = get_test_unrequired_tracks();
+    let audio_track_id = audio_track.id;
+    let video_track_id = video_track.id;
+    let pc_state = peer::State::new(
+        PeerId(0),
+        Vec::new(),
+        false,
+        None,
+        ConnectionMode::Mesh,
+    );
+    pc_state.insert_track(&audio_track, LocalTracksConstraints::default());
+    pc_state.insert_track(&video_track, LocalTracksConstraints::default());
+
+    let (tx, rx) = mpsc::unbounded();
+    let recv_constraints = Rc::new(RecvConstraints::default());
+    let pc = peer::Component::new(
+        peer::PeerConnection::new(
+            &pc_state,
+            tx,
+            Rc::new(MediaManager::default()),
+            LocalTracksConstraints::default(),
+            Rc::new(Connections::new(Rc::clone(&recv_constraints))),
+            recv_constraints,
+            &RtcConfiguration::default(),
+        )
+        .await
+        .unwrap(),
+        Rc::new(pc_state),
+    );
+    pc.state().when_all_tracks_created().await;
+    pc.state().when_updated().await;
+
+    pc.obj()
+        .get_sender_state_by_id(audio_track_id)
+        .unwrap()
+        .media_state_transition_to(Disabled.into())
+        .unwrap();
+    pc.obj()
+        .get_sender_state_by_id(video_track_id)
+        .unwrap()
+        .media_state_transition_to(Disabled.into())
+        .unwrap();
+
+    let mut commands = Box::pin(rx.filter_map(|e| async move {
+        if let PeerEvent::MediaUpdateCommand { command } = e {
+            Some(command)
+        } else {
+            None
+        }
+    }));
+    let command = timeout(1000, commands.next()).await.unwrap().unwrap();
+    match command {
+        Command::UpdateTracks { tracks_patches, .. } => {
+            let mut ids: Vec<_> = tracks_patches.iter().map(|p| p.id).collect();
+            ids.sort_unstable_by_key(|id| id.0);
+            assert_eq!(ids, vec![audio_track_id, video_track_id]);
+            assert!(tracks_patches.iter().all(|p| p.enabled == Some(false)));
+        }
+        _ => unreachable!("unexpected command"),
+    }
+    assert!(timeout(100, commands.next()).await.is_err());
+}
+
+/// Checks that an SDP answer of a receive-only [`peer::PeerConnection`]
+/// answers all the offered media as `recvonly`, even though it's offered as
+/// `sendrecv`.
+#[wasm_bindgen_test]
+async fn answers_recvonly_for_receive_only_member() {
+    let (tx1, _rx1) = mpsc::unbounded();
+    let (tx2, _rx2) = mpsc::unbounded();
+    let manager = Rc::new(MediaManager::default());
+
+    let offerer_state = peer::State::new(
+        PeerId(1),
+        Vec::new(),
+        false,
+        None,
+        ConnectionMode::Mesh,
+    );
+    let recv_constraints = Rc::new(RecvConstraints::default());
+    let offerer = peer::Component::new(
+        peer::PeerConnection::new(
+            &offerer_state,
+            tx1,
+            Rc::clone(&manager),
+            LocalTracksConstraints::default(),
+            Rc::new(Connections::new(Rc::clone(&recv_constraints))),
+            recv_constraints,
+            &RtcConfiguration::default(),
+        )
+        .await
+        .unwrap(),
+        Rc::new(offerer_state),
+    );
+    let (audio_track, video_track) = get_test_unrequired_tracks();
+    offerer
+        .state()
+        .insert_track(&audio_track, LocalTracksConstraints::default());
+    offerer
+        .state()
+        .insert_track(&video_track, LocalTracksConstraints::default());
+    offerer
+        .state()
+        .set_negotiation_role(NegotiationRole::Offerer)
+        .await;
+    let offer = offerer.state().when_local_sdp_updated().await.unwrap();
+    assert_eq!(offer.match_indices("a=sendrecv").count(), 2);
+
+    let answerer_state = peer::State::new(
+        PeerId(2),
+        Vec::new(),
+        false,
+        None,
+        ConnectionMode::Mesh,
+    );
+    let recv_constraints = Rc::new(RecvConstraints::default());
+    let answerer = peer::Component::new(
+        peer::PeerConnection::new(
+            &answerer_state,
+            tx2,
+            manager,
+            LocalTracksConstraints::default(),
+            Rc::new(Connections::new(Rc::clone(&recv_constraints))),
+            recv_constraints,
+            &RtcConfiguration::default(),
+        )
+        .await
+        .unwrap(),
+        Rc::new(answerer_state),
+    );
+    for (id, mid, media_type) in [
+        (
+            TrackId(1),
+            "0",
+            MediaType::Audio(AudioSettings { required: false }),
+        ),
+        (
+            TrackId(2),
+            "1",
+            MediaType::Video(VideoSettings {
+                required: false,
+                source_kind: MediaSourceKind::Device,
+            }),
+        ),
+    ] {
+        answerer.state().insert_track(
+            &Track {
+                id,
+                direction: Direction::Recv {
+                    sender: MemberId::from("alice"),
+                    mid: Some(String::from(mid)),
+                },
+                media_direction: MediaDirection::SendRecv,
+                muted: false,
+                media_type,
+            },
+            LocalTracksConstraints::default(),
+        );
+    }
+    answerer.state().when_all_tracks_created().await;
+    answerer.state().stabilize_all();
+    answerer.state().when_all_updated().await;
+
+    answerer
+        .state()
+        .set_negotiation_role(NegotiationRole::Answerer(offer))
+        .await;
+    let answer = answerer.state().when_local_sdp_updated().await.unwrap();
+
+    assert_eq!(answer.match_indices("a=recvonly").count(), 2, "{answer}");
+    assert!(!answer.contains("a=sendrecv"), "{answer}");
+}
+
+#[wasm_bindgen(inline_js = "
+    let failedOffers = 0;
+    export function fail_next_remote_offer() {
+        const proto = RTCPeerConnection.prototype;
+        const original = proto.setRemoteDescription;
+        proto.setRemoteDescription = async function (desc) {
+            if (desc.type !== 'offer') {
+                return await original.call(this, desc);
+            }
+            proto.setRemoteDescription = original;
+            failedOffers += 1;
+            await this.setLocalDescription(await this.createOffer());
+            throw new DOMException(
+                'Called in wrong state: have-local-offer',
+                'InvalidStateError'
+            );
+        };
+    }
+    export function failed_remote_offers() {
+        return failedOffers;
+    }
+")]
+extern "C" {
+    fn fail_next_remote_offer();
+    fn failed_remote_offers() -> u32;
+}
+
+/// Checks that a [`peer::PeerConnection`] recovers from a transient
+/// `InvalidStateError` thrown by a remote SDP offer application during a glare
+/// with its own local SDP offer, by rolling back and applying the remote SDP
+/// offer once again.
+#[wasm_bindgen_test]
+async fn retries_remote_offer_after_glare() {
+    let (tx1, _rx1) = mpsc::unbounded();
+    let (tx2, _rx2) = mpsc::unbounded();
+    let manager = Rc::new(MediaManager::default());
+
+    let offerer_state = peer::State::new(
+        PeerId(1),
+        Vec::new(),
+        false,
+        None,
+        ConnectionMode::Mesh,
+    );
+    let recv_constraints = Rc::new(RecvConstraints::default());
+    let offerer = peer::Component::new(
+        peer::PeerConnection::new(
+            &offerer_state,
+            tx1,
+            Rc::clone(&manager),
+            LocalTracksConstraints::default(),
+            Rc::new(Connections::new(Rc::clone(&recv_constraints))),
+            recv_constraints,
+            &RtcConfiguration::default(),
+        )
+        .await
+        .unwrap(),
+        Rc::new(offerer_state),
+    );
+    let (audio_track, video_track) = get_test_unrequired_tracks();
+    offerer
+        .state()
+        .insert_track(&audio_track, LocalTracksConstraints::default());
+    offerer
+        .state()
+        .insert_track(&video_track, LocalTracksConstraints::default());
+    offerer
+        .state()
+        .set_negotiation_role(NegotiationRole::Offerer)
+        .await;
+    let offer = offerer.state().when_local_sdp_updated().await.unwrap();
+
+    let answerer_state = peer::State::new(
+        PeerId(2),
+        Vec::new(),
+        false,
+        None,
+        ConnectionMode::Mesh,
+    );
+    let recv_constraints = Rc::new(RecvConstraints::default());
+    let answerer = peer::Component::new(
+        peer::PeerConnection::new(
+            &answerer_state,
+            tx2,
+            manager,
+            LocalTracksConstraints::default(),
+            Rc::new(Connections::new(Rc::clone(&recv_constraints))),
+            recv_constraints,
+            &RtcConfiguration::default(),
+        )
+        .await
+        .unwrap(),
+        Rc::new(answerer_state),
+    );
+
+    fail_next_remote_offer();
+    answerer
+        .state()
+        .set_negotiation_role(NegotiationRole::Answerer(offer))
+        .await;
+    let answer = timeout(3000, answerer.state().when_local_sdp_updated())
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(failed_remote_offers(), 1);
+    assert_eq!(answer.match_indices("m=").count(), 2, "{answer}");
+}
+
+#[wasm_bindgen(inline_js = "
+    let originalGetStats = null;
+    export function break_get_stats() {
+        const proto = RTCPeerConnection.prototype;
+        originalGetStats = proto.getStats;
+        proto.getStats = async function () {
+            return {};
+        };
+    }
+    export function restore_get_stats() {
+        RTCPeerConnection.prototype.getStats = originalGetStats;
+    }
+")]
+extern "C" {
+    fn break_get_stats();
+    fn restore_get_stats();
+}
+
+/// Checks that a [`peer::PeerConnection`] reports a failed parsing of its
+/// [`RtcStats`] as a [`PeerEvent::NonFatalError`], and that identical
+/// repeated errors are rate-limited.
+#[wasm_bindgen_test]
+async fn reports_stats_parsing_error() {
+    let (tx, rx) = mpsc::unbounded();
+    let manager = Rc::new(MediaManager::default());
+    let recv_constraints = Rc::new(RecvConstraints::default());
+    let peer = peer::PeerConnection::new(
+        &peer::State::new(
+            PeerId(1),
+            Vec::new(),
+            false,
+            None,
+            ConnectionMode::Mesh,
+        ),
+        tx,
+        manager,
+        LocalTracksConstraints::default(),
+        Rc::new(Connections::new(Rc::clone(&recv_constraints))),
+        recv_constraints,
+        &RtcConfiguration::default(),
+    )
+    .await
+    .unwrap();
+
+    break_get_stats();
+    peer.scrape_and_send_peer_stats().await;
+    peer.scrape_and_send_peer_stats().await;
+    restore_get_stats();
+
+    let errors: Vec<_> = timeout(
+        3000,
+        rx.filter_map(|e| async move {
+            if let PeerEvent::NonFatalError { error } = e {
+                Some(error)
+            } else {
+                None
+            }
+        })
+        .take(2)
+        .collect(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(errors.len(), 2);
+    assert!(errors.iter().all(|e| e.kind == NonFatalErrorKind::Stats));
+    assert_eq!(errors[0].message, errors[1].message);
+
+    let limiter = ErrorRateLimiter::default();
+    assert!(limiter.pass(&errors[0]));
+    assert!(!limiter.pass(&errors[1]));
+}
@@ -1,18 +1,25 @@
 mod room;
 
-use std::{cell::RefCell, rc::Rc};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
 
 use futures::{
     channel::{mpsc, oneshot},
-    stream, StreamExt,
+    future, stream, StreamExt,
 };
 use medea_client_api_proto::{
     ClientMsg, CloseReason, Command, Event, ServerMsg,
 };
 use medea_jason::{
-    api,
+    api::{
+        self,
+        err::{AbortedException, RpcClientException, RpcClientExceptionKind},
+    },
     jason::Jason,
     platform::{MockRpcTransport, RpcTransport, TransportState},
+    room::RoomHandle,
     rpc::{CloseMsg, WebSocketRpcClient},
 };
 use medea_reactive::ObservableCell;
@@ -20,7 +27,9 @@ use wasm_bindgen::closure::Closure;
 use wasm_bindgen_futures::{spawn_local, JsFuture};
 use wasm_bindgen_test::*;
 
-use crate::{rpc::RPC_SETTINGS, timeout, yield_now, TEST_ROOM_URL};
+use crate::{
+    delay_for, jsval_cast, rpc::RPC_SETTINGS, timeout, yield_now, TEST_ROOM_URL,
+};
 
 wasm_bindgen_test_configure!(run_in_browser);
 
@@ -319,3 +328,167 @@ async fn room_closes_on_rpc_transport_close() {
 
     timeout(300, test_rx).await.unwrap().unwrap();
 }
+
+/// Tests that aborting [`RoomHandle::join_abortable()`] during a WebSocket
+/// handshake rejects with an [`AbortedException`] and closes the [`Room`],
+/// so it isn't authorized once the handshake completes.
+#[wasm_bindgen_test]
+async fn join_aborted_during_handshake() {
+    let join_commands = Rc::new(Cell::new(0));
+    let ws = Rc::new(WebSocketRpcClient::new(Box::new({
+        let join_commands = Rc::clone(&join_commands);
+        move || {
+            let join_commands = Rc::clone(&join_commands);
+            let mut transport = MockRpcTransport::new();
+            transport.expect_connect().return_once(|_| {
+                Box::pin(async {
+                    delay_for(300).await;
+                    Ok(())
+                })
+            });
+            transport
+                .expect_on_message()
+                .returning_st(|| Box::pin(stream::iter(vec![RPC_SETTINGS])));
+            transport.expect_send().returning_st(move |msg| {
+                if matches!(
+                    msg,
+                    ClientMsg::Command {
+                        command: Command::JoinRoom { .. },
+                        ..
+                    }
+                ) {
+                    join_commands.set(join_commands.get() + 1);
+                }
+                Ok(())
+            });
+            transport.expect_set_close_reason().returning(drop);
+            transport.expect_on_state_change().returning_st(|| {
+                Box::pin(stream::once(async { TransportState::Open }))
+            });
+            let transport = Rc::new(transport);
+            transport as Rc<dyn RpcTransport>
+        }
+    })));
+    let jason = api::Jason::from(Jason::with_rpc_client(ws));
+
+    let room = jason.init_room();
+    room.on_failed_local_media(Closure::once_into_js(|| {}).into())
+        .unwrap();
+    room.on_connection_loss(Closure::once_into_js(|| {}).into())
+        .unwrap();
+    let (close_tx, close_rx) = oneshot::channel();
+    room.on_close(
+        Closure::once_into_js(move || {
+            close_tx.send(()).unwrap();
+        })
+        .into(),
+    )
+    .unwrap();
+
+    let joining = room.join_abortable(TEST_ROOM_URL.to_string());
+    delay_for(100).await;
+    joining.abort_handle().abort();
+
+    let err = timeout(100, JsFuture::from(joining.promise()))
+        .await
+        .unwrap()
+        .unwrap_err();
+    let _: AbortedException = jsval_cast(err, "AbortedException").unwrap();
+    timeout(100, close_rx).await.unwrap().unwrap();
+
+    delay_for(400).await;
+    assert_eq!(join_commands.get(), 0);
+}
+
+/// Checks that concurrent [`RoomHandle::join()`] calls share a single
+/// connection to a media server, and that a closed [`Room`] cannot be joined
+/// anymore.
+#[wasm_bindgen_test]
+async fn concurrent_joins_share_single_connection() {
+    let connects = Rc::new(Cell::new(0));
+    let join_commands = Rc::new(Cell::new(0));
+    let ws = Rc::new(WebSocketRpcClient::new(Box::new({
+        let connects = Rc::clone(&connects);
+        let join_commands = Rc::clone(&join_commands);
+        move || {
+            let connects = Rc::clone(&connects);
+            let join_commands = Rc::clone(&join_commands);
+            let mut transport = MockRpcTransport::new();
+            transport.expect_connect().return_once(move |_| {
+                connects.set(connects.get() + 1);
+                Box::pin(async {
+                    delay_for(100).await;
+                    Ok(())
+                })
+            });
+            transport.expect_on_message().returning_st(|| {
+                Box::pin(stream::iter(vec![
+                    RPC_SETTINGS,
+                    ServerMsg::Event {
+                        room_id: "room_id".into(),
+                        event: Event::RoomJoined {
+                            member_id: "member_id".into(),
+                        },
+                    },
+                ]))
+            });
+            transport.expect_send().returning_st(move |msg| {
+                if matches!(
+                    msg,
+                    ClientMsg::Command {
+                        command: Command::JoinRoom { .. },
+                        ..
+                    }
+                ) {
+                    join_commands.set(join_commands.get() + 1);
+                }
+                Ok(())
+            });
+            transport.expect_set_close_reason().returning(drop);
+            transport.expect_on_state_change().returning_st(|| {
+                Box::pin(stream::once(async { TransportState::Open }))
+            });
+            let transport = Rc::new(transport);
+            transport as Rc<dyn RpcTransport>
+        }
+    })));
+    let jason = api::Jason::from(Jason::with_rpc_client(ws));
+
+    let room = jason.init_room();
+    room.on_failed_local_media(Closure::once_into_js(|| {}).into())
+        .unwrap();
+    room.on_connection_loss(Closure::once_into_js(|| {}).into())
+        .unwrap();
+    let room = RoomHandle::from(room);
+
+    let first = JsFuture::from(
+        api::RoomHandle::from(room.clone()).join(TEST_ROOM_URL.to_string()),
+    );
+    let second = JsFuture::from(
+        api::RoomHandle::from(room.clone()).join(TEST_ROOM_URL.to_string()),
+    );
+    let (first, second) =
+        timeout(500, future::join(first, second)).await.unwrap();
+    first.unwrap();
+    second.unwrap();
+    assert_eq!(connects.get(), 1);
+    assert_eq!(join_commands.get(), 1);
+
+    JsFuture::from(
+        api::RoomHandle::from(room.clone()).join(TEST_ROOM_URL.to_string()),
+    )
+    .await
+    .unwrap();
+    assert_eq!(join_commands.get(), 1);
+
+    jason.close_room(api::RoomHandle::from(room.clone()));
+    let err = JsFuture::from(
+        api::RoomHandle::from(room).join(TEST_ROOM_URL.to_string()),
+    )
+    .await
+    .unwrap_err();
+    let err: RpcClientException =
+        jsval_cast(err, "RpcClientException").unwrap();
+    assert_eq!(err.kind(), RpcClientExceptionKind::RoomClosed);
+    assert_eq!(join_commands.get(), 1);
+}
@@ -3,6 +3,7 @@
 use std::{
     collections::{HashMap, HashSet},
     rc::Rc,
+    time::Duration,
 };
 
 use futures::{
@@ -30,7 +31,7 @@ use medea_jason::{
     peer::PeerConnection,
     platform,
     room::Room,
-    rpc::MockRpcSession,
+    rpc::{ClientDisconnect, MockRpcSession},
     utils::Updatable,
 };
 use wasm_bindgen::{prelude::*, JsValue};
@@ -118,6 +119,7 @@ async fn get_test_room_and_exist_peer(
                 &media_stream_settings,
                 false,
                 false,
+                None,
             ),
         )
         .await
@@ -149,6 +151,7 @@ async fn error_get_local_stream_on_new_peer() {
         &media_stream_settings(true, true),
         false,
         false,
+        None,
     ))
     .await
     .unwrap();
@@ -266,6 +269,7 @@ mod connection_mode {
             &media_stream_settings(true, true),
             false,
             false,
+            None,
         ))
         .await
         .unwrap();
@@ -292,6 +296,7 @@ mod connection_mode {
             &media_stream_settings(true, true),
             false,
             false,
+            None,
         ))
         .await
         .unwrap();
@@ -586,6 +591,7 @@ mod receivers_patch_send_tracks {
                     media_direction: None,
                     receivers: Some(Vec::new()),
                     muted: None,
+                    display_surface: None,
                 })]),
                 negotiation_role: None,
             })
@@ -606,6 +612,7 @@ mod receivers_patch_send_tracks {
                         MemberId::from("eva"),
                     ])),
                     muted: None,
+                    display_surface: None,
                 })]),
                 negotiation_role: None,
             })
@@ -859,7 +866,7 @@ mod disable_send_tracks {
 
     /// Tests that if [`RoomHandle::disable_audio`] and
     /// [`RoomHandle::enable_audio`] are called simultaneously, then first
-    /// call will be rejected, and second resolved.
+    /// call will be resolved as superseded, and second applied.
     ///
     /// # Algorithm
     ///
@@ -893,8 +900,14 @@ mod disable_send_tracks {
                 JsFuture::from(room_handle.enable_audio()),
             )
             .await;
-        disable_audio_result.unwrap_err();
-        enable_audio_result.unwrap();
+        assert_eq!(
+            disable_audio_result.unwrap(),
+            JsValue::from(api::MediaStateOutcome::Superseded),
+        );
+        assert_eq!(
+            enable_audio_result.unwrap(),
+            JsValue::from(api::MediaStateOutcome::Applied),
+        );
 
         assert!(peer.is_all_transceiver_sides_in_media_state(
             MediaKind::Audio,
@@ -906,7 +919,7 @@ mod disable_send_tracks {
 
     /// Tests that if [`RoomHandle::disable_video`] and
     /// [`RoomHandle::enable_video`] are called simultaneously, then first
-    /// call will be rejected, and second resolved.
+    /// call will be resolved as superseded, and second applied.
     ///
     /// # Algorithm
     ///
@@ -940,8 +953,14 @@ mod disable_send_tracks {
                 JsFuture::from(room_handle.enable_video(None)),
             )
             .await;
-        disable_video_result.unwrap_err();
-        enable_video_result.unwrap();
+        assert_eq!(
+            disable_video_result.unwrap(),
+            JsValue::from(api::MediaStateOutcome::Superseded),
+        );
+        assert_eq!(
+            enable_video_result.unwrap(),
+            JsValue::from(api::MediaStateOutcome::Applied),
+        );
 
         assert!(peer.is_all_transceiver_sides_in_media_state(
             MediaKind::Video,
@@ -1009,6 +1028,53 @@ mod disable_send_tracks {
         ));
     }
 
+    /// Tests that two simultaneous calls of [`RoomHandle::enable_audio`] are
+    /// coalesced and both resolved as applied, rather than the first one
+    /// being superseded by the second one.
+    ///
+    /// # Algorithm
+    ///
+    /// 1. Create [`Room`] with audio in
+    /// [`media_exchange_state::Stable::Disabled`].
+    ///
+    /// 2. Call [`RoomHandle::enable_audio`] simultaneous twice.
+    ///
+    /// 3. Check that both calls are resolved with
+    /// [`api::MediaStateOutcome::Applied`].
+    #[wasm_bindgen_test]
+    async fn join_two_audio_enables() {
+        let (audio_track, video_track) = get_test_unrequired_tracks();
+        let (room, peer, _, _) = get_test_room_and_exist_peer(
+            vec![audio_track, video_track],
+            Some(media_stream_settings(true, true)),
+        )
+        .await;
+
+        let room_handle = api::RoomHandle::from(room.new_handle());
+        JsFuture::from(room_handle.disable_audio()).await.unwrap();
+
+        let (first, second) = futures::future::join(
+            JsFuture::from(room_handle.enable_audio()),
+            JsFuture::from(room_handle.enable_audio()),
+        )
+        .await;
+        assert_eq!(
+            first.unwrap(),
+            JsValue::from(api::MediaStateOutcome::Applied),
+        );
+        assert_eq!(
+            second.unwrap(),
+            JsValue::from(api::MediaStateOutcome::Applied),
+        );
+
+        assert!(peer.is_all_transceiver_sides_in_media_state(
+            MediaKind::Audio,
+            TrackDirection::Send,
+            None,
+            media_exchange_state::Stable::Enabled.into()
+        ));
+    }
+
     #[wasm_bindgen_test]
     async fn disable_audio_room_before_init_peer() {
         let (event_tx, event_rx) = mpsc::unbounded();
@@ -1019,6 +1085,7 @@ mod disable_send_tracks {
             &media_stream_settings(true, true),
             false,
             false,
+            None,
         ))
         .await
         .unwrap();
@@ -1048,7 +1115,8 @@ mod disable_send_tracks {
                     TrackPatchCommand {
                         id: TrackId(1),
                         enabled: Some(false),
-                        muted: None
+                        muted: None,
+                        display_surface: None,
                     }
                 );
             }
@@ -1062,6 +1130,7 @@ mod disable_send_tracks {
                     receivers: None,
                     media_direction: Some(MediaDirection::RecvOnly),
                     muted: None,
+                    display_surface: None,
                 })],
                 negotiation_role: None,
             })
@@ -1100,6 +1169,7 @@ mod disable_send_tracks {
             &media_stream_settings(true, true),
             false,
             false,
+            None,
         ))
         .await
         .unwrap();
@@ -1129,7 +1199,8 @@ mod disable_send_tracks {
                     TrackPatchCommand {
                         id: TrackId(1),
                         enabled: None,
-                        muted: Some(true)
+                        muted: Some(true),
+                        display_surface: None,
                     }
                 );
             }
@@ -1143,6 +1214,7 @@ mod disable_send_tracks {
                     receivers: None,
                     media_direction: None,
                     muted: Some(true),
+                    display_surface: None,
                 })],
                 negotiation_role: None,
             })
@@ -1182,6 +1254,7 @@ mod disable_send_tracks {
             &media_stream_settings(true, true),
             false,
             false,
+            None,
         ))
         .await
         .unwrap();
@@ -1213,7 +1286,8 @@ mod disable_send_tracks {
                     TrackPatchCommand {
                         id: TrackId(2),
                         enabled: Some(false),
-                        muted: None
+                        muted: None,
+                        display_surface: None,
                     }
                 );
             }
@@ -1227,6 +1301,7 @@ mod disable_send_tracks {
                     receivers: None,
                     media_direction: Some(MediaDirection::RecvOnly),
                     muted: None,
+                    display_surface: None,
                 })],
                 negotiation_role: None,
             })
@@ -1571,6 +1646,7 @@ mod patches_generation {
                                 state,
                                 MediaState::Mute(mute_state::Stable::Muted)
                             )),
+                            display_surface: None,
                         })],
                         negotiation_role: None,
                     })
@@ -1629,6 +1705,7 @@ mod patches_generation {
                     id: TrackId(0),
                     enabled: Some(false),
                     muted: None,
+                    display_surface: None,
                 }]
             }
         );
@@ -1683,6 +1760,7 @@ mod patches_generation {
                 id: TrackId(0),
                 enabled: Some(false),
                 muted: None,
+                display_surface: None,
             }]
         );
 
@@ -1692,6 +1770,7 @@ mod patches_generation {
                 id: TrackId(0),
                 enabled: Some(false),
                 muted: None,
+                display_surface: None,
             }]
         );
     }
@@ -1767,6 +1846,7 @@ mod patches_generation {
                     id: TrackId(0),
                     enabled: Some(false),
                     muted: None,
+                    display_surface: None,
                 }]
             }
         );
@@ -1819,6 +1899,7 @@ mod patches_generation {
                         id: TrackId(1),
                         enabled: Some(false),
                         muted: None,
+                        display_surface: None,
                     }]
                 ),
                 _ => {
@@ -1875,6 +1956,7 @@ mod patches_generation {
                         id: TrackId(2),
                         enabled: Some(false),
                         muted: None,
+                        display_surface: None,
                     }]
                 ),
                 _ => {
@@ -1907,6 +1989,7 @@ mod patches_generation {
                     id: TrackId(0),
                     enabled: None,
                     muted: Some(true),
+                    display_surface: None,
                 }]
             }
         );
@@ -1937,6 +2020,7 @@ mod patches_generation {
                     id: TrackId(0),
                     enabled: None,
                     muted: Some(false),
+                    display_surface: None,
                 }]
             }
         );
@@ -2022,6 +2106,7 @@ async fn disable_by_server() {
                 receivers: None,
                 media_direction: Some(MediaDirection::RecvOnly),
                 muted: None,
+                display_surface: None,
             })],
         })
         .unwrap();
@@ -2053,6 +2138,7 @@ async fn enable_by_server() {
                 receivers: None,
                 media_direction: Some(MediaDirection::RecvOnly),
                 muted: None,
+                display_surface: None,
             })],
         })
         .unwrap();
@@ -2072,6 +2158,7 @@ async fn enable_by_server() {
                 receivers: None,
                 media_direction: Some(MediaDirection::SendRecv),
                 muted: None,
+                display_surface: None,
             })],
         })
         .unwrap();
@@ -2108,6 +2195,7 @@ async fn only_one_gum_performed_on_enable() {
                 receivers: None,
                 media_direction: Some(MediaDirection::RecvOnly),
                 muted: None,
+                display_surface: None,
             })],
         })
         .unwrap();
@@ -2127,6 +2215,7 @@ async fn only_one_gum_performed_on_enable() {
                 receivers: None,
                 media_direction: Some(MediaDirection::RecvOnly),
                 muted: None,
+                display_surface: None,
             })],
         })
         .unwrap();
@@ -2180,7 +2269,8 @@ async fn no_updates_sent_if_gum_fails_on_enable() {
             tracks_patches: vec![TrackPatchCommand {
                 id: TrackId(1),
                 enabled: Some(false),
-                muted: None
+                muted: None,
+                display_surface: None,
             }]
         }
     );
@@ -2248,6 +2338,7 @@ async fn only_one_gum_performed_on_enable_by_server() {
                 receivers: None,
                 media_direction: Some(MediaDirection::RecvOnly),
                 muted: None,
+                display_surface: None,
             })],
         })
         .unwrap();
@@ -2266,6 +2357,7 @@ async fn only_one_gum_performed_on_enable_by_server() {
                 receivers: None,
                 media_direction: Some(MediaDirection::RecvOnly),
                 muted: None,
+                display_surface: None,
             })],
         })
         .unwrap();
@@ -2304,6 +2396,7 @@ async fn send_enabling_holds_local_tracks() {
         &media_stream_settings(true, true),
         false,
         false,
+        None,
     ))
     .await
     .unwrap();
@@ -2328,6 +2421,7 @@ async fn send_enabling_holds_local_tracks() {
                 receivers: None,
                 media_direction: Some(MediaDirection::RecvOnly),
                 muted: None,
+                display_surface: None,
             })],
         })
         .unwrap();
@@ -2352,12 +2446,132 @@ async fn send_enabling_holds_local_tracks() {
     mock.stop();
 }
 
+/// Tests that `LocalMediaTrack.on_disabled` callback is invoked when a Media
+/// Server disables media exchange of a local track on its own, without any
+/// client request.
+#[wasm_bindgen_test]
+async fn on_disabled_fires_on_server_forced_disable() {
+    let (event_tx, event_rx) = mpsc::unbounded();
+    let (room, _commands_rx) = get_test_room(Box::pin(event_rx));
+    let room_handle = api::RoomHandle::from(room.new_handle());
+    JsFuture::from(room_handle.set_local_media_settings(
+        &media_stream_settings(true, false),
+        false,
+        false,
+        None,
+    ))
+    .await
+    .unwrap();
+
+    let (track_tx, mut track_rx) = mpsc::unbounded();
+    room_handle
+        .on_local_track(
+            Closure::<dyn Fn(JsValue)>::new(move |track| {
+                let track: api::LocalMediaTrack =
+                    jsval_cast(track, "LocalMediaTrack").unwrap();
+                track_tx.unbounded_send(track).unwrap();
+            })
+            .into_js_value()
+            .into(),
+        )
+        .unwrap();
+
+    let (audio_track, _) = get_test_tracks(false, false);
+    let audio_track_id = audio_track.id;
+    event_tx
+        .unbounded_send(Event::PeerCreated {
+            peer_id: PeerId(1),
+            negotiation_role: NegotiationRole::Offerer,
+            tracks: vec![audio_track],
+            ice_servers: Vec::new(),
+            force_relay: false,
+            connection_mode: ConnectionMode::Mesh,
+        })
+        .unwrap();
+    let track = timeout(3000, track_rx.next()).await.unwrap().unwrap();
+
+    let (disabled_tx, disabled_rx) = oneshot::channel();
+    track.on_disabled(
+        Closure::once_into_js(move || {
+            disabled_tx.send(()).unwrap();
+        })
+        .into(),
+    );
+    event_tx
+        .unbounded_send(Event::PeerUpdated {
+            peer_id: PeerId(1),
+            negotiation_role: None,
+            updates: vec![PeerUpdate::Updated(TrackPatchEvent {
+                id: audio_track_id,
+                receivers: None,
+                media_direction: Some(MediaDirection::RecvOnly),
+                muted: None,
+                display_surface: None,
+            })],
+        })
+        .unwrap();
+
+    timeout(1000, disabled_rx).await.unwrap().unwrap();
+}
+
+/// Tests that closing a [`Room`] during a [getUserMedia()][1] request rejects
+/// the pending [`RoomHandle`] call instead of hanging, and stops the tracks
+/// captured afterwards.
+///
+/// [1]: https://tinyurl.com/w3-streams#dom-mediadevices-getusermedia
+#[wasm_bindgen_test]
+async fn closing_room_stops_late_captured_tracks() {
+    let (audio_track, video_track) = get_test_tracks(false, false);
+    let video_track_id = video_track.id;
+    let (room, peer, event_tx, _) = get_test_room_and_exist_peer(
+        vec![audio_track, video_track],
+        Some(media_stream_settings(true, true)),
+    )
+    .await;
+    let room_handle = api::RoomHandle::from(room.new_handle());
+    event_tx
+        .unbounded_send(Event::PeerUpdated {
+            peer_id: peer.id(),
+            negotiation_role: None,
+            updates: vec![PeerUpdate::Updated(TrackPatchEvent {
+                id: video_track_id,
+                receivers: None,
+                media_direction: Some(MediaDirection::RecvOnly),
+                muted: None,
+                display_surface: None,
+            })],
+        })
+        .unwrap();
+    delay_for(100).await;
+    drop(peer);
+
+    let mock = MockNavigator::new();
+    mock.set_get_user_media_latency(500);
+    let enabling = JsFuture::from(room_handle.enable_video(None));
+    delay_for(100).await;
+    assert_eq!(mock.get_user_media_requests_count(), 1);
+
+    room.close(ClientDisconnect::RoomClosed.into());
+    timeout(100, enabling).await.unwrap().unwrap_err();
+
+    delay_for(600).await;
+    let tracks = mock.captured_tracks();
+    assert_eq!(tracks.length(), 1);
+    for track in tracks.iter() {
+        assert_eq!(
+            web_sys::MediaStreamTrack::from(track).ready_state(),
+            web_sys::MediaStreamTrackState::Ended,
+        );
+    }
+    mock.stop();
+}
+
 /// Tests for [`RoomHandle::set_local_media_settings`].
 mod set_local_media_settings {
     use super::*;
 
     use medea_jason::api::err::{
-        InternalException, LocalMediaInitException,
+        AbortedException, InternalException, LocalMediaInitException,
         MediaSettingsUpdateException, MediaStateTransitionException,
         MediaStateTransitionExceptionKind,
     };
@@ -2510,6 +2724,47 @@ mod set_local_media_settings {
         settings
     }
 
+    /// Tests that aborting [`RoomHandle::set_local_media_settings_abortable`]
+    /// during a [getUserMedia()][1] request rejects with an
+    /// [`AbortedException`], and stops the tracks captured afterwards.
+    ///
+    /// [1]: https://tinyurl.com/w3-streams#dom-mediadevices-getusermedia
+    #[wasm_bindgen_test]
+    async fn aborted_during_get_user_media() {
+        let (room, _peer1, _peer2) = room_with_connected_peers().await;
+        let room_handle = api::RoomHandle::from(room.new_handle());
+
+        let mut settings = api::MediaStreamSettings::new();
+        let mut device_video = api::DeviceVideoTrackConstraints::new();
+        device_video.ideal_width(320);
+        settings.device_video(device_video);
+
+        let mock = MockNavigator::new();
+        mock.set_get_user_media_latency(500);
+        let updating = room_handle
+            .set_local_media_settings_abortable(&settings, true, false, None);
+        delay_for(100).await;
+        assert_eq!(mock.get_user_media_requests_count(), 1);
+        updating.abort_handle().abort();
+
+        let err = timeout(100, JsFuture::from(updating.promise()))
+            .await
+            .unwrap()
+            .unwrap_err();
+        let _: AbortedException = jsval_cast(err, "AbortedException").unwrap();
+
+        delay_for(600).await;
+        let tracks = mock.captured_tracks();
+        assert_eq!(tracks.length(), 1);
+        for track in tracks.iter() {
+            assert_eq!(
+                web_sys::MediaStreamTrack::from(track).ready_state(),
+                web_sys::MediaStreamTrackState::Ended,
+            );
+        }
+        mock.stop();
+    }
+
     /// Tests RoomHandle::set_local_media_settings before creating
     /// PeerConnection. Setup:
     ///     1. Create Room.
@@ -2544,6 +2799,7 @@ mod set_local_media_settings {
             &constraints,
             false,
             false,
+            None,
         ))
         .await
         .unwrap();
@@ -2595,6 +2851,7 @@ mod set_local_media_settings {
                 &constraints,
                 false,
                 false,
+                None,
             ))
             .await
             .unwrap_err(),
@@ -2652,7 +2909,8 @@ mod set_local_media_settings {
                 JsFuture::from(room_handle.set_local_media_settings(
                     &constraints,
                     false,
-                    false
+                    false,
+                    None
                 ))
                 .await
                 .is_ok(),
@@ -2696,6 +2954,7 @@ mod set_local_media_settings {
             &media_stream_settings(true, true),
             false,
             false,
+            None,
         ))
         .await
         .unwrap();
@@ -2719,6 +2978,7 @@ mod set_local_media_settings {
                     &media_stream_settings(false, false),
                     false,
                     false,
+                    None,
                 ))
                 .await,
             );
@@ -2755,6 +3015,7 @@ mod set_local_media_settings {
                 &media_settings_with_device_id(),
                 true,
                 false,
+                None,
             ))
             .await
             .unwrap_err(),
@@ -2783,6 +3044,7 @@ mod set_local_media_settings {
             &media_stream_settings(true, true),
             false,
             false,
+            None,
         ))
         .await
         .unwrap();
@@ -2793,6 +3055,7 @@ mod set_local_media_settings {
                 &media_settings_with_device_id(),
                 true,
                 true,
+                None,
             ))
             .await
             .unwrap_err(),
@@ -2824,6 +3087,7 @@ mod set_local_media_settings {
             &media_stream_settings(true, true),
             false,
             false,
+            None,
         ))
         .await
         .unwrap();
@@ -2835,6 +3099,7 @@ mod set_local_media_settings {
                 &media_settings_with_device_id(),
                 true,
                 true,
+                None,
             ))
             .await
             .unwrap_err(),
@@ -2864,6 +3129,7 @@ mod set_local_media_settings {
             &media_stream_settings(true, true),
             false,
             false,
+            None,
         ))
         .await
         .unwrap();
@@ -2877,6 +3143,7 @@ mod set_local_media_settings {
                 &media_settings_with_device_id(),
                 false,
                 true,
+                None,
             ))
             .await
             .unwrap_err(),
@@ -3007,6 +3274,7 @@ async fn intentions_are_sent_on_reconnect() {
             &media_stream_settings(true, true),
             false,
             false,
+            None,
         ),
     )
     .await
@@ -3083,13 +3351,18 @@ async fn sender_answerer() {
         &media_stream_settings(true, true),
         false,
         false,
+        None,
     ))
     .await
     .unwrap();
 
-    let peer = platform::RtcPeerConnection::new(Vec::new(), false)
-        .await
-        .unwrap();
+    let peer = platform::RtcPeerConnection::new(
+        Vec::new(),
+        false,
+        &platform::RtcConfiguration::default(),
+    )
+    .await
+    .unwrap();
 
     let a_tr = peer
         .add_transceiver(MediaKind::Audio, platform::TransceiverDirection::RECV)
@@ -3097,7 +3370,10 @@ async fn sender_answerer() {
     let v_tr = peer
         .add_transceiver(MediaKind::Video, platform::TransceiverDirection::RECV)
         .await;
-    let offer = peer.create_offer().await.unwrap();
+    let offer = peer
+        .create_offer(platform::OfferReceiveOptions::default())
+        .await
+        .unwrap();
     peer.set_offer(&offer).await.unwrap();
 
     event_tx
@@ -3161,3 +3437,355 @@ async fn sender_answerer() {
         }
     }
 }
+
+mod ice_candidate_filter {
+    use medea_jason::platform::{IceCandidateFilter, IceCandidateType};
+
+    use super::*;
+
+    /// Returns the ICE candidates sent to a media server by a [`Room`] with
+    /// the provided [`IceCandidateFilter`] during one second of gathering.
+    async fn sent_candidates(filter: IceCandidateFilter) -> Vec<String> {
+        let (event_tx, event_rx) = mpsc::unbounded();
+        let (room, commands_rx) = get_test_room(Box::pin(event_rx));
+        room.new_handle().set_ice_candidate_filter(filter).unwrap();
+        let (audio_track, video_track) = get_test_unrequired_tracks();
+
+        event_tx
+            .unbounded_send(Event::PeerCreated {
+                peer_id: PeerId(1),
+                negotiation_role: NegotiationRole::Offerer,
+                tracks: vec![audio_track, video_track],
+                ice_servers: Vec::new(),
+                force_relay: false,
+                connection_mode: ConnectionMode::Mesh,
+            })
+            .unwrap();
+
+        let commands = commands_rx
+            .take_until(delay_for(1000))
+            .collect::<Vec<_>>()
+            .await;
+        drop(room);
+
+        commands
+            .into_iter()
+            .filter_map(|command| match command {
+                Command::SetIceCandidate { candidate, .. } => {
+                    Some(candidate.candidate)
+                }
+                _ => None,
+            })
+            .filter(|candidate| !candidate.is_empty())
+            .collect()
+    }
+
+    /// Checks that all the gathered ICE candidates are sent to a media server
+    /// by default.
+    #[wasm_bindgen_test]
+    async fn sends_all_candidates_by_default() {
+        let sent = sent_candidates(IceCandidateFilter::default()).await;
+
+        assert!(!sent.is_empty());
+    }
+
+    /// Checks that the locally gathered ICE candidates filtered out by the
+    /// [`IceCandidateFilter`] aren't sent to a media server.
+    #[wasm_bindgen_test]
+    async fn doesnt_send_filtered_out_candidates() {
+        let sent = sent_candidates(IceCandidateFilter::Custom(Rc::new(|c| {
+            c.kind != IceCandidateType::Host
+        })))
+        .await;
+
+        assert!(sent.is_empty(), "{sent:?}");
+    }
+}
+
+mod snapshot {
+    use medea_jason::room::RoomSnapshot;
+
+    use super::*;
+
+    /// Checks that a [`RoomSnapshot`] exported from a [`Room`] survives a JSON
+    /// round trip and is exported unchanged from a [`Room`] it's restored
+    /// into.
+    #[wasm_bindgen_test]
+    async fn round_trips() {
+        let (room, _commands_rx) = get_test_room(stream::pending().boxed());
+        let room_handle = api::RoomHandle::from(room.new_handle());
+        JsFuture::from(room_handle.mute_audio()).await.unwrap();
+        JsFuture::from(room_handle.disable_video(None))
+            .await
+            .unwrap();
+
+        let snapshot = room.new_handle().export_state().unwrap();
+        assert!(snapshot.audio.muted);
+        assert!(!snapshot.device_video.enabled);
+        assert!(snapshot.room_url.is_none());
+
+        let parsed: RoomSnapshot = snapshot.to_json().parse().unwrap();
+        assert_eq!(parsed, snapshot);
+
+        let (restored, _commands_rx) = get_test_room(stream::pending().boxed());
+        restored.restore_state(&parsed);
+        assert_eq!(restored.new_handle().export_state().unwrap(), snapshot);
+    }
+
+    /// Checks that device IDs of a [`RoomSnapshot`] are restored.
+    #[wasm_bindgen_test]
+    async fn restores_device_ids() {
+        let (room, _commands_rx) = get_test_room(stream::pending().boxed());
+        let mut snapshot = room.new_handle().export_state().unwrap();
+        snapshot.audio.device_id = Some(String::from("mic"));
+        room.restore_state(&snapshot);
+
+        let settings = room.new_handle().effective_media_settings().unwrap();
+        assert_eq!(
+            settings.device_id(MediaKind::Audio, MediaSourceKind::Device),
+            Some(String::from("mic")),
+        );
+        assert_eq!(room.new_handle().export_state().unwrap(), snapshot);
+    }
+
+    /// Checks that a malformed [`RoomSnapshot`] is rejected.
+    #[wasm_bindgen_test]
+    async fn rejects_malformed_json() {
+        assert!("{\"audio\": 1}".parse::<RoomSnapshot>().is_err());
+        assert!("".parse::<RoomSnapshot>().is_err());
+    }
+
+    /// Checks that the media states of a restored [`RoomSnapshot`] are applied
+    /// to the tracks right on their creation.
+    #[wasm_bindgen_test]
+    async fn applies_restored_states_on_peer_creation() {
+        let (room, _commands_rx) = get_test_room(stream::pending().boxed());
+        let mut snapshot = room.new_handle().export_state().unwrap();
+        snapshot.audio.muted = true;
+        snapshot.device_video.enabled = false;
+
+        let (event_tx, event_rx) = mpsc::unbounded();
+        let (restored, _commands_rx) = get_test_room(Box::pin(event_rx));
+        restored.restore_state(&snapshot);
+        let room_handle = api::RoomHandle::from(restored.new_handle());
+        JsFuture::from(room_handle.set_local_media_settings(
+            &media_stream_settings(true, true),
+            false,
+            false,
+            None,
+        ))
+        .await
+        .unwrap();
+
+        let (audio_track, video_track) = get_test_tracks(false, false);
+        event_tx
+            .unbounded_send(Event::PeerCreated {
+                peer_id: PeerId(1),
+                negotiation_role: NegotiationRole::Offerer,
+                tracks: vec![audio_track, video_track],
+                ice_servers: Vec::new(),
+                force_relay: false,
+                connection_mode: ConnectionMode::Mesh,
+            })
+            .unwrap();
+        delay_for(500).await;
+
+        let peer = restored.get_peer_by_id(PeerId(1)).unwrap();
+        assert!(!peer.is_send_audio_unmuted());
+        assert!(!peer.is_send_video_enabled(Some(MediaSourceKind::Device)));
+    }
+}
+
+mod media_state {
+    use medea_jason::room::SendMediaState;
+
+    use super::*;
+
+    /// Checks that a [`RoomMediaState`] reflects the media states changed via
+    /// a [`RoomHandle`].
+    ///
+    /// [`RoomHandle`]: api::RoomHandle
+    /// [`RoomMediaState`]: medea_jason::room::RoomMediaState
+    #[wasm_bindgen_test]
+    async fn reflects_changes() {
+        let (room, _commands_rx) = get_test_room(stream::pending().boxed());
+        let state = room.new_handle().media_state().unwrap();
+        let unchanged = SendMediaState {
+            enabled: true,
+            muted: false,
+        };
+        assert_eq!(state.send_device_audio, unchanged);
+        assert_eq!(state.send_device_video, unchanged);
+        assert!(state.recv_audio);
+        assert!(state.recv_device_video);
+
+        let room_handle = api::RoomHandle::from(room.new_handle());
+        JsFuture::from(room_handle.mute_audio()).await.unwrap();
+        JsFuture::from(room_handle.disable_video(None))
+            .await
+            .unwrap();
+        JsFuture::from(room_handle.disable_remote_audio())
+            .await
+            .unwrap();
+
+        let state = room.new_handle().media_state().unwrap();
+        assert!(state.send_device_audio.enabled);
+        assert!(state.send_device_audio.muted);
+        assert_eq!(state.send_display_audio, state.send_device_audio);
+        assert!(!state.send_device_video.enabled);
+        assert!(!state.send_display_video.enabled);
+        assert!(!state.recv_audio);
+        assert!(state.recv_device_video);
+    }
+
+    /// Checks that a [`RoomMediaState`] is already updated once the
+    /// `on_forced_media_state` callback is invoked.
+    ///
+    /// [`RoomMediaState`]: medea_jason::room::RoomMediaState
+    #[wasm_bindgen_test]
+    async fn is_updated_before_forced_media_state_callback() {
+        let (event_tx, event_rx) = mpsc::unbounded();
+        let (room, _commands_rx) = get_test_room(Box::pin(event_rx));
+        let room_handle = api::RoomHandle::from(room.new_handle());
+        JsFuture::from(room_handle.set_local_media_settings(
+            &media_stream_settings(true, false),
+            false,
+            false,
+            None,
+        ))
+        .await
+        .unwrap();
+
+        let (state_tx, mut state_rx) = mpsc::unbounded();
+        let handle = room.new_handle();
+        room_handle
+            .on_forced_media_state(
+                Closure::<dyn Fn(JsValue)>::new(move |_| {
+                    let state = handle.media_state().unwrap();
+                    state_tx.unbounded_send(state.send_device_audio).unwrap();
+                })
+                .into_js_value()
+                .into(),
+            )
+            .unwrap();
+
+        let (audio_track, _) = get_test_tracks(false, false);
+        let audio_track_id = audio_track.id;
+        event_tx
+            .unbounded_send(Event::PeerCreated {
+                peer_id: PeerId(1),
+                negotiation_role: NegotiationRole::Offerer,
+                tracks: vec![audio_track],
+                ice_servers: Vec::new(),
+                force_relay: false,
+                connection_mode: ConnectionMode::Mesh,
+            })
+            .unwrap();
+        delay_for(500).await;
+
+        event_tx
+            .unbounded_send(Event::PeerUpdated {
+                peer_id: PeerId(1),
+                negotiation_role: None,
+                updates: vec![PeerUpdate::Updated(TrackPatchEvent {
+                    id: audio_track_id,
+                    receivers: None,
+                    media_direction: Some(MediaDirection::RecvOnly),
+                    muted: None,
+                    display_surface: None,
+                })],
+            })
+            .unwrap();
+
+        let state = timeout(1000, state_rx.next()).await.unwrap().unwrap();
+        assert!(!state.enabled);
+    }
+}
+
+/// Checks that the final quality summary of a removed `PeerConnection` is
+/// recorded into the [`Room`]'s event log.
+#[wasm_bindgen_test]
+async fn quality_summary_is_dumped_on_peer_removal() {
+    let (audio_track, video_track) = get_test_tracks(false, false);
+    let (room, peer, event_tx, _commands_rx) = get_test_room_and_exist_peer(
+        vec![audio_track, video_track],
+        Some(media_stream_settings(true, true)),
+    )
+    .await;
+
+    let stats: Vec<proto::stats::RtcStat> =
+        serde_json::from_value(serde_json::json!([{
+            "id": "out",
+            "timestamp": 1.0,
+            "type": "outbound-rtp",
+            "mediaType": "video",
+            "bytesSent": 100,
+            "qualityLimitationDurations": {
+                "none": 3.0,
+                "cpu": 2.0,
+                "bandwidth": 0.0,
+                "other": 0.0
+            }
+        }]))
+        .unwrap();
+    peer.send_peer_stats(platform::RtcStats(stats));
+    drop(peer);
+
+    event_tx
+        .unbounded_send(Event::PeersRemoved {
+            peer_ids: vec![PeerId(1)],
+        })
+        .unwrap();
+    delay_for(100).await;
+
+    let dump = room.new_handle().dump_events(None).unwrap();
+    assert!(
+        dump.contains(
+            "QualitySummary { peer_id: 1, summary: QualitySummary \
+                       { none: 3s, cpu: 2s, bandwidth: 0ns, other: 0ns } }"
+        ),
+        "unexpected events dump: {dump}",
+    );
+}
+
+/// Checks that [`Room`]'s session metrics are accumulated across synthetic
+/// connection loss and recovery cycles, and recorded into its event log.
+#[wasm_bindgen_test]
+async fn session_metrics_accumulate_across_reconnects() {
+    let mut rpc = MockRpcSession::new();
+    let (_event_tx, event_rx) = mpsc::unbounded();
+    let (loss_tx, loss_rx) = mpsc::unbounded();
+    let (reconnected_tx, reconnected_rx) = mpsc::unbounded();
+    rpc.expect_subscribe()
+        .return_once(move || Box::pin(event_rx));
+    rpc.expect_on_connection_loss()
+        .return_once(move || Box::pin(loss_rx));
+    rpc.expect_on_reconnected()
+        .return_once(move || Box::pin(reconnected_rx));
+    rpc.expect_send_command().return_const(());
+    rpc.expect_close_with_reason().return_const(());
+    let room = Room::new(Rc::new(rpc), Rc::default());
+
+    for _ in 0..2 {
+        loss_tx.unbounded_send(()).unwrap();
+        delay_for(10).await;
+        // Failed reconnection attempt.
+        loss_tx.unbounded_send(()).unwrap();
+        delay_for(10).await;
+        reconnected_tx.unbounded_send(()).unwrap();
+        delay_for(10).await;
+    }
+
+    let metrics = room.new_handle().session_metrics().unwrap();
+    assert_eq!(metrics.connection_losses, 2);
+    assert_eq!(metrics.session_restores, 2);
+    assert_eq!(metrics.new_sessions, 0);
+    assert_eq!(metrics.ice_restarts, 0);
+    assert!(metrics.disconnected >= Duration::from_millis(30));
+
+    let dump = room.new_handle().dump_events(None).unwrap();
+    assert!(
+        dump.contains("SessionMetrics { connection_losses: 2, disconnected: "),
+        "unexpected events dump: {dump}",
+    );
+}
@@ -3,6 +3,8 @@
 mod heartbeat;
 mod reconnect_handle;
 mod rpc_session;
+#[cfg(feature = "testing")]
+mod transport_factory;
 mod websocket;
 
 use std::{cell::Cell, collections::HashMap, rc::Rc};
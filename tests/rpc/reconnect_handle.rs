@@ -10,8 +10,9 @@ use medea_client_api_proto::{Event, ServerMsg};
 use medea_jason::{
     platform::{self, MockRpcTransport, RpcTransport, TransportState},
     rpc::{
-        CloseMsg, ConnectionInfo, ReconnectError, ReconnectHandle, RpcSession,
-        WebSocketRpcClient, WebSocketRpcSession,
+        ClientDisconnect, CloseMsg, CloseReason, ConnectionInfo,
+        ReconnectError, ReconnectHandle, ReconnectOutcome, RpcSession,
+        SessionError, WebSocketRpcClient, WebSocketRpcSession,
     },
 };
 use medea_reactive::ObservableCell;
@@ -21,15 +22,14 @@ use crate::{delay_for, rpc::RPC_SETTINGS, timeout, TEST_ROOM_URL};
 
 wasm_bindgen_test_configure!(run_in_browser);
 
-/// Makes sure that [`ReconnectHandle::reconnect_with_backoff()`] works as
-/// expected.
-#[wasm_bindgen_test]
-async fn reconnect_with_backoff() {
-    let transport_state = Rc::new(ObservableCell::new(TransportState::Open));
-
-    let state_clone = Rc::clone(&transport_state);
-    let session = WebSocketRpcSession::new(Rc::new(WebSocketRpcClient::new(
-        Box::new(move || {
+/// Creates a new [`WebSocketRpcSession`] with [`MockRpcTransport`]s following
+/// the provided [`TransportState`].
+fn new_session(
+    transport_state: &Rc<ObservableCell<TransportState>>,
+) -> Rc<WebSocketRpcSession> {
+    let state_clone = Rc::clone(transport_state);
+    WebSocketRpcSession::new(Rc::new(WebSocketRpcClient::new(Box::new(
+        move || {
             let state_clone = Rc::clone(&state_clone);
             let mut transport = MockRpcTransport::new();
             transport
@@ -53,8 +53,19 @@ async fn reconnect_with_backoff() {
                 .return_once_st(move || state_clone.subscribe());
             let transport = Rc::new(transport);
             transport as Rc<dyn RpcTransport>
-        }),
-    )));
+        },
+    ))))
+}
+
+/// Creates a new [`WebSocketRpcSession`] which has lost its connection, along
+/// with the [`TransportState`] its [`MockRpcTransport`]s follow.
+async fn new_lost_session() -> (
+    Rc<ObservableCell<TransportState>>,
+    Rc<WebSocketRpcSession>,
+    ReconnectHandle,
+) {
+    let transport_state = Rc::new(ObservableCell::new(TransportState::Open));
+    let session = new_session(&transport_state);
 
     let connect_fut = Rc::clone(&session)
         .connect(ConnectionInfo::from_str(TEST_ROOM_URL).unwrap());
@@ -68,7 +79,15 @@ async fn reconnect_with_backoff() {
     let handle =
         ReconnectHandle::new(Rc::downgrade(&session) as Weak<dyn RpcSession>);
 
+    (transport_state, session, handle)
+}
+
+/// Makes sure that [`ReconnectHandle::reconnect_with_backoff()`] works as
+/// expected.
+#[wasm_bindgen_test]
+async fn reconnect_with_backoff() {
     // Checks that max_elapsed is not exceeded if starting_delay > max_elapsed.
+    let (_state, _session, handle) = new_lost_session().await;
     let start = instant::Instant::now();
     let err = handle
         .reconnect_with_backoff(1000, 999.0, 50, Some(300))
@@ -80,6 +99,7 @@ async fn reconnect_with_backoff() {
     assert!(matches!(err, ReconnectError::Session(_)));
 
     // Checks that reconnect attempts are made for an expected period.
+    let (_state, _session, handle) = new_lost_session().await;
     let start = instant::Instant::now();
     let err = handle
         .reconnect_with_backoff(10, 1.5, 50, Some(444))
@@ -91,6 +111,7 @@ async fn reconnect_with_backoff() {
     assert!(matches!(err, ReconnectError::Session(_)));
 
     // Checks that reconnect returns Ok immediately after a successful attempt.
+    let (transport_state, session, handle) = new_lost_session().await;
     platform::spawn({
         let transport_state = Rc::clone(&transport_state);
         async move {
@@ -103,7 +124,7 @@ async fn reconnect_with_backoff() {
     let err = handle.reconnect_with_backoff(30, 3.0, 9999, None).await;
     let elapsed = start.elapsed().as_millis();
     assert!(elapsed >= 120 && elapsed < 200); // 30 + 90
-    err.unwrap();
+    assert_eq!(err.unwrap(), ReconnectOutcome::Reconnected);
 
     // Checks that ReconnectError::Detached is fired when session is dropped.
     transport_state.set(TransportState::Closed(CloseMsg::Abnormal(999)));
@@ -126,3 +147,113 @@ async fn reconnect_with_backoff() {
     assert!(elapsed >= 20 && elapsed < 100);
     assert!(matches!(err, ReconnectError::Detached));
 }
+
+/// Makes sure that [`RpcSession`] is closed with
+/// [`ClientDisconnect::ConnectionLost`] once
+/// [`ReconnectHandle::reconnect_with_backoff()`] reaches its max elapsed time.
+#[wasm_bindgen_test]
+async fn gives_up_once_max_elapsed_time_is_reached() {
+    let (_state, session, handle) = new_lost_session().await;
+    let on_close = session.on_normal_close();
+
+    drop(handle.reconnect_with_backoff(10, 1.0, 10, Some(100)).await);
+
+    let reason = timeout(100, on_close).await.unwrap();
+    assert_eq!(
+        reason,
+        CloseReason::ByClient {
+            reason: ClientDisconnect::ConnectionLost,
+            is_err: true,
+        },
+    );
+    assert!(!handle.is_reconnecting());
+}
+
+/// Makes sure that [`ReconnectHandle::cancel()`] closes [`RpcSession`] with
+/// [`ClientDisconnect::ConnectionLost`] and stops the ongoing reconnection.
+#[wasm_bindgen_test]
+async fn cancel_gives_up_reconnecting() {
+    let (_state, session, handle) = new_lost_session().await;
+    let on_close = session.on_normal_close();
+
+    platform::spawn({
+        let handle = handle.clone();
+        async move {
+            delay_for(50).await;
+            handle.cancel();
+        }
+    });
+    let err = timeout(200, handle.reconnect_with_backoff(10, 1.0, 30, None))
+        .await
+        .unwrap()
+        .expect_err("should err since reconnecting is canceled")
+        .into_inner();
+    assert!(matches!(
+        err,
+        ReconnectError::Session(SessionError::SessionFinished(_)),
+    ));
+
+    let reason = timeout(100, on_close).await.unwrap();
+    assert_eq!(
+        reason,
+        CloseReason::ByClient {
+            reason: ClientDisconnect::ConnectionLost,
+            is_err: true,
+        },
+    );
+}
+
+/// Makes sure that the same [`ReconnectHandle`] can be used across repeated
+/// connection losses, and that concurrent reconnections on it share the same
+/// attempts.
+#[wasm_bindgen_test]
+async fn reconnect_handle_is_reusable() {
+    let transport_state = Rc::new(ObservableCell::new(TransportState::Open));
+    let session = new_session(&transport_state);
+
+    let connect_fut = Rc::clone(&session)
+        .connect(ConnectionInfo::from_str(TEST_ROOM_URL).unwrap());
+    timeout(100, connect_fut).await.unwrap().unwrap();
+    let handle =
+        ReconnectHandle::new(Rc::downgrade(&session) as Weak<dyn RpcSession>);
+
+    // Checks that reconnect resolves immediately while being connected.
+    let outcome = timeout(10, handle.reconnect_with_delay(1000))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(outcome, ReconnectOutcome::AlreadyConnected);
+    assert!(!handle.is_reconnecting());
+    assert_eq!(handle.attempt_number(), 0);
+
+    for _ in 0..2 {
+        transport_state.set(TransportState::Closed(CloseMsg::Abnormal(999)));
+        timeout(100, session.on_connection_loss().next())
+            .await
+            .unwrap()
+            .unwrap();
+
+        platform::spawn({
+            let transport_state = Rc::clone(&transport_state);
+            async move {
+                delay_for(40).await;
+                transport_state.set(TransportState::Connecting);
+                transport_state.set(TransportState::Open);
+            }
+        });
+        let (first, second) = future::join(
+            handle.reconnect_with_backoff(30, 1.0, 30, None),
+            async {
+                delay_for(10).await;
+                assert!(handle.is_reconnecting());
+                handle.reconnect_with_delay(0).await
+            },
+        )
+        .await;
+
+        assert_eq!(first.unwrap(), ReconnectOutcome::Reconnected);
+        assert_eq!(second.unwrap(), ReconnectOutcome::Reconnected);
+        assert!(!handle.is_reconnecting());
+        assert!(handle.attempt_number() >= 2);
+    }
+}
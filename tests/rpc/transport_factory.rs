@@ -0,0 +1,96 @@
+#![cfg(target_arch = "wasm32")]
+
+//! Tests for [`medea_jason::testing::InMemoryServer`] injected as an RPC
+//! transport factory.
+
+use std::{rc::Rc, str::FromStr};
+
+use futures::{FutureExt as _, StreamExt as _};
+use medea_client_api_proto::{ClientMsg, Command};
+use medea_jason::{
+    rpc::{
+        ConnectionInfo, RpcSession, WebSocketRpcClient, WebSocketRpcSession,
+    },
+    testing::InMemoryServer,
+};
+use wasm_bindgen_test::*;
+
+use crate::{delay_for, timeout, TEST_ROOM_URL};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// Makes sure that a [`WebSocketRpcSession`] reconnects to an
+/// [`InMemoryServer`] in time after its connection is dropped.
+///
+/// # Algorithm
+///
+/// 1. Connect [`WebSocketRpcSession`] to an [`InMemoryServer`].
+///
+/// 2. Drop the connection on the [`InMemoryServer`] side.
+///
+/// 3. Check that `on_connection_loss` fires.
+///
+/// 4. Reconnect and check that the `Room` is joined again via a new connection.
+#[wasm_bindgen_test]
+async fn reconnects_in_time_after_connection_drop() {
+    let server = InMemoryServer::default();
+    let session = WebSocketRpcSession::new(Rc::new(WebSocketRpcClient::new(
+        Box::new(server.transport_factory()),
+    )));
+
+    let mut on_normal_close = session.on_normal_close().fuse();
+    let mut on_reconnected = session.on_reconnected().fuse();
+    let mut on_connection_loss = session.on_connection_loss().fuse();
+
+    let connect_fut = Rc::clone(&session)
+        .connect(ConnectionInfo::from_str(TEST_ROOM_URL).unwrap());
+    timeout(100, connect_fut).await.unwrap().unwrap();
+    assert_eq!(server.connections_count(), 1);
+
+    server.drop_connection();
+    futures::select! {
+        _ = delay_for(100).fuse() => panic!("on_connection_loss should fire"),
+        _ = on_normal_close => panic!("on_normal_close fired"),
+        _ = on_connection_loss.next() => (),
+        _ = on_reconnected.next() => panic!("on_reconnected fired")
+    };
+
+    timeout(100, Rc::clone(&session).reconnect())
+        .await
+        .unwrap()
+        .unwrap();
+    on_reconnected.select_next_some().await;
+    assert_eq!(server.connections_count(), 2);
+
+    let joins = server
+        .received()
+        .into_iter()
+        .filter(|msg| {
+            matches!(
+                msg,
+                ClientMsg::Command {
+                    command: Command::JoinRoom { .. },
+                    ..
+                },
+            )
+        })
+        .count();
+    assert_eq!(joins, 2);
+}
+
+/// Makes sure that a [`WebSocketRpcSession`] fails to connect to an
+/// unavailable [`InMemoryServer`].
+#[wasm_bindgen_test]
+async fn fails_to_connect_to_unavailable_server() {
+    let server = InMemoryServer::default();
+    server.set_available(false);
+    let session = WebSocketRpcSession::new(Rc::new(WebSocketRpcClient::new(
+        Box::new(server.transport_factory()),
+    )));
+
+    let connect_fut = Rc::clone(&session)
+        .connect(ConnectionInfo::from_str(TEST_ROOM_URL).unwrap());
+    assert!(timeout(100, connect_fut).await.unwrap().is_err());
+    assert_eq!(server.connections_count(), 0);
+    assert!(server.received().is_empty());
+}
@@ -116,12 +116,18 @@ extern "C" {
     #[wasm_bindgen(method, setter = errorGetUserMedia)]
     fn error_get_user_media(this: &MockNavigator, err: JsValue);
 
+    #[wasm_bindgen(method, js_name = failGetUserMedia)]
+    fn fail_get_user_media(this: &MockNavigator, err: JsValue, times: i32);
+
     #[wasm_bindgen(method, setter = errorGetDisplayMedia)]
     fn error_get_display_media(this: &MockNavigator, err: JsValue);
 
     #[wasm_bindgen(method, setter = errorEnumerateDevices)]
     fn error_enumerate_devices(this: &MockNavigator, err: JsValue);
 
+    #[wasm_bindgen(method, setter = setEnumerateDevicesReturns)]
+    fn set_enumerate_devices_returns(this: &MockNavigator, devices: JsValue);
+
     #[wasm_bindgen(method, getter = getUserMediaRequestsCount)]
     fn get_user_media_requests_count(this: &MockNavigator) -> i32;
 
@@ -137,6 +143,12 @@ extern "C" {
         stream: web_sys::MediaStream,
     );
 
+    #[wasm_bindgen(method, setter = getUserMediaLatency)]
+    fn set_get_user_media_latency(this: &MockNavigator, ms: i32);
+
+    #[wasm_bindgen(method, getter = capturedTracks)]
+    fn captured_tracks(this: &MockNavigator) -> js_sys::Array;
+
     #[wasm_bindgen(method)]
     fn stop(this: &MockNavigator);
 }